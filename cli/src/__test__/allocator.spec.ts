@@ -0,0 +1,59 @@
+import test from 'ava'
+
+import { allocatorFeature } from '../allocator'
+import { CargoDependency, CargoPackage } from '../cargo-metadata'
+import { parseTriple } from '../parse-triple'
+
+const linux = parseTriple('x86_64-unknown-linux-gnu')
+const windows = parseTriple('x86_64-pc-windows-msvc')
+
+const cargoPackage = (dependencies: CargoDependency[]): CargoPackage => ({
+  id: 'addon 0.1.0 (path+file:///addon)',
+  name: 'addon',
+  manifest_path: '/addon/Cargo.toml',
+  targets: [{ name: 'addon', kind: ['cdylib'], crate_types: ['cdylib'] }],
+  dependencies,
+})
+
+const napi: CargoDependency = { name: 'napi', rename: null, kind: null }
+
+test('should enable the allocator feature of napi', (t) => {
+  t.is(
+    allocatorFeature(cargoPackage([napi]), 'mimalloc', linux),
+    'napi/allocator_mimalloc',
+  )
+  t.is(
+    allocatorFeature(cargoPackage([napi]), 'jemalloc', linux),
+    'napi/allocator_jemalloc',
+  )
+  t.is(allocatorFeature(cargoPackage([napi]), 'system', linux), null)
+})
+
+test('should address the renamed napi', (t) => {
+  t.is(
+    allocatorFeature(
+      cargoPackage([{ name: 'napi', rename: 'node-api', kind: null }]),
+      'mimalloc',
+      windows,
+    ),
+    'node-api/allocator_mimalloc',
+  )
+})
+
+test('should reject the unsupported allocators', (t) => {
+  t.throws(() => allocatorFeature(cargoPackage([napi]), 'snmalloc', linux), {
+    message: /Unsupported allocator/,
+  })
+  t.throws(() => allocatorFeature(cargoPackage([napi]), 'jemalloc', windows), {
+    message: /jemalloc doesn't support/,
+  })
+  t.throws(
+    () =>
+      allocatorFeature(
+        cargoPackage([{ name: 'napi', rename: null, kind: 'dev' }]),
+        'mimalloc',
+        linux,
+      ),
+    { message: /requires/ },
+  )
+})
@@ -0,0 +1,72 @@
+import { mkdirSync, mkdtempSync, writeFileSync } from 'fs'
+import { tmpdir } from 'os'
+import { join } from 'path'
+
+import test from 'ava'
+
+import { findAndroidNdk, getAndroidToolchainEnv } from '../android-ndk'
+
+const HOST_TAG =
+  process.platform === 'darwin'
+    ? 'darwin-x86_64'
+    : process.platform === 'win32'
+    ? 'windows-x86_64'
+    : 'linux-x86_64'
+
+const WRAPPER_EXT = process.platform === 'win32' ? '.cmd' : ''
+
+function createFakeNdk(...clangWrappers: string[]) {
+  const sdk = mkdtempSync(join(tmpdir(), 'napi-rs-android-sdk-'))
+  const bin = join(
+    sdk,
+    'ndk',
+    '25.1.8937393',
+    'toolchains',
+    'llvm',
+    'prebuilt',
+    HOST_TAG,
+    'bin',
+  )
+  mkdirSync(bin, { recursive: true })
+  mkdirSync(join(sdk, 'ndk', '23.2.8568313'))
+  for (const wrapper of clangWrappers) {
+    writeFileSync(join(bin, `${wrapper}${WRAPPER_EXT}`), '')
+  }
+  return { sdk, bin }
+}
+
+test('should find the newest ndk in android sdk', (t) => {
+  const { sdk } = createFakeNdk()
+  t.is(findAndroidNdk({ ANDROID_HOME: sdk }), join(sdk, 'ndk', '25.1.8937393'))
+})
+
+test('should prefer ANDROID_NDK_HOME', (t) => {
+  const { sdk } = createFakeNdk()
+  const ndk = join(sdk, 'ndk', '23.2.8568313')
+  t.is(findAndroidNdk({ ANDROID_HOME: sdk, ANDROID_NDK_HOME: ndk }), ndk)
+})
+
+test('should pick clang wrapper for the api level', (t) => {
+  const { sdk, bin } = createFakeNdk('armv7a-linux-androideabi29-clang')
+  const ndk = join(sdk, 'ndk', '25.1.8937393')
+  const env = getAndroidToolchainEnv('armv7-linux-androideabi', ndk, 29, {})
+  t.is(
+    env.CARGO_TARGET_ARMV7_LINUX_ANDROIDEABI_LINKER,
+    join(bin, `armv7a-linux-androideabi29-clang${WRAPPER_EXT}`),
+  )
+  t.is(
+    env.CC_armv7_linux_androideabi,
+    env.CARGO_TARGET_ARMV7_LINUX_ANDROIDEABI_LINKER,
+  )
+  t.throws(() => getAndroidToolchainEnv('armv7-linux-androideabi', ndk, 21))
+})
+
+test('should not override env set by user', (t) => {
+  const { sdk } = createFakeNdk('aarch64-linux-android24-clang')
+  const ndk = join(sdk, 'ndk', '25.1.8937393')
+  const env = getAndroidToolchainEnv('aarch64-linux-android', ndk, 24, {
+    CC_aarch64_linux_android: 'clang',
+  })
+  t.false('CC_aarch64_linux_android' in env)
+  t.truthy(env.CARGO_TARGET_AARCH64_LINUX_ANDROID_LINKER)
+})
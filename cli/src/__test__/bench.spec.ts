@@ -0,0 +1,55 @@
+import test from 'ava'
+
+import { collectBenchCases, generateBenchHarness } from '../bench'
+import type { TypeDef } from '../build'
+
+const defs: TypeDef[] = [
+  {
+    kind: 'fn',
+    name: 'add',
+    def: 'export function add(a: number, b: number): number',
+    js_doc: '',
+    benches: [{ name: '1, 2', args: '1, 2' }],
+  },
+  {
+    kind: 'fn',
+    name: 'parse',
+    js_mod: 'json',
+    def: 'export function parse(input: string): object',
+    js_doc: '',
+    benches: [
+      { name: 'small', args: "'{}'" },
+      { name: 'large', args: "JSON.stringify({ a: 'a'.repeat(1000) })" },
+    ],
+  },
+  {
+    kind: 'fn',
+    name: 'noop',
+    def: 'export function noop(): void',
+    js_doc: '',
+  },
+]
+
+test('should collect the bench cases of the fns', (t) => {
+  t.deepEqual(collectBenchCases(defs), [
+    { path: 'add', name: '1, 2', args: '1, 2' },
+    { path: 'json.parse', name: 'small', args: "'{}'" },
+    {
+      path: 'json.parse',
+      name: 'large',
+      args: "JSON.stringify({ a: 'a'.repeat(1000) })",
+    },
+  ])
+})
+
+test('should call the fns with the args in the harness', (t) => {
+  const harness = generateBenchHarness(defs, './index.js')
+  t.true(harness.includes("const binding = require('./index.js')"))
+  t.true(harness.includes('["add", "1, 2", () => binding.add(1, 2)],'))
+  t.true(
+    harness.includes(
+      `["json.parse", "small", () => binding.json.parse('{}')],`,
+    ),
+  )
+  t.false(harness.includes('noop'))
+})
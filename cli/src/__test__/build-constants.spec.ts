@@ -0,0 +1,52 @@
+import test from 'ava'
+
+import {
+  buildConstantsEnv,
+  createBuildConstantsBinding,
+  createBuildConstantsDts,
+  resolveBuildConstants,
+} from '../build-constants'
+
+test('should override the constants by the target', (t) => {
+  t.deepEqual(
+    resolveBuildConstants(
+      { API_URL: 'https://api.example.com', TELEMETRY: true },
+      { TELEMETRY: false },
+    ),
+    { API_URL: 'https://api.example.com', TELEMETRY: false },
+  )
+  t.deepEqual(resolveBuildConstants(undefined, undefined), {})
+})
+
+test('should throw on the invalid constants', (t) => {
+  t.throws(() => resolveBuildConstants({ apiUrl: 'x' }), {
+    message: /Invalid build constant name/,
+  })
+  t.throws(() => resolveBuildConstants({ LIMITS: [1, 2] as any }), {
+    message: /must be a string, number or boolean/,
+  })
+})
+
+test('should pass the constants to cargo by env', (t) => {
+  t.deepEqual(buildConstantsEnv({ RETRIES: 3, TELEMETRY: false }), {
+    NAPI_RS_CONSTANT_RETRIES: '3',
+    NAPI_RS_CONSTANT_TELEMETRY: 'false',
+  })
+})
+
+test('should generate the frozen constants and the types of them', (t) => {
+  const constants = { API_URL: 'https://api.example.com', RETRIES: 3 }
+  const binding = createBuildConstantsBinding(constants)
+  t.deepEqual(new Function(`${binding}\nreturn BUILD_CONSTANTS`)(), constants)
+  t.true(
+    new Function(`${binding}\nreturn Object.isFrozen(BUILD_CONSTANTS)`)(),
+  )
+  t.is(
+    createBuildConstantsDts(constants),
+    `export const BUILD_CONSTANTS: {
+  readonly API_URL: string
+  readonly RETRIES: number
+}
+`,
+  )
+})
@@ -0,0 +1,85 @@
+import { join, resolve } from 'path'
+
+import test from 'ava'
+
+import {
+  CargoMetadata,
+  CargoPackage,
+  findCargoPackage,
+  findCdylibTarget,
+  resolveTargetDir,
+} from '../cargo-metadata'
+
+const root = join(__dirname, 'workspace')
+
+const cargoPackage = (
+  name: string,
+  kind: string[],
+  libName = name.replace(/-/g, '_'),
+): CargoPackage => ({
+  id: `${name} 0.1.0 (path+file://${join(root, name)})`,
+  name,
+  manifest_path: join(root, name, 'Cargo.toml'),
+  targets: [{ name: libName, kind, crate_types: kind }],
+})
+
+const metadata = (packages: CargoPackage[]): CargoMetadata => ({
+  packages,
+  workspace_members: packages.map(({ id }) => id),
+  workspace_root: root,
+  target_directory: join(root, 'target'),
+})
+
+test('should find the package of cwd', (t) => {
+  const workspace = metadata([
+    cargoPackage('addon', ['cdylib']),
+    cargoPackage('utils', ['lib']),
+  ])
+  t.is(findCargoPackage(workspace, join(root, 'utils')).name, 'utils')
+  t.is(findCargoPackage(workspace, join(root, 'addon')).name, 'addon')
+})
+
+test('should find the package by -p', (t) => {
+  const workspace = metadata([
+    cargoPackage('addon', ['cdylib']),
+    cargoPackage('another-addon', ['cdylib']),
+  ])
+  t.is(findCargoPackage(workspace, root, 'another-addon').name, 'another-addon')
+  t.throws(() => findCargoPackage(workspace, root, 'unknown'), {
+    message: /is not in the workspace/,
+  })
+  t.throws(() => findCargoPackage(workspace, root), {
+    message: /virtual manifest/,
+  })
+})
+
+test('should find the only cdylib package in the virtual workspace', (t) => {
+  const workspace = metadata([
+    cargoPackage('addon', ['cdylib'], 'addon_binding'),
+    cargoPackage('utils', ['lib']),
+  ])
+  const addon = findCargoPackage(workspace, root)
+  t.is(addon.name, 'addon')
+  t.is(findCdylibTarget(addon)?.name, 'addon_binding')
+  t.is(findCdylibTarget(workspace.packages[1]), null)
+})
+
+test('should resolve the target dir', (t) => {
+  const workspace = metadata([cargoPackage('addon', ['cdylib'])])
+  const cwd = join(root, 'addon')
+  t.is(resolveTargetDir(workspace, cwd), join(root, 'target'))
+  t.is(resolveTargetDir(workspace, cwd, 'dist/target'), resolve('dist/target'))
+  t.is(
+    resolveTargetDir(workspace, cwd, undefined, '--locked --target-dir ../out'),
+    join(root, 'out'),
+  )
+  t.is(
+    resolveTargetDir(
+      workspace,
+      cwd,
+      undefined,
+      '--target-dir="/tmp/my target"',
+    ),
+    resolve('/tmp/my target'),
+  )
+})
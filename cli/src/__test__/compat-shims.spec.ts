@@ -0,0 +1,73 @@
+import { mkdirSync, mkdtempSync, writeFileSync } from 'fs'
+import { tmpdir } from 'os'
+import { join } from 'path'
+
+import test from 'ava'
+
+import { createCompatShims } from '../compat-shims'
+
+function createPackage() {
+  const dir = mkdtempSync(join(tmpdir(), 'napi-rs-compat-shims-'))
+  mkdirSync(join(dir, 'lib'))
+  writeFileSync(join(dir, 'lib', 'index.js'), 'module.exports.sum = 1')
+  writeFileSync(
+    join(dir, 'lib', 'index.d.ts'),
+    'export declare function sum(): number',
+  )
+  return dir
+}
+
+test('should re-export the new file from the old path', (t) => {
+  const dir = createPackage()
+  const [shim] = createCompatShims(
+    { 'binding.js': 'lib/index.js' },
+    dir,
+    '@napi-rs/cli',
+  )
+  t.is(shim.path, join(dir, 'binding.js'))
+  t.true(shim.code.includes("module.exports = require('./lib/index.js')"))
+  t.true(
+    shim.code.includes(
+      "'@napi-rs/cli/binding.js is deprecated, require @napi-rs/cli/lib/index.js instead'",
+    ),
+  )
+  t.true(shim.code.includes("code: 'NAPI_RS_COMPAT_SHIM'"))
+  t.deepEqual(shim.dts, {
+    path: join(dir, 'binding.d.ts'),
+    code: `// @napi-rs/cli/binding.js is deprecated, require @napi-rs/cli/lib/index.js instead
+export * from './lib/index'
+`,
+  })
+})
+
+test('should require the new file relative to the shim in a sub-dir', (t) => {
+  const dir = createPackage()
+  const [shim] = createCompatShims(
+    { 'dist/binding.cjs': 'lib/index.js' },
+    dir,
+    'pkg',
+  )
+  t.true(shim.code.includes("module.exports = require('../lib/index.js')"))
+  t.is(shim.dts!.path, join(dir, 'dist', 'binding.d.cts'))
+})
+
+test('should skip the dts shim if the new file has no dts', (t) => {
+  const dir = createPackage()
+  writeFileSync(join(dir, 'plugin.js'), '')
+  t.is(createCompatShims({ 'old.js': 'plugin.js' }, dir, 'pkg')[0].dts, null)
+})
+
+test('should throw if the shim is not a JS file in the package', (t) => {
+  const dir = createPackage()
+  const create = (shims: Record<string, string>) => () =>
+    createCompatShims(shims, dir, 'pkg')
+  t.throws(create({ 'index.node': 'lib/index.js' }), {
+    message: /must be the .js or .cjs files in the package/,
+  })
+  t.throws(create({ '../index.js': 'lib/index.js' }), {
+    message: /must be the .js or .cjs files in the package/,
+  })
+  t.throws(create({ 'index.js': 'lib/missing.js' }), {
+    message: /is not existed/,
+  })
+})
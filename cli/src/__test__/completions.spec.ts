@@ -0,0 +1,94 @@
+import test from 'ava'
+
+import {
+  CommandDefinition,
+  generateCompletions,
+  toCompletionCommands,
+} from '../completions'
+import { generateManPage } from '../man'
+
+const definitions: CommandDefinition[] = [
+  {
+    path: 'napi dev <entry> ...',
+    description: 'Rebuild on change',
+    options: [{ definition: '--watch #0', description: 'Files or dirs' }],
+  },
+  {
+    path: 'napi build [--release] [--target #0]',
+    description: 'Build and copy native module into specified dir',
+    options: [
+      {
+        definition: '--release',
+        description: 'Bypass to \u001b[32mcargo build --release\u001b[39m',
+      },
+      {
+        definition: '--config,-c #0',
+        description: "napi config path. It's JSON",
+      },
+      { definition: '--target #0' },
+    ],
+  },
+]
+
+test('should read the commands from the definitions', (t) => {
+  t.deepEqual(toCompletionCommands(definitions), [
+    {
+      name: 'build',
+      description: 'Build and copy native module into specified dir',
+      options: [
+        {
+          names: ['--release'],
+          takesValue: false,
+          description: 'Bypass to cargo build --release',
+        },
+        {
+          names: ['--config', '-c'],
+          takesValue: true,
+          description: 'napi config path',
+        },
+        { names: ['--target'], takesValue: true, description: '' },
+      ],
+    },
+    {
+      name: 'dev',
+      description: 'Rebuild on change',
+      options: [
+        { names: ['--watch'], takesValue: true, description: 'Files or dirs' },
+      ],
+    },
+  ])
+})
+
+test('should complete the target triples', (t) => {
+  const commands = toCompletionCommands(definitions)
+  const bash = generateCompletions('bash', commands)
+  t.true(bash.includes('compgen -W "build dev"'))
+  t.true(
+    bash.includes(
+      '--target)\n          COMPREPLY=($(compgen -W "$(_napi_targets)"',
+    ),
+  )
+  t.true(bash.includes('--config|-c)\n          COMPREPLY=($(compgen -f'))
+  t.true(
+    generateCompletions('zsh', commands).includes(
+      `'--target[]:target:_napi_targets'`,
+    ),
+  )
+  t.true(
+    generateCompletions('fish', commands).includes(
+      `complete -c napi -n '__fish_seen_subcommand_from build' -l target -x -a '(__napi_targets)'`,
+    ),
+  )
+  t.true(
+    generateCompletions('powershell', commands).includes(
+      `'build' = @('--release', '--config', '-c', '--target')`,
+    ),
+  )
+})
+
+test('should generate the man page', (t) => {
+  const manPage = generateManPage(definitions, '2.6.2')
+  t.true(manPage.startsWith('.TH NAPI 1 "" "napi 2.6.2" "napi-rs"\n'))
+  t.true(manPage.includes('.B \\-\\-config, \\-c \\fIvalue\\fR\n'))
+  t.true(manPage.includes('Bypass to cargo build \\-\\-release\n'))
+})
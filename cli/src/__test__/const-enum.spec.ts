@@ -0,0 +1,54 @@
+import { mkdtempSync, readFileSync, writeFileSync } from 'fs'
+import { tmpdir } from 'os'
+import { join } from 'path'
+
+import test from 'ava'
+import { Cli } from 'clipanion'
+
+import { BuildCommand, processIntermediateTypeFile } from '../build'
+
+function parseBuild(args: string[]) {
+  const cli = new Cli()
+  cli.register(BuildCommand)
+  return cli.process(['build', ...args]) as BuildCommand
+}
+
+async function generateDts(constEnum: boolean) {
+  const dir = mkdtempSync(join(tmpdir(), 'napi-rs-const-enum-'))
+  const source = join(dir, 'type_def.tmp')
+  const target = join(dir, 'index.d.ts')
+  writeFileSync(
+    source,
+    JSON.stringify({
+      kind: 'enum',
+      name: 'Kind',
+      js_doc: '',
+      def: 'Dog = 0,\nCat = 1',
+    }) + '\n',
+  )
+  await processIntermediateTypeFile(
+    source,
+    target,
+    null,
+    null,
+    null,
+    null,
+    true,
+    constEnum,
+    { binaryName: 'index', packageName: 'index' },
+  )
+  return readFileSync(target, 'utf8')
+}
+
+test('should declare the enums as export enum by default', async (t) => {
+  t.false(parseBuild([]).constEnum)
+  const dts = await generateDts(false)
+  t.true(dts.includes('export enum Kind {\n  Dog = 0,\n  Cat = 1\n}'))
+  t.false(dts.includes('const enum'))
+})
+
+test('should declare the enums as export const enum by --const-enum', async (t) => {
+  t.true(parseBuild(['--const-enum']).constEnum)
+  const dts = await generateDts(true)
+  t.true(dts.includes('export const enum Kind {\n  Dog = 0,\n  Cat = 1\n}'))
+})
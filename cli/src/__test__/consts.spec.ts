@@ -0,0 +1,87 @@
+import { mkdtempSync, writeFileSync } from 'fs'
+import { tmpdir } from 'os'
+import { join } from 'path'
+
+import test from 'ava'
+
+import { getNapiConfig, parseNapiConfigFile } from '../consts'
+
+const createPackage = (files: Record<string, string>) => {
+  const dir = mkdtempSync(join(tmpdir(), 'napi-rs-config-'))
+  for (const [name, content] of Object.entries(files)) {
+    writeFileSync(join(dir, name), content)
+  }
+  return dir
+}
+
+test('should merge napi.toml over the napi field of package.json', (t) => {
+  const dir = createPackage({
+    'package.json': JSON.stringify({
+      name: 'cli',
+      version: '1.0.0',
+      napi: { name: 'pkg', useCache: true },
+    }),
+    'napi.toml': `name = "cli"
+preBuild = "node scripts/prepare.js"
+
+[triples]
+defaults = false
+additional = ["x86_64-unknown-linux-musl"]
+
+[target.x86_64-unknown-linux-musl]
+cargoFlags = ["--no-default-features"]
+env = { CC = "musl-gcc" }
+`,
+  })
+  const config = getNapiConfig('package.json', dir)
+  t.is(config.napiConfigPath, join(dir, 'napi.toml'))
+  t.is(config.binaryName, 'cli')
+  t.true(config.useCache)
+  t.deepEqual(config.preBuild, ['node scripts/prepare.js'])
+  t.deepEqual(
+    config.platforms.map(({ raw }) => raw),
+    ['x86_64-unknown-linux-musl'],
+  )
+  t.deepEqual(config.targetConfigs, {
+    'x86_64-unknown-linux-musl': {
+      cargoFlags: ['--no-default-features'],
+      env: { CC: 'musl-gcc' },
+    },
+  })
+})
+
+test('should read .naprc without package.json', (t) => {
+  const dir = createPackage({
+    '.naprc': JSON.stringify({
+      name: 'cli',
+      package: { name: '@napi-rs/cli' },
+    }),
+  })
+  const config = getNapiConfig('.naprc', dir)
+  t.is(config.packageName, '@napi-rs/cli')
+  t.is(config.packageJsonPath, join(dir, 'package.json'))
+  t.deepEqual(config.napiConfig, {
+    name: 'cli',
+    package: { name: '@napi-rs/cli' },
+  })
+})
+
+test('should report the location of the invalid napi.toml', (t) => {
+  t.throws(() => parseNapiConfigFile('napi.toml', 'name = \n'), {
+    message: /^Failed to parse \[.*napi\.toml:1:\d+.*\]/,
+  })
+})
+
+test('should list napi.name first in the binaries', (t) => {
+  const dir = createPackage({
+    'package.json': JSON.stringify({
+      name: 'cli',
+      version: '1.0.0',
+      napi: { name: 'core', binaries: ['plugin', 'core'] },
+    }),
+  })
+  t.deepEqual(getNapiConfig('package.json', dir).binaryNames, [
+    'core',
+    'plugin',
+  ])
+})
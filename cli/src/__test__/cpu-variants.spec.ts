@@ -0,0 +1,54 @@
+import test from 'ava'
+
+import {
+  cpuVariantFileName,
+  cpuVariantsOfTarget,
+  resolveCpuVariant,
+} from '../cpu-variants'
+
+const variants = {
+  avx2: { targets: 'x86_64-*', cpuFeatures: ['avx2', 'fma'] },
+  dotprod: { targets: ['aarch64-*', '!*-android'], cpuFeatures: ['dotprod'] },
+}
+
+test('should enable the CPU features of the variant', (t) => {
+  t.is(
+    resolveCpuVariant(variants, 'avx2', 'x86_64-unknown-linux-gnu'),
+    '-C target-feature=+avx2,+fma',
+  )
+  t.is(
+    resolveCpuVariant(variants, 'dotprod', 'aarch64-apple-darwin'),
+    '-C target-feature=+dotprod',
+  )
+})
+
+test('should throw if the variant is not built for the target', (t) => {
+  t.throws(
+    () => resolveCpuVariant(variants, 'avx2', 'aarch64-apple-darwin'),
+    { message: /is not built for/ },
+  )
+  t.throws(
+    () => resolveCpuVariant(variants, 'avx512', 'x86_64-apple-darwin'),
+    { message: /is not in/ },
+  )
+  t.throws(
+    () =>
+      resolveCpuVariant(
+        { avx2: { targets: 'x86_64-*', cpuFeatures: [] } },
+        'avx2',
+        'x86_64-apple-darwin',
+      ),
+    { message: /must be a lowercase name/ },
+  )
+})
+
+test('should list the variants of the target', (t) => {
+  t.deepEqual(cpuVariantsOfTarget(variants, 'aarch64-unknown-linux-gnu'), [
+    'dotprod',
+  ])
+  t.deepEqual(cpuVariantsOfTarget(variants, 'aarch64-linux-android'), [])
+  t.is(
+    cpuVariantFileName('index.linux-x64-gnu.node', 'avx2'),
+    'index.linux-x64-gnu.avx2.node',
+  )
+})
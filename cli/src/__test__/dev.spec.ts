@@ -0,0 +1,36 @@
+import test from 'ava'
+
+import { getSwapProblem, isNapiClass } from '../dev'
+
+class Animal {
+  name() {
+    return 'animal'
+  }
+}
+
+test('should tell the classes from the functions', (t) => {
+  t.true(isNapiClass(Animal))
+  t.true(isNapiClass(function Point() {}))
+  t.false(isNapiClass(function sum() {}))
+  t.false(isNapiClass(() => {}))
+  t.false(isNapiClass({ Animal }))
+})
+
+test('should swap the functions only if the exports are unchanged', (t) => {
+  const sum = () => 1
+  t.is(getSwapProblem({ sum, KIND: { A: 0 } }, { sum, KIND: { A: 0 } }), null)
+  t.is(
+    getSwapProblem({ sum }, { sum, sub: sum }),
+    'the exports are changed, +sub',
+  )
+  t.is(
+    getSwapProblem({ sum, sub: sum }, { sum }),
+    'the exports are changed, -sub',
+  )
+  t.is(getSwapProblem({ Animal }, { Animal }), 'Animal is a class')
+  t.is(getSwapProblem({ sum }, { sum: 1 }), 'the type of sum is changed')
+  t.is(
+    getSwapProblem({ KIND: { A: 0 } }, { KIND: { A: 1 } }),
+    'the value of KIND is changed',
+  )
+})
@@ -0,0 +1,59 @@
+import test from 'ava'
+
+import { findTypeDefConflicts, toNapiMessage } from '../diagnostics'
+
+test('should find the names declared more than once in the d.ts', (t) => {
+  t.deepEqual(
+    findTypeDefConflicts([
+      { kind: 'struct', name: 'Animal' },
+      { kind: 'impl', name: 'Animal' },
+      { kind: 'fn', name: 'Animal' },
+      { kind: 'fn', name: 'sum' },
+      { kind: 'fn', name: 'sum', js_mod: 'math' },
+    ]),
+    [
+      {
+        code: 'napi::dts_conflict',
+        message:
+          '`Animal` is declared 2 times in the d.ts, as struct, fn, rename them with `js_name`',
+        location: null,
+      },
+    ],
+  )
+})
+
+test('should convert the warning to the cargo message shape', (t) => {
+  const location = {
+    file_name: 'src/lib.rs',
+    line_start: 5,
+    column_start: 20,
+    line_end: 5,
+    column_end: 28,
+  }
+  t.deepEqual(
+    toNapiMessage({
+      code: 'napi::unsupported_type',
+      message: '`[u32 ; 3]` is not supported by the type generation',
+      location,
+    }),
+    {
+      reason: 'napi-message',
+      message: {
+        message: '`[u32 ; 3]` is not supported by the type generation',
+        code: { code: 'napi::unsupported_type' },
+        level: 'warning',
+        spans: [{ ...location, is_primary: true }],
+        rendered:
+          'warning[napi::unsupported_type]: `[u32 ; 3]` is not supported by the type generation\n  --> src/lib.rs:5:20\n',
+      },
+    },
+  )
+  t.deepEqual(
+    toNapiMessage({
+      code: 'napi::dts_conflict',
+      message: '`sum` is declared 2 times in the d.ts',
+      location: null,
+    }).message.spans,
+    [],
+  )
+})
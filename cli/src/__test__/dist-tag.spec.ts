@@ -0,0 +1,35 @@
+import test from 'ava'
+
+import { DEFAULT_DIST_TAGS, getGitRef, resolveDistTag } from '../dist-tag'
+
+test('should get the git ref from GitHub Actions env', (t) => {
+  t.deepEqual(getGitRef({ GITHUB_REF: 'refs/heads/release/1.x' }), {
+    branch: 'release/1.x',
+    pullRequest: null,
+  })
+  t.deepEqual(
+    getGitRef({ GITHUB_REF: 'refs/pull/42/merge', GITHUB_HEAD_REF: 'feat' }),
+    { branch: 'feat', pullRequest: 42 },
+  )
+  t.deepEqual(getGitRef({ GITHUB_REF: 'refs/tags/v1.0.0' }), {
+    branch: null,
+    pullRequest: null,
+  })
+})
+
+test('should resolve the dist-tag of the branch', (t) => {
+  const ref = (branch: string | null, pullRequest: number | null = null) => ({
+    branch,
+    pullRequest,
+  })
+  t.is(resolveDistTag(DEFAULT_DIST_TAGS, ref('main'), '1.0.0'), 'latest')
+  t.is(resolveDistTag(DEFAULT_DIST_TAGS, ref('next'), '1.1.0-beta.0'), 'next')
+  t.is(resolveDistTag(DEFAULT_DIST_TAGS, ref('feat', 42), '1.0.0'), 'pr-42')
+  t.is(resolveDistTag(DEFAULT_DIST_TAGS, ref('feat'), '1.0.0'), null)
+  t.is(resolveDistTag(DEFAULT_DIST_TAGS, ref(null), '1.0.0'), null)
+  // prereleases are never published as latest
+  t.is(resolveDistTag(DEFAULT_DIST_TAGS, ref('main'), '1.0.0-rc.1'), 'next')
+  const config = { 'release/*': '{branch}', '**': 'canary' }
+  t.is(resolveDistTag(config, ref('release/1.x'), '1.0.0'), 'release-1.x')
+  t.is(resolveDistTag(config, ref('release/1.x/hotfix'), '1.0.0'), 'canary')
+})
@@ -0,0 +1,85 @@
+import test from 'ava'
+
+import {
+  DryRunPackage,
+  formatDryRunSummary,
+  getDryRunProblems,
+} from '../dry-run'
+
+const pkg = (
+  name: string,
+  file: string,
+  files: string[] | null,
+  version = '1.0.0',
+): DryRunPackage => ({
+  name,
+  version: '1.0.0',
+  file: `npm/${name}/${file}`,
+  exists: files !== null,
+  pack: files && {
+    name,
+    version,
+    size: 1024,
+    unpackedSize: 4096,
+    files: files.map((path) => ({ path, size: 1024 })),
+  },
+})
+
+test('should find the problems of the packages', (t) => {
+  t.deepEqual(
+    getDryRunProblems(
+      pkg('cli-linux-x64-gnu', 'cli.linux-x64-gnu.node', [
+        'cli.linux-x64-gnu.node',
+        'package.json',
+      ]),
+    ),
+    [],
+  )
+  t.deepEqual(
+    getDryRunProblems(
+      pkg('cli-win32-x64-msvc', 'cli.win32-x64-msvc.node', null),
+    ),
+    ['cli.win32-x64-msvc.node is not existed, skipped'],
+  )
+  t.deepEqual(
+    getDryRunProblems(
+      pkg(
+        'cli-darwin-arm64',
+        'cli.darwin-arm64.node',
+        ['package.json'],
+        '0.9.0',
+      ),
+    ),
+    [
+      'version of package.json is 0.9.0, expected 1.0.0',
+      'cli.darwin-arm64.node is not included by the files of package.json',
+    ],
+  )
+})
+
+test('should print the summary of the packages', (t) => {
+  const summary = formatDryRunSummary(
+    [
+      pkg('cli-linux-x64-gnu', 'cli.linux-x64-gnu.node', [
+        'cli.linux-x64-gnu.node',
+        'package.json',
+      ]),
+      pkg('cli-win32-x64-msvc', 'cli.win32-x64-msvc.node', null),
+    ],
+    'npm publish --tag next',
+    'v1.0.0',
+  )
+  t.true(summary.includes('would publish 1 of 2 packages'))
+  t.true(
+    summary.includes(
+      'cli-linux-x64-gnu@1.0.0  tarball 1.0KB, unpacked 4.0KB, 2 files',
+    ),
+  )
+  t.true(summary.includes('cli.win32-x64-msvc.node is not existed, skipped'))
+  t.true(summary.includes('GitHub release v1.0.0'))
+  t.true(
+    formatDryRunSummary([], 'npm publish', null).includes(
+      'No GitHub release would be created',
+    ),
+  )
+})
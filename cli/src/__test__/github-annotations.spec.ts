@@ -0,0 +1,45 @@
+import { join } from 'path'
+
+import test from 'ava'
+
+import { CargoDiagnostic, toAnnotation } from '../github-annotations'
+
+const span = {
+  file_name: 'src/lib.rs',
+  line_start: 3,
+  line_end: 3,
+  column_start: 7,
+  column_end: 8,
+  is_primary: true,
+}
+
+test('should convert warning to annotation', (t) => {
+  const diagnostic: CargoDiagnostic = {
+    message: 'unused variable: `a`',
+    code: { code: 'unused_variables' },
+    level: 'warning',
+    spans: [span],
+    rendered: null,
+  }
+  const workspace = join('/', 'home', 'runner', 'work', 'addon')
+  t.is(
+    toAnnotation(diagnostic, join(workspace, 'crates', 'core'), workspace),
+    '::warning file=crates/core/src/lib.rs,line=3,endLine=3,col=7,endColumn=8,title=unused_variables::unused variable: `a`',
+  )
+})
+
+test('should escape annotation', (t) => {
+  const diagnostic: CargoDiagnostic = {
+    message: 'mismatched types\n100% sure',
+    code: null,
+    level: 'error',
+    spans: [span],
+    rendered: null,
+  }
+  t.is(
+    toAnnotation(diagnostic, '/build', '/build'),
+    '::error file=src/lib.rs,line=3,endLine=3,col=7,endColumn=8,title=cargo error::mismatched types%0A100%25 sure',
+  )
+  t.is(toAnnotation({ ...diagnostic, spans: [] }, '/build', '/build'), null)
+  t.is(toAnnotation({ ...diagnostic, level: 'note' }, '/build', '/build'), null)
+})
@@ -0,0 +1,109 @@
+import test from 'ava'
+
+import { createDenoEntry, createJsBinding } from '../js-binding-template'
+
+test('should require the binaries next to the binding', (t) => {
+  const binding = createJsBinding('index', '@napi-rs/cli')
+  t.true(
+    binding.includes(
+      `existsSync(join(__dirname, localFile('index.darwin-x64.node')))`,
+    ),
+  )
+  t.true(
+    binding.includes("require(`./${localFile('index.darwin-x64.node')}`)"),
+  )
+})
+
+test('should require the binaries relative to the binding in a sub-dir', (t) => {
+  const binding = createJsBinding('index', '@napi-rs/cli', '..')
+  t.true(
+    binding.includes(
+      `existsSync(join(__dirname, '..', localFile('index.darwin-x64.node')))`,
+    ),
+  )
+  t.true(
+    binding.includes("require(`../${localFile('index.darwin-x64.node')}`)"),
+  )
+  t.true(
+    createJsBinding('index', '@napi-rs/cli', 'dist').includes(
+      "require(`./dist/${localFile('index.darwin-x64.node')}`)",
+    ),
+  )
+})
+
+test('should require the other binaries from the subpath of the platform packages', (t) => {
+  const binding = createJsBinding('plugin', '@napi-rs/cli', '.', {
+    subpath: true,
+  })
+  t.true(
+    binding.includes(
+      "require('@napi-rs/cli-darwin-x64/plugin.darwin-x64.node')",
+    ),
+  )
+  t.true(
+    binding.includes("require(`./${localFile('plugin.darwin-x64.node')}`)"),
+  )
+  t.true(
+    createJsBinding('index', '@napi-rs/cli').includes(
+      "require('@napi-rs/cli-darwin-x64')",
+    ),
+  )
+})
+
+test('should detect musl by the filesystem under Bun with --bun', (t) => {
+  const binding = createJsBinding('index', '@napi-rs/cli', '.', { bun: true })
+  t.true(
+    binding.startsWith(
+      "const { existsSync, readdirSync, readFileSync } = require('fs')",
+    ),
+  )
+  t.true(binding.includes('if (process.versions.bun) {'))
+  t.false(createJsBinding('index', '@napi-rs/cli').includes('versions.bun'))
+})
+
+test('should detect musl by the filesystem under Deno with --deno', (t) => {
+  t.true(
+    createJsBinding('index', '@napi-rs/cli', '.', { deno: true }).includes(
+      'if (process.versions.deno) {',
+    ),
+  )
+  t.true(
+    createJsBinding('index', '@napi-rs/cli', '.', {
+      bun: true,
+      deno: true,
+    }).includes('if (process.versions.bun || process.versions.deno) {'),
+  )
+})
+
+test('should prefer the CPU variants with napi.variants', (t) => {
+  const binding = createJsBinding('index', '@napi-rs/cli', '.', {
+    cpuVariants: {
+      avx2: { targets: 'x86_64-*', cpuFeatures: ['avx2', 'sse4.2'] },
+    },
+  })
+  t.true(
+    binding.includes('const cpuVariants = [["avx2",[["avx2"],["sse4_2"]]]]'),
+  )
+  t.true(binding.includes('return variantFile(file)'))
+  t.true(
+    binding.includes(
+      "require(packageFile('@napi-rs/cli-darwin-x64', 'index.darwin-x64.node'))",
+    ),
+  )
+  t.false(createJsBinding('index', '@napi-rs/cli').includes('cpuVariants'))
+})
+
+test('should require the binding by createRequire in the Deno entry', (t) => {
+  t.is(
+    createDenoEntry('./index.js', './index.d.ts', ['sum', 'Animal']),
+    `// @ts-self-types="./index.d.ts"
+import { createRequire } from 'node:module'
+
+const require = createRequire(import.meta.url)
+const nativeBinding = require('./index.js')
+
+export const { sum, Animal } = nativeBinding
+export default nativeBinding
+`,
+  )
+})
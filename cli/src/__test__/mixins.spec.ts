@@ -0,0 +1,98 @@
+import { mkdirSync, mkdtempSync, writeFileSync } from 'fs'
+import { createRequire } from 'module'
+import { tmpdir } from 'os'
+import { join } from 'path'
+
+import test from 'ava'
+
+import {
+  checkMixinClasses,
+  createMixinDts,
+  createMixinsBinding,
+  resolveMixins,
+} from '../mixins'
+
+function createPackage(withDts = true) {
+  const dir = mkdtempSync(join(tmpdir(), 'napi-rs-mixins-'))
+  mkdirSync(join(dir, 'mixins'))
+  writeFileSync(
+    join(dir, 'mixins', 'animal.js'),
+    `module.exports = {
+  shout() {
+    return this.name.toUpperCase()
+  },
+  get loud() {
+    return true
+  },
+}`,
+  )
+  if (withDts) {
+    writeFileSync(
+      join(dir, 'mixins', 'animal.d.ts'),
+      `export declare function shout(): string
+export declare const loud: boolean`,
+    )
+  }
+  return dir
+}
+
+function applyMixins(dir: string, Class: Function) {
+  const mixins = resolveMixins({ Animal: './mixins/animal.js' }, dir)
+  const bindingPath = join(dir, 'index.js')
+  // eslint-disable-next-line @typescript-eslint/no-implied-eval
+  new Function('Animal', 'require', createMixinsBinding(mixins, bindingPath))(
+    Class,
+    createRequire(bindingPath),
+  )
+}
+
+test('should merge the mixin onto the prototype', (t) => {
+  class Animal {
+    constructor(public name: string) {}
+  }
+  applyMixins(createPackage(), Animal)
+  const animal = new Animal('cat') as Animal & {
+    shout(): string
+    loud: boolean
+  }
+  t.is(animal.shout(), 'CAT')
+  t.true(animal.loud)
+  t.deepEqual(Object.keys(Animal.prototype), [])
+})
+
+test('should not override the native members', (t) => {
+  class Animal {
+    shout() {
+      return 'native'
+    }
+  }
+  t.throws(() => applyMixins(createPackage(), Animal), {
+    message: /The mixin \.\/mixins\/animal\.js overrides Animal\.prototype\.shout/,
+  })
+})
+
+test('should merge the types of the mixin into the class', (t) => {
+  const dir = createPackage()
+  const [mixin] = resolveMixins({ Animal: './mixins/animal.js' }, dir)
+  t.is(
+    createMixinDts(mixin, join(dir, 'index.d.ts')),
+    `type __AnimalMixin = typeof import('./mixins/animal')
+export interface Animal extends __AnimalMixin {}
+`,
+  )
+})
+
+test('should validate the mixins', (t) => {
+  const config = { Animal: './mixins/animal.js' }
+  t.throws(() => resolveMixins(config, createPackage(false)), {
+    message: /animal\.d\.ts\] of Animal is not existed/,
+  })
+  t.throws(() => resolveMixins({ Animal: './animal.ts' }, createPackage()), {
+    message: /must be a \.js or \.cjs file/,
+  })
+  const mixins = resolveMixins(config, createPackage())
+  t.notThrows(() => checkMixinClasses(mixins, ['Animal', 'Plant']))
+  t.throws(() => checkMixinClasses(mixins, ['Plant']), {
+    message: /doesn't match any top level class/,
+  })
+})
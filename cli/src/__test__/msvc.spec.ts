@@ -0,0 +1,42 @@
+import { mkdirSync, mkdtempSync, writeFileSync } from 'fs'
+import { tmpdir } from 'os'
+import { join } from 'path'
+
+import test from 'ava'
+
+import { findMsvcArm64Libs, findVisualStudioInstallations } from '../msvc'
+
+function createFakeVisualStudio(...arm64Versions: string[]) {
+  const installation = mkdtempSync(join(tmpdir(), 'napi-rs-vs-'))
+  const msvcDir = join(installation, 'VC', 'Tools', 'MSVC')
+  mkdirSync(join(msvcDir, '14.29.30133', 'lib', 'x64'), { recursive: true })
+  for (const version of arm64Versions) {
+    const lib = join(msvcDir, version, 'lib', 'arm64')
+    mkdirSync(lib, { recursive: true })
+    writeFileSync(join(lib, 'msvcrt.lib'), '')
+  }
+  return { installation, msvcDir }
+}
+
+test('should prefer VSINSTALLDIR', (t) => {
+  const { installation } = createFakeVisualStudio()
+  t.deepEqual(findVisualStudioInstallations({ VSINSTALLDIR: installation }), [
+    installation,
+  ])
+})
+
+test('should find the newest arm64 libs', (t) => {
+  const { installation, msvcDir } = createFakeVisualStudio(
+    '14.9.10000',
+    '14.34.31933',
+  )
+  t.is(
+    findMsvcArm64Libs([installation]),
+    join(msvcDir, '14.34.31933', 'lib', 'arm64'),
+  )
+})
+
+test('should return null without the arm64 component', (t) => {
+  const { installation } = createFakeVisualStudio()
+  t.is(findMsvcArm64Libs([installation]), null)
+})
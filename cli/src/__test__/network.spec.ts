@@ -0,0 +1,95 @@
+import test from 'ava'
+
+import {
+  describeNetworkError,
+  getProxyForUrl,
+  networkEnv,
+  withRetries,
+} from '../network'
+
+test('should get the proxy of the url', (t) => {
+  const proxy = 'http://proxy.internal:8080'
+  t.is(getProxyForUrl('https://api.github.com', {}), null)
+  t.is(getProxyForUrl('https://api.github.com', { HTTPS_PROXY: proxy }), proxy)
+  t.is(getProxyForUrl('https://api.github.com', { http_proxy: proxy }), proxy)
+  t.is(getProxyForUrl('http://example.com', { HTTPS_PROXY: proxy }), null)
+  t.is(
+    getProxyForUrl('https://api.github.com', {
+      HTTPS_PROXY: proxy,
+      NO_PROXY: 'localhost,.github.com',
+    }),
+    null,
+  )
+  t.is(
+    getProxyForUrl('https://api.github.com', {
+      HTTPS_PROXY: proxy,
+      no_proxy: 'github.com:80',
+    }),
+    proxy,
+  )
+  t.is(
+    getProxyForUrl('https://github.com.evil.com', {
+      HTTPS_PROXY: proxy,
+      NO_PROXY: 'github.com',
+    }),
+    proxy,
+  )
+  t.is(
+    getProxyForUrl('https://example.com', { HTTPS_PROXY: proxy, NO_PROXY: '*' }),
+    null,
+  )
+})
+
+test('should pass the CA bundle to the child processes', (t) => {
+  t.deepEqual(networkEnv({ PATH: '/bin' }), { PATH: '/bin' })
+  t.deepEqual(
+    networkEnv({ NAPI_RS_CA_FILE: '/etc/ca.pem', AWS_CA_BUNDLE: '/aws.pem' }),
+    {
+      NAPI_RS_CA_FILE: '/etc/ca.pem',
+      npm_config_cafile: '/etc/ca.pem',
+      NODE_EXTRA_CA_CERTS: '/etc/ca.pem',
+      AWS_CA_BUNDLE: '/aws.pem',
+      REQUESTS_CA_BUNDLE: '/etc/ca.pem',
+      CLOUDSDK_CORE_CUSTOM_CA_CERTS_FILE: '/etc/ca.pem',
+    },
+  )
+})
+
+test('should retry the network errors', async (t) => {
+  let calls = 0
+  const result = await withRetries(
+    'Request',
+    async () => {
+      if (++calls < 3) {
+        throw Object.assign(new Error('socket hang up'), { code: 'ECONNRESET' })
+      }
+      return calls
+    },
+    3,
+    1,
+  )
+  t.is(result, 3)
+  calls = 0
+  await t.throwsAsync(
+    withRetries(
+      'Request',
+      async () => {
+        calls++
+        throw Object.assign(new Error('Not Found'), { status: 404 })
+      },
+      3,
+      1,
+    ),
+    { message: 'Not Found' },
+  )
+  t.is(calls, 1)
+})
+
+test('should describe the TLS errors', (t) => {
+  const error = Object.assign(new Error('self-signed certificate'), {
+    code: 'SELF_SIGNED_CERT_IN_CHAIN',
+  })
+  t.regex(describeNetworkError(error).message, /NAPI_RS_CA_FILE/)
+  const notFound = new Error('Not Found')
+  t.is(describeNetworkError(notFound), notFound)
+})
@@ -0,0 +1,89 @@
+import { mkdirSync, mkdtempSync, writeFileSync } from 'fs'
+import { tmpdir } from 'os'
+import { join } from 'path'
+
+import test from 'ava'
+
+import {
+  findIncludeDir,
+  findNodeLibDir,
+  readNapiVersion,
+  requiredNapiVersion,
+  resolveNodeHeaders,
+} from '../node-headers'
+import { parseTriple } from '../parse-triple'
+
+const JS_NATIVE_API = `#ifndef NAPI_VERSION
+#ifdef NAPI_EXPERIMENTAL
+#define NAPI_VERSION NAPI_VERSION_EXPERIMENTAL
+#else
+// The baseline version for N-API.
+#define NAPI_VERSION 8
+#endif
+#endif
+`
+
+// the layout of `node-v16.14.0-headers.tar.gz`
+function createHeaders(nodeVersionHeader?: string) {
+  const root = mkdtempSync(join(tmpdir(), 'napi-rs-node-headers-'))
+  const includeDir = join(root, 'node-v16.14.0', 'include', 'node')
+  mkdirSync(includeDir, { recursive: true })
+  writeFileSync(join(includeDir, 'node_api.h'), '')
+  writeFileSync(join(includeDir, 'js_native_api.h'), JS_NATIVE_API)
+  if (nodeVersionHeader) {
+    writeFileSync(join(includeDir, 'node_version.h'), nodeVersionHeader)
+  }
+  return { root, includeDir }
+}
+
+test('should find the include dir in the extracted tarball', (t) => {
+  const { root, includeDir } = createHeaders()
+  t.is(findIncludeDir(root), includeDir)
+  t.is(findIncludeDir(includeDir), includeDir)
+  t.is(findIncludeDir(tmpdir()), null)
+})
+
+test('should read NAPI_VERSION of node_version.h first', (t) => {
+  t.is(readNapiVersion(createHeaders().includeDir), 8)
+  t.is(
+    readNapiVersion(
+      createHeaders('#define NODE_MAJOR_VERSION 18\n#define NAPI_VERSION  9\n')
+        .includeDir,
+    ),
+    9,
+  )
+})
+
+test('should read the required N-API version from the features', (t) => {
+  t.is(requiredNapiVersion(['compat-mode', 'napi1', 'napi2', 'napi3']), 3)
+  t.is(requiredNapiVersion(['napi8', 'napi4', 'serde-json']), 8)
+  t.is(requiredNapiVersion([]), 1)
+})
+
+test('should find node.lib in the node-gyp and nodejs.org layouts', (t) => {
+  const root = mkdtempSync(join(tmpdir(), 'napi-rs-node-lib-'))
+  mkdirSync(join(root, 'win-x86'))
+  writeFileSync(join(root, 'win-x86', 'node.lib'), '')
+  mkdirSync(join(root, 'arm64'))
+  writeFileSync(join(root, 'arm64', 'node.lib'), '')
+  t.is(findNodeLibDir(root, 'ia32'), join(root, 'win-x86'))
+  t.is(findNodeLibDir(root, 'arm64'), join(root, 'arm64'))
+  t.is(findNodeLibDir(root, 'x64'), null)
+})
+
+test('should validate the exported N-API version', async (t) => {
+  const { root, includeDir } = createHeaders()
+  const triple = parseTriple('x86_64-unknown-linux-gnu')
+  t.deepEqual(await resolveNodeHeaders(root, triple, 6), {
+    includeDir,
+    libDir: null,
+    napiVersion: 8,
+  })
+  await t.throwsAsync(() => resolveNodeHeaders(root, triple, 9), {
+    message: /export N-API 8, but the features of napi require N-API 9/,
+  })
+  await t.throwsAsync(
+    () => resolveNodeHeaders(root, parseTriple('x86_64-pc-windows-msvc'), 3),
+    { message: /Could not find .*node\.lib/ },
+  )
+})
@@ -0,0 +1,85 @@
+import { mkdirSync, mkdtempSync, writeFileSync } from 'fs'
+import { tmpdir } from 'os'
+import { join } from 'path'
+
+import test from 'ava'
+
+import {
+  assertOnline,
+  isVendored,
+  readLockedCrates,
+  vendorConfigFlags,
+} from '../offline'
+
+const CARGO_LOCK = `# This file is automatically @generated by Cargo.
+version = 3
+
+[[package]]
+name = "addon"
+version = "0.1.0"
+dependencies = [
+ "napi",
+]
+
+[[package]]
+name = "napi"
+version = "2.0.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+checksum = "0e8e4d1f5e8c6f8ce7cbef5a4ac5f6e3e6d5a4ee8ac98c1b1c2c6ef8ec6a0b82"
+
+[[package]]
+name = "forked"
+version = "0.2.0"
+source = "git+https://github.com/napi-rs/forked?rev=abc#abc"
+`
+
+test('should read the fetched crates of Cargo.lock', (t) => {
+  const dir = mkdtempSync(join(tmpdir(), 'napi-rs-offline-'))
+  writeFileSync(join(dir, 'Cargo.lock'), CARGO_LOCK)
+  t.deepEqual(readLockedCrates(dir), [
+    {
+      name: 'napi',
+      version: '2.0.0',
+      source: 'registry+https://github.com/rust-lang/crates.io-index',
+      checksum:
+        '0e8e4d1f5e8c6f8ce7cbef5a4ac5f6e3e6d5a4ee8ac98c1b1c2c6ef8ec6a0b82',
+    },
+    {
+      name: 'forked',
+      version: '0.2.0',
+      source: 'git+https://github.com/napi-rs/forked?rev=abc#abc',
+    },
+  ])
+})
+
+test('should require Cargo.lock', (t) => {
+  const dir = mkdtempSync(join(tmpdir(), 'napi-rs-offline-'))
+  t.throws(() => readLockedCrates(dir), { message: /Cargo\.lock/ })
+})
+
+test('should replace crates.io with the vendor dir', (t) => {
+  const dir = mkdtempSync(join(tmpdir(), 'napi-rs-offline-'))
+  t.deepEqual(vendorConfigFlags(dir), [])
+  t.false(isVendored(dir))
+  mkdirSync(join(dir, 'vendor'))
+  t.true(isVendored(dir))
+  t.deepEqual(vendorConfigFlags(dir), [
+    `--config "source.crates-io.replace-with='vendored-sources'"`,
+    `--config "source.vendored-sources.directory='${join(dir, 'vendor')
+      .split('\\')
+      .join('/')}'"`,
+  ])
+  mkdirSync(join(dir, '.cargo'))
+  writeFileSync(
+    join(dir, '.cargo', 'config.toml'),
+    '[source.crates-io]\nreplace-with = "vendored-sources"\n',
+  )
+  t.deepEqual(vendorConfigFlags(dir), [])
+})
+
+test('should forbid the network access offline', (t) => {
+  t.notThrows(() => assertOnline('Upload', {}))
+  t.throws(() => assertOnline('Upload', { NAPI_RS_OFFLINE: 'true' }), {
+    message: /Upload requires network access/,
+  })
+})
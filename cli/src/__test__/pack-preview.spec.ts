@@ -0,0 +1,57 @@
+import test from 'ava'
+
+import {
+  checkSizeBudgets,
+  formatSize,
+  getSizeBudget,
+  PackResult,
+  parseSize,
+} from '../pack-preview'
+
+const pack = (name: string, unpackedSize: number): PackResult => ({
+  name,
+  version: '1.0.0',
+  size: unpackedSize / 2,
+  unpackedSize,
+  files: [],
+})
+
+test('should parse size', (t) => {
+  t.is(parseSize(1000), 1000)
+  t.is(parseSize('512'), 512)
+  t.is(parseSize('20MB'), 20 * 1024 * 1024)
+  t.is(parseSize('1.5 kb'), 1536)
+  t.throws(() => parseSize('20 megabytes'), { instanceOf: TypeError })
+})
+
+test('should format size', (t) => {
+  t.is(formatSize(100), '100B')
+  t.is(formatSize(1536), '1.5KB')
+  t.is(formatSize(80 * 1024 * 1024), '80.0MB')
+})
+
+test('should get the size budget of the package', (t) => {
+  t.is(getSizeBudget('@napi-rs/cli', undefined), null)
+  t.is(getSizeBudget('@napi-rs/cli', '1KB'), 1024)
+  const config = { '*': '10MB', '@napi-rs/cli': '1KB' }
+  t.is(getSizeBudget('@napi-rs/cli', config), 1024)
+  t.is(getSizeBudget('@napi-rs/cli-linux-x64-gnu', config), 10 * 1024 * 1024)
+  t.is(getSizeBudget('@napi-rs/cli-linux-x64-gnu', { '@napi-rs/cli': 1 }), null)
+})
+
+test('should check the size budgets', (t) => {
+  const packs = [
+    pack('@napi-rs/cli', 2048),
+    pack('@napi-rs/cli-linux-x64-gnu', 80 * 1024 * 1024),
+    pack('@napi-rs/cli-darwin-x64', 5 * 1024 * 1024),
+  ]
+  t.deepEqual(checkSizeBudgets(packs), [])
+  t.deepEqual(checkSizeBudgets(packs, { '*': '20MB', '@napi-rs/cli': '1KB' }), [
+    { name: '@napi-rs/cli', unpackedSize: 2048, budget: 1024 },
+    {
+      name: '@napi-rs/cli-linux-x64-gnu',
+      unpackedSize: 80 * 1024 * 1024,
+      budget: 20 * 1024 * 1024,
+    },
+  ])
+})
@@ -0,0 +1,51 @@
+import test from 'ava'
+
+import { findMissingAddons, PackedPackage } from '../pack'
+import { parsePackOutput } from '../pack-preview'
+
+const NPM_PACK_OUTPUT = `[
+  {
+    "id": "@napi-rs/cli-linux-x64-gnu@1.0.0",
+    "name": "@napi-rs/cli-linux-x64-gnu",
+    "version": "1.0.0",
+    "size": 205,
+    "unpackedSize": 125,
+    "filename": "napi-rs-cli-linux-x64-gnu-1.0.0.tgz",
+    "files": [
+      { "path": "cli.linux-x64-gnu.node", "size": 2, "mode": 420 },
+      { "path": "package.json", "size": 123, "mode": 420 }
+    ],
+    "entryCount": 2,
+    "bundled": []
+  }
+]`
+
+test('should parse the output of npm pack', (t) => {
+  t.deepEqual(parsePackOutput(NPM_PACK_OUTPUT), {
+    name: '@napi-rs/cli-linux-x64-gnu',
+    version: '1.0.0',
+    filename: 'napi-rs-cli-linux-x64-gnu-1.0.0.tgz',
+    size: 205,
+    unpackedSize: 125,
+    files: [
+      { path: 'cli.linux-x64-gnu.node', size: 2 },
+      { path: 'package.json', size: 123 },
+    ],
+  })
+})
+
+test('should find the tarballs without the addons', (t) => {
+  const pack = parsePackOutput(NPM_PACK_OUTPUT)
+  const packed = (addon: string | null): PackedPackage => ({
+    dir: 'npm/linux-x64-gnu',
+    addon,
+    tarball: `dist/${pack.filename}`,
+    pack,
+  })
+  t.deepEqual(
+    findMissingAddons([packed(null), packed('cli.linux-x64-gnu.node')]),
+    [],
+  )
+  const missing = packed('cli.linux-x64-musl.node')
+  t.deepEqual(findMissingAddons([packed(null), missing]), [missing])
+})
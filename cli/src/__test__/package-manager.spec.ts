@@ -0,0 +1,35 @@
+import test from 'ava'
+
+import {
+  findWorkspaceProtocolDependencies,
+  getPackageManagerDetail,
+  runScript,
+} from '../package-manager'
+
+test('should pass args to script', (t) => {
+  t.is(
+    runScript(getPackageManagerDetail('npm'), 'build', '--release'),
+    'npm run build -- --release',
+  )
+  t.is(
+    runScript(getPackageManagerDetail('pnpm'), 'build', '--release'),
+    'pnpm build --release',
+  )
+  t.is(runScript(getPackageManagerDetail('yarn-berry'), 'test'), 'yarn test')
+})
+
+test('should throw on unsupported package manager', (t) => {
+  t.throws(() => getPackageManagerDetail('bun'), {
+    instanceOf: TypeError,
+  })
+})
+
+test('should find workspace protocol dependencies', (t) => {
+  t.deepEqual(
+    findWorkspaceProtocolDependencies({
+      dependencies: { a: 'workspace:*', b: '^1.0.0' },
+      devDependencies: { c: 'workspace:^' },
+    }),
+    ['a', 'c'],
+  )
+})
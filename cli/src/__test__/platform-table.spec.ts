@@ -0,0 +1,72 @@
+import test from 'ava'
+
+import { parseTriple } from '../parse-triple'
+import {
+  createPlatformTable,
+  getPlatformRequirement,
+  injectPlatformTable,
+  PLATFORM_TABLE_END,
+  PLATFORM_TABLE_START,
+} from '../platform-table'
+
+test('should read the requirements of the platforms', (t) => {
+  t.is(
+    getPlatformRequirement(parseTriple('x86_64-unknown-linux-gnu')),
+    'glibc >= 2.12',
+  )
+  t.is(
+    getPlatformRequirement(parseTriple('aarch64-unknown-linux-musl')),
+    'musl',
+  )
+  t.is(
+    getPlatformRequirement(parseTriple('armv7-unknown-linux-gnueabihf')),
+    'glibc',
+  )
+  t.is(
+    getPlatformRequirement(parseTriple('aarch64-linux-android')),
+    'API level >= 24',
+  )
+  t.is(getPlatformRequirement(parseTriple('x86_64-unknown-freebsd')), '')
+})
+
+test('should create the platform table', (t) => {
+  t.is(
+    createPlatformTable(
+      [
+        parseTriple('x86_64-unknown-linux-gnu'),
+        parseTriple('aarch64-apple-darwin'),
+      ],
+      '@napi-rs/cool',
+      '>= 12',
+    ),
+    `| Platform | Target | Package | Node.js | Requirement |
+| --- | --- | --- | --- | --- |
+| Linux x64 gnu | \`x86_64-unknown-linux-gnu\` | \`@napi-rs/cool-linux-x64-gnu\` | \`>= 12\` | glibc >= 2.12 |
+| macOS arm64 | \`aarch64-apple-darwin\` | \`@napi-rs/cool-darwin-arm64\` | \`>= 12\` | macOS >= 11 |`,
+  )
+})
+
+test('should inject the table between the markers', (t) => {
+  const readme = `# cool
+
+${PLATFORM_TABLE_START}
+outdated
+${PLATFORM_TABLE_END}
+
+## Usage
+`
+  t.is(
+    injectPlatformTable(readme, '| table |'),
+    `# cool
+
+${PLATFORM_TABLE_START}
+
+| table |
+
+${PLATFORM_TABLE_END}
+
+## Usage
+`,
+  )
+  t.is(injectPlatformTable('# cool\n', '| table |'), null)
+})
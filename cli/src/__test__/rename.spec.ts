@@ -0,0 +1,46 @@
+import test from 'ava'
+
+import { renameCargoPackage, renameJsBinding } from '../rename'
+
+test('should rename the JS binding', (t) => {
+  const jsBinding = `localFileExisted = existsSync(join(__dirname, 'index.darwin-x64.node'))
+nativeBinding = require('./index.darwin-x64.node')
+nativeBinding = require('@scope/package-darwin-x64')
+nativeBinding = require('@scope/package-utils')
+`
+  t.is(
+    renameJsBinding(jsBinding, {
+      packageName: '@scope/package',
+      binaryName: 'index',
+      newPackageName: '@my-scope/addon',
+      newBinaryName: 'addon',
+    }),
+    `localFileExisted = existsSync(join(__dirname, 'addon.darwin-x64.node'))
+nativeBinding = require('./addon.darwin-x64.node')
+nativeBinding = require('@my-scope/addon-darwin-x64')
+nativeBinding = require('@scope/package-utils')
+`,
+  )
+})
+
+test('should rename the cargo package', (t) => {
+  t.is(
+    renameCargoPackage(
+      `[package]
+authors = ["LongYinan <lynweklm@gmail.com>"]
+name = "napi-package-template"
+
+[lib]
+name = "template"
+`,
+      'addon',
+    ),
+    `[package]
+authors = ["LongYinan <lynweklm@gmail.com>"]
+name = "addon"
+
+[lib]
+name = "template"
+`,
+  )
+})
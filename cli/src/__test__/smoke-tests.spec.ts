@@ -0,0 +1,102 @@
+import test from 'ava'
+
+import type { TypeDef } from '../build'
+import { collectExportAssertions, generateSmokeTest } from '../smoke-tests'
+
+const defs: TypeDef[] = [
+  {
+    kind: 'const',
+    name: 'DEFAULT_COST',
+    js_doc: '',
+    def: 'export const DEFAULT_COST: number',
+  },
+  {
+    kind: 'const',
+    name: 'MAPPING',
+    js_doc: '',
+    def: 'export const MAPPING: Record<string, number>',
+  },
+  {
+    kind: 'fn',
+    name: 'sum',
+    js_doc: '',
+    def: 'export function sum(a: number, b: number): number',
+  },
+  {
+    kind: 'enum',
+    name: 'Kind',
+    js_doc: '',
+    def: 'Dog = 0',
+  },
+  {
+    kind: 'interface',
+    name: 'Options',
+    js_doc: '',
+    def: 'name: string',
+  },
+  {
+    kind: 'struct',
+    name: 'Animal',
+    js_doc: '',
+    def: 'readonly kind: Kind',
+  },
+  {
+    kind: 'impl',
+    name: 'Animal',
+    js_doc: '',
+    def: 'constructor(kind: Kind)\nget name(): string\nwhoami(): string\nstatic getDogKind(): Kind',
+  },
+  // `impl Task`, not a class
+  {
+    kind: 'impl',
+    name: 'DelaySum',
+    js_doc: '',
+    def: '',
+  },
+  {
+    kind: 'fn',
+    name: 'xxh64',
+    js_doc: '',
+    js_mod: 'xxh3',
+    def: 'export function xxh64(input: Buffer): bigint',
+  },
+]
+
+test('should collect the typeof of the exports', (t) => {
+  t.deepEqual(collectExportAssertions(defs), [
+    { path: 'DEFAULT_COST', type: 'number' },
+    { path: 'MAPPING', type: null },
+    { path: 'sum', type: 'function' },
+    { path: 'Kind', type: 'object' },
+    { path: 'Animal', type: 'function' },
+    { path: 'xxh3', type: 'object' },
+    { path: 'xxh3.xxh64', type: 'function' },
+    { path: 'Animal.prototype.whoami', type: 'function' },
+    { path: 'Animal.getDogKind', type: 'function' },
+  ])
+})
+
+test('should check every level of the nested namespaces', (t) => {
+  t.deepEqual(
+    collectExportAssertions([
+      {
+        kind: 'fn',
+        name: 'seedValue',
+        js_doc: '',
+        js_mod: 'xxh3.streaming',
+        def: 'export function seedValue(seed: Seed): number',
+      },
+    ]),
+    [
+      { path: 'xxh3', type: 'object' },
+      { path: 'xxh3.streaming', type: 'object' },
+      { path: 'xxh3.streaming.seedValue', type: 'function' },
+    ],
+  )
+})
+
+test('should require the binding in the smoke test', (t) => {
+  const smokeTest = generateSmokeTest(defs, './index.js')
+  t.true(smokeTest.includes(`const binding = require('./index.js')\n`))
+  t.true(smokeTest.includes(`  ["Animal.prototype.whoami","function"],\n`))
+})
@@ -0,0 +1,63 @@
+import test from 'ava'
+
+import {
+  blobKey,
+  isNotFoundError,
+  manifestKey,
+  parseStorageUrl,
+} from '../storage'
+
+test('should parse storage url', (t) => {
+  t.deepEqual(parseStorageUrl('s3://bucket/napi/artifacts/'), {
+    provider: 's3',
+    bucket: 'bucket',
+    prefix: 'napi/artifacts',
+  })
+  t.deepEqual(parseStorageUrl('az://container'), {
+    provider: 'az',
+    bucket: 'container',
+    prefix: '',
+  })
+  t.throws(() => parseStorageUrl('https://bucket/napi'))
+})
+
+test('should generate content addressed keys', (t) => {
+  const location = parseStorageUrl('gs://bucket/napi')
+  t.is(
+    blobKey(location, 'abc', 'index.linux-x64-gnu.node'),
+    'napi/blobs/abc/index.linux-x64-gnu.node',
+  )
+  t.is(
+    manifestKey(parseStorageUrl('gs://bucket'), '1.0.0', 'index.node'),
+    '1.0.0/index.node.json',
+  )
+})
+
+test('should only treat the missing objects as not found', (t) => {
+  t.true(
+    isNotFoundError('s3', {
+      status: 254,
+      stderr: Buffer.from(
+        'An error occurred (404) when calling the HeadObject operation: Not Found',
+      ),
+    }),
+  )
+  t.false(
+    isNotFoundError('s3', {
+      status: 254,
+      stderr: Buffer.from(
+        'An error occurred (403) when calling the HeadObject operation: Forbidden',
+      ),
+    }),
+  )
+  t.true(isNotFoundError('gs', { status: 1, stderr: Buffer.from('') }))
+  t.false(
+    isNotFoundError('gs', {
+      status: 1,
+      stderr: Buffer.from('AccessDeniedException: 403 Forbidden'),
+    }),
+  )
+  // the CLI is not installed
+  t.false(isNotFoundError('gs', { code: 'ENOENT' }))
+  t.false(isNotFoundError('az', { status: 1, stderr: 'ContainerNotFound' }))
+})
@@ -0,0 +1,40 @@
+import test from 'ava'
+
+import { resolveTargetFeatures } from '../target-features'
+
+const features = {
+  simd: ['*', '!armv7-*'],
+  'vendored-openssl': '*-musl*',
+}
+
+test('should enable the features of the matched targets', (t) => {
+  t.deepEqual(resolveTargetFeatures(features, 'x86_64-unknown-linux-gnu'), [
+    'simd',
+  ])
+  t.deepEqual(resolveTargetFeatures(features, 'x86_64-unknown-linux-musl'), [
+    'simd',
+    'vendored-openssl',
+  ])
+  t.deepEqual(
+    resolveTargetFeatures(features, 'armv7-unknown-linux-musleabihf'),
+    ['vendored-openssl'],
+  )
+  t.deepEqual(resolveTargetFeatures(undefined, 'aarch64-apple-darwin'), [])
+})
+
+test('should match the dots of the triples literally', (t) => {
+  t.deepEqual(
+    resolveTargetFeatures({ wasm: 'wasm32-wasi.json' }, 'wasm32-wasiXjson'),
+    [],
+  )
+})
+
+test('should throw on the invalid targets', (t) => {
+  const triple = 'armv7-linux-androideabi'
+  t.throws(() => resolveTargetFeatures({ simd: [] }, triple), {
+    message: /must be a triple pattern or a list of them/,
+  })
+  t.throws(() => resolveTargetFeatures({ simd: [1] as any }, triple), {
+    message: /must be a triple pattern or a list of them/,
+  })
+})
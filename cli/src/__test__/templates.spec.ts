@@ -0,0 +1,75 @@
+import { mkdirSync, mkdtempSync, writeFileSync } from 'fs'
+import { tmpdir } from 'os'
+import { join } from 'path'
+
+import test from 'ava'
+
+import {
+  applyTemplate,
+  findTemplatesDir,
+  renderTemplate,
+  TEMPLATES_DIR,
+} from '../templates'
+
+test('should render the variables', (t) => {
+  t.is(
+    renderTemplate('{{ packageName }}-{{platform.platformArchABI}}', {
+      packageName: '@napi-rs/cli',
+      platform: { platformArchABI: 'linux-x64-gnu' },
+    }),
+    '@napi-rs/cli-linux-x64-gnu',
+  )
+  t.is(renderTemplate('[{{ abi }}]', { abi: null }), '[]')
+  t.is(renderTemplate('{{ os }}', { os: ['linux'] }), '[\n  "linux"\n]')
+  t.throws(() => renderTemplate('{{ version }}', { packageName: 'cli' }), {
+    message: /Unknown variable version/,
+  })
+})
+
+test('should find the templates dir of the parents', (t) => {
+  const root = mkdtempSync(join(tmpdir(), 'napi-rs-templates-'))
+  const packageDir = join(root, 'packages', 'addon')
+  mkdirSync(packageDir, { recursive: true })
+  t.is(findTemplatesDir(packageDir), null)
+  mkdirSync(join(root, TEMPLATES_DIR), { recursive: true })
+  t.is(findTemplatesDir(packageDir), join(root, TEMPLATES_DIR))
+})
+
+test('should apply the template with the default content', (t) => {
+  const root = mkdtempSync(join(tmpdir(), 'napi-rs-templates-'))
+  t.is(
+    applyTemplate('header.d.ts', '/* eslint-disable */\n', {}, root),
+    '/* eslint-disable */\n',
+  )
+  mkdirSync(join(root, TEMPLATES_DIR, 'npm'), { recursive: true })
+  writeFileSync(
+    join(root, TEMPLATES_DIR, 'header.d.ts'),
+    '/* Copyright (c) Example Inc, {{ packageName }} */\n{{ default }}',
+  )
+  writeFileSync(
+    join(root, TEMPLATES_DIR, 'npm', 'README.md'),
+    '# {{ packageName }} for {{ platform.raw }}\n',
+  )
+  t.is(
+    applyTemplate(
+      'header.d.ts',
+      '/* eslint-disable */\n',
+      { packageName: 'addon' },
+      root,
+    ),
+    '/* Copyright (c) Example Inc, addon */\n/* eslint-disable */\n',
+  )
+  t.is(
+    applyTemplate(
+      'npm/README.md',
+      '# addon-linux-x64-gnu\n',
+      { packageName: 'addon', platform: { raw: 'x86_64-unknown-linux-gnu' } },
+      root,
+    ),
+    '# addon for x86_64-unknown-linux-gnu\n',
+  )
+  t.is(
+    applyTemplate('index.js', 'module.exports = {}\n', {}, root),
+    'module.exports = {}\n',
+  )
+})
@@ -0,0 +1,119 @@
+import { mkdirSync, mkdtempSync, writeFileSync } from 'fs'
+import { tmpdir } from 'os'
+import { join } from 'path'
+
+import test from 'ava'
+
+import { parseTriple } from '../parse-triple'
+import {
+  checkToolchain,
+  formatToolchainProblems,
+  isLinkerConfigured,
+  ToolchainCheckContext,
+} from '../toolchain-check'
+
+function context(
+  overrides: Partial<ToolchainCheckContext> = {},
+): ToolchainCheckContext {
+  return {
+    platform: 'linux',
+    arch: 'x64',
+    useZig: false,
+    env: {},
+    linkerConfigured: false,
+    hasCommand: () => false,
+    ...overrides,
+  }
+}
+
+test('should require cargo-xwin for msvc on non-Windows hosts', (t) => {
+  const [problem] = checkToolchain(
+    parseTriple('aarch64-pc-windows-msvc'),
+    context(),
+  )
+  t.deepEqual(problem.commands, [
+    'cargo install --locked cargo-xwin',
+    'eval "$(cargo xwin env --target aarch64-pc-windows-msvc)"',
+  ])
+})
+
+test('should skip the target with the linker configured', (t) => {
+  const target = parseTriple('x86_64-pc-windows-msvc')
+  t.deepEqual(
+    checkToolchain(
+      target,
+      context({
+        env: { CARGO_TARGET_X86_64_PC_WINDOWS_MSVC_LINKER: 'lld-link' },
+      }),
+    ),
+    [],
+  )
+  t.deepEqual(checkToolchain(target, context({ linkerConfigured: true })), [])
+})
+
+test('should print the cross gcc of the linux gnu targets', (t) => {
+  const target = parseTriple('aarch64-unknown-linux-gnu')
+  t.deepEqual(checkToolchain(target, context())[0].commands, [
+    'sudo apt-get install -y gcc-aarch64-linux-gnu',
+    'export CARGO_TARGET_AARCH64_UNKNOWN_LINUX_GNU_LINKER=aarch64-linux-gnu-gcc',
+  ])
+  t.deepEqual(
+    checkToolchain(target, context({ hasCommand: () => true }))[0].commands,
+    [
+      'export CARGO_TARGET_AARCH64_UNKNOWN_LINUX_GNU_LINKER=aarch64-linux-gnu-gcc',
+    ],
+  )
+  t.deepEqual(checkToolchain(target, context({ arch: 'arm64' })), [])
+})
+
+test('should require zig for --zig', (t) => {
+  const target = parseTriple('aarch64-apple-darwin')
+  const [problem] = checkToolchain(target, context({ useZig: true }))
+  t.is(problem.missing, "zig is not found in PATH, it's the linker of --zig")
+  t.deepEqual(
+    checkToolchain(target, context({ useZig: true, hasCommand: () => true })),
+    [],
+  )
+  t.regex(checkToolchain(target, context())[0].missing, /build it on macOS/)
+})
+
+test('should require the NDK for android', (t) => {
+  const sdk = mkdtempSync(join(tmpdir(), 'napi-rs-android-sdk-'))
+  const problems = checkToolchain(
+    parseTriple('aarch64-linux-android'),
+    context({ platform: 'darwin', arch: 'arm64', env: { ANDROID_HOME: sdk } }),
+  )
+  t.is(problems.length, 1)
+  t.regex(problems[0].commands[0], /^sdkmanager --install "ndk;/)
+})
+
+test('should format the problems with the commands', (t) => {
+  t.is(
+    formatToolchainProblems('x86_64-apple-darwin', [
+      { missing: 'zig is missing', commands: ['brew install zig'] },
+    ]),
+    `Could not build x86_64-apple-darwin on this host:
+  - zig is missing
+      brew install zig
+Set CARGO_TARGET_X86_64_APPLE_DARWIN_LINKER if the toolchain is installed somewhere else`,
+  )
+})
+
+test('should find the linker in the cargo config of the ancestors', (t) => {
+  const root = mkdtempSync(join(tmpdir(), 'napi-rs-cargo-config-'))
+  const cwd = join(root, 'packages', 'binding')
+  mkdirSync(cwd, { recursive: true })
+  mkdirSync(join(root, '.cargo'))
+  writeFileSync(
+    join(root, '.cargo', 'config.toml'),
+    `[target.armv7-unknown-linux-gnueabihf]
+linker = "arm-linux-gnueabihf-gcc"
+
+[target.x86_64-pc-windows-msvc]
+rustflags = ["-C", "target-feature=+crt-static"]
+`,
+  )
+  const env = { CARGO_HOME: join(root, 'cargo-home') }
+  t.true(isLinkerConfigured('armv7-unknown-linux-gnueabihf', cwd, env))
+  t.false(isLinkerConfigured('x86_64-pc-windows-msvc', cwd, env))
+})
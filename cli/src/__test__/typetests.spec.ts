@@ -0,0 +1,91 @@
+import test from 'ava'
+
+import { generateTypeTests } from '../typetests'
+
+test('should generate type assertions', (t) => {
+  const code = generateTypeTests(
+    [
+      {
+        kind: 'fn',
+        name: 'getCwd',
+        js_doc: '',
+        def: 'export function getCwd(callback: (arg0: string) => void): void',
+      },
+      {
+        kind: 'struct',
+        name: 'Animal',
+        js_doc: '',
+        def: '/** Kind of animal */\nreadonly kind: Kind',
+      },
+      {
+        kind: 'impl',
+        name: 'Animal',
+        js_doc: '',
+        def: 'constructor(kind: Kind, name: string)\nstatic withKind(kind: Kind): Animal\nget name(): string\nset name(name: string)\nget(id: number): string | null',
+      },
+      {
+        kind: 'enum',
+        name: 'Kind',
+        js_doc: '',
+        def: 'Dog = 0',
+      },
+      {
+        kind: 'fn',
+        name: 'xxh2Plus',
+        js_doc: '',
+        js_mod: 'xxh2',
+        def: 'export function xxh2Plus(a: number, b: number): number',
+      },
+    ],
+    './index',
+  )
+  const lines = code.split('\n')
+  t.true(lines.includes(`import type { Animal, Kind } from './index'`))
+  for (const expected of [
+    'expectTypeOf<typeof binding.getCwd>().toEqualTypeOf<(callback: (arg0: string) => void) => void>()',
+    `expectTypeOf<Animal['kind']>().toEqualTypeOf<Kind>()`,
+    'expectTypeOf<ConstructorParameters<typeof Animal>>().toEqualTypeOf<[kind: Kind, name: string]>()',
+    `expectTypeOf<typeof Animal['withKind']>().toEqualTypeOf<(kind: Kind) => Animal>()`,
+    `expectTypeOf<Animal['name']>().toEqualTypeOf<string>()`,
+    `expectTypeOf<Animal['get']>().toEqualTypeOf<(id: number) => string | null>()`,
+    '  expectTypeOf<typeof binding.xxh2.xxh2Plus>().toEqualTypeOf<(a: number, b: number) => number>()',
+  ]) {
+    t.true(lines.includes(expected), expected)
+  }
+})
+
+test('should skip the generic interfaces and functions', (t) => {
+  const code = generateTypeTests(
+    [
+      {
+        kind: 'interface',
+        name: 'Page',
+        generics: '<T>',
+        js_doc: '',
+        def: 'items: Array<T>\ntotal: number',
+      },
+      {
+        kind: 'fn',
+        name: 'firstItem',
+        js_doc: '',
+        def: 'export function firstItem<T>(page: Page<T>): T | undefined | null',
+      },
+      {
+        kind: 'fn',
+        name: 'pageSize',
+        js_doc: '',
+        def: 'export function pageSize(page: Page<string>): number',
+      },
+    ],
+    './index',
+  )
+  const lines = code.split('\n')
+  t.true(lines.includes(`import type { Page } from './index'`))
+  t.true(
+    lines.includes(
+      'expectTypeOf<typeof binding.pageSize>().toEqualTypeOf<(page: Page<string>) => number>()',
+    ),
+  )
+  t.false(code.includes(`Page['items']`))
+  t.false(code.includes('binding.firstItem'))
+})
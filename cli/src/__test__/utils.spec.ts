@@ -0,0 +1,30 @@
+import test from 'ava'
+
+import { toLongPath } from '../utils'
+
+const longSegments = Array.from({ length: 30 }, (_, i) => `nested-dir-${i}`)
+
+test('should keep the paths on other platforms', (t) => {
+  t.is(toLongPath('npm/linux-x64-gnu', 'linux'), 'npm/linux-x64-gnu')
+})
+
+test('should normalize short Windows paths', (t) => {
+  t.is(
+    toLongPath('C:/a/b/../npm/win32-x64-msvc', 'win32'),
+    'C:\\a\\npm\\win32-x64-msvc',
+  )
+})
+
+test('should prefix long Windows paths', (t) => {
+  const path = `C:\\${longSegments.join('\\')}\\index.win32-x64-msvc.node`
+  t.is(toLongPath(path, 'win32'), `\\\\?\\${path}`)
+  t.is(toLongPath(`\\\\?\\${path}`, 'win32'), `\\\\?\\${path}`)
+})
+
+test('should prefix long UNC paths', (t) => {
+  const path = `\\\\server\\share\\${longSegments.join('\\')}`
+  t.is(
+    toLongPath(path, 'win32'),
+    `\\\\?\\UNC\\server\\share\\${longSegments.join('\\')}`,
+  )
+})
@@ -0,0 +1,88 @@
+import test from 'ava'
+import { load } from 'js-yaml'
+
+import { createGithubActionsCIYml } from '../new/ci-yml'
+import { lintWorkflow } from '../new/workflow-lint'
+import { getPackageManagerDetail } from '../package-manager'
+
+test('generated workflow should pass the lint', (t) => {
+  for (const packageManager of ['npm', 'yarn', 'pnpm']) {
+    const workflow = createGithubActionsCIYml(
+      'index',
+      ['x86_64-unknown-linux-gnu', 'aarch64-unknown-linux-musl'],
+      getPackageManagerDetail(packageManager),
+      true,
+      ['16', '18'],
+    )
+    t.deepEqual(lintWorkflow(load(workflow)), [])
+  }
+})
+
+test('should test the compatibility with the package managers and nightly', (t) => {
+  const workflow: any = load(
+    createGithubActionsCIYml(
+      'index',
+      ['x86_64-unknown-linux-gnu'],
+      getPackageManagerDetail('pnpm'),
+      false,
+      ['18', '20', 'nightly'],
+      undefined,
+      ['npm', 'bun'],
+    ),
+  )
+  t.deepEqual(lintWorkflow(workflow), [])
+  const { matrix } = workflow.jobs['test-compatibility'].strategy
+  t.deepEqual(matrix.node, ['18', '20', 'nightly'])
+  t.deepEqual(matrix['package-manager'][1], {
+    name: 'bun',
+    setup: 'npm install -g bun',
+    install: 'bun install --ignore-scripts',
+    test: 'bun run test',
+  })
+  t.deepEqual(
+    workflow.jobs['test-linux-x64-gnu-binding'].strategy.matrix.node,
+    ['18', '20'],
+  )
+  t.true(workflow.jobs.publish.needs.includes('test-compatibility'))
+
+  const defaultWorkflow: any = load(
+    createGithubActionsCIYml(
+      'index',
+      ['x86_64-unknown-linux-gnu'],
+      getPackageManagerDetail('pnpm'),
+    ),
+  )
+  t.false('test-compatibility' in defaultWorkflow.jobs)
+})
+
+test('should report the invalid workflow', (t) => {
+  const errors = lintWorkflow({
+    jobs: {
+      build: {
+        'runs-on': '${{ matrix.settings.host }}',
+        strategy: {
+          matrix: { settings: [{ host: 'ubuntu-latset' }] },
+        },
+        steps: [
+          { run: 'echo ${{ matrix.node }}' },
+          { if: "startsWith(github.ref, 'refs/tags/'", run: 'echo' },
+        ],
+      },
+      publish: {
+        'runs-on': 'ubuntu-latest',
+        needs: ['build', 'test'],
+        steps: [{ if: '${{ secret.NPM_TOKEN }}', run: 'echo' }],
+      },
+    },
+  })
+  t.deepEqual(
+    errors.map(({ path, message }) => `${path}: ${message}`),
+    [
+      "jobs.build.steps[1].if: Unbalanced parentheses in [startsWith(github.ref, 'refs/tags/']",
+      'jobs.publish.steps[0].if: Unknown context [secret]',
+      'jobs.build.steps[0].run: matrix.node is not defined',
+      'jobs.build.runs-on: Unknown runner label [ubuntu-latset]',
+      "jobs.publish.needs[1]: Job [test] doesn't exist",
+    ],
+  )
+})
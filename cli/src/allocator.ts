@@ -0,0 +1,45 @@
+import chalk from 'chalk'
+
+import { CargoPackage } from './cargo-metadata'
+import { PlatformDetail } from './parse-triple'
+
+export const ALLOCATORS = ['system', 'mimalloc', 'jemalloc']
+
+/**
+ * The feature of the `napi` dependency swapping the global allocator, `null` for the system allocator.
+ * The feature is addressed by the key of `napi` in `Cargo.toml`, which may be renamed.
+ */
+export function allocatorFeature(
+  cargoPackage: CargoPackage,
+  allocator: string,
+  triple: PlatformDetail,
+): string | null {
+  if (!ALLOCATORS.includes(allocator)) {
+    throw new TypeError(
+      `Unsupported allocator ${chalk.yellowBright(
+        allocator,
+      )}, expected one of ${ALLOCATORS.join(', ')}`,
+    )
+  }
+  if (allocator === 'system') {
+    return null
+  }
+  if (allocator === 'jemalloc' && triple.abi === 'msvc') {
+    throw new TypeError(
+      `jemalloc doesn't support ${chalk.yellowBright(
+        triple.raw,
+      )}, use ${chalk.green('--allocator mimalloc')} instead`,
+    )
+  }
+  const napi = (cargoPackage.dependencies ?? []).find(
+    ({ name, kind }) => name === 'napi' && kind === null,
+  )
+  if (!napi) {
+    throw new TypeError(
+      `${chalk.green('--allocator')} requires ${chalk.green(
+        'napi',
+      )} in the dependencies of ${chalk.green(cargoPackage.name)}`,
+    )
+  }
+  return `${napi.rename ?? napi.name}/allocator_${allocator}`
+}
@@ -0,0 +1,142 @@
+import { existsSync, readdirSync } from 'fs'
+import { homedir } from 'os'
+import { join } from 'path'
+
+import { debugFactory } from './debug'
+
+const debug = debugFactory('android-ndk')
+
+export const DEFAULT_ANDROID_API_LEVEL = 24
+
+// prefix of the clang wrappers in `toolchains/llvm/prebuilt/<host>/bin`
+const ANDROID_CLANG_TARGET_MAP: Record<string, string> = {
+  'aarch64-linux-android': 'aarch64-linux-android',
+  'armv7-linux-androideabi': 'armv7a-linux-androideabi',
+  'x86_64-linux-android': 'x86_64-linux-android',
+  'i686-linux-android': 'i686-linux-android',
+}
+
+// prefix of the binutils shipped by NDK < 23, which don't have `llvm-ar`
+const ANDROID_BINUTILS_TARGET_MAP: Record<string, string> = {
+  'aarch64-linux-android': 'aarch64-linux-android',
+  'armv7-linux-androideabi': 'arm-linux-androideabi',
+  'x86_64-linux-android': 'x86_64-linux-android',
+  'i686-linux-android': 'i686-linux-android',
+}
+
+function getSdkDirs(env: NodeJS.ProcessEnv): string[] {
+  const dirs = [env.ANDROID_HOME, env.ANDROID_SDK_ROOT]
+  switch (process.platform) {
+    case 'darwin':
+      dirs.push(join(homedir(), 'Library', 'Android', 'sdk'))
+      break
+    case 'win32':
+      if (env.LOCALAPPDATA) {
+        dirs.push(join(env.LOCALAPPDATA, 'Android', 'Sdk'))
+      }
+      break
+    default:
+      dirs.push(join(homedir(), 'Android', 'Sdk'))
+  }
+  return dirs.filter((dir): dir is string => Boolean(dir))
+}
+
+/**
+ * Find the NDK from `ANDROID_NDK_HOME`, `ANDROID_NDK_ROOT`, `ANDROID_NDK_LATEST_HOME` (GitHub Actions),
+ * then the newest `ndk/<version>` or `ndk-bundle` in the Android SDK.
+ */
+export function findAndroidNdk(env = process.env): string | null {
+  const fromEnv = [
+    env.ANDROID_NDK_HOME,
+    env.ANDROID_NDK_ROOT,
+    env.ANDROID_NDK_LATEST_HOME,
+  ].find((dir) => dir && existsSync(dir))
+  if (fromEnv) {
+    return fromEnv
+  }
+  for (const sdkDir of getSdkDirs(env)) {
+    const ndkDir = join(sdkDir, 'ndk')
+    if (existsSync(ndkDir)) {
+      const [latest] = readdirSync(ndkDir)
+        .filter((version) => /^\d+\./.test(version))
+        .sort((a, b) => b.localeCompare(a, undefined, { numeric: true }))
+      if (latest) {
+        return join(ndkDir, latest)
+      }
+    }
+    const ndkBundle = join(sdkDir, 'ndk-bundle')
+    if (existsSync(ndkBundle)) {
+      return ndkBundle
+    }
+  }
+  return null
+}
+
+function getHostTag() {
+  switch (process.platform) {
+    case 'darwin':
+      // NDK only ships x86_64 toolchains for macOS, they run on Apple Silicon through Rosetta
+      return 'darwin-x86_64'
+    case 'win32':
+      return 'windows-x86_64'
+    default:
+      return 'linux-x86_64'
+  }
+}
+
+/**
+ * Env for cross compiling to Android with the NDK clang wrappers,
+ * variables already set by the user are preserved.
+ */
+export function getAndroidToolchainEnv(
+  target: string,
+  ndkPath: string,
+  apiLevel = DEFAULT_ANDROID_API_LEVEL,
+  env = process.env,
+): Record<string, string> {
+  const clangTarget = ANDROID_CLANG_TARGET_MAP[target]
+  if (!clangTarget) {
+    throw new TypeError(`${target} is not a supported Android target`)
+  }
+  const binDir = join(
+    ndkPath,
+    'toolchains',
+    'llvm',
+    'prebuilt',
+    getHostTag(),
+    'bin',
+  )
+  if (!existsSync(binDir)) {
+    throw new TypeError(`Could not find the NDK toolchain in ${binDir}`)
+  }
+  const wrapperExt = process.platform === 'win32' ? '.cmd' : ''
+  const cc = join(binDir, `${clangTarget}${apiLevel}-clang${wrapperExt}`)
+  if (!existsSync(cc)) {
+    throw new TypeError(
+      `Could not find ${cc}, Android API level ${apiLevel} may not be supported by the NDK in ${ndkPath}`,
+    )
+  }
+  const llvmAr = join(
+    binDir,
+    `llvm-ar${process.platform === 'win32' ? '.exe' : ''}`,
+  )
+  const ar = existsSync(llvmAr)
+    ? llvmAr
+    : join(binDir, `${ANDROID_BINUTILS_TARGET_MAP[target]}-ar`)
+  debug(`Android toolchain: ${binDir}, API level ${apiLevel}`)
+
+  const envTarget = target.replaceAll('-', '_')
+  const toolchainEnv: Record<string, string> = {
+    [`CARGO_TARGET_${envTarget.toUpperCase()}_LINKER`]: cc,
+    // read by the `cc` crate, only affects the build of the target, not the build scripts
+    [`CC_${envTarget}`]: cc,
+    [`CXX_${envTarget}`]: join(
+      binDir,
+      `${clangTarget}${apiLevel}-clang++${wrapperExt}`,
+    ),
+    [`AR_${envTarget}`]: ar,
+  }
+  return Object.fromEntries(
+    Object.entries(toolchainEnv).filter(([key]) => !env[key]),
+  )
+}
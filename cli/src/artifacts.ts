@@ -1,3 +1,4 @@
+import { existsSync } from 'fs'
 import { join, parse } from 'path'
 
 import chalk from 'chalk'
@@ -6,7 +7,15 @@ import { fdir } from 'fdir'
 
 import { getNapiConfig } from './consts'
 import { debugFactory } from './debug'
-import { readFileAsync, writeFileAsync } from './utils'
+import { enableJsonOutput, JsonOption, printJson } from './json-output'
+import { ArtifactStorage, parseStorageUrl } from './storage'
+import {
+  copyFileAsync,
+  findLicenseFile,
+  readFileAsync,
+  toLongPath,
+  writeFileAsync,
+} from './utils'
 
 const debug = debugFactory('artifacts')
 
@@ -23,10 +32,52 @@ export class ArtifactsCommand extends Command {
 
   configFileName?: string = Option.String('-c,--config')
 
+  upload?: string = Option.String('--upload', {
+    description: `Upload the addons in ${chalk.green(
+      '--dir',
+    )} to ${chalk.green('s3://bucket/prefix')}, ${chalk.green(
+      'gs://bucket/prefix',
+    )} or ${chalk.green(
+      'az://container/prefix',
+    )} instead of copying them, download them by ${chalk.green(
+      'napi prepublish --from-storage',
+    )}`,
+  })
+
+  offline = Option.Boolean('--offline', false, {
+    description: `Forbid the network access, ${chalk.green(
+      '--upload',
+    )} fails`,
+  })
+
+  json = JsonOption()
+
   async execute() {
-    const { platforms, binaryName, packageJsonPath } = getNapiConfig(
-      this.configFileName,
-    )
+    if (this.json) {
+      enableJsonOutput()
+    }
+    if (this.offline) {
+      process.env.NAPI_RS_OFFLINE = 'true'
+    }
+    const { platforms, binaryNames, packageJsonPath, platformTypes, version } =
+      getNapiConfig(this.configFileName)
+
+    if (this.upload) {
+      const uploaded = await this.uploadArtifacts(
+        this.upload,
+        platforms.flatMap((p) =>
+          binaryNames.map((name) => `${name}.${p.platformArchABI}.node`),
+        ),
+        version,
+      )
+      if (this.json) {
+        printJson({ version, uploaded })
+      }
+      return
+    }
+
+    // source and dist of the copied files
+    const copied: { source: string; dist: string }[] = []
 
     const packageJsonDir = parse(packageJsonPath).dir
 
@@ -38,18 +89,50 @@ export class ArtifactsCommand extends Command {
       join(process.cwd(), this.distDir, platform.platformArchABI),
     )
 
+    const licenseFile = findLicenseFile(packageJsonDir)
+    if (licenseFile) {
+      await Promise.all(
+        distDirs
+          .filter((dir) => existsSync(toLongPath(dir)))
+          .map((dir) => {
+            const distLicense = join(dir, parse(licenseFile).base)
+            debug(`Copy license to [${chalk.yellowBright(distLicense)}]`)
+            return copyFileAsync(licenseFile, distLicense)
+          }),
+      )
+    }
+
     await sourceApi.withPromise().then((output) =>
       Promise.all(
         (output as string[]).map(async (filePath) => {
           debug(`Read [${chalk.yellowBright(filePath)}]`)
           const sourceContent = await readFileAsync(filePath)
+          const platformDts = /\.(\w+)\.d\.ts$/.exec(filePath)
+          if (platformDts) {
+            const typesDir = join(
+              process.cwd(),
+              this.distDir,
+              `types-${platformDts[1]}`,
+            )
+            if (!platformTypes || !existsSync(toLongPath(typesDir))) {
+              debug(`No types dir for [${chalk.yellowBright(filePath)}], skip`)
+              return
+            }
+            const distDtsPath = join(typesDir, 'index.d.ts')
+            debug(`Write file content to [${chalk.yellowBright(distDtsPath)}]`)
+            await writeFileAsync(distDtsPath, sourceContent)
+            copied.push({ source: filePath, dist: distDtsPath })
+            return
+          }
           const parsedName = parse(filePath)
           const [_binaryName, platformArchABI] = parsedName.name.split('.')
-          if (_binaryName !== binaryName) {
+          if (!binaryNames.includes(_binaryName)) {
             debug(
               `[${chalk.yellowBright(
                 _binaryName,
-              )}] is not matched with [${chalk.greenBright(binaryName)}], skip`,
+              )}] is not matched with [${chalk.greenBright(
+                binaryNames.join(', '),
+              )}], skip`,
             )
           }
           const dir = distDirs.find((dir) => dir.includes(platformArchABI))
@@ -59,13 +142,47 @@ export class ArtifactsCommand extends Command {
           const distFilePath = join(dir, parsedName.base)
           debug(`Write file content to [${chalk.yellowBright(distFilePath)}]`)
           await writeFileAsync(distFilePath, sourceContent)
+          copied.push({ source: filePath, dist: distFilePath })
           const distFilePathLocal = join(packageJsonDir, parsedName.base)
           debug(
             `Write file content to [${chalk.yellowBright(distFilePathLocal)}]`,
           )
           await writeFileAsync(distFilePathLocal, sourceContent)
+          copied.push({ source: filePath, dist: distFilePathLocal })
         }),
       ),
     )
+
+    if (this.json) {
+      printJson({ version, copied })
+    }
+  }
+
+  private async uploadArtifacts(
+    url: string,
+    artifactNames: string[],
+    version: string,
+  ) {
+    const storage = new ArtifactStorage(parseStorageUrl(url))
+    const artifacts = (await new fdir()
+      .withFullPaths()
+      .exclude((dirName) => dirName === 'node_modules' || dirName === 'target')
+      .filter((filePath) => artifactNames.includes(parse(filePath).base))
+      .crawl(join(process.cwd(), this.sourceDir))
+      .withPromise()) as string[]
+    if (!artifacts.length) {
+      throw new TypeError(
+        `No ${artifactNames.join(', ')} found in [${this.sourceDir}]`,
+      )
+    }
+    const manifests = []
+    for (const filePath of artifacts) {
+      const manifest = await storage.uploadArtifact(filePath, version)
+      console.info(
+        `${chalk.green(filePath)} uploaded to ${chalk.blue(manifest.blob)}`,
+      )
+      manifests.push(manifest)
+    }
+    return manifests
   }
 }
@@ -0,0 +1,183 @@
+import { spawn } from 'child_process'
+import { tmpdir } from 'os'
+import { join } from 'path'
+
+import chalk from 'chalk'
+import { Command, Option } from 'clipanion'
+
+import type { TypeDef } from './build'
+import { debugFactory } from './debug'
+
+const debug = debugFactory('bench')
+
+export interface BenchCase {
+  // `a.b.sum` of the fn in the namespace `a.b`
+  path: string
+  name: string
+  args: string
+}
+
+/**
+ * The `#[napi(bench)]` cases of the fns, in the order of the defs.
+ */
+export function collectBenchCases(defs: TypeDef[]): BenchCase[] {
+  return defs
+    .filter((def) => def.kind === 'fn')
+    .flatMap((def) =>
+      (def.benches ?? []).map(({ name, args }) => ({
+        path: def.js_mod ? `${def.js_mod}.${def.name}` : def.name,
+        name,
+        args,
+      })),
+    )
+}
+
+/**
+ * An ES module calling the `#[napi(bench)]` fns of the addon required from `bindingPath` with the args of the cases,
+ * prints the ops/sec of each case, exits with 1 if any of them throws.
+ */
+export function generateBenchHarness(defs: TypeDef[], bindingPath: string) {
+  const cases = collectBenchCases(defs)
+  return `/* auto-generated by NAPI-RS */
+
+import { createRequire } from 'module'
+
+const require = createRequire(import.meta.url)
+const binding = require('${bindingPath}')
+
+const cases = [
+${cases
+  .map(
+    ({ path, name, args }) =>
+      `  [${JSON.stringify(path)}, ${JSON.stringify(
+        name,
+      )}, () => binding.${path}(${args})],`,
+  )
+  .join('\n')}
+]
+
+// milliseconds of each case, the cases matching \`process.argv[2]\` are run if it's passed
+const duration = Number(process.env.NAPI_RS_BENCH_DURATION || 1000)
+const filter = process.argv[2]
+
+async function measure(fn) {
+  // called once before timing, the promises of the async fns are awaited one by one
+  const first = fn()
+  const isAsync = first instanceof Promise
+  await first
+  const batch = isAsync
+    ? async (iterations) => {
+        for (let i = 0; i < iterations; i++) {
+          await fn()
+        }
+      }
+    : (iterations) => {
+        for (let i = 0; i < iterations; i++) {
+          fn()
+        }
+      }
+  // the batches of at least 10ms, so the resolution of the timer doesn't matter
+  let iterations = 1
+  const samples = []
+  const end = Date.now() + duration
+  while (Date.now() < end || samples.length < 5) {
+    const start = process.hrtime.bigint()
+    await batch(iterations)
+    const elapsed = Number(process.hrtime.bigint() - start) / 1e9
+    if (elapsed < 0.01) {
+      iterations *= 2
+      continue
+    }
+    samples.push(iterations / elapsed)
+  }
+  const mean = samples.reduce((acc, cur) => acc + cur, 0) / samples.length
+  const variance =
+    samples.reduce((acc, cur) => acc + (cur - mean) ** 2, 0) / (samples.length - 1)
+  const margin = ((1.96 * Math.sqrt(variance / samples.length)) / mean) * 100
+  return { ops: Math.round(mean), margin: margin.toFixed(2), samples: samples.length }
+}
+
+let failed = 0
+for (const [path, name, fn] of cases) {
+  if (filter && !\`\${path}#\${name}\`.includes(filter)) {
+    continue
+  }
+  try {
+    const { ops, margin, samples } = await measure(fn)
+    console.info(\`\${path}#\${name} x \${ops} ops/sec ±\${margin}% (\${samples} runs sampled)\`)
+  } catch (e) {
+    failed++
+    console.error(\`\${path}#\${name} failed:\`, e)
+  }
+}
+if (failed) {
+  process.exit(1)
+}
+`
+}
+
+export class BenchCommand extends Command {
+  static usage = Command.Usage({
+    description:
+      'Build the native module in release mode, then run the #[napi(bench)] cases',
+    details: `The cases are declared by ${chalk.green(
+      '#[napi(bench(args = "1, 2"))]',
+    )} on the fns, the harness is generated by ${chalk.green(
+      'napi build --bench-harness',
+    )}`,
+    examples: [
+      ['Run all the cases', 'napi bench'],
+      ['Run the cases of sum', 'napi bench sum'],
+    ],
+  })
+
+  static paths = [['bench']]
+
+  configFileName?: string = Option.String('--config,-c', {
+    description: 'napi config path, package.json, napi.toml or .naprc',
+  })
+
+  buildArgs = Option.String('--build-args', '', {
+    description: `Flags passed to ${chalk.green('napi build')}`,
+  })
+
+  harness = Option.String('--harness', {
+    description:
+      'Path of the generated harness, relative to cwd, in the tmp dir by default',
+  })
+
+  filter = Option.String({ required: false })
+
+  async execute() {
+    const harness =
+      this.harness ?? join(tmpdir(), `napi-rs-bench-${process.pid}.mjs`)
+    const buildArgs = [
+      process.argv[1],
+      'build',
+      '--platform',
+      '--release',
+      '--bench-harness',
+      harness,
+      ...(this.configFileName ? ['--config', this.configFileName] : []),
+      ...this.buildArgs.split(' ').filter(Boolean),
+    ]
+    const buildCode = await run(process.execPath, buildArgs)
+    if (buildCode !== 0) {
+      console.error(chalk.red('Build failed, skip the benchmarks'))
+      return buildCode
+    }
+    return run(process.execPath, [
+      harness,
+      ...(this.filter ? [this.filter] : []),
+    ])
+  }
+}
+
+function run(command: string, args: string[]) {
+  debug(`Run ${chalk.green([command, ...args].join(' '))}`)
+  return new Promise<number>((resolve, reject) => {
+    spawn(command, args, { stdio: 'inherit', env: process.env })
+      .on('close', (code) => resolve(code ?? 1))
+      .on('error', reject)
+  })
+}
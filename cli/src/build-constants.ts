@@ -0,0 +1,70 @@
+import chalk from 'chalk'
+
+/**
+ * `napi.constants` of the napi config, overridden by the `constants` of `napi.target.[triple]`.
+ * Read by `napi::build_constant!("NAME")` in Rust and exported as the frozen `BUILD_CONSTANTS` object by the JS binding,
+ * so both sides of the build get the same values.
+ */
+export type BuildConstants = Record<string, string | number | boolean>
+
+export const BUILD_CONSTANTS_EXPORT = 'BUILD_CONSTANTS'
+
+// read by `napi::build_constant!`
+const ENV_PREFIX = 'NAPI_RS_CONSTANT_'
+
+export function resolveBuildConstants(
+  ...configs: (BuildConstants | undefined)[]
+): BuildConstants {
+  const constants: BuildConstants = Object.assign({}, ...configs)
+  for (const [name, value] of Object.entries(constants)) {
+    if (!/^[A-Z_][A-Z0-9_]*$/.test(name)) {
+      throw new TypeError(
+        `Invalid build constant name ${chalk.yellowBright(
+          name,
+        )}, expected the UPPER_SNAKE_CASE names like ${chalk.green(
+          'API_URL',
+        )}`,
+      )
+    }
+    if (!['string', 'number', 'boolean'].includes(typeof value)) {
+      throw new TypeError(
+        `The build constant ${chalk.green(
+          name,
+        )} must be a string, number or boolean, received ${chalk.yellowBright(
+          JSON.stringify(value),
+        )}`,
+      )
+    }
+  }
+  return constants
+}
+
+/**
+ * Passed to cargo, `env!` of rustc rebuilds the crate when any of them changes.
+ */
+export function buildConstantsEnv(constants: BuildConstants) {
+  return Object.fromEntries(
+    Object.entries(constants).map(([name, value]) => [
+      `${ENV_PREFIX}${name}`,
+      String(value),
+    ]),
+  )
+}
+
+export function createBuildConstantsBinding(constants: BuildConstants) {
+  return `const ${BUILD_CONSTANTS_EXPORT} = Object.freeze(${JSON.stringify(
+    constants,
+    null,
+    2,
+  )})\n`
+}
+
+/**
+ * Typed by the primitive types instead of the literals, the values could differ between the targets sharing the `.d.ts`.
+ */
+export function createBuildConstantsDts(constants: BuildConstants) {
+  const entries = Object.entries(constants)
+    .map(([name, value]) => `  readonly ${name}: ${typeof value}\n`)
+    .join('')
+  return `export const ${BUILD_CONSTANTS_EXPORT}: {\n${entries}}\n`
+}
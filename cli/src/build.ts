@@ -1,22 +1,89 @@
 import { execSync } from 'child_process'
-import { existsSync, mkdirSync } from 'fs'
+import { existsSync, mkdirSync, writeFileSync } from 'fs'
 import { tmpdir } from 'os'
-import { join, parse, sep } from 'path'
+import { dirname, isAbsolute, join, parse, relative, resolve, sep } from 'path'
 
 import { Instance } from 'chalk'
 import { Command, Option } from 'clipanion'
 import envPaths from 'env-paths'
-import { groupBy } from 'lodash-es'
-import toml from 'toml'
+import { groupBy, partition } from 'lodash-es'
 
-import { getNapiConfig } from './consts'
+import { allocatorFeature, ALLOCATORS } from './allocator'
+import { generateBenchHarness } from './bench'
+import {
+  DEFAULT_ANDROID_API_LEVEL,
+  findAndroidNdk,
+  getAndroidToolchainEnv,
+} from './android-ndk'
+import {
+  BUILD_CONSTANTS_EXPORT,
+  BuildConstants,
+  buildConstantsEnv,
+  createBuildConstantsBinding,
+  createBuildConstantsDts,
+  resolveBuildConstants,
+} from './build-constants'
+import {
+  findCargoPackage,
+  findCdylibTarget,
+  getCargoMetadata,
+  getResolvedFeatures,
+  resolveTargetDir,
+} from './cargo-metadata'
+import { createCompatShims } from './compat-shims'
+import { getNapiConfig, TargetConfig } from './consts'
+import { resolveCpuVariant } from './cpu-variants'
 import { debugFactory } from './debug'
-import { createJsBinding } from './js-binding-template'
-import { getDefaultTargetTriple, parseTriple } from './parse-triple'
+import {
+  findTypeDefConflicts,
+  MESSAGE_FORMATS,
+  printNapiMessage,
+  runCargoWithDiagnostics,
+  TypeDefWarning,
+} from './diagnostics'
+import { buildInDocker, DOCKER_BUILD_CONFIGS } from './docker'
+import { runCargoWithAnnotations } from './github-annotations'
+import {
+  createDenoEntry,
+  createJsBinding,
+  JsBindingOptions,
+} from './js-binding-template'
+import {
+  enableJsonOutput,
+  JsonOption,
+  logStdio,
+  printJson,
+} from './json-output'
+import {
+  checkMixinClasses,
+  createMixinDts,
+  createMixinsBinding,
+  Mixin,
+  resolveMixins,
+} from './mixins'
+import { requiredNapiVersion, resolveNodeHeaders } from './node-headers'
+import {
+  isVendored,
+  OfflineManifest,
+  readLockedCrates,
+  vendorConfigFlags,
+} from './offline'
+import { getHostTargetTriple, parseTriple } from './parse-triple'
+import { generateSmokeTest } from './smoke-tests'
+import { resolveTargetFeatures } from './target-features'
+import { applyTemplate } from './templates'
+import {
+  checkToolchain,
+  formatToolchainProblems,
+  hasCommand,
+  isLinkerConfigured,
+} from './toolchain-check'
+import { generateTypeTests } from './typetests'
 import {
   copyFileAsync,
   mkdirAsync,
   readFileAsync,
+  toLongPath,
   unlinkAsync,
   writeFileAsync,
 } from './utils'
@@ -77,11 +144,21 @@ export class BuildCommand extends Command {
   })
 
   configFileName?: string = Option.String('--config,-c', {
-    description: `napi config path, only JSON format accepted. Default to ${chalk.underline(
+    description: `napi config path, package.json, napi.toml or .naprc. Default to ${chalk.underline(
       chalk.green('package.json'),
     )}`,
   })
 
+  binaryName?: string = Option.String('--binary-name', {
+    description: `Build one of the ${chalk.green(
+      'napi.binaries',
+    )} in the napi config instead of ${chalk.green(
+      'napi.name',
+    )}, the ${chalk.green(
+      '.node',
+    )} file is named by it and the JS binding loads it from the subpath of the platform packages`,
+  })
+
   cargoName?: string = Option.String('--cargo-name', {
     description: `Override the ${chalk.green(
       'name',
@@ -100,6 +177,14 @@ export class BuildCommand extends Command {
     description: `Bypass to ${chalk.green('cargo build --features')}`,
   })
 
+  allocator?: string = Option.String('--allocator', {
+    description: `Global allocator of the addon, one of ${ALLOCATORS.join(
+      ', ',
+    )}. Enables the ${chalk.green('allocator_*')} feature of ${chalk.green(
+      'napi',
+    )}`,
+  })
+
   bin?: string = Option.String('--bin', {
     description: `Bypass to ${chalk.green('cargo build --bin')}`,
   })
@@ -110,6 +195,30 @@ export class BuildCommand extends Command {
     )} file, relative to cwd`,
   })
 
+  dtsSort = Option.Boolean('--dts-sort', true, {
+    description: `Sort the declarations of ${chalk.green(
+      '.d.ts',
+    )} by kind and name, so they don't depend on the compilation order. Disable by ${chalk.green(
+      '--no-dts-sort',
+    )}`,
+  })
+
+  constEnum = Option.Boolean('--const-enum', false, {
+    description: `Declare the enums as ${chalk.green(
+      'export const enum',
+    )}, inlined by the TypeScript compiler, instead of ${chalk.green(
+      'export enum',
+    )}`,
+  })
+
+  dtsHeader?: string = Option.String('--dts-header', {
+    description: `File of the banner and imports replacing the default header of ${chalk.green(
+      '.d.ts',
+    )}, relative to cwd. Could also be set by ${chalk.green(
+      'napi.dtsHeader',
+    )} in the napi config`,
+  })
+
   project = Option.String('-p', {
     description: `Bypass to ${chalk.green('cargo -p')}`,
   })
@@ -119,11 +228,27 @@ export class BuildCommand extends Command {
   })
 
   jsBinding = Option.String('--js', 'index.js', {
-    description: `Path to the JS binding file, pass ${chalk.underline(
+    description: `Path to the JS binding file, the dirs of it are created if missing, pass ${chalk.underline(
       chalk.yellow('false'),
+    )} or ${chalk.green(
+      '--no-js',
     )} to disable it. Only affect if ${chalk.green('--target')} is specified.`,
   })
 
+  noJsBinding = Option.Boolean('--no-js', false, {
+    description: `Don't generate the JS binding file, for the hand-written loaders`,
+  })
+
+  bun = Option.Boolean('--bun', false, {
+    description: `Support Bun in the JS binding, the libc of Linux is detected by the filesystem while running in Bun`,
+  })
+
+  deno = Option.Boolean('--deno', false, {
+    description: `Write the ESM entry for Deno next to the JS binding, named ${chalk.green(
+      '[js].deno.mjs',
+    )}, which requires the JS binding by ${chalk.green('createRequire')}`,
+  })
+
   jsPackageName = Option.String('--js-package-name', {
     description: `Package name in generated js binding file, Only affect if ${chalk.green(
       '--target',
@@ -137,6 +262,14 @@ export class BuildCommand extends Command {
     )} file`,
   })
 
+  cargoTargetDir?: string = Option.String('--target-dir', {
+    description: `Directory of the cargo build artifacts, passed to ${chalk.green(
+      'cargo build',
+    )}. Default to the target dir of ${chalk.green(
+      'cargo metadata',
+    )}, which respects ${chalk.green('CARGO_TARGET_DIR')}`,
+  })
+
   pipe?: string = Option.String('--pipe', {
     description: `Pipe [${chalk.green(
       '.js/.ts',
@@ -176,55 +309,171 @@ export class BuildCommand extends Command {
     )} ${chalk.green('--zig-abi-suffix=2.17')}`,
   })
 
+  electronVersion?: string = Option.String('--electron-version', {
+    description: `Build for the specified ${chalk.green(
+      'Electron',
+    )} version. Buffers are copied instead of shared on Electron >= 21, and ${chalk.green(
+      '[name].[platform].electron.node',
+    )} is generated with ${chalk.green('--platform')}`,
+  })
+
+  variant?: string = Option.String('--variant', {
+    description: `Build the variant in ${chalk.green(
+      'napi.variants',
+    )} with the CPU features of it enabled, ${chalk.green(
+      '[name].[platform].[variant].node',
+    )} is generated with ${chalk.green(
+      '--platform',
+    )} and loaded by the JS binding if the CPU supports them`,
+  })
+
+  nodeHeaders?: string = Option.String('--node-headers', {
+    description: `The headers of the custom Node.js or the embedded runtime, a dir or the url of the headers tarball. The ${chalk.green(
+      'NAPI_VERSION',
+    )} exported is validated against the napi features, on Windows the addon links with ${chalk.green(
+      'node.lib',
+    )} of it`,
+  })
+
+  androidApiLevel = Option.String(
+    '--android-api-level',
+    `${DEFAULT_ANDROID_API_LEVEL}`,
+    {
+      description: `The Android API level of the ${chalk.green(
+        'NDK',
+      )} clang wrapper used as linker while targeting Android`,
+    },
+  )
+
+  typeTests?: string = Option.String('--typetests', {
+    description: `Generate ${chalk.green(
+      'expectTypeOf',
+    )} assertions for the exported API into the file, relative to cwd. ${chalk.green(
+      'tsc',
+    )} checks the generated ${chalk.green('.d.ts')} with it`,
+  })
+
+  benchHarness?: string = Option.String('--bench-harness', {
+    description: `Generate an ES module into the file, relative to cwd, running the ${chalk.green(
+      '#[napi(bench)]',
+    )} cases of the fns. Run by ${chalk.green('napi bench')}`,
+  })
+
+  smokeTest?: string = Option.String('--smoke-test', {
+    description: `Generate an ES module into the file, relative to cwd, asserting the ${chalk.green(
+      'typeof',
+    )} of everything exported by the ${chalk.green(
+      '.d.ts',
+    )} at runtime. Run it with ${chalk.green('node')} on each platform`,
+  })
+
+  autoInstallTarget?: boolean = Option.Boolean('--auto-install-target', {
+    description: `Run ${chalk.green(
+      'rustup target add',
+    )} if the std of ${chalk.green(
+      '--target',
+    )} is not installed. Could also be enabled by ${chalk.green(
+      'napi.autoInstallTarget',
+    )} in the napi config`,
+  })
+
+  useCache?: boolean = Option.Boolean('--use-cache', {
+    description: `Compile with ${chalk.green('sccache')} as ${chalk.green(
+      'RUSTC_WRAPPER',
+    )} if it's installed. Could also be enabled by ${chalk.green(
+      'napi.useCache',
+    )} in the napi config`,
+  })
+
+  useDocker = Option.Boolean('--use-docker', false, {
+    description: `Build inside the docker image of the target used by the generated GitHub Actions workflow, supports ${Object.keys(
+      DOCKER_BUILD_CONFIGS,
+    )
+      .map((target) => chalk.green(target))
+      .join(', ')}`,
+  })
+
   isStrip = Option.Boolean(`--strip`, false, {
     description: `${chalk.green('Strip')} the library for minimum file size`,
   })
 
+  offline = Option.Boolean('--offline', false, {
+    description: `Build without network access, with the crates vendored by ${chalk.green(
+      'cargo vendor',
+    )} or in the cargo cache. Writes ${chalk.green(
+      'napi-offline-manifest.json',
+    )} of everything that would have been fetched into the target dir`,
+  })
+
+  messageFormat?: string = Option.String('--message-format', {
+    description: `Print the cargo messages and the warnings of the type generation as JSON lines on stdout, only ${chalk.green(
+      'json-diagnostic',
+    )} is supported`,
+  })
+
+  json = JsonOption()
+
   async execute() {
+    if (this.messageFormat !== undefined) {
+      if (!MESSAGE_FORMATS.includes(this.messageFormat)) {
+        throw new TypeError(
+          `Unsupported message format ${chalk.yellowBright(
+            this.messageFormat,
+          )}, expected one of ${MESSAGE_FORMATS.join(', ')}`,
+        )
+      }
+      if (this.json) {
+        throw new TypeError(
+          `${chalk.green('--json')} can't be used with ${chalk.green(
+            '--message-format',
+          )}`,
+        )
+      }
+    }
+    if (this.json || this.messageFormat) {
+      enableJsonOutput()
+    }
+    if (this.offline) {
+      if (this.useDocker) {
+        throw new TypeError(
+          `${chalk.green('--use-docker')} pulls the image and can't be used with ${chalk.green(
+            '--offline',
+          )}`,
+        )
+      }
+      // read by the other commands spawned by the build, e.g. the hooks
+      process.env.NAPI_RS_OFFLINE = 'true'
+    }
+    if (this.useDocker) {
+      if (!this.targetTripleDir) {
+        throw new TypeError(`${chalk.green('--use-docker')} requires --target`)
+      }
+      // forward the flags to the cli in the container
+      const args = process.argv
+        .slice(process.argv.indexOf('build') + 1)
+        .filter((arg) => arg !== '--use-docker')
+      buildInDocker(this.targetTripleDir, args, process.cwd())
+      return
+    }
+
     const cwd = this.cargoCwd
       ? join(process.cwd(), this.cargoCwd)
       : process.cwd()
 
-    let tomlContentString: string
-    let tomlContent: any
-    try {
-      debug('Start read toml')
-      tomlContentString = await readFileAsync(join(cwd, 'Cargo.toml'), 'utf-8')
-    } catch {
+    if (!existsSync(toLongPath(join(cwd, 'Cargo.toml')))) {
       throw new TypeError(`Could not find Cargo.toml in ${cwd}`)
     }
 
-    try {
-      debug('Start parse toml')
-      tomlContent = toml.parse(tomlContentString)
-    } catch {
-      throw new TypeError('Could not parse the Cargo.toml')
-    }
-
-    let cargoPackageName: string
-    if (tomlContent.package?.name) {
-      cargoPackageName = tomlContent.package.name
-    } else if (this.cargoName) {
-      cargoPackageName = this.cargoName
-    } else {
-      throw new TypeError('No package.name field in Cargo.toml')
-    }
-
-    const cargoMetadata = JSON.parse(
-      execSync('cargo metadata --format-version 1', {
-        stdio: 'pipe',
-        maxBuffer: 1024 * 1024 * 10,
-      }).toString('utf8'),
-    )
-    const packages = cargoMetadata.packages
-    const cargoPackage = packages.find(
-      (p: { name: string }) => p.name === cargoPackageName,
-    )
+    debug('Start read cargo metadata')
+    const cargoMetadata = getCargoMetadata(cwd)
+    const cargoPackage = findCargoPackage(cargoMetadata, cwd, this.project)
+    const cargoPackageName = cargoPackage.name
+    debug(`Cargo package: ${chalk.green(cargoPackageName)}`)
     if (
       !this.bin &&
-      cargoPackage?.targets?.length === 1 &&
-      cargoPackage?.targets[0].kind.length === 1 &&
-      cargoPackage?.targets[0].kind[0] === 'bin'
+      cargoPackage.targets.length === 1 &&
+      cargoPackage.targets[0].kind.length === 1 &&
+      cargoPackage.targets[0].kind[0] === 'bin'
     ) {
       this.bin = cargoPackageName
     }
@@ -233,23 +482,120 @@ export class BuildCommand extends Command {
     const targetFlag = this.targetTripleDir
       ? `--target ${this.targetTripleDir}`
       : ''
-    const featuresFlag = this.features ? `--features ${this.features}` : ''
     const binFlag = this.bin ? `--bin ${this.bin}` : ''
     const triple = this.targetTripleDir
       ? parseTriple(this.targetTripleDir)
-      : getDefaultTargetTriple(
-          execSync('rustup show active-toolchain', {
-            env: process.env,
-          }).toString('utf8'),
+      : getHostTargetTriple()
+    const allocatorFeatureName = this.allocator
+      ? allocatorFeature(cargoPackage, this.allocator, triple)
+      : null
+    const targetFeatures = resolveTargetFeatures(
+      getNapiConfig(this.configFileName).features,
+      triple.raw,
+    )
+    if (this.variant && (this.bin || !this.appendPlatformToFilename)) {
+      throw new TypeError(
+        `${chalk.green('--variant')} requires ${chalk.green(
+          '--platform',
+        )}, the JS binding loads ${chalk.green(
+          '[name].[platform].[variant].node',
+        )}`,
+      )
+    }
+    const cpuVariantFlag = this.variant
+      ? resolveCpuVariant(
+          getNapiConfig(this.configFileName).variants,
+          this.variant,
+          triple.raw,
         )
+      : null
+    if (targetFeatures.length) {
+      debug(
+        `Features of ${triple.raw} in napi config: ${chalk.green(
+          targetFeatures.join(','),
+        )}`,
+      )
+    }
+    const featuresFlag = [
+      this.features,
+      allocatorFeatureName,
+      targetFeatures.join(','),
+    ]
+      .filter((features) => Boolean(features))
+      .map((features) => `--features ${features}`)
+      .join(' ')
     debug(`Current triple is: ${chalk.green(triple.raw)}`)
-    const pFlag = this.project ? `-p ${this.project}` : ''
+    const missingRustTargets: string[] = []
+    if (this.targetTripleDir && !this.targetTripleDir.endsWith('.json')) {
+      const autoInstallTarget =
+        !this.offline &&
+        (this.autoInstallTarget ??
+          getNapiConfig(this.configFileName).autoInstallTarget)
+      if (!ensureRustTarget(this.targetTripleDir, cwd, autoInstallTarget)) {
+        missingRustTargets.push(this.targetTripleDir)
+      }
+    }
+    if (this.targetTripleDir && !this.targetTripleDir.endsWith('.json')) {
+      // fail before cargo compiles the whole crate graph and then can't link it
+      const toolchainProblems = checkToolchain(triple, {
+        platform: process.platform,
+        arch: process.arch,
+        useZig: this.useZig,
+        env: process.env,
+        linkerConfigured: isLinkerConfigured(triple.raw, cwd),
+        hasCommand,
+      })
+      if (toolchainProblems.length) {
+        throw new Error(formatToolchainProblems(triple.raw, toolchainProblems))
+      }
+    }
+    const targetDirectory = resolveTargetDir(
+      cargoMetadata,
+      cwd,
+      this.cargoTargetDir,
+      this.cargoFlags,
+    )
+    debug(`Target dir: ${chalk.green(targetDirectory)}`)
+    const targetDirFlag = this.cargoTargetDir
+      ? `--target-dir "${targetDirectory}"`
+      : ''
+    const offlineFlags = this.offline
+      ? ['--offline', ...vendorConfigFlags(cargoMetadata.workspace_root)]
+      : []
+    // the package found in the virtual workspace is built by `-p` as well
+    const pFlag =
+      this.project ||
+      resolve(cargoPackage.manifest_path) !== resolve(cwd, 'Cargo.toml')
+        ? `-p ${cargoPackageName}`
+        : ''
+    const { binaryName: mainBinaryName, binaryNames } = getNapiConfig(
+      this.configFileName,
+    )
+    const binaryName = this.binaryName ?? mainBinaryName
+    if (!binaryNames.includes(binaryName)) {
+      throw new TypeError(
+        `${chalk.yellowBright(
+          binaryName,
+        )} is not in the binaries of the napi config, add it to ${chalk.green(
+          'napi.binaries',
+        )}, expected one of ${binaryNames.join(', ')}`,
+      )
+    }
+    const targetConfig: TargetConfig =
+      getNapiConfig(this.configFileName).targetConfigs[triple.raw] ?? {}
+    const buildConstants = resolveBuildConstants(
+      getNapiConfig(this.configFileName).constants,
+      targetConfig.constants,
+    )
     const externalFlags = [
       releaseFlag,
       targetFlag,
       featuresFlag,
       binFlag,
       pFlag,
+      targetDirFlag,
+      ...offlineFlags,
+      ...(targetConfig.cargoFlags ?? []),
       this.cargoFlags,
     ]
       .filter((flag) => Boolean(flag))
@@ -258,6 +604,29 @@ export class BuildCommand extends Command {
     const intermediateTypeFile = join(tmpdir(), `type_def.${Date.now()}.tmp`)
     debug(`Run ${chalk.green(cargoCommand)}`)
     const additionalEnv = {}
+    let offlineManifestPath: string | null = null
+    if (this.offline) {
+      additionalEnv['CARGO_NET_OFFLINE'] = 'true'
+      const offlineManifest: OfflineManifest = {
+        target: triple.raw,
+        crates: readLockedCrates(cargoMetadata.workspace_root),
+        vendored: isVendored(cargoMetadata.workspace_root),
+        rustTargets: missingRustTargets,
+      }
+      mkdirSync(toLongPath(targetDirectory), {
+        recursive: true,
+      })
+      offlineManifestPath = join(targetDirectory, 'napi-offline-manifest.json')
+      writeFileSync(
+        toLongPath(offlineManifestPath),
+        JSON.stringify(offlineManifest, null, 2),
+      )
+      console.info(
+        `Write the offline manifest of ${
+          offlineManifest.crates.length
+        } crates to ${chalk.green(offlineManifestPath)}`,
+      )
+    }
     if (
       triple.arch === 'ia32' &&
       triple.platform === 'win32' &&
@@ -280,6 +649,11 @@ export class BuildCommand extends Command {
       }
     }
 
+    if (cpuVariantFlag) {
+      debug(`Variant ${chalk.green(this.variant)}: ${cpuVariantFlag}`)
+      rustflags.push(cpuVariantFlag)
+    }
+
     if (this.isStrip && !rustflags.includes('-C link-arg=-s')) {
       rustflags.push('-C link-arg=-s')
     }
@@ -288,6 +662,69 @@ export class BuildCommand extends Command {
       additionalEnv['RUSTFLAGS'] = rustflags.join(' ')
     }
 
+    if (this.useCache ?? getNapiConfig(this.configFileName).useCache) {
+      const rustcWrapper = findRustcWrapper()
+      if (rustcWrapper) {
+        additionalEnv['RUSTC_WRAPPER'] = rustcWrapper
+      }
+    }
+
+    if (this.electronVersion) {
+      if (!/^\d+(\.\d+){0,2}$/.test(this.electronVersion)) {
+        throw new TypeError(
+          `Invalid Electron version [${this.electronVersion}], expected something like ${chalk.green(
+            '21.0.0',
+          )}`,
+        )
+      }
+      // read by napi crate at compile time, see `is_external_buffer_disallowed`
+      additionalEnv['NAPI_RS_ELECTRON_VERSION'] = this.electronVersion
+    }
+
+    if (this.nodeHeaders) {
+      const nodeHeaders = await resolveNodeHeaders(
+        this.nodeHeaders,
+        triple,
+        requiredNapiVersion(
+          getResolvedFeatures(
+            cwd,
+            'napi',
+            [
+              `--filter-platform ${triple.raw}`,
+              featuresFlag,
+              ...offlineFlags,
+            ].join(' '),
+          ),
+        ),
+      )
+      console.info(
+        `Build against N-API ${nodeHeaders.napiVersion} headers in ${chalk.green(
+          nodeHeaders.includeDir,
+        )}`,
+      )
+      // for the build scripts compiling C/C++ with the headers
+      additionalEnv['NAPI_RS_NODE_HEADERS'] = nodeHeaders.includeDir
+      if (nodeHeaders.libDir) {
+        // read by napi-build instead of the bundled `node.lib`
+        additionalEnv['NAPI_RS_NODE_LIB_DIR'] = nodeHeaders.libDir
+      }
+    }
+
+    if (triple.platform === 'android' && !this.useZig) {
+      const ndkPath = findAndroidNdk()
+      if (ndkPath) {
+        debug(`Android NDK: ${chalk.green(ndkPath)}`)
+        Object.assign(
+          additionalEnv,
+          getAndroidToolchainEnv(
+            triple.raw,
+            ndkPath,
+            Number(this.androidApiLevel),
+          ),
+        )
+      }
+    }
+
     if (this.useZig) {
       const zigTarget = `${ZIG_PLATFORM_TARGET_MAP[triple.raw]}${
         this.zigABIVersion ? `.${this.zigABIVersion}` : ''
@@ -357,30 +794,77 @@ export class BuildCommand extends Command {
       additionalEnv[`CARGO_TARGET_${envTarget}_LINKER`] = linkerWrapperShell
     }
 
-    execSync(cargoCommand, {
-      env: {
-        ...process.env,
-        ...additionalEnv,
-        TYPE_DEF_TMP_PATH: intermediateTypeFile,
-      },
-      stdio: 'inherit',
-      cwd,
-    })
-    const { binaryName, packageName } = getNapiConfig(this.configFileName)
+    if (Object.keys(buildConstants).length) {
+      debug(`Build constants: ${JSON.stringify(buildConstants)}`)
+      Object.assign(additionalEnv, buildConstantsEnv(buildConstants))
+    }
+
+    if (targetConfig.env) {
+      debug(
+        `Env of ${triple.raw} in napi config: ${JSON.stringify(
+          targetConfig.env,
+        )}`,
+      )
+      Object.assign(additionalEnv, targetConfig.env)
+    }
+
+    const { preBuild, postBuild } = getNapiConfig(this.configFileName)
+    const hookEnv = {
+      ...process.env,
+      NAPI_RS_TARGET: triple.raw,
+      NAPI_RS_PLATFORM: triple.platformArchABI,
+      NAPI_RS_PROFILE: this.isRelease ? 'release' : 'debug',
+    }
+    runBuildScripts('preBuild', preBuild, hookEnv)
+
+    const cargoEnv = {
+      ...process.env,
+      ...additionalEnv,
+      TYPE_DEF_TMP_PATH: intermediateTypeFile,
+    }
+    if (this.messageFormat) {
+      await runCargoWithDiagnostics(cargoCommand, { env: cargoEnv, cwd })
+    } else if (
+      process.env.GITHUB_ACTIONS === 'true' &&
+      !this.cargoFlags.includes('--message-format')
+    ) {
+      await runCargoWithAnnotations(
+        cargoCommand,
+        cargoMetadata.workspace_root,
+        { env: cargoEnv, cwd },
+      )
+    } else {
+      execSync(cargoCommand, {
+        env: cargoEnv,
+        stdio: logStdio(),
+        cwd,
+      })
+    }
+    const {
+      packageName,
+      platformTypes,
+      packageJsonPath,
+      dtsHeader,
+      dtsFormatter,
+      mixins: mixinsConfig,
+      variants,
+      compatShims,
+    } = getNapiConfig(this.configFileName)
     let cargoArtifactName = this.cargoName
     if (!cargoArtifactName) {
       if (this.bin) {
         cargoArtifactName = cargoPackageName
       } else {
-        cargoArtifactName = cargoPackageName.replace(/-/g, '_')
-      }
-
-      if (!this.bin && !tomlContent.lib?.['crate-type']?.includes?.('cdylib')) {
-        throw new TypeError(
-          `Missing ${chalk.green('crate-type = ["cdylib"]')} in ${chalk.green(
-            '[lib]',
-          )}`,
-        )
+        // `[lib] name` of the package, which could differ from the package name
+        const cdylibTarget = findCdylibTarget(cargoPackage)
+        if (!cdylibTarget) {
+          throw new TypeError(
+            `Missing ${chalk.green(
+              'crate-type = ["cdylib"]',
+            )} in ${chalk.green('[lib]')} of ${cargoPackageName}`,
+          )
+        }
+        cargoArtifactName = cdylibTarget.name.replace(/-/g, '_')
       }
     }
 
@@ -420,19 +904,15 @@ export class BuildCommand extends Command {
       }
     }
 
-    const targetRootDir = await findUp(cwd)
-
-    if (!targetRootDir) {
-      throw new TypeError('No target dir found')
-    }
-
     const targetDir = join(
       this.targetTripleDir,
       this.isRelease ? 'release' : 'debug',
     )
 
     const platformName = this.appendPlatformToFilename
-      ? `.${triple.platformArchABI}`
+      ? `.${triple.platformArchABI}${this.electronVersion ? '.electron' : ''}${
+          this.variant ? `.${this.variant}` : ''
+        }`
       : ''
 
     debug(`Platform name: ${platformName || chalk.green('[Empty]')}`)
@@ -444,7 +924,7 @@ export class BuildCommand extends Command {
 
     const parsedDist = parse(distModulePath)
 
-    if (parsedDist.dir && !existsSync(parsedDist.dir)) {
+    if (parsedDist.dir && !existsSync(toLongPath(parsedDist.dir))) {
       await mkdirAsync(parsedDist.dir, { recursive: true }).catch((e) => {
         console.warn(
           chalk.bgYellowBright(
@@ -455,19 +935,32 @@ export class BuildCommand extends Command {
     }
 
     const sourcePath = join(
-      targetRootDir,
-      'target',
+      targetDirectory,
       targetDir,
       `${cargoArtifactName}${libExt}`,
     )
 
-    if (existsSync(distModulePath)) {
+    if (!existsSync(toLongPath(sourcePath))) {
+      throw new TypeError(
+        `Could not find the artifact [${chalk.yellowBright(
+          sourcePath,
+        )}], pass ${chalk.green('--target-dir')} if cargo writes it elsewhere`,
+      )
+    }
+
+    if (existsSync(toLongPath(distModulePath))) {
       debug(`remove old binary [${chalk.yellowBright(distModulePath)}]`)
       await unlinkAsync(distModulePath)
     }
 
     debug(`Write binary content to [${chalk.yellowBright(distModulePath)}]`)
     await copyFileAsync(sourcePath, distModulePath)
+    const artifacts = [resolve(distModulePath)]
+
+    runBuildScripts('postBuild', postBuild, {
+      ...hookEnv,
+      NAPI_RS_ARTIFACT: resolve(distModulePath),
+    })
 
     if (!this.bin) {
       const dtsFilePath = join(
@@ -480,7 +973,7 @@ export class BuildCommand extends Command {
         const pipeCommand = `${this.pipe} ${dtsFilePath}`
         console.info(`Run ${chalk.green(pipeCommand)}`)
         try {
-          execSync(pipeCommand, { stdio: 'inherit', env: process.env })
+          execSync(pipeCommand, { stdio: logStdio(), env: process.env })
         } catch (e) {
           console.warn(
             chalk.bgYellowBright('Pipe the dts file to command failed'),
@@ -491,24 +984,143 @@ export class BuildCommand extends Command {
       const jsBindingFilePath =
         this.jsBinding &&
         this.jsBinding !== 'false' &&
+        !this.noJsBinding &&
         this.appendPlatformToFilename
           ? join(process.cwd(), this.jsBinding)
           : null
-      const idents = await processIntermediateTypeFile(
+      const denoEntryFilePath =
+        this.deno && jsBindingFilePath
+          ? join(
+              dirname(jsBindingFilePath),
+              `${parse(jsBindingFilePath).name}.deno.mjs`,
+            )
+          : null
+      const typeTestsFilePath = this.typeTests
+        ? join(process.cwd(), this.typeTests)
+        : null
+      const smokeTestFilePath = this.smokeTest
+        ? join(process.cwd(), this.smokeTest)
+        : null
+      const benchHarnessFilePath = this.benchHarness
+        ? resolve(this.benchHarness)
+        : null
+      const dtsHeaderPath = this.dtsHeader
+        ? resolve(this.dtsHeader)
+        : dtsHeader
+        ? join(parse(packageJsonPath).dir, dtsHeader)
+        : null
+      if (dtsHeaderPath && !existsSync(toLongPath(dtsHeaderPath))) {
+        throw new TypeError(
+          `The dts header [${chalk.yellowBright(dtsHeaderPath)}] is not existed`,
+        )
+      }
+      const mixins = resolveMixins(mixinsConfig, parse(packageJsonPath).dir)
+      const { idents, warnings } = await processIntermediateTypeFile(
         intermediateTypeFile,
         dtsFilePath,
+        typeTestsFilePath,
+        smokeTestFilePath
+          ? {
+              target: smokeTestFilePath,
+              binding: jsBindingFilePath ?? resolve(distModulePath),
+            }
+          : null,
+        benchHarnessFilePath
+          ? {
+              target: benchHarnessFilePath,
+              binding: jsBindingFilePath ?? resolve(distModulePath),
+            }
+          : null,
+        dtsHeaderPath ? await readFileAsync(dtsHeaderPath, 'utf8') : null,
+        this.dtsSort,
+        this.constEnum,
+        { binaryName, packageName },
+        mixins,
+        // exported by the JS binding
+        jsBindingFilePath ? buildConstants : {},
       )
+      for (const warning of warnings) {
+        if (this.messageFormat) {
+          printNapiMessage(warning)
+        } else {
+          debug(`${warning.code}: ${warning.message}`)
+        }
+      }
+      if (dtsFormatter && existsSync(dtsFilePath)) {
+        const formatCommand = `${dtsFormatter} "${dtsFilePath}"`
+        debug(`Run ${chalk.green(formatCommand)}`)
+        try {
+          execSync(formatCommand, { stdio: logStdio(), env: process.env })
+        } catch (e) {
+          throw new TypeError(
+            `Format the dts file by ${chalk.green(formatCommand)} failed`,
+          )
+        }
+      }
+      // APIs behind `#[cfg(target_os)]` only exist in the dts of the platform they are built on
+      if (
+        platformTypes &&
+        this.appendPlatformToFilename &&
+        existsSync(dtsFilePath)
+      ) {
+        const platformDtsFilePath = dtsFilePath.replace(
+          /(\.d)?\.ts$/,
+          `.${triple.platform}.d.ts`,
+        )
+        debug(
+          `Write platform dts to [${chalk.yellowBright(platformDtsFilePath)}]`,
+        )
+        await copyFileAsync(dtsFilePath, platformDtsFilePath)
+        artifacts.push(platformDtsFilePath)
+      }
       await writeJsBinding(
         binaryName,
         this.jsPackageName ?? packageName,
         jsBindingFilePath,
+        distModulePath,
         idents,
+        mixins,
+        buildConstants,
+        denoEntryFilePath
+          ? { target: denoEntryFilePath, dts: dtsFilePath }
+          : null,
+        {
+          bun: this.bun,
+          deno: this.deno,
+          subpath: binaryName !== mainBinaryName,
+          cpuVariants: variants,
+        },
+      )
+      for (const shim of createCompatShims(
+        compatShims,
+        parse(packageJsonPath).dir,
+        packageName,
+      )) {
+        debug(`Write compat shim to [${chalk.yellowBright(shim.path)}]`)
+        await mkdirAsync(dirname(shim.path), { recursive: true })
+        await writeFileAsync(shim.path, shim.code, 'utf8')
+        artifacts.push(shim.path)
+        if (shim.dts) {
+          await writeFileAsync(shim.dts.path, shim.dts.code, 'utf8')
+          artifacts.push(shim.dts.path)
+        }
+      }
+      artifacts.push(
+        ...[
+          dtsFilePath,
+          typeTestsFilePath,
+          smokeTestFilePath,
+          jsBindingFilePath,
+          denoEntryFilePath,
+        ].filter(
+          (path): path is string => path !== null && existsSync(path),
+        ),
       )
       if (this.pipe && jsBindingFilePath) {
         const pipeCommand = `${this.pipe} ${jsBindingFilePath}`
         console.info(`Run ${chalk.green(pipeCommand)}`)
         try {
-          execSync(pipeCommand, { stdio: 'inherit', env: process.env })
+          execSync(pipeCommand, { stdio: logStdio(), env: process.env })
         } catch (e) {
           console.warn(
             chalk.bgYellowBright('Pipe the js binding file to command failed'),
@@ -517,39 +1129,136 @@ export class BuildCommand extends Command {
         }
       }
     }
+
+    if (this.json) {
+      printJson({
+        target: triple.raw,
+        platform: triple.platformArchABI,
+        packageName,
+        binaryName,
+        artifacts,
+        // enabled by `napi.features` for the target, `--features` not included
+        features: targetFeatures,
+        variant: this.variant ?? null,
+        offlineManifest: offlineManifestPath,
+      })
+    }
+  }
+}
+
+/**
+ * cargo fails with `can't find crate for core` halfway through the build if the std of the target is missing.
+ * Returns `false` if it is still missing.
+ */
+function ensureRustTarget(
+  target: string,
+  cwd: string,
+  autoInstall: boolean,
+): boolean {
+  let installedTargets: string[]
+  try {
+    // respects `rust-toolchain.toml` in cwd
+    installedTargets = execSync('rustup target list --installed', {
+      cwd,
+      stdio: 'pipe',
+    })
+      .toString('utf8')
+      .split('\n')
+      .map((line) => line.trim())
+  } catch (e) {
+    debug(`rustup is not available, skip checking installed targets: ${e}`)
+    return true
   }
+  if (installedTargets.includes(target)) {
+    return true
+  }
+  if (!autoInstall) {
+    console.warn(
+      chalk.yellowBright(
+        `The std of ${target} is not installed, run ${chalk.green(
+          `rustup target add ${target}`,
+        )} or pass ${chalk.green('--auto-install-target')}`,
+      ),
+    )
+    return false
+  }
+  const command = `rustup target add ${target}`
+  debug(`Run ${chalk.green(command)}`)
+  execSync(command, { cwd, stdio: logStdio() })
+  return true
 }
 
-async function findUp(dir = process.cwd()): Promise<string | null> {
-  const dist = join(dir, 'target')
-  if (existsSync(dist)) {
-    return dir
+/**
+ * `sccache` if it's installed and `RUSTC_WRAPPER` is not set by user.
+ */
+function findRustcWrapper(): string | null {
+  if (process.env.RUSTC_WRAPPER) {
+    debug(`Use RUSTC_WRAPPER: ${chalk.green(process.env.RUSTC_WRAPPER)}`)
+    return null
   }
-  const dirs = dir.split(sep)
-  if (dirs.length < 2) {
+  try {
+    const sccacheVersion = execSync('sccache --version', { stdio: 'pipe' })
+    debug(`Use ${chalk.green(sccacheVersion.toString('utf8').trim())}`)
+    return 'sccache'
+  } catch (e) {
+    console.warn(
+      chalk.yellowBright(
+        `sccache is not installed, build without cache. Install it with ${chalk.green(
+          'cargo install sccache',
+        )}`,
+      ),
+    )
     return null
   }
-  dirs.pop()
-  return findUp(dirs.join(sep))
 }
 
-interface TypeDef {
-  kind: 'fn' | 'struct' | 'impl' | 'enum' | 'interface'
+function runBuildScripts(
+  hook: 'preBuild' | 'postBuild',
+  scripts: string[],
+  env: NodeJS.ProcessEnv,
+) {
+  for (const script of scripts) {
+    console.info(`Run ${hook} script ${chalk.green(script)}`)
+    try {
+      execSync(script, { stdio: logStdio(), env })
+    } catch {
+      throw new TypeError(`${hook} script [${script}] failed`)
+    }
+  }
+}
+
+export interface TypeDef {
+  kind: 'fn' | 'struct' | 'impl' | 'enum' | 'interface' | 'type' | 'const'
   name: string
   original_name?: string
+  generics?: string
   def: string
   js_mod?: string
+  // the parent class of `#[napi(extends)]`
+  extends?: string
   js_doc: string
+  warnings?: TypeDefWarning[]
+  // `#[napi(bench)]` of the fns
+  benches?: { name: string; args: string }[]
 }
 
-async function processIntermediateTypeFile(
+export async function processIntermediateTypeFile(
   source: string,
   target: string,
-): Promise<string[]> {
+  typeTestsTarget: string | null,
+  smokeTest: { target: string; binding: string } | null,
+  benchHarness: { target: string; binding: string } | null,
+  header: string | null,
+  sort: boolean,
+  constEnum: boolean,
+  templateData: { binaryName: string; packageName: string },
+  mixins: Mixin[] = [],
+  buildConstants: BuildConstants = {},
+): Promise<{ idents: string[]; warnings: TypeDefWarning[] }> {
   const idents: string[] = []
   if (!existsSync(source)) {
     debug(`do not find tmp type file. skip type generation`)
-    return idents
+    return { idents, warnings: [] }
   }
 
   const tmpFile = await readFileAsync(source, 'utf8')
@@ -559,19 +1268,31 @@ async function processIntermediateTypeFile(
     .filter(Boolean)
 
   if (!lines.length) {
-    return idents
+    return { idents, warnings: [] }
   }
 
-  const allDefs = lines.map((line) => JSON.parse(line) as TypeDef)
+  const parsedDefs = lines.map((line) => JSON.parse(line) as TypeDef)
+  const allDefs = sort ? sortTypeDefs(parsedDefs) : parsedDefs
+  const warnings = [
+    ...parsedDefs.flatMap((def) => def.warnings ?? []),
+    ...findTypeDefConflicts(parsedDefs),
+  ]
 
-  function convertDefs(defs: TypeDef[], nested = false): string {
+  // `depth` of the namespaces the defs are in
+  function convertDefs(defs: TypeDef[], depth = 0): string {
     const classes = new Map<
       string,
-      { def: string; js_doc: string; original_name?: string }
+      {
+        def: string
+        js_doc: string
+        original_name?: string
+        extends?: string
+      }
     >()
     const impls = new Map<string, string>()
     let dts = ''
-    const nest = nested ? 2 : 0
+    const nested = depth > 0
+    const nest = depth * 2
 
     defs.forEach((def) => {
       switch (def.kind) {
@@ -583,25 +1304,47 @@ async function processIntermediateTypeFile(
             original_name: def.original_name,
             def: def.def,
             js_doc: def.js_doc,
+            extends: def.extends,
           })
           break
-        case 'impl':
-          impls.set(def.name, `${def.js_doc}${def.def}`)
+        case 'impl': {
+          // a class could have more than one `#[napi] impl`, e.g. `impl Iterator for Class`
+          const members = `${def.js_doc}${def.def}`
+          const existing = impls.get(def.name)
+          impls.set(def.name, existing ? `${existing}\n${members}` : members)
           break
+        }
         case 'interface':
           dts +=
-            indentLines(`${def.js_doc}export interface ${def.name} {`, nest) +
+            indentLines(
+              `${def.js_doc}export interface ${def.name}${
+                def.generics ?? ''
+              } {`,
+              nest,
+            ) +
             '\n'
           dts += indentLines(def.def, nest + 2) + '\n'
           dts += indentLines(`}`, nest) + '\n'
           break
+        case 'type':
+          dts +=
+            indentLines(
+              `${def.js_doc}export type ${def.name}${def.generics ?? ''} =`,
+              nest,
+            ) + '\n'
+          dts += indentLines(def.def, nest + 2) + '\n'
+          break
         case 'enum':
           if (!nested) {
             idents.push(def.name)
           }
           dts +=
-            indentLines(`${def.js_doc}export const enum ${def.name} {`, nest) +
-            '\n'
+            indentLines(
+              `${def.js_doc}export ${constEnum ? 'const enum' : 'enum'} ${
+                def.name
+              } {`,
+              nest,
+            ) + '\n'
           dts += indentLines(def.def, nest + 2) + '\n'
           dts += indentLines(`}`, nest) + '\n'
           break
@@ -613,14 +1356,20 @@ async function processIntermediateTypeFile(
       }
     })
 
-    for (const [name, { js_doc, def, original_name }] of classes.entries()) {
+    for (const [
+      name,
+      { js_doc, def, original_name, extends: parent },
+    ] of classes.entries()) {
       const implDef = impls.get(name)
 
       if (original_name && name !== original_name) {
         dts += indentLines(`export type ${original_name} = ${name}\n`, nest)
       }
 
-      dts += indentLines(`${js_doc}export class ${name} {`, nest)
+      dts += indentLines(
+        `${js_doc}export class ${name}${parent ? ` extends ${parent}` : ''} {`,
+        nest,
+      )
 
       if (def) {
         dts += '\n' + indentLines(def, nest + 2)
@@ -637,28 +1386,63 @@ async function processIntermediateTypeFile(
       }
 
       dts += indentLines(`}`, nest) + '\n'
+
+      const mixin = nested
+        ? undefined
+        : mixins.find(({ className }) => className === name)
+      if (mixin) {
+        dts += createMixinDts(mixin, target)
+      }
     }
 
     return dts
   }
 
   const topLevelDef = convertDefs(allDefs.filter((def) => !def.js_mod))
+  checkMixinClasses(
+    mixins,
+    allDefs
+      .filter((def) => def.kind === 'struct' && !def.js_mod)
+      .map((def) => def.name),
+  )
 
-  const namespaceDefs = Object.entries(
-    groupBy(
-      allDefs.filter((def) => def.js_mod),
-      'js_mod',
-    ),
-  ).reduce((acc, [mod, defs]) => {
-    idents.push(mod)
-    return acc + `export namespace ${mod} {\n${convertDefs(defs, true)}}\n`
-  }, '')
-
-  const dtsHeader = `/* tslint:disable */
+  // the nested namespace `a.b` is the `export namespace b` in `export namespace a`
+  function convertNamespaces(defs: TypeDef[], depth = 0): string {
+    return Object.entries(
+      groupBy(defs, (def) => def.js_mod!.split('.')[depth]),
+    ).reduce((acc, [mod, modDefs]) => {
+      if (depth === 0) {
+        idents.push(mod)
+      }
+      const [ownDefs, nestedDefs] = partition(
+        modDefs,
+        (def) => def.js_mod!.split('.').length === depth + 1,
+      )
+      return (
+        acc +
+        indentLines(`export namespace ${mod} {`, depth * 2) +
+        '\n' +
+        convertDefs(ownDefs, depth + 1) +
+        convertNamespaces(nestedDefs, depth + 1) +
+        indentLines('}', depth * 2) +
+        '\n'
+      )
+    }, '')
+  }
+
+  const namespaceDefs = convertNamespaces(allDefs.filter((def) => def.js_mod))
+
+  const dtsHeader = applyTemplate(
+    'header.d.ts',
+    header !== null
+      ? `${header.trimEnd()}\n\n`
+      : `/* tslint:disable */
 /* eslint-disable */
 
 /* auto-generated by NAPI-RS */\n
-`
+`,
+    templateData,
+  )
 
   const externalDef =
     topLevelDef.indexOf('ExternalObject<') > -1 ||
@@ -671,13 +1455,105 @@ async function processIntermediateTypeFile(
 }\n`
       : ''
 
+  let buildConstantsDef = ''
+  if (Object.keys(buildConstants).length) {
+    if (idents.includes(BUILD_CONSTANTS_EXPORT)) {
+      throw new TypeError(
+        `${chalk.green(
+          BUILD_CONSTANTS_EXPORT,
+        )} is exported by the build constants, rename the #[napi] export of it`,
+      )
+    }
+    buildConstantsDef = createBuildConstantsDts(buildConstants)
+  }
+
   await unlinkAsync(source)
   await writeFileAsync(
     target,
-    dtsHeader + externalDef + topLevelDef + namespaceDefs,
+    dtsHeader + externalDef + buildConstantsDef + topLevelDef + namespaceDefs,
     'utf8',
   )
-  return idents
+  if (typeTestsTarget) {
+    const dtsImportPath = relative(parse(typeTestsTarget).dir, target)
+      .replace(/\.d\.ts$/, '')
+      .replaceAll('\\', '/')
+    debug(`Write type tests to [${chalk.yellowBright(typeTestsTarget)}]`)
+    await writeFileAsync(
+      typeTestsTarget,
+      generateTypeTests(
+        allDefs,
+        dtsImportPath.startsWith('.') ? dtsImportPath : `./${dtsImportPath}`,
+      ),
+      'utf8',
+    )
+  }
+  if (smokeTest) {
+    const bindingPath = relative(parse(smokeTest.target).dir, smokeTest.binding)
+      .split(sep)
+      .join('/')
+    debug(`Write smoke test to [${chalk.yellowBright(smokeTest.target)}]`)
+    await writeFileAsync(
+      smokeTest.target,
+      generateSmokeTest(
+        allDefs,
+        bindingPath.startsWith('.') ? bindingPath : `./${bindingPath}`,
+      ),
+      'utf8',
+    )
+  }
+  if (benchHarness) {
+    // absolute if the harness is on another drive, like the one in the tmp dir
+    const bindingPath = relative(
+      parse(benchHarness.target).dir,
+      benchHarness.binding,
+    )
+      .split(sep)
+      .join('/')
+    debug(`Write bench harness to [${chalk.yellowBright(benchHarness.target)}]`)
+    await writeFileAsync(
+      benchHarness.target,
+      generateBenchHarness(
+        allDefs,
+        bindingPath.startsWith('.') || isAbsolute(bindingPath)
+          ? bindingPath
+          : `./${bindingPath}`,
+      ),
+      'utf8',
+    )
+  }
+  return { idents, warnings }
+}
+
+const TYPE_DEF_KINDS: TypeDef['kind'][] = [
+  'const',
+  'interface',
+  'type',
+  'enum',
+  'fn',
+  'struct',
+  'impl',
+]
+
+function compareNames(a = '', b = '') {
+  // not `localeCompare`, the order must not depend on the locale
+  return a < b ? -1 : a > b ? 1 : 0
+}
+
+/**
+ * Sort by namespace, kind and name, the defs of the same name keep the order of the cargo output.
+ */
+function sortTypeDefs(defs: TypeDef[]) {
+  return defs
+    .map((def, index) => ({ def, index }))
+    .sort(
+      (a, b) =>
+        compareNames(a.def.js_mod, b.def.js_mod) ||
+        TYPE_DEF_KINDS.indexOf(a.def.kind) -
+          TYPE_DEF_KINDS.indexOf(b.def.kind) ||
+        compareNames(a.def.name, b.def.name) ||
+        a.index - b.index,
+    )
+    .map(({ def }) => def)
 }
 
 function indentLines(input: string, spaces: number) {
@@ -695,19 +1571,61 @@ async function writeJsBinding(
   localName: string,
   packageName: string,
   distFileName: string | null,
-  idents: string[],
+  binaryPath: string,
+  nativeIdents: string[],
+  mixins: Mixin[],
+  buildConstants: BuildConstants,
+  denoEntry: { target: string; dts: string } | null,
+  options: JsBindingOptions,
 ) {
+  const hasBuildConstants = Object.keys(buildConstants).length > 0
+  const idents = hasBuildConstants
+    ? [...nativeIdents, BUILD_CONSTANTS_EXPORT]
+    : nativeIdents
   if (distFileName && idents.length) {
-    const template = createJsBinding(localName, packageName)
-    const declareCodes = `const { ${idents.join(', ')} } = nativeBinding\n`
+    // the `.node` files are required relative to the binding in a sub-dir
+    const binaryDir =
+      relative(dirname(distFileName), resolve(dirname(binaryPath)))
+        .split(sep)
+        .join('/') || '.'
+    await mkdirAsync(dirname(distFileName), { recursive: true })
+    const template = createJsBinding(localName, packageName, binaryDir, options)
+    const declareCodes =
+      (nativeIdents.length
+        ? `const { ${nativeIdents.join(', ')} } = nativeBinding\n`
+        : '') +
+      (hasBuildConstants ? createBuildConstantsBinding(buildConstants) : '') +
+      createMixinsBinding(mixins, distFileName)
     const exportsCode = idents.reduce(
       (acc, cur) => `${acc}\nmodule.exports.${cur} = ${cur}`,
       '',
     )
     await writeFileAsync(
       distFileName,
-      template + declareCodes + exportsCode + '\n',
+      applyTemplate('index.js', template + declareCodes + exportsCode + '\n', {
+        binaryName: localName,
+        packageName,
+        exports: idents.join(', '),
+      }),
       'utf8',
     )
+    if (denoEntry) {
+      const relativeToEntry = (path: string) => {
+        const relativePath = relative(dirname(denoEntry.target), path)
+          .split(sep)
+          .join('/')
+        return relativePath.startsWith('.') ? relativePath : `./${relativePath}`
+      }
+      debug(`Write Deno entry to [${chalk.yellowBright(denoEntry.target)}]`)
+      await writeFileAsync(
+        denoEntry.target,
+        createDenoEntry(
+          relativeToEntry(distFileName),
+          relativeToEntry(denoEntry.dts),
+          idents,
+        ),
+        'utf8',
+      )
+    }
   }
 }
@@ -0,0 +1,142 @@
+import { execSync } from 'child_process'
+import { resolve } from 'path'
+
+import chalk from 'chalk'
+
+export interface CargoTarget {
+  name: string
+  kind: string[]
+  crate_types: string[]
+}
+
+export interface CargoDependency {
+  name: string
+  // the key in `Cargo.toml` if it's renamed by `package = "..."`
+  rename: string | null
+  // `null` for the normal dependencies
+  kind: 'dev' | 'build' | null
+}
+
+export interface CargoPackage {
+  id: string
+  name: string
+  manifest_path: string
+  targets: CargoTarget[]
+  dependencies?: CargoDependency[]
+}
+
+export interface CargoMetadata {
+  packages: CargoPackage[]
+  workspace_members: string[]
+  workspace_root: string
+  target_directory: string
+}
+
+export function getCargoMetadata(cwd: string): CargoMetadata {
+  return JSON.parse(
+    execSync('cargo metadata --format-version 1 --no-deps', {
+      cwd,
+      stdio: 'pipe',
+      maxBuffer: 1024 * 1024 * 10,
+    }).toString('utf8'),
+  )
+}
+
+export function findCdylibTarget(cargoPackage: CargoPackage) {
+  return (
+    cargoPackage.targets.find(({ kind }) => kind.includes('cdylib')) ?? null
+  )
+}
+
+/**
+ * The package selected by `-p`, or the package of `Cargo.toml` in `cwd`.
+ * In a virtual workspace, the only member with a `cdylib` target.
+ */
+export function findCargoPackage(
+  metadata: CargoMetadata,
+  cwd: string,
+  project?: string,
+): CargoPackage {
+  const members = metadata.packages.filter(({ id }) =>
+    metadata.workspace_members.includes(id),
+  )
+  if (project) {
+    const cargoPackage = members.find(({ name }) => name === project)
+    if (!cargoPackage) {
+      throw new TypeError(
+        `Package ${chalk.green(
+          project,
+        )} is not in the workspace, the members are ${members
+          .map(({ name }) => chalk.green(name))
+          .join(', ')}`,
+      )
+    }
+    return cargoPackage
+  }
+  const manifestPath = resolve(cwd, 'Cargo.toml')
+  const cargoPackage = members.find(
+    ({ manifest_path }) => resolve(manifest_path) === manifestPath,
+  )
+  if (cargoPackage) {
+    return cargoPackage
+  }
+  const addons = members.filter((member) => findCdylibTarget(member))
+  if (addons.length === 1) {
+    return addons[0]
+  }
+  throw new TypeError(
+    addons.length
+      ? `${manifestPath} is a virtual manifest, select one of ${addons
+          .map(({ name }) => chalk.green(name))
+          .join(', ')} by ${chalk.green('-p')}`
+      : `No package with ${chalk.green(
+          'crate-type = ["cdylib"]',
+        )} in the workspace of ${manifestPath}`,
+  )
+}
+
+/**
+ * The dir cargo writes the artifacts into, `--target-dir` of napi or in the cargo flags, relative to cwd.
+ * Otherwise the one of `cargo metadata`, which respects `CARGO_TARGET_DIR` and `build.target-dir` of `.cargo/config.toml`.
+ */
+export function resolveTargetDir(
+  metadata: CargoMetadata,
+  cwd: string,
+  targetDir?: string,
+  cargoFlags = '',
+) {
+  if (targetDir) {
+    return resolve(targetDir)
+  }
+  const flag = /--target-dir[= ]+("[^"]+"|\S+)/.exec(cargoFlags)
+  return flag
+    ? resolve(cwd, flag[1].replace(/"/g, ''))
+    : metadata.target_directory
+}
+
+/**
+ * The features of `crateName` resolved for the workspace, e.g. the `napiN` of `napi`.
+ * Requires the dependencies in the metadata, so it is not a part of `getCargoMetadata`.
+ */
+export function getResolvedFeatures(
+  cwd: string,
+  crateName: string,
+  flags = '',
+): string[] {
+  const { packages, resolve: resolved } = JSON.parse(
+    execSync(`cargo metadata --format-version 1 ${flags}`.trim(), {
+      cwd,
+      stdio: 'pipe',
+      maxBuffer: 1024 * 1024 * 50,
+    }).toString('utf8'),
+  ) as {
+    packages: CargoPackage[]
+    resolve: { nodes: { id: string; features: string[] }[] } | null
+  }
+  const ids = packages
+    .filter(({ name }) => name === crateName)
+    .map(({ id }) => id)
+  return (resolved?.nodes ?? [])
+    .filter(({ id }) => ids.includes(id))
+    .flatMap(({ features }) => features)
+}
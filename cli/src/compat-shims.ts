@@ -0,0 +1,98 @@
+import { existsSync } from 'fs'
+import { dirname, relative, resolve, sep } from 'path'
+
+import chalk from 'chalk'
+
+import { dtsOf } from './mixins'
+import { toLongPath } from './utils'
+
+/**
+ * `napi.compatShims` in the napi config, the old paths of the moved JS files to the new ones, relative to package.json,
+ * eg: `{ "binding.js": "lib/index.js" }` after the JS binding is moved by `--js lib/index.js`.
+ */
+export type CompatShims = Record<string, string>
+
+export interface CompatShim {
+  path: string
+  code: string
+  // `null` if the new file has no `.d.ts`
+  dts: { path: string; code: string } | null
+}
+
+export const COMPAT_SHIM_WARNING_CODE = 'NAPI_RS_COMPAT_SHIM'
+
+function toPosix(path: string) {
+  return path.split(sep).join('/')
+}
+
+function checkShimPath(packageJsonDir: string, path: unknown) {
+  const resolved =
+    typeof path === 'string' && /\.c?js$/.test(path)
+      ? resolve(packageJsonDir, path)
+      : null
+  if (!resolved || relative(packageJsonDir, resolved).startsWith('..')) {
+    throw new TypeError(
+      `The paths of ${chalk.green(
+        'napi.compatShims',
+      )} must be the .js or .cjs files in the package, received ${chalk.yellowBright(
+        String(path),
+      )}`,
+    )
+  }
+  return resolved
+}
+
+/**
+ * The files at the old paths re-exporting the new ones with a `DeprecationWarning`,
+ * so the deep imports and the bundler configs of the older versions keep working.
+ * The new files must be generated before, eg: the JS binding written by the same `napi build`.
+ */
+export function createCompatShims(
+  shims: CompatShims,
+  packageJsonDir: string,
+  packageName: string,
+): CompatShim[] {
+  return Object.entries(shims).map(([from, to]) => {
+    const path = checkShimPath(packageJsonDir, from)
+    const target = checkShimPath(packageJsonDir, to)
+    if (path === target) {
+      throw new TypeError(
+        `The compat shim ${chalk.yellowBright(from)} re-exports itself`,
+      )
+    }
+    if (!existsSync(toLongPath(target))) {
+      throw new TypeError(
+        `The new file [${chalk.yellowBright(
+          target,
+        )}] of the compat shim ${chalk.green(from)} is not existed`,
+      )
+    }
+    const relativeToShim = (file: string) => {
+      const relativePath = toPosix(relative(dirname(path), file))
+      return relativePath.startsWith('.') ? relativePath : `./${relativePath}`
+    }
+    const oldName = `${packageName}/${toPosix(relative(packageJsonDir, path))}`
+    const newName = `${packageName}/${toPosix(relative(packageJsonDir, target))}`
+    const code = `// ${oldName} is moved to ${newName}
+process.emitWarning(
+  '${oldName} is deprecated, require ${newName} instead',
+  { type: 'DeprecationWarning', code: '${COMPAT_SHIM_WARNING_CODE}' }
+)
+
+module.exports = require('${relativeToShim(target)}')
+`
+    const targetDts = dtsOf(target)
+    return {
+      path,
+      code,
+      dts: existsSync(toLongPath(targetDts))
+        ? {
+            path: dtsOf(path),
+            code: `// ${oldName} is deprecated, require ${newName} instead
+export * from '${relativeToShim(target).replace(/\.js$/, '')}'
+`,
+          }
+        : null,
+    }
+  })
+}
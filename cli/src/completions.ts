@@ -0,0 +1,373 @@
+import { Command, Option } from 'clipanion'
+
+import { SupportedPlatforms } from './parse-triple'
+
+export const SHELLS = ['bash', 'zsh', 'fish', 'powershell'] as const
+
+export type Shell = typeof SHELLS[number]
+
+/**
+ * The part of the clipanion `Definition` of a command used by the completions and the man page.
+ */
+export interface CommandDefinition {
+  path: string
+  description?: string
+  options: { definition: string; description?: string }[]
+}
+
+export interface CompletionOption {
+  names: string[]
+  takesValue: boolean
+  description: string
+}
+
+export interface CompletionCommand {
+  name: string
+  description: string
+  options: CompletionOption[]
+}
+
+// options completed with the target triples, by `rustup target list` when the completion runs
+const TARGET_OPTIONS: Record<string, string[]> = {
+  build: ['--target'],
+  new: ['--targets', '-t'],
+}
+
+export const ANSI_PATTERN = /\u001b\[[0-9;]*m/g
+
+/**
+ * The first sentence of the description without colors, the descriptions are colored by chalk when they are created.
+ */
+function summary(description = '') {
+  return description
+    .replace(ANSI_PATTERN, '')
+    .replace(/\s+/g, ' ')
+    .trim()
+    .split(/\.\s/)[0]
+    .replace(/\.$/, '')
+}
+
+export function toCompletionCommands(
+  definitions: CommandDefinition[],
+): CompletionCommand[] {
+  return definitions
+    .map(({ path, description, options }) => {
+      const words = path.split(' ').slice(1)
+      const end = words.findIndex((word) => !/^[a-z][\w-]*$/.test(word))
+      return {
+        name: words.slice(0, end === -1 ? words.length : end).join(' '),
+        description: summary(description),
+        options: options.map(({ definition, description }) => {
+          const [names, ...args] = definition.split(' ')
+          return {
+            names: names.split(','),
+            takesValue: args.length > 0,
+            description: summary(description),
+          }
+        }),
+      }
+    })
+    .filter(({ name }) => name)
+    .sort((a, b) => a.name.localeCompare(b.name))
+}
+
+export function isTargetOption(command: string, name: string) {
+  return (TARGET_OPTIONS[command] ?? []).includes(name)
+}
+
+function singleQuote(value: string) {
+  return `'${value.replace(/'/g, `'\\''`)}'`
+}
+
+function bash(commands: CompletionCommand[]) {
+  const cases = commands.map(({ name, options }) => {
+    const targetOptions = options
+      .flatMap(({ names }) => names)
+      .filter((option) => isTargetOption(name, option))
+    const valueOptions = options
+      .filter(({ takesValue }) => takesValue)
+      .flatMap(({ names }) => names)
+      .filter((option) => !isTargetOption(name, option))
+    const prevCases = [
+      targetOptions.length
+        ? `        ${targetOptions.join('|')})
+          COMPREPLY=($(compgen -W "$(_napi_targets)" -- "$cur"))
+          return
+          ;;`
+        : '',
+      valueOptions.length
+        ? `        ${valueOptions.join('|')})
+          COMPREPLY=($(compgen -f -- "$cur"))
+          return
+          ;;`
+        : '',
+    ].filter(Boolean)
+    return `    ${name})
+${
+  prevCases.length
+    ? `      case "$prev" in
+${prevCases.join('\n')}
+      esac
+`
+    : ''
+}      opts="${options.flatMap(({ names }) => names).join(' ')}"
+      ;;`
+  })
+  return `# bash completion of napi, generated by \`napi completions bash\`
+_napi_targets() {
+  local targets
+  targets="$(rustup target list 2>/dev/null | cut -d' ' -f1)"
+  if [ -z "$targets" ]; then
+    targets="${SupportedPlatforms.join(' ')}"
+  fi
+  echo "$targets"
+}
+
+_napi() {
+  local cur prev opts
+  cur="\${COMP_WORDS[COMP_CWORD]}"
+  prev="\${COMP_WORDS[COMP_CWORD-1]}"
+  COMPREPLY=()
+  if [ "$COMP_CWORD" -eq 1 ]; then
+    COMPREPLY=($(compgen -W "${commands
+      .map(({ name }) => name)
+      .join(' ')}" -- "$cur"))
+    return
+  fi
+  case "\${COMP_WORDS[1]}" in
+${cases.join('\n')}
+  esac
+  # the positional arguments are completed with the files by \`-o default\`
+  if [[ "$cur" == -* ]]; then
+    COMPREPLY=($(compgen -W "$opts" -- "$cur"))
+  fi
+}
+
+complete -o default -F _napi napi
+`
+}
+
+function zshDescription(description: string) {
+  return description.replace(/[[\]:\\]/g, '\\$&')
+}
+
+function zsh(commands: CompletionCommand[]) {
+  const cases = commands.map(({ name, options }) => {
+    const specs = options.flatMap(({ names, takesValue, description }) => {
+      const exclusion = names.length > 1 ? `(${names.join(' ')})` : ''
+      return names.map((option) => {
+        const action = !takesValue
+          ? ''
+          : isTargetOption(name, option)
+          ? ':target:_napi_targets'
+          : ':value:_files'
+        return `        ${singleQuote(
+          `${exclusion}${option}[${zshDescription(description)}]${action}`,
+        )} \\`
+      })
+    })
+    return `    ${name})
+      _arguments -s \\
+${specs.join('\n')}
+        '*:file:_files'
+      ;;`
+  })
+  return `#compdef napi
+# zsh completion of napi, generated by \`napi completions zsh\`
+
+_napi_targets() {
+  local -a targets
+  targets=(\${(f)"$(rustup target list 2>/dev/null | cut -d' ' -f1)"})
+  if (( \${#targets} == 0 )); then
+    targets=(${SupportedPlatforms.join(' ')})
+  fi
+  compadd -a targets
+}
+
+_napi() {
+  local -a commands
+  commands=(
+${commands
+  .map(
+    ({ name, description }) =>
+      `    ${singleQuote(`${name}:${description.replace(/:/g, '\\:')}`)}`,
+  )
+  .join('\n')}
+  )
+  if (( CURRENT == 2 )); then
+    _describe 'command' commands
+    return
+  fi
+  local command="\${words[2]}"
+  shift words
+  (( CURRENT-- ))
+  case "$command" in
+${cases.join('\n')}
+  esac
+}
+
+if [ "$funcstack[1]" = "_napi" ]; then
+  _napi "$@"
+else
+  compdef _napi napi
+fi
+`
+}
+
+function fishQuote(value: string) {
+  return `'${value.replace(/[\\']/g, '\\$&')}'`
+}
+
+function fish(commands: CompletionCommand[]) {
+  const lines = commands.flatMap(({ name, description, options }) => [
+    `complete -c napi -f -n __fish_use_subcommand -a ${name} -d ${fishQuote(
+      description,
+    )}`,
+    ...options.map(({ names, takesValue, description }) => {
+      const flags = names
+        .map((option) =>
+          option.startsWith('--')
+            ? `-l ${option.substring(2)}`
+            : `-s ${option.substring(1)}`,
+        )
+        .join(' ')
+      const value = !takesValue
+        ? ''
+        : names.some((option) => isTargetOption(name, option))
+        ? ` -x -a '(__napi_targets)'`
+        : ' -r -F'
+      const help = description ? ` -d ${fishQuote(description)}` : ''
+      return `complete -c napi -n '__fish_seen_subcommand_from ${name}' ${flags}${value}${help}`
+    }),
+  ])
+  return `# fish completion of napi, generated by \`napi completions fish\`
+function __napi_targets
+  set -l targets (rustup target list 2>/dev/null | string replace -r ' .*' '')
+  if test (count $targets) -eq 0
+    set targets ${SupportedPlatforms.join(' ')}
+  end
+  printf '%s\\n' $targets
+end
+
+complete -c napi -n __fish_use_subcommand -f
+${lines.join('\n')}
+`
+}
+
+function powershellQuote(value: string) {
+  return `'${value.replace(/'/g, "''")}'`
+}
+
+function powershell(commands: CompletionCommand[]) {
+  const list = (values: string[]) =>
+    `@(${values.map(powershellQuote).join(', ')})`
+  return `# PowerShell completion of napi, generated by \`napi completions powershell\`
+Register-ArgumentCompleter -Native -CommandName napi -ScriptBlock {
+  param($wordToComplete, $commandAst, $cursorPosition)
+  $commands = [ordered]@{
+${commands
+  .map(
+    ({ name, description }) =>
+      `    ${powershellQuote(name)} = ${powershellQuote(description)}`,
+  )
+  .join('\n')}
+  }
+  $options = @{
+${commands
+  .map(
+    ({ name, options }) =>
+      `    ${powershellQuote(name)} = ${list(
+        options.flatMap(({ names }) => names),
+      )}`,
+  )
+  .join('\n')}
+  }
+  $targetOptions = @{
+${Object.entries(TARGET_OPTIONS)
+  .map(([name, options]) => `    ${powershellQuote(name)} = ${list(options)}`)
+  .join('\n')}
+  }
+  # the words before the one being completed
+  $words = @($commandAst.CommandElements | Where-Object { $_.Extent.EndOffset -lt $cursorPosition } | ForEach-Object { $_.ToString() })
+  if ($words.Count -le 1) {
+    $commands.Keys | Where-Object { $_ -like "$wordToComplete*" } | ForEach-Object {
+      [System.Management.Automation.CompletionResult]::new($_, $_, 'ParameterValue', $commands[$_])
+    }
+    return
+  }
+  $command = $words[1]
+  if ($targetOptions[$command] -contains $words[-1]) {
+    $targets = @()
+    if (Get-Command rustup -ErrorAction SilentlyContinue) {
+      $targets = @(rustup target list 2>$null | ForEach-Object { ($_ -split ' ')[0] })
+    }
+    if ($targets.Count -eq 0) {
+      $targets = ${list(SupportedPlatforms)}
+    }
+    $targets | Where-Object { $_ -like "$wordToComplete*" } | ForEach-Object {
+      [System.Management.Automation.CompletionResult]::new($_, $_, 'ParameterValue', $_)
+    }
+    return
+  }
+  if ($wordToComplete -like '-*') {
+    $options[$command] | Where-Object { $_ -like "$wordToComplete*" } | ForEach-Object {
+      [System.Management.Automation.CompletionResult]::new($_, $_, 'ParameterName', $_)
+    }
+  }
+}
+`
+}
+
+export function generateCompletions(
+  shell: Shell,
+  commands: CompletionCommand[],
+) {
+  switch (shell) {
+    case 'bash':
+      return bash(commands)
+    case 'zsh':
+      return zsh(commands)
+    case 'fish':
+      return fish(commands)
+    case 'powershell':
+      return powershell(commands)
+  }
+}
+
+export class CompletionsCommand extends Command {
+  static usage = Command.Usage({
+    description: `Print the completion script of ${SHELLS.join(', ')}`,
+    examples: [
+      ['Enable in bash', 'source <(napi completions bash)'],
+      ['Enable in zsh', 'napi completions zsh > "${fpath[1]}/_napi"'],
+      [
+        'Enable in fish',
+        'napi completions fish > ~/.config/fish/completions/napi.fish',
+      ],
+      [
+        'Enable in PowerShell',
+        'napi completions powershell | Out-String | Invoke-Expression',
+      ],
+    ],
+  })
+
+  static paths = [['completions']]
+
+  shell = Option.String({ required: true })
+
+  async execute() {
+    if (!(SHELLS as readonly string[]).includes(this.shell)) {
+      throw new TypeError(
+        `Unsupported shell [${this.shell}], expected one of ${SHELLS.join(
+          ', ',
+        )}`,
+      )
+    }
+    this.context.stdout.write(
+      generateCompletions(
+        this.shell as Shell,
+        toCompletionCommands(this.cli.definitions()),
+      ),
+    )
+  }
+}
@@ -1,15 +1,87 @@
-import { join } from 'path'
+import { existsSync, readFileSync } from 'fs'
+import { basename, dirname, join } from 'path'
 
+import chalk from 'chalk'
+import toml from 'toml'
+
+import type { BuildConstants } from './build-constants'
+import type { CompatShims } from './compat-shims'
+import type { CpuVariants } from './cpu-variants'
+import type { DistTagConfig } from './dist-tag'
+import type { SizeBudgetConfig } from './pack-preview'
 import { DefaultPlatforms, PlatformDetail, parseTriple } from './parse-triple'
+import type { SecretsScanConfig } from './secrets-scan'
+import type { TargetFeatures } from './target-features'
+import { toLongPath } from './utils'
+
+/**
+ * Looked up next to package.json in this order, `napi.toml` is TOML and `.naprc` is JSON.
+ * Both have the same keys as the `napi` field of package.json, and override them.
+ */
+export const NAPI_CONFIG_FILES = ['napi.toml', '.naprc']
+
+/**
+ * `target.[triple]` of the napi config, applied by `napi build` for the target
+ */
+export interface TargetConfig {
+  env?: Record<string, string>
+  cargoFlags?: string[]
+  constants?: BuildConstants
+}
+
+export function isNapiConfigFile(path: string) {
+  return NAPI_CONFIG_FILES.includes(basename(path)) || path.endsWith('.toml')
+}
 
+export function parseNapiConfigFile(
+  path: string,
+  content: string,
+): Record<string, any> {
+  try {
+    return path.endsWith('.toml') ? toml.parse(content) : JSON.parse(content)
+  } catch (e) {
+    const location =
+      typeof e.line === 'number' ? `:${e.line}:${e.column}` : ''
+    throw new TypeError(
+      `Failed to parse [${chalk.yellowBright(`${path}${location}`)}]: ${
+        e.message
+      }`,
+    )
+  }
+}
+
+/**
+ * @param packageJson package.json, or the napi config file with package.json in the same dir
+ */
 export function getNapiConfig(
   packageJson = 'package.json',
   cwd = process.cwd(),
 ) {
-  const packageJsonPath = join(cwd, packageJson)
+  const configPath = join(cwd, packageJson)
+  const packageJsonPath = isNapiConfigFile(configPath)
+    ? join(dirname(configPath), 'package.json')
+    : configPath
+  const napiConfigPath = isNapiConfigFile(configPath)
+    ? configPath
+    : NAPI_CONFIG_FILES.map((file) =>
+        join(dirname(packageJsonPath), file),
+      ).find((path) => existsSync(toLongPath(path))) ?? null
 
-  const pkgJson = require(packageJsonPath)
-  const { version: packageVersion, napi, name } = pkgJson
+  // package.json could be generated after napi config file
+  const pkgJson =
+    napiConfigPath && !existsSync(toLongPath(packageJsonPath))
+      ? {}
+      : require(packageJsonPath)
+  const { version: packageVersion, name } = pkgJson
+  const napi = napiConfigPath
+    ? {
+        ...pkgJson.napi,
+        ...parseNapiConfigFile(
+          napiConfigPath,
+          readFileSync(toLongPath(napiConfigPath), 'utf8'),
+        ),
+      }
+    : pkgJson.napi
   const additionPlatforms: PlatformDetail[] = (
     napi?.triples?.additional ?? []
   ).map(parseTriple)
@@ -24,13 +96,71 @@ export function getNapiConfig(
   const packageName = napi?.package?.name ?? name
 
   const binaryName: string = napi?.name ?? 'index'
+  // the `.node` files of the other `#[napi]` crates shipped in the same packages, built by `napi build --binary-name`
+  const binaryNames: string[] = [
+    binaryName,
+    ...(napi?.binaries ?? []).filter((name: string) => name !== binaryName),
+  ]
+  const autoInstallTarget: boolean = napi?.autoInstallTarget === true
+  // `sccache` as `RUSTC_WRAPPER`
+  const useCache: boolean = napi?.useCache === true
+  // extra `patterns` and `ignore` of the files checked before publishing
+  const secretsScan: SecretsScanConfig = napi?.secretsScan ?? {}
+  // the largest unpacked size of the published packages
+  const sizeBudget: SizeBudgetConfig | undefined = napi?.sizeBudget
+  // branch patterns to the dist-tags of `npm publish`
+  const distTags: DistTagConfig | undefined = napi?.distTags
+  // scripts run by `napi build` before and after cargo
+  const preBuild: string[] = [napi?.preBuild ?? []].flat()
+  const postBuild: string[] = [napi?.postBuild ?? []].flat()
+  // run by `napi test` after building
+  const testCommand: string | undefined = napi?.testCommand
+  // replaces the default header of the generated `.d.ts`, relative to package.json
+  const dtsHeader: string | undefined = napi?.dtsHeader
+  // formats the generated `.d.ts` in place, run with the path of it
+  const dtsFormatter: string | undefined = napi?.dtsFormatter
+  // `[dts].[platform].d.ts` and `[packageName]-types-[platform]` packages with the APIs available on the platform
+  const platformTypes: boolean = napi?.platformTypes === true
+  // env and cargo flags of `napi build` per target triple
+  const targetConfigs: Record<string, TargetConfig> = napi?.target ?? {}
+  // the JS names of the classes to the files of the JS methods merged onto the prototypes, relative to package.json
+  const mixins: Record<string, string> = napi?.mixins ?? {}
+  // injected into both Rust and the JS binding, overridden by the `constants` of `target.[triple]`
+  const constants: BuildConstants = napi?.constants ?? {}
+  // cargo features enabled by `napi build` for the matched target triples
+  const features: TargetFeatures = napi?.features ?? {}
+  // built by `napi build --variant` with the CPU features, preferred by the JS binding if the CPU supports them
+  const variants: CpuVariants = napi?.variants ?? {}
+  // the old paths of the moved JS files to the new ones, re-exported with a deprecation warning
+  const compatShims: CompatShims = napi?.compatShims ?? {}
 
   return {
     platforms,
     version,
     packageName,
     binaryName,
+    binaryNames,
+    autoInstallTarget,
+    useCache,
+    secretsScan,
+    sizeBudget,
+    testCommand,
+    preBuild,
+    postBuild,
+    distTags,
+    dtsHeader,
+    dtsFormatter,
+    platformTypes,
+    targetConfigs,
+    mixins,
+    constants,
+    features,
+    variants,
+    compatShims,
     packageJsonPath,
+    napiConfigPath,
+    // merged from package.json and napi config file
+    napiConfig: napi ?? null,
     content: pkgJson,
   }
 }
@@ -0,0 +1,172 @@
+import chalk from 'chalk'
+
+import { matchTriplePatterns } from './target-features'
+
+/**
+ * A variant of `napi.variants` in the napi config, built by `napi build --variant` with the CPU features enabled.
+ * The JS binding loads it instead of the baseline `.node` file if the CPU supports all the features,
+ * eg: `{ "avx2": { "targets": "x86_64-*", "cpuFeatures": ["avx2", "fma"] } }`.
+ */
+export interface CpuVariant {
+  // triple patterns the same as `napi.features`
+  targets: string | string[]
+  // `-C target-feature` of rustc
+  cpuFeatures: string[]
+}
+
+export type CpuVariants = Record<string, CpuVariant>
+
+/**
+ * The Rust names of the CPU features to the names in `/proc/cpuinfo` and `sysctl hw.optional` of macOS,
+ * the others are the same in all of them.
+ */
+const CPU_FEATURE_NAMES: Record<string, string[]> = {
+  'sse4.1': ['sse4_1'],
+  'sse4.2': ['sse4_2'],
+  avx: ['avx', 'avx1'],
+  lzcnt: ['abm', 'lzcnt'],
+  sha: ['sha_ni', 'sha'],
+  neon: ['asimd', 'neon'],
+  dotprod: ['asimddp', 'dotprod'],
+  fp16: ['fphp', 'fp16'],
+  rdm: ['asimdrdm', 'rdm'],
+  lse: ['atomics', 'lse'],
+  crc: ['crc32'],
+  sha2: ['sha2', 'sha256'],
+}
+
+function checkCpuVariant(name: string, variant: CpuVariant | undefined) {
+  const targets = [variant?.targets ?? []].flat()
+  if (
+    !/^[a-z0-9][a-z0-9-]*$/.test(name) ||
+    !targets.length ||
+    targets.some((target) => typeof target !== 'string') ||
+    !Array.isArray(variant?.cpuFeatures) ||
+    !variant!.cpuFeatures.length ||
+    variant!.cpuFeatures.some(
+      (feature) => typeof feature !== 'string' || !/^[\w.]+$/.test(feature),
+    )
+  ) {
+    throw new TypeError(
+      `The variant ${chalk.green(
+        name,
+      )} must be a lowercase name with the ${chalk.green(
+        'targets',
+      )} triple patterns and the ${chalk.green(
+        'cpuFeatures',
+      )} of rustc, received ${chalk.yellowBright(JSON.stringify(variant))}`,
+    )
+  }
+  return { targets, cpuFeatures: variant!.cpuFeatures }
+}
+
+/**
+ * The rustflag enabling the CPU features of the variant built for the triple.
+ */
+export function resolveCpuVariant(
+  variants: CpuVariants,
+  name: string,
+  triple: string,
+): string {
+  if (!(name in variants)) {
+    throw new TypeError(
+      `The variant ${chalk.green(name)} is not in ${chalk.green(
+        'napi.variants',
+      )}, available: ${Object.keys(variants).join(', ') || '[Empty]'}`,
+    )
+  }
+  const { targets, cpuFeatures } = checkCpuVariant(name, variants[name])
+  if (!matchTriplePatterns(targets, triple)) {
+    throw new TypeError(
+      `The variant ${chalk.green(name)} is not built for ${chalk.yellowBright(
+        triple,
+      )}, the targets of it: ${targets.join(', ')}`,
+    )
+  }
+  return `-C target-feature=${cpuFeatures
+    .map((feature) => `+${feature}`)
+    .join(',')}`
+}
+
+/**
+ * The variants built for the triple, in the order of the config.
+ */
+export function cpuVariantsOfTarget(
+  variants: CpuVariants,
+  triple: string,
+): string[] {
+  return Object.entries(variants)
+    .filter(([name, variant]) =>
+      matchTriplePatterns(checkCpuVariant(name, variant).targets, triple),
+    )
+    .map(([name]) => name)
+}
+
+/**
+ * `[name].[platform].node` to `[name].[platform].[variant].node`.
+ */
+export function cpuVariantFileName(file: string, variant: string) {
+  return file.replace(/\.node$/, `.${variant}.node`)
+}
+
+/**
+ * `supportedVariants()` of the JS binding, the variants the CPU supports in the order of the config.
+ * The CPU features are read from `/proc/cpuinfo` on Linux and Android and `sysctl` on macOS,
+ * none of the variants are supported on the other platforms, or with `NAPI_RS_CPU_VARIANTS=false`.
+ */
+export function createCpuVariantsBinding(variants: CpuVariants) {
+  const variantFeatures = Object.entries(variants).map(([name, variant]) => [
+    name,
+    checkCpuVariant(name, variant).cpuFeatures.map(
+      (feature) => CPU_FEATURE_NAMES[feature] ?? [feature],
+    ),
+  ])
+  return `
+// the variants of \`napi.variants\` to the names of the CPU features they require
+const cpuVariants = ${JSON.stringify(variantFeatures)}
+let supportedCpuVariants = null
+
+function detectCpuFeatures() {
+  try {
+    if (platform === 'linux' || platform === 'android') {
+      // \`flags\` on x86 and \`Features\` on ARM
+      const line = readFileSync('/proc/cpuinfo', 'utf8')
+        .split('\\n')
+        .find((line) => /^(flags|Features)\\s*:/.test(line))
+      return line ? line.split(':')[1].trim().split(/\\s+/) : []
+    }
+    if (platform === 'darwin') {
+      // \`hw.optional.avx2_0: 1\` on Intel and \`hw.optional.arm.FEAT_DotProd: 1\` on Apple silicon
+      return require('child_process')
+        .execSync('sysctl hw.optional', { encoding: 'utf8' })
+        .split('\\n')
+        .filter((line) => line.endsWith(': 1'))
+        .map((line) =>
+          line
+            .slice('hw.optional.'.length, -': 1'.length)
+            .replace(/^arm\\.FEAT_/, '')
+            .replace(/_0$/, '')
+            .toLowerCase()
+        )
+    }
+  } catch (e) {
+    // loads the baseline
+  }
+  return []
+}
+
+function supportedVariants() {
+  if (!supportedCpuVariants) {
+    const cpuFeatures = new Set(
+      process.env.NAPI_RS_CPU_VARIANTS === 'false' ? [] : detectCpuFeatures()
+    )
+    supportedCpuVariants = cpuVariants
+      .filter(([, features]) =>
+        features.every((names) => names.some((name) => cpuFeatures.has(name)))
+      )
+      .map(([variant]) => variant)
+  }
+  return supportedCpuVariants
+}
+`
+}
@@ -1,13 +1,26 @@
 import { mkdirSync } from 'fs'
-import { join } from 'path'
+import { join, parse } from 'path'
 
 import chalk from 'chalk'
 import { Command, Option } from 'clipanion'
 
 import { getNapiConfig } from './consts'
+import { cpuVariantFileName, cpuVariantsOfTarget } from './cpu-variants'
 import { debugFactory } from './debug'
+import {
+  detectPackageManager,
+  getPackageManagerDetail,
+  PackageManagerDetail,
+} from './package-manager'
 import { PlatformDetail } from './parse-triple'
-import { writeFileAsync, pick } from './utils'
+import { applyTemplate } from './templates'
+import {
+  copyFileAsync,
+  findLicenseFile,
+  pick,
+  toLongPath,
+  writeFileAsync,
+} from './utils'
 
 const debug = debugFactory('create-npm-dir')
 
@@ -22,11 +35,25 @@ export class CreateNpmDirCommand extends Command {
     config: string,
     targetDirPath: string,
     cwd: string,
+    packageManager: PackageManagerDetail = getPackageManagerDetail(
+      detectPackageManager(cwd),
+    ),
   ) => {
     const pkgJsonDir = config
     debug(`Read content from [${chalk.yellowBright(pkgJsonDir)}]`)
-    const { platforms, packageName, version, binaryName, content } =
-      getNapiConfig(pkgJsonDir, cwd)
+    const {
+      platforms,
+      packageName,
+      version,
+      binaryName,
+      binaryNames,
+      content,
+      packageJsonPath,
+      platformTypes,
+      variants,
+    } = getNapiConfig(pkgJsonDir, cwd)
+    // npm flags packages without license, copy the one of main package
+    const licenseFile = findLicenseFile(parse(packageJsonPath).dir)
 
     for (const platformDetail of platforms) {
       const targetDir = join(
@@ -34,7 +61,7 @@ export class CreateNpmDirCommand extends Command {
         'npm',
         `${platformDetail.platformArchABI}`,
       )
-      mkdirSync(targetDir, {
+      mkdirSync(toLongPath(targetDir), {
         recursive: true,
       })
       const binaryFileName = `${binaryName}.${platformDetail.platformArchABI}.node`
@@ -49,7 +76,16 @@ export class CreateNpmDirCommand extends Command {
         os: [platformDetail.platform],
         cpu: [platformDetail.arch],
         main: binaryFileName,
-        files: [binaryFileName],
+        // the others are required by the subpath, the variants are resolved by the JS binding
+        files: binaryNames.flatMap((name) => {
+          const file = `${name}.${platformDetail.platformArchABI}.node`
+          return [
+            file,
+            ...cpuVariantsOfTarget(variants, platformDetail.raw).map(
+              (variant) => cpuVariantFileName(file, variant),
+            ),
+          ]
+        }),
         ...pick(
           content,
           'description',
@@ -71,13 +107,99 @@ export class CreateNpmDirCommand extends Command {
       } else if (platformDetail.abi === 'musl') {
         packageJson.libc = ['musl']
       }
+      const templateData = {
+        packageName,
+        binaryName,
+        version,
+        platform: platformDetail,
+      }
+      await writeFileAsync(
+        targetPackageJson,
+        applyTemplate(
+          'npm/package.json',
+          JSON.stringify(packageJson, null, 2),
+          templateData,
+          cwd,
+        ),
+      )
+      const targetReadme = join(targetDir, 'README.md')
+      debug(`Write target README.md [${chalk.yellowBright(targetReadme)}]`)
+      await writeFileAsync(
+        targetReadme,
+        applyTemplate(
+          'npm/README.md',
+          readme(packageName, platformDetail, packageManager),
+          templateData,
+          cwd,
+        ),
+      )
+      if (licenseFile) {
+        const targetLicense = join(targetDir, parse(licenseFile).base)
+        debug(`Copy license to [${chalk.yellowBright(targetLicense)}]`)
+        await copyFileAsync(licenseFile, targetLicense)
+      }
+    }
+
+    if (!platformTypes) {
+      return
+    }
+    const typesPlatforms = [
+      ...new Set(platforms.map((platformDetail) => platformDetail.platform)),
+    ]
+    for (const platform of typesPlatforms) {
+      const targetDir = join(targetDirPath, 'npm', `types-${platform}`)
+      mkdirSync(toLongPath(targetDir), {
+        recursive: true,
+      })
+      const targetPackageJson = join(targetDir, 'package.json')
+      debug(`Write file [${chalk.yellowBright(targetPackageJson)}]`)
+      const templateData = { packageName, binaryName, version, platform }
       await writeFileAsync(
         targetPackageJson,
-        JSON.stringify(packageJson, null, 2),
+        applyTemplate(
+          'npm/types/package.json',
+          JSON.stringify(
+            {
+              name: `${packageName}-types-${platform}`,
+              version,
+              types: 'index.d.ts',
+              files: ['index.d.ts'],
+              ...pick(
+                content,
+                'description',
+                'keywords',
+                'author',
+                'authors',
+                'homepage',
+                'license',
+                'publishConfig',
+                'repository',
+                'bugs',
+              ),
+            },
+            null,
+            2,
+          ),
+          templateData,
+          cwd,
+        ),
       )
       const targetReadme = join(targetDir, 'README.md')
       debug(`Write target README.md [${chalk.yellowBright(targetReadme)}]`)
-      await writeFileAsync(targetReadme, readme(packageName, platformDetail))
+      await writeFileAsync(
+        targetReadme,
+        applyTemplate(
+          'npm/types/README.md',
+          typesReadme(packageName, platform),
+          templateData,
+          cwd,
+        ),
+      )
+      if (licenseFile) {
+        const targetLicense = join(targetDir, parse(licenseFile).base)
+        debug(`Copy license to [${chalk.yellowBright(targetLicense)}]`)
+        await copyFileAsync(licenseFile, targetLicense)
+      }
     }
   }
 
@@ -85,18 +207,64 @@ export class CreateNpmDirCommand extends Command {
 
   config = Option.String('-c,--config', 'package.json')
 
+  packageManager?: string = Option.String('--package-manager', {
+    description: `Package manager used by the project, ${chalk.green(
+      'npm',
+    )}, ${chalk.green('yarn')}, ${chalk.green('yarn-berry')} or ${chalk.green(
+      'pnpm',
+    )}. Detected from the lockfile by default`,
+  })
+
   async execute() {
     await CreateNpmDirCommand.create(
       this.config,
       join(process.cwd(), this.targetDir),
       process.cwd(),
+      getPackageManagerDetail(
+        this.packageManager ?? detectPackageManager(process.cwd()),
+      ),
     )
   }
 }
 
-function readme(packageName: string, platformDetail: PlatformDetail) {
+function readme(
+  packageName: string,
+  platformDetail: PlatformDetail,
+  packageManager: PackageManagerDetail,
+) {
   return `# \`${packageName}-${platformDetail.platformArchABI}\`
 
 This is the **${platformDetail.raw}** binary for \`${packageName}\`
+
+| Platform | Architecture | ABI | Target |
+| -------- | ------------ | --- | ------ |
+| ${platformDetail.platform} | ${platformDetail.arch} | ${
+    platformDetail.abi ?? '-'
+  } | ${platformDetail.raw} |
+
+Install \`${packageName}\` instead of this package, the right binary is picked automatically:
+
+\`\`\`
+${packageManager.add} ${packageName}
+\`\`\`
+`
+}
+
+function typesReadme(packageName: string, platform: string) {
+  return `# \`${packageName}-types-${platform}\`
+
+The type definitions of \`${packageName}\` with only the APIs available on **${platform}**.
+
+Map the types of \`${packageName}\` to this package in \`tsconfig.json\`:
+
+\`\`\`json
+{
+  "compilerOptions": {
+    "paths": {
+      "${packageName}": ["./node_modules/${packageName}-types-${platform}"]
+    }
+  }
+}
+\`\`\`
 `
 }
@@ -0,0 +1,385 @@
+import { ChildProcess, fork, spawn } from 'child_process'
+import {
+  existsSync,
+  FSWatcher,
+  mkdirSync,
+  readdirSync,
+  statSync,
+  watch,
+} from 'fs'
+import { join } from 'path'
+
+import { Instance } from 'chalk'
+import { Command, Option } from 'clipanion'
+import envPaths from 'env-paths'
+
+import { debugFactory } from './debug'
+import { writeFileAsync } from './utils'
+
+const debug = debugFactory('dev')
+const chalk = new Instance({ level: 1 })
+
+const IGNORED_DIRS = ['target', 'node_modules', '.git']
+
+/**
+ * napi-rs classes have methods in the prototype, and are named in PascalCase.
+ * Embedded into the dev runtime by `toString`.
+ */
+export function isNapiClass(value: unknown) {
+  if (typeof value !== 'function') {
+    return false
+  }
+  const members = value.prototype
+    ? Object.getOwnPropertyNames(value.prototype)
+    : []
+  return members.length > 1 || /^[A-Z]/.test(value.name)
+}
+
+/**
+ * Why the exports of the rebuilt native module can't replace the loaded ones in place, `null` if they can.
+ *
+ * The functions are called through the wrappers, so they could be swapped. But the instances of the classes hold the
+ * native data of the old binary, and the other values may have been copied by the JS side.
+ * Embedded into the dev runtime by `toString`.
+ */
+export function getSwapProblem(
+  previous: Record<string, unknown>,
+  next: Record<string, unknown>,
+): string | null {
+  const previousKeys = Object.keys(previous)
+  const nextKeys = Object.keys(next)
+  const added = nextKeys.filter((key) => !previousKeys.includes(key))
+  const removed = previousKeys.filter((key) => !nextKeys.includes(key))
+  if (added.length || removed.length) {
+    return `the exports are changed, ${added
+      .map((key) => `+${key}`)
+      .concat(removed.map((key) => `-${key}`))
+      .join(' ')}`
+  }
+  for (const key of nextKeys) {
+    const previousValue = previous[key]
+    const nextValue = next[key]
+    if (typeof previousValue !== typeof nextValue) {
+      return `the type of ${key} is changed`
+    }
+    if (typeof nextValue === 'function') {
+      if (isNapiClass(previousValue) || isNapiClass(nextValue)) {
+        return `${key} is a class`
+      }
+    } else if (JSON.stringify(previousValue) !== JSON.stringify(nextValue)) {
+      return `the value of ${key} is changed`
+    }
+  }
+  return null
+}
+
+// preloaded into the child process by `--require`
+// the child exits on `napi:teardown`, the addon is unloaded with the Node.js environment and the env cleanup hooks
+// on `napi:swap`, the native modules loaded from cwd are copied to versioned paths and loaded again,
+// because `dlopen` returns the cached handle of the same path
+const DEV_RUNTIME = `const Module = require('module')
+const { copyFileSync, mkdirSync } = require('fs')
+const { tmpdir } = require('os')
+const { basename, join, sep } = require('path')
+${isNapiClass.toString()}
+${getSwapProblem.toString()}
+const listeners = []
+process.napiTeardown = (listener) => listeners.push(listener)
+const addons = new Map()
+const swapDir = join(tmpdir(), \`napi-rs-dev-\${process.pid}\`)
+let version = 0
+const loadNativeModule = Module._extensions['.node']
+Module._extensions['.node'] = function (module, filename) {
+  loadNativeModule(module, filename)
+  if (!filename.startsWith(process.cwd() + sep) || filename.includes(sep + 'node_modules' + sep)) {
+    return
+  }
+  const addon = { filename, current: module.exports }
+  const exports = {}
+  for (const key of Object.keys(module.exports)) {
+    const value = module.exports[key]
+    if (typeof value === 'function' && !isNapiClass(value)) {
+      exports[key] = function (...args) {
+        return addon.current[key].apply(this, args)
+      }
+      Object.defineProperty(exports[key], 'name', { value: value.name })
+    } else {
+      exports[key] = value
+    }
+  }
+  module.exports = exports
+  addons.set(filename, addon)
+}
+function swap() {
+  if (!addons.size) {
+    return { type: 'napi:swap-failed', reason: 'no native module is loaded' }
+  }
+  mkdirSync(swapDir, { recursive: true })
+  version += 1
+  const swapped = []
+  for (const addon of addons.values()) {
+    const name = basename(addon.filename)
+    const versioned = join(swapDir, name.replace(/\\.node$/, \`.\${version}.node\`))
+    const module = { exports: {} }
+    try {
+      copyFileSync(addon.filename, versioned)
+      process.dlopen(module, versioned)
+    } catch (e) {
+      return { type: 'napi:swap-failed', reason: \`\${name}: \${e.message}\` }
+    }
+    const problem = getSwapProblem(addon.current, module.exports)
+    if (problem) {
+      return { type: 'napi:swap-failed', reason: \`\${name}: \${problem}\` }
+    }
+    swapped.push([addon, module.exports])
+  }
+  for (const [addon, exports] of swapped) {
+    addon.current = exports
+  }
+  return { type: 'napi:swapped', count: swapped.length }
+}
+process.on('message', (message) => {
+  if (message && message.type === 'napi:teardown') {
+    Promise.all(listeners.map((listener) => listener())).finally(() => process.exit(0))
+  }
+  if (message && message.type === 'napi:swap') {
+    process.send(swap())
+  }
+})
+// don't keep the child alive because of the IPC channel
+if (process.channel) {
+  process.channel.unref()
+}
+`
+
+export class DevCommand extends Command {
+  static usage = Command.Usage({
+    description:
+      'Rebuild on change and hot swap the native module in the Node.js process, or restart it',
+  })
+
+  static paths = [['dev']]
+
+  entry = Option.String({
+    required: true,
+  })
+
+  args = Option.Rest()
+
+  watchPaths = Option.Array('--watch', ['src', 'Cargo.toml', 'build.rs'], {
+    description: `Files or dirs to watch, relative to cwd. Default to ${chalk.green(
+      'src',
+    )}, ${chalk.green('Cargo.toml')} and ${chalk.green('build.rs')}`,
+  })
+
+  buildArgs = Option.String('--build-args', '', {
+    description: `Flags passed to ${chalk.green('napi build')}`,
+  })
+
+  killTimeout = Option.String('--kill-timeout', '3000', {
+    description: `Milliseconds to wait for the process to tear down before ${chalk.red(
+      'SIGKILL',
+    )} it`,
+  })
+
+  hot = Option.Boolean('--hot', true, {
+    description: `Swap the rebuilt native module in the running process, restart it if the swap is not possible. ${chalk.green(
+      '--no-hot',
+    )} to always restart`,
+  })
+
+  private child: ChildProcess | null = null
+
+  private building = false
+
+  private pendingBuild = false
+
+  async execute() {
+    const cwd = process.cwd()
+    const runtimePath = join(envPaths('napi-rs').cache, 'dev-runtime.js')
+    mkdirSync(envPaths('napi-rs').cache, { recursive: true })
+    await writeFileAsync(runtimePath, DEV_RUNTIME)
+
+    const watchers = this.watchPaths
+      .map((path) => join(cwd, path))
+      .filter((path) => existsSync(path))
+      .reduce<FSWatcher[]>(
+        (acc, path) =>
+          acc.concat(watchRecursive(path, () => this.rebuild(runtimePath))),
+        [],
+      )
+    debug(`Watching ${watchers.length} files and dirs`)
+
+    process.on('SIGINT', () => {
+      watchers.forEach((watcher) => watcher.close())
+      this.stopChild().finally(() => process.exit(0))
+    })
+
+    await this.rebuild(runtimePath)
+    // wait for SIGINT
+    await new Promise(() => {})
+  }
+
+  private async rebuild(runtimePath: string) {
+    if (this.building) {
+      this.pendingBuild = true
+      return
+    }
+    this.building = true
+    const start = Date.now()
+    const success = await this.build()
+    if (success) {
+      const built = `Built in ${Date.now() - start}ms`
+      // `null` if swapped, `undefined` if the swap is not tried
+      const swapProblem =
+        this.hot && this.child ? await this.swapChild() : undefined
+      if (swapProblem === null) {
+        console.info(
+          chalk.green(`${built}, swapped the native module of ${this.entry}`),
+        )
+      } else {
+        console.info(
+          chalk.green(
+            `${built}, ${
+              swapProblem ? `${swapProblem}, ` : ''
+            }restarting ${this.entry}`,
+          ),
+        )
+        await this.stopChild()
+        this.startChild(runtimePath)
+      }
+    } else {
+      console.error(chalk.red('Build failed, keep the current process running'))
+    }
+    this.building = false
+    if (this.pendingBuild) {
+      this.pendingBuild = false
+      await this.rebuild(runtimePath)
+    }
+  }
+
+  private build(): Promise<boolean> {
+    const args = [
+      process.argv[1],
+      'build',
+      ...this.buildArgs.split(' ').filter(Boolean),
+    ]
+    debug(`Run ${chalk.green(`napi ${args.slice(1).join(' ')}`)}`)
+    return new Promise((resolve) => {
+      spawn(process.execPath, args, { stdio: 'inherit' })
+        .on('close', (code) => resolve(code === 0))
+        .on('error', () => resolve(false))
+    })
+  }
+
+  private startChild(runtimePath: string) {
+    const child = fork(this.entry, this.args, {
+      execArgv: [...process.execArgv, '--require', runtimePath],
+      stdio: 'inherit',
+    })
+    child.on('exit', (code, signal) => {
+      if (this.child === child) {
+        this.child = null
+        console.info(
+          chalk.yellowBright(
+            `${this.entry} exited with ${signal ?? code}, waiting for changes`,
+          ),
+        )
+      }
+    })
+    this.child = child
+  }
+
+  /**
+   * Resolve with why the native module can't be swapped, or `null` if swapped
+   */
+  private swapChild(): Promise<string | null> {
+    const child = this.child
+    if (!child || !child.connected) {
+      return Promise.resolve('the process is not running')
+    }
+    return new Promise((resolve) => {
+      const timer = setTimeout(() => {
+        child.off('message', onMessage)
+        resolve('the process did not respond to the swap')
+      }, Number(this.killTimeout))
+      const onMessage = (message: { type?: string; reason?: string }) => {
+        if (
+          message?.type !== 'napi:swapped' &&
+          message?.type !== 'napi:swap-failed'
+        ) {
+          return
+        }
+        clearTimeout(timer)
+        child.off('message', onMessage)
+        debug(`Swap result ${JSON.stringify(message)}`)
+        resolve(
+          message.type === 'napi:swapped'
+            ? null
+            : `can't swap the native module, ${message.reason}`,
+        )
+      }
+      child.on('message', onMessage)
+      child.send({ type: 'napi:swap' })
+    })
+  }
+
+  private stopChild(): Promise<void> {
+    const child = this.child
+    if (!child) {
+      return Promise.resolve()
+    }
+    this.child = null
+    return new Promise((resolve) => {
+      const timer = setTimeout(() => {
+        debug(`Tear down timeout, kill ${child.pid}`)
+        child.kill('SIGKILL')
+      }, Number(this.killTimeout))
+      child.once('exit', () => {
+        clearTimeout(timer)
+        resolve()
+      })
+      if (child.connected) {
+        child.send({ type: 'napi:teardown' })
+      } else {
+        child.kill('SIGTERM')
+      }
+    })
+  }
+}
+
+/**
+ * `fs.watch` with `recursive` is not supported on Linux in old Node.js, watch every dir instead.
+ * Dirs created after the start are not watched.
+ */
+function watchRecursive(path: string, onChange: () => void): FSWatcher[] {
+  let timer: NodeJS.Timeout | null = null
+  const listener = (_event: string, filename: string | Buffer | null) => {
+    const file = filename?.toString() ?? ''
+    if (file.endsWith('.node')) {
+      return
+    }
+    debug(`${chalk.yellowBright(file)} changed`)
+    // editors write a file several times on save
+    if (timer) {
+      clearTimeout(timer)
+    }
+    timer = setTimeout(onChange, 100)
+  }
+  if (!statSync(path).isDirectory()) {
+    return [watch(path, listener)]
+  }
+  return collectDirs(path).map((dir) => watch(dir, listener))
+}
+
+function collectDirs(dir: string): string[] {
+  return readdirSync(dir, { withFileTypes: true })
+    .filter((entry) => entry.isDirectory() && !isIgnored(entry.name))
+    .reduce((dirs, entry) => dirs.concat(collectDirs(join(dir, entry.name))), [
+      dir,
+    ])
+}
+
+function isIgnored(name: string) {
+  return IGNORED_DIRS.includes(name)
+}
@@ -0,0 +1,95 @@
+import { CargoDiagnostic, runCargoWithMessages } from './github-annotations'
+
+export const MESSAGE_FORMATS = ['json-diagnostic']
+
+/**
+ * Written by `napi-derive` into the intermediate type file alongside the type def.
+ */
+export interface TypeDefWarning {
+  code: string
+  message: string
+  // `null` if the compiler doesn't provide the locations to the proc macros
+  location: {
+    file_name: string
+    line_start: number
+    column_start: number
+    line_end: number
+    column_end: number
+  } | null
+}
+
+/**
+ * napi-rs problem in the shape of the `compiler-message` of cargo, so the editors could read both of them the same way.
+ */
+export interface NapiMessage {
+  reason: 'napi-message'
+  message: CargoDiagnostic
+}
+
+/**
+ * The names declared more than once in the d.ts, the class and the methods of it are not counted.
+ */
+export function findTypeDefConflicts(
+  defs: { kind: string; name: string; js_mod?: string }[],
+): TypeDefWarning[] {
+  const kinds = new Map<string, string[]>()
+  for (const def of defs) {
+    if (def.kind === 'impl') {
+      continue
+    }
+    const name = def.js_mod ? `${def.js_mod}.${def.name}` : def.name
+    kinds.set(name, [...(kinds.get(name) ?? []), def.kind])
+  }
+  return [...kinds.entries()]
+    .filter(([, kinds]) => kinds.length > 1)
+    .map(([name, kinds]) => ({
+      code: 'napi::dts_conflict',
+      message: `\`${name}\` is declared ${kinds.length} times in the d.ts, as ${kinds.join(', ')}, rename them with \`js_name\``,
+      location: null,
+    }))
+}
+
+export function toNapiMessage(warning: TypeDefWarning): NapiMessage {
+  const { location } = warning
+  return {
+    reason: 'napi-message',
+    message: {
+      message: warning.message,
+      code: { code: warning.code },
+      level: 'warning',
+      spans: location ? [{ ...location, is_primary: true }] : [],
+      rendered: `warning[${warning.code}]: ${warning.message}\n${
+        location
+          ? `  --> ${location.file_name}:${location.line_start}:${location.column_start}\n`
+          : ''
+      }`,
+    },
+  }
+}
+
+/**
+ * Run `cargo build` with `--message-format=json`, forward the cargo messages to stdout as JSON lines
+ * and print the rendered diagnostics to stderr.
+ */
+export function runCargoWithDiagnostics(
+  cargoCommand: string,
+  options: { env: NodeJS.ProcessEnv; cwd: string },
+) {
+  return runCargoWithMessages(
+    cargoCommand,
+    'json',
+    options,
+    (message, line) => {
+      process.stdout.write(`${line}\n`)
+      if (message.reason === 'compiler-message' && message.message?.rendered) {
+        process.stderr.write(message.message.rendered)
+      }
+    },
+  )
+}
+
+export function printNapiMessage(warning: TypeDefWarning) {
+  const message = toNapiMessage(warning)
+  process.stdout.write(`${JSON.stringify(message)}\n`)
+  process.stderr.write(message.message.rendered!)
+}
@@ -0,0 +1,104 @@
+import { execSync } from 'child_process'
+
+import { debugFactory } from './debug'
+import { globToRegExp } from './secrets-scan'
+
+const debug = debugFactory('dist-tag')
+
+/**
+ * Branch patterns to the npm dist-tags, `pull_request` for the pull requests.
+ * `{branch}` and `{pr}` in the tags are replaced with the branch name and the pull request number.
+ */
+export type DistTagConfig = Record<string, string>
+
+export const PULL_REQUEST = 'pull_request'
+
+export const DEFAULT_DIST_TAGS: DistTagConfig = {
+  main: 'latest',
+  next: 'next',
+  [PULL_REQUEST]: 'pr-{pr}',
+}
+
+export interface GitRef {
+  branch: string | null
+  pullRequest: number | null
+}
+
+/**
+ * The branch or the pull request being built, from the GitHub Actions env or the checked out branch.
+ */
+export function getGitRef(env: NodeJS.ProcessEnv = process.env): GitRef {
+  const ref = env.GITHUB_REF
+  if (ref) {
+    // refs/pull/123/merge
+    const pullRequest = /^refs\/pull\/(\d+)\//.exec(ref)
+    if (pullRequest) {
+      return {
+        branch: env.GITHUB_HEAD_REF || null,
+        pullRequest: Number(pullRequest[1]),
+      }
+    }
+    return {
+      branch: ref.startsWith('refs/heads/')
+        ? ref.substring('refs/heads/'.length)
+        : null,
+      pullRequest: null,
+    }
+  }
+  try {
+    const branch = execSync('git rev-parse --abbrev-ref HEAD', {
+      stdio: ['ignore', 'pipe', 'ignore'],
+    })
+      .toString('utf8')
+      .trim()
+    return { branch: branch === 'HEAD' ? null : branch, pullRequest: null }
+  } catch (e) {
+    debug(`Get the current branch failed: ${e}`)
+    return { branch: null, pullRequest: null }
+  }
+}
+
+/**
+ * The dist-tag of the first pattern matching the ref, `null` if none of them matches.
+ * Prerelease versions are never published as `latest`, but `next`.
+ */
+export function resolveDistTag(
+  config: DistTagConfig,
+  ref: GitRef,
+  version: string,
+): string | null {
+  const matched = Object.entries(config).find(([pattern]) =>
+    pattern === PULL_REQUEST
+      ? ref.pullRequest !== null
+      : ref.pullRequest === null &&
+        ref.branch !== null &&
+        globToRegExp(pattern, false).test(ref.branch),
+  )
+  if (!matched) {
+    return null
+  }
+  const tag = matched[1]
+    .replace('{pr}', String(ref.pullRequest))
+    .replace('{branch}', (ref.branch ?? '').replace(/[^\w.-]/g, '-'))
+  return tag === 'latest' && version.includes('-') ? 'next' : tag
+}
+
+/**
+ * The `case` of the `GITHUB_REF` setting `DIST_TAG` in the generated workflow, the same as `resolveDistTag`.
+ */
+export function distTagShellCase(config: DistTagConfig) {
+  const branches = Object.entries(config).map(([pattern, tag]) => {
+    const ref =
+      pattern === PULL_REQUEST
+        ? 'refs/pull/*'
+        : `refs/heads/${pattern.replace(/\*\*/g, '*')}`
+    const value = tag
+      .replace('{pr}', '$PR_NUMBER')
+      .replace(
+        '{branch}',
+        `$(printf %s "$GITHUB_REF_NAME" | tr -c 'A-Za-z0-9_.-' '-')`,
+      )
+    return `  ${ref}) DIST_TAG="${value}" ;;`
+  })
+  return ['case "$GITHUB_REF" in', ...branches, 'esac'].join('\n')
+}
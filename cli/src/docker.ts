@@ -0,0 +1,128 @@
+import { spawnSync } from 'child_process'
+import { mkdirSync } from 'fs'
+import { homedir } from 'os'
+import { join, relative, sep } from 'path'
+
+import { version } from '../package.json'
+
+import { debugFactory } from './debug'
+
+const debug = debugFactory('docker')
+
+const DOCKER_IMAGE_PREFIX = 'ghcr.io/napi-rs/napi-rs/nodejs-rust'
+
+export interface DockerBuildConfig {
+  image: string
+  // flags of `napi build` needed by the toolchain in the image
+  args: string[]
+}
+
+/**
+ * The images used by the generated GitHub Actions workflow, shared with `napi build --use-docker`.
+ */
+export const DOCKER_BUILD_CONFIGS: Record<string, DockerBuildConfig> = {
+  'x86_64-unknown-linux-gnu': {
+    image: `${DOCKER_IMAGE_PREFIX}:lts-alpine-zig`,
+    args: ['--zig', '--zig-abi-suffix', '2.12'],
+  },
+  'x86_64-unknown-linux-musl': {
+    image: `${DOCKER_IMAGE_PREFIX}:lts-alpine`,
+    args: [],
+  },
+  'aarch64-unknown-linux-gnu': {
+    image: `${DOCKER_IMAGE_PREFIX}:lts-alpine-zig`,
+    args: ['--zig', '--zig-abi-suffix', '2.17'],
+  },
+  'aarch64-unknown-linux-musl': {
+    image: `${DOCKER_IMAGE_PREFIX}:lts-alpine`,
+    args: [],
+  },
+}
+
+/**
+ * Node.js versions of `nodejs:aarch64-[version]` and `nodejs:armhf-[version]` used by the test jobs of the generated workflow.
+ */
+export const NODE_ARM_IMAGE_VERSIONS = ['12', '14', '16', '17']
+
+// `/root/.cargo` in the images
+const CONTAINER_CARGO_HOME = '/root/.cargo'
+
+const CONTAINER_WORKDIR = '/build'
+
+/**
+ * Run `napi build` with `args` inside the docker image of `target`, `projectDir` is mounted as `/build`.
+ */
+export function buildInDocker(
+  target: string,
+  args: string[],
+  projectDir: string,
+  env = process.env,
+) {
+  const config = DOCKER_BUILD_CONFIGS[target]
+  if (!config) {
+    throw new TypeError(
+      `No docker image for ${target}, --use-docker supports ${Object.keys(
+        DOCKER_BUILD_CONFIGS,
+      ).join(', ')}`,
+    )
+  }
+  const cargoHome = env.CARGO_HOME ?? join(homedir(), '.cargo')
+  const volumes = [`${projectDir}:${CONTAINER_WORKDIR}`]
+  for (const cache of ['registry', 'git']) {
+    const hostDir = join(cargoHome, cache)
+    mkdirSync(hostDir, { recursive: true })
+    volumes.push(`${hostDir}:${CONTAINER_CARGO_HOME}/${cache}`)
+  }
+  // paths like `CARGO_HOME` and the host linkers don't exist in the container
+  const envFlags = Object.keys(env).filter((key) =>
+    /^(DEBUG|RUST_TARGET|CARGO_BUILD_TARGET|CARGO_PROFILE_.+|CARGO_NET_.+|NAPI_RS_.+)$/.test(
+      key,
+    ),
+  )
+  const buildArgs = config.args.filter((arg, index) => {
+    // don't repeat the flags passed by user
+    const flag = arg.startsWith('--') ? arg : config.args[index - 1]
+    return !args.some((a) => a === flag || a.startsWith(`${flag}=`))
+  })
+  const dockerArgs = [
+    'run',
+    '--rm',
+    ...volumes.reduce<string[]>((acc, volume) => acc.concat('-v', volume), []),
+    ...envFlags.reduce<string[]>((acc, key) => acc.concat('-e', key), []),
+    '-w',
+    CONTAINER_WORKDIR,
+    config.image,
+    ...cliCommand(projectDir),
+    'build',
+    ...args,
+    ...buildArgs,
+  ]
+  debug(`Run docker ${dockerArgs.join(' ')}`)
+  const { status, error } = spawnSync('docker', dockerArgs, {
+    stdio: 'inherit',
+    env,
+  })
+  if (error) {
+    throw new Error(`Run docker failed, is docker installed? ${error.message}`)
+  }
+  if (status !== 0) {
+    throw new Error(`Build in ${config.image} failed with exit code ${status}`)
+  }
+}
+
+function cliCommand(projectDir: string): string[] {
+  const script = process.argv[1]
+  const relativeScript = script && relative(projectDir, script)
+  if (
+    relativeScript &&
+    !relativeScript.startsWith('..') &&
+    !relativeScript.includes(':')
+  ) {
+    // the cli installed in node_modules of the project
+    return [
+      'node',
+      `${CONTAINER_WORKDIR}/${relativeScript.split(sep).join('/')}`,
+    ]
+  }
+  return ['npx', `@napi-rs/cli@${version}`]
+}
@@ -0,0 +1,74 @@
+import { basename } from 'path'
+
+import chalk from 'chalk'
+
+import { formatSize, PackResult } from './pack-preview'
+
+export interface DryRunPackage {
+  name: string
+  version: string
+  // the addon or the `index.d.ts` of the package
+  file: string
+  exists: boolean
+  // `null` if the package.json is not existed
+  pack: PackResult | null
+}
+
+/**
+ * Why `npm publish` of the package would be skipped or publish a broken package.
+ */
+export function getDryRunProblems(pkg: DryRunPackage): string[] {
+  const file = basename(pkg.file)
+  if (!pkg.exists) {
+    return [`${file} is not existed, skipped`]
+  }
+  if (!pkg.pack) {
+    return ['package.json is not existed']
+  }
+  const problems: string[] = []
+  if (pkg.pack.version !== pkg.version) {
+    problems.push(
+      `version of package.json is ${pkg.pack.version}, expected ${pkg.version}`,
+    )
+  }
+  if (!pkg.pack.files.some(({ path }) => path === file)) {
+    problems.push(`${file} is not included by the files of package.json`)
+  }
+  return problems
+}
+
+/**
+ * What `napi prepublish` would publish without `--dry-run`, one line per package.
+ */
+export function formatDryRunSummary(
+  packages: DryRunPackage[],
+  publishCommand: string,
+  releaseTag: string | null,
+) {
+  const lines = packages.map((pkg) => {
+    const problems = getDryRunProblems(pkg)
+    const name = `${pkg.name}@${pkg.version}`
+    const size = pkg.pack
+      ? `  tarball ${formatSize(pkg.pack.size)}, unpacked ${formatSize(
+          pkg.pack.unpackedSize,
+        )}, ${pkg.pack.files.length} files`
+      : ''
+    return problems.length
+      ? `  ${chalk.yellowBright(name)}${size}\n${problems
+          .map((problem) => `    ${chalk.red(problem)}`)
+          .join('\n')}`
+      : `  ${chalk.green(name)}${size}`
+  })
+  const published = packages.filter((pkg) => pkg.exists && pkg.pack).length
+  return [
+    `Dry run, ${chalk.green(publishCommand)} would publish ${published} of ${
+      packages.length
+    } packages:`,
+    ...lines,
+    releaseTag
+      ? `The addons would be uploaded to the GitHub release ${chalk.green(
+          releaseTag,
+        )}`
+      : 'No GitHub release would be created',
+  ].join('\n')
+}
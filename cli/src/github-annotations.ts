@@ -0,0 +1,146 @@
+import { spawn } from 'child_process'
+import { isAbsolute, join, relative, sep } from 'path'
+import { createInterface } from 'readline'
+
+import { debugFactory } from './debug'
+import { logStream } from './json-output'
+
+const debug = debugFactory('annotations')
+
+interface DiagnosticSpan {
+  file_name: string
+  line_start: number
+  line_end: number
+  column_start: number
+  column_end: number
+  is_primary: boolean
+}
+
+export interface CargoDiagnostic {
+  message: string
+  code: { code: string } | null
+  level: string
+  spans: DiagnosticSpan[]
+  rendered: string | null
+}
+
+/**
+ * `::error` or `::warning` workflow command of the diagnostic, `null` for the notes and the diagnostics without location.
+ */
+export function toAnnotation(
+  diagnostic: CargoDiagnostic,
+  workspaceRoot: string,
+  githubWorkspace: string,
+): string | null {
+  const command =
+    diagnostic.level === 'warning'
+      ? 'warning'
+      : diagnostic.level.startsWith('error')
+      ? 'error'
+      : null
+  const span = diagnostic.spans.find((span) => span.is_primary)
+  if (!command || !span) {
+    return null
+  }
+  const file = isAbsolute(span.file_name)
+    ? span.file_name
+    : join(workspaceRoot, span.file_name)
+  const properties = {
+    file: relative(githubWorkspace, file).split(sep).join('/'),
+    line: span.line_start,
+    endLine: span.line_end,
+    col: span.column_start,
+    endColumn: span.column_end,
+    title: diagnostic.code?.code ?? `cargo ${command}`,
+  }
+  return `::${command} ${Object.entries(properties)
+    .map(([key, value]) => `${key}=${escapeProperty(String(value))}`)
+    .join(',')}::${escapeData(diagnostic.message)}`
+}
+
+function escapeData(data: string) {
+  return data.replace(/%/g, '%25').replace(/\r/g, '%0D').replace(/\n/g, '%0A')
+}
+
+function escapeProperty(value: string) {
+  return escapeData(value).replace(/:/g, '%3A').replace(/,/g, '%2C')
+}
+
+export interface CargoMessage {
+  reason?: string
+  message?: CargoDiagnostic
+}
+
+/**
+ * Run `cargo build` with `--message-format=<messageFormat>` and pass the parsed cargo messages to `onMessage`,
+ * the lines printed by the build scripts are printed as is.
+ */
+export function runCargoWithMessages(
+  cargoCommand: string,
+  messageFormat: string,
+  options: { env: NodeJS.ProcessEnv; cwd: string },
+  onMessage: (message: CargoMessage, line: string) => void,
+) {
+  const output = logStream()
+  return new Promise<void>((resolve, reject) => {
+    const cargo = spawn(`${cargoCommand} --message-format=${messageFormat}`, {
+      ...options,
+      stdio: ['inherit', 'pipe', 'inherit'],
+      shell: true,
+    })
+    createInterface({ input: cargo.stdout }).on('line', (line) => {
+      let message: CargoMessage
+      try {
+        message = JSON.parse(line)
+      } catch {
+        // not a cargo message, printed by the build scripts
+        output.write(`${line}\n`)
+        return
+      }
+      onMessage(message, line)
+    })
+    cargo.on('error', reject)
+    cargo.on('close', (code) => {
+      debug(`cargo exited with ${code}`)
+      if (code === 0) {
+        resolve()
+      } else {
+        reject(new Error(`Command failed: ${cargoCommand}`))
+      }
+    })
+  })
+}
+
+/**
+ * Run `cargo build` with `--message-format=json-diagnostic-rendered-ansi`,
+ * print the rendered diagnostics as usual and the annotations of them for GitHub Actions.
+ */
+export function runCargoWithAnnotations(
+  cargoCommand: string,
+  workspaceRoot: string,
+  options: { env: NodeJS.ProcessEnv; cwd: string },
+) {
+  const githubWorkspace = options.env.GITHUB_WORKSPACE ?? options.cwd
+  const output = logStream()
+  return runCargoWithMessages(
+    cargoCommand,
+    'json-diagnostic-rendered-ansi',
+    options,
+    (message) => {
+      if (message.reason !== 'compiler-message' || !message.message) {
+        return
+      }
+      if (message.message.rendered) {
+        process.stderr.write(message.message.rendered)
+      }
+      const annotation = toAnnotation(
+        message.message,
+        workspaceRoot,
+        githubWorkspace,
+      )
+      if (annotation) {
+        output.write(`${annotation}\n`)
+      }
+    },
+  )
+}
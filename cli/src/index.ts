@@ -5,11 +5,18 @@ import { Cli } from 'clipanion'
 import { version } from '../package.json'
 
 import { ArtifactsCommand } from './artifacts'
+import { BenchCommand } from './bench'
 import { BuildCommand } from './build'
+import { CompletionsCommand } from './completions'
 import { CreateNpmDirCommand } from './create-npm-dir'
+import { DevCommand } from './dev'
+import { InfoCommand } from './info'
+import { ManCommand } from './man'
 import { NewProjectCommand } from './new'
+import { PackCommand } from './pack'
 import { PrePublishCommand } from './pre-publish'
 import { RenameCommand } from './rename'
+import { TestCommand } from './test'
 import { VersionCommand } from './version'
 
 const cli = new Cli({
@@ -21,9 +28,16 @@ cli.register(ArtifactsCommand)
 cli.register(BuildCommand)
 cli.register(CreateNpmDirCommand)
 cli.register(PrePublishCommand)
+cli.register(PackCommand)
 cli.register(VersionCommand)
 cli.register(NewProjectCommand)
 cli.register(RenameCommand)
+cli.register(DevCommand)
+cli.register(TestCommand)
+cli.register(BenchCommand)
+cli.register(CompletionsCommand)
+cli.register(ManCommand)
+cli.register(InfoCommand)
 
 cli
   .run(process.argv.slice(2), {
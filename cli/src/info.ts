@@ -0,0 +1,194 @@
+import { execSync } from 'child_process'
+import { existsSync } from 'fs'
+import { arch, platform, release } from 'os'
+import { join } from 'path'
+
+import { Command, Option } from 'clipanion'
+
+import { version as cliVersion } from '../package.json'
+
+import { getNapiConfig } from './consts'
+import { debugFactory } from './debug'
+import { enableJsonOutput, JsonOption, printJson } from './json-output'
+import { detectPackageManager } from './package-manager'
+
+const debug = debugFactory('info')
+
+// the env variables changing how the addons are built and published
+const ENV_NAMES = [
+  'RUSTFLAGS',
+  'CC',
+  'CXX',
+  'AR',
+  'CI',
+  'GITHUB_ACTIONS',
+  'ANDROID_NDK_HOME',
+  'ANDROID_NDK_LATEST_HOME',
+  'NODE_OPTIONS',
+  'HTTPS_PROXY',
+  'HTTP_PROXY',
+  'NO_PROXY',
+  'RELEASE_VERSION',
+  'npm_config_user_agent',
+]
+
+const ENV_PREFIXES = ['NAPI_RS_', 'CARGO_', 'RUSTC', 'RUSTUP_', 'RUST_']
+
+const SECRET_NAME = /TOKEN|SECRET|PASSWORD|CREDENTIAL|AUTH|KEY/i
+
+export interface EnvironmentReport {
+  cli: string
+  node: { version: string; napi: string | null }
+  os: { platform: string; release: string; arch: string; libc: string | null }
+  rust: {
+    rustc: string | null
+    cargo: string | null
+    toolchain: string | null
+    installedTargets: string[]
+  }
+  packageManager: string
+  // `triples` of the napi config
+  targets: string[]
+  napiConfig: unknown
+  env: Record<string, string>
+}
+
+function tryExec(command: string) {
+  try {
+    return execSync(command, { stdio: ['ignore', 'pipe', 'ignore'] })
+      .toString('utf8')
+      .trim()
+  } catch (e) {
+    debug(`${command} failed: ${e}`)
+    return null
+  }
+}
+
+function getLibc() {
+  if (platform() !== 'linux') {
+    return null
+  }
+  const header = (process.report?.getReport() as any)?.header
+  if (header?.glibcVersionRuntime) {
+    return `glibc ${header.glibcVersionRuntime}`
+  }
+  return tryExec('ldd --version 2>&1')?.includes('musl') ? 'musl' : null
+}
+
+/**
+ * The relevant env variables, the secrets are redacted and so are the credentials of the proxies.
+ */
+export function collectEnv(env: NodeJS.ProcessEnv = process.env) {
+  return Object.keys(env)
+    .filter(
+      (name) =>
+        ENV_NAMES.includes(name) ||
+        ENV_PREFIXES.some((prefix) => name.startsWith(prefix)),
+    )
+    .sort()
+    .reduce<Record<string, string>>((acc, name) => {
+      const value = env[name] ?? ''
+      if (SECRET_NAME.test(name)) {
+        acc[name] = '<redacted>'
+      } else if (/PROXY$/.test(name) && /:\/\/[^/]*@/.test(value)) {
+        acc[name] = value.replace(/:\/\/[^/]*@/, '://<redacted>@')
+      } else {
+        acc[name] = value
+      }
+      return acc
+    }, {})
+}
+
+export function collectReport(
+  configFileName = 'package.json',
+  cwd = process.cwd(),
+): EnvironmentReport {
+  let targets: string[] = []
+  let napiConfig: unknown = null
+  if (existsSync(join(cwd, configFileName))) {
+    const config = getNapiConfig(configFileName, cwd)
+    targets = config.platforms.map(({ raw }) => raw)
+    napiConfig = config.napiConfig
+  }
+  return {
+    cli: cliVersion,
+    node: { version: process.version, napi: process.versions.napi ?? null },
+    os: {
+      platform: platform(),
+      release: release(),
+      arch: arch(),
+      libc: getLibc(),
+    },
+    rust: {
+      rustc: tryExec('rustc --version'),
+      cargo: tryExec('cargo --version'),
+      toolchain: tryExec('rustup show active-toolchain'),
+      installedTargets: (tryExec('rustup target list --installed') ?? '')
+        .split('\n')
+        .map((line) => line.trim())
+        .filter(Boolean),
+    },
+    packageManager: detectPackageManager(cwd),
+    targets,
+    napiConfig,
+    env: collectEnv(),
+  }
+}
+
+/**
+ * The report in markdown, to be pasted into the issues.
+ */
+export function formatReport(report: EnvironmentReport) {
+  const { cli, node, os, rust } = report
+  const unknown = (value: string | null) => value ?? 'not found'
+  const env = Object.entries(report.env)
+  return `### Environment
+
+- @napi-rs/cli: ${cli}
+- Node.js: ${node.version}${node.napi ? ` (N-API ${node.napi})` : ''}
+- OS: ${os.platform} ${os.release} ${os.arch}${os.libc ? ` ${os.libc}` : ''}
+- rustc: ${unknown(rust.rustc)}
+- cargo: ${unknown(rust.cargo)}
+- rustup toolchain: ${unknown(rust.toolchain)}
+- installed targets: ${rust.installedTargets.join(', ') || 'none'}
+- package manager: ${report.packageManager}
+- configured targets: ${report.targets.join(', ') || 'none'}
+
+### napi config
+
+\`\`\`json
+${JSON.stringify(report.napiConfig, null, 2)}
+\`\`\`
+
+### Env
+
+\`\`\`
+${env.length ? env.map(([name, value]) => `${name}=${value}`).join('\n') : '-'}
+\`\`\`
+`
+}
+
+export class InfoCommand extends Command {
+  static usage = Command.Usage({
+    description:
+      'Print the environment report of the bug reports, nothing is sent anywhere',
+  })
+
+  static paths = [['info']]
+
+  configFileName?: string = Option.String('-c,--config')
+
+  json = JsonOption()
+
+  async execute() {
+    if (this.json) {
+      enableJsonOutput()
+    }
+    const report = collectReport(this.configFileName)
+    if (this.json) {
+      printJson(report)
+    } else {
+      this.context.stdout.write(formatReport(report))
+    }
+  }
+}
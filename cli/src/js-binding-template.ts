@@ -1,7 +1,92 @@
+import { CpuVariants, createCpuVariantsBinding } from './cpu-variants'
+
+export interface JsBindingOptions {
+  // detect the libc by the filesystem under Bun, which has no glibc version in `process.report`
+  bun?: boolean
+  // same for Deno, the ESM entry of `createDenoEntry` loads the binding
+  deno?: boolean
+  // other than `napi.name`, the `.node` file is required from the platform package by the subpath
+  subpath?: boolean
+  // `napi.variants`, the `.node` files built by `napi build --variant` are preferred if the CPU supports them
+  cpuVariants?: CpuVariants
+}
+
+/**
+ * `binaryDir` is the dir of the `.node` files relative to the dir of the binding, in posix.
+ */
 export const createJsBinding = (
   localName: string,
   pkgName: string,
-) => `const { existsSync, readFileSync } = require('fs')
+  binaryDir = '.',
+  {
+    bun = false,
+    deno = false,
+    subpath = false,
+    cpuVariants = {},
+  }: JsBindingOptions = {},
+) => {
+  const hasCpuVariants = Object.keys(cpuVariants).length > 0
+  // the JS expression of the module id
+  const platformPackage = (platformArchABI: string) =>
+    hasCpuVariants
+      ? `packageFile('${pkgName}-${platformArchABI}', '${localName}.${platformArchABI}.node')`
+      : subpath
+      ? `'${pkgName}-${platformArchABI}/${localName}.${platformArchABI}.node'`
+      : `'${pkgName}-${platformArchABI}'`
+  const dirArgs = binaryDir === '.' ? '__dirname' : `__dirname, '${binaryDir}'`
+  const requirePrefix = binaryDir.startsWith('.') ? binaryDir : `./${binaryDir}`
+  const runtimes = [bun && 'Bun', deno && 'Deno'].filter(
+    (runtime): runtime is string => !!runtime,
+  )
+  const runtimeMusl = runtimes.length
+    ? `  // \`process.report\` of ${runtimes.join(
+        ' and ',
+      )} doesn't tell the libc of the system
+  if (${runtimes
+    .map((runtime) => `process.versions.${runtime.toLowerCase()}`)
+    .join(' || ')}) {
+    try {
+      return readFileSync('/usr/bin/ldd', 'utf8').includes('musl')
+    } catch (e) {
+      try {
+        return readdirSync('/lib').some((file) => file.startsWith('ld-musl-'))
+      } catch (e) {
+        return false
+      }
+    }
+  }
+`
+    : ''
+  const cpuVariantsCode = hasCpuVariants
+    ? `${createCpuVariantsBinding(cpuVariants)}
+// prefer the binaries built by \`napi build --variant\` the CPU supports, in the order of \`napi.variants\`
+function variantFile(file) {
+  for (const variant of supportedVariants()) {
+    const variantFile = file.replace(/\\.node$/, \`.\${variant}.node\`)
+    if (existsSync(join(${dirArgs}, variantFile))) {
+      return variantFile
+    }
+  }
+  return file
+}
+
+function packageFile(pkg, file) {
+  for (const variant of supportedVariants()) {
+    try {
+      return require.resolve(
+        \`\${pkg}/\${file.replace(/\\.node$/, \`.\${variant}.node\`)}\`
+      )
+    } catch (e) {
+      // not published with the package
+    }
+  }
+  return ${subpath ? '`${pkg}/${file}`' : 'pkg'}
+}
+`
+    : ''
+  return `const { existsSync, ${
+    runtimes.length ? 'readdirSync, ' : ''
+  }readFileSync } = require('fs')
 const { join } = require('path')
 
 const { platform, arch } = process
@@ -11,7 +96,7 @@ let localFileExisted = false
 let loadError = null
 
 function isMusl() {
-  // For Node 10
+${runtimeMusl}  // For Node 10
   if (!process.report || typeof process.report.getReport !== 'function') {
     try {
       return readFileSync('/usr/bin/ldd', 'utf8').includes('musl')
@@ -24,28 +109,39 @@ function isMusl() {
   }
 }
 
+// prefer the binary built by \`napi build --electron-version\` while running in Electron
+function localFile(file) {
+  if (process.versions.electron) {
+    const electronFile = file.replace(/\\.node$/, '.electron.node')
+    if (existsSync(join(${dirArgs}, electronFile))) {
+      return ${hasCpuVariants ? 'variantFile(electronFile)' : 'electronFile'}
+    }
+  }
+  return ${hasCpuVariants ? 'variantFile(file)' : 'file'}
+}
+${cpuVariantsCode}
 switch (platform) {
   case 'android':
     switch (arch) {
       case 'arm64':
-        localFileExisted = existsSync(join(__dirname, '${localName}.android-arm64.node'))
+        localFileExisted = existsSync(join(${dirArgs}, localFile('${localName}.android-arm64.node')))
         try {
           if (localFileExisted) {
-            nativeBinding = require('./${localName}.android-arm64.node')
+            nativeBinding = require(\`${requirePrefix}/\${localFile('${localName}.android-arm64.node')}\`)
           } else {
-            nativeBinding = require('${pkgName}-android-arm64')
+            nativeBinding = require(${platformPackage('android-arm64')})
           }
         } catch (e) {
           loadError = e
         }
         break
       case 'arm':
-        localFileExisted = existsSync(join(__dirname, '${localName}.android-arm-eabi.node'))
+        localFileExisted = existsSync(join(${dirArgs}, localFile('${localName}.android-arm-eabi.node')))
         try {
           if (localFileExisted) {
-            nativeBinding = require('./${localName}.android-arm-eabi.node')
+            nativeBinding = require(\`${requirePrefix}/\${localFile('${localName}.android-arm-eabi.node')}\`)
           } else {
-            nativeBinding = require('${pkgName}-android-arm-eabi')
+            nativeBinding = require(${platformPackage('android-arm-eabi')})
           }
         } catch (e) {
           loadError = e
@@ -59,13 +155,13 @@ switch (platform) {
     switch (arch) {
       case 'x64':
         localFileExisted = existsSync(
-          join(__dirname, '${localName}.win32-x64-msvc.node')
+          join(${dirArgs}, localFile('${localName}.win32-x64-msvc.node'))
         )
         try {
           if (localFileExisted) {
-            nativeBinding = require('./${localName}.win32-x64-msvc.node')
+            nativeBinding = require(\`${requirePrefix}/\${localFile('${localName}.win32-x64-msvc.node')}\`)
           } else {
-            nativeBinding = require('${pkgName}-win32-x64-msvc')
+            nativeBinding = require(${platformPackage('win32-x64-msvc')})
           }
         } catch (e) {
           loadError = e
@@ -73,13 +169,13 @@ switch (platform) {
         break
       case 'ia32':
         localFileExisted = existsSync(
-          join(__dirname, '${localName}.win32-ia32-msvc.node')
+          join(${dirArgs}, localFile('${localName}.win32-ia32-msvc.node'))
         )
         try {
           if (localFileExisted) {
-            nativeBinding = require('./${localName}.win32-ia32-msvc.node')
+            nativeBinding = require(\`${requirePrefix}/\${localFile('${localName}.win32-ia32-msvc.node')}\`)
           } else {
-            nativeBinding = require('${pkgName}-win32-ia32-msvc')
+            nativeBinding = require(${platformPackage('win32-ia32-msvc')})
           }
         } catch (e) {
           loadError = e
@@ -87,13 +183,13 @@ switch (platform) {
         break
       case 'arm64':
         localFileExisted = existsSync(
-          join(__dirname, '${localName}.win32-arm64-msvc.node')
+          join(${dirArgs}, localFile('${localName}.win32-arm64-msvc.node'))
         )
         try {
           if (localFileExisted) {
-            nativeBinding = require('./${localName}.win32-arm64-msvc.node')
+            nativeBinding = require(\`${requirePrefix}/\${localFile('${localName}.win32-arm64-msvc.node')}\`)
           } else {
-            nativeBinding = require('${pkgName}-win32-arm64-msvc')
+            nativeBinding = require(${platformPackage('win32-arm64-msvc')})
           }
         } catch (e) {
           loadError = e
@@ -106,12 +202,12 @@ switch (platform) {
   case 'darwin':
     switch (arch) {
       case 'x64':
-        localFileExisted = existsSync(join(__dirname, '${localName}.darwin-x64.node'))
+        localFileExisted = existsSync(join(${dirArgs}, localFile('${localName}.darwin-x64.node')))
         try {
           if (localFileExisted) {
-            nativeBinding = require('./${localName}.darwin-x64.node')
+            nativeBinding = require(\`${requirePrefix}/\${localFile('${localName}.darwin-x64.node')}\`)
           } else {
-            nativeBinding = require('${pkgName}-darwin-x64')
+            nativeBinding = require(${platformPackage('darwin-x64')})
           }
         } catch (e) {
           loadError = e
@@ -119,13 +215,13 @@ switch (platform) {
         break
       case 'arm64':
         localFileExisted = existsSync(
-          join(__dirname, '${localName}.darwin-arm64.node')
+          join(${dirArgs}, localFile('${localName}.darwin-arm64.node'))
         )
         try {
           if (localFileExisted) {
-            nativeBinding = require('./${localName}.darwin-arm64.node')
+            nativeBinding = require(\`${requirePrefix}/\${localFile('${localName}.darwin-arm64.node')}\`)
           } else {
-            nativeBinding = require('${pkgName}-darwin-arm64')
+            nativeBinding = require(${platformPackage('darwin-arm64')})
           }
         } catch (e) {
           loadError = e
@@ -139,12 +235,12 @@ switch (platform) {
     if (arch !== 'x64') {
       throw new Error(\`Unsupported architecture on FreeBSD: \${arch}\`)
     }
-    localFileExisted = existsSync(join(__dirname, '${localName}.freebsd-x64.node'))
+    localFileExisted = existsSync(join(${dirArgs}, localFile('${localName}.freebsd-x64.node')))
     try {
       if (localFileExisted) {
-        nativeBinding = require('./${localName}.freebsd-x64.node')
+        nativeBinding = require(\`${requirePrefix}/\${localFile('${localName}.freebsd-x64.node')}\`)
       } else {
-        nativeBinding = require('${pkgName}-freebsd-x64')
+        nativeBinding = require(${platformPackage('freebsd-x64')})
       }
     } catch (e) {
       loadError = e
@@ -155,26 +251,26 @@ switch (platform) {
       case 'x64':
         if (isMusl()) {
           localFileExisted = existsSync(
-            join(__dirname, '${localName}.linux-x64-musl.node')
+            join(${dirArgs}, localFile('${localName}.linux-x64-musl.node'))
           )
           try {
             if (localFileExisted) {
-              nativeBinding = require('./${localName}.linux-x64-musl.node')
+              nativeBinding = require(\`${requirePrefix}/\${localFile('${localName}.linux-x64-musl.node')}\`)
             } else {
-              nativeBinding = require('${pkgName}-linux-x64-musl')
+              nativeBinding = require(${platformPackage('linux-x64-musl')})
             }
           } catch (e) {
             loadError = e
           }
         } else {
           localFileExisted = existsSync(
-            join(__dirname, '${localName}.linux-x64-gnu.node')
+            join(${dirArgs}, localFile('${localName}.linux-x64-gnu.node'))
           )
           try {
             if (localFileExisted) {
-              nativeBinding = require('./${localName}.linux-x64-gnu.node')
+              nativeBinding = require(\`${requirePrefix}/\${localFile('${localName}.linux-x64-gnu.node')}\`)
             } else {
-              nativeBinding = require('${pkgName}-linux-x64-gnu')
+              nativeBinding = require(${platformPackage('linux-x64-gnu')})
             }
           } catch (e) {
             loadError = e
@@ -184,26 +280,26 @@ switch (platform) {
       case 'arm64':
         if (isMusl()) {
           localFileExisted = existsSync(
-            join(__dirname, '${localName}.linux-arm64-musl.node')
+            join(${dirArgs}, localFile('${localName}.linux-arm64-musl.node'))
           )
           try {
             if (localFileExisted) {
-              nativeBinding = require('./${localName}.linux-arm64-musl.node')
+              nativeBinding = require(\`${requirePrefix}/\${localFile('${localName}.linux-arm64-musl.node')}\`)
             } else {
-              nativeBinding = require('${pkgName}-linux-arm64-musl')
+              nativeBinding = require(${platformPackage('linux-arm64-musl')})
             }
           } catch (e) {
             loadError = e
           }
         } else {
           localFileExisted = existsSync(
-            join(__dirname, '${localName}.linux-arm64-gnu.node')
+            join(${dirArgs}, localFile('${localName}.linux-arm64-gnu.node'))
           )
           try {
             if (localFileExisted) {
-              nativeBinding = require('./${localName}.linux-arm64-gnu.node')
+              nativeBinding = require(\`${requirePrefix}/\${localFile('${localName}.linux-arm64-gnu.node')}\`)
             } else {
-              nativeBinding = require('${pkgName}-linux-arm64-gnu')
+              nativeBinding = require(${platformPackage('linux-arm64-gnu')})
             }
           } catch (e) {
             loadError = e
@@ -212,13 +308,13 @@ switch (platform) {
         break
       case 'arm':
         localFileExisted = existsSync(
-          join(__dirname, '${localName}.linux-arm-gnueabihf.node')
+          join(${dirArgs}, localFile('${localName}.linux-arm-gnueabihf.node'))
         )
         try {
           if (localFileExisted) {
-            nativeBinding = require('./${localName}.linux-arm-gnueabihf.node')
+            nativeBinding = require(\`${requirePrefix}/\${localFile('${localName}.linux-arm-gnueabihf.node')}\`)
           } else {
-            nativeBinding = require('${pkgName}-linux-arm-gnueabihf')
+            nativeBinding = require(${platformPackage('linux-arm-gnueabihf')})
           }
         } catch (e) {
           loadError = e
@@ -240,3 +336,25 @@ if (!nativeBinding) {
 }
 
 `
+}
+
+/**
+ * ESM entry for Deno, which requires the CommonJS binding by \`createRequire\` of its \`node:\` compatibility layer,
+ * the platform packages are resolved from \`node_modules\` or the \`npm:\` specifiers like Node.js.
+ * \`binding\` and \`dts\` are relative to the dir of the entry, in posix.
+ */
+export const createDenoEntry = (
+  binding: string,
+  dts: string,
+  idents: string[],
+) => `// @ts-self-types="${dts}"
+import { CpuVariants, createCpuVariantsBinding } from './cpu-variants'
+
+import { createRequire } from 'node:module'
+
+const require = createRequire(import.meta.url)
+const nativeBinding = require('${binding}')
+
+export const { ${idents.join(', ')} } = nativeBinding
+export default nativeBinding
+`
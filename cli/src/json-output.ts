@@ -0,0 +1,35 @@
+import { StdioOptions } from 'child_process'
+
+import { Option } from 'clipanion'
+
+let jsonOutput = false
+
+export const JsonOption = () =>
+  Option.Boolean('--json', false, {
+    description:
+      'Print the result as JSON on stdout, the logs are printed on stderr',
+  })
+
+/**
+ * Move the logs and the stdout of the child processes to stderr, so stdout only has the JSON result.
+ */
+export function enableJsonOutput() {
+  jsonOutput = true
+  console.log = console.error
+  console.info = console.error
+}
+
+/**
+ * `stdio` of the child processes whose output are logs.
+ */
+export function logStdio(): StdioOptions {
+  return jsonOutput ? ['inherit', 2, 'inherit'] : 'inherit'
+}
+
+export function logStream(): NodeJS.WritableStream {
+  return jsonOutput ? process.stderr : process.stdout
+}
+
+export function printJson(result: unknown) {
+  process.stdout.write(`${JSON.stringify(result, null, 2)}\n`)
+}
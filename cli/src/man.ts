@@ -0,0 +1,82 @@
+import { Command } from 'clipanion'
+
+import { ANSI_PATTERN, CommandDefinition } from './completions'
+
+export interface ManPageDefinition extends CommandDefinition {
+  details?: string
+  examples?: [string, string][]
+}
+
+function roff(text = '') {
+  return text
+    .replace(ANSI_PATTERN, '')
+    .replace(/\\/g, '\\e')
+    .replace(/-/g, '\\-')
+    .split('\n')
+    .map((line) => line.trim())
+    .map((line) => (/^[.']/.test(line) ? `\\&${line}` : line))
+    .join('\n')
+    .trim()
+}
+
+/**
+ * `napi(1)` in roff, with all the commands, their options and examples.
+ */
+export function generateManPage(
+  definitions: ManPageDefinition[],
+  version: string,
+) {
+  const sections = [...definitions]
+    .sort((a, b) => a.path.localeCompare(b.path))
+    .map(({ path, description, details, options, examples }) => {
+      const lines = [`.SS "${roff(path).replace(/"/g, '\\(dq')}"`]
+      if (description) {
+        lines.push(roff(description))
+      }
+      if (details) {
+        lines.push('.PP', roff(details))
+      }
+      for (const option of options) {
+        const [names, ...args] = option.definition.split(' ')
+        lines.push(
+          '.TP',
+          `.B ${roff(names.split(',').join(', '))}${
+            args.length ? ` \\fIvalue\\fR` : ''
+          }`,
+          roff(option.description),
+        )
+      }
+      for (const [title, command] of examples ?? []) {
+        lines.push('.PP', roff(title), '.IP', `.B ${roff(command)}`)
+      }
+      return lines.join('\n')
+    })
+  return `.TH NAPI 1 "" "napi ${version}" "napi-rs"
+.SH NAME
+napi \\- Cli tools for napi-rs
+.SH SYNOPSIS
+.B napi
+\\fIcommand\\fR [\\fIoptions\\fR]
+.SH COMMANDS
+${sections.join('\n')}
+.SH SEE ALSO
+https://napi.rs
+`
+}
+
+export class ManCommand extends Command {
+  static usage = Command.Usage({
+    description: 'Print the man page of napi',
+    examples: [
+      ['Install the man page', 'napi man > /usr/local/share/man/man1/napi.1'],
+    ],
+  })
+
+  static paths = [['man']]
+
+  async execute() {
+    this.context.stdout.write(
+      generateManPage(this.cli.definitions(), this.cli.binaryVersion ?? ''),
+    )
+  }
+}
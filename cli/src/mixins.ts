@@ -0,0 +1,121 @@
+import { existsSync } from 'fs'
+import { dirname, relative, resolve, sep } from 'path'
+
+import chalk from 'chalk'
+
+import { toLongPath } from './utils'
+
+/**
+ * The entry of `napi.mixins`, which maps the JS names of the classes to the CommonJS files relative to package.json.
+ * The own properties of `module.exports` of the file are defined onto the prototype of the class by the JS binding,
+ * and the `.d.ts` of the file is merged into the class.
+ */
+export interface Mixin {
+  className: string
+  path: string
+  dts: string
+}
+
+// `./animal.js` is typed by `./animal.d.ts`, `./animal.cjs` by `./animal.d.cts`
+export function dtsOf(path: string) {
+  return path.endsWith('.cjs')
+    ? path.replace(/\.cjs$/, '.d.cts')
+    : path.replace(/\.js$/, '') + '.d.ts'
+}
+
+export function resolveMixins(
+  mixins: Record<string, string> | undefined,
+  packageJsonDir: string,
+): Mixin[] {
+  return Object.entries(mixins ?? {}).map(([className, file]) => {
+    if (typeof file !== 'string' || !/\.c?js$/.test(file)) {
+      throw new TypeError(
+        `The mixin of ${chalk.green(className)} must be a ${chalk.green(
+          '.js',
+        )} or ${chalk.green('.cjs')} file, received ${chalk.yellowBright(
+          String(file),
+        )}`,
+      )
+    }
+    const path = resolve(packageJsonDir, file)
+    const dts = dtsOf(path)
+    for (const required of [path, dts]) {
+      if (!existsSync(toLongPath(required))) {
+        throw new TypeError(
+          `The mixin file [${chalk.yellowBright(required)}] of ${chalk.green(
+            className,
+          )} is not existed`,
+        )
+      }
+    }
+    return { className, path, dts }
+  })
+}
+
+/**
+ * Only the top level classes can be extended, the namespaced ones are not exported by the binding directly.
+ */
+export function checkMixinClasses(mixins: Mixin[], classNames: string[]) {
+  for (const { className } of mixins) {
+    if (!classNames.includes(className)) {
+      throw new TypeError(
+        `The mixin of ${chalk.green(
+          className,
+        )} doesn't match any top level class, expected one of ${
+          classNames.join(', ') || 'nothing'
+        }`,
+      )
+    }
+  }
+}
+
+function relativeImport(from: string, path: string) {
+  const relativePath = relative(from, path).split(sep).join('/')
+  return relativePath.startsWith('.') ? relativePath : `./${relativePath}`
+}
+
+/**
+ * Appended to the JS binding after the classes are declared, the helper is prefixed not to collide with the exports.
+ * The members of the native class are never replaced, the conflicts throw while loading the binding.
+ */
+export function createMixinsBinding(mixins: Mixin[], bindingPath: string) {
+  if (!mixins.length) {
+    return ''
+  }
+  const bindingDir = dirname(bindingPath)
+  return `
+function __applyMixin(Class, mixin, path) {
+  for (const key of Reflect.ownKeys(mixin)) {
+    if (Object.prototype.hasOwnProperty.call(Class.prototype, key)) {
+      throw new Error(\`The mixin \${path} overrides \${Class.name}.prototype.\${String(key)}\`)
+    }
+    Object.defineProperty(Class.prototype, key, {
+      ...Object.getOwnPropertyDescriptor(mixin, key),
+      enumerable: false,
+    })
+  }
+}
+
+${mixins
+  .map(({ className, path }) => {
+    const importPath = relativeImport(bindingDir, path)
+    return `__applyMixin(${className}, require('${importPath}'), '${importPath}')`
+  })
+  .join('\n')}
+`
+}
+
+/**
+ * Declaration merging of the class and the interface extending the types of the mixin.
+ * `extends` only accepts the names, so the type of the module is aliased first.
+ */
+export function createMixinDts(mixin: Mixin, dtsPath: string) {
+  const importPath = relativeImport(
+    dirname(dtsPath),
+    mixin.dts.replace(/\.d\.ts$/, '').replace(/\.d\.cts$/, '.cjs'),
+  )
+  const alias = `__${mixin.className}Mixin`
+  return `type ${alias} = typeof import('${importPath}')
+export interface ${mixin.className} extends ${alias} {}
+`
+}
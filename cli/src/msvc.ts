@@ -0,0 +1,61 @@
+import { execSync } from 'child_process'
+import { existsSync, readdirSync } from 'fs'
+import { join } from 'path'
+
+import { debugFactory } from './debug'
+
+const debug = debugFactory('msvc')
+
+export const MSVC_ARM64_COMPONENT =
+  'Microsoft.VisualStudio.Component.VC.Tools.ARM64'
+
+/**
+ * Visual Studio installations from `VSINSTALLDIR`, or all the installations `vswhere` knows.
+ */
+export function findVisualStudioInstallations(env = process.env): string[] {
+  if (env.VSINSTALLDIR && existsSync(env.VSINSTALLDIR)) {
+    return [env.VSINSTALLDIR]
+  }
+  const vswhere = join(
+    env['ProgramFiles(x86)'] ?? 'C:\\Program Files (x86)',
+    'Microsoft Visual Studio',
+    'Installer',
+    'vswhere.exe',
+  )
+  if (!existsSync(vswhere)) {
+    return []
+  }
+  try {
+    return execSync(
+      `"${vswhere}" -all -products * -property installationPath`,
+      { encoding: 'utf8' },
+    )
+      .split(/\r?\n/)
+      .map((line) => line.trim())
+      .filter(Boolean)
+  } catch (e) {
+    debug(`Run vswhere failed: ${e}`)
+    return []
+  }
+}
+
+/**
+ * The newest `VC/Tools/MSVC/<version>/lib/arm64` with the CRT libs in the installations.
+ */
+export function findMsvcArm64Libs(installations: string[]): string | null {
+  for (const installation of installations) {
+    const msvcDir = join(installation, 'VC', 'Tools', 'MSVC')
+    if (!existsSync(msvcDir)) {
+      continue
+    }
+    const arm64Lib = readdirSync(msvcDir)
+      .sort((a, b) => b.localeCompare(a, undefined, { numeric: true }))
+      .map((version) => join(msvcDir, version, 'lib', 'arm64'))
+      .find((lib) => existsSync(join(lib, 'msvcrt.lib')))
+    if (arm64Lib) {
+      debug(`ARM64 MSVC libs: ${arm64Lib}`)
+      return arm64Lib
+    }
+  }
+  return null
+}
@@ -0,0 +1,254 @@
+import { readFileSync } from 'fs'
+import { Agent } from 'https'
+import { connect as netConnect, Socket } from 'net'
+import { connect as tlsConnect, rootCertificates } from 'tls'
+
+import chalk from 'chalk'
+
+import { debugFactory } from './debug'
+import { assertOnline } from './offline'
+import { toLongPath } from './utils'
+
+const debug = debugFactory('network')
+
+// errors of the flaky networks, the requests are retried
+const RETRY_CODES = [
+  'ECONNRESET',
+  'ETIMEDOUT',
+  'ECONNREFUSED',
+  'EAI_AGAIN',
+  'EPIPE',
+  'ENETUNREACH',
+]
+
+const TLS_ERROR_PATTERN =
+  /certificate|self[- ]signed|CERT_|unable to verify|ERR_TLS|SSL routines/i
+
+/**
+ * The CA bundle of the TLS intercepting proxies, `NAPI_RS_CA_FILE` or the `cafile` of npm.
+ */
+export function getCaFile(env: NodeJS.ProcessEnv = process.env) {
+  return env.NAPI_RS_CA_FILE || env.npm_config_cafile || null
+}
+
+/**
+ * `HTTPS_PROXY` or `HTTP_PROXY` of the url, `null` if there is no proxy or the host matches `NO_PROXY`.
+ */
+export function getProxyForUrl(
+  url: string,
+  env: NodeJS.ProcessEnv = process.env,
+): string | null {
+  const { protocol, hostname, port } = new URL(url)
+  const proxy =
+    protocol === 'https:'
+      ? env.HTTPS_PROXY || env.https_proxy || env.HTTP_PROXY || env.http_proxy
+      : env.HTTP_PROXY || env.http_proxy
+  if (!proxy) {
+    return null
+  }
+  const targetPort = port || (protocol === 'https:' ? '443' : '80')
+  const bypass = (env.NO_PROXY || env.no_proxy || '')
+    .split(/[,\s]+/)
+    .filter(Boolean)
+    .some((entry) => {
+      if (entry === '*') {
+        return true
+      }
+      const [host, entryPort] = entry.split(':')
+      if (entryPort && entryPort !== targetPort) {
+        return false
+      }
+      // `example.com`, `.example.com` and `*.example.com` match the subdomains
+      const domain = host.replace(/^\*?\./, '')
+      return hostname === domain || hostname.endsWith(`.${domain}`)
+    })
+  return bypass ? null : proxy
+}
+
+/**
+ * Agent of the HTTPS requests to `url`, tunneled through the proxy and trusting the extra CA bundle.
+ */
+export function createAgent(
+  url: string,
+  env: NodeJS.ProcessEnv = process.env,
+) {
+  assertOnline(`Request to ${url}`, env)
+  const caFile = getCaFile(env)
+  let ca: string[] | undefined
+  if (caFile) {
+    try {
+      ca = [...rootCertificates, readFileSync(toLongPath(caFile), 'utf8')]
+    } catch (e) {
+      throw new TypeError(`Could not read the CA bundle [${caFile}]: ${e}`)
+    }
+  }
+  const agent = new Agent({ keepAlive: true, ca })
+  const proxy = getProxyForUrl(url, env)
+  if (proxy) {
+    debug(`Request ${url} through the proxy ${chalk.green(proxy)}`)
+    const proxyUrl = new URL(proxy)
+    // the Agent waits for the callback when `createConnection` returns nothing
+    ;(agent as any).createConnection = (
+      options: { host: string; port: number; servername?: string },
+      callback: (err: Error | null, socket?: Socket) => void,
+    ) => {
+      tunnel(proxyUrl, options.host, options.port, ca, (err, socket) => {
+        if (err) {
+          callback(err)
+        } else {
+          callback(
+            null,
+            tlsConnect({
+              socket,
+              servername: options.servername || options.host,
+              ca,
+            }),
+          )
+        }
+      })
+    }
+  }
+  return agent
+}
+
+function tunnel(
+  proxy: URL,
+  host: string,
+  port: number,
+  ca: string[] | undefined,
+  callback: (err: Error | null, socket?: Socket) => void,
+) {
+  const proxyPort =
+    Number(proxy.port) || (proxy.protocol === 'https:' ? 443 : 80)
+  const socket: Socket =
+    proxy.protocol === 'https:'
+      ? tlsConnect({
+          host: proxy.hostname,
+          port: proxyPort,
+          servername: proxy.hostname,
+          ca,
+        })
+      : netConnect({ host: proxy.hostname, port: proxyPort })
+  const target = `${host}:${port}`
+  const headers = [`CONNECT ${target} HTTP/1.1`, `Host: ${target}`]
+  if (proxy.username) {
+    const credentials = `${decodeURIComponent(
+      proxy.username,
+    )}:${decodeURIComponent(proxy.password)}`
+    headers.push(
+      `Proxy-Authorization: Basic ${Buffer.from(credentials).toString(
+        'base64',
+      )}`,
+    )
+  }
+  let response = ''
+  const onError = (err: NodeJS.ErrnoException) => {
+    callback(
+      Object.assign(
+        new Error(`Connect to the proxy ${proxy.host} failed: ${err.message}`),
+        { code: err.code },
+      ),
+    )
+  }
+  const onData = (chunk: Buffer) => {
+    response += chunk.toString('latin1')
+    if (!response.includes('\r\n\r\n')) {
+      return
+    }
+    socket.removeListener('data', onData)
+    socket.removeListener('error', onError)
+    const statusLine = response.split('\r\n')[0]
+    if (/^HTTP\/1\.[01] 200/.test(statusLine)) {
+      callback(null, socket)
+    } else {
+      socket.destroy()
+      callback(
+        new Error(
+          `The proxy ${proxy.host} responded [${statusLine}] to CONNECT ${target}`,
+        ),
+      )
+    }
+  }
+  socket.on('error', onError)
+  socket.on('data', onData)
+  socket.write(`${headers.join('\r\n')}\r\n\r\n`)
+}
+
+/**
+ * Env of the child processes accessing the network, `npm`, `aws`, `gsutil` and `az`, so they trust the CA bundle as well.
+ * They read the proxy from `HTTPS_PROXY` and `NO_PROXY` themselves.
+ */
+export function networkEnv(
+  env: NodeJS.ProcessEnv = process.env,
+): NodeJS.ProcessEnv {
+  const caFile = getCaFile(env)
+  if (!caFile) {
+    return env
+  }
+  return {
+    ...env,
+    npm_config_cafile: caFile,
+    NODE_EXTRA_CA_CERTS: env.NODE_EXTRA_CA_CERTS || caFile,
+    AWS_CA_BUNDLE: env.AWS_CA_BUNDLE || caFile,
+    REQUESTS_CA_BUNDLE: env.REQUESTS_CA_BUNDLE || caFile,
+    CLOUDSDK_CORE_CUSTOM_CA_CERTS_FILE:
+      env.CLOUDSDK_CORE_CUSTOM_CA_CERTS_FILE || caFile,
+  }
+}
+
+export function isTlsError(e: any) {
+  return (
+    TLS_ERROR_PATTERN.test(e?.code ?? '') ||
+    TLS_ERROR_PATTERN.test(e?.message ?? '')
+  )
+}
+
+/**
+ * TLS errors with the hint of the CA bundle, other errors are returned as they are.
+ */
+export function describeNetworkError(e: any) {
+  if (!isTlsError(e)) {
+    return e
+  }
+  return new Error(
+    `TLS error: ${e.message}. If the network is behind a proxy intercepting the TLS connections, set ${chalk.green(
+      'NAPI_RS_CA_FILE',
+    )} to the CA bundle of it`,
+  )
+}
+
+function isRetryable(e: any) {
+  if (isTlsError(e)) {
+    return false
+  }
+  if (RETRY_CODES.includes(e?.code)) {
+    return true
+  }
+  // @octokit/request reports the failed requests as 500
+  return e?.status === 429 || e?.status >= 500
+}
+
+/**
+ * Retry `request` on the network errors and the 429 and 5xx responses with backoff.
+ */
+export async function withRetries<T>(
+  name: string,
+  request: () => Promise<T>,
+  retries = 3,
+  delay = 1000,
+): Promise<T> {
+  for (let attempt = 0; ; attempt++) {
+    try {
+      return await request()
+    } catch (e: any) {
+      if (attempt >= retries || !isRetryable(e)) {
+        throw describeNetworkError(e)
+      }
+      const wait = delay * 2 ** attempt
+      console.warn(
+        chalk.yellowBright(`${name} failed: ${e.message}, retry in ${wait}ms`),
+      )
+      await new Promise((resolve) => setTimeout(resolve, wait))
+    }
+  }
+}
@@ -1,4 +1,91 @@
-export const YAML = (app: string) => `
+import { DEFAULT_DIST_TAGS, distTagShellCase } from '../dist-tag'
+import { DOCKER_BUILD_CONFIGS, NODE_ARM_IMAGE_VERSIONS } from '../docker'
+import {
+  getPackageManagerDetail,
+  PackageManagerDetail,
+  runScript,
+} from '../package-manager'
+
+export const DEFAULT_NODE_VERSIONS = ['12', '14', '16']
+
+// tested by the compatibility job only, there are no docker images of it
+export const NIGHTLY = 'nightly'
+
+export const TEST_PACKAGE_MANAGERS = ['npm', 'pnpm', 'yarn', 'bun']
+
+/**
+ * The package managers of the compatibility job, installing without the lockfile unless it's the one of the project.
+ */
+export const testPackageManagerMatrix = (
+  pm: PackageManagerDetail,
+  testPackageManagers: string[],
+) =>
+  (testPackageManagers.length ? testPackageManagers : [pm.bin]).map((name) =>
+    name === pm.bin
+      ? {
+          name,
+          setup: pm.setup,
+          install: pm.install,
+          test: runScript(pm, 'test'),
+        }
+      : {
+          name,
+          setup:
+            name === 'bun'
+              ? 'npm install -g bun'
+              : getPackageManagerDetail(name).setup,
+          install: `${name} install --ignore-scripts`,
+          test: `${name} run test`,
+        },
+  )
+
+/**
+ * Node.js versions of the test jobs running in the arm images, the newest image if none of them is available.
+ */
+export const armNodeVersions = (nodeVersions: string[]) => {
+  const versions = nodeVersions.filter((version) =>
+    NODE_ARM_IMAGE_VERSIONS.includes(version),
+  )
+  return versions.length
+    ? versions
+    : NODE_ARM_IMAGE_VERSIONS.slice(NODE_ARM_IMAGE_VERSIONS.length - 1)
+}
+
+export const YAML = (
+  app: string,
+  pm: PackageManagerDetail,
+  nodeVersions = DEFAULT_NODE_VERSIONS,
+  distTags = DEFAULT_DIST_TAGS,
+  testPackageManagers: string[] = [],
+) => {
+  const run = (script: string, args = '') => runScript(pm, script, args)
+  const releasedNodeVersions = nodeVersions.filter(
+    (version) => version !== NIGHTLY,
+  )
+  // build and publish with the newest one
+  const buildNodeVersion = [...releasedNodeVersions].sort(
+    (a, b) => +b - +a,
+  )[0]
+  const nodeMatrix = `[${releasedNodeVersions
+    .map((v) => `'${v}'`)
+    .join(', ')}]`
+  const armNodeMatrix = `[${armNodeVersions(releasedNodeVersions)
+    .map((v) => `'${v}'`)
+    .join(', ')}]`
+  const compatibilityNodeMatrix = `[${nodeVersions
+    .map((v) => `'${v}'`)
+    .join(', ')}]`
+  const packageManagerMatrix = JSON.stringify(
+    testPackageManagerMatrix(pm, testPackageManagers),
+  )
+  // docker images and VMs only have npm and yarn classic installed
+  const setupInDocker = pm.name === 'pnpm' ? pm.setup : ''
+  const dockerRun = (script: string) =>
+    setupInDocker
+      ? `sh -c "${setupInDocker} && ${run(script)}"`
+      : run(script)
+
+  return `
 name: CI
 
 env:
@@ -32,32 +119,32 @@ jobs:
             target: 'x86_64-apple-darwin'
             architecture: 'x64'
             build: |
-              yarn build
+              ${run('build')}
               strip -x *.node
           - host: windows-latest
-            build: yarn build
+            build: ${run('build')}
             target: 'x86_64-pc-windows-msvc'
             architecture: 'x64'
           - host: windows-latest
             build: |
-              yarn build --target i686-pc-windows-msvc
-              yarn test
+              ${run('build', '--target i686-pc-windows-msvc')}
+              ${run('test')}
             target: 'i686-pc-windows-msvc'
             architecture: 'x86'
           - host: ubuntu-latest
             target: 'x86_64-unknown-linux-gnu'
-            docker: ghcr.io/napi-rs/napi-rs/nodejs-rust:lts-alpine-zig
+            docker: ${DOCKER_BUILD_CONFIGS['x86_64-unknown-linux-gnu'].image}
             build: >-
               set -e &&\n
-              rustup target add x86_64-unknown-linux-gnu &&\n
-              yarn build --target x86_64-unknown-linux-gnu --zig --zig-abi-suffix 2.12 &&\n
+              ${setupInDocker ? `${setupInDocker} &&\n              ` : ''}rustup target add x86_64-unknown-linux-gnu &&\n
+              ${run('build', '--target x86_64-unknown-linux-gnu --zig --zig-abi-suffix 2.12')} &&\n
               llvm-strip -x *.node
           - host: ubuntu-latest
             target: 'x86_64-unknown-linux-musl'
-            docker: ghcr.io/napi-rs/napi-rs/nodejs-rust:lts-alpine
+            docker: ${DOCKER_BUILD_CONFIGS['x86_64-unknown-linux-musl'].image}
             build: >-
-              set -e &&
-              yarn build &&
+              set -e &&${setupInDocker ? ` ${setupInDocker} &&` : ''}
+              ${run('build')} &&
               strip *.node
           - host: macos-latest
             target: 'aarch64-apple-darwin'
@@ -67,15 +154,15 @@ jobs:
               export CXX=$(xcrun -f clang++);
               SYSROOT=$(xcrun --sdk macosx --show-sdk-path);
               export CFLAGS="-isysroot $SYSROOT -isystem $SYSROOT";
-              yarn build --target aarch64-apple-darwin
+              ${run('build', '--target aarch64-apple-darwin')}
               strip -x *.node
           - host: ubuntu-latest
             target: 'aarch64-unknown-linux-gnu'
-            docker: ghcr.io/napi-rs/napi-rs/nodejs-rust:lts-alpine-zig
+            docker: ${DOCKER_BUILD_CONFIGS['aarch64-unknown-linux-gnu'].image}
             build: >-
               set -e &&\n
-              rustup target add aarch64-unknown-linux-gnu &&\n
-              yarn build --target aarch64-unknown-linux-gnu --zig --zig-abi-suffix 2.17 &&\n
+              ${setupInDocker ? `${setupInDocker} &&\n              ` : ''}rustup target add aarch64-unknown-linux-gnu &&\n
+              ${run('build', '--target aarch64-unknown-linux-gnu --zig --zig-abi-suffix 2.17')} &&\n
               llvm-strip -x *.node
           - host: ubuntu-latest
             architecture: 'x64'
@@ -84,43 +171,40 @@ jobs:
               sudo apt-get update
               sudo apt-get install gcc-arm-linux-gnueabihf g++-arm-linux-gnueabihf -y
             build: |
-              yarn build --target=armv7-unknown-linux-gnueabihf
+              ${run('build', '--target armv7-unknown-linux-gnueabihf')}
               arm-linux-gnueabihf-strip *.node
           - host: ubuntu-latest
             architecture: 'x64'
             target: 'aarch64-linux-android'
             build: |
-              export CARGO_TARGET_AARCH64_LINUX_ANDROID_LINKER="\${ANDROID_NDK_HOME}/toolchains/llvm/prebuilt/linux-x86_64/bin/aarch64-linux-android24-clang"
-              export CC="\${ANDROID_NDK_HOME}/toolchains/llvm/prebuilt/linux-x86_64/bin/aarch64-linux-android24-clang"
-              export CXX="\${ANDROID_NDK_HOME}/toolchains/llvm/prebuilt/linux-x86_64/bin/aarch64-linux-android24-clang++"
-              export PATH="\${ANDROID_NDK_HOME}/toolchains/llvm/prebuilt/linux-x86_64/bin:\${PATH}"
-              yarn build --target aarch64-linux-android
+              ${run('build', '--target aarch64-linux-android')}
               \${ANDROID_NDK_HOME}/toolchains/llvm/prebuilt/linux-x86_64/bin/aarch64-linux-android-strip *.node
           - host: ubuntu-latest
             architecture: 'x64'
             target: 'armv7-linux-androideabi'
             build: |
-              export CARGO_TARGET_ARMV7_LINUX_ANDROIDEABI_LINKER="\${ANDROID_NDK_HOME}/toolchains/llvm/prebuilt/linux-x86_64/bin/armv7a-linux-androideabi24-clang"
-              export CC="\${ANDROID_NDK_HOME}/toolchains/llvm/prebuilt/linux-x86_64/bin/armv7a-linux-androideabi24-clang"
-              export CXX="\${ANDROID_NDK_HOME}/toolchains/llvm/prebuilt/linux-x86_64/bin/armv7a-linux-androideabi24-clang++"
-              export PATH="\${ANDROID_NDK_HOME}/toolchains/llvm/prebuilt/linux-x86_64/bin:\${PATH}"
-              yarn build --target armv7-linux-androideabi
+              ${run('build', '--target armv7-linux-androideabi')}
               \${ANDROID_NDK_HOME}/toolchains/llvm/prebuilt/linux-x86_64/bin/arm-linux-androideabi-strip *.node
           - host: ubuntu-latest
             architecture: 'x64'
             target: 'aarch64-unknown-linux-musl'
-            docker: ghcr.io/napi-rs/napi-rs/nodejs-rust:lts-alpine
+            docker: ${DOCKER_BUILD_CONFIGS['aarch64-unknown-linux-musl'].image}
             build: >-
               set -e &&\n
-              rustup target add aarch64-unknown-linux-musl &&\n
-              yarn build --target aarch64-unknown-linux-musl &&\n
+              ${setupInDocker ? `${setupInDocker} &&\n              ` : ''}rustup target add aarch64-unknown-linux-musl &&\n
+              ${run('build', '--target aarch64-unknown-linux-musl')} &&\n
               /aarch64-linux-musl-cross/bin/aarch64-linux-musl-strip *.node
           - host: windows-latest
             architecture: 'x64'
             target: 'aarch64-pc-windows-msvc'
-            build: yarn build --target aarch64-pc-windows-msvc
+            setup: |
+              VS_INSTALLER="C:/Program Files (x86)/Microsoft Visual Studio/Installer"
+              VS_INSTALL_PATH=$("$VS_INSTALLER/vswhere.exe" -latest -property installationPath)
+              powershell -Command "Start-Process -Wait -FilePath '$VS_INSTALLER/vs_installer.exe' -ArgumentList 'modify --installPath \\"$VS_INSTALL_PATH\\" --add Microsoft.VisualStudio.Component.VC.Tools.ARM64 --quiet --norestart --nocache'"
+              echo "VSINSTALLDIR=$VS_INSTALL_PATH" >> $GITHUB_ENV
+            build: ${run('build', '--target aarch64-pc-windows-msvc')}
 
-    name: stable - \${{ matrix.settings.target }} - node@16
+    name: stable - \${{ matrix.settings.target }} - node@${buildNodeVersion}
     runs-on: \${{ matrix.settings.host }}
 
     steps:
@@ -130,9 +214,9 @@ jobs:
         uses: actions/setup-node@v3
         if: \${{ !matrix.settings.docker }}
         with:
-          node-version: 16
+          node-version: ${buildNodeVersion}
           check-latest: true
-          cache: yarn
+          cache: ${pm.cache}
           architecture: \${{ matrix.settings.architecture }}
 
       - name: Install
@@ -166,7 +250,7 @@ jobs:
         uses: actions/cache@v2
         with:
           path: node_modules
-          key: npm-cache-build-\${{ matrix.settings.target }}-node@16
+          key: npm-cache-build-\${{ matrix.settings.target }}-node@${buildNodeVersion}
 
       - name: Setup toolchain
         run: \${{ matrix.settings.setup }}
@@ -174,7 +258,7 @@ jobs:
         shell: bash
 
       - name: 'Install dependencies'
-        run: yarn install --ignore-scripts --frozen-lockfile --registry https://registry.npmjs.org --network-timeout 300000
+        run: ${pm.install}
 
       - name: Build in docker
         uses: addnab/docker-run-action@v3
@@ -193,56 +277,47 @@ jobs:
         uses: actions/upload-artifact@v2
         with:
           name: bindings-\${{ matrix.settings.target }}
-          path: \${{ env.APP_NAME }}.*.node
+          path: |
+            \${{ env.APP_NAME }}.*.node
+            index.*.d.ts
           if-no-files-found: error
 
   build-freebsd:
-    runs-on: macos-10.15
+    if: "!contains(github.event.head_commit.message, 'skip ci')"
+    runs-on: macos-12
     name: Build FreeBSD
     steps:
       - uses: actions/checkout@v3
       - name: Build
         id: build
-        uses: vmactions/freebsd-vm@v0.1.6
+        uses: vmactions/freebsd-vm@v0
         env:
           DEBUG: 'napi:*'
-          RUSTUP_HOME: /usr/local/rustup
-          CARGO_HOME: /usr/local/cargo
-          RUSTUP_IO_THREADS: 1
         with:
-          envs: 'DEBUG RUSTUP_HOME CARGO_HOME RUSTUP_IO_THREADS'
+          envs: 'DEBUG'
           usesh: true
           mem: 3000
+          # Rust and Node.js from the FreeBSD packages, \`napi build\` reads the host triple from \`rustc -vV\` without rustup
           prepare: |
-            pkg install -y curl node14 python2
-            curl -qL https://www.npmjs.com/install.sh | sh
-            npm install -g yarn
-            curl https://sh.rustup.rs -sSf --output rustup.sh
-            sh rustup.sh -y --profile minimal --default-toolchain stable
-            export PATH="/usr/local/cargo/bin:$PATH"
-            echo "~~~~ rustc --version ~~~~"
+            pkg install -y -f curl node npm rust
+            ${pm.setup ? `${pm.setup}\n            ` : ''}echo "~~~~ rustc --version ~~~~"
             rustc --version
             echo "~~~~ node -v ~~~~"
             node -v
-            echo "~~~~ yarn --version ~~~~"
-            yarn --version
+            echo "~~~~ ${pm.bin} --version ~~~~"
+            ${pm.bin} --version
           run: |
-            export PATH="/usr/local/cargo/bin:$PATH"
-            pwd
-            ls -lah
-            whoami
-            env
             freebsd-version
-            yarn install --ignore-scripts --frozen-lockfile --registry https://registry.npmjs.org --network-timeout 300000
-            yarn build
+            ${pm.install}
+            ${run('build')}
             strip -x *.node
-            yarn test
+            ${run('test')}
             rm -rf node_modules
             rm -rf target
       - name: Upload artifact
         uses: actions/upload-artifact@v3
         with:
-          name: bindings-freebsd
+          name: bindings-x86_64-unknown-freebsd
           path: \${{ env.APP_NAME }}.*.node
           if-no-files-found: error
 
@@ -258,7 +333,7 @@ jobs:
             target: 'x86_64-apple-darwin'
           - host: windows-latest
             target: 'x86_64-pc-windows-msvc'
-        node: ['12', '14', '16']
+        node: ${nodeMatrix}
     runs-on: \${{ matrix.settings.host }}
 
     steps:
@@ -269,16 +344,16 @@ jobs:
         with:
           node-version: \${{ matrix.node }}
           check-latest: true
-          cache: 'yarn'
+          cache: '${pm.cache}'
 
       - name: Cache NPM dependencies
         uses: actions/cache@v2
         with:
           path: node_modules
-          key: npm-cache-test-\${{ matrix.settings.target }}-\${{ matrix.node }}-\${{ hashFiles('yarn.lock') }}
+          key: npm-cache-test-\${{ matrix.settings.target }}-\${{ matrix.node }}-\${{ hashFiles('${pm.lockfile}') }}
 
       - name: 'Install dependencies'
-        run: yarn install --ignore-scripts --frozen-lockfile --registry https://registry.npmjs.org --network-timeout 300000
+        run: ${pm.install}
 
       - name: Download artifacts
         uses: actions/download-artifact@v3
@@ -291,7 +366,7 @@ jobs:
         shell: bash
 
       - name: Test bindings
-        run: yarn test
+        run: ${run('test')}
 
   test-linux-x64-gnu-binding:
     name: Test bindings on Linux-x64-gnu - node@\${{ matrix.node }}
@@ -300,7 +375,7 @@ jobs:
     strategy:
       fail-fast: false
       matrix:
-        node: ['12', '14', '16']
+        node: ${nodeMatrix}
     runs-on: ubuntu-latest
 
     steps:
@@ -311,16 +386,64 @@ jobs:
         with:
           node-version: \${{ matrix.node }}
           check-latest: true
-          cache: 'yarn'
+          cache: '${pm.cache}'
+
+      - name: Cache NPM dependencies
+        uses: actions/cache@v2
+        with:
+          path: node_modules
+          key: npm-cache-test-linux-x64-gnu-\${{ matrix.node }}-\${{ hashFiles('${pm.lockfile}') }}
+
+      - name: 'Install dependencies'
+        run: ${pm.install}
+
+      - name: Download artifacts
+        uses: actions/download-artifact@v3
+        with:
+          name: bindings-x86_64-unknown-linux-gnu
+          path: .
+
+      - name: List packages
+        run: ls -R .
+        shell: bash
+
+      - name: Test bindings
+        run: docker run --rm -v $(pwd):/build -w /build node:\${{ matrix.node }}-slim ${dockerRun('test')}
+
+  test-compatibility:
+    name: Test bindings on node@\${{ matrix.node }} - \${{ matrix.package-manager.name }}
+    needs:
+      - build
+    # nightly is only a warning of the upcoming breakages
+    continue-on-error: \${{ matrix.node == '${NIGHTLY}' }}
+    strategy:
+      fail-fast: false
+      matrix:
+        node: ${compatibilityNodeMatrix}
+        package-manager: ${packageManagerMatrix}
+    runs-on: ubuntu-latest
+
+    steps:
+      - uses: actions/checkout@v3
+
+      - name: Setup node
+        uses: actions/setup-node@v3
+        with:
+          node-version: \${{ matrix.node }}
+          check-latest: true
+
+      - name: Setup package manager
+        run: \${{ matrix.package-manager.setup }}
+        if: \${{ matrix.package-manager.setup }}
 
       - name: Cache NPM dependencies
         uses: actions/cache@v2
         with:
           path: node_modules
-          key: npm-cache-test-linux-x64-gnu-\${{ matrix.node }}-\${{ hashFiles('yarn.lock') }}
+          key: npm-cache-test-compatibility-\${{ matrix.package-manager.name }}-\${{ matrix.node }}-\${{ hashFiles('package.json') }}
 
       - name: 'Install dependencies'
-        run: yarn install --ignore-scripts --frozen-lockfile --registry https://registry.npmjs.org --network-timeout 300000
+        run: \${{ matrix.package-manager.install }}
 
       - name: Download artifacts
         uses: actions/download-artifact@v3
@@ -333,7 +456,7 @@ jobs:
         shell: bash
 
       - name: Test bindings
-        run: docker run --rm -v $(pwd):/build -w /build node:\${{ matrix.node }}-slim yarn test
+        run: \${{ matrix.package-manager.test }}
 
   test-linux-x64-musl-binding:
     name: Test bindings on x86_64-unknown-linux-musl - node@\${{ matrix.node }}
@@ -342,7 +465,7 @@ jobs:
     strategy:
       fail-fast: false
       matrix:
-        node: ['12', '14', '16']
+        node: ${nodeMatrix}
     runs-on: ubuntu-latest
 
     steps:
@@ -353,16 +476,16 @@ jobs:
         with:
           node-version: \${{ matrix.node }}
           check-latest: true
-          cache: 'yarn'
+          cache: '${pm.cache}'
 
       - name: Cache NPM dependencies
         uses: actions/cache@v2
         with:
           path: node_modules
-          key: npm-cache-test-x86_64-unknown-linux-musl-\${{ matrix.node }}-\${{ hashFiles('yarn.lock') }}
+          key: npm-cache-test-x86_64-unknown-linux-musl-\${{ matrix.node }}-\${{ hashFiles('${pm.lockfile}') }}
 
       - name: 'Install dependencies'
-        run: yarn install --ignore-scripts --frozen-lockfile --registry https://registry.npmjs.org --network-timeout 300000
+        run: ${pm.install}
 
       - name: Download artifacts
         uses: actions/download-artifact@v3
@@ -375,7 +498,7 @@ jobs:
         shell: bash
 
       - name: Test bindings
-        run: docker run --rm -v $(pwd):/build -w /build node:\${{ matrix.node }}-alpine yarn test
+        run: docker run --rm -v $(pwd):/build -w /build node:\${{ matrix.node }}-alpine ${dockerRun('test')}
 
   test-linux-aarch64-gnu-binding:
     name: Test bindings on aarch64-unknown-linux-gnu - node@\${{ matrix.node }}
@@ -384,7 +507,7 @@ jobs:
     strategy:
       fail-fast: false
       matrix:
-        node: ['12', '14', '16']
+        node: ${armNodeMatrix}
     runs-on: ubuntu-latest
 
     steps:
@@ -406,10 +529,10 @@ jobs:
         uses: actions/cache@v2
         with:
           path: node_modules
-          key: npm-cache-test-linux-aarch64-gnu-\${{ matrix.node }}-\${{ hashFiles('yarn.lock') }}
+          key: npm-cache-test-linux-aarch64-gnu-\${{ matrix.node }}-\${{ hashFiles('${pm.lockfile}') }}
 
       - name: Install dependencies
-        run: yarn install --ignore-scripts --ignore-platform --frozen-lockfile --registry https://registry.npmjs.org --network-timeout 300000
+        run: ${pm.installIgnorePlatform}
 
       - name: Setup and run tests
         uses: addnab/docker-run-action@v3
@@ -418,11 +541,11 @@ jobs:
           options: -v \${{ github.workspace }}:/build -w /build
           run: |
             set -e
-            yarn test
+            ${setupInDocker ? `${setupInDocker}\n            ` : ''}${run('test')}
             ls -la
 
   test-linux-aarch64-musl-binding:
-    name: Test bindings on aarch64-unknown-linux-musl - node@\${{ matrix.node }}
+    name: Test bindings on aarch64-unknown-linux-musl - node@alpine
     needs:
       - build
 
@@ -447,10 +570,10 @@ jobs:
         uses: actions/cache@v2
         with:
           path: node_modules
-          key: npm-cache-test-linux-aarch64-musl-\${{ matrix.node }}-\${{ hashFiles('yarn.lock') }}
+          key: npm-cache-test-linux-aarch64-musl-\${{ hashFiles('${pm.lockfile}') }}
 
       - name: Install dependencies
-        run: yarn install --ignore-scripts --ignore-platform --frozen-lockfile --registry https://registry.npmjs.org --network-timeout 300000
+        run: ${pm.installIgnorePlatform}
 
       - name: Setup and run tests
         uses: addnab/docker-run-action@v3
@@ -459,8 +582,8 @@ jobs:
           options: -v \${{ github.workspace }}:/build -w /build
           run: |
             set -e
-            apk add nodejs npm yarn
-            yarn test
+            apk add nodejs npm${pm.bin === 'yarn' ? ' yarn' : ''}
+            ${setupInDocker ? `${setupInDocker}\n            ` : ''}${run('test')}
 
   test-linux-arm-gnueabihf-binding:
     name: Test bindings on armv7-unknown-linux-gnueabihf - node@\${{ matrix.node }}
@@ -469,7 +592,7 @@ jobs:
     strategy:
       fail-fast: false
       matrix:
-        node: ['12', '14', '16']
+        node: ${armNodeMatrix}
     runs-on: ubuntu-latest
 
     steps:
@@ -491,10 +614,10 @@ jobs:
         uses: actions/cache@v2
         with:
           path: node_modules
-          key: npm-cache-test-linux-arm-gnueabihf-\${{ matrix.node }}-\${{ hashFiles('yarn.lock') }}
+          key: npm-cache-test-linux-arm-gnueabihf-\${{ matrix.node }}-\${{ hashFiles('${pm.lockfile}') }}
 
       - name: Install dependencies
-        run: yarn install --ignore-scripts --ignore-platform --frozen-lockfile --registry https://registry.npmjs.org --network-timeout 300000
+        run: ${pm.installIgnorePlatform}
 
       - name: Setup and run tests
         uses: addnab/docker-run-action@v3
@@ -503,7 +626,7 @@ jobs:
           options: -v \${{ github.workspace }}:/build -w /build
           run: |
             set -e
-            yarn test
+            ${setupInDocker ? `${setupInDocker}\n            ` : ''}${run('test')}
             ls -la
 
   publish:
@@ -524,19 +647,19 @@ jobs:
       - name: Setup node
         uses: actions/setup-node@v3
         with:
-          node-version: 16
+          node-version: ${buildNodeVersion}
           check-latest: true
-          cache: 'yarn'
+          cache: '${pm.cache}'
 
       - name: Cache NPM dependencies
         uses: actions/cache@v2
         with:
           path: node_modules
-          key: npm-cache-ubuntu-latest-\${{ hashFiles('yarn.lock') }}
+          key: npm-cache-ubuntu-latest-\${{ hashFiles('${pm.lockfile}') }}
           restore-keys: |
             npm-cache-
       - name: 'Install dependencies'
-        run: yarn install --ignore-scripts --frozen-lockfile --registry https://registry.npmjs.org --network-timeout 300000
+        run: ${pm.install}
 
       - name: Download all artifacts
         uses: actions/download-artifact@v3
@@ -544,7 +667,7 @@ jobs:
           path: artifacts
 
       - name: Move artifacts
-        run: yarn artifacts
+        run: ${run('artifacts')}
 
       - name: List packages
         run: ls -R ./npm
@@ -552,18 +675,23 @@ jobs:
 
       - name: Publish
         run: |
+          ${distTagShellCase(distTags).split('\n').join('\n          ')}
           if git log -1 --pretty=%B | grep "^[0-9]\\+\\.[0-9]\\+\\.[0-9]\\+$";
           then
-            echo "//registry.npmjs.org/:_authToken=$NPM_TOKEN" >> ~/.npmrc
-            npm publish --access public
+            ${pm.publishAuth}
+            ${pm.publish} --tag "\${DIST_TAG:-latest}" --access public
           elif git log -1 --pretty=%B | grep "^[0-9]\\+\\.[0-9]\\+\\.[0-9]\\+";
           then
-            echo "//registry.npmjs.org/:_authToken=$NPM_TOKEN" >> ~/.npmrc
-            npm publish --tag next --access public
+            # prereleases are never published as latest
+            if [ -z "$DIST_TAG" ] || [ "$DIST_TAG" = latest ]; then DIST_TAG=next; fi
+            ${pm.publishAuth}
+            ${pm.publish} --tag "$DIST_TAG" --access public
           else
             echo "Not a release, skipping publish"
           fi
         env:
           GITHUB_TOKEN: \${{ secrets.GITHUB_TOKEN }}
           NPM_TOKEN: \${{ secrets.NPM_TOKEN }}
+          PR_NUMBER: \${{ github.event.pull_request.number }}
 `
+}
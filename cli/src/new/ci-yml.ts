@@ -1,11 +1,16 @@
 import { load, dump } from 'js-yaml'
 
-import { YAML } from './ci-template'
+import { DEFAULT_DIST_TAGS, PULL_REQUEST } from '../dist-tag'
+import { PackageManagerDetail } from '../package-manager'
+
+import { DEFAULT_NODE_VERSIONS, NIGHTLY, YAML } from './ci-template'
+import { formatLintErrors, lintWorkflow } from './workflow-lint'
 
 const BUILD_FREEBSD = 'build-freebsd'
 const TEST_MACOS_WINDOWS = 'test-macOS-windows-binding'
 const TEST_LINUX_X64_GNU = 'test-linux-x64-gnu-binding'
 const TEST_LINUX_X64_MUSL = 'test-linux-x64-musl-binding'
+const TEST_COMPATIBILITY = 'test-compatibility'
 const TEST_LINUX_AARCH64_GNU = 'test-linux-aarch64-gnu-binding'
 const TEST_LINUX_AARCH64_MUSL = 'test-linux-aarch64-musl-binding'
 const TEST_LINUX_ARM_GNUEABIHF = 'test-linux-arm-gnueabihf-binding'
@@ -13,8 +18,21 @@ const TEST_LINUX_ARM_GNUEABIHF = 'test-linux-arm-gnueabihf-binding'
 export const createGithubActionsCIYml = (
   binaryName: string,
   targets: string[],
+  packageManager: PackageManagerDetail,
+  useCache = false,
+  nodeVersions = DEFAULT_NODE_VERSIONS,
+  distTags = DEFAULT_DIST_TAGS,
+  testPackageManagers: string[] = [],
 ) => {
-  const fullTemplate = load(YAML(binaryName)) as any
+  const fullTemplate = load(
+    YAML(
+      binaryName,
+      packageManager,
+      nodeVersions,
+      distTags,
+      testPackageManagers,
+    ),
+  ) as any
   const requiredSteps = []
   const enableWindowsX86 = targets.includes('x86_64-pc-windows-msvc')
   const enableMacOSX86 = targets.includes('x86_64-apple-darwin')
@@ -25,12 +43,20 @@ export const createGithubActionsCIYml = (
   const enableLinuxArm7 = targets.includes('armv7-unknown-linux-gnueabihf')
   const enableFreeBSD = targets.includes('x86_64-unknown-freebsd')
   fullTemplate.env.APP_NAME = binaryName
+  // run on the pushes to the branches published with a dist-tag
+  fullTemplate.on.push.branches = [
+    ...new Set([
+      ...fullTemplate.on.push.branches,
+      ...Object.keys(distTags).filter((pattern) => pattern !== PULL_REQUEST),
+    ]),
+  ]
   fullTemplate.jobs.build.strategy.matrix.settings =
     fullTemplate.jobs.build.strategy.matrix.settings.filter(
       ({ target }: { target: string }) => targets.includes(target),
     )
+  // FreeBSD is built in its own job
   if (!fullTemplate.jobs.build.strategy.matrix.settings.length) {
-    delete fullTemplate.jobs.build.strategy.matrix
+    delete fullTemplate.jobs.build
   }
 
   if (!enableFreeBSD) {
@@ -57,6 +83,16 @@ export const createGithubActionsCIYml = (
     requiredSteps.push(TEST_LINUX_X64_GNU)
   }
 
+  // reuses the x86_64-unknown-linux-gnu bindings, only if it tests something the other jobs don't
+  if (
+    !enableLinuxX86Gnu ||
+    (!testPackageManagers.length && !nodeVersions.includes(NIGHTLY))
+  ) {
+    delete fullTemplate.jobs[TEST_COMPATIBILITY]
+  } else {
+    requiredSteps.push(TEST_COMPATIBILITY)
+  }
+
   if (!enableLinuxX86Musl) {
     delete fullTemplate.jobs[TEST_LINUX_X64_MUSL]
   } else {
@@ -83,7 +119,83 @@ export const createGithubActionsCIYml = (
 
   fullTemplate.jobs.publish.needs = requiredSteps
 
+  if (useCache && fullTemplate.jobs.build) {
+    addSccacheSteps(fullTemplate)
+  }
+
+  // `cache: pnpm` in actions/setup-node requires pnpm to be installed before it
+  if (packageManager.name === 'pnpm') {
+    for (const job of Object.values<any>(fullTemplate.jobs)) {
+      const setupNodeIndex = job.steps.findIndex(
+        ({ uses }: { uses?: string }) => uses?.startsWith('actions/setup-node'),
+      )
+      if (setupNodeIndex !== -1) {
+        const setupPnpm: Record<string, any> = {
+          name: 'Setup pnpm',
+          uses: 'pnpm/action-setup@v2',
+          with: {
+            version: 'latest',
+          },
+        }
+        const condition = job.steps[setupNodeIndex].if
+        if (condition) {
+          setupPnpm.if = condition
+        }
+        job.steps.splice(setupNodeIndex, 0, setupPnpm)
+      }
+    }
+  }
+
+  const lintErrors = lintWorkflow(fullTemplate)
+  if (lintErrors.length) {
+    throw new TypeError(
+      `The generated workflow is invalid:\n${formatLintErrors(lintErrors)}`,
+    )
+  }
+
   return dump(fullTemplate, {
     lineWidth: 1000,
   })
 }
+
+/**
+ * Install `sccache` in the build job and restore/save its cache per target, docker builds are not cached.
+ */
+function addSccacheSteps(fullTemplate: any) {
+  const { build } = fullTemplate.jobs
+  const notInDocker = '${{ !matrix.settings.docker }}'
+  build.env = {
+    ...build.env,
+    SCCACHE_DIR: '${{ github.workspace }}/.sccache',
+  }
+  const cacheIndex = build.steps.findIndex(
+    ({ name }: { name?: string }) => name === 'Cache cargo index',
+  )
+  build.steps.splice(
+    cacheIndex + 1,
+    0,
+    {
+      name: 'Setup sccache',
+      uses: 'mozilla-actions/sccache-action@v0.0.3',
+      if: notInDocker,
+    },
+    {
+      name: 'Cache sccache',
+      uses: 'actions/cache@v2',
+      if: notInDocker,
+      with: {
+        path: '${{ env.SCCACHE_DIR }}',
+        key: '${{ matrix.settings.target }}-sccache-${{ github.sha }}',
+        'restore-keys': '${{ matrix.settings.target }}-sccache-',
+      },
+    },
+  )
+  const buildIndex = build.steps.findIndex(
+    ({ name }: { name?: string }) => name === 'Build',
+  )
+  build.steps.splice(buildIndex + 1, 0, {
+    name: 'Show sccache stats',
+    run: 'sccache --show-stats',
+    if: notInDocker,
+  })
+}
@@ -7,18 +7,31 @@ import inquirer, { prompt } from 'inquirer'
 
 import { CreateNpmDirCommand } from '../create-npm-dir'
 import { debugFactory } from '../debug'
-import { DefaultPlatforms } from '../parse-triple'
+import {
+  getPackageManagerDetail,
+  SupportedPackageManagers,
+} from '../package-manager'
+import { DefaultPlatforms, SupportedPlatforms } from '../parse-triple'
+import { applyTemplate } from '../templates'
 
 import { createCargoContent } from './cargo'
 import { createCargoConfig } from './cargo-config'
+import {
+  armNodeVersions,
+  DEFAULT_NODE_VERSIONS,
+  NIGHTLY,
+  TEST_PACKAGE_MANAGERS,
+} from './ci-template'
 import { createGithubActionsCIYml } from './ci-yml'
 import { LibRs } from './lib-rs'
 import { NPMIgnoreFiles } from './npmignore'
 import { createPackageJson } from './package'
+import { createReadme } from './readme'
 
 const NAME_PROMOTE_NAME = 'Package name'
 const DIR_PROMOTE_NAME = 'Dir name'
 const ENABLE_GITHUB_ACTIONS_PROMOTE_NAME = 'Enable github actions'
+const PACKAGE_MANAGER_PROMOTE_NAME = 'Package manager'
 
 const debug = debugFactory('create')
 
@@ -29,22 +42,6 @@ fn main() {
 }
 `
 
-const SupportedPlatforms: string[] = [
-  'aarch64-apple-darwin',
-  'aarch64-linux-android',
-  'aarch64-unknown-linux-gnu',
-  'aarch64-unknown-linux-musl',
-  'aarch64-pc-windows-msvc',
-  'armv7-unknown-linux-gnueabihf',
-  'x86_64-apple-darwin',
-  'x86_64-pc-windows-msvc',
-  'x86_64-unknown-linux-gnu',
-  'x86_64-unknown-linux-musl',
-  'x86_64-unknown-freebsd',
-  'i686-pc-windows-msvc',
-  'armv7-linux-androideabi',
-]
-
 export class NewProjectCommand extends Command {
   static usage = Command.Usage({
     description: 'Create a new project from scratch',
@@ -68,6 +65,30 @@ export class NewProjectCommand extends Command {
 
   enableGithubActions?: boolean = Option.Boolean(`--enable-github-actions`)
 
+  nodeVersions?: string = Option.String('--node-versions', {
+    description: `Comma separated Node.js versions tested in GitHub Actions, default to ${chalk.green(
+      DEFAULT_NODE_VERSIONS.join(','),
+    )}, ${chalk.green(NIGHTLY)} is tested in the compatibility job`,
+  })
+
+  testPackageManagers?: string = Option.String('--test-package-managers', {
+    description: `Comma separated package managers of the compatibility job in GitHub Actions, ${TEST_PACKAGE_MANAGERS.map(
+      (name) => chalk.green(name),
+    ).join(', ')}`,
+  })
+
+  useCache = Option.Boolean('--use-cache', false, {
+    description: `Build with ${chalk.green(
+      'sccache',
+    )} and cache it in GitHub Actions`,
+  })
+
+  packageManager?: string = Option.String('--package-manager', {
+    description: `${chalk.green('npm')}, ${chalk.green(
+      'yarn',
+    )}, ${chalk.green('yarn-berry')} or ${chalk.green('pnpm')}`,
+  })
+
   async execute() {
     await this.getName()
     if (!this.dirname) {
@@ -113,6 +134,21 @@ export class NewProjectCommand extends Command {
       this.enableGithubActions = answer[ENABLE_GITHUB_ACTIONS_PROMOTE_NAME]
     }
 
+    if (!this.packageManager) {
+      const answer = await inquirer.prompt([
+        {
+          type: 'list',
+          name: PACKAGE_MANAGER_PROMOTE_NAME,
+          message: 'Choose the package manager',
+          default: 'yarn',
+          choices: SupportedPackageManagers,
+        },
+      ])
+      this.packageManager = answer[PACKAGE_MANAGER_PROMOTE_NAME]
+    }
+
+    const packageManager = getPackageManagerDetail(this.packageManager)
+
     debug(`Running command: ${chalk.green('[${command}]')}`)
     if (!this.dryRun) {
       mkdirSync(join(process.cwd(), this.dirname!), {
@@ -132,13 +168,24 @@ export class NewProjectCommand extends Command {
     this.writeFile(
       'package.json',
       JSON.stringify(
-        createPackageJson(this.name!, binaryName, this.targets!),
+        createPackageJson(
+          this.name!,
+          binaryName,
+          this.targets!,
+          this.useCache,
+        ),
         null,
         2,
       ),
     )
+    this.writeFile('README.md', createReadme(this.name!, this.targets!))
     this.writeFile('src/lib.rs', LibRs)
 
+    if (packageManager.name === 'yarn-berry') {
+      // ava and the generated `index.js` don't work with Plug'n'Play
+      this.writeFile('.yarnrc.yml', 'nodeLinker: node-modules\n')
+    }
+
     mkdirSync(join(process.cwd(), this.dirname!, '__test__'), {
       recursive: true,
     })
@@ -163,7 +210,23 @@ test('sum from native', (t) => {
       }
       this.writeFile(
         join('.github', 'workflows', 'CI.yml'),
-        createGithubActionsCIYml(binaryName, this.targets!),
+        applyTemplate(
+          'workflows/CI.yml',
+          createGithubActionsCIYml(
+            binaryName,
+            this.targets!,
+            packageManager,
+            this.useCache,
+            this.parseNodeVersions(),
+            undefined,
+            this.parseTestPackageManagers(),
+          ),
+          {
+            packageName: this.name,
+            binaryName,
+            packageManager: packageManager.name,
+          },
+        ),
       )
     }
 
@@ -171,6 +234,7 @@ test('sum from native', (t) => {
       'package.json',
       join(process.cwd(), this.dirname!),
       join(process.cwd(), this.dirname!),
+      packageManager,
     )
 
     const enableLinuxArm8Gnu = this.targets!.includes(
@@ -202,6 +266,69 @@ edition = "2021"
     )
   }
 
+  private parseNodeVersions() {
+    if (!this.nodeVersions) {
+      return DEFAULT_NODE_VERSIONS
+    }
+    const nodeVersions = this.nodeVersions
+      .split(',')
+      .map((version) => version.trim())
+      .filter(Boolean)
+    const invalid = nodeVersions.filter(
+      (version) => !/^\d+$/.test(version) && version !== NIGHTLY,
+    )
+    const releasedNodeVersions = nodeVersions.filter(
+      (version) => version !== NIGHTLY,
+    )
+    if (!releasedNodeVersions.length || invalid.length) {
+      throw new TypeError(
+        `Invalid --node-versions [${this.nodeVersions}], expected major versions like ${chalk.green(
+          '18,20,22,nightly',
+        )}`,
+      )
+    }
+    const unavailable = releasedNodeVersions.filter(
+      (version) => !armNodeVersions(releasedNodeVersions).includes(version),
+    )
+    if (unavailable.length) {
+      console.warn(
+        chalk.yellowBright(
+          `No arm test images of Node.js ${unavailable.join(
+            ', ',
+          )}, the arm targets are tested on ${armNodeVersions(
+            releasedNodeVersions,
+          ).join(', ')}`,
+        ),
+      )
+    }
+    return nodeVersions
+  }
+
+  private parseTestPackageManagers() {
+    if (!this.testPackageManagers) {
+      return []
+    }
+    const testPackageManagers = [
+      ...new Set(
+        this.testPackageManagers
+          .split(',')
+          .map((name) => name.trim())
+          .filter(Boolean),
+      ),
+    ]
+    const invalid = testPackageManagers.filter(
+      (name) => !TEST_PACKAGE_MANAGERS.includes(name),
+    )
+    if (invalid.length) {
+      throw new TypeError(
+        `Invalid --test-package-managers [${this.testPackageManagers}], expected ${TEST_PACKAGE_MANAGERS.join(
+          ', ',
+        )}`,
+      )
+    }
+    return testPackageManagers
+  }
+
   private writeFile(path: string, content: string) {
     const distDir = join(process.cwd(), this.dirname!)
     this.context.stdout.write(chalk.green(`Writing ${chalk.blue(path)}\n`))
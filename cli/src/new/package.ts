@@ -1,10 +1,12 @@
 import { version } from '../../package.json'
+import { DEFAULT_DIST_TAGS } from '../dist-tag'
 import { DefaultPlatforms } from '../parse-triple'
 
 export const createPackageJson = (
   name: string,
   binaryName: string,
   targets: string[],
+  useCache = false,
 ) => {
   const pkgContent = {
     name,
@@ -13,6 +15,7 @@ export const createPackageJson = (
     types: 'index.d.ts',
     napi: {
       name: binaryName,
+      distTags: DEFAULT_DIST_TAGS,
     },
     license: 'MIT',
     devDependencies: {
@@ -56,5 +59,10 @@ export const createPackageJson = (
   // @ts-expect-error
   pkgContent.napi.triples = triples
 
+  if (useCache) {
+    // @ts-expect-error
+    pkgContent.napi.useCache = true
+  }
+
   return pkgContent
 }
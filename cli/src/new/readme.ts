@@ -0,0 +1,20 @@
+import { parseTriple } from '../parse-triple'
+import {
+  createPlatformTable,
+  PLATFORM_TABLE_END,
+  PLATFORM_TABLE_START,
+} from '../platform-table'
+
+export const createReadme = (
+  name: string,
+  targets: string[],
+) => `# \`${name}\`
+
+## Supported platforms
+
+${PLATFORM_TABLE_START}
+
+${createPlatformTable(targets.map(parseTriple), name)}
+
+${PLATFORM_TABLE_END}
+`
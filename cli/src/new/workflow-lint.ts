@@ -0,0 +1,278 @@
+/**
+ * A subset of the actionlint checks for the generated workflow: expressions, `needs` and `runs-on`.
+ * Mistakes in the templates are reported by `napi new` instead of the failed workflow run.
+ */
+
+export interface WorkflowLintError {
+  // path of the value in the workflow, like `jobs.build.steps[2].if`
+  path: string
+  message: string
+}
+
+const CONTEXTS = [
+  'github',
+  'env',
+  'vars',
+  'job',
+  'jobs',
+  'steps',
+  'runner',
+  'secrets',
+  'strategy',
+  'matrix',
+  'needs',
+  'inputs',
+]
+
+const FUNCTIONS = [
+  'contains',
+  'startsWith',
+  'endsWith',
+  'format',
+  'join',
+  'toJSON',
+  'fromJSON',
+  'hashFiles',
+  'success',
+  'always',
+  'cancelled',
+  'failure',
+]
+
+const GITHUB_HOSTED_RUNNERS = [
+  'ubuntu-latest',
+  'ubuntu-22.04',
+  'ubuntu-20.04',
+  'ubuntu-18.04',
+  'macos-latest',
+  'macos-12',
+  'macos-11',
+  'macos-10.15',
+  'windows-latest',
+  'windows-2022',
+  'windows-2019',
+  'self-hosted',
+]
+
+export function lintWorkflow(workflow: any): WorkflowLintError[] {
+  const errors: WorkflowLintError[] = []
+  const jobs: Record<string, any> = workflow?.jobs ?? {}
+  if (!Object.keys(jobs).length) {
+    errors.push({ path: 'jobs', message: 'No jobs in the workflow' })
+  }
+  walk(workflow, '', (value, path) => {
+    for (const expression of extractExpressions(value, path, errors)) {
+      errors.push(...lintExpression(expression, path))
+    }
+  })
+  for (const [id, job] of Object.entries(jobs)) {
+    const matrix = job?.strategy?.matrix
+    walk(job, `jobs.${id}`, (value, path) => {
+      for (const expression of extractExpressions(value, path, [])) {
+        errors.push(...lintMatrixReferences(expression, matrix, path))
+      }
+    })
+    errors.push(...lintRunsOn(job?.['runs-on'], matrix, `jobs.${id}.runs-on`))
+  }
+  errors.push(...lintNeeds(jobs))
+  return errors
+}
+
+export function formatLintErrors(errors: WorkflowLintError[]) {
+  return errors.map(({ path, message }) => `  ${path}: ${message}`).join('\n')
+}
+
+function walk(
+  value: any,
+  path: string,
+  visit: (value: string, path: string) => void,
+) {
+  if (typeof value === 'string') {
+    visit(value, path)
+  } else if (Array.isArray(value)) {
+    value.forEach((item, index) => walk(item, `${path}[${index}]`, visit))
+  } else if (value && typeof value === 'object') {
+    for (const [key, item] of Object.entries(value)) {
+      walk(item, path ? `${path}.${key}` : key, visit)
+    }
+  }
+}
+
+/**
+ * The `${{ }}` expressions in the value, and the `if` conditions without `${{ }}`.
+ */
+function extractExpressions(
+  value: string,
+  path: string,
+  errors: WorkflowLintError[],
+): string[] {
+  const expressions: string[] = []
+  let rest = value
+  let start: number
+  while ((start = rest.indexOf('${{')) !== -1) {
+    const end = rest.indexOf('}}', start)
+    if (end === -1) {
+      errors.push({ path, message: `Unclosed expression in [${value}]` })
+      return expressions
+    }
+    expressions.push(rest.slice(start + 3, end).trim())
+    rest = rest.slice(end + 2)
+  }
+  if (!expressions.length && /(^|\.)if$/.test(path)) {
+    expressions.push(value)
+  }
+  return expressions
+}
+
+const TOKEN_PATTERN =
+  /\s+|'(?:[^']|'')*'|[A-Za-z_][\w-]*(?:\.[\w-]+|\.\*|\[[^\]]*\])*|\d+(?:\.\d+)?|==|!=|<=|>=|&&|\|\||[()!<>,]/y
+
+function lintExpression(expression: string, path: string) {
+  const errors: WorkflowLintError[] = []
+  let depth = 0
+  TOKEN_PATTERN.lastIndex = 0
+  while (TOKEN_PATTERN.lastIndex < expression.length) {
+    const index = TOKEN_PATTERN.lastIndex
+    const matched = TOKEN_PATTERN.exec(expression)
+    if (!matched) {
+      errors.push({
+        path,
+        message: `Invalid token [${expression.slice(index)}] in [${expression}]`,
+      })
+      return errors
+    }
+    const token = matched[0]
+    if (token === '(') {
+      depth++
+    } else if (token === ')') {
+      depth--
+    } else if (/^[A-Za-z_]/.test(token)) {
+      const name = token.split(/[.[]/)[0]
+      const isCall = expression.slice(TOKEN_PATTERN.lastIndex).trimStart()[0]
+      if (isCall === '(') {
+        if (!FUNCTIONS.some((fn) => fn.toLowerCase() === name.toLowerCase())) {
+          errors.push({ path, message: `Unknown function [${name}]` })
+        }
+      } else if (
+        !CONTEXTS.includes(name) &&
+        !['true', 'false', 'null'].includes(name)
+      ) {
+        errors.push({ path, message: `Unknown context [${name}]` })
+      }
+    }
+    if (depth < 0) {
+      break
+    }
+  }
+  if (depth !== 0) {
+    errors.push({ path, message: `Unbalanced parentheses in [${expression}]` })
+  }
+  return errors
+}
+
+/**
+ * `matrix.[key]` should be defined in the matrix of the job.
+ */
+function lintMatrixReferences(expression: string, matrix: any, path: string) {
+  const errors: WorkflowLintError[] = []
+  for (const [, key] of expression.matchAll(/\bmatrix\.([\w-]+)/g)) {
+    if (!matrix) {
+      errors.push({ path, message: `matrix.${key} is used without a matrix` })
+    } else if (
+      !(key in matrix) &&
+      !(matrix.include ?? []).some((item: any) => key in item)
+    ) {
+      errors.push({ path, message: `matrix.${key} is not defined` })
+    }
+  }
+  return errors
+}
+
+function lintRunsOn(runsOn: any, matrix: any, path: string) {
+  if (runsOn === undefined) {
+    return [{ path, message: 'runs-on is required' }]
+  }
+  const labels: string[] = Array.isArray(runsOn) ? runsOn : [runsOn]
+  const errors: WorkflowLintError[] = []
+  for (const label of labels) {
+    const matrixLabel = /^\$\{\{\s*matrix\.([\w.-]+)\s*\}\}$/.exec(label)
+    const resolved = matrixLabel
+      ? resolveMatrixValues(matrix, matrixLabel[1])
+      : [label]
+    for (const value of resolved) {
+      if (typeof value === 'string' && value.includes('${{')) {
+        continue
+      }
+      if (!GITHUB_HOSTED_RUNNERS.includes(value)) {
+        errors.push({ path, message: `Unknown runner label [${value}]` })
+      }
+    }
+  }
+  return errors
+}
+
+function resolveMatrixValues(matrix: any, keyPath: string): any[] {
+  const [key, ...rest] = keyPath.split('.')
+  const values: any[] = [
+    ...(Array.isArray(matrix?.[key]) ? matrix[key] : []),
+    ...(matrix?.include ?? [])
+      .filter((item: any) => key in item)
+      .map((item: any) => item[key]),
+  ]
+  return rest.reduce(
+    (acc, segment) =>
+      acc
+        .map((value) => value?.[segment])
+        .filter((value) => value !== undefined),
+    values,
+  )
+}
+
+function lintNeeds(jobs: Record<string, any>) {
+  const errors: WorkflowLintError[] = []
+  const graph = new Map<string, string[]>()
+  for (const [id, job] of Object.entries(jobs)) {
+    const needs: string[] = job?.needs
+      ? Array.isArray(job.needs)
+        ? job.needs
+        : [job.needs]
+      : []
+    needs.forEach((need, index) => {
+      if (!(need in jobs)) {
+        errors.push({
+          path: `jobs.${id}.needs[${index}]`,
+          message: `Job [${need}] doesn't exist`,
+        })
+      }
+    })
+    graph.set(
+      id,
+      needs.filter((need) => need in jobs),
+    )
+  }
+  const visited = new Set<string>()
+  const visiting: string[] = []
+  const visit = (id: string) => {
+    if (visiting.includes(id)) {
+      errors.push({
+        path: `jobs.${id}.needs`,
+        message: `Cyclic needs [${[
+          ...visiting.slice(visiting.indexOf(id)),
+          id,
+        ].join(' -> ')}]`,
+      })
+      return
+    }
+    if (visited.has(id)) {
+      return
+    }
+    visiting.push(id)
+    graph.get(id)!.forEach(visit)
+    visiting.pop()
+    visited.add(id)
+  }
+  for (const id of graph.keys()) {
+    visit(id)
+  }
+  return errors
+}
@@ -0,0 +1,246 @@
+import { execSync } from 'child_process'
+import { createHash } from 'crypto'
+import {
+  createWriteStream,
+  existsSync,
+  mkdirSync,
+  readdirSync,
+  readFileSync,
+  renameSync,
+  statSync,
+} from 'fs'
+import { get } from 'https'
+import { join, resolve } from 'path'
+
+import chalk from 'chalk'
+import envPaths from 'env-paths'
+
+import { debugFactory } from './debug'
+import { createAgent, withRetries } from './network'
+import { PlatformDetail } from './parse-triple'
+import { toLongPath } from './utils'
+
+const debug = debugFactory('node-headers')
+
+// the arch in the paths of `node.lib` on https://nodejs.org/download/release
+const NODE_LIB_DIST_ARCH: Record<string, string> = {
+  x64: 'x64',
+  ia32: 'x86',
+  arm64: 'arm64',
+}
+
+export interface NodeHeaders {
+  // the dir of `node_api.h`
+  includeDir: string
+  // the dir of `node.lib`, only for the Windows MSVC targets
+  libDir: string | null
+  napiVersion: number
+}
+
+export function isUrl(source: string) {
+  return /^https:\/\//.test(source)
+}
+
+function isDir(path: string) {
+  return (
+    existsSync(toLongPath(path)) && statSync(toLongPath(path)).isDirectory()
+  )
+}
+
+/**
+ * The dir of `node_api.h` in `root`, which is the include dir itself, the root of the headers tarball of Node.js or the dir the tarball is extracted into.
+ */
+export function findIncludeDir(root: string): string | null {
+  const candidates = [
+    root,
+    join(root, 'include', 'node'),
+    join(root, 'include'),
+  ]
+  const found = candidates.find((dir) =>
+    existsSync(toLongPath(join(dir, 'node_api.h'))),
+  )
+  if (found) {
+    return found
+  }
+  // `node-v16.14.0/include/node` of the tarball
+  const children = isDir(root) ? readdirSync(toLongPath(root)) : []
+  if (children.length === 1 && isDir(join(root, children[0]))) {
+    return findIncludeDir(join(root, children[0]))
+  }
+  return null
+}
+
+/**
+ * `NAPI_VERSION` defined by `node_version.h`, or the default of `js_native_api.h`.
+ */
+export function readNapiVersion(includeDir: string): number | null {
+  for (const header of ['node_version.h', 'js_native_api.h']) {
+    const headerPath = join(includeDir, header)
+    if (!existsSync(toLongPath(headerPath))) {
+      continue
+    }
+    const matched = /^\s*#\s*define\s+NAPI_VERSION\s+(\d+)\s*$/m.exec(
+      readFileSync(toLongPath(headerPath), 'utf8'),
+    )
+    if (matched) {
+      return Number(matched[1])
+    }
+  }
+  return null
+}
+
+/**
+ * The N-API version required by the `napiN` features enabled of the napi crate.
+ */
+export function requiredNapiVersion(features: string[]) {
+  return features.reduce((version, feature) => {
+    const matched = /^napi(\d+)$/.exec(feature)
+    return matched ? Math.max(version, Number(matched[1])) : version
+  }, 1)
+}
+
+/**
+ * The dir of `node.lib` of `arch` in `root`, in the layout of the node-gyp cache, nodejs.org or flat.
+ */
+export function findNodeLibDir(root: string, arch: string): string | null {
+  const distArch = NODE_LIB_DIST_ARCH[arch] ?? arch
+  const candidates = [
+    join(root, arch),
+    join(root, `win-${distArch}`),
+    join(root, 'Release'),
+    join(root, 'lib'),
+    root,
+  ]
+  return (
+    candidates.find((dir) => existsSync(toLongPath(join(dir, 'node.lib')))) ??
+    null
+  )
+}
+
+function download(url: string, dest: string): Promise<void> {
+  return new Promise((resolve, reject) => {
+    get(url, { agent: createAgent(url) }, (res) => {
+      const { statusCode = 0, headers } = res
+      if (statusCode >= 300 && statusCode < 400 && headers.location) {
+        res.resume()
+        download(new URL(headers.location, url).toString(), dest).then(
+          resolve,
+          reject,
+        )
+        return
+      }
+      if (statusCode !== 200) {
+        res.resume()
+        reject(
+          Object.assign(new Error(`GET ${url} responded ${statusCode}`), {
+            status: statusCode,
+          }),
+        )
+        return
+      }
+      const file = createWriteStream(toLongPath(dest))
+      file.on('error', reject)
+      file.on('finish', () => resolve())
+      res.pipe(file)
+    }).on('error', reject)
+  })
+}
+
+async function downloadTo(url: string, dest: string) {
+  debug(`Download ${chalk.green(url)} to ${dest}`)
+  const tmp = `${dest}.download`
+  await withRetries(`Download ${url}`, () => download(url, tmp))
+  renameSync(toLongPath(tmp), toLongPath(dest))
+}
+
+/**
+ * Download and extract the headers tarball into the cache, the cached one is reused.
+ * The `node.lib` next to it is downloaded for the Windows MSVC targets, like node-gyp does.
+ */
+async function fetchNodeHeaders(url: string, triple: PlatformDetail) {
+  const cacheDir = join(
+    envPaths('napi-rs').cache,
+    'node-headers',
+    createHash('sha256').update(url).digest('hex').substring(0, 16),
+  )
+  const extractDir = join(cacheDir, 'headers')
+  if (!findIncludeDir(extractDir)) {
+    mkdirSync(toLongPath(extractDir), { recursive: true })
+    const archive = join(cacheDir, 'headers.tar.gz')
+    await downloadTo(url, archive)
+    execSync(`tar -xzf "${archive}" -C "${extractDir}"`, { stdio: 'pipe' })
+  }
+  if (triple.platform === 'win32' && triple.abi === 'msvc') {
+    const libDir = join(cacheDir, triple.arch)
+    if (!existsSync(toLongPath(join(libDir, 'node.lib')))) {
+      mkdirSync(toLongPath(libDir), { recursive: true })
+      const libUrl = new URL(
+        `win-${NODE_LIB_DIST_ARCH[triple.arch] ?? triple.arch}/node.lib`,
+        url,
+      ).toString()
+      await downloadTo(libUrl, join(libDir, 'node.lib'))
+    }
+  }
+  return cacheDir
+}
+
+/**
+ * The headers and `node.lib` of `--node-headers`, a local dir or the url of a headers tarball.
+ * The N-API version exported must not be lower than the one required by the napi crate.
+ */
+export async function resolveNodeHeaders(
+  source: string,
+  triple: PlatformDetail,
+  requiredVersion: number,
+): Promise<NodeHeaders> {
+  const root = isUrl(source)
+    ? await fetchNodeHeaders(source, triple)
+    : resolve(source)
+  if (!isUrl(source) && !isDir(root)) {
+    throw new TypeError(
+      `Node headers dir ${chalk.yellowBright(root)} is not existed`,
+    )
+  }
+  const includeDir = findIncludeDir(root)
+  if (!includeDir) {
+    throw new TypeError(
+      `Could not find ${chalk.green('node_api.h')} in ${chalk.yellowBright(
+        root,
+      )}`,
+    )
+  }
+  const napiVersion = readNapiVersion(includeDir)
+  if (napiVersion === null) {
+    throw new TypeError(
+      `Could not read ${chalk.green('NAPI_VERSION')} from the headers in ${chalk.yellowBright(
+        includeDir,
+      )}`,
+    )
+  }
+  if (napiVersion < requiredVersion) {
+    throw new TypeError(
+      `The headers in ${chalk.yellowBright(
+        includeDir,
+      )} export N-API ${napiVersion}, but the features of napi require N-API ${requiredVersion}, disable ${chalk.green(
+        `napi${requiredVersion}`,
+      )} and the features depending on it`,
+    )
+  }
+  let libDir: string | null = null
+  if (triple.platform === 'win32' && triple.abi === 'msvc') {
+    libDir = findNodeLibDir(root, triple.arch)
+    if (!libDir) {
+      throw new TypeError(
+        `Could not find ${chalk.green('node.lib')} of ${
+          triple.arch
+        } in ${chalk.yellowBright(root)}`,
+      )
+    }
+  }
+  debug(
+    `Node headers: ${chalk.green(
+      includeDir,
+    )}, N-API ${napiVersion}, lib: ${libDir}`,
+  )
+  return { includeDir, libDir, napiVersion }
+}
@@ -0,0 +1,117 @@
+import { existsSync, readFileSync } from 'fs'
+import { join } from 'path'
+
+import chalk from 'chalk'
+import toml from 'toml'
+
+import { debugFactory } from './debug'
+import { toLongPath } from './utils'
+
+const debug = debugFactory('offline')
+
+export interface LockedCrate {
+  name: string
+  version: string
+  // `registry+https://github.com/rust-lang/crates.io-index` or `git+https://...`
+  source: string
+  checksum?: string
+}
+
+/**
+ * Everything `napi build` would have fetched without `--offline`.
+ */
+export interface OfflineManifest {
+  target: string
+  // the crates from registries and git, vendored or in the cargo cache
+  crates: LockedCrate[]
+  vendored: boolean
+  // the std of the targets not installed, `rustup target add` is skipped
+  rustTargets: string[]
+}
+
+export function isOffline(env: NodeJS.ProcessEnv = process.env) {
+  return env.NAPI_RS_OFFLINE === 'true' || env.NAPI_RS_OFFLINE === '1'
+}
+
+/**
+ * Throw if the network is forbidden by `--offline`.
+ */
+export function assertOnline(
+  operation: string,
+  env: NodeJS.ProcessEnv = process.env,
+) {
+  if (isOffline(env)) {
+    throw new TypeError(
+      `${operation} requires network access, which is forbidden by ${chalk.green(
+        '--offline',
+      )}`,
+    )
+  }
+}
+
+/**
+ * The crates in `Cargo.lock` fetched from the registries and git, path dependencies are excluded.
+ */
+export function readLockedCrates(workspaceRoot: string): LockedCrate[] {
+  const lockfile = join(workspaceRoot, 'Cargo.lock')
+  if (!existsSync(toLongPath(lockfile))) {
+    throw new TypeError(
+      `${chalk.yellowBright(lockfile)} is required by ${chalk.green(
+        '--offline',
+      )}, run ${chalk.green('cargo generate-lockfile')} with network access`,
+    )
+  }
+  const { package: packages = [] } = toml.parse(
+    readFileSync(toLongPath(lockfile), 'utf8'),
+  )
+  return packages
+    .filter(({ source }: LockedCrate) => source)
+    .map(({ name, version, source, checksum }: LockedCrate) => ({
+      name,
+      version,
+      source,
+      ...(checksum ? { checksum } : {}),
+    }))
+}
+
+function hasSourceReplacement(workspaceRoot: string) {
+  return ['config.toml', 'config'].some((file) => {
+    const configPath = join(workspaceRoot, '.cargo', file)
+    return (
+      existsSync(toLongPath(configPath)) &&
+      readFileSync(toLongPath(configPath), 'utf8').includes('replace-with')
+    )
+  })
+}
+
+/**
+ * If the crates are vendored, into `vendor` or the source replacement in `.cargo/config.toml`.
+ */
+export function isVendored(workspaceRoot: string) {
+  return (
+    existsSync(toLongPath(join(workspaceRoot, 'vendor'))) ||
+    hasSourceReplacement(workspaceRoot)
+  )
+}
+
+/**
+ * `--config` flags of cargo replacing crates.io with `vendor` in the workspace, created by `cargo vendor`.
+ * Nothing if there is no `vendor` dir, or the source replacement is already configured in `.cargo/config.toml`.
+ */
+export function vendorConfigFlags(workspaceRoot: string): string[] {
+  const vendorDir = join(workspaceRoot, 'vendor')
+  if (!existsSync(toLongPath(vendorDir))) {
+    return []
+  }
+  if (hasSourceReplacement(workspaceRoot)) {
+    debug(`Source replacement is configured in ${workspaceRoot}/.cargo`)
+    return []
+  }
+  // TOML literal strings, the double quotes work in both sh and cmd
+  return [
+    `--config "source.crates-io.replace-with='vendored-sources'"`,
+    `--config "source.vendored-sources.directory='${vendorDir
+      .split('\\')
+      .join('/')}'"`,
+  ]
+}
@@ -0,0 +1,147 @@
+import { execSync } from 'child_process'
+
+import chalk from 'chalk'
+
+import { debugFactory } from './debug'
+
+const debug = debugFactory('pack-preview')
+
+export interface PackedFile {
+  path: string
+  size: number
+}
+
+export interface PackResult {
+  name: string
+  version: string
+  // size of the tarball
+  size: number
+  unpackedSize: number
+  files: PackedFile[]
+}
+
+/**
+ * The largest `unpackedSize` of the packages, in bytes or like `20MB`.
+ * A single budget applies to every package, the keys of an object are the package names and `*` for the others.
+ */
+export type SizeBudgetConfig = number | string | Record<string, number | string>
+
+export interface SizeBudgetViolation {
+  name: string
+  unpackedSize: number
+  budget: number
+}
+
+const SIZE_PATTERN = /^(\d+(?:\.\d+)?)\s*(B|KB|MB|GB)?$/i
+
+const SIZE_UNITS: Record<string, number> = {
+  B: 1,
+  KB: 1024,
+  MB: 1024 ** 2,
+  GB: 1024 ** 3,
+}
+
+/**
+ * The result of `npm pack --json`.
+ */
+export function parsePackOutput(output: string): PackResult & {
+  filename: string
+} {
+  const [pack] = JSON.parse(output)
+  return {
+    name: pack.name,
+    version: pack.version,
+    filename: pack.filename,
+    size: pack.size,
+    unpackedSize: pack.unpackedSize,
+    files: (pack.files ?? []).map(({ path, size }: PackedFile) => ({
+      path,
+      size,
+    })),
+  }
+}
+
+/**
+ * The tarball `npm publish` would create from `dir`.
+ */
+export function packDryRun(dir: string): PackResult {
+  debug(`Run npm pack --dry-run in [${chalk.yellowBright(dir)}]`)
+  const output = execSync('npm pack --dry-run --json --ignore-scripts', {
+    cwd: dir,
+    stdio: ['ignore', 'pipe', 'pipe'],
+    env: process.env,
+  }).toString('utf8')
+  const { filename, ...pack } = parsePackOutput(output)
+  return pack
+}
+
+export function parseSize(size: number | string): number {
+  if (typeof size === 'number') {
+    return size
+  }
+  const matched = SIZE_PATTERN.exec(size.trim())
+  if (!matched) {
+    throw new TypeError(
+      `Invalid size [${size}], expected bytes or like ${chalk.green('20MB')}`,
+    )
+  }
+  const [, value, unit = 'B'] = matched
+  return Math.round(Number(value) * SIZE_UNITS[unit.toUpperCase()])
+}
+
+export function formatSize(bytes: number) {
+  const unit =
+    ['GB', 'MB', 'KB'].find((unit) => bytes >= SIZE_UNITS[unit]) ?? 'B'
+  return unit === 'B'
+    ? `${bytes}B`
+    : `${(bytes / SIZE_UNITS[unit]).toFixed(1)}${unit}`
+}
+
+export function getSizeBudget(
+  packageName: string,
+  config?: SizeBudgetConfig,
+): number | null {
+  if (config === undefined) {
+    return null
+  }
+  if (typeof config !== 'object') {
+    return parseSize(config)
+  }
+  const budget = config[packageName] ?? config['*']
+  return budget === undefined ? null : parseSize(budget)
+}
+
+export function checkSizeBudgets(
+  packs: PackResult[],
+  config?: SizeBudgetConfig,
+): SizeBudgetViolation[] {
+  return packs.flatMap(({ name, unpackedSize }) => {
+    const budget = getSizeBudget(name, config)
+    return budget !== null && unpackedSize > budget
+      ? [{ name, unpackedSize, budget }]
+      : []
+  })
+}
+
+/**
+ * The files of the package from the largest, with the sizes of the tarball.
+ */
+export function formatPackPreview(pack: PackResult, budget: number | null) {
+  const files = [...pack.files]
+    .sort((a, b) => b.size - a.size)
+    .map(({ path, size }) => `  ${formatSize(size).padStart(8)}  ${path}`)
+  const unpacked = formatSize(pack.unpackedSize)
+  return [
+    chalk.green(`${pack.name}@${pack.version}`),
+    ...files,
+    `  ${chalk.bold('tarball')} ${formatSize(pack.size)}, ${chalk.bold(
+      'unpacked',
+    )} ${
+      budget === null
+        ? unpacked
+        : `${
+            pack.unpackedSize > budget ? chalk.red(unpacked) : unpacked
+          } / ${formatSize(budget)}`
+    }, ${pack.files.length} files`,
+  ].join('\n')
+}
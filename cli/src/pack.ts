@@ -0,0 +1,128 @@
+import { execSync } from 'child_process'
+import { existsSync, mkdirSync } from 'fs'
+import { dirname, join, relative, resolve } from 'path'
+
+import chalk from 'chalk'
+import { Command, Option } from 'clipanion'
+
+import { getNapiConfig } from './consts'
+import { debugFactory } from './debug'
+import { enableJsonOutput, JsonOption, printJson } from './json-output'
+import { formatSize, PackResult, parsePackOutput } from './pack-preview'
+import { toLongPath } from './utils'
+
+const debug = debugFactory('pack')
+
+export interface PackedPackage {
+  dir: string
+  // the file the tarball must contain, `null` for the main package
+  addon: string | null
+  tarball: string
+  pack: PackResult
+}
+
+/**
+ * The packages whose tarball doesn't contain the addon, or the `index.d.ts` of the types packages.
+ */
+export function findMissingAddons(packages: PackedPackage[]) {
+  return packages.filter(
+    ({ addon, pack }) =>
+      addon !== null && !pack.files.some(({ path }) => path === addon),
+  )
+}
+
+export class PackCommand extends Command {
+  static usage = Command.Usage({
+    description:
+      'Create the tarballs of the main package and the platform packages into a dir',
+  })
+
+  static paths = [['pack']]
+
+  prefix = Option.String(`-p,--prefix`, 'npm')
+
+  distDir = Option.String('--dist', 'dist', {
+    description: 'Dir of the tarballs',
+  })
+
+  configFileName?: string = Option.String('-c,--config')
+
+  json = JsonOption()
+
+  async execute() {
+    if (this.json) {
+      enableJsonOutput()
+    }
+    const { packageJsonPath, platforms, binaryName, platformTypes, version } =
+      getNapiConfig(this.configFileName)
+    const distDir = resolve(this.distDir)
+    mkdirSync(toLongPath(distDir), { recursive: true })
+
+    const packages: { dir: string; addon: string | null }[] = [
+      { dir: dirname(packageJsonPath), addon: null },
+      ...platforms.map((platformDetail) => ({
+        dir: join(process.cwd(), this.prefix, platformDetail.platformArchABI),
+        addon: `${binaryName}.${platformDetail.platformArchABI}.node`,
+      })),
+      ...(platformTypes
+        ? [...new Set(platforms.map((p) => p.platform))].map((platform) => ({
+            dir: join(process.cwd(), this.prefix, `types-${platform}`),
+            addon: 'index.d.ts',
+          }))
+        : []),
+    ]
+
+    const packed: PackedPackage[] = []
+    for (const { dir, addon } of packages) {
+      if (!existsSync(toLongPath(join(dir, 'package.json')))) {
+        console.warn(
+          `[${chalk.yellowBright(join(dir, 'package.json'))}] is not existed`,
+        )
+        continue
+      }
+      debug(`Run npm pack [${chalk.yellowBright(dir)}]`)
+      // run in the dist dir, npm writes the tarball into the cwd
+      const output = execSync(`npm pack "${dir}" --json --ignore-scripts`, {
+        cwd: distDir,
+        stdio: ['ignore', 'pipe', 'inherit'],
+        env: process.env,
+      }).toString('utf8')
+      const pack = parsePackOutput(output)
+      const tarball = join(distDir, pack.filename)
+      packed.push({ dir, addon, tarball, pack })
+      console.info(
+        `${chalk.green(relative(process.cwd(), tarball))} ${formatSize(
+          pack.size,
+        )}`,
+      )
+    }
+
+    const missing = findMissingAddons(packed)
+    if (missing.length) {
+      throw new TypeError(
+        `The tarballs don't contain the addons, build them and copy them by ${chalk.green(
+          'napi artifacts',
+        )}, or add them to the files of package.json:\n${missing
+          .map(
+            ({ tarball, addon }) =>
+              `  ${chalk.yellowBright(
+                relative(process.cwd(), tarball),
+              )} ${addon}`,
+          )
+          .join('\n')}`,
+      )
+    }
+
+    if (this.json) {
+      printJson({
+        version,
+        tarballs: packed.map(({ pack, tarball }) => ({
+          name: pack.name,
+          tarball,
+          size: pack.size,
+          unpackedSize: pack.unpackedSize,
+        })),
+      })
+    }
+  }
+}
@@ -0,0 +1,183 @@
+import { existsSync, readFileSync } from 'fs'
+import { join } from 'path'
+
+export type PackageManager = 'npm' | 'yarn' | 'yarn-berry' | 'pnpm'
+
+export const SupportedPackageManagers: PackageManager[] = [
+  'npm',
+  'yarn',
+  'yarn-berry',
+  'pnpm',
+]
+
+export interface PackageManagerDetail {
+  name: PackageManager
+  // the binary name
+  bin: string
+  lockfile: string
+  // `cache` option of actions/setup-node
+  cache: string
+  // install dependencies with the lockfile, without running scripts
+  install: string
+  // install dependencies even they don't match the current os/cpu
+  installIgnorePlatform: string
+  // prefix to run a script in package.json, `${run} build`
+  run: string
+  // publish the package in current dir, `workspace:` protocol is rewritten by pnpm/yarn berry
+  publish: string
+  // write the `NPM_TOKEN` env into the config of package manager
+  publishAuth: string
+  // make the package manager available in a bare Node.js environment, like docker images and VMs
+  setup: string
+  // add a package to the dependencies
+  add: string
+}
+
+const YARN_REGISTRY_FLAGS =
+  '--frozen-lockfile --registry https://registry.npmjs.org --network-timeout 300000'
+
+const NPM_PUBLISH_AUTH =
+  'echo "//registry.npmjs.org/:_authToken=$NPM_TOKEN" >> ~/.npmrc'
+
+const PackageManagerDetails: Record<PackageManager, PackageManagerDetail> = {
+  npm: {
+    name: 'npm',
+    bin: 'npm',
+    lockfile: 'package-lock.json',
+    cache: 'npm',
+    install: 'npm ci --ignore-scripts',
+    installIgnorePlatform: 'npm ci --ignore-scripts --force',
+    run: 'npm run',
+    publish: 'npm publish',
+    publishAuth: NPM_PUBLISH_AUTH,
+    setup: '',
+    add: 'npm install',
+  },
+  yarn: {
+    name: 'yarn',
+    bin: 'yarn',
+    lockfile: 'yarn.lock',
+    cache: 'yarn',
+    install: `yarn install --ignore-scripts ${YARN_REGISTRY_FLAGS}`,
+    installIgnorePlatform: `yarn install --ignore-scripts --ignore-platform ${YARN_REGISTRY_FLAGS}`,
+    run: 'yarn',
+    publish: 'npm publish',
+    publishAuth: NPM_PUBLISH_AUTH,
+    setup: 'npm install -g yarn',
+    add: 'yarn add',
+  },
+  'yarn-berry': {
+    name: 'yarn-berry',
+    bin: 'yarn',
+    lockfile: 'yarn.lock',
+    cache: 'yarn',
+    install: 'yarn install --immutable --mode=skip-build',
+    installIgnorePlatform: 'yarn install --immutable --mode=skip-build',
+    run: 'yarn',
+    publish: 'yarn npm publish',
+    publishAuth: 'yarn config set npmAuthToken "$NPM_TOKEN"',
+    setup: 'npm install -g yarn',
+    add: 'yarn add',
+  },
+  pnpm: {
+    name: 'pnpm',
+    bin: 'pnpm',
+    lockfile: 'pnpm-lock.yaml',
+    cache: 'pnpm',
+    install: 'pnpm install --frozen-lockfile --ignore-scripts',
+    installIgnorePlatform:
+      'pnpm install --frozen-lockfile --ignore-scripts --force',
+    run: 'pnpm',
+    publish: 'pnpm publish --no-git-checks',
+    publishAuth: NPM_PUBLISH_AUTH,
+    setup: 'npm install -g pnpm',
+    add: 'pnpm add',
+  },
+}
+
+export function getPackageManagerDetail(
+  packageManager = 'yarn',
+): PackageManagerDetail {
+  const detail = PackageManagerDetails[packageManager as PackageManager]
+  if (!detail) {
+    throw new TypeError(
+      `Unsupported package manager [${packageManager}], expected one of ${SupportedPackageManagers.join(
+        ', ',
+      )}`,
+    )
+  }
+  return detail
+}
+
+/**
+ * Run a script in package.json, npm requires `--` to pass args to the script.
+ */
+export function runScript(
+  packageManager: PackageManagerDetail,
+  script: string,
+  args = '',
+) {
+  if (!args) {
+    return `${packageManager.run} ${script}`
+  }
+  return packageManager.name === 'npm'
+    ? `${packageManager.run} ${script} -- ${args}`
+    : `${packageManager.run} ${script} ${args}`
+}
+
+/**
+ * Detect the package manager from the `packageManager` field in package.json or the lockfile in `cwd`.
+ * Fallback to yarn classic, which is what the generated project used before.
+ */
+export function detectPackageManager(cwd = process.cwd()): PackageManager {
+  const packageJsonPath = join(cwd, 'package.json')
+  if (existsSync(packageJsonPath)) {
+    const { packageManager } = JSON.parse(
+      readFileSync(packageJsonPath, 'utf8'),
+    )
+    if (typeof packageManager === 'string') {
+      const [name, version = ''] = packageManager.split('@')
+      if (name === 'pnpm' || name === 'npm') {
+        return name
+      }
+      if (name === 'yarn') {
+        return version.startsWith('1.') ? 'yarn' : 'yarn-berry'
+      }
+    }
+  }
+  if (existsSync(join(cwd, 'pnpm-lock.yaml'))) {
+    return 'pnpm'
+  }
+  if (existsSync(join(cwd, 'yarn.lock'))) {
+    return existsSync(join(cwd, '.yarnrc.yml')) ? 'yarn-berry' : 'yarn'
+  }
+  if (existsSync(join(cwd, 'package-lock.json'))) {
+    return 'npm'
+  }
+  return 'yarn'
+}
+
+const DEPENDENCIES_FIELDS = [
+  'dependencies',
+  'devDependencies',
+  'optionalDependencies',
+  'peerDependencies',
+]
+
+/**
+ * `workspace:` protocol is only rewritten to the real version by pnpm and yarn berry while publishing,
+ * npm and yarn classic would publish it as it is.
+ */
+export function findWorkspaceProtocolDependencies(
+  packageJson: Record<string, any>,
+): string[] {
+  return DEPENDENCIES_FIELDS.reduce<string[]>(
+    (acc, field) =>
+      acc.concat(
+        Object.entries<string>(packageJson[field] ?? {})
+          .filter(([, range]) => range.startsWith('workspace:'))
+          .map(([name]) => name),
+      ),
+    [],
+  )
+}
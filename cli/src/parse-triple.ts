@@ -60,6 +60,23 @@ export const DefaultPlatforms: PlatformDetail[] = [
   },
 ]
 
+// the targets of the generated GitHub Actions workflow
+export const SupportedPlatforms: string[] = [
+  'aarch64-apple-darwin',
+  'aarch64-linux-android',
+  'aarch64-unknown-linux-gnu',
+  'aarch64-unknown-linux-musl',
+  'aarch64-pc-windows-msvc',
+  'armv7-unknown-linux-gnueabihf',
+  'x86_64-apple-darwin',
+  'x86_64-pc-windows-msvc',
+  'x86_64-unknown-linux-gnu',
+  'x86_64-unknown-linux-musl',
+  'x86_64-unknown-freebsd',
+  'i686-pc-windows-msvc',
+  'armv7-linux-androideabi',
+]
+
 /**
  * A triple is a specific format for specifying a target architecture.
  * Triples may be referred to as a target triple which is the architecture for the artifact produced, and the host triple which is the architecture that the compiler is running on.
@@ -125,3 +142,25 @@ export function getDefaultTargetTriple(rustcfg: string): PlatformDetail {
   }
   return parseTriple(triple)
 }
+
+/**
+ * The triple of the active toolchain, from `rustc -vV` if Rust is not installed by rustup, like the `rust` package of FreeBSD.
+ */
+export function getHostTargetTriple(): PlatformDetail {
+  let activeToolchain: string
+  try {
+    activeToolchain = execSync('rustup show active-toolchain', {
+      env: process.env,
+      stdio: 'pipe',
+    }).toString('utf8')
+  } catch {
+    const host = /^host: (.+)$/m.exec(
+      execSync('rustc -vV', { env: process.env }).toString('utf8'),
+    )
+    if (!host) {
+      throw new TypeError('Can not get the host triple from rustc -vV')
+    }
+    return parseTriple(host[1].trim())
+  }
+  return getDefaultTargetTriple(activeToolchain)
+}
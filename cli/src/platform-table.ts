@@ -0,0 +1,110 @@
+import { existsSync, readFileSync, writeFileSync } from 'fs'
+
+import { DEFAULT_ANDROID_API_LEVEL } from './android-ndk'
+import { DOCKER_BUILD_CONFIGS } from './docker'
+import { PlatformDetail } from './parse-triple'
+import { toLongPath } from './utils'
+
+export const PLATFORM_TABLE_START = '<!-- napi-rs platforms start -->'
+export const PLATFORM_TABLE_END = '<!-- napi-rs platforms end -->'
+
+const PLATFORM_NAMES: Record<string, string> = {
+  win32: 'Windows',
+  darwin: 'macOS',
+  linux: 'Linux',
+  android: 'Android',
+  freebsd: 'FreeBSD',
+}
+
+// the oldest versions supported by the Rust targets
+const OS_REQUIREMENTS: Record<string, string> = {
+  'x86_64-apple-darwin': 'macOS >= 10.7',
+  'aarch64-apple-darwin': 'macOS >= 11',
+  'x86_64-pc-windows-msvc': 'Windows >= 7',
+  'i686-pc-windows-msvc': 'Windows >= 7',
+  'aarch64-pc-windows-msvc': 'Windows >= 10',
+}
+
+/**
+ * The libc or the OS version the prebuilt addon of the generated GitHub Actions workflow requires.
+ */
+export function getPlatformRequirement(platform: PlatformDetail) {
+  if (platform.platform === 'linux') {
+    if (platform.abi?.startsWith('musl')) {
+      return 'musl'
+    }
+    // linked with the older glibc by `zig cc`
+    const args = DOCKER_BUILD_CONFIGS[platform.raw]?.args ?? []
+    const abiSuffix = args.indexOf('--zig-abi-suffix')
+    return abiSuffix === -1 ? 'glibc' : `glibc >= ${args[abiSuffix + 1]}`
+  }
+  if (platform.platform === 'android') {
+    return `API level >= ${DEFAULT_ANDROID_API_LEVEL}`
+  }
+  return OS_REQUIREMENTS[platform.raw] ?? ''
+}
+
+/**
+ * The markdown table of the platforms with prebuilt addons, `node` is the `engines.node` of package.json.
+ */
+export function createPlatformTable(
+  platforms: PlatformDetail[],
+  packageName: string,
+  node = '>= 10',
+) {
+  const rows = platforms.map((platform) => [
+    `${PLATFORM_NAMES[platform.platform] ?? platform.platform} ${
+      platform.arch
+    }${platform.abi ? ` ${platform.abi}` : ''}`,
+    `\`${platform.raw}\``,
+    `\`${packageName}-${platform.platformArchABI}\``,
+    `\`${node}\``,
+    getPlatformRequirement(platform),
+  ])
+  return [
+    ['Platform', 'Target', 'Package', 'Node.js', 'Requirement'],
+    ['---', '---', '---', '---', '---'],
+    ...rows,
+  ]
+    .map((cells) => `| ${cells.join(' | ')} |`)
+    .join('\n')
+}
+
+/**
+ * Replace the content between the markers of `readme` with `table`, `null` if the markers are missing.
+ */
+export function injectPlatformTable(readme: string, table: string) {
+  const start = readme.indexOf(PLATFORM_TABLE_START)
+  const end = readme.indexOf(PLATFORM_TABLE_END)
+  if (start === -1 || end < start) {
+    return null
+  }
+  return `${readme.substring(
+    0,
+    start + PLATFORM_TABLE_START.length,
+  )}\n\n${table}\n\n${readme.substring(end)}`
+}
+
+/**
+ * Update the table between the markers of the README, returns whether it's changed.
+ */
+export function updateReadmePlatformTable(
+  readmePath: string,
+  platforms: PlatformDetail[],
+  packageName: string,
+  node?: string,
+) {
+  if (!existsSync(toLongPath(readmePath))) {
+    return false
+  }
+  const readme = readFileSync(toLongPath(readmePath), 'utf8')
+  const updated = injectPlatformTable(
+    readme,
+    createPlatformTable(platforms, packageName, node),
+  )
+  if (updated === null || updated === readme) {
+    return false
+  }
+  writeFileSync(toLongPath(readmePath), updated)
+  return true
+}
@@ -1,5 +1,5 @@
 import { createReadStream, existsSync, statSync } from 'fs'
-import { join } from 'path'
+import { dirname, join, relative } from 'path'
 
 import { Octokit } from '@octokit/rest'
 import chalk from 'chalk'
@@ -7,8 +7,29 @@ import { Command, Option } from 'clipanion'
 
 import { getNapiConfig } from './consts'
 import { debugFactory } from './debug'
+import { DryRunPackage, formatDryRunSummary, getDryRunProblems } from './dry-run'
+import { getGitRef, resolveDistTag } from './dist-tag'
+import { enableJsonOutput, JsonOption, printJson } from './json-output'
+import { createAgent, networkEnv, withRetries } from './network'
+import { assertOnline } from './offline'
+import {
+  checkSizeBudgets,
+  formatPackPreview,
+  formatSize,
+  getSizeBudget,
+  packDryRun,
+} from './pack-preview'
+import {
+  detectPackageManager,
+  findWorkspaceProtocolDependencies,
+  getPackageManagerDetail,
+} from './package-manager'
+import { updateReadmePlatformTable } from './platform-table'
+import { scanFiles, SecretFinding } from './secrets-scan'
 import { spawn } from './spawn'
+import { ArtifactStorage, parseStorageUrl } from './storage'
 import { updatePackageJson } from './update-package'
+import { toLongPath } from './utils'
 import { VersionCommand } from './version'
 
 const debug = debugFactory('prepublish')
@@ -33,25 +54,234 @@ export class PrePublishCommand extends Command {
 
   configFileName?: string = Option.String('-c,--config')
 
-  isDryRun = Option.Boolean('--dry-run', false)
+  isDryRun = Option.Boolean('--dry-run', false, {
+    description: `Run every step but ${chalk.green(
+      'npm publish',
+    )} and the GitHub release, and print what would be published`,
+  })
 
   skipGHRelease = Option.Boolean('--skip-gh-release', false)
 
+  distTag?: string = Option.String('--dist-tag', {
+    description: `npm dist-tag of the platform packages, resolved from ${chalk.green(
+      'napi.distTags',
+    )} by the current branch by default`,
+  })
+
+  skipSecretsScan = Option.Boolean('--skip-secrets-scan', false, {
+    description: `Don't check the packages for env files, private keys and debug artifacts before publishing, configured by ${chalk.green(
+      'napi.secretsScan',
+    )}`,
+  })
+
+  packPreview = Option.Boolean('--pack-preview', false, {
+    description: `Print the files and sizes of the packages ${chalk.green(
+      'npm publish',
+    )} would create and check them against ${chalk.green(
+      'napi.sizeBudget',
+    )}, without publishing`,
+  })
+
+  fromStorage?: string = Option.String('--from-storage', {
+    description: `Download the addons uploaded by ${chalk.green(
+      'napi artifacts --upload',
+    )} into the platform packages before publishing`,
+  })
+
+  packageManager?: string = Option.String('--package-manager', {
+    description: `Package manager used to publish the main package, ${chalk.green(
+      'npm',
+    )}, ${chalk.green('yarn')}, ${chalk.green('yarn-berry')} or ${chalk.green(
+      'pnpm',
+    )}. Detected from the lockfile by default`,
+  })
+
+  json = JsonOption()
+
   async execute() {
-    const { packageJsonPath, platforms, version, packageName, binaryName } =
-      getNapiConfig(this.configFileName)
+    if (this.json) {
+      enableJsonOutput()
+    }
+    // the platform and types packages published by npm
+    const published: { name: string; version: string; dir: string }[] = []
+    const releaseAssets: { file: string; url: string }[] = []
+    const {
+      packageJsonPath,
+      platforms,
+      version,
+      packageName,
+      binaryName,
+      binaryNames,
+      content,
+      platformTypes,
+      secretsScan,
+      sizeBudget,
+      distTags,
+    } = getNapiConfig(this.configFileName)
+    const distTag =
+      this.distTag ??
+      (distTags ? resolveDistTag(distTags, getGitRef(), version) : null)
+    const publishCommand = distTag
+      ? `npm publish --tag ${distTag}`
+      : 'npm publish'
+    debug(`Publish with ${chalk.green(publishCommand)}`)
+    const packageManager = getPackageManagerDetail(
+      this.packageManager ?? detectPackageManager(),
+    )
+    if (packageManager.name === 'npm' || packageManager.name === 'yarn') {
+      // `optionalDependencies` of the platform packages are replaced with the real version below
+      const platformPackages = platforms.map(
+        (platformDetail) => `${packageName}-${platformDetail.platformArchABI}`,
+      )
+      const workspaceDependencies = findWorkspaceProtocolDependencies(
+        content,
+      ).filter((name) => !platformPackages.includes(name))
+      if (workspaceDependencies.length) {
+        throw new TypeError(
+          `${chalk.yellowBright(
+            workspaceDependencies.join(', '),
+          )} use the workspace: protocol, which is published as it is by ${
+            packageManager.name
+          }. Publish with pnpm or yarn berry, or replace them with the real versions`,
+        )
+      }
+    }
     debug(`Update optionalDependencies in [${packageJsonPath}]`)
-    if (!this.isDryRun) {
-      await VersionCommand.updatePackageJson(this.prefix, this.configFileName)
-      await updatePackageJson(packageJsonPath, {
-        optionalDependencies: platforms.reduce(
-          (acc: Record<string, string>, cur) => {
-            acc[`${packageName}-${cur.platformArchABI}`] = `${version}`
-            return acc
-          },
-          {},
-        ),
-      })
+    await VersionCommand.updatePackageJson(this.prefix, this.configFileName)
+    await updatePackageJson(packageJsonPath, {
+      optionalDependencies: platforms.reduce(
+        (acc: Record<string, string>, cur) => {
+          acc[`${packageName}-${cur.platformArchABI}`] = `${version}`
+          return acc
+        },
+        {},
+      ),
+    })
+    const readmePath = join(dirname(packageJsonPath), 'README.md')
+    if (
+      updateReadmePlatformTable(
+        readmePath,
+        platforms,
+        packageName,
+        content.engines?.node,
+      )
+    ) {
+      debug(`Update the platform table in [${readmePath}]`)
+    }
+
+    if (this.fromStorage) {
+      const storage = new ArtifactStorage(parseStorageUrl(this.fromStorage))
+      for (const platformDetail of platforms) {
+        for (const name of binaryNames) {
+          const filename = `${name}.${platformDetail.platformArchABI}.node`
+          const dstPath = join(
+            process.cwd(),
+            this.prefix,
+            platformDetail.platformArchABI,
+            filename,
+          )
+          const manifest = await storage.downloadArtifact(
+            filename,
+            version,
+            dstPath,
+          )
+          if (manifest) {
+            debug(
+              `Download [${chalk.greenBright(
+                manifest.blob,
+              )}] to [${chalk.yellowBright(dstPath)}]`,
+            )
+          } else {
+            console.warn(
+              `[${chalk.yellowBright(filename)}] of ${version} is not uploaded`,
+            )
+          }
+        }
+      }
+    }
+
+    const packageDirs = [
+      dirname(packageJsonPath),
+      ...platforms.map((p) =>
+        join(process.cwd(), this.prefix, p.platformArchABI),
+      ),
+      ...(platformTypes
+        ? [...new Set(platforms.map((p) => p.platform))].map((platform) =>
+            join(process.cwd(), this.prefix, `types-${platform}`),
+          )
+        : []),
+    ].filter((dir) => existsSync(toLongPath(join(dir, 'package.json'))))
+    const packs =
+      !this.skipSecretsScan ||
+      this.packPreview ||
+      this.isDryRun ||
+      sizeBudget !== undefined
+        ? packageDirs.map((dir) => ({ dir, pack: packDryRun(dir) }))
+        : []
+
+    if (!this.skipSecretsScan) {
+      const findings: SecretFinding[] = []
+      for (const { dir, pack } of packs) {
+        debug(`Scan files of [${chalk.yellowBright(dir)}]`)
+        findings.push(
+          ...scanFiles(
+            dir,
+            pack.files.map(({ path }) => path),
+            secretsScan,
+          ).map(({ file, reason }) => ({
+            file: relative(process.cwd(), join(dir, file)),
+            reason,
+          })),
+        )
+      }
+      if (findings.length) {
+        throw new TypeError(
+          `Found files which should not be published, remove them or add them to ${chalk.green(
+            'napi.secretsScan.ignore',
+          )}:\n${findings
+            .map(
+              ({ file, reason }) => `  ${chalk.yellowBright(file)} ${reason}`,
+            )
+            .join('\n')}`,
+        )
+      }
+    }
+
+    if (this.packPreview) {
+      for (const { pack } of packs) {
+        console.info(
+          `${formatPackPreview(pack, getSizeBudget(pack.name, sizeBudget))}\n`,
+        )
+      }
+    }
+
+    const violations = checkSizeBudgets(
+      packs.map(({ pack }) => pack),
+      sizeBudget,
+    )
+    if (violations.length) {
+      throw new TypeError(
+        `Packages are larger than ${chalk.green('napi.sizeBudget')}:\n${violations
+          .map(
+            ({ name, unpackedSize, budget }) =>
+              `  ${chalk.yellowBright(name)} ${formatSize(
+                unpackedSize,
+              )} > ${formatSize(budget)}`,
+          )
+          .join('\n')}`,
+      )
+    }
+
+    if (this.packPreview) {
+      if (this.json) {
+        printJson({
+          packages: packs.map(({ pack }) => ({
+            ...pack,
+            budget: getSizeBudget(pack.name, sizeBudget),
+          })),
+        })
+      }
+      return
     }
 
     const { owner, repo, pkgInfo, octokit } = await this.createGhRelease(
@@ -59,6 +289,69 @@ export class PrePublishCommand extends Command {
       version,
     )
 
+    if (this.isDryRun) {
+      const packOf = (dir: string) =>
+        packs.find((p) => p.dir === dir)?.pack ?? null
+      const packages: DryRunPackage[] = [
+        ...platforms.map((platformDetail) => {
+          const dir = join(
+            process.cwd(),
+            this.prefix,
+            platformDetail.platformArchABI,
+          )
+          return {
+            name: `${packageName}-${platformDetail.platformArchABI}`,
+            file: join(
+              dir,
+              `${binaryName}.${platformDetail.platformArchABI}.node`,
+            ),
+            pack: packOf(dir),
+          }
+        }),
+        ...(platformTypes
+          ? [...new Set(platforms.map((p) => p.platform))].map((platform) => {
+              const dir = join(process.cwd(), this.prefix, `types-${platform}`)
+              return {
+                name: `${packageName}-types-${platform}`,
+                file: join(dir, 'index.d.ts'),
+                pack: packOf(dir),
+              }
+            })
+          : []),
+      ].map(({ name, file, pack }) => ({
+        name,
+        version,
+        file: relative(process.cwd(), file),
+        exists: existsSync(toLongPath(file)),
+        pack,
+      }))
+      console.info(
+        formatDryRunSummary(
+          packages,
+          publishCommand,
+          !this.skipGHRelease && repo && owner ? pkgInfo.tag : null,
+        ),
+      )
+      if (this.json) {
+        printJson({
+          dryRun: true,
+          packageName,
+          version,
+          tag: pkgInfo.tag,
+          distTag,
+          packages: packages.map((pkg) => ({
+            name: pkg.name,
+            version: pkg.version,
+            file: pkg.file,
+            size: pkg.pack?.size ?? null,
+            unpackedSize: pkg.pack?.unpackedSize ?? null,
+            problems: getDryRunProblems(pkg),
+          })),
+        })
+      }
+      return
+    }
+
     for (const platformDetail of platforms) {
       const pkgDir = join(
         process.cwd(),
@@ -68,42 +361,63 @@ export class PrePublishCommand extends Command {
       const filename = `${binaryName}.${platformDetail.platformArchABI}.node`
       const dstPath = join(pkgDir, filename)
 
-      if (!this.isDryRun) {
-        if (!existsSync(dstPath)) {
-          console.warn(`[${chalk.yellowBright(dstPath)}] is not existed`)
-          continue
-        }
-        await spawn('npm publish', {
-          cwd: pkgDir,
-          env: process.env,
-        })
-        if (!this.skipGHRelease && repo && owner) {
+      if (!existsSync(toLongPath(dstPath))) {
+        console.warn(`[${chalk.yellowBright(dstPath)}] is not existed`)
+        continue
+      }
+      assertOnline(chalk.green(publishCommand))
+      await spawn(publishCommand, {
+        cwd: pkgDir,
+        env: networkEnv(),
+      })
+      published.push({
+        name: `${packageName}-${platformDetail.platformArchABI}`,
+        version,
+        dir: pkgDir,
+      })
+      if (!this.skipGHRelease && repo && owner) {
+        for (const name of binaryNames) {
+          const assetName = `${name}.${platformDetail.platformArchABI}.node`
+          const assetPath = join(pkgDir, assetName)
+          if (!existsSync(toLongPath(assetPath))) {
+            console.warn(`[${chalk.yellowBright(assetPath)}] is not existed`)
+            continue
+          }
           debug(
             `Start upload [${chalk.greenBright(
-              dstPath,
+              assetPath,
             )}] to Github release, [${chalk.greenBright(pkgInfo.tag)}]`,
           )
           try {
-            const releaseInfo = await octokit!.repos.getReleaseByTag({
-              repo: repo,
-              owner: owner,
-              tag: pkgInfo.tag,
-            })
-            const dstFileStats = statSync(dstPath)
-            const assetInfo = await octokit!.repos.uploadReleaseAsset({
-              owner: owner,
-              repo: repo,
-              name: filename,
-              release_id: releaseInfo.data.id,
-              mediaType: { format: 'raw' },
-              headers: {
-                'content-length': dstFileStats.size,
-                'content-type': 'application/octet-stream',
-              },
-              // @ts-expect-error
-              data: createReadStream(dstPath),
+            const releaseInfo = await withRetries('Get release', () =>
+              octokit!.repos.getReleaseByTag({
+                repo: repo,
+                owner: owner,
+                tag: pkgInfo.tag,
+              }),
+            )
+            const assetStats = statSync(toLongPath(assetPath))
+            // the file is read again on every retry
+            const assetInfo = await withRetries('Upload release asset', () =>
+              octokit!.repos.uploadReleaseAsset({
+                owner: owner,
+                repo: repo,
+                name: assetName,
+                release_id: releaseInfo.data.id,
+                mediaType: { format: 'raw' },
+                headers: {
+                  'content-length': assetStats.size,
+                  'content-type': 'application/octet-stream',
+                },
+                // @ts-expect-error
+                data: createReadStream(toLongPath(assetPath)),
+              }),
+            )
+            console.info(`${chalk.green(assetPath)} upload success`)
+            releaseAssets.push({
+              file: assetPath,
+              url: assetInfo.data.browser_download_url,
             })
-            console.info(`${chalk.green(dstPath)} upload success`)
             console.info(
               `Download url: ${chalk.blueBright(
                 assetInfo.data.browser_download_url,
@@ -112,7 +426,7 @@ export class PrePublishCommand extends Command {
           } catch (e) {
             debug(
               `Param: ${JSON.stringify(
-                { owner, repo, tag: pkgInfo.tag, filename: dstPath },
+                { owner, repo, tag: pkgInfo.tag, filename: assetPath },
                 null,
                 2,
               )}`,
@@ -122,6 +436,39 @@ export class PrePublishCommand extends Command {
         }
       }
     }
+
+    if (platformTypes) {
+      for (const platform of new Set(platforms.map((p) => p.platform))) {
+        const pkgDir = join(process.cwd(), this.prefix, `types-${platform}`)
+        if (!existsSync(toLongPath(join(pkgDir, 'index.d.ts')))) {
+          console.warn(
+            `[${chalk.yellowBright(join(pkgDir, 'index.d.ts'))}] is not existed`,
+          )
+          continue
+        }
+        assertOnline(chalk.green(publishCommand))
+        await spawn(publishCommand, {
+          cwd: pkgDir,
+          env: networkEnv(),
+        })
+        published.push({
+          name: `${packageName}-types-${platform}`,
+          version,
+          dir: pkgDir,
+        })
+      }
+    }
+
+    if (this.json) {
+      printJson({
+        packageName,
+        version,
+        tag: pkgInfo.tag,
+        distTag,
+        published,
+        releaseAssets,
+      })
+    }
   }
 
   private async createGhRelease(packageName: string, version: string) {
@@ -147,6 +494,9 @@ export class PrePublishCommand extends Command {
     const [owner, repo] = GITHUB_REPOSITORY.split('/')
     const octokit = new Octokit({
       auth: process.env.GITHUB_TOKEN,
+      request: {
+        agent: createAgent('https://api.github.com'),
+      },
     })
     let pkgInfo: PackageInfo | undefined
     if (this.tagStyle === 'lerna') {
@@ -173,15 +523,17 @@ export class PrePublishCommand extends Command {
     }
     if (!this.isDryRun) {
       try {
-        await octokit.repos.createRelease({
-          owner,
-          repo,
-          tag_name: pkgInfo.tag,
-          prerelease:
-            version.includes('alpha') ||
-            version.includes('beta') ||
-            version.includes('rc'),
-        })
+        await withRetries('Create release', () =>
+          octokit.repos.createRelease({
+            owner,
+            repo,
+            tag_name: pkgInfo.tag,
+            prerelease:
+              version.includes('alpha') ||
+              version.includes('beta') ||
+              version.includes('rc'),
+          }),
+        )
       } catch (e) {
         debug(
           `Params: ${JSON.stringify(
@@ -1,3 +1,4 @@
+import { existsSync, readdirSync, renameSync } from 'fs'
 import { join } from 'path'
 
 import chalk from 'chalk'
@@ -5,13 +6,63 @@ import { Command, Option } from 'clipanion'
 import { prompt } from 'inquirer'
 import { load, dump } from 'js-yaml'
 
+import { CreateNpmDirCommand } from './create-npm-dir'
 import { debugFactory } from './debug'
-import { spawn } from './spawn'
-import { readFileAsync, writeFileAsync } from './utils'
+import { readFileAsync, toLongPath, writeFileAsync } from './utils'
 
 const debug = debugFactory('rename')
 
+interface RenameNames {
+  packageName: string
+  binaryName: string
+  newPackageName: string
+  newBinaryName: string
+}
+
+// `-[platform]-[arch]-[abi]` of the platform packages
+const PLATFORM_SUFFIX = '-(?:android|darwin|freebsd|linux|win32)-[\\w-]+'
+
+function escapeRegExp(s: string) {
+  return s.replace(/[.*+?^${}()|[\]\\]/g, '\\$&')
+}
+
+/**
+ * Rename the platform packages required and the `.node` files loaded by the generated JS binding.
+ */
+export function renameJsBinding(content: string, names: RenameNames) {
+  return content
+    .replace(
+      new RegExp(
+        `(['"\`])${escapeRegExp(names.packageName)}(${PLATFORM_SUFFIX}['"\`])`,
+        'g',
+      ),
+      `$1${names.newPackageName}$2`,
+    )
+    .replace(
+      new RegExp(
+        `(['"\`/])${escapeRegExp(names.binaryName)}\\.([\\w.-]+\\.node)`,
+        'g',
+      ),
+      `$1${names.newBinaryName}.$2`,
+    )
+}
+
+/**
+ * Replace `name` of the `[package]` table.
+ */
+export function renameCargoPackage(toml: string, name: string) {
+  return toml.replace(
+    /(^\[package\]\s*\n(?:(?!\[)[^\n]*\n)*?name\s*=\s*)"[^"]*"/m,
+    `$1"${name}"`,
+  )
+}
+
 export class RenameCommand extends Command {
+  static usage = Command.Usage({
+    description:
+      'Rename the npm package and the binary name in package.json, the platform packages, the JS binding, Cargo.toml and the GitHub workflows',
+  })
+
   static paths = [['rename']]
 
   name = Option.String('-n', {
@@ -43,6 +94,9 @@ export class RenameCommand extends Command {
     const cwd = this.cwd ?? process.cwd()
     const packageJson = await readFileAsync(join(cwd, 'package.json'), 'utf8')
     const packageJsonData = JSON.parse(packageJson)
+    const packageName: string =
+      packageJsonData.napi?.package?.name ?? packageJsonData.name
+    const binaryName: string = packageJsonData.napi?.name ?? 'index'
     const name =
       this.name ??
       (
@@ -64,6 +118,29 @@ export class RenameCommand extends Command {
     debug('name: %s, napi name: %s', name, napiName)
     packageJsonData.name = name
     packageJsonData.napi.name = napiName
+    if (packageJsonData.napi.package?.name) {
+      packageJsonData.napi.package.name = name
+    }
+    const names = {
+      packageName,
+      binaryName,
+      newPackageName: name,
+      newBinaryName: napiName,
+    }
+    if (packageJsonData.optionalDependencies) {
+      const platformPackage = new RegExp(
+        `^${escapeRegExp(packageName)}${PLATFORM_SUFFIX}$`,
+      )
+      packageJsonData.optionalDependencies = Object.entries(
+        packageJsonData.optionalDependencies,
+      ).reduce((acc: Record<string, unknown>, [dependency, version]) => {
+        const renamed = platformPackage.test(dependency)
+          ? `${name}${dependency.substring(packageName.length)}`
+          : dependency
+        acc[renamed] = version
+        return acc
+      }, {})
+    }
     const repository =
       this.repository ??
       (
@@ -94,28 +171,67 @@ export class RenameCommand extends Command {
       join(cwd, 'package.json'),
       JSON.stringify(packageJsonData, null, 2),
     )
+    console.info(`Renamed ${chalk.yellowBright('package.json')}`)
+
+    const workflowsDir = join(cwd, '.github', 'workflows')
+    const workflows = existsSync(workflowsDir)
+      ? readdirSync(workflowsDir).filter((file) => /\.ya?ml$/.test(file))
+      : []
+    for (const workflow of workflows) {
+      const workflowPath = join(workflowsDir, workflow)
+      const CIObject = load(await readFileAsync(workflowPath, 'utf8')) as any
+      if (!CIObject?.env?.APP_NAME) {
+        continue
+      }
+      CIObject.env.APP_NAME = napiName
+      await writeFileAsync(
+        workflowPath,
+        dump(CIObject, {
+          lineWidth: 1000,
+        }),
+      )
+      console.info(`Renamed ${chalk.yellowBright(workflowPath)}`)
+    }
 
-    const CI = await readFileAsync(
-      join(cwd, '.github', 'workflows', 'CI.yml'),
-      'utf8',
-    )
-    const CIObject = load(CI) as any
-    CIObject.env.APP_NAME = napiName
+    const tomlPath = join(cwd, 'Cargo.toml')
+    const tomlContent = await readFileAsync(tomlPath, 'utf8')
+    await writeFileAsync(tomlPath, renameCargoPackage(tomlContent, napiName))
+    console.info(`Renamed ${chalk.yellowBright('Cargo.toml')}`)
 
-    await writeFileAsync(
-      join(cwd, '.github', 'workflows', 'CI.yml'),
-      dump(CIObject, {
-        lineWidth: 1000,
-      }),
-    )
+    const jsBindingPath = join(cwd, packageJsonData.main ?? 'index.js')
+    if (existsSync(toLongPath(jsBindingPath))) {
+      const jsBinding = await readFileAsync(jsBindingPath, 'utf8')
+      await writeFileAsync(jsBindingPath, renameJsBinding(jsBinding, names))
+      console.info(`Renamed ${chalk.yellowBright(jsBindingPath)}`)
+    }
 
-    let tomlContent = await readFileAsync(join(cwd, 'Cargo.toml'), 'utf8')
-    tomlContent = tomlContent.replace(
-      'name = "napi-package-template"',
-      `name = "${napiName}"`,
-    )
-    await writeFileAsync(join(cwd, 'Cargo.toml'), tomlContent)
+    // the built addons in the project and the platform packages
+    const npmDir = join(cwd, 'npm')
+    const addonDirs = [
+      cwd,
+      ...(existsSync(npmDir)
+        ? readdirSync(npmDir, { withFileTypes: true })
+            .filter((entry) => entry.isDirectory())
+            .map((entry) => join(npmDir, entry.name))
+        : []),
+    ]
+    for (const dir of addonDirs) {
+      for (const file of readdirSync(dir)) {
+        if (file.startsWith(`${binaryName}.`) && file.endsWith('.node')) {
+          const newFile = `${napiName}${file.substring(binaryName.length)}`
+          debug(`Rename [${file}] to [${newFile}] in [${dir}]`)
+          renameSync(
+            toLongPath(join(dir, file)),
+            toLongPath(join(dir, newFile)),
+          )
+        }
+      }
+    }
 
-    await spawn('napi create-npm-dir -t .')
+    // rewrite the `package.json` and `README.md` of the platform packages
+    await CreateNpmDirCommand.create('package.json', cwd, cwd)
+    console.info(
+      `Renamed the platform packages in ${chalk.yellowBright(npmDir)}`,
+    )
   }
 }
@@ -0,0 +1,116 @@
+import { closeSync, openSync, readSync, statSync } from 'fs'
+import { join } from 'path'
+
+import { debugFactory } from './debug'
+
+const debug = debugFactory('secrets-scan')
+
+/**
+ * Files which should never be published: env files, private keys, credentials and debug artifacts.
+ */
+export const DEFAULT_SECRET_PATTERNS = [
+  '.env',
+  '.env.*',
+  '.npmrc',
+  '*.pem',
+  '*.key',
+  '*.p12',
+  '*.pfx',
+  'id_rsa*',
+  'id_ed25519*',
+  '*.pdb',
+  '**/*.dSYM/**',
+  '*.log',
+]
+
+// the private keys pasted in other files
+const PRIVATE_KEY_PATTERN = /-----BEGIN ([A-Z]+ )*PRIVATE KEY-----/
+
+// only the head of the files is read for the private keys
+const CONTENT_SCAN_BYTES = 64 * 1024
+
+export interface SecretsScanConfig {
+  // added to the default patterns
+  patterns?: string[]
+  // files matched by these are not reported
+  ignore?: string[]
+}
+
+export interface SecretFinding {
+  file: string
+  reason: string
+}
+
+/**
+ * Convert the glob to RegExp, `*` matches in a path segment and `**` matches across them.
+ * Patterns without `/` are matched against the file name by default.
+ */
+export function globToRegExp(glob: string, matchBase = !glob.includes('/')) {
+  const source = glob
+    .split(/(\*\*\/|\*\*|\*|\?)/)
+    .map((part) => {
+      switch (part) {
+        case '**/':
+          return '(?:.*/)?'
+        case '**':
+          return '.*'
+        case '*':
+          return '[^/]*'
+        case '?':
+          return '[^/]'
+        default:
+          return part.replace(/[.+^${}()|[\]\\]/g, '\\$&')
+      }
+    })
+    .join('')
+  return new RegExp(matchBase ? `(^|/)${source}$` : `^${source}$`)
+}
+
+export function scanFiles(
+  dir: string,
+  files: string[],
+  config: SecretsScanConfig = {},
+): SecretFinding[] {
+  const patterns = [...DEFAULT_SECRET_PATTERNS, ...(config.patterns ?? [])]
+  const matchers = patterns.map((pattern) => ({
+    pattern,
+    regexp: globToRegExp(pattern),
+  }))
+  const ignore = (config.ignore ?? []).map(globToRegExp)
+  const findings: SecretFinding[] = []
+  for (const file of files) {
+    const normalized = file.split('\\').join('/')
+    if (ignore.some((regexp) => regexp.test(normalized))) {
+      debug(`Ignore [${file}]`)
+      continue
+    }
+    const matched = matchers.find(({ regexp }) => regexp.test(normalized))
+    if (matched) {
+      findings.push({ file, reason: `matches ${matched.pattern}` })
+    } else if (!file.endsWith('.node') && containsPrivateKey(join(dir, file))) {
+      findings.push({ file, reason: 'contains a private key' })
+    }
+  }
+  return findings
+}
+
+function containsPrivateKey(path: string) {
+  try {
+    if (!statSync(path).isFile()) {
+      return false
+    }
+    const buffer = Buffer.alloc(CONTENT_SCAN_BYTES)
+    const fd = openSync(path, 'r')
+    try {
+      const bytesRead = readSync(fd, buffer, 0, CONTENT_SCAN_BYTES, 0)
+      return PRIVATE_KEY_PATTERN.test(
+        buffer.subarray(0, bytesRead).toString('utf8'),
+      )
+    } finally {
+      closeSync(fd)
+    }
+  } catch (e) {
+    debug(`Read [${path}] failed: ${e}`)
+    return false
+  }
+}
@@ -0,0 +1,124 @@
+import type { TypeDef } from './build'
+import { parseMembers } from './typetests'
+
+// `typeof` of the constants, the other types are only checked to be defined
+const PRIMITIVE_TYPES = ['number', 'string', 'boolean', 'bigint', 'symbol']
+
+export interface ExportAssertion {
+  path: string
+  // `null` for the constants of the non-primitive types
+  type: string | null
+}
+
+/**
+ * `typeof` of everything exported by the generated `.d.ts` at runtime, the methods of the classes included.
+ * The getters are skipped, they throw on the prototype.
+ */
+export function collectExportAssertions(defs: TypeDef[]): ExportAssertion[] {
+  const assertions: ExportAssertion[] = []
+  const mods = new Set<string>()
+  const classes = new Set<string>()
+  const classMembers = new Map<string, string[]>()
+  for (const def of defs) {
+    const path = def.js_mod ? `${def.js_mod}.${def.name}` : def.name
+    // `a` and `a.b` of the nested namespace `a.b`
+    const segments = def.js_mod?.split('.') ?? []
+    segments.forEach((_, index) => {
+      const mod = segments.slice(0, index + 1).join('.')
+      if (!mods.has(mod)) {
+        mods.add(mod)
+        assertions.push({ path: mod, type: 'object' })
+      }
+    })
+    switch (def.kind) {
+      case 'fn':
+        assertions.push({ path, type: 'function' })
+        break
+      case 'struct':
+        assertions.push({ path, type: 'function' })
+        classes.add(path)
+        classMembers.set(
+          path,
+          (classMembers.get(path) ?? []).concat(def.def.split('\n')),
+        )
+        break
+      case 'impl':
+        classMembers.set(
+          path,
+          (classMembers.get(path) ?? []).concat(def.def.split('\n')),
+        )
+        break
+      case 'enum':
+        assertions.push({ path, type: 'object' })
+        break
+      case 'interface':
+      case 'type':
+        break
+      default: {
+        const constant = /^export const \w+: (\w+)$/.exec(def.def)
+        assertions.push({
+          path,
+          type:
+            constant && PRIMITIVE_TYPES.includes(constant[1])
+              ? constant[1]
+              : null,
+        })
+      }
+    }
+  }
+  for (const [path, lines] of classMembers.entries()) {
+    // like the `.d.ts`, the impls without the struct are not classes, e.g. `impl Task`
+    if (!classes.has(path)) {
+      continue
+    }
+    for (const member of parseMembers(lines)) {
+      if (member.kind === 'method') {
+        assertions.push({
+          path: member.isStatic
+            ? `${path}.${member.name}`
+            : `${path}.prototype.${member.name}`,
+          type: 'function',
+        })
+      }
+    }
+  }
+  return assertions
+}
+
+/**
+ * An ES module asserting the exports of the addon required from `bindingPath`, exits with 1 on any mismatch.
+ */
+export function generateSmokeTest(defs: TypeDef[], bindingPath: string) {
+  const assertions = collectExportAssertions(defs)
+  return `/* auto-generated by NAPI-RS */
+
+import { createRequire } from 'module'
+
+const require = createRequire(import.meta.url)
+const binding = require('${bindingPath}')
+
+const expected = [
+${assertions
+  .map(({ path, type }) => `  ${JSON.stringify([path, type])},`)
+  .join('\n')}
+]
+
+const failures = expected.filter(([path, type]) => {
+  const value = path
+    .split('.')
+    .reduce((acc, key) => (acc == null ? undefined : acc[key]), binding)
+  const actual = typeof value
+  if (type === null ? actual === 'undefined' : actual !== type) {
+    console.error(\`\${path}: expected \${type || 'defined'}, got \${actual}\`)
+    return true
+  }
+  return false
+})
+
+if (failures.length) {
+  console.error(\`\${failures.length} of \${expected.length} exports are missing or mistyped\`)
+  process.exit(1)
+}
+console.info(\`\${expected.length} exports are registered\`)
+`
+}
@@ -1,6 +1,7 @@
 import { spawn as _spawn, SpawnOptionsWithoutStdio } from 'child_process'
 
 import { debugFactory } from './debug'
+import { logStream } from './json-output'
 
 const debug = debugFactory('spawn')
 
@@ -17,7 +18,7 @@ export function spawn(
     spawnStream.stdout?.on('data', (chunk) => {
       chunks.push(chunk)
     })
-    spawnStream.stdout.pipe(process.stdout)
+    spawnStream.stdout.pipe(logStream())
     spawnStream.stderr.pipe(process.stderr)
     spawnStream.on('close', (code) => {
       if (code !== 0) {
@@ -0,0 +1,286 @@
+import { execFileSync } from 'child_process'
+import { createHash } from 'crypto'
+import { existsSync, mkdirSync, statSync } from 'fs'
+import { tmpdir } from 'os'
+import { dirname, join, parse } from 'path'
+
+import chalk from 'chalk'
+
+import { debugFactory } from './debug'
+import { networkEnv } from './network'
+import { assertOnline } from './offline'
+import {
+  readFileAsync,
+  toLongPath,
+  unlinkAsync,
+  writeFileAsync,
+} from './utils'
+
+const debug = debugFactory('storage')
+
+export type StorageProvider = 's3' | 'gs' | 'az'
+
+export interface StorageLocation {
+  provider: StorageProvider
+  // bucket of S3 and GCS, container of Azure Blob
+  bucket: string
+  prefix: string
+}
+
+export interface ArtifactManifest {
+  name: string
+  version: string
+  sha256: string
+  size: number
+  // key of the content addressed blob
+  blob: string
+}
+
+const STORAGE_URL_PATTERN = /^(s3|gs|az):\/\/([^/]+)\/?(.*)$/
+
+/**
+ * Parse `s3://bucket/prefix`, `gs://bucket/prefix` or `az://container/prefix`.
+ */
+export function parseStorageUrl(url: string): StorageLocation {
+  const matched = STORAGE_URL_PATTERN.exec(url)
+  if (!matched) {
+    throw new TypeError(
+      `Invalid storage url [${url}], expected ${chalk.green(
+        's3://bucket/prefix',
+      )}, ${chalk.green('gs://bucket/prefix')} or ${chalk.green(
+        'az://container/prefix',
+      )}`,
+    )
+  }
+  const [, provider, bucket, prefix] = matched
+  return {
+    provider: provider as StorageProvider,
+    bucket,
+    prefix: prefix.replace(/\/+$/, ''),
+  }
+}
+
+/**
+ * Blobs are stored by the sha256 of the content, so the unchanged artifacts are uploaded only once.
+ */
+export function blobKey(
+  location: StorageLocation,
+  sha256: string,
+  name: string,
+) {
+  return joinKey(location.prefix, 'blobs', sha256, name)
+}
+
+/**
+ * The manifest of an artifact in a version, written by the job built it.
+ */
+export function manifestKey(
+  location: StorageLocation,
+  version: string,
+  name: string,
+) {
+  return joinKey(location.prefix, version, `${name}.json`)
+}
+
+function joinKey(...segments: string[]) {
+  return segments.filter(Boolean).join('/')
+}
+
+/**
+ * Object storage accessed through the CLI of the provider: `aws`, `gsutil` or `az`, which are installed and logged in on the CI runners.
+ */
+export class ArtifactStorage {
+  constructor(private readonly location: StorageLocation) {}
+
+  async uploadArtifact(filePath: string, version: string) {
+    const { base: name } = parse(filePath)
+    const content = await readFileAsync(filePath)
+    const sha256 = createHash('sha256').update(content).digest('hex')
+    const blob = blobKey(this.location, sha256, name)
+    if (this.exists(blob)) {
+      debug(`[${chalk.yellowBright(blob)}] is already uploaded, skip`)
+    } else {
+      this.upload(filePath, blob)
+    }
+    const manifest: ArtifactManifest = {
+      name,
+      version,
+      sha256,
+      size: statSync(toLongPath(filePath)).size,
+      blob,
+    }
+    const manifestPath = join(tmpdir(), `${name}.${Date.now()}.json`)
+    await writeFileAsync(manifestPath, JSON.stringify(manifest, null, 2))
+    try {
+      this.upload(manifestPath, manifestKey(this.location, version, name))
+    } finally {
+      await unlinkAsync(manifestPath)
+    }
+    return manifest
+  }
+
+  /**
+   * Download the artifact `name` of `version` to `dest`, `null` if it's not uploaded.
+   */
+  async downloadArtifact(
+    name: string,
+    version: string,
+    dest: string,
+  ): Promise<ArtifactManifest | null> {
+    const key = manifestKey(this.location, version, name)
+    if (!this.exists(key)) {
+      return null
+    }
+    const manifestPath = join(tmpdir(), `${name}.${Date.now()}.json`)
+    this.download(key, manifestPath)
+    const manifest: ArtifactManifest = JSON.parse(
+      await readFileAsync(manifestPath, 'utf8'),
+    )
+    await unlinkAsync(manifestPath)
+    mkdirSync(toLongPath(dirname(dest)), { recursive: true })
+    this.download(manifest.blob, dest)
+    const sha256 = createHash('sha256')
+      .update(await readFileAsync(dest))
+      .digest('hex')
+    if (sha256 !== manifest.sha256) {
+      throw new Error(
+        `Checksum of [${dest}] is ${sha256}, but ${manifest.sha256} in the manifest`,
+      )
+    }
+    return manifest
+  }
+
+  private exists(key: string) {
+    const { provider, bucket } = this.location
+    try {
+      switch (provider) {
+        case 's3':
+          this.run('aws', [
+            's3api',
+            'head-object',
+            '--bucket',
+            bucket,
+            '--key',
+            key,
+          ])
+          return true
+        case 'gs':
+          this.run('gsutil', ['-q', 'stat', `gs://${bucket}/${key}`])
+          return true
+        case 'az':
+          // missing blobs are reported by the output, the failures are thrown
+          return (
+            this.run('az', [
+              'storage',
+              'blob',
+              'exists',
+              '--container-name',
+              bucket,
+              '--name',
+              key,
+              '--query',
+              'exists',
+              '--output',
+              'tsv',
+            ]).trim() === 'true'
+          )
+      }
+    } catch (e) {
+      if (isNotFoundError(provider, e)) {
+        debug(`[${key}] doesn't exist: ${e}`)
+        return false
+      }
+      throw e
+    }
+  }
+
+  private upload(filePath: string, key: string) {
+    const { provider, bucket } = this.location
+    debug(`Upload [${chalk.yellowBright(filePath)}] to [${key}]`)
+    switch (provider) {
+      case 's3':
+        this.run('aws', ['s3', 'cp', filePath, `s3://${bucket}/${key}`])
+        break
+      case 'gs':
+        this.run('gsutil', ['cp', filePath, `gs://${bucket}/${key}`])
+        break
+      case 'az':
+        this.run('az', [
+          'storage',
+          'blob',
+          'upload',
+          '--container-name',
+          bucket,
+          '--name',
+          key,
+          '--file',
+          filePath,
+          '--overwrite',
+        ])
+    }
+  }
+
+  private download(key: string, filePath: string) {
+    const { provider, bucket } = this.location
+    debug(`Download [${key}] to [${chalk.yellowBright(filePath)}]`)
+    switch (provider) {
+      case 's3':
+        this.run('aws', ['s3', 'cp', `s3://${bucket}/${key}`, filePath])
+        break
+      case 'gs':
+        this.run('gsutil', ['cp', `gs://${bucket}/${key}`, filePath])
+        break
+      case 'az':
+        this.run('az', [
+          'storage',
+          'blob',
+          'download',
+          '--container-name',
+          bucket,
+          '--name',
+          key,
+          '--file',
+          filePath,
+          '--overwrite',
+        ])
+    }
+    if (!existsSync(filePath)) {
+      throw new Error(`Download [${key}] failed`)
+    }
+  }
+
+  // the arguments are passed without the shell, the keys and the paths are never interpreted by it
+  private run(file: string, args: string[]) {
+    const command = [file, ...args].join(' ')
+    assertOnline(chalk.green(command))
+    debug(`Run ${chalk.green(command)}`)
+    return execFileSync(file, args, {
+      stdio: 'pipe',
+      env: networkEnv(),
+    }).toString('utf8')
+  }
+}
+
+/**
+ * Whether the failure of checking the object is caused by the missing object,
+ * the other failures like the denied access or the missing CLI are not.
+ */
+export function isNotFoundError(provider: StorageProvider, error: unknown) {
+  const { status, stderr } = (error ?? {}) as {
+    status?: number | null
+    stderr?: Buffer | string
+  }
+  const output = stderr?.toString() ?? ''
+  switch (provider) {
+    case 's3':
+      // An error occurred (404) when calling the HeadObject operation: Not Found
+      return /\(404\)|Not Found/.test(output)
+    case 'gs':
+      // `gsutil -q stat` exits with 1 and prints nothing if the object doesn't exist
+      return (
+        status === 1 && (output.trim() === '' || /No URLs matched/.test(output))
+      )
+    case 'az':
+      return false
+  }
+}
@@ -0,0 +1,56 @@
+import chalk from 'chalk'
+
+/**
+ * `napi.features` of the napi config, the cargo features to the patterns of the target triples they are enabled for.
+ * `*` matches any part of the triple, the patterns starting with `!` exclude the matched triples,
+ * eg: `{ "simd": ["*", "!armv7-*"], "vendored-openssl": "*-musl*" }`.
+ */
+export type TargetFeatures = Record<string, string | string[]>
+
+function patternToRegExp(pattern: string) {
+  return new RegExp(
+    `^${pattern
+      .split('*')
+      .map((part) => part.replace(/[.+?^${}()|[\]\\]/g, '\\$&'))
+      .join('.*')}$`,
+  )
+}
+
+/**
+ * Any of the patterns matches the triple and none of the `!` ones does.
+ */
+export function matchTriplePatterns(patterns: string[], triple: string) {
+  const excluded = patterns
+    .filter((pattern) => pattern.startsWith('!'))
+    .some((pattern) => patternToRegExp(pattern.substring(1)).test(triple))
+  return (
+    !excluded &&
+    patterns
+      .filter((pattern) => !pattern.startsWith('!'))
+      .some((pattern) => patternToRegExp(pattern).test(triple))
+  )
+}
+
+/**
+ * The features enabled for the triple, in the order of the config.
+ */
+export function resolveTargetFeatures(
+  features: TargetFeatures | undefined,
+  triple: string,
+): string[] {
+  return Object.entries(features ?? {})
+    .filter(([feature, value]) => {
+      const patterns = [value].flat()
+      if (!patterns.length || patterns.some((p) => typeof p !== 'string')) {
+        throw new TypeError(
+          `The targets of the feature ${chalk.green(
+            feature,
+          )} must be a triple pattern or a list of them, received ${chalk.yellowBright(
+            JSON.stringify(value),
+          )}`,
+        )
+      }
+      return matchTriplePatterns(patterns, triple)
+    })
+    .map(([feature]) => feature)
+}
@@ -0,0 +1,106 @@
+import { existsSync, readFileSync } from 'fs'
+import { dirname, join } from 'path'
+
+import chalk from 'chalk'
+
+import { debugFactory } from './debug'
+import { toLongPath } from './utils'
+
+const debug = debugFactory('templates')
+
+export const TEMPLATES_DIR = join('.napi', 'templates')
+
+/**
+ * The generated files which could be overridden by `.napi/templates/[name]`.
+ */
+export const TEMPLATE_NAMES = [
+  'index.js',
+  'header.d.ts',
+  'npm/package.json',
+  'npm/README.md',
+  'npm/types/package.json',
+  'npm/types/README.md',
+  'workflows/CI.yml',
+] as const
+
+export type TemplateName = typeof TEMPLATE_NAMES[number]
+
+export type TemplateData = Record<string, unknown>
+
+const PLACEHOLDER = /{{\s*([\w.]+)\s*}}/g
+
+/**
+ * The closest `.napi/templates` of `cwd` and its parents, so the templates of a monorepo apply to all of its packages.
+ */
+export function findTemplatesDir(cwd: string = process.cwd()): string | null {
+  for (let dir = cwd; ; dir = dirname(dir)) {
+    const templatesDir = join(dir, TEMPLATES_DIR)
+    if (existsSync(toLongPath(templatesDir))) {
+      return templatesDir
+    }
+    if (dirname(dir) === dir) {
+      return null
+    }
+  }
+}
+
+/**
+ * Replace `{{ name }}` and `{{ name.field }}` in the template, objects are rendered as JSON.
+ */
+export function renderTemplate(
+  template: string,
+  data: TemplateData,
+  name = 'template',
+) {
+  return template.replace(PLACEHOLDER, (_, path: string) => {
+    const value = path
+      .split('.')
+      .reduce<unknown>(
+        (acc, key) =>
+          acc !== null && typeof acc === 'object' && key in acc
+            ? (acc as TemplateData)[key]
+            : undefined,
+        data,
+      )
+    if (value === undefined && !(path in data)) {
+      throw new TypeError(
+        `Unknown variable ${chalk.green(path)} in ${chalk.yellowBright(
+          name,
+        )}, available: ${Object.keys(data)
+          .map((key) => chalk.green(key))
+          .join(', ')}`,
+      )
+    }
+    if (value === undefined || value === null) {
+      return ''
+    }
+    return typeof value === 'object'
+      ? JSON.stringify(value, null, 2)
+      : String(value)
+  })
+}
+
+/**
+ * The content of the generated file, rendered from `.napi/templates/[name]` if it exists.
+ * `{{ default }}` in the template is the content generated without it.
+ */
+export function applyTemplate(
+  name: TemplateName,
+  content: string,
+  data: TemplateData,
+  cwd: string = process.cwd(),
+) {
+  const templatesDir = findTemplatesDir(cwd)
+  const templatePath = templatesDir ? join(templatesDir, name) : null
+  if (!templatePath || !existsSync(toLongPath(templatePath))) {
+    return content
+  }
+  debug(
+    `Render ${chalk.green(name)} with [${chalk.yellowBright(templatePath)}]`,
+  )
+  return renderTemplate(
+    readFileSync(toLongPath(templatePath), 'utf8'),
+    { ...data, default: content },
+    templatePath,
+  )
+}
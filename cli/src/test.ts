@@ -0,0 +1,78 @@
+import { spawn } from 'child_process'
+import { constants } from 'os'
+
+import chalk from 'chalk'
+import { Command, Option } from 'clipanion'
+
+import { getNapiConfig } from './consts'
+import { debugFactory } from './debug'
+
+const debug = debugFactory('test')
+
+export class TestCommand extends Command {
+  static usage = Command.Usage({
+    description:
+      'Build the native module for the host target, then run the JS tests',
+    details: `The test command is ${chalk.green(
+      'napi.testCommand',
+    )} by default, or the one after ${chalk.green('--')}`,
+    examples: [
+      ['Run the test command in package.json', 'napi test'],
+      ['Build in release mode and run ava', 'napi test --release -- ava'],
+    ],
+  })
+
+  static paths = [['test']]
+
+  configFileName?: string = Option.String('--config,-c', {
+    description: 'napi config path, package.json, napi.toml or .naprc',
+  })
+
+  isRelease = Option.Boolean('--release', false, {
+    description: 'Build in release mode',
+  })
+
+  buildArgs = Option.String('--build-args', '', {
+    description: `Flags passed to ${chalk.green('napi build')}`,
+  })
+
+  command = Option.Rest()
+
+  async execute() {
+    const { testCommand } = getNapiConfig(this.configFileName)
+    const command = this.command.length ? this.command.join(' ') : testCommand
+    if (!command) {
+      throw new TypeError(
+        `No test command, set ${chalk.green(
+          'napi.testCommand',
+        )} in package.json or pass it after ${chalk.green('--')}`,
+      )
+    }
+    const buildArgs = [
+      process.argv[1],
+      'build',
+      '--platform',
+      ...(this.isRelease ? ['--release'] : []),
+      ...(this.configFileName ? ['--config', this.configFileName] : []),
+      ...this.buildArgs.split(' ').filter(Boolean),
+    ]
+    const buildCode = await run(process.execPath, buildArgs)
+    if (buildCode !== 0) {
+      console.error(chalk.red('Build failed, skip the tests'))
+      return buildCode
+    }
+    return run(command, [], true)
+  }
+}
+
+function run(command: string, args: string[], shell = false) {
+  debug(`Run ${chalk.green([command, ...args].join(' '))}`)
+  return new Promise<number>((resolve, reject) => {
+    spawn(command, args, { stdio: 'inherit', env: process.env, shell })
+      .on('close', (code, signal) => {
+        // killed by a signal, exit with 128 + the number of it like the shells
+        resolve(code ?? 128 + (signal ? constants.signals[signal] : 0))
+      })
+      .on('error', reject)
+  })
+}
@@ -0,0 +1,213 @@
+import { spawnSync } from 'child_process'
+import { existsSync, readFileSync } from 'fs'
+import { homedir } from 'os'
+import { dirname, join } from 'path'
+
+import { findAndroidNdk } from './android-ndk'
+import { debugFactory } from './debug'
+import {
+  findMsvcArm64Libs,
+  findVisualStudioInstallations,
+  MSVC_ARM64_COMPONENT,
+} from './msvc'
+import type { PlatformDetail } from './parse-triple'
+
+const debug = debugFactory('toolchain-check')
+
+// `ndk;<version>` of `sdkmanager`, r26d
+export const ANDROID_NDK_VERSION = '26.3.11579264'
+
+// the cross gcc of Debian and Ubuntu for the linux gnu targets
+const LINUX_GNU_CROSS_GCC: Record<
+  string,
+  { gcc: string; aptPackage: string }
+> = {
+  'aarch64-unknown-linux-gnu': {
+    gcc: 'aarch64-linux-gnu-gcc',
+    aptPackage: 'gcc-aarch64-linux-gnu',
+  },
+  'armv7-unknown-linux-gnueabihf': {
+    gcc: 'arm-linux-gnueabihf-gcc',
+    aptPackage: 'gcc-arm-linux-gnueabihf',
+  },
+  'x86_64-unknown-linux-gnu': {
+    gcc: 'x86_64-linux-gnu-gcc',
+    aptPackage: 'gcc-x86-64-linux-gnu',
+  },
+}
+
+export interface ToolchainProblem {
+  // what is missing to link the target on the host
+  missing: string
+  // the commands installing or configuring it, in order
+  commands: string[]
+}
+
+export interface ToolchainCheckContext {
+  platform: NodeJS.Platform
+  arch: string
+  useZig: boolean
+  env: NodeJS.ProcessEnv
+  // the linker of the target is configured in the cargo config files
+  linkerConfigured: boolean
+  hasCommand: (command: string) => boolean
+}
+
+export function linkerEnvName(target: string) {
+  return `CARGO_TARGET_${target.replaceAll('-', '_').toUpperCase()}_LINKER`
+}
+
+/**
+ * The known combinations of the host and the target which fail after the whole crate graph is compiled,
+ * because the linker or the SDK of the target is missing.
+ * The target is skipped if its linker is set by the env or the cargo config.
+ */
+export function checkToolchain(
+  target: PlatformDetail,
+  context: ToolchainCheckContext,
+): ToolchainProblem[] {
+  const { platform, arch, env, useZig } = context
+  if (env[linkerEnvName(target.raw)] || context.linkerConfigured) {
+    debug(`The linker of ${target.raw} is configured, skip checking`)
+    return []
+  }
+  const problems: ToolchainProblem[] = []
+
+  if (useZig && !context.hasCommand('zig')) {
+    problems.push({
+      missing: "zig is not found in PATH, it's the linker of --zig",
+      commands:
+        platform === 'darwin'
+          ? ['brew install zig']
+          : ['snap install zig --classic --beta'],
+    })
+  }
+
+  if (target.abi === 'msvc' && platform !== 'win32') {
+    problems.push({
+      missing: `${target.raw} is linked by link.exe with the Windows SDK, which are not available on ${platform}`,
+      commands: [
+        'cargo install --locked cargo-xwin',
+        `eval "$(cargo xwin env --target ${target.raw})"`,
+      ],
+    })
+  }
+
+  if (
+    target.raw === 'aarch64-pc-windows-msvc' &&
+    platform === 'win32' &&
+    !useZig
+  ) {
+    const installations = findVisualStudioInstallations(env)
+    // without Visual Studio, rustc reports the missing `link.exe` itself
+    if (installations.length && !findMsvcArm64Libs(installations)) {
+      problems.push({
+        missing: `The ARM64 MSVC libs are not found in ${installations.join(
+          ', ',
+        )}`,
+        commands: [
+          `vs_installer.exe modify --installPath "${installations[0]}" --add ${MSVC_ARM64_COMPONENT} --quiet --norestart`,
+        ],
+      })
+    }
+  }
+
+  if (target.platform === 'android' && !useZig && !findAndroidNdk(env)) {
+    problems.push({
+      missing: `The Android NDK is not found, it provides the clang linking ${target.raw}`,
+      commands: [
+        `sdkmanager --install "ndk;${ANDROID_NDK_VERSION}"`,
+        `export ANDROID_NDK_HOME="$ANDROID_HOME/ndk/${ANDROID_NDK_VERSION}"`,
+      ],
+    })
+  }
+
+  if (target.platform === 'darwin' && platform !== 'darwin' && !useZig) {
+    problems.push({
+      missing: `${target.raw} is linked with the macOS SDK, which is not available on ${platform}, build it on macOS or pass --zig`,
+      commands: ['snap install zig --classic --beta'],
+    })
+  }
+
+  const crossGcc = LINUX_GNU_CROSS_GCC[target.raw]
+  if (
+    crossGcc &&
+    platform === 'linux' &&
+    target.arch !== arch &&
+    !useZig
+  ) {
+    const hasGcc = context.hasCommand(crossGcc.gcc)
+    problems.push({
+      missing: hasGcc
+        ? `${target.raw} is linked by the cc of the ${arch} host unless ${crossGcc.gcc} is set as the linker, or pass --zig`
+        : `${crossGcc.gcc} linking ${target.raw} is not installed, or pass --zig`,
+      commands: [
+        ...(hasGcc ? [] : [`sudo apt-get install -y ${crossGcc.aptPackage}`]),
+        `export ${linkerEnvName(target.raw)}=${crossGcc.gcc}`,
+      ],
+    })
+  }
+
+  return problems
+}
+
+export function formatToolchainProblems(
+  target: string,
+  problems: ToolchainProblem[],
+) {
+  return [
+    `Could not build ${target} on this host:`,
+    ...problems.map(
+      ({ missing, commands }) =>
+        `  - ${missing}\n${commands.map((c) => `      ${c}`).join('\n')}`,
+    ),
+    `Set ${linkerEnvName(
+      target,
+    )} if the toolchain is installed somewhere else`,
+  ].join('\n')
+}
+
+/**
+ * `[target.<target>] linker` in `.cargo/config.toml` of the cwd and its ancestors, or of `CARGO_HOME`.
+ */
+export function isLinkerConfigured(
+  target: string,
+  cwd: string,
+  env = process.env,
+): boolean {
+  const dirs: string[] = []
+  for (let dir = cwd; ; dir = dirname(dir)) {
+    dirs.push(join(dir, '.cargo'))
+    if (dirname(dir) === dir) {
+      break
+    }
+  }
+  dirs.push(env.CARGO_HOME ?? join(homedir(), '.cargo'))
+  const header = new RegExp(
+    `^\\s*\\[target\\.(["']?)${target.replace(/[.]/g, '\\.')}\\1\\]\\s*$`,
+  )
+  return dirs
+    .flatMap((dir) => [join(dir, 'config.toml'), join(dir, 'config')])
+    .filter((file) => existsSync(file))
+    .some((file) => {
+      let inTarget = false
+      for (const line of readFileSync(file, 'utf8').split(/\r?\n/)) {
+        if (/^\s*\[/.test(line)) {
+          inTarget = header.test(line)
+        } else if (inTarget && /^\s*linker\s*=/.test(line)) {
+          debug(`The linker of ${target} is set in ${file}`)
+          return true
+        }
+      }
+      return false
+    })
+}
+
+export function hasCommand(command: string) {
+  const { status } = spawnSync(
+    process.platform === 'win32' ? 'where' : 'which',
+    [command],
+    { stdio: 'ignore' },
+  )
+  return status === 0
+}
@@ -0,0 +1,270 @@
+import type { TypeDef } from './build'
+
+const TYPE_TESTS_HEADER = `/* tslint:disable */
+/* eslint-disable */
+
+/* auto-generated by NAPI-RS */
+`
+
+const TYPE_TESTS_HELPERS = `
+type Equal<X, Y> = (<T>() => T extends X ? 1 : 2) extends (<T>() => T extends Y ? 1 : 2) ? true : false
+
+interface TypeAssertion<Actual> {
+  toEqualTypeOf<Expected>(
+    ...mismatch: Equal<Actual, Expected> extends true ? [] : [never]
+  ): void
+}
+
+function expectTypeOf<Actual>(_actual?: Actual): TypeAssertion<Actual> {
+  return { toEqualTypeOf: () => {} }
+}
+`
+
+export interface Member {
+  name: string
+  isStatic: boolean
+  // `constructor`, `method` or `property`
+  kind: 'constructor' | 'method' | 'property'
+  args: string
+  type: string
+}
+
+/**
+ * Generate a file with `expectTypeOf` assertions for the exported functions, classes, interfaces and constants,
+ * so `tsc` checks the generated `.d.ts` and the signatures it contains.
+ */
+export function generateTypeTests(defs: TypeDef[], dtsImportPath: string) {
+  const topLevel = defs.filter((def) => !def.js_mod)
+  const assertions = assertDefs(topLevel, 'binding')
+  const imports = referencedNames(topLevel, assertions)
+  let code = TYPE_TESTS_HEADER + '\n'
+  if (
+    assertions.some((line) => line.includes('binding.')) ||
+    defs.some((def) => def.js_mod)
+  ) {
+    code += `import * as binding from '${dtsImportPath}'\n`
+  }
+  if (imports.length) {
+    code += `import type { ${imports.join(', ')} } from '${dtsImportPath}'\n`
+  }
+  code += TYPE_TESTS_HELPERS
+  code += '\n' + assertions.join('\n') + '\n'
+
+  const mods = [...new Set(defs.map((def) => def.js_mod).filter(Boolean))]
+  for (const mod of mods as string[]) {
+    const modDefs = defs.filter((def) => def.js_mod === mod)
+    const modAssertions = assertDefs(modDefs, `binding.${mod}`)
+    code += `\nnamespace __typetests_${mod.replace(/\./g, '_')} {\n`
+    code += referencedNames(modDefs, modAssertions)
+      .map((name) => `  import ${name} = binding.${mod}.${name}\n`)
+      .join('')
+    code += modAssertions.map((line) => `  ${line}\n`).join('')
+    code += '}\n'
+  }
+  return code
+}
+
+/**
+ * The exported types referenced by assertions, imported only if used, for `noUnusedLocals`.
+ */
+function referencedNames(defs: TypeDef[], assertions: string[]) {
+  const names = defs.reduce<string[]>((acc, def) => {
+    switch (def.kind) {
+      case 'struct':
+        return acc.concat(
+          def.original_name && def.original_name !== def.name
+            ? [def.name, def.original_name]
+            : [def.name],
+        )
+      case 'enum':
+      case 'interface':
+      case 'type':
+        return acc.concat(def.name)
+      default:
+        return acc
+    }
+  }, [])
+  if (defs.some((def) => def.def.includes('ExternalObject<'))) {
+    names.push('ExternalObject')
+  }
+  return [...new Set(names)].filter((name) =>
+    assertions.some((line) => new RegExp(`\\b${name}\\b`).test(line)),
+  )
+}
+
+function assertDefs(defs: TypeDef[], scope: string): string[] {
+  const assertions: string[] = []
+  const classMembers = new Map<string, string[]>()
+  for (const def of defs) {
+    switch (def.kind) {
+      case 'fn': {
+        const fn = parseFunction(def.def)
+        if (fn) {
+          assertions.push(
+            assertion(
+              `typeof ${scope}.${fn.name}`,
+              `(${fn.args}) => ${fn.type}`,
+            ),
+          )
+        }
+        break
+      }
+      case 'struct':
+      case 'impl':
+        classMembers.set(
+          def.name,
+          (classMembers.get(def.name) ?? []).concat(def.def.split('\n')),
+        )
+        break
+      case 'interface':
+        // generic interfaces can't be indexed without the type arguments
+        if (def.generics) {
+          break
+        }
+        for (const member of parseMembers(def.def.split('\n'))) {
+          if (member.kind === 'property') {
+            assertions.push(
+              assertion(`${def.name}['${member.name}']`, member.type),
+            )
+          }
+        }
+        break
+      default: {
+        const constant = /^export const (\w+): (.+)$/.exec(def.def)
+        if (constant) {
+          assertions.push(
+            assertion(`typeof ${scope}.${constant[1]}`, constant[2]),
+          )
+        }
+      }
+    }
+  }
+  for (const [name, lines] of classMembers.entries()) {
+    for (const member of parseMembers(lines)) {
+      const target = member.isStatic
+        ? `typeof ${name}['${member.name}']`
+        : `${name}['${member.name}']`
+      switch (member.kind) {
+        case 'constructor':
+          assertions.push(
+            assertion(
+              `ConstructorParameters<typeof ${name}>`,
+              `[${member.args}]`,
+            ),
+          )
+          break
+        case 'method':
+          assertions.push(
+            assertion(target, `(${member.args}) => ${member.type}`),
+          )
+          break
+        case 'property':
+          assertions.push(assertion(target, member.type))
+      }
+    }
+  }
+  return assertions
+}
+
+function assertion(actual: string, expected: string) {
+  return `expectTypeOf<${actual}>().toEqualTypeOf<${expected}>()`
+}
+
+function parseFunction(def: string) {
+  const matched = /^export function (\w+)\(/.exec(def)
+  if (!matched) {
+    // generic functions can't be compared without instantiating them
+    return null
+  }
+  const signature = splitSignature(def, matched[0].length - 1)
+  return signature && { name: matched[1], ...signature }
+}
+
+export function parseMembers(lines: string[]): Member[] {
+  const members: Member[] = []
+  for (const raw of lines) {
+    const line = raw.trim()
+    // js doc and the type of `this` which doesn't exist outside of the class
+    if (!line || /^(\/\*\*|\*)/.test(line) || /\bthis\b/.test(line)) {
+      continue
+    }
+    const constructor = /^constructor\(/.exec(line)
+    if (constructor) {
+      const signature = splitSignature(line, constructor[0].length - 1)
+      if (signature) {
+        members.push({
+          name: 'constructor',
+          isStatic: false,
+          kind: 'constructor',
+          args: signature.args,
+          type: '',
+        })
+      }
+      continue
+    }
+    const getter = /^(static )?get (\w+)\(/.exec(line)
+    if (getter) {
+      const signature = splitSignature(line, getter[0].length - 1)
+      if (signature) {
+        members.push({
+          name: getter[2],
+          isStatic: Boolean(getter[1]),
+          kind: 'property',
+          args: '',
+          type: signature.type,
+        })
+      }
+      continue
+    }
+    if (/^(static )?set \w+\(/.test(line)) {
+      continue
+    }
+    const method = /^(static )?(\w+)\(/.exec(line)
+    if (method) {
+      const signature = splitSignature(line, method[0].length - 1)
+      if (signature) {
+        members.push({
+          name: method[2],
+          isStatic: Boolean(method[1]),
+          kind: 'method',
+          args: signature.args,
+          type: signature.type,
+        })
+      }
+      continue
+    }
+    const field = /^(static )?(readonly )?(\w+)(\?)?: (.+)$/.exec(line)
+    if (field) {
+      members.push({
+        name: field[3],
+        isStatic: Boolean(field[1]),
+        kind: 'property',
+        args: '',
+        type: field[4] ? `${field[5]} | undefined` : field[5],
+      })
+    }
+  }
+  return members
+}
+
+/**
+ * Split `(args): ret` from the `(` at `start`, args could contain callbacks with parentheses.
+ */
+function splitSignature(def: string, start: number) {
+  let depth = 0
+  for (let i = start; i < def.length; i++) {
+    if (def[i] === '(') {
+      depth++
+    } else if (def[i] === ')') {
+      depth--
+      if (depth === 0) {
+        const rest = def.slice(i + 1).trim()
+        return {
+          args: def.slice(start + 1, i),
+          type: rest.startsWith(':') ? rest.slice(1).trim() : 'void',
+        }
+      }
+    }
+  }
+  return null
+}
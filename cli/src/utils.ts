@@ -1,11 +1,51 @@
-import { readFile, writeFile, copyFile, mkdir, unlink } from 'fs'
+import {
+  readFile,
+  writeFile,
+  copyFile,
+  mkdir,
+  unlink,
+  existsSync,
+  PathLike,
+} from 'fs'
+import { join, win32 } from 'path'
 import { promisify } from 'util'
 
-export const readFileAsync = promisify(readFile)
-export const writeFileAsync = promisify(writeFile)
-export const unlinkAsync = promisify(unlink)
-export const copyFileAsync = promisify(copyFile)
-export const mkdirAsync = promisify(mkdir)
+// `MAX_PATH` of the Win32 APIs, including the trailing NUL
+const WINDOWS_MAX_PATH = 260
+
+/**
+ * Prefix the long Windows paths with `\\?\` (`\\?\UNC\` for the UNC paths) so they are not limited to `MAX_PATH`,
+ * the files in deeply nested CI workspaces could be read and written. Other paths are returned as they are.
+ */
+export function toLongPath(path: string, platform = process.platform) {
+  if (platform !== 'win32') {
+    return path
+  }
+  const resolved = win32.resolve(path)
+  return resolved.length < WINDOWS_MAX_PATH
+    ? resolved
+    : win32.toNamespacedPath(resolved)
+}
+
+function longPath(path: PathLike) {
+  return typeof path === 'string' ? toLongPath(path) : path
+}
+
+function withLongPath<F extends (path: PathLike, ...args: any[]) => any>(
+  fn: F,
+): F {
+  return ((path: PathLike, ...args: any[]) =>
+    fn(longPath(path), ...args)) as F
+}
+
+const copyFilePromise = promisify(copyFile)
+
+export const readFileAsync = withLongPath(promisify(readFile))
+export const writeFileAsync = withLongPath(promisify(writeFile))
+export const unlinkAsync = withLongPath(promisify(unlink))
+export const copyFileAsync = ((src: PathLike, dest: PathLike, mode?: number) =>
+  copyFilePromise(longPath(src), longPath(dest), mode)) as typeof copyFilePromise
+export const mkdirAsync = withLongPath(promisify(mkdir))
 
 export function pick<O, K extends keyof O>(o: O, ...keys: K[]): Pick<O, K> {
   return keys.reduce((acc, key) => {
@@ -13,3 +53,17 @@ export function pick<O, K extends keyof O>(o: O, ...keys: K[]): Pick<O, K> {
     return acc
   }, {} as O)
 }
+
+const LICENSE_FILES = [
+  'LICENSE',
+  'LICENSE.md',
+  'LICENSE.txt',
+  'LICENCE',
+  'LICENCE.md',
+  'LICENCE.txt',
+]
+
+export function findLicenseFile(dir: string): string | null {
+  const licenseFile = LICENSE_FILES.find((file) => existsSync(join(dir, file)))
+  return licenseFile ? join(dir, licenseFile) : null
+}
@@ -18,9 +18,16 @@ export class VersionCommand extends Command {
   static paths = [['version']]
 
   static async updatePackageJson(prefix: string, configFileName?: string) {
-    const { version, platforms } = getNapiConfig(configFileName)
-    for (const platformDetail of platforms) {
-      const pkgDir = join(process.cwd(), prefix, platformDetail.platformArchABI)
+    const { version, platforms, platformTypes } = getNapiConfig(configFileName)
+    const pkgDirs = platforms.map((platformDetail) =>
+      join(process.cwd(), prefix, platformDetail.platformArchABI),
+    )
+    if (platformTypes) {
+      for (const platform of new Set(platforms.map((p) => p.platform))) {
+        pkgDirs.push(join(process.cwd(), prefix, `types-${platform}`))
+      }
+    }
+    for (const pkgDir of pkgDirs) {
       debug(
         `Update version to ${chalk.greenBright(
           version,
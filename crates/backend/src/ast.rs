@@ -6,7 +6,7 @@ pub struct NapiFn {
   pub name: Ident,
   pub js_name: String,
   pub attrs: Vec<Attribute>,
-  pub args: Vec<NapiFnArgKind>,
+  pub args: Vec<NapiFnArg>,
   pub ret: Option<syn::Type>,
   pub is_ret_result: bool,
   pub is_async: bool,
@@ -20,6 +20,34 @@ pub struct NapiFn {
   pub ts_return_type: Option<String>,
   pub skip_typescript: bool,
   pub comments: Vec<String>,
+  /// js name of the promise returning variant generated by `#[napi(also_async = "...")]`
+  pub also_async: Option<String>,
+  pub execution: FnExecution,
+  /// static method also exported as a module level function by `#[napi(module_function)]`
+  pub module_function: Option<ModuleFunction>,
+  /// module level function also defined as a static method of the class by `#[napi(static_method = Class)]`
+  pub static_method: Option<StaticMethodOf>,
+  /// `Some(object_mode)` if the returned `Stream` is converted to `stream.Readable` by `#[napi(readable_stream)]`
+  pub readable_stream: Option<bool>,
+  /// The returned `Stream` is converted to the async iterator by `#[napi(async_iterator)]`
+  pub async_iterator: bool,
+  /// The panics on the JavaScript thread are thrown as the JavaScript errors by `#[napi(catch_unwind)]`
+  pub catch_unwind: bool,
+  /// type parameters other than the closures, instantiated by `JsUnknown` in the native callback
+  pub type_params: Vec<Ident>,
+  /// `#[napi(enumerable)]` of the getters and setters
+  pub enumerable: bool,
+  /// `#[napi(configurable)]` of the getters and setters
+  pub configurable: bool,
+  /// the inputs of `#[napi(bench(args = "..."))]`, run by `napi bench`
+  pub benches: Vec<NapiBench>,
+}
+
+/// `#[napi(bench(args = "1, 2", name = "small"))]`, the JS expressions of the arguments and the name of the case
+#[derive(Debug, Clone)]
+pub struct NapiBench {
+  pub args: String,
+  pub name: String,
 }
 
 #[derive(Debug, Clone)]
@@ -29,6 +57,17 @@ pub struct CallbackArg {
   pub ret: Option<syn::Type>,
 }
 
+#[derive(Debug, Clone)]
+pub struct NapiFnArg {
+  pub kind: NapiFnArgKind,
+  /// `#[napi(ts_arg_type = "...")]` of the param, overrides the generated TypeScript type
+  pub ts_arg_type: Option<String>,
+  /// `#[napi(default = "...")]` of the param, the Rust expression used if the argument is `undefined` or `null`
+  pub default: Option<syn::Expr>,
+  /// `#[napi(variadic)]` of the last `Vec<T>` param, collects the rest of the arguments
+  pub variadic: bool,
+}
+
 #[derive(Debug, Clone)]
 pub enum NapiFnArgKind {
   PatType(Box<syn::PatType>),
@@ -44,6 +83,34 @@ pub enum FnKind {
   Setter,
 }
 
+/// Where the function body runs, declared by `#[napi(execution = "...")]`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FnExecution {
+  /// Call the function in the JavaScript thread, the default of sync fn
+  JsThread,
+  /// Call the function in the libuv thread pool and return a `Promise`
+  BlockingPool,
+  /// Call the function in the tokio runtime and return a `Promise`, the default of async fn
+  Tokio,
+}
+
+/// How a static method is exported by `#[napi(module_function)]`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ModuleFunction {
+  /// Both the static method of the class and the module level function
+  Also,
+  /// Only the module level function, `#[napi(module_function = only)]`
+  Only,
+}
+
+/// The class of `#[napi(static_method = Class)]`, recorded while parsing the class
+#[derive(Debug, Clone)]
+pub struct StaticMethodOf {
+  pub class: Ident,
+  pub js_name: String,
+  pub js_mod: Option<String>,
+}
+
 #[derive(Debug, Clone)]
 pub enum FnSelf {
   Value,
@@ -61,6 +128,29 @@ pub struct NapiStruct {
   pub kind: NapiStructKind,
   pub js_mod: Option<String>,
   pub comments: Vec<String>,
+  /// `Columnar<Self>` is transferred as one column per field, `#[napi(object, columnar)]`
+  pub columnar: bool,
+  /// the `Option` fields are required and `null` for `None`, `#[napi(object, use_nullable)]`
+  pub use_nullable: bool,
+  /// the properties not declared by the fields are rejected, `#[napi(object, deny_unknown_fields)]`
+  pub deny_unknown_fields: bool,
+  /// only `#[napi(object)]` could be generic, converted if the type parameters are convertible
+  pub generics: syn::Generics,
+  /// `#[napi(extends = Parent)]`, the parent is the first field of the `#[repr(C)]` class
+  pub parent: Option<NapiClassParent>,
+  /// `toString()` of the class by `Display`, `#[napi(to_string)]`
+  pub to_string: bool,
+  /// `toJSON()` of the class by `serde::Serialize`, `#[napi(to_json)]`
+  pub to_json: bool,
+  /// `[util.inspect.custom]()` of the class by `Debug`, `#[napi(inspect)]`
+  pub inspect: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct NapiClassParent {
+  pub name: Ident,
+  /// referenced by the `extends` of the `.d.ts`, qualified by the namespace of the parent
+  pub ts_name: String,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -80,6 +170,10 @@ pub struct NapiStructField {
   pub comments: Vec<String>,
   pub skip_typescript: bool,
   pub ts_type: Option<String>,
+  /// `#[napi(enumerable)]` of the accessors of the class fields
+  pub enumerable: bool,
+  /// `#[napi(configurable)]` of the accessors of the class fields
+  pub configurable: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -88,6 +182,8 @@ pub struct NapiImpl {
   pub js_name: String,
   pub items: Vec<NapiFn>,
   pub task_output_type: Option<Type>,
+  /// The `Item` of `impl Iterator for Class`, the instances are the iterators of JavaScript
+  pub iterator_item: Option<Type>,
   pub js_mod: Option<String>,
   pub comments: Vec<String>,
 }
@@ -97,6 +193,11 @@ pub struct NapiEnum {
   pub name: Ident,
   pub js_name: String,
   pub variants: Vec<NapiEnumVariant>,
+  pub is_string_enum: bool,
+  /// The variants carry fields, converted from and to `{ type: 'Variant', ...fields }`
+  pub is_data_enum: bool,
+  /// `#[napi(error)]`, the variants are thrown as the instances of the subclasses of the enum class
+  pub is_error: bool,
   pub js_mod: Option<String>,
   pub comments: Vec<String>,
   pub skip_typescript: bool,
@@ -105,8 +206,20 @@ pub struct NapiEnum {
 #[derive(Debug, Clone)]
 pub struct NapiEnumVariant {
   pub name: Ident,
-  pub val: i32,
+  pub val: NapiEnumValue,
   pub comments: Vec<String>,
+  /// Only the variants of the data enums have fields, `is_tuple` for `Variant(A, B)`
+  pub fields: Vec<NapiStructField>,
+  pub is_tuple: bool,
+  /// The JavaScript class of the variant of the `#[napi(error)]` enums, the `val` is the `code` of it
+  pub error_class: Option<String>,
+}
+
+/// `i32` unless the enum is `#[napi(string_enum)]`, the values of the data enums are the `type` of the variants
+#[derive(Debug, Clone)]
+pub enum NapiEnumValue {
+  Number(i32),
+  String(String),
 }
 
 #[derive(Debug, Clone)]
@@ -118,6 +231,52 @@ pub struct NapiConst {
   pub js_mod: Option<String>,
   pub comments: Vec<String>,
   pub skip_typescript: bool,
+  /// `#[napi] pub static`, cloned instead of moved out while registering the module
+  pub is_static: bool,
+  /// `#[napi(lazy)] pub static`, declared by `lazy_static!` and dereferenced while registering the module
+  pub is_lazy: bool,
+}
+
+/// `#[napi] trait`, the TypeScript interface of the JavaScript objects implementing it,
+/// which are converted into `Box<dyn Trait>`
+#[derive(Debug, Clone)]
+pub struct NapiTrait {
+  pub name: Ident,
+  pub js_name: String,
+  /// The methods without the default bodies
+  pub methods: Vec<NapiTraitMethod>,
+  pub js_mod: Option<String>,
+  pub comments: Vec<String>,
+  pub skip_typescript: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct NapiTraitMethod {
+  pub name: Ident,
+  pub js_name: String,
+  /// The signature of the trait, the args are renamed to `arg0`, `arg1`...
+  pub sig: syn::Signature,
+  /// The names of the args in the signature of the trait and the types
+  pub args: Vec<(String, Type)>,
+  /// The `T` of `Result<T>`, `None` for `Result<()>`
+  pub ret: Option<Type>,
+  pub comments: Vec<String>,
+}
+
+/// `#[napi(module_init)]`, called while registering the module on every env before the other exports
+#[derive(Debug, Clone)]
+pub struct NapiModuleInit {
+  pub name: Ident,
+  pub args: Vec<ModuleInitArg>,
+  /// `Result<()>` is returned instead of `()`
+  pub returns_result: bool,
+}
+
+/// The params of the `#[napi(module_init)]` fn, in the order of the declaration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModuleInitArg {
+  Env,
+  Exports,
 }
 
 #[derive(Debug, Clone)]
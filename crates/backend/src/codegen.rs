@@ -5,7 +5,9 @@ use crate::BindgenResult;
 mod r#const;
 mod r#enum;
 mod r#fn;
+mod module_init;
 mod r#struct;
+mod r#trait;
 
 pub trait TryToTokens {
   fn try_to_tokens(&self, tokens: &mut TokenStream) -> BindgenResult<()>;
@@ -18,17 +20,24 @@ pub trait TryToTokens {
   }
 }
 
-fn get_intermediate_ident(name: &str) -> Ident {
+/// The span of the code generated for the user code at `span`.
+/// It's located at `span`, so the errors and go-to-definition of IDEs point to the user code,
+/// but resolved at the call site like the other generated code, so the lints of it are not reported to the user.
+fn located_span(span: Span) -> Span {
+  Span::call_site().located_at(span)
+}
+
+fn get_intermediate_ident(name: &str, span: Span) -> Ident {
   let new_name = format!("__napi__{}", name);
-  Ident::new(&new_name, Span::call_site())
+  Ident::new(&new_name, located_span(span))
 }
 
-fn get_register_ident(name: &str) -> Ident {
+pub(crate) fn get_register_ident(name: &str, span: Span) -> Ident {
   let new_name = format!("__napi_register__{}", name);
-  Ident::new(&new_name, Span::call_site())
+  Ident::new(&new_name, located_span(span))
 }
 
-fn js_mod_to_token_stream(js_mod: Option<&String>) -> TokenStream {
+pub(crate) fn js_mod_to_token_stream(js_mod: Option<&String>) -> TokenStream {
   js_mod
     .map(|i| {
       let i = format!("{}\0", i);
@@ -36,3 +45,74 @@ fn js_mod_to_token_stream(js_mod: Option<&String>) -> TokenStream {
     })
     .unwrap_or_else(|| quote! { None })
 }
+
+/// The typed array holding a `#[napi(columnar)]` column of the numeric `ty`.
+/// 64-bit integers stay in arrays, typed arrays of them are BigInt arrays.
+pub(crate) fn columnar_typed_array(ty: &syn::Type) -> Option<&'static str> {
+  let ident = match ty {
+    syn::Type::Path(syn::TypePath { qself: None, path }) => path.get_ident()?,
+    _ => return None,
+  };
+  Some(match ident.to_string().as_str() {
+    "i8" => "Int8Array",
+    "u8" => "Uint8Array",
+    "i16" => "Int16Array",
+    "u16" => "Uint16Array",
+    "i32" => "Int32Array",
+    "u32" => "Uint32Array",
+    "f32" => "Float32Array",
+    "f64" => "Float64Array",
+    _ => return None,
+  })
+}
+
+/// `Some` with the generic argument of `ty` if it's `name<..>`, or `Some(None)` if it's just `name`.
+fn extract_generic_ty<'a>(ty: &'a syn::Type, name: &str) -> Option<Option<&'a syn::Type>> {
+  let segment = match ty {
+    syn::Type::Path(syn::TypePath { qself: None, path }) => path.segments.last()?,
+    _ => return None,
+  };
+  if segment.ident != name {
+    return None;
+  }
+  match &segment.arguments {
+    syn::PathArguments::None => Some(None),
+    syn::PathArguments::AngleBracketed(arguments) if arguments.args.len() == 1 => {
+      match arguments.args.first()? {
+        syn::GenericArgument::Type(elem) => Some(Some(elem)),
+        _ => None,
+      }
+    }
+    _ => None,
+  }
+}
+
+/// The `T` of `Vec<T>`, the type of the arguments collected by a `#[napi(variadic)]` param.
+pub fn extract_vec_elem_ty(ty: &syn::Type) -> Option<&syn::Type> {
+  extract_generic_ty(ty, "Vec").flatten()
+}
+
+/// `Reference<Class>` param of the method of `Class`, it's the reference of `this` instead of an argument.
+pub fn is_this_reference(ty: &syn::Type, class: Option<&syn::Ident>) -> bool {
+  let class = match class {
+    Some(class) => class,
+    None => return false,
+  };
+  match extract_generic_ty(ty, "Reference").flatten() {
+    Some(syn::Type::Path(syn::TypePath { qself: None, path })) => path
+      .segments
+      .last()
+      .map(|segment| segment.ident == *class || segment.ident == "Self")
+      .unwrap_or(false),
+    _ => false,
+  }
+}
+
+/// The `T` of `This<T>`, the type of the JavaScript `this` of a fn, `JsObject` by default.
+pub fn extract_this_ty(ty: &syn::Type) -> Option<syn::Type> {
+  extract_generic_ty(ty, "This").map(|this_ty| {
+    this_ty
+      .cloned()
+      .unwrap_or_else(|| syn::parse_quote!(napi::JsObject))
+  })
+}
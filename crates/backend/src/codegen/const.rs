@@ -22,19 +22,26 @@ impl NapiConst {
   fn gen_module_register(&self) -> TokenStream {
     let name_str = self.name.to_string();
     let name_ident = self.name.clone();
-    let js_name_lit = Literal::string(&format!("{}\0", self.name));
-    let register_name = get_register_ident(&name_str);
+    let js_name_lit = Literal::string(&format!("{}\0", self.js_name));
+    let register_name = get_register_ident(&name_str, self.name.span());
     let type_name = &self.type_name;
     let cb_name = Ident::new(
       &format!("__register__const__{}_callback__", register_name),
       self.name.span(),
     );
     let js_mod_ident = js_mod_to_token_stream(self.js_mod.as_ref());
+    let value = if self.is_lazy {
+      quote! { std::clone::Clone::clone(std::ops::Deref::deref(&#name_ident)) }
+    } else if self.is_static {
+      quote! { std::clone::Clone::clone(&#name_ident) }
+    } else {
+      quote! { #name_ident }
+    };
     quote! {
       #[allow(non_snake_case)]
       #[allow(clippy::all)]
       unsafe fn #cb_name(env: napi::sys::napi_env) -> napi::Result<napi::sys::napi_value> {
-        <#type_name as napi::bindgen_prelude::ToNapiValue>::to_napi_value(env, #name_ident)
+        <#type_name as napi::bindgen_prelude::ToNapiValue>::to_napi_value(env, #value)
       }
       #[allow(non_snake_case)]
       #[allow(clippy::all)]
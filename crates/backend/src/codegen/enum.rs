@@ -1,13 +1,26 @@
 use proc_macro2::{Ident, Literal, Span, TokenStream};
 use quote::ToTokens;
+use syn::spanned::Spanned;
 
 use crate::{
-  codegen::{get_register_ident, js_mod_to_token_stream},
-  BindgenResult, NapiEnum, TryToTokens,
+  codegen::{get_register_ident, js_mod_to_token_stream, located_span},
+  BindgenResult, NapiEnum, NapiEnumValue, TryToTokens,
 };
 
 impl TryToTokens for NapiEnum {
   fn try_to_tokens(&self, tokens: &mut TokenStream) -> BindgenResult<()> {
+    if self.is_error {
+      self.gen_error_class_impl().to_tokens(tokens);
+      self.gen_error_classes_register().to_tokens(tokens);
+      return Ok(());
+    }
+
+    // the data enums are only the types in JavaScript, nothing is exported
+    if self.is_data_enum {
+      self.gen_data_enum_napi_value_impl().to_tokens(tokens);
+      return Ok(());
+    }
+
     let register = self.gen_module_register();
     let napi_value_conversion = self.gen_napi_value_map_impl();
 
@@ -29,13 +42,37 @@ impl NapiEnum {
     let mut to_napi_branches = vec![];
 
     self.variants.iter().for_each(|v| {
-      let val = Literal::i32_unsuffixed(v.val);
+      let val = v.val.to_literal();
       let v_name = &v.name;
 
       from_napi_branches.push(quote! { #val => Ok(#name::#v_name) });
       to_napi_branches.push(quote! { #name::#v_name => #val });
     });
 
+    let (val_ty, value_type, from_val) = if self.is_string_enum {
+      (
+        quote! { &'static str },
+        quote! { String },
+        quote! { String::from_napi_value(env, napi_val) },
+      )
+    } else {
+      (
+        quote! { i32 },
+        quote! { Number },
+        quote! { i32::from_napi_value(env, napi_val) },
+      )
+    };
+    let match_val = if self.is_string_enum {
+      quote! { val.as_str() }
+    } else {
+      quote! { val }
+    };
+    let type_value_type = if self.is_string_enum {
+      quote! { String }
+    } else {
+      quote! { Object }
+    };
+
     quote! {
       impl napi::bindgen_prelude::TypeName for #name {
         fn type_name() -> &'static str {
@@ -43,7 +80,7 @@ impl NapiEnum {
         }
 
         fn value_type() -> napi::ValueType {
-          napi::ValueType::Object
+          napi::ValueType::#type_value_type
         }
       }
 
@@ -52,7 +89,7 @@ impl NapiEnum {
           env: napi::bindgen_prelude::sys::napi_env,
           napi_val: napi::bindgen_prelude::sys::napi_value
         ) -> napi::bindgen_prelude::Result<napi::sys::napi_value> {
-          napi::bindgen_prelude::assert_type_of!(env, napi_val, napi::bindgen_prelude::ValueType::Number)?;
+          napi::bindgen_prelude::assert_type_of!(env, napi_val, napi::bindgen_prelude::ValueType::#value_type)?;
           Ok(std::ptr::null_mut())
         }
       }
@@ -62,7 +99,7 @@ impl NapiEnum {
           env: napi::bindgen_prelude::sys::napi_env,
           napi_val: napi::bindgen_prelude::sys::napi_value
         ) -> napi::bindgen_prelude::Result<Self> {
-          let val = i32::from_napi_value(env, napi_val).map_err(|e| {
+          let val = #from_val.map_err(|e| {
             napi::bindgen_prelude::error!(
               e.status,
               "Failed to convert napi value into enum `{}`. {}",
@@ -71,7 +108,7 @@ impl NapiEnum {
             )
           })?;
 
-          match val {
+          match #match_val {
             #(#from_napi_branches,)*
             _ => {
               Err(napi::bindgen_prelude::error!(
@@ -90,32 +127,298 @@ impl NapiEnum {
           env: napi::bindgen_prelude::sys::napi_env,
           val: Self
         ) -> napi::bindgen_prelude::Result<napi::bindgen_prelude::sys::napi_value> {
-          let val = match val {
+          let val: #val_ty = match val {
             #(#to_napi_branches,)*
           };
 
-          i32::to_napi_value(env, val)
+          <#val_ty as napi::bindgen_prelude::ToNapiValue>::to_napi_value(env, val)
         }
       }
     }
   }
 
+  fn gen_data_enum_napi_value_impl(&self) -> TokenStream {
+    let name = &self.name;
+    let name_str = self.name.to_string();
+    let mut from_napi_branches = vec![];
+    let mut to_napi_branches = vec![];
+
+    for v in self.variants.iter() {
+      let tag = v.val.to_literal();
+      let v_name = &v.name;
+      let v_name_str = v.name.to_string();
+      let mut members = vec![];
+      let mut bindings = vec![];
+      let mut field_setters = vec![];
+      let mut field_getters = vec![];
+
+      for (i, field) in v.fields.iter().enumerate() {
+        let field_js_name = &field.js_name;
+        let ty = &field.ty;
+        let span = located_span(ty.span());
+        // prefixed, so the fields never shadow the locals of the generated code, e.g. `obj` or `env`
+        let binding = match &field.name {
+          syn::Member::Named(ident) => format_ident!("__napi_field_{}", ident),
+          syn::Member::Unnamed(_) => format_ident!("__napi_field_{}", i),
+        };
+        if is_optional(ty) {
+          field_setters.push(quote_spanned! { span=>
+            if #binding.is_some() {
+              obj.set(#field_js_name, #binding)?;
+            }
+          });
+          field_getters
+            .push(quote_spanned! { span=> let #binding: #ty = obj.get(#field_js_name)?; });
+        } else {
+          field_setters.push(quote_spanned! { span=> obj.set(#field_js_name, #binding)?; });
+          field_getters.push(quote_spanned! { span=>
+            let #binding: #ty = obj.get(#field_js_name)?.ok_or_else(|| napi::bindgen_prelude::Error::new(
+              napi::bindgen_prelude::Status::InvalidArg,
+              format!("Missing field `{}` of the variant `{}`", #field_js_name, #v_name_str),
+            ))?;
+          });
+        }
+        members.push(&field.name);
+        bindings.push(binding);
+      }
+
+      let pattern = if v.fields.is_empty() {
+        quote! { #name::#v_name }
+      } else if v.is_tuple {
+        quote! { #name::#v_name(#(#bindings),*) }
+      } else {
+        quote! { #name::#v_name { #(#members: #bindings),* } }
+      };
+
+      to_napi_branches.push(quote! {
+        #pattern => {
+          obj.set("type", #tag)?;
+          #(#field_setters)*
+        }
+      });
+      from_napi_branches.push(quote! {
+        #tag => {
+          #(#field_getters)*
+          Ok(#pattern)
+        }
+      });
+    }
+
+    quote! {
+      impl napi::bindgen_prelude::TypeName for #name {
+        fn type_name() -> &'static str {
+          #name_str
+        }
+
+        fn value_type() -> napi::ValueType {
+          napi::ValueType::Object
+        }
+      }
+
+      impl napi::bindgen_prelude::ValidateNapiValue for #name {
+        unsafe fn validate(
+          env: napi::bindgen_prelude::sys::napi_env,
+          napi_val: napi::bindgen_prelude::sys::napi_value
+        ) -> napi::bindgen_prelude::Result<napi::sys::napi_value> {
+          napi::bindgen_prelude::assert_type_of!(env, napi_val, napi::bindgen_prelude::ValueType::Object)?;
+          Ok(std::ptr::null_mut())
+        }
+      }
+
+      impl napi::bindgen_prelude::FromNapiValue for #name {
+        unsafe fn from_napi_value(
+          env: napi::bindgen_prelude::sys::napi_env,
+          napi_val: napi::bindgen_prelude::sys::napi_value
+        ) -> napi::bindgen_prelude::Result<Self> {
+          napi::bindgen_prelude::debug_validate_object(env, napi_val)?;
+          let obj = napi::bindgen_prelude::Object::from_napi_value(env, napi_val)?;
+          let val: String = obj.get("type")?.ok_or_else(|| napi::bindgen_prelude::Error::new(
+            napi::bindgen_prelude::Status::InvalidArg,
+            format!("Missing field `type` of enum `{}`", #name_str),
+          ))?;
+
+          match val.as_str() {
+            #(#from_napi_branches,)*
+            _ => {
+              Err(napi::bindgen_prelude::error!(
+                napi::bindgen_prelude::Status::InvalidArg,
+                "value `{}` does not match any variant of enum `{}`",
+                val,
+                #name_str
+              ))
+            }
+          }
+        }
+      }
+
+      impl napi::bindgen_prelude::ToNapiValue for #name {
+        unsafe fn to_napi_value(
+          env: napi::bindgen_prelude::sys::napi_env,
+          val: Self
+        ) -> napi::bindgen_prelude::Result<napi::bindgen_prelude::sys::napi_value> {
+          let env_wrapper = napi::bindgen_prelude::Env::from(env);
+          let mut obj = env_wrapper.create_object()?;
+
+          match val {
+            #(#to_napi_branches,)*
+          }
+
+          napi::bindgen_prelude::Object::to_napi_value(env, obj)
+        }
+      }
+    }
+  }
+
+  fn gen_error_class_impl(&self) -> TokenStream {
+    let name = &self.name;
+    let base_class = &self.js_name;
+    let mut class_branches = vec![];
+    let mut code_branches = vec![];
+    let mut property_branches = vec![];
+
+    for v in self.variants.iter() {
+      let v_name = &v.name;
+      let class = v.error_class.as_deref().unwrap_or_default();
+      let code = v.val.to_literal();
+      let mut members = vec![];
+      let mut bindings = vec![];
+      let mut property_setters = vec![];
+      for (i, field) in v.fields.iter().enumerate() {
+        let member = &field.name;
+        let binding = match member {
+          syn::Member::Named(ident) => format_ident!("__napi_field_{}", ident),
+          syn::Member::Unnamed(_) => format_ident!("__napi_field_{}", i),
+        };
+        let field_js_name = &field.js_name;
+        let span = located_span(field.ty.span());
+        if is_optional(&field.ty) {
+          property_setters.push(quote_spanned! { span=>
+            if let Some(value) = #binding {
+              error.set(#field_js_name, value.clone())?;
+            }
+          });
+        } else {
+          property_setters
+            .push(quote_spanned! { span=> error.set(#field_js_name, #binding.clone())?; });
+        }
+        members.push(member);
+        bindings.push(binding);
+      }
+      class_branches.push(quote! { #name::#v_name { .. } => #class });
+      code_branches.push(quote! { #name::#v_name { .. } => #code });
+      property_branches.push(quote! {
+        #name::#v_name { #(#members: #bindings,)* .. } => {
+          #(#property_setters)*
+        }
+      });
+    }
+
+    quote! {
+      impl napi::bindgen_prelude::ErrorClass for #name {
+        fn class_name(&self) -> &'static str {
+          match self {
+            #(#class_branches,)*
+          }
+        }
+
+        fn base_class_name(&self) -> &'static str {
+          #base_class
+        }
+
+        fn code(&self) -> &'static str {
+          match self {
+            #(#code_branches,)*
+          }
+        }
+
+        unsafe fn set_properties(
+          &self,
+          env: napi::bindgen_prelude::sys::napi_env,
+          error: napi::bindgen_prelude::sys::napi_value
+        ) -> napi::bindgen_prelude::Result<()> {
+          #[allow(unused_mut, unused_variables)]
+          let mut error = <napi::bindgen_prelude::Object as napi::bindgen_prelude::FromNapiValue>::from_napi_value(env, error)?;
+          match self {
+            #(#property_branches,)*
+          }
+          Ok(())
+        }
+      }
+
+      impl From<#name> for napi::bindgen_prelude::Error {
+        fn from(err: #name) -> Self {
+          napi::bindgen_prelude::Error::from_class(err)
+        }
+      }
+    }
+  }
+
+  /// The enum class and the classes of the variants are exported, created on the env by the first of them
+  fn gen_error_classes_register(&self) -> TokenStream {
+    let name_str = self.name.to_string();
+    let js_mod_ident = js_mod_to_token_stream(self.js_mod.as_ref());
+    let classes =
+      std::iter::once((self.js_name.as_str(), None)).chain(self.variants.iter().map(|v| {
+        (
+          v.error_class.as_deref().unwrap_or_default(),
+          Some(&self.js_name),
+        )
+      }));
+
+    classes
+      .map(|(class, base)| {
+        let class_name_lit = Literal::string(&format!("{}\0", class));
+        let base = match base {
+          Some(base) => quote! { Some(#base) },
+          None => quote! { None },
+        };
+        let callback_name = Ident::new(
+          &format!("__register__error_class__{}_{}_callback__", name_str, class),
+          Span::call_site(),
+        );
+        let register_name = get_register_ident(
+          &format!("{}_error_class_{}", name_str, class),
+          self.name.span(),
+        );
+        quote! {
+          #[allow(non_snake_case)]
+          #[allow(clippy::all)]
+          unsafe fn #callback_name(env: napi::bindgen_prelude::sys::napi_env) -> napi::bindgen_prelude::Result<napi::bindgen_prelude::sys::napi_value> {
+            napi::bindgen_prelude::get_error_class(env, #class, #base)
+          }
+          #[allow(non_snake_case)]
+          #[allow(clippy::all)]
+          #[cfg(all(not(test), not(feature = "noop")))]
+          #[napi::bindgen_prelude::ctor]
+          fn #register_name() {
+            napi::bindgen_prelude::register_module_export(#js_mod_ident, #class_name_lit, #callback_name);
+          }
+        }
+      })
+      .collect()
+  }
+
   fn gen_module_register(&self) -> TokenStream {
     let name_str = self.name.to_string();
     let js_name_lit = Literal::string(&format!("{}\0", &self.js_name));
-    let register_name = get_register_ident(&name_str);
+    let register_name = get_register_ident(&name_str, self.name.span());
 
     let mut define_properties = vec![];
 
     for variant in self.variants.iter() {
       let name_lit = Literal::string(&format!("{}\0", variant.name));
-      let val_lit = Literal::i32_unsuffixed(variant.val);
+      let val_lit = variant.val.to_literal();
+      let val_ty = if self.is_string_enum {
+        quote! { &str }
+      } else {
+        quote! { i32 }
+      };
 
       define_properties.push(quote! {
         {
           let name = std::ffi::CStr::from_bytes_with_nul_unchecked(#name_lit.as_bytes());
           napi::bindgen_prelude::check_status!(
-            napi::bindgen_prelude::sys::napi_set_named_property(env, obj_ptr, name.as_ptr(), i32::to_napi_value(env, #val_lit)?),
+            napi::bindgen_prelude::sys::napi_set_named_property(env, obj_ptr, name.as_ptr(), <#val_ty as napi::bindgen_prelude::ToNapiValue>::to_napi_value(env, #val_lit)?),
             "Failed to defined enum `{}`",
             #js_name_lit
           )?;
@@ -158,3 +461,21 @@ impl NapiEnum {
     }
   }
 }
+
+impl NapiEnumValue {
+  fn to_literal(&self) -> Literal {
+    match self {
+      NapiEnumValue::Number(val) => Literal::i32_unsuffixed(*val),
+      NapiEnumValue::String(val) => Literal::string(val),
+    }
+  }
+}
+
+fn is_optional(ty: &syn::Type) -> bool {
+  match ty {
+    syn::Type::Path(syn::TypePath { path, .. }) => {
+      matches!(path.segments.last(), Some(last_path) if last_path.ident == "Option")
+    }
+    _ => false,
+  }
+}
@@ -1,15 +1,20 @@
+use convert_case::{Case, Casing};
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::ToTokens;
+use syn::spanned::Spanned;
 
 use crate::{
-  codegen::{get_intermediate_ident, get_register_ident, js_mod_to_token_stream},
-  BindgenResult, CallbackArg, FnKind, FnSelf, NapiFn, NapiFnArgKind, TryToTokens,
+  codegen::{
+    extract_this_ty, extract_vec_elem_ty, get_intermediate_ident, get_register_ident,
+    is_this_reference, js_mod_to_token_stream, located_span,
+  },
+  BindgenResult, CallbackArg, FnExecution, FnKind, FnSelf, NapiFn, NapiFnArgKind, TryToTokens,
 };
 
 impl TryToTokens for NapiFn {
   fn try_to_tokens(&self, tokens: &mut TokenStream) -> BindgenResult<()> {
     let name_str = self.name.to_string();
-    let intermediate_ident = get_intermediate_ident(&name_str);
+    let intermediate_ident = get_intermediate_ident(&name_str, self.name.span());
     let args_len = self.args.len();
 
     let (arg_conversions, arg_names) = self.gen_arg_conversions();
@@ -17,9 +22,15 @@ impl TryToTokens for NapiFn {
     let receiver_ret_name = Ident::new("_ret", Span::call_site());
     let ret = self.gen_fn_return(&receiver_ret_name);
     let register = self.gen_fn_register();
+    let also_async = self.gen_also_async();
+    let static_method = self.gen_static_method();
+    let async_runtime_check = self.gen_async_runtime_check();
+    let type_params = self.gen_type_param_aliases();
     let attrs = &self.attrs;
 
-    let native_call = if !self.is_async {
+    let native_call = if !self.is_async && self.execution != FnExecution::JsThread {
+      self.gen_off_thread_call(self.execution, &arg_names)
+    } else if !self.is_async {
       quote! {
         let #receiver_ret_name = {
           #receiver(#(#arg_names),*)
@@ -32,8 +43,28 @@ impl TryToTokens for NapiFn {
       } else {
         quote! { Ok(#receiver(#(#arg_names),*).await) }
       };
+      // the instance of `&self` is referenced until the future is done
+      let (this_ref, keep_this_ref) = match (&self.parent, &self.fn_self) {
+        (Some(parent), Some(_)) => (
+          quote! {
+            let this_ref = napi::bindgen_prelude::Reference::<#parent>::from_value_ptr(this_ptr as *mut std::ffi::c_void, env)?;
+          },
+          quote! { let _this_ref = this_ref; },
+        ),
+        _ => (quote! {}, quote! {}),
+      };
+      let future = quote! { async move { #keep_this_ref #call } };
+      let (abort_handle, future) = match self.gen_abort_handle(&arg_names) {
+        Some(handle) => (
+          quote! { let abort_handle = #handle; },
+          quote! { napi::bindgen_prelude::abortable(abort_handle, #future) },
+        ),
+        None => (quote! {}, future),
+      };
       quote! {
-        napi::bindgen_prelude::execute_tokio_future(env, async move { #call }, |env, #receiver_ret_name| {
+        #this_ref
+        #abort_handle
+        napi::bindgen_prelude::execute_tokio_future(env, #future, |env, #receiver_ret_name| {
           #ret
         })
       }
@@ -45,25 +76,27 @@ impl TryToTokens for NapiFn {
       && self.kind != FnKind::Factory
     {
       quote! { #native_call }
-    } else if self.kind == FnKind::Constructor {
+    } else {
       quote! {
-        // constructor function is called from class `factory`
-        // so we should skip the original `constructor` logic
-        if napi::bindgen_prelude::___CALL_FROM_FACTORY.with(|inner| inner.load(std::sync::atomic::Ordering::Relaxed)) {
-          return std::ptr::null_mut();
-        }
         napi::bindgen_prelude::CallbackInfo::<#args_len>::new(env, cb, None).and_then(|mut cb| {
           #(#arg_conversions)*
           #native_call
         })
       }
-    } else {
+    };
+    let function_call = self.gen_catch_panic(function_call);
+
+    let function_call = if self.kind == FnKind::Constructor {
       quote! {
-        napi::bindgen_prelude::CallbackInfo::<#args_len>::new(env, cb, None).and_then(|mut cb| {
-          #(#arg_conversions)*
-          #native_call
-        })
+        // constructor function is called from class `factory`
+        // so we should skip the original `constructor` logic
+        if napi::bindgen_prelude::___CALL_FROM_FACTORY.with(|inner| inner.load(std::sync::atomic::Ordering::Relaxed)) {
+          return std::ptr::null_mut();
+        }
+        #function_call
       }
+    } else {
+      function_call
     };
 
     (quote! {
@@ -75,6 +108,7 @@ impl TryToTokens for NapiFn {
         env: napi::bindgen_prelude::sys::napi_env,
         cb: napi::bindgen_prelude::sys::napi_callback_info
       ) -> napi::bindgen_prelude::sys::napi_value {
+        #type_params
         unsafe {
           #function_call.unwrap_or_else(|e| {
             napi::bindgen_prelude::JsError::from(e).throw_into(env);
@@ -83,7 +117,13 @@ impl TryToTokens for NapiFn {
         }
       }
 
+      #async_runtime_check
+
       #register
+
+      #also_async
+
+      #static_method
     })
     .to_tokens(tokens);
 
@@ -120,37 +160,31 @@ impl NapiFn {
       let i = i - skipped_arg_count;
       let ident = Ident::new(&format!("arg{}", i), Span::call_site());
 
-      match arg {
+      match &arg.kind {
         NapiFnArgKind::PatType(path) => {
           if &path.ty.to_token_stream().to_string() == "Env" {
             args.push(quote! { napi::bindgen_prelude::Env::from(env) });
             skipped_arg_count += 1;
+          } else if extract_this_ty(&path.ty).is_some() {
+            let ty = &*path.ty;
+            let span = located_span(ty.span());
+            args.push(quote_spanned! { span=>
+              <#ty as napi::bindgen_prelude::FromNapiValue>::from_napi_value(env, cb.this())?
+            });
+            skipped_arg_count += 1;
           } else {
-            if self.parent.is_some() {
-              if let syn::Type::Path(path) = path.ty.as_ref() {
-                if let Some(p) = path.path.segments.first() {
-                  if p.ident == "Reference" {
-                    if let syn::PathArguments::AngleBracketed(
-                      syn::AngleBracketedGenericArguments { args: angle_bracketed_args, .. },
-                    ) = &p.arguments
-                    {
-                      if let Some(syn::GenericArgument::Type(syn::Type::Path(path))) = angle_bracketed_args.first() {
-                          if let Some(p) = path.path.segments.first() {
-                            if p.ident == *self.parent.as_ref().unwrap() {
-                              args.push(
-                                quote! { napi::bindgen_prelude::Reference::from_value_ptr(this_ptr as *mut std::ffi::c_void, env)? },
-                              );
-                              skipped_arg_count += 1;
-                              return;
-                            }
-                          }
-                      }
-                    }
-                  }
-                }
-              }
+            if self.fn_self.is_some() && is_this_reference(&path.ty, self.parent.as_ref()) {
+              args.push(
+                quote! { napi::bindgen_prelude::Reference::from_value_ptr(this_ptr as *mut std::ffi::c_void, env)? },
+              );
+              skipped_arg_count += 1;
+              return;
+            }
+            if arg.variadic {
+              arg_conversions.push(self.gen_rest_arg_conversion(&ident, i, path));
+            } else {
+              arg_conversions.push(self.gen_ty_arg_conversion(&ident, i, path, arg.default.as_ref()));
             }
-            arg_conversions.push(self.gen_ty_arg_conversion(&ident, i, path));
             args.push(quote! { #ident });
           }
         }
@@ -164,31 +198,51 @@ impl NapiFn {
     (arg_conversions, args)
   }
 
+  /// The `AbortHandle` of the first `AbortSignal` or `Option<AbortSignal>` argument of the `async fn`,
+  /// the future is dropped once it's aborted
+  fn gen_abort_handle(&self, arg_names: &[TokenStream]) -> Option<TokenStream> {
+    self
+      .args
+      .iter()
+      .zip(arg_names)
+      .find_map(|(arg, name)| match &arg.kind {
+        NapiFnArgKind::PatType(path) => match abort_signal_ty(&path.ty) {
+          Some(false) => Some(quote! { Some(#name.handle()) }),
+          Some(true) => Some(quote! { #name.as_ref().map(|signal| signal.handle()) }),
+          None => None,
+        },
+        _ => None,
+      })
+  }
+
   fn gen_ty_arg_conversion(
     &self,
     arg_name: &Ident,
     index: usize,
     path: &syn::PatType,
+    default: Option<&syn::Expr>,
   ) -> TokenStream {
     let ty = &*path.ty;
+    // the errors of the types not convertible point to the types of the arguments
+    let span = located_span(ty.span());
     match ty {
       syn::Type::Reference(syn::TypeReference {
         mutability: Some(_),
         elem,
         ..
       }) => {
-        quote! {
+        quote_spanned! { span=>
           let #arg_name = <#elem as napi::bindgen_prelude::FromNapiMutRef>::from_napi_mut_ref(env, cb.get_arg(#index))?;
         }
       }
       syn::Type::Reference(syn::TypeReference { elem, .. }) => {
-        quote! {
+        quote_spanned! { span=>
           let #arg_name = <#elem as napi::bindgen_prelude::FromNapiRef>::from_napi_ref(env, cb.get_arg(#index))?;
         }
       }
       _ => {
         let type_check = if self.strict {
-          quote! {
+          quote_spanned! { span=>
             let maybe_promise = <#ty as napi::bindgen_prelude::ValidateNapiValue>::validate(env, cb.get_arg(#index))?;
             if !maybe_promise.is_null() {
               return Ok(maybe_promise);
@@ -198,16 +252,88 @@ impl NapiFn {
           quote! {}
         };
 
-        quote! {
-          let #arg_name = {
+        // the same name as the `.d.ts`, for the path in the errors
+        let js_arg_name = match &*path.pat {
+          syn::Pat::Ident(pat) => pat.ident.to_string().to_case(Case::Camel),
+          _ => format!("arguments[{}]", index),
+        };
+
+        // every `Memoized` argument has its own cache
+        let conversion = if is_memoized(ty) {
+          quote_spanned! { span=>
+            {
+              static MEMO_CACHE: napi::bindgen_prelude::MemoCache = napi::bindgen_prelude::MemoCache::new();
+              <#ty>::from_napi_value_cached(env, cb.get_arg(#index), &MEMO_CACHE)
+            }
+          }
+        } else {
+          quote_spanned! { span=>
+            <#ty as napi::bindgen_prelude::FromNapiValue>::from_napi_value(env, cb.get_arg(#index))
+          }
+        };
+
+        let conversion = quote_spanned! { span=>
+          {
             #type_check
-            <#ty as napi::bindgen_prelude::FromNapiValue>::from_napi_value(env, cb.get_arg(#index))?
-          };
+            napi::bindgen_prelude::with_arg_path(
+              #conversion,
+              #js_arg_name,
+            )?
+          }
+        };
+
+        match default {
+          // the missing arguments are `undefined`
+          Some(default) => quote! {
+            let #arg_name = match napi::bindgen_prelude::type_of!(env, cb.get_arg(#index))? {
+              napi::bindgen_prelude::ValueType::Undefined | napi::bindgen_prelude::ValueType::Null => #default,
+              _ => #conversion,
+            };
+          },
+          None => quote! {
+            let #arg_name = #conversion;
+          },
         }
       }
     }
   }
 
+  fn gen_rest_arg_conversion(
+    &self,
+    arg_name: &Ident,
+    index: usize,
+    path: &syn::PatType,
+  ) -> TokenStream {
+    let ty = &*path.ty;
+    let elem = extract_vec_elem_ty(ty).expect("#[napi(variadic)] is checked to be a `Vec<T>`");
+    let span = located_span(elem.span());
+    let conversion = quote_spanned! { span=>
+      <#elem as napi::bindgen_prelude::FromNapiValue>::from_napi_value(env, arg)
+    };
+    let conversion = if self.strict {
+      quote_spanned! { span=>
+        <#elem as napi::bindgen_prelude::ValidateNapiValue>::validate(env, arg).and_then(|_| #conversion)
+      }
+    } else {
+      conversion
+    };
+    let js_arg_name = match &*path.pat {
+      syn::Pat::Ident(pat) => pat.ident.to_string().to_case(Case::Camel),
+      _ => format!("arguments[{}]", index),
+    };
+
+    quote_spanned! { span=>
+      let #arg_name = napi::bindgen_prelude::with_arg_path(
+        cb.get_rest_args(#index)?
+          .into_iter()
+          .enumerate()
+          .map(|(i, arg)| napi::bindgen_prelude::with_value_path(#conversion, || format!("[{}]", i)))
+          .collect::<napi::bindgen_prelude::Result<Vec<#elem>>>(),
+        #js_arg_name,
+      )?;
+    }
+  }
+
   fn gen_cb_arg_conversion(&self, arg_name: &Ident, index: usize, cb: &CallbackArg) -> TokenStream {
     let mut inputs = vec![];
     let mut arg_conversions = vec![];
@@ -257,6 +383,17 @@ impl NapiFn {
     }
   }
 
+  /// The arguments and the return value of `T` are converted as `JsUnknown`, the generic fn is inferred by them
+  fn gen_type_param_aliases(&self) -> TokenStream {
+    let type_params = &self.type_params;
+    quote! {
+      #(
+        #[allow(non_camel_case_types, dead_code)]
+        type #type_params = napi::JsUnknown;
+      )*
+    }
+  }
+
   fn gen_fn_receiver(&self) -> TokenStream {
     let name = &self.name;
 
@@ -277,6 +414,7 @@ impl NapiFn {
     let js_name = &self.js_name;
 
     if let Some(ty) = &self.ret {
+      let span = located_span(ty.span());
       let ty_string = ty.into_token_stream().to_string();
       let is_return_self = ty_string == "& Self" || ty_string == "&mut Self";
       if self.kind == FnKind::Constructor {
@@ -286,20 +424,42 @@ impl NapiFn {
           quote! { cb.construct(#js_name, #ret) }
         }
       } else if self.kind == FnKind::Factory {
-        if self.is_ret_result {
+        if self.is_async {
+          // resolved after the `CallbackInfo` is gone, the instance is created by the registered class
+          let parent = &self.parent;
+          quote! { <#parent as napi::bindgen_prelude::ToNapiValue>::to_napi_value(env, #ret) }
+        } else if self.is_ret_result {
           quote! { cb.factory(#js_name, #ret?) }
         } else {
           quote! { cb.factory(#js_name, #ret) }
         }
+      } else if self.readable_stream.is_some() || self.async_iterator {
+        if self.is_ret_result {
+          let stream = self.gen_wrap_stream(quote! { stream });
+          quote_spanned! { span=>
+            match #ret {
+              Ok(stream) => napi::bindgen_prelude::ToNapiValue::to_napi_value(env, #stream),
+              Err(err) => {
+                napi::bindgen_prelude::JsError::from(err).throw_into(env);
+                Ok(std::ptr::null_mut())
+              },
+            }
+          }
+        } else {
+          let stream = self.gen_wrap_stream(quote! { #ret });
+          quote_spanned! { span=>
+            napi::bindgen_prelude::ToNapiValue::to_napi_value(env, #stream)
+          }
+        }
       } else if self.is_ret_result {
         if self.is_async {
-          quote! {
+          quote_spanned! { span=>
             <#ty as napi::bindgen_prelude::ToNapiValue>::to_napi_value(env, #ret)
           }
         } else if is_return_self {
           quote! { #ret.map(|_| cb.this) }
         } else {
-          quote! {
+          quote_spanned! { span=>
             match #ret {
               Ok(value) => napi::bindgen_prelude::ToNapiValue::to_napi_value(env, value),
               Err(err) => {
@@ -312,7 +472,7 @@ impl NapiFn {
       } else if is_return_self {
         quote! { Ok(cb.this) }
       } else {
-        quote! {
+        quote_spanned! { span=>
           <#ty as napi::bindgen_prelude::ToNapiValue>::to_napi_value(env, #ret)
         }
       }
@@ -323,17 +483,172 @@ impl NapiFn {
     }
   }
 
+  /// Convert the returned `stream` of `#[napi(readable_stream)]` or `#[napi(async_iterator)]`
+  fn gen_wrap_stream(&self, stream: TokenStream) -> TokenStream {
+    match self.readable_stream {
+      Some(object_mode) => {
+        let object_mode = object_mode.then(|| quote! { .object_mode() });
+        quote! { napi::bindgen_prelude::ReadableStream::new(#stream)#object_mode }
+      }
+      None => quote! { napi::bindgen_prelude::AsyncIterator::new(#stream) },
+    }
+  }
+
+  /// Throw the panics of the call as the JavaScript errors with `#[napi(catch_unwind)]` or the `catch_unwind` feature of napi
+  fn gen_catch_panic(&self, call: TokenStream) -> TokenStream {
+    let catch_unwind = self.catch_unwind;
+    quote! {
+      napi::bindgen_prelude::catch_panic(env, #catch_unwind, || { #call })
+    }
+  }
+
+  /// Fail the build with the feature to enable if the fn needs the tokio runtime, which is not in the `minimal` feature set of napi.
+  fn gen_async_runtime_check(&self) -> TokenStream {
+    let item = if self.is_async {
+      "`async fn`"
+    } else if self.execution == FnExecution::Tokio {
+      "`#[napi(execution = \"tokio\")]`"
+    } else if self.readable_stream.is_some() {
+      "`#[napi(readable_stream)]`"
+    } else if self.async_iterator {
+      "`#[napi(async_iterator)]`"
+    } else {
+      return quote! {};
+    };
+    // only the `cfg`s of the fn, the other attrs like the docs can't be applied to the macro invocation
+    let cfgs = self
+      .attrs
+      .iter()
+      .filter(|attr| attr.path.is_ident("cfg") || attr.path.is_ident("cfg_attr"));
+    let span = self.name.span();
+    quote_spanned! { span=>
+      #(#cfgs)*
+      napi::__assert_tokio_rt!(#item);
+    }
+  }
+
+  /// Generate the promise returning variant of `#[napi(also_async = "...")]`.
+  /// The arguments are converted on the JavaScript thread, then the native function is called in the libuv thread pool.
+  fn gen_also_async(&self) -> TokenStream {
+    let also_async = match &self.also_async {
+      Some(also_async) => also_async,
+      None => return quote! {},
+    };
+    let name_str = format!("{}_also_async", self.name);
+    let intermediate_ident = get_intermediate_ident(&name_str, self.name.span());
+    let args_len = self.args.len();
+    let (arg_conversions, arg_names) = self.gen_arg_conversions();
+    let native_call = self.gen_off_thread_call(FnExecution::BlockingPool, &arg_names);
+    let function_call = self.gen_catch_panic(quote! {
+      napi::bindgen_prelude::CallbackInfo::<#args_len>::new(env, cb, None).and_then(|mut cb| {
+        #(#arg_conversions)*
+        #native_call
+      })
+    });
+    let register = self.gen_fn_register_as(&name_str, also_async);
+    let type_params = self.gen_type_param_aliases();
+    let attrs = &self.attrs;
+
+    quote! {
+      #(#attrs)*
+      #[doc(hidden)]
+      #[allow(non_snake_case)]
+      #[allow(clippy::all)]
+      extern "C" fn #intermediate_ident(
+        env: napi::bindgen_prelude::sys::napi_env,
+        cb: napi::bindgen_prelude::sys::napi_callback_info
+      ) -> napi::bindgen_prelude::sys::napi_value {
+        #type_params
+        unsafe {
+          #function_call.unwrap_or_else(|e| {
+            napi::bindgen_prelude::JsError::from(e).throw_into(env);
+            std::ptr::null_mut::<napi::bindgen_prelude::sys::napi_value__>()
+          })
+        }
+      }
+
+      #register
+    }
+  }
+
+  /// Define the module level fn and the `also_async` variant of it on the class of `#[napi(static_method = Class)]`.
+  fn gen_static_method(&self) -> TokenStream {
+    let static_method = match &self.static_method {
+      Some(static_method) => static_method,
+      None => return quote! {},
+    };
+    let name_str = self.name.to_string();
+    let span = self.name.span();
+    let class_str = static_method.class.to_string();
+    let class_js_name = format!("{}\0", static_method.js_name);
+    let class_js_mod = js_mod_to_token_stream(static_method.js_mod.as_ref());
+    let register_name = get_register_ident(&format!("{}_static_method", name_str), span);
+    let mut props = vec![(
+      self.js_name.clone(),
+      get_intermediate_ident(&name_str, span),
+    )];
+    if let Some(also_async) = &self.also_async {
+      props.push((
+        also_async.clone(),
+        get_intermediate_ident(&format!("{}_also_async", name_str), span),
+      ));
+    }
+    let props = props.into_iter().map(|(js_name, intermediate_ident)| {
+      quote! {
+        napi::bindgen_prelude::Property::new(#js_name).unwrap()
+          .with_method(#intermediate_ident)
+          .with_property_attributes(napi::bindgen_prelude::PropertyAttributes::Static)
+      }
+    });
+
+    quote! {
+      #[allow(clippy::all)]
+      #[allow(non_snake_case)]
+      #[cfg(all(not(test), not(feature = "noop")))]
+      #[napi::bindgen_prelude::ctor]
+      fn #register_name() {
+        napi::bindgen_prelude::register_class(#class_str, #class_js_mod, #class_js_name, vec![#(#props),*]);
+      }
+    }
+  }
+
+  /// Move the converted arguments into the thread pool of `execution`, and call the function there.
+  fn gen_off_thread_call(&self, execution: FnExecution, arg_names: &[TokenStream]) -> TokenStream {
+    let receiver = self.gen_fn_receiver();
+    let call = if self.is_ret_result {
+      quote! { #receiver(#(#arg_names),*) }
+    } else {
+      quote! { Ok(#receiver(#(#arg_names),*)) }
+    };
+    match execution {
+      FnExecution::Tokio => quote! {
+        napi::bindgen_prelude::execute_tokio_blocking(env, move || { #call })
+      },
+      _ => quote! {
+        <napi::bindgen_prelude::AsyncTask<_> as napi::bindgen_prelude::ToNapiValue>::to_napi_value(
+          env,
+          napi::bindgen_prelude::AsyncTask::new(napi::bindgen_prelude::BlockingTask::new(move || { #call })),
+        )
+      },
+    }
+  }
+
   fn gen_fn_register(&self) -> TokenStream {
-    if self.parent.is_some() {
+    self.gen_fn_register_as(&self.name.to_string(), &self.js_name)
+  }
+
+  fn gen_fn_register_as(&self, name_str: &str, js_name: &str) -> TokenStream {
+    // methods are registered with the class, except the module functions
+    if self.parent.is_some() && self.module_function.is_none() {
       quote! {}
     } else {
-      let name_str = self.name.to_string();
-      let js_name = format!("{}\0", &self.js_name);
+      let js_name = format!("{}\0", js_name);
       let name_len = js_name.len();
-      let module_register_name = get_register_ident(&name_str);
-      let intermediate_ident = get_intermediate_ident(&name_str);
+      let span = self.name.span();
+      let module_register_name = get_register_ident(name_str, span);
+      let intermediate_ident = get_intermediate_ident(name_str, span);
       let js_mod_ident = js_mod_to_token_stream(self.js_mod.as_ref());
-      let cb_name = Ident::new(&format!("{}_js_function", name_str), Span::call_site());
+      let cb_name = Ident::new(&format!("{}_js_function", name_str), located_span(span));
       quote! {
         #[allow(non_snake_case)]
         #[allow(clippy::all)]
@@ -367,3 +682,36 @@ impl NapiFn {
     }
   }
 }
+
+fn is_memoized(ty: &syn::Type) -> bool {
+  match ty {
+    syn::Type::Path(syn::TypePath { qself: None, path }) => path
+      .segments
+      .last()
+      .map(|segment| segment.ident == "Memoized")
+      .unwrap_or(false),
+    _ => false,
+  }
+}
+
+/// `Some(is_optional)` of `AbortSignal` and `Option<AbortSignal>`
+fn abort_signal_ty(ty: &syn::Type) -> Option<bool> {
+  let segment = match ty {
+    syn::Type::Path(syn::TypePath { qself: None, path }) => path.segments.last()?,
+    _ => return None,
+  };
+  if segment.ident == "AbortSignal" {
+    return Some(false);
+  }
+  match &segment.arguments {
+    syn::PathArguments::AngleBracketed(args) if segment.ident == "Option" => {
+      match args.args.first() {
+        Some(syn::GenericArgument::Type(inner)) => abort_signal_ty(inner)
+          .filter(|is_optional| !is_optional)
+          .map(|_| true),
+        _ => None,
+      }
+    }
+    _ => None,
+  }
+}
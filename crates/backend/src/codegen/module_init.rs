@@ -0,0 +1,49 @@
+use proc_macro2::{Ident, TokenStream};
+use quote::ToTokens;
+
+use crate::{
+  codegen::get_register_ident, BindgenResult, ModuleInitArg, NapiModuleInit, TryToTokens,
+};
+
+impl TryToTokens for NapiModuleInit {
+  fn try_to_tokens(&self, tokens: &mut TokenStream) -> BindgenResult<()> {
+    let name = &self.name;
+    let name_str = self.name.to_string();
+    let register_name = get_register_ident(&name_str, self.name.span());
+    let cb_name = Ident::new(
+      &format!("__register__module_init__{}_callback__", register_name),
+      self.name.span(),
+    );
+    let args = self.args.iter().map(|arg| match arg {
+      ModuleInitArg::Env => quote! { napi::Env::from_raw(env) },
+      ModuleInitArg::Exports => {
+        quote! { <napi::JsObject as napi::NapiValue>::from_raw_unchecked(env, exports) }
+      }
+    });
+    let call = if self.returns_result {
+      quote! { #name(#(#args),*) }
+    } else {
+      quote! {
+        #name(#(#args),*);
+        Ok(())
+      }
+    };
+    (quote! {
+      #[allow(non_snake_case)]
+      #[allow(clippy::all)]
+      unsafe fn #cb_name(env: napi::sys::napi_env, exports: napi::sys::napi_value) -> napi::Result<()> {
+        #call
+      }
+      #[allow(non_snake_case)]
+      #[allow(clippy::all)]
+      #[cfg(all(not(test), not(feature = "noop")))]
+      #[napi::bindgen_prelude::ctor]
+      fn #register_name() {
+        napi::bindgen_prelude::register_module_init(#cb_name);
+      }
+    })
+    .to_tokens(tokens);
+
+    Ok(())
+  }
+}
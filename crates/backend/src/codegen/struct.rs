@@ -3,10 +3,14 @@ use std::sync::atomic::{AtomicU32, Ordering};
 
 use proc_macro2::{Ident, Literal, Span, TokenStream};
 use quote::ToTokens;
+use syn::spanned::Spanned;
 
 use crate::{
-  codegen::{get_intermediate_ident, get_register_ident, js_mod_to_token_stream},
-  BindgenResult, FnKind, NapiImpl, NapiStruct, NapiStructKind, TryToTokens,
+  codegen::{
+    columnar_typed_array, get_intermediate_ident, get_register_ident, js_mod_to_token_stream,
+    located_span,
+  },
+  BindgenResult, FnKind, ModuleFunction, NapiImpl, NapiStruct, NapiStructKind, TryToTokens,
 };
 
 static NAPI_IMPL_ID: AtomicU32 = AtomicU32::new(0);
@@ -54,6 +58,7 @@ fn gen_napi_value_map_impl(name: &Ident, to_napi_val_impl: TokenStream) -> Token
       ) -> napi::bindgen_prelude::Result<&'static Self> {
         let mut wrapped_val: *mut std::ffi::c_void = std::ptr::null_mut();
 
+        napi::bindgen_prelude::check_class_instance::<#name>(env, napi_val)?;
         napi::bindgen_prelude::check_status!(
           napi::bindgen_prelude::sys::napi_unwrap(env, napi_val, &mut wrapped_val),
           "Failed to recover `{}` type from napi value",
@@ -71,6 +76,7 @@ fn gen_napi_value_map_impl(name: &Ident, to_napi_val_impl: TokenStream) -> Token
       ) -> napi::bindgen_prelude::Result<&'static mut Self> {
         let mut wrapped_val: *mut std::ffi::c_void = std::ptr::null_mut();
 
+        napi::bindgen_prelude::check_class_instance::<#name>(env, napi_val)?;
         napi::bindgen_prelude::check_status!(
           napi::bindgen_prelude::sys::napi_unwrap(env, napi_val, &mut wrapped_val),
           "Failed to recover `{}` type from napi value",
@@ -135,7 +141,10 @@ impl TryToTokens for NapiStruct {
 
 impl NapiStruct {
   fn gen_helper_mod(&self) -> TokenStream {
-    let mod_name = Ident::new(&format!("__napi_helper__{}", self.name), Span::call_site());
+    let mod_name = Ident::new(
+      &format!("__napi_helper__{}", self.name),
+      located_span(self.name.span()),
+    );
 
     let ctor = if self.kind == NapiStructKind::Constructor {
       self.gen_default_ctor()
@@ -145,6 +154,7 @@ impl NapiStruct {
 
     let mut getters_setters = self.gen_default_getters_setters();
     getters_setters.sort_by(|a, b| a.0.cmp(&b.0));
+    let format_methods = self.gen_format_methods();
     let register = self.gen_register();
 
     let getters_setters_token = getters_setters.into_iter().map(|(_, token)| token);
@@ -158,6 +168,7 @@ impl NapiStruct {
 
         #ctor
         #(#getters_setters_token)*
+        #format_methods
         #register
       }
     }
@@ -210,7 +221,71 @@ impl NapiStruct {
       NapiStructKind::Constructor => {
         gen_napi_value_map_impl(&self.name, self.gen_to_napi_value_ctor_impl())
       }
-      NapiStructKind::Object => self.gen_to_napi_value_obj_impl(),
+      NapiStructKind::Object => {
+        let obj_impl = self.gen_to_napi_value_obj_impl();
+        let columns_impl = self.gen_to_columns_impl();
+        quote! {
+          #obj_impl
+          #columns_impl
+        }
+      }
+    }
+  }
+
+  /// Collect the fields of the rows into columns, numeric fields into typed arrays and the others into arrays.
+  fn gen_to_columns_impl(&self) -> TokenStream {
+    if !self.columnar {
+      return quote! {};
+    }
+    let name = &self.name;
+    let mut field_js_names = vec![];
+    let mut field_idents = vec![];
+    let mut column_idents = vec![];
+    let mut column_conversions = vec![];
+
+    for field in self.fields.iter() {
+      let ident = match &field.name {
+        syn::Member::Named(ident) => ident,
+        // rejected by the parser
+        syn::Member::Unnamed(_) => continue,
+      };
+      let column_ident = quote::format_ident!("{}_column", ident);
+      let ty = &field.ty;
+      column_conversions.push(match columnar_typed_array(ty) {
+        Some(typed_array) => {
+          let typed_array = Ident::new(typed_array, Span::call_site());
+          quote! {
+          <napi::bindgen_prelude::#typed_array as napi::bindgen_prelude::ToNapiValue>::to_napi_value(
+            env,
+            napi::bindgen_prelude::#typed_array::new(#column_ident),
+          )?
+          }
+        }
+        None => quote! {
+          <Vec<#ty> as napi::bindgen_prelude::ToNapiValue>::to_napi_value(env, #column_ident)?
+        },
+      });
+      field_js_names.push(&field.js_name);
+      field_idents.push(ident);
+      column_idents.push(column_ident);
+    }
+
+    quote! {
+      impl napi::bindgen_prelude::ToColumns for #name {
+        const FIELDS: &'static [&'static str] = &[#(#field_js_names),*];
+
+        unsafe fn to_columns(
+          env: napi::bindgen_prelude::sys::napi_env,
+          rows: Vec<Self>,
+        ) -> napi::bindgen_prelude::Result<Vec<napi::bindgen_prelude::sys::napi_value>> {
+          #(let mut #column_idents = Vec::with_capacity(rows.len());)*
+          for row in rows {
+            let Self { #(#field_idents),* } = row;
+            #(#column_idents.push(#field_idents);)*
+          }
+          Ok(vec![#(#column_conversions),*])
+        }
+      }
     }
   }
 
@@ -254,6 +329,7 @@ impl NapiStruct {
               "Failed to wrap native object of class `{}`",
               #js_name_str
             )?;
+            napi::bindgen_prelude::tag_class_instance::<#name>(env, result)?;
             napi::bindgen_prelude::Reference::<#name>::add_ref(wrapped_value, (wrapped_value, object_ref, finalize_callbacks_ptr));
             napi::bindgen_prelude::___CALL_FROM_FACTORY.with(|inner| inner.store(false, std::sync::atomic::Ordering::Relaxed));
             Ok(result)
@@ -339,6 +415,9 @@ impl NapiStruct {
   }
 
   fn gen_to_napi_value_obj_impl(&self) -> TokenStream {
+    if self.is_tuple {
+      return self.gen_to_napi_value_tuple_impl();
+    }
     let name = &self.name;
     let name_str = self.name.to_string();
 
@@ -349,6 +428,8 @@ impl NapiStruct {
     for field in self.fields.iter() {
       let field_js_name = &field.js_name;
       let ty = &field.ty;
+      // the errors of the types not convertible point to the types of the fields
+      let span = located_span(ty.span());
       let is_optional_field = if let syn::Type::Path(syn::TypePath {
         path: syn::Path { segments, .. },
         ..
@@ -365,19 +446,27 @@ impl NapiStruct {
       match &field.name {
         syn::Member::Named(ident) => {
           field_destructions.push(quote! { #ident });
-          if is_optional_field {
-            obj_field_setters.push(quote! {
+          if is_optional_field && !self.use_nullable {
+            obj_field_setters.push(quote_spanned! { span=>
               if #ident.is_some() {
                 obj.set(#field_js_name, #ident)?;
               }
             });
           } else {
-            obj_field_setters.push(quote! { obj.set(#field_js_name, #ident)?; });
+            obj_field_setters.push(quote_spanned! { span=> obj.set(#field_js_name, #ident)?; });
           }
-          if is_optional_field {
-            obj_field_getters.push(quote! { let #ident: #ty = obj.get(#field_js_name)?; });
+          if is_optional_field && self.use_nullable {
+            obj_field_getters.push(quote_spanned! { span=>
+              let #ident: #ty = obj.get_nullable(#field_js_name)?.ok_or_else(|| napi::bindgen_prelude::Error::new(
+                napi::bindgen_prelude::Status::InvalidArg,
+                format!("Missing field `{}`", #field_js_name),
+              ))?;
+            });
+          } else if is_optional_field {
+            obj_field_getters
+              .push(quote_spanned! { span=> let #ident: #ty = obj.get(#field_js_name)?; });
           } else {
-            obj_field_getters.push(quote! {
+            obj_field_getters.push(quote_spanned! { span=>
               let #ident: #ty = obj.get(#field_js_name)?.ok_or_else(|| napi::bindgen_prelude::Error::new(
                 napi::bindgen_prelude::Status::InvalidArg,
                 format!("Missing field `{}`", #field_js_name),
@@ -387,19 +476,27 @@ impl NapiStruct {
         }
         syn::Member::Unnamed(i) => {
           field_destructions.push(quote! { arg #i });
-          if is_optional_field {
-            obj_field_setters.push(quote! {
+          if is_optional_field && !self.use_nullable {
+            obj_field_setters.push(quote_spanned! { span=>
               if arg #1.is_some() {
                 obj.set(#field_js_name, arg #i)?;
               }
             });
           } else {
-            obj_field_setters.push(quote! { obj.set(#field_js_name, arg #1)?; });
+            obj_field_setters.push(quote_spanned! { span=> obj.set(#field_js_name, arg #1)?; });
           }
-          if is_optional_field {
-            obj_field_getters.push(quote! { let arg #i: #ty = obj.get(#field_js_name)?; });
+          if is_optional_field && self.use_nullable {
+            obj_field_getters.push(quote_spanned! { span=>
+              let arg #i: #ty = obj.get_nullable(#field_js_name)?.ok_or_else(|| napi::bindgen_prelude::Error::new(
+                napi::bindgen_prelude::Status::InvalidArg,
+                format!("Missing field `{}`", #field_js_name),
+              ))?;
+            });
+          } else if is_optional_field {
+            obj_field_getters
+              .push(quote_spanned! { span=> let arg #i: #ty = obj.get(#field_js_name)?; });
           } else {
-            obj_field_getters.push(quote! {
+            obj_field_getters.push(quote_spanned! { span=>
               let arg #i: #ty = obj.get(#field_js_name)?.ok_or_else(|| napi::bindgen_prelude::Error::new(
                 napi::bindgen_prelude::Status::InvalidArg,
                 format!("Missing field `{}`", #field_js_name),
@@ -420,8 +517,21 @@ impl NapiStruct {
       }
     };
 
+    let deny_unknown_fields = if self.deny_unknown_fields {
+      let field_js_names = self.fields.iter().map(|field| &field.js_name);
+      quote! { obj.deny_unknown_fields(&[#(#field_js_names),*])?; }
+    } else {
+      quote! {}
+    };
+    let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+    let to_napi_generics = self.generics_bound_by(quote! { napi::bindgen_prelude::ToNapiValue });
+    let (to_napi_impl_generics, _, to_napi_where_clause) = to_napi_generics.split_for_impl();
+    let from_napi_generics =
+      self.generics_bound_by(quote! { napi::bindgen_prelude::FromNapiValue });
+    let (from_napi_impl_generics, _, from_napi_where_clause) = from_napi_generics.split_for_impl();
+
     quote! {
-      impl napi::bindgen_prelude::TypeName for #name {
+      impl #impl_generics napi::bindgen_prelude::TypeName for #name #ty_generics #where_clause {
         fn type_name() -> &'static str {
           #name_str
         }
@@ -431,8 +541,8 @@ impl NapiStruct {
         }
       }
 
-      impl napi::bindgen_prelude::ToNapiValue for #name {
-        unsafe fn to_napi_value(env: napi::bindgen_prelude::sys::napi_env, val: #name) -> napi::bindgen_prelude::Result<napi::bindgen_prelude::sys::napi_value> {
+      impl #to_napi_impl_generics napi::bindgen_prelude::ToNapiValue for #name #ty_generics #to_napi_where_clause {
+        unsafe fn to_napi_value(env: napi::bindgen_prelude::sys::napi_env, val: Self) -> napi::bindgen_prelude::Result<napi::bindgen_prelude::sys::napi_value> {
           let env_wrapper = napi::bindgen_prelude::Env::from(env);
           let mut obj = env_wrapper.create_object()?;
 
@@ -443,14 +553,16 @@ impl NapiStruct {
         }
       }
 
-      impl napi::bindgen_prelude::FromNapiValue for #name {
+      impl #from_napi_impl_generics napi::bindgen_prelude::FromNapiValue for #name #ty_generics #from_napi_where_clause {
         unsafe fn from_napi_value(
           env: napi::bindgen_prelude::sys::napi_env,
           napi_val: napi::bindgen_prelude::sys::napi_value
         ) -> napi::bindgen_prelude::Result<Self> {
           let env_wrapper = napi::bindgen_prelude::Env::from(env);
+          napi::bindgen_prelude::debug_validate_object(env, napi_val)?;
           let mut obj = napi::bindgen_prelude::Object::from_napi_value(env, napi_val)?;
 
+          #deny_unknown_fields
           #(#obj_field_getters)*
 
           let val = #destructed_fields;
@@ -461,6 +573,71 @@ impl NapiStruct {
     }
   }
 
+  /// `#[napi(object)] struct Pair(A, B)` is converted from and to the array `[a, b]`, the same as the tuple `(A, B)`
+  fn gen_to_napi_value_tuple_impl(&self) -> TokenStream {
+    let name = &self.name;
+    let name_str = self.name.to_string();
+    let tys = self
+      .fields
+      .iter()
+      .map(|field| &field.ty)
+      .collect::<Vec<_>>();
+    let elems = (0..self.fields.len())
+      .map(|i| Ident::new(&format!("elem{}", i), Span::call_site()))
+      .collect::<Vec<_>>();
+
+    let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+    let to_napi_generics = self.generics_bound_by(quote! { napi::bindgen_prelude::ToNapiValue });
+    let (to_napi_impl_generics, _, to_napi_where_clause) = to_napi_generics.split_for_impl();
+    let from_napi_generics =
+      self.generics_bound_by(quote! { napi::bindgen_prelude::FromNapiValue });
+    let (from_napi_impl_generics, _, from_napi_where_clause) = from_napi_generics.split_for_impl();
+
+    quote! {
+      impl #impl_generics napi::bindgen_prelude::TypeName for #name #ty_generics #where_clause {
+        fn type_name() -> &'static str {
+          #name_str
+        }
+
+        fn value_type() -> napi::ValueType {
+          napi::ValueType::Object
+        }
+      }
+
+      impl #to_napi_impl_generics napi::bindgen_prelude::ToNapiValue for #name #ty_generics #to_napi_where_clause {
+        unsafe fn to_napi_value(env: napi::bindgen_prelude::sys::napi_env, val: Self) -> napi::bindgen_prelude::Result<napi::bindgen_prelude::sys::napi_value> {
+          let Self(#(#elems),*) = val;
+          <(#(#tys,)*) as napi::bindgen_prelude::ToNapiValue>::to_napi_value(env, (#(#elems,)*))
+        }
+      }
+
+      impl #from_napi_impl_generics napi::bindgen_prelude::FromNapiValue for #name #ty_generics #from_napi_where_clause {
+        unsafe fn from_napi_value(
+          env: napi::bindgen_prelude::sys::napi_env,
+          napi_val: napi::bindgen_prelude::sys::napi_value
+        ) -> napi::bindgen_prelude::Result<Self> {
+          let (#(#elems,)*) = <(#(#tys,)*) as napi::bindgen_prelude::FromNapiValue>::from_napi_value(env, napi_val)?;
+          Ok(Self(#(#elems),*))
+        }
+      }
+    }
+  }
+
+  /// The generics of the struct with `bound` added to each type parameter
+  fn generics_bound_by(&self, bound: TokenStream) -> syn::Generics {
+    let mut generics = self.generics.clone();
+    let predicates = self
+      .generics
+      .type_params()
+      .map(|param| {
+        let ident = &param.ident;
+        syn::parse_quote! { #ident: #bound }
+      })
+      .collect::<Vec<syn::WherePredicate>>();
+    generics.make_where_clause().predicates.extend(predicates);
+    generics
+  }
+
   fn gen_default_getters_setters(&self) -> Vec<(String, TokenStream)> {
     let mut getters_setters = vec![];
     let struct_name = &self.name;
@@ -473,8 +650,9 @@ impl NapiStruct {
       };
       let ty = &field.ty;
 
-      let getter_name = Ident::new(&format!("get_{}", field_name), Span::call_site());
-      let setter_name = Ident::new(&format!("set_{}", field_name), Span::call_site());
+      let field_span = located_span(field_ident.span());
+      let getter_name = Ident::new(&format!("get_{}", field_name), field_span);
+      let setter_name = Ident::new(&format!("set_{}", field_name), field_span);
 
       if field.getter {
         getters_setters.push((
@@ -531,9 +709,68 @@ impl NapiStruct {
     getters_setters
   }
 
+  /// `toString()`, `toJSON()` and `[util.inspect.custom]()` of `#[napi(to_string)]`, `#[napi(to_json)]` and `#[napi(inspect)]`,
+  /// by `Display`, `serde::Serialize` and `Debug` of the struct
+  fn gen_format_methods(&self) -> TokenStream {
+    let name = &self.name;
+    let mut methods = vec![];
+    if self.to_string {
+      methods.push((
+        quote! { js_to_string },
+        quote! {
+          <String as napi::bindgen_prelude::ToNapiValue>::to_napi_value(env, std::string::ToString::to_string(this))
+        },
+      ));
+    }
+    if self.to_json {
+      methods.push((
+        quote! { js_to_json },
+        quote! {
+          napi::Env::from_raw(env).to_js_value(this).map(|value| napi::NapiRaw::raw(&value))
+        },
+      ));
+    }
+    if self.inspect {
+      methods.push((
+        quote! { js_inspect },
+        quote! {
+          <String as napi::bindgen_prelude::ToNapiValue>::to_napi_value(env, format!("{:?}", this))
+        },
+      ));
+    }
+    let serde_json_check = self.to_json.then(|| {
+      quote! {
+        napi::__assert_serde_json!("`#[napi(to_json)]`");
+      }
+    });
+    let methods = methods.into_iter().map(|(method_name, convert)| {
+      quote! {
+        extern "C" fn #method_name(
+          env: napi::bindgen_prelude::sys::napi_env,
+          cb: napi::bindgen_prelude::sys::napi_callback_info
+        ) -> napi::bindgen_prelude::sys::napi_value {
+          unsafe {
+            napi::bindgen_prelude::CallbackInfo::<0>::new(env, cb, None).and_then(|mut cb| {
+              let this = cb.unwrap_borrow::<#name>()?;
+              #convert
+            }).unwrap_or_else(|e| {
+              napi::bindgen_prelude::JsError::from(e).throw_into(env);
+              std::ptr::null_mut::<napi::bindgen_prelude::sys::napi_value__>()
+            })
+          }
+        }
+      }
+    });
+    quote! {
+      #serde_json_check
+      #(#methods)*
+    }
+  }
+
   fn gen_register(&self) -> TokenStream {
     let name_str = self.name.to_string();
-    let struct_register_name = get_register_ident(&format!("{}_struct", name_str));
+    let struct_register_name =
+      get_register_ident(&format!("{}_struct", name_str), self.name.span());
     let js_name = format!("{}\0", self.js_name);
     let mut props = vec![];
 
@@ -558,18 +795,51 @@ impl NapiStruct {
       };
 
       if field.getter {
-        let getter_name = Ident::new(&format!("get_{}", field_name), Span::call_site());
+        let getter_name = Ident::new(
+          &format!("get_{}", field_name),
+          located_span(field.name.span()),
+        );
         (quote! { .with_getter(#getter_name) }).to_tokens(&mut prop);
       }
 
       if field.setter {
-        let setter_name = Ident::new(&format!("set_{}", field_name), Span::call_site());
+        let setter_name = Ident::new(
+          &format!("set_{}", field_name),
+          located_span(field.name.span()),
+        );
         (quote! { .with_setter(#setter_name) }).to_tokens(&mut prop);
       }
 
+      if field.enumerable {
+        (quote! { .with_enumerable(true) }).to_tokens(&mut prop);
+      }
+
+      if field.configurable {
+        (quote! { .with_configurable(true) }).to_tokens(&mut prop);
+      }
+
       props.push(prop);
     }
+    if self.to_string {
+      props.push(quote! { napi::bindgen_prelude::Property::new("toString").unwrap().with_method(js_to_string) });
+    }
+    if self.to_json {
+      props.push(
+        quote! { napi::bindgen_prelude::Property::new("toJSON").unwrap().with_method(js_to_json) },
+      );
+    }
+    let register_inspect = self.inspect.then(|| {
+      quote! {
+        napi::bindgen_prelude::register_class_symbol_method(#name_str, napi::bindgen_prelude::ClassSymbol::InspectCustom, js_inspect);
+      }
+    });
     let js_mod_ident = js_mod_to_token_stream(self.js_mod.as_ref());
+    let register_parent = self.parent.as_ref().map(|parent| {
+      let parent_name_str = parent.name.to_string();
+      quote! {
+        napi::bindgen_prelude::register_class_parent(#name_str, #parent_name_str);
+      }
+    });
     quote! {
       #[allow(non_snake_case)]
       #[allow(clippy::all)]
@@ -577,6 +847,8 @@ impl NapiStruct {
       #[napi::bindgen_prelude::ctor]
       fn #struct_register_name() {
         napi::bindgen_prelude::register_class(#name_str, #js_mod_ident, #js_name, vec![#(#props),*]);
+        #register_parent
+        #register_inspect
       }
     }
   }
@@ -603,17 +875,22 @@ impl NapiImpl {
       Span::call_site(),
     );
 
-    let register_name = get_register_ident(&format!("{}_impl", name_str));
+    let register_name = get_register_ident(&format!("{}_impl", name_str), self.name.span());
 
     let mut methods = vec![];
+    let iterator_next = String::from("next");
     let mut props = HashMap::new();
 
     for item in self.items.iter() {
       let js_name = Literal::string(&item.js_name);
       let item_str = item.name.to_string();
-      let intermediate_name = get_intermediate_ident(&item_str);
+      let intermediate_name = get_intermediate_ident(&item_str, item.name.span());
       methods.push(item.try_to_token_stream()?);
 
+      if item.module_function == Some(ModuleFunction::Only) {
+        continue;
+      }
+
       let prop = props.entry(&item.js_name).or_insert_with(|| {
         quote! {
           napi::bindgen_prelude::Property::new(#js_name).unwrap()
@@ -634,6 +911,46 @@ impl NapiImpl {
       };
 
       appendix.to_tokens(prop);
+
+      // either the getter or the setter of the accessor
+      if item.enumerable {
+        (quote! { .with_enumerable(true) }).to_tokens(prop);
+      }
+      if item.configurable {
+        (quote! { .with_configurable(true) }).to_tokens(prop);
+      }
+
+      if let Some(also_async) = &item.also_async {
+        let js_name = Literal::string(also_async);
+        let intermediate_name =
+          get_intermediate_ident(&format!("{}_also_async", item_str), item.name.span());
+        let prop = props.entry(also_async).or_insert_with(|| {
+          quote! {
+            napi::bindgen_prelude::Property::new(#js_name).unwrap()
+          }
+        });
+        let appendix = if item.fn_self.is_some() {
+          quote! { .with_method(#intermediate_name) }
+        } else {
+          quote! { .with_method(#intermediate_name).with_property_attributes(napi::bindgen_prelude::PropertyAttributes::Static) }
+        };
+        appendix.to_tokens(prop);
+      }
+    }
+
+    let mut register_iterator = quote! {};
+    if self.iterator_item.is_some() {
+      let (next_method, next_name) = self.gen_iterator_next();
+      methods.push(next_method);
+      props.insert(
+        &iterator_next,
+        quote! {
+          napi::bindgen_prelude::Property::new(#iterator_next).unwrap().with_method(#next_name)
+        },
+      );
+      register_iterator = quote! {
+        napi::bindgen_prelude::register_class_iterator(#name_str);
+      };
     }
 
     let mut props: Vec<_> = props.into_iter().collect();
@@ -651,8 +968,37 @@ impl NapiImpl {
         #[napi::bindgen_prelude::ctor]
         fn #register_name() {
           napi::bindgen_prelude::register_class(#name_str, #js_mod_ident, #js_name, vec![#(#props),*]);
+          #register_iterator
         }
       }
     })
   }
+
+  /// `next()` of the JavaScript iterator protocol, calls `Iterator::next` of the instance
+  fn gen_iterator_next(&self) -> (TokenStream, Ident) {
+    let name = &self.name;
+    let next_name =
+      get_intermediate_ident(&format!("{}_iterator_next", self.name), self.name.span());
+    (
+      quote! {
+        #[doc(hidden)]
+        extern "C" fn #next_name(
+          env: napi::bindgen_prelude::sys::napi_env,
+          cb: napi::bindgen_prelude::sys::napi_callback_info
+        ) -> napi::bindgen_prelude::sys::napi_value {
+          unsafe {
+            napi::bindgen_prelude::CallbackInfo::<0>::new(env, cb, None).and_then(|mut cb| {
+              let this = cb.unwrap_borrow_mut::<#name>()?;
+              let result = napi::bindgen_prelude::IteratorResult(Iterator::next(this));
+              <napi::bindgen_prelude::IteratorResult<_> as napi::bindgen_prelude::ToNapiValue>::to_napi_value(env, result)
+            }).unwrap_or_else(|e| {
+              napi::bindgen_prelude::JsError::from(e).throw_into(env);
+              std::ptr::null_mut::<napi::bindgen_prelude::sys::napi_value__>()
+            })
+          }
+        }
+      },
+      next_name,
+    )
+  }
 }
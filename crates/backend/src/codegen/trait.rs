@@ -0,0 +1,70 @@
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::ToTokens;
+
+use crate::{BindgenResult, NapiTrait, TryToTokens};
+
+impl TryToTokens for NapiTrait {
+  fn try_to_tokens(&self, tokens: &mut TokenStream) -> BindgenResult<()> {
+    let name = &self.name;
+    let name_str = self.name.to_string();
+    let js_impl = Ident::new(&format!("__napi_js_impl__{}", name_str), Span::call_site());
+
+    let methods = self.methods.iter().map(|method| {
+      let sig = &method.sig;
+      let js_name = &method.js_name;
+      let args =
+        (0..method.args.len()).map(|i| Ident::new(&format!("arg{}", i), Span::call_site()));
+      if method.ret.is_some() {
+        quote! {
+          #sig {
+            self.0.call_method(#js_name, (#(#args,)*))
+          }
+        }
+      } else {
+        quote! {
+          #sig {
+            self.0.call_void_method(#js_name, (#(#args,)*))
+          }
+        }
+      }
+    });
+
+    (quote! {
+      #[allow(non_camel_case_types)]
+      #[doc(hidden)]
+      struct #js_impl(napi::bindgen_prelude::JsTraitObject);
+
+      impl #name for #js_impl {
+        #(#methods)*
+      }
+
+      impl napi::bindgen_prelude::TypeName for Box<dyn #name> {
+        fn type_name() -> &'static str {
+          #name_str
+        }
+
+        fn value_type() -> napi::ValueType {
+          napi::ValueType::Object
+        }
+      }
+
+      impl napi::bindgen_prelude::ValidateNapiValue for Box<dyn #name> {
+        fn type_of() -> Vec<napi::ValueType> {
+          vec![napi::ValueType::Object]
+        }
+      }
+
+      impl napi::bindgen_prelude::FromNapiValue for Box<dyn #name> {
+        unsafe fn from_napi_value(
+          env: napi::bindgen_prelude::sys::napi_env,
+          napi_val: napi::bindgen_prelude::sys::napi_value
+        ) -> napi::bindgen_prelude::Result<Self> {
+          Ok(Box::new(#js_impl(napi::bindgen_prelude::JsTraitObject::new(env, napi_val)?)))
+        }
+      }
+    })
+    .to_tokens(tokens);
+
+    Ok(())
+  }
+}
@@ -7,6 +7,7 @@ use proc_macro2::TokenStream;
 pub mod error;
 pub mod ast;
 pub mod codegen;
+pub mod metadata;
 #[cfg(feature = "type-def")]
 pub mod typegen;
 
@@ -47,10 +48,45 @@ macro_rules! napi_ast_impl {
   };
 }
 
+#[cfg(feature = "type-def")]
+impl Napi {
+  /// Type defs of the extra items generated alongside this one,
+  /// like the `also_async` variant and the static method of a fn and the module functions of an impl
+  pub fn extra_type_defs(&self) -> Vec<TypeDef> {
+    match self.item {
+      NapiItem::Fn(ref f) => f
+        .also_async_type_def()
+        .into_iter()
+        .chain(f.static_method_type_def())
+        .collect(),
+      NapiItem::Impl(ref i) => i.module_function_type_defs(),
+      NapiItem::Struct(ref s) => s.columnar_type_def().into_iter().collect(),
+      NapiItem::Enum(ref e) => e.error_class_type_defs(),
+      _ => vec![],
+    }
+  }
+
+  /// Type defs written to `TYPE_DEF_TMP_PATH`, the warnings of the item are attached to the first one
+  pub fn type_defs(&self) -> Vec<TypeDef> {
+    let mut type_defs = self
+      .to_type_def()
+      .into_iter()
+      .chain(self.extra_type_defs())
+      .collect::<Vec<_>>();
+    let warnings = take_warnings();
+    if let Some(type_def) = type_defs.first_mut() {
+      type_def.warnings = warnings;
+    }
+    type_defs
+  }
+}
+
 napi_ast_impl! {
  (Fn, NapiFn),
  (Struct, NapiStruct),
  (Impl, NapiImpl),
  (Enum, NapiEnum),
  (Const, NapiConst),
+ (Trait, NapiTrait),
+ (ModuleInit, NapiModuleInit),
 }
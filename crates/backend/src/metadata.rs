@@ -0,0 +1,163 @@
+//! The API for the third party macros, which export the values of their own items from the module
+//! and declare them in the `.d.ts` generated by `napi build`, the same way as `#[napi]`.
+//!
+//! The items in this module follow semver, the rest of this crate is the internals of `napi-derive`
+//! and could be changed in any release.
+//!
+//! ```ignore
+//! #[proc_macro_attribute]
+//! pub fn napi_orm_model(_attr: TokenStream, input: TokenStream) -> TokenStream {
+//!   let item = syn::parse_macro_input!(input as syn::ItemStruct);
+//!   let name = item.ident.to_string();
+//!   // `unsafe fn(napi::sys::napi_env) -> napi::Result<napi::sys::napi_value>`
+//!   let callback = format_ident!("__orm_model_{}", item.ident);
+//!   let export = ModuleExport::new(&name, callback.clone().into()).namespace("models");
+//!   write_type_defs(&[TypeDef::interface(&name, "id: number").with_js_mod("models")]).unwrap();
+//!   quote! {
+//!     #item
+//!     unsafe fn #callback(env: napi::sys::napi_env) -> napi::Result<napi::sys::napi_value> { /* ... */ }
+//!     #export
+//!   }
+//!   .into()
+//! }
+//! ```
+
+use proc_macro2::{Literal, TokenStream};
+use quote::ToTokens;
+use syn::spanned::Spanned;
+
+use crate::codegen::{get_register_ident, js_mod_to_token_stream};
+#[cfg(feature = "type-def")]
+pub use crate::typegen::TypeDef;
+
+/// A property of the module exports, or of the namespace `js_mod`, set to the value returned by the `callback`
+/// while the module is initialized for every env.
+///
+/// `callback` is the path of an `unsafe fn(napi::sys::napi_env) -> napi::Result<napi::sys::napi_value>`.
+/// The tokens of it are the registration run before the module is loaded, emitted next to the `callback`.
+#[derive(Debug, Clone)]
+pub struct ModuleExport {
+  js_name: String,
+  js_mod: Option<String>,
+  callback: syn::Path,
+}
+
+impl ModuleExport {
+  pub fn new(js_name: impl Into<String>, callback: syn::Path) -> Self {
+    ModuleExport {
+      js_name: js_name.into(),
+      js_mod: None,
+      callback,
+    }
+  }
+
+  /// The namespace like `#[napi(namespace = "...")]`, `a.b` for the nested ones
+  pub fn namespace(mut self, js_mod: impl Into<String>) -> Self {
+    self.js_mod = Some(js_mod.into());
+    self
+  }
+}
+
+impl ToTokens for ModuleExport {
+  fn to_tokens(&self, tokens: &mut TokenStream) {
+    let callback = &self.callback;
+    let callback_name = callback
+      .segments
+      .iter()
+      .map(|segment| segment.ident.to_string())
+      .collect::<Vec<_>>()
+      .join("_");
+    let register_name = get_register_ident(&callback_name, callback.span());
+    let js_name_lit = Literal::string(&format!("{}\0", self.js_name));
+    let js_mod_ident = js_mod_to_token_stream(self.js_mod.as_ref());
+    (quote! {
+      #[allow(non_snake_case)]
+      #[allow(clippy::all)]
+      #[cfg(all(not(test), not(feature = "noop")))]
+      #[napi::bindgen_prelude::ctor]
+      fn #register_name() {
+        napi::bindgen_prelude::register_module_export(#js_mod_ident, #js_name_lit, #callback);
+      }
+    })
+    .to_tokens(tokens);
+  }
+}
+
+#[cfg(feature = "type-def")]
+impl TypeDef {
+  fn new(kind: &str, name: &str, def: &str) -> Self {
+    TypeDef {
+      kind: kind.to_owned(),
+      name: name.to_owned(),
+      def: def.to_owned(),
+      ..Default::default()
+    }
+  }
+
+  /// `def` is the whole declaration, like `export function sum(a: number, b: number): number`
+  pub fn function(name: &str, def: &str) -> Self {
+    TypeDef::new("fn", name, def)
+  }
+
+  /// `def` is the whole declaration, like `export const VERSION: string`
+  pub fn constant(name: &str, def: &str) -> Self {
+    TypeDef::new("const", name, def)
+  }
+
+  /// `export interface name { body }`
+  pub fn interface(name: &str, body: &str) -> Self {
+    TypeDef::new("interface", name, body)
+  }
+
+  /// `export type name = ty`
+  pub fn type_alias(name: &str, ty: &str) -> Self {
+    TypeDef::new("type", name, ty)
+  }
+
+  /// `export class name { body }`, the bodies of the `class_impl`s of the same name are appended to it
+  pub fn class(name: &str, body: &str) -> Self {
+    TypeDef::new("struct", name, body)
+  }
+
+  /// The methods of the `class` declared separately, like the `impl` blocks
+  pub fn class_impl(name: &str, body: &str) -> Self {
+    TypeDef::new("impl", name, body)
+  }
+
+  /// Declared in the namespace, `a.b` for the nested ones
+  pub fn with_js_mod(mut self, js_mod: &str) -> Self {
+    self.js_mod = Some(js_mod.to_owned());
+    self
+  }
+
+  /// The JSDoc from the lines of the doc comments, like the `#[doc]`s of the item
+  pub fn with_js_doc(mut self, comments: &[String]) -> Self {
+    self.js_doc = crate::js_doc_from_comments(comments);
+    self
+  }
+}
+
+/// Append the type defs to the intermediate file of `napi build`, which is `TYPE_DEF_TMP_PATH`.
+/// Nothing is written if the crate is not built by `napi build`.
+#[cfg(feature = "type-def")]
+pub fn write_type_defs(type_defs: &[TypeDef]) -> std::io::Result<()> {
+  use std::io::Write;
+
+  let type_def_file = match std::env::var("TYPE_DEF_TMP_PATH") {
+    Ok(type_def_file) => type_def_file,
+    Err(_) => return Ok(()),
+  };
+  if type_defs.is_empty() {
+    return Ok(());
+  }
+  let file = std::fs::OpenOptions::new()
+    .append(true)
+    .create(true)
+    .open(type_def_file)?;
+  let mut writer = std::io::BufWriter::new(file);
+  for type_def in type_defs {
+    writer.write_all(type_def.to_string().as_bytes())?;
+    writer.write_all(b"\n")?;
+  }
+  writer.flush()
+}
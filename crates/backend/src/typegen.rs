@@ -2,24 +2,49 @@ mod r#const;
 mod r#enum;
 mod r#fn;
 pub(crate) mod r#struct;
+mod r#trait;
 
-use std::{cell::RefCell, collections::HashMap};
+use std::{
+  cell::RefCell,
+  collections::HashMap,
+  fmt::{self, Display, Formatter},
+};
 
 use once_cell::sync::Lazy;
-use syn::Type;
+use proc_macro2::Span;
+use quote::ToTokens;
+use syn::{spanned::Spanned, Type};
 
 #[derive(Default, Debug)]
 pub struct TypeDef {
   pub kind: String,
   pub name: String,
   pub original_name: Option<String>,
+  /// `<T, U>` of the generic interfaces
+  pub generics: Option<String>,
+  /// the parent class of `#[napi(extends)]`, `Error` and the enum class of the `#[napi(error)]` classes
+  pub extends: Option<String>,
   pub def: String,
   pub js_mod: Option<String>,
   pub js_doc: String,
+  pub warnings: Vec<TypeDefWarning>,
+  /// `#[napi(bench)]` of the fns
+  pub benches: Vec<crate::NapiBench>,
+}
+
+/// Problem found while generating the type def, reported by `napi build --message-format json-diagnostic`
+#[derive(Debug, PartialEq, Eq)]
+pub struct TypeDefWarning {
+  pub code: &'static str,
+  pub message: String,
+  /// `(file, line_start, column_start, line_end, column_end)`, 1-based like the rustc diagnostics,
+  /// `None` if the compiler doesn't provide the locations to the proc macros
+  pub location: Option<(String, usize, usize, usize, usize)>,
 }
 
 thread_local! {
   static ALIAS: RefCell<HashMap<String, String>> = Default::default();
+  static WARNINGS: RefCell<Vec<TypeDefWarning>> = Default::default();
 }
 
 fn add_alias(name: String, alias: String) {
@@ -32,6 +57,11 @@ pub fn js_doc_from_comments(comments: &[String]) -> String {
   if comments.is_empty() {
     return "".to_owned();
   }
+  // `*/` in the comments would end the JSDoc
+  let comments = comments
+    .iter()
+    .map(|c| c.replace("*/", "*\\/"))
+    .collect::<Vec<_>>();
 
   if comments.len() == 1 {
     return format!("/**{} */\n", comments[0]);
@@ -47,6 +77,50 @@ pub fn js_doc_from_comments(comments: &[String]) -> String {
   )
 }
 
+/// The names of the fields which are not identifiers, like `kebab-case` of `#[napi(rename_all)]`, are quoted
+pub fn ts_property_name(name: &str) -> String {
+  let mut chars = name.chars();
+  let is_ident = matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_' || c == '$')
+    && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+  if is_ident {
+    name.to_owned()
+  } else {
+    format!("'{}'", name.replace('\\', "\\\\").replace('\'', "\\'"))
+  }
+}
+
+/// Attached to the type def of the `#[napi]` item being expanded
+pub fn add_warning(code: &'static str, span: Span, message: String) {
+  let (start, end) = (span.start(), span.end());
+  let location = span.local_file().filter(|_| start.line > 0).map(|file| {
+    (
+      // `escape_json` keeps the `\n` of the Windows paths as is
+      file.to_string_lossy().replace('\\', "/"),
+      start.line,
+      start.column + 1,
+      end.line,
+      end.column + 1,
+    )
+  });
+  let warning = TypeDefWarning {
+    code,
+    message,
+    location,
+  };
+  WARNINGS.with(|warnings| {
+    let mut warnings = warnings.borrow_mut();
+    // the return type is converted again for the `also_async` variant
+    if !warnings.contains(&warning) {
+      warnings.push(warning);
+    }
+  });
+}
+
+/// Warnings since the last call, attached to the type defs of each `#[napi]` item
+pub fn take_warnings() -> Vec<TypeDefWarning> {
+  WARNINGS.with(|warnings| warnings.take())
+}
+
 fn escape_json(src: &str) -> String {
   use std::fmt::Write;
   let mut escaped = String::with_capacity(src.len());
@@ -102,14 +176,84 @@ impl ToString for TypeDef {
     } else {
       "".to_owned()
     };
+    let generics = if let Some(generics) = &self.generics {
+      format!(", \"generics\": \"{}\"", generics)
+    } else {
+      "".to_owned()
+    };
+    let extends = if let Some(extends) = &self.extends {
+      format!(", \"extends\": \"{}\"", extends)
+    } else {
+      "".to_owned()
+    };
+    let warnings = if self.warnings.is_empty() {
+      "".to_owned()
+    } else {
+      format!(
+        ", \"warnings\": [{}]",
+        self
+          .warnings
+          .iter()
+          .map(|warning| warning.to_string())
+          .collect::<Vec<_>>()
+          .join(", ")
+      )
+    };
+    let benches = if self.benches.is_empty() {
+      "".to_owned()
+    } else {
+      format!(
+        ", \"benches\": [{}]",
+        self
+          .benches
+          .iter()
+          .map(|bench| format!(
+            r#"{{"name": "{}", "args": "{}"}}"#,
+            escape_json(&bench.name),
+            escape_json(&bench.args)
+          ))
+          .collect::<Vec<_>>()
+          .join(", ")
+      )
+    };
     format!(
-      r#"{{"kind": "{}", "name": "{}", "js_doc": "{}", "def": "{}"{}{}}}"#,
+      r#"{{"kind": "{}", "name": "{}", "js_doc": "{}", "def": "{}"{}{}{}{}{}{}}}"#,
       self.kind,
       self.name,
       escape_json(&self.js_doc),
       escape_json(&self.def),
       original_name,
+      generics,
+      extends,
       js_mod,
+      warnings,
+      benches,
+    )
+  }
+}
+
+impl Display for TypeDefWarning {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    let location = if let Some((file, line_start, column_start, line_end, column_end)) =
+      &self.location
+    {
+      format!(
+        r#"{{"file_name": "{}", "line_start": {}, "column_start": {}, "line_end": {}, "column_end": {}}}"#,
+        escape_json(file),
+        line_start,
+        column_start,
+        line_end,
+        column_end
+      )
+    } else {
+      "null".to_owned()
+    };
+    write!(
+      f,
+      r#"{{"code": "{}", "message": "{}", "location": {}}}"#,
+      self.code,
+      escape_json(&self.message),
+      location
     )
   }
 }
@@ -118,6 +262,22 @@ pub trait ToTypeDef {
   fn to_type_def(&self) -> Option<TypeDef>;
 }
 
+/// Nothing is exported by `#[napi(module_init)]` itself
+impl ToTypeDef for crate::NapiModuleInit {
+  fn to_type_def(&self) -> Option<TypeDef> {
+    None
+  }
+}
+
+/// `stream` is imported inline, so the d.ts doesn't need the `import` of it
+const READABLE: &str = "import('stream').Readable";
+
+/// `napi::allocator::AllocatorStats`, declared inline since it's not a `#[napi(object)]`
+const ALLOCATOR_STATS: &str = "{ allocator: 'mimalloc' | 'jemalloc' | 'system'; allocated?: number; active?: number; resident?: number }";
+
+/// `napi::net::FetchResponse`, declared inline since it's not a `#[napi(object)]`
+const FETCH_RESPONSE: &str = "{ status: number; statusText: string; url: string; headers: Record<string, string>; body: Buffer }";
+
 static KNOWN_TYPES: Lazy<HashMap<&'static str, &'static str>> = Lazy::new(|| {
   let mut map = HashMap::default();
   map.extend([
@@ -174,6 +334,8 @@ static KNOWN_TYPES: Lazy<HashMap<&'static str, &'static str>> = Lazy::new(|| {
     ("JsBuffer", "Buffer"),
     ("Buffer", "Buffer"),
     ("Vec", "Array<{}>"),
+    ("Columnar", "{}Columns"),
+    ("Reference", "{}"),
     ("Result", "Error | {}"),
     ("Either", "{} | {}"),
     ("Either3", "{} | {} | {}"),
@@ -189,15 +351,21 @@ static KNOWN_TYPES: Lazy<HashMap<&'static str, &'static str>> = Lazy::new(|| {
     ("JsFunction", "(...args: any[]) => any"),
     ("JsGlobal", "typeof global"),
     ("External", "ExternalObject<{}>"),
+    ("ReadableStream", READABLE),
+    ("AsyncIterator", "AsyncIterableIterator<{}>"),
+    ("FsTask", "Promise<{}>"),
+    ("FetchResponse", FETCH_RESPONSE),
+    ("Memoized", "{}"),
+    ("AllocatorStats", ALLOCATOR_STATS),
   ]);
 
   map
 });
 
-fn fill_ty(template: &str, args: Vec<String>) -> String {
+fn fill_ty(template: &str, args: Vec<String>) -> Option<String> {
   let matches = template.match_indices("{}").collect::<Vec<_>>();
   if args.len() != matches.len() {
-    return String::from("any");
+    return None;
   }
 
   let mut ret = String::from("");
@@ -209,7 +377,44 @@ fn fill_ty(template: &str, args: Vec<String>) -> String {
   });
 
   ret.push_str(&template[prev..]);
-  ret
+  Some(ret)
+}
+
+fn unsupported_ty(ty: &Type) -> (String, bool) {
+  add_warning(
+    "napi::unsupported_type",
+    ty.span(),
+    format!(
+      "`{}` is not supported by the type generation, it is typed as `any`, override it with `ts_type`, `ts_arg_type`, `ts_args_type` or `ts_return_type`",
+      ty.to_token_stream()
+    ),
+  );
+  ("any".to_owned(), false)
+}
+
+/// `Function<(A, B), R>` is `(arg0: A, arg1: B) => R`
+fn function_ts_type(arguments: &syn::PathArguments) -> String {
+  let mut tys = match arguments {
+    syn::PathArguments::AngleBracketed(arguments) => arguments
+      .args
+      .iter()
+      .filter_map(|arg| match arg {
+        syn::GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+      })
+      .collect::<Vec<_>>(),
+    _ => vec![],
+  }
+  .into_iter();
+  let args = match tys.next() {
+    Some(Type::Tuple(tuple)) => tuple.elems.iter().cloned().collect(),
+    Some(ty) => vec![ty.clone()],
+    None => vec![],
+  };
+  let ret = tys
+    .next()
+    .filter(|ty| !matches!(ty, Type::Tuple(tuple) if tuple.elems.is_empty()));
+  r#fn::gen_fn_type(&args, ret)
 }
 
 pub fn ty_to_ts_type(ty: &Type, is_return_ty: bool) -> (String, bool) {
@@ -268,17 +473,35 @@ pub fn ty_to_ts_type(ty: &Type, is_return_ty: bool) -> (String, bool) {
           });
         } else if let Some(&known_ty) = KNOWN_TYPES.get(rust_ty.as_str()) {
           if known_ty.contains("{}") {
-            ts_ty = Some((
-              fill_ty(known_ty, args.into_iter().map(|(arg, _)| arg).collect()),
-              false,
-            ));
+            ts_ty = fill_ty(known_ty, args.into_iter().map(|(arg, _)| arg).collect())
+              .map(|ty| (ty, false));
           } else {
             ts_ty = Some((known_ty.to_owned(), false));
           }
         } else if let Some(t) = crate::typegen::r#struct::CLASS_STRUCTS
           .with(|c| c.borrow_mut().get(rust_ty.as_str()).cloned())
         {
-          ts_ty = Some((t, false));
+          // the type arguments of the generic `#[napi(object)]`
+          if args.is_empty() {
+            ts_ty = Some((t, false));
+          } else {
+            ts_ty = Some((
+              format!(
+                "{}<{}>",
+                t,
+                args
+                  .into_iter()
+                  .map(|(arg, _)| arg)
+                  .collect::<Vec<_>>()
+                  .join(", ")
+              ),
+              false,
+            ));
+          }
+        } else if rust_ty == "Function" {
+          ts_ty = Some((function_ts_type(arguments), false));
+        } else if rust_ty == "Box" {
+          ts_ty = args.first().cloned();
         } else if rust_ty == "Promise" {
           ts_ty = Some((
             format!("Promise<{}>", args.first().map(|(arg, _)| arg).unwrap()),
@@ -296,9 +519,27 @@ pub fn ty_to_ts_type(ty: &Type, is_return_ty: bool) -> (String, bool) {
         }
       }
 
-      ts_ty.unwrap_or_else(|| ("any".to_owned(), false))
+      ts_ty.unwrap_or_else(|| unsupported_ty(ty))
     }
     Type::Group(g) => ty_to_ts_type(&g.elem, is_return_ty),
-    _ => ("any".to_owned(), false),
+    // `dyn Trait` of the `#[napi] trait`, the `Send` and `Sync` bounds are ignored
+    Type::TraitObject(trait_object) => trait_object
+      .bounds
+      .iter()
+      .find_map(|bound| match bound {
+        syn::TypeParamBound::Trait(bound) => bound
+          .path
+          .segments
+          .last()
+          .map(|segment| segment.ident.to_string())
+          .filter(|name| name != "Send" && name != "Sync"),
+        _ => None,
+      })
+      .map(|name| {
+        let ts_name = ALIAS.with(|aliases| aliases.borrow().get(name.as_str()).cloned());
+        (ts_name.unwrap_or(name), false)
+      })
+      .unwrap_or_else(|| unsupported_ty(ty)),
+    _ => unsupported_ty(ty),
   }
 }
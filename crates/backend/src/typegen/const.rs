@@ -14,6 +14,7 @@ impl ToTypeDef for NapiConst {
       kind: "const".to_owned(),
       name: self.js_name.to_owned(),
       original_name: Some(self.name.to_string()),
+      generics: None,
       def: format!(
         "export const {}: {}",
         &self.js_name,
@@ -21,6 +22,9 @@ impl ToTypeDef for NapiConst {
       ),
       js_mod: self.js_mod.to_owned(),
       js_doc: js_doc_from_comments(&self.comments),
+      extends: None,
+      warnings: vec![],
+      benches: vec![],
     })
   }
 }
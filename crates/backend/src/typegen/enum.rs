@@ -1,5 +1,5 @@
 use super::{add_alias, ToTypeDef, TypeDef};
-use crate::{js_doc_from_comments, NapiEnum};
+use crate::{js_doc_from_comments, ts_property_name, ty_to_ts_type, NapiEnum, NapiEnumValue};
 
 impl ToTypeDef for NapiEnum {
   fn to_type_def(&self) -> Option<TypeDef> {
@@ -9,18 +9,99 @@ impl ToTypeDef for NapiEnum {
 
     add_alias(self.name.to_string(), self.js_name.to_string());
 
+    if self.is_error {
+      return Some(TypeDef {
+        kind: "struct".to_owned(),
+        name: self.js_name.to_owned(),
+        original_name: Some(self.name.to_string()),
+        generics: None,
+        def: format!(
+          "readonly code: {}",
+          self
+            .variants
+            .iter()
+            .map(|v| v.val.to_ts_literal())
+            .collect::<Vec<_>>()
+            .join(" | ")
+        ),
+        js_doc: js_doc_from_comments(&self.comments),
+        extends: Some("Error".to_owned()),
+        warnings: vec![],
+        benches: vec![],
+        js_mod: self.js_mod.to_owned(),
+      });
+    }
+
+    if self.is_data_enum {
+      return Some(TypeDef {
+        kind: "type".to_owned(),
+        name: self.js_name.to_owned(),
+        original_name: Some(self.name.to_string()),
+        generics: None,
+        def: self.gen_ts_union(),
+        js_doc: js_doc_from_comments(&self.comments),
+        extends: None,
+        warnings: vec![],
+        benches: vec![],
+        js_mod: self.js_mod.to_owned(),
+      });
+    }
+
     Some(TypeDef {
       kind: "enum".to_owned(),
       name: self.js_name.to_owned(),
       original_name: Some(self.name.to_string()),
+      generics: None,
       def: self.gen_ts_variants(),
       js_doc: js_doc_from_comments(&self.comments),
+      extends: None,
+      warnings: vec![],
+      benches: vec![],
       js_mod: self.js_mod.to_owned(),
     })
   }
 }
 
 impl NapiEnum {
+  /// The classes of the variants of `#[napi(error)]`, extending the enum class
+  pub fn error_class_type_defs(&self) -> Vec<TypeDef> {
+    if !self.is_error || self.skip_typescript {
+      return vec![];
+    }
+    self
+      .variants
+      .iter()
+      .map(|v| {
+        let members = std::iter::once(format!("readonly code: {}", v.val.to_ts_literal()))
+          .chain(v.fields.iter().filter(|f| !f.skip_typescript).map(|f| {
+            let (ty, is_optional) = ty_to_ts_type(&f.ty, false);
+            let ty = f.ts_type.clone().unwrap_or(ty);
+            format!(
+              "{}{}{}: {}",
+              js_doc_from_comments(&f.comments),
+              ts_property_name(&f.js_name),
+              if is_optional { "?" } else { "" },
+              ty
+            )
+          }))
+          .collect::<Vec<_>>()
+          .join("\n");
+        TypeDef {
+          kind: "struct".to_owned(),
+          name: v.error_class.clone().unwrap_or_default(),
+          original_name: None,
+          generics: None,
+          def: members,
+          js_doc: js_doc_from_comments(&v.comments),
+          extends: Some(self.js_name.to_owned()),
+          warnings: vec![],
+          benches: vec![],
+          js_mod: self.js_mod.to_owned(),
+        }
+      })
+      .collect()
+  }
+
   fn gen_ts_variants(&self) -> String {
     self
       .variants
@@ -30,10 +111,46 @@ impl NapiEnum {
           "{}{} = {}",
           js_doc_from_comments(&v.comments),
           v.name,
-          v.val,
+          v.val.to_ts_literal()
         )
       })
       .collect::<Vec<_>>()
       .join(",\n ")
   }
+
+  /// `| { type: 'Variant'; field: T }` of each variant
+  fn gen_ts_union(&self) -> String {
+    self
+      .variants
+      .iter()
+      .map(|v| {
+        let members = std::iter::once(format!("type: {}", v.val.to_ts_literal()))
+          .chain(v.fields.iter().filter(|f| !f.skip_typescript).map(|f| {
+            let (ty, is_optional) = ty_to_ts_type(&f.ty, false);
+            let ty = f.ts_type.clone().unwrap_or(ty);
+            format!(
+              "{}{}: {}",
+              ts_property_name(&f.js_name),
+              if is_optional { "?" } else { "" },
+              ty
+            )
+          }))
+          .collect::<Vec<_>>()
+          .join("; ");
+        format!("{}| {{ {} }}", js_doc_from_comments(&v.comments), members)
+      })
+      .collect::<Vec<_>>()
+      .join("\n")
+  }
+}
+
+impl NapiEnumValue {
+  fn to_ts_literal(&self) -> String {
+    match self {
+      NapiEnumValue::Number(val) => val.to_string(),
+      NapiEnumValue::String(val) => {
+        format!("'{}'", val.replace('\\', "\\\\").replace('\'', "\\'"))
+      }
+    }
+  }
 }
@@ -3,16 +3,21 @@ use quote::ToTokens;
 use std::fmt::{Display, Formatter};
 use syn::Pat;
 
-use super::{ty_to_ts_type, ToTypeDef, TypeDef};
-use crate::{js_doc_from_comments, CallbackArg, FnKind, NapiFn};
+use super::{ty_to_ts_type, ToTypeDef, TypeDef, READABLE};
+use crate::{
+  extract_this_ty, is_this_reference, js_doc_from_comments, CallbackArg, FnExecution, FnKind,
+  NapiFn,
+};
 
-struct FnArg {
-  arg: String,
-  ts_type: String,
-  is_optional: bool,
+pub(crate) struct FnArg {
+  pub(crate) arg: String,
+  pub(crate) ts_type: String,
+  pub(crate) is_optional: bool,
+  // `...arg: T[]` of the `#[napi(variadic)]` param
+  pub(crate) is_rest: bool,
 }
 
-struct FnArgList {
+pub(crate) struct FnArgList {
   args: Vec<FnArg>,
   last_required: Option<usize>,
 }
@@ -27,7 +32,9 @@ impl Display for FnArgList {
         && self
           .last_required
           .map_or(true, |last_required| i > last_required);
-      if is_optional {
+      if arg.is_rest {
+        write!(f, "...{}: {}", arg.arg, arg.ts_type)?;
+      } else if is_optional {
         write!(f, "{}?: {}", arg.arg, arg.ts_type)?;
       } else {
         write!(f, "{}: {}", arg.arg, arg.ts_type)?;
@@ -59,9 +66,10 @@ impl ToTypeDef for NapiFn {
     }
 
     let def = format!(
-      r#"{prefix} {name}({args}){ret}"#,
+      r#"{prefix} {name}{generics}({args}){ret}"#,
       prefix = self.gen_ts_func_prefix(),
       name = &self.js_name,
+      generics = self.gen_ts_generics(),
       args = self
         .ts_args_type
         .clone()
@@ -77,18 +85,98 @@ impl ToTypeDef for NapiFn {
       kind: "fn".to_owned(),
       name: self.js_name.clone(),
       original_name: None,
+      generics: None,
       def,
       js_mod: self.js_mod.to_owned(),
-      js_doc: js_doc_from_comments(&self.comments),
+      js_doc: self.gen_js_doc(),
+      extends: None,
+      warnings: vec![],
+      benches: self.benches.clone(),
+    })
+  }
+}
+
+impl NapiFn {
+  /// Type def of the promise returning variant generated by `#[napi(also_async = "...")]`
+  pub fn also_async_type_def(&self) -> Option<TypeDef> {
+    if self.skip_typescript {
+      return None;
+    }
+
+    self.also_async.as_ref().map(|also_async| {
+      let ret = self
+        .ts_return_type
+        .clone()
+        .unwrap_or_else(|| self.gen_ts_ret_type());
+      TypeDef {
+        kind: "fn".to_owned(),
+        name: also_async.clone(),
+        original_name: None,
+        generics: None,
+        def: format!(
+          r#"{prefix} {name}{generics}({args}): Promise<{ret}>"#,
+          prefix = self.gen_ts_func_prefix(),
+          name = also_async,
+          generics = self.gen_ts_generics(),
+          args = self
+            .ts_args_type
+            .clone()
+            .unwrap_or_else(|| self.gen_ts_func_args()),
+          ret = ret,
+        ),
+        js_mod: self.js_mod.to_owned(),
+        js_doc: self.gen_js_doc(),
+        extends: None,
+        warnings: vec![],
+        benches: vec![],
+      }
+    })
+  }
+
+  /// Type def of the static method defined by `#[napi(static_method = Class)]`, merged into the class
+  pub fn static_method_type_def(&self) -> Option<TypeDef> {
+    if self.skip_typescript {
+      return None;
+    }
+
+    self.static_method.as_ref().map(|static_method| {
+      let method = NapiFn {
+        parent: Some(static_method.class.clone()),
+        static_method: None,
+        ..self.clone()
+      };
+      let def = method
+        .to_type_def()
+        .into_iter()
+        .chain(method.also_async_type_def())
+        .map(|type_def| format!("{}{}", type_def.js_doc, type_def.def))
+        .collect::<Vec<_>>()
+        .join("\\n");
+      TypeDef {
+        kind: "impl".to_owned(),
+        name: static_method.js_name.clone(),
+        original_name: None,
+        generics: None,
+        def,
+        js_mod: static_method.js_mod.clone(),
+        js_doc: "".to_owned(),
+        extends: None,
+        warnings: vec![],
+        benches: vec![],
+      }
     })
   }
 }
 
 fn gen_callback_type(callback: &CallbackArg) -> String {
+  gen_fn_type(&callback.args, callback.ret.as_ref())
+}
+
+/// `(arg0: A, arg1: B) => R` of the JavaScript functions called from Rust, `void` if `ret` is `None`
+pub(crate) fn gen_fn_type(args: &[syn::Type], ret: Option<&syn::Type>) -> String {
   format!(
     "({args}) => {ret}",
-    args = &callback
-      .args
+    args = args
       .iter()
       .enumerate()
       .map(|(i, arg)| {
@@ -97,10 +185,11 @@ fn gen_callback_type(callback: &CallbackArg) -> String {
           arg: format!("arg{}", i),
           ts_type,
           is_optional,
+          is_rest: false,
         }
       })
       .collect::<FnArgList>(),
-    ret = match &callback.ret {
+    ret = match ret {
       Some(ty) => ty_to_ts_type(ty, true).0,
       None => "void".to_owned(),
     }
@@ -108,40 +197,82 @@ fn gen_callback_type(callback: &CallbackArg) -> String {
 }
 
 impl NapiFn {
+  fn gen_ts_generics(&self) -> String {
+    if self.type_params.is_empty() {
+      return String::new();
+    }
+    format!(
+      "<{}>",
+      self
+        .type_params
+        .iter()
+        .map(|param| param.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+    )
+  }
+
   fn gen_ts_func_args(&self) -> String {
     format!(
       "{}",
       self
         .args
         .iter()
-        .filter_map(|arg| match arg {
+        .filter_map(|fn_arg| match &fn_arg.kind {
           crate::NapiFnArgKind::PatType(path) => {
             let ty_string = path.ty.to_token_stream().to_string();
-            if ty_string == "Env" || ty_string.replace(' ', "").starts_with("Reference<") {
+            if ty_string == "Env"
+              || (self.fn_self.is_some() && is_this_reference(&path.ty, self.parent.as_ref()))
+            {
               return None;
             }
+            if let Some(this_ty) = extract_this_ty(&path.ty) {
+              return Some(FnArg {
+                arg: "this".to_owned(),
+                ts_type: fn_arg
+                  .ts_arg_type
+                  .clone()
+                  .unwrap_or_else(|| ty_to_ts_type(&this_ty, false).0),
+                is_optional: false,
+                is_rest: false,
+              });
+            }
             let mut path = path.clone();
             // remove mutability from PatIdent
             if let Pat::Ident(i) = path.pat.as_mut() {
               i.mutability = None;
             }
             let arg = path.pat.to_token_stream().to_string().to_case(Case::Camel);
-            let (ts_type, is_optional) = ty_to_ts_type(&path.ty, false);
+            let (ts_type, is_optional) = match &fn_arg.default {
+              // `undefined` and `null` are replaced by the default
+              Some(_) => {
+                let ty = &path.ty;
+                ty_to_ts_type(&syn::parse_quote!(Option<#ty>), false)
+              }
+              None => ty_to_ts_type(&path.ty, false),
+            };
+            let ts_type = fn_arg.ts_arg_type.clone().unwrap_or(ts_type);
 
             Some(FnArg {
               arg,
               ts_type,
-              is_optional,
+              // the rest param doesn't make the params before it required
+              is_optional: is_optional || fn_arg.variadic,
+              is_rest: fn_arg.variadic,
             })
           }
           crate::NapiFnArgKind::Callback(cb) => {
             let arg = cb.pat.to_token_stream().to_string().to_case(Case::Camel);
-            let ts_type = gen_callback_type(cb);
+            let ts_type = fn_arg
+              .ts_arg_type
+              .clone()
+              .unwrap_or_else(|| gen_callback_type(cb));
 
             Some(FnArg {
               arg,
               ts_type,
               is_optional: false,
+              is_rest: false,
             })
           }
         })
@@ -149,6 +280,33 @@ impl NapiFn {
     )
   }
 
+  /// The doc comments, followed by the `@param` of the params with `#[napi(default)]`
+  fn gen_js_doc(&self) -> String {
+    let mut comments = self.comments.clone();
+    for arg in self.args.iter() {
+      if let (crate::NapiFnArgKind::PatType(path), Some(default)) = (&arg.kind, &arg.default) {
+        let mut pat = path.pat.clone();
+        if let Pat::Ident(i) = pat.as_mut() {
+          i.mutability = None;
+        }
+        let name = pat.to_token_stream().to_string().to_case(Case::Camel);
+        // the other expressions are only meaningful in Rust
+        comments.push(match default {
+          syn::Expr::Lit(lit) => format!(
+            " @param {} - `{}` if it's `undefined` or `null`",
+            name,
+            lit.to_token_stream()
+          ),
+          _ => format!(
+            " @param {} - has a default if it's `undefined` or `null`",
+            name
+          ),
+        });
+      }
+    }
+    js_doc_from_comments(&comments)
+  }
+
   fn gen_ts_func_prefix(&self) -> &'static str {
     if self.parent.is_some() {
       match self.kind {
@@ -172,23 +330,19 @@ impl NapiFn {
       FnKind::Factory => self
         .parent
         .clone()
-        .map(|i| format!(": {}", i.to_string().to_case(Case::Pascal)))
-        .unwrap_or_else(|| "".to_owned()),
-      _ => {
-        let ret = if let Some(ret) = &self.ret {
-          let (ts_type, _) = ty_to_ts_type(ret, true);
-          if ts_type == "undefined" {
-            "void".to_owned()
-          } else if ts_type == "Self" {
-            "this".to_owned()
+        .map(|i| {
+          let class = i.to_string().to_case(Case::Pascal);
+          if self.is_async {
+            format!(": Promise<{}>", class)
           } else {
-            ts_type
+            format!(": {}", class)
           }
-        } else {
-          "void".to_owned()
-        };
+        })
+        .unwrap_or_else(|| "".to_owned()),
+      _ => {
+        let ret = self.gen_ts_ret_type();
 
-        if self.is_async {
+        if self.is_async || self.execution != FnExecution::JsThread {
           format!(": Promise<{}>", ret)
         } else {
           format!(": {}", ret)
@@ -196,4 +350,48 @@ impl NapiFn {
       }
     }
   }
+
+  fn gen_ts_ret_type(&self) -> String {
+    if self.readable_stream.is_some() {
+      return READABLE.to_owned();
+    }
+    if self.async_iterator {
+      let item = self
+        .ret
+        .as_ref()
+        .and_then(stream_item)
+        .map_or_else(|| "unknown".to_owned(), |item| ty_to_ts_type(item, true).0);
+      return format!("AsyncIterableIterator<{}>", item);
+    }
+    if let Some(ret) = &self.ret {
+      let (ts_type, _) = ty_to_ts_type(ret, true);
+      if ts_type == "undefined" {
+        "void".to_owned()
+      } else if ts_type == "Self" {
+        "this".to_owned()
+      } else {
+        ts_type
+      }
+    } else {
+      "void".to_owned()
+    }
+  }
+}
+
+/// `Result<T>` of the returned `impl Stream<Item = Result<T>>`
+fn stream_item(ty: &syn::Type) -> Option<&syn::Type> {
+  let bounds = match ty {
+    syn::Type::ImplTrait(impl_trait) => &impl_trait.bounds,
+    _ => return None,
+  };
+  bounds.iter().find_map(|bound| match bound {
+    syn::TypeParamBound::Trait(bound) => match &bound.path.segments.last()?.arguments {
+      syn::PathArguments::AngleBracketed(args) => args.args.iter().find_map(|arg| match arg {
+        syn::GenericArgument::Binding(binding) if binding.ident == "Item" => Some(&binding.ty),
+        _ => None,
+      }),
+      _ => None,
+    },
+    _ => None,
+  })
 }
@@ -2,7 +2,10 @@ use std::cell::RefCell;
 use std::collections::HashMap;
 
 use super::{add_alias, ToTypeDef, TypeDef};
-use crate::{js_doc_from_comments, ty_to_ts_type, NapiImpl, NapiStruct, NapiStructKind};
+use crate::{
+  js_doc_from_comments, ts_property_name, ty_to_ts_type, ModuleFunction, NapiFn, NapiImpl,
+  NapiStruct, NapiStructKind,
+};
 
 thread_local! {
   pub(crate) static TASK_STRUCTS: RefCell<HashMap<String, String>> = Default::default();
@@ -17,17 +20,28 @@ impl ToTypeDef for NapiStruct {
     });
     add_alias(self.name.to_string(), self.js_name.to_string());
 
+    let is_tuple_object = self.kind == NapiStructKind::Object && self.is_tuple;
     Some(TypeDef {
-      kind: String::from(if self.kind == NapiStructKind::Object {
+      kind: String::from(if is_tuple_object {
+        "type"
+      } else if self.kind == NapiStructKind::Object {
         "interface"
       } else {
         "struct"
       }),
       name: self.js_name.to_owned(),
       original_name: Some(self.name.to_string()),
-      def: self.gen_ts_class(),
+      generics: self.gen_ts_generics(),
+      def: if is_tuple_object {
+        self.gen_ts_tuple()
+      } else {
+        self.gen_ts_class()
+      },
       js_mod: self.js_mod.to_owned(),
       js_doc: js_doc_from_comments(&self.comments),
+      extends: self.parent.as_ref().map(|parent| parent.ts_name.clone()),
+      warnings: vec![],
+      benches: vec![],
     })
   }
 }
@@ -41,34 +55,132 @@ impl ToTypeDef for NapiImpl {
       });
     }
 
+    let mut defs = self
+      .items
+      .iter()
+      .filter_map(|f| {
+        if f.skip_typescript || f.module_function == Some(ModuleFunction::Only) {
+          None
+        } else {
+          let mut def = f.to_type_def().map_or(String::default(), |type_def| {
+            format!("{}{}", type_def.js_doc, type_def.def)
+          });
+          if let Some(type_def) = f.also_async_type_def() {
+            def.push_str(&format!("\\n{}{}", type_def.js_doc, type_def.def));
+          }
+          Some(def)
+        }
+      })
+      .collect::<Vec<_>>();
+    if let Some(item) = &self.iterator_item {
+      let item = ty_to_ts_type(item, false).0;
+      defs.push(format!("next(): IteratorResult<{}, void>", item));
+      defs.push(format!("[Symbol.iterator](): Iterator<{}, void>", item));
+    }
+
     Some(TypeDef {
       kind: "impl".to_owned(),
       name: self.js_name.to_owned(),
       original_name: None,
-      def: self
-        .items
-        .iter()
-        .filter_map(|f| {
-          if f.skip_typescript {
-            None
-          } else {
-            Some(format!(
-              "{}{}",
-              js_doc_from_comments(&f.comments),
-              f.to_type_def()
-                .map_or(String::default(), |type_def| type_def.def)
-            ))
-          }
-        })
-        .collect::<Vec<_>>()
-        .join("\\n"),
+      generics: None,
+      def: defs.join("\\n"),
       js_mod: self.js_mod.to_owned(),
       js_doc: "".to_string(),
+      extends: None,
+      warnings: vec![],
+      benches: vec![],
     })
   }
 }
 
+impl NapiImpl {
+  /// Type defs of the module level functions exported by `#[napi(module_function)]`
+  pub fn module_function_type_defs(&self) -> Vec<TypeDef> {
+    self
+      .items
+      .iter()
+      .filter(|f| f.module_function.is_some())
+      .flat_map(|f| {
+        let module_fn = NapiFn {
+          parent: None,
+          ..f.clone()
+        };
+        module_fn
+          .to_type_def()
+          .into_iter()
+          .chain(module_fn.also_async_type_def())
+      })
+      .collect()
+  }
+}
+
 impl NapiStruct {
+  fn gen_ts_generics(&self) -> Option<String> {
+    let params = self
+      .generics
+      .type_params()
+      .map(|param| param.ident.to_string())
+      .collect::<Vec<_>>();
+    if params.is_empty() {
+      None
+    } else {
+      Some(format!("<{}>", params.join(", ")))
+    }
+  }
+
+  /// The `ColumnarRows` returned for `Columnar<Self>` of `#[napi(object, columnar)]`
+  pub fn columnar_type_def(&self) -> Option<TypeDef> {
+    if !self.columnar {
+      return None;
+    }
+    let columns = self
+      .fields
+      .iter()
+      .map(|f| {
+        let column_ty = crate::codegen::columnar_typed_array(&f.ty)
+          .map(|ty| ty.to_owned())
+          .unwrap_or_else(|| format!("Array<{}>", ty_to_ts_type(&f.ty, false).0));
+        format!("{}: {}", f.js_name, column_ty)
+      })
+      .collect::<Vec<_>>()
+      .join("; ");
+    let row = &self.js_name;
+
+    Some(TypeDef {
+      kind: "interface".to_owned(),
+      name: format!("{}Columns", row),
+      original_name: None,
+      generics: None,
+      def: [
+        "readonly length: number".to_owned(),
+        format!("readonly columns: {{ {} }}", columns),
+        format!("get(index: number): {} | undefined", row),
+        format!("toArray(): Array<{}>", row),
+        format!("[Symbol.iterator](): Iterator<{}>", row),
+      ]
+      .join("\\n"),
+      js_mod: self.js_mod.to_owned(),
+      js_doc: String::new(),
+      extends: None,
+      warnings: vec![],
+      benches: vec![],
+    })
+  }
+
+  /// `[A, B]` of `#[napi(object)] struct Pair(A, B)`
+  fn gen_ts_tuple(&self) -> String {
+    let elems = self
+      .fields
+      .iter()
+      .map(|f| {
+        f.ts_type
+          .clone()
+          .unwrap_or_else(|| ty_to_ts_type(&f.ty, false).0)
+      })
+      .collect::<Vec<_>>();
+    format!("[{}]", elems.join(", "))
+  }
+
   fn gen_ts_class(&self) -> String {
     let mut ctor_args = vec![];
     let def = self
@@ -90,11 +202,17 @@ impl NapiStruct {
           field_str.push_str("readonly ")
         }
 
-        let (arg, is_optional) = ty_to_ts_type(&f.ty, false);
+        let (arg, is_optional) = match nullable_ty(&f.ty) {
+          // required, but could be `null`
+          Some(ty) if self.use_nullable => {
+            (format!("{} | null", ty_to_ts_type(ty, false).0), false)
+          }
+          _ => ty_to_ts_type(&f.ty, false),
+        };
         let arg = f.ts_type.as_ref().map(|ty| ty.to_string()).unwrap_or(arg);
 
         let sep = if is_optional { "?" } else { "" };
-        let arg = format!("{}{}: {}", &f.js_name, sep, arg);
+        let arg = format!("{}{}: {}", ts_property_name(&f.js_name), sep, arg);
         if self.kind == NapiStructKind::Constructor {
           ctor_args.push(arg.clone());
         }
@@ -105,10 +223,40 @@ impl NapiStruct {
       .collect::<Vec<_>>()
       .join("\\n");
 
-    if self.kind == NapiStructKind::Constructor {
+    let mut def = if self.kind == NapiStructKind::Constructor {
       format!("{}\\nconstructor({})", def, ctor_args.join(", "))
     } else {
       def
+    };
+    // `[util.inspect.custom]()` of `#[napi(inspect)]` is not declared, it's only called by `util.inspect`
+    for (enabled, method) in [
+      (self.to_string, "toString(): string"),
+      (self.to_json, "toJSON(): unknown"),
+    ] {
+      if enabled {
+        if !def.is_empty() {
+          def.push_str("\\n");
+        }
+        def.push_str(method);
+      }
     }
+    def
+  }
+}
+
+/// `T` of the `Option<T>` field
+fn nullable_ty(ty: &syn::Type) -> Option<&syn::Type> {
+  match ty {
+    syn::Type::Path(syn::TypePath { path, .. }) => match path.segments.last() {
+      Some(segment) if segment.ident == "Option" => match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) => match args.args.first() {
+          Some(syn::GenericArgument::Type(ty)) => Some(ty),
+          _ => None,
+        },
+        _ => None,
+      },
+      _ => None,
+    },
+    _ => None,
   }
 }
@@ -0,0 +1,62 @@
+use super::r#fn::{FnArg, FnArgList};
+use super::{add_alias, ty_to_ts_type, ToTypeDef, TypeDef};
+use crate::{js_doc_from_comments, NapiTrait};
+
+impl ToTypeDef for NapiTrait {
+  fn to_type_def(&self) -> Option<TypeDef> {
+    if self.skip_typescript {
+      return None;
+    }
+
+    add_alias(self.name.to_string(), self.js_name.to_string());
+
+    Some(TypeDef {
+      kind: "interface".to_owned(),
+      name: self.js_name.to_owned(),
+      original_name: Some(self.name.to_string()),
+      generics: None,
+      def: self.gen_ts_methods(),
+      js_doc: js_doc_from_comments(&self.comments),
+      extends: None,
+      warnings: vec![],
+      benches: vec![],
+      js_mod: self.js_mod.to_owned(),
+    })
+  }
+}
+
+impl NapiTrait {
+  fn gen_ts_methods(&self) -> String {
+    self
+      .methods
+      .iter()
+      .map(|method| {
+        let args = method
+          .args
+          .iter()
+          .map(|(arg, ty)| {
+            let (ts_type, is_optional) = ty_to_ts_type(ty, false);
+            FnArg {
+              arg: arg.to_owned(),
+              ts_type,
+              is_optional,
+              is_rest: false,
+            }
+          })
+          .collect::<FnArgList>();
+        let ret = match &method.ret {
+          Some(ret) => ty_to_ts_type(ret, true).0,
+          None => "void".to_owned(),
+        };
+        format!(
+          "{}{}({}): {}",
+          js_doc_from_comments(&method.comments),
+          method.js_name,
+          args,
+          ret
+        )
+      })
+      .collect::<Vec<_>>()
+      .join("\n")
+  }
+}
@@ -12,7 +12,7 @@ fn copy_node_lib(arch: &str) -> Vec<u8> {
   }
 }
 
-pub fn setup() {
+fn bundled_node_lib() -> PathBuf {
   let out_dir = env::var("OUT_DIR").expect("OUT_DIR is not set");
 
   // NPM also gives us an arch var, but let's trust cargo more.
@@ -36,7 +36,6 @@ pub fn setup() {
     .expect("Failed to determine target arch");
 
   let mut node_lib_file_path = PathBuf::from(out_dir);
-  let link_search_dir = node_lib_file_path.clone();
 
   // Encode arch to detect and require node.lib.
   let node_lib_file_name = format!("node-{arch}.lib", arch = arch,);
@@ -52,6 +51,18 @@ pub fn setup() {
     ));
   }
 
+  node_lib_file_path
+}
+
+pub fn setup() {
+  println!("cargo:rerun-if-env-changed=NAPI_RS_NODE_LIB_DIR");
+  // `node.lib` of the custom Node.js headers, passed in by `napi build --node-headers`
+  let node_lib_file_path = match env::var("NAPI_RS_NODE_LIB_DIR") {
+    Ok(node_lib_dir) => PathBuf::from(node_lib_dir).join("node.lib"),
+    Err(_) => bundled_node_lib(),
+  };
+  let link_search_dir = node_lib_file_path.parent().unwrap();
+
   println!(
     "cargo:rustc-link-lib={}",
     node_lib_file_path.file_stem().unwrap().to_str().unwrap()
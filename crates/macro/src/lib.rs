@@ -11,16 +11,11 @@ extern crate quote;
 
 #[cfg(not(feature = "noop"))]
 use std::env;
-#[cfg(all(feature = "type-def", not(feature = "noop")))]
-use std::{
-  fs,
-  io::{BufWriter, Result as IOResult, Write},
-};
 
+#[cfg(all(feature = "type-def", not(feature = "noop")))]
+use napi_derive_backend::metadata::write_type_defs;
 #[cfg(not(feature = "noop"))]
 use napi_derive_backend::{BindgenResult, TryToTokens};
-#[cfg(all(feature = "type-def", not(feature = "noop")))]
-use napi_derive_backend::{ToTypeDef, TypeDef};
 #[cfg(not(feature = "noop"))]
 use parser::{attrs::BindgenAttrs, ParseNapi};
 use proc_macro::TokenStream as RawStream;
@@ -42,7 +37,8 @@ use syn::{Attribute, Item};
 #[cfg(not(feature = "noop"))]
 #[proc_macro_attribute]
 pub fn napi(attr: RawStream, input: RawStream) -> RawStream {
-  match expand(attr.into(), input.into()) {
+  let input: TokenStream = input.into();
+  match expand(attr.into(), input.clone()) {
     Ok(tokens) => {
       if env::var("DEBUG_GENERATED_CODE").is_ok() {
         println!("{}", tokens);
@@ -52,7 +48,13 @@ pub fn napi(attr: RawStream, input: RawStream) -> RawStream {
     Err(diagnostic) => {
       println!("`napi` macro expand failed.");
 
-      (quote! { #diagnostic }).into()
+      // keep the item without the `#[napi]` attributes, so IDEs still resolve it and
+      // the code using it doesn't fail with more errors than the one of the macro
+      let item = syn::parse2::<Item>(input).ok().map(|mut item| {
+        strip_napi_attrs(&mut item);
+        item
+      });
+      (quote! { #diagnostic #item }).into()
     }
   }
 }
@@ -68,91 +70,144 @@ fn expand(attr: TokenStream, input: TokenStream) -> BindgenResult<TokenStream> {
   let mut item = syn::parse2::<syn::Item>(input)?;
   let opts: BindgenAttrs = syn::parse2(attr)?;
   let mut tokens = proc_macro2::TokenStream::new();
-  if let Item::Mod(mut js_mod) = item {
+  if let Item::Mod(js_mod) = item {
     let js_name = opts.js_name().map_or_else(
       || js_mod.ident.to_string(),
       |(js_name, _)| js_name.to_owned(),
     );
-    if let Some((_, mut items)) = js_mod.content.clone() {
-      for item in items.iter_mut() {
-        let mut empty_attrs = vec![];
-        if let Some(item_opts) = replace_napi_attr_in_mod(
-          js_name.clone(),
-          match item {
-            syn::Item::Fn(ref mut function) => &mut function.attrs,
-            syn::Item::Struct(ref mut struct_) => &mut struct_.attrs,
-            syn::Item::Enum(ref mut enum_) => &mut enum_.attrs,
-            syn::Item::Const(ref mut const_) => &mut const_.attrs,
-            syn::Item::Impl(ref mut impl_) => &mut impl_.attrs,
-            syn::Item::Mod(mod_) => {
-              let mod_in_mod = mod_
-                .attrs
-                .iter()
-                .enumerate()
-                .find(|(_, m)| m.path.segments[0].ident == "napi");
-              if mod_in_mod.is_some() {
-                bail_span!(
-                  mod_,
-                  "napi module cannot be nested under another napi module"
-                );
-              } else {
-                &mut empty_attrs
-              }
-            }
-            _ => &mut empty_attrs,
-          },
-        ) {
-          let napi = item.parse_napi(&mut tokens, item_opts)?;
-          napi.try_to_tokens(&mut tokens)?;
-          #[cfg(feature = "type-def")]
-          if let Ok(type_def_file) = env::var("TYPE_DEF_TMP_PATH") {
-            if let Err(e) = output_type_def(type_def_file, napi.to_type_def()) {
-              println!("Failed to write type def file: {:?}", e);
-            };
-          }
-        } else {
-          item.to_tokens(&mut tokens);
-        };
-      }
-      js_mod.content = None;
-    };
-    let js_mod_attrs: Vec<Attribute> = js_mod
-      .attrs
-      .clone()
-      .into_iter()
-      .filter(|attr| attr.path.segments[0].ident != "napi")
-      .collect();
-    let mod_name = js_mod.ident;
-    let visible = js_mod.vis;
-    let mod_tokens = quote! { #(#js_mod_attrs)* #visible mod #mod_name { #tokens } };
-    Ok(mod_tokens)
+    expand_mod(js_name, js_mod)
   } else {
     let napi = item.parse_napi(&mut tokens, opts)?;
     napi.try_to_tokens(&mut tokens)?;
 
     #[cfg(feature = "type-def")]
-    if let Ok(type_def_file) = env::var("TYPE_DEF_TMP_PATH") {
-      if let Err(e) = output_type_def(type_def_file, napi.to_type_def()) {
-        println!("Failed to write type def file: {:?}", e);
-      };
+    if let Err(e) = write_type_defs(&napi.type_defs()) {
+      println!("Failed to write type def file: {:?}", e);
     }
     Ok(tokens)
   }
 }
 
-#[cfg(all(feature = "type-def", not(feature = "noop")))]
-fn output_type_def(type_def_file: String, type_def: Option<TypeDef>) -> IOResult<()> {
-  if type_def.is_some() {
-    let file = fs::OpenOptions::new()
-      .append(true)
-      .create(true)
-      .open(type_def_file)?;
+/// The items of `#[napi] mod`s are registered under the `js_name` of the mod,
+/// the `#[napi] mod`s in it are the nested namespaces.
+#[cfg(not(feature = "noop"))]
+fn expand_mod(js_name: String, mut js_mod: syn::ItemMod) -> BindgenResult<TokenStream> {
+  let mut tokens = proc_macro2::TokenStream::new();
+  if let Some((_, mut items)) = js_mod.content.clone() {
+    for item in items.iter_mut() {
+      let mut empty_attrs = vec![];
+      if let Some(item_opts) = replace_napi_attr_in_mod(
+        js_name.clone(),
+        match item {
+          syn::Item::Fn(ref mut function) => &mut function.attrs,
+          syn::Item::Struct(ref mut struct_) => &mut struct_.attrs,
+          syn::Item::Enum(ref mut enum_) => &mut enum_.attrs,
+          syn::Item::Const(ref mut const_) => &mut const_.attrs,
+          syn::Item::Static(ref mut static_) => &mut static_.attrs,
+          syn::Item::Impl(ref mut impl_) => &mut impl_.attrs,
+          syn::Item::Trait(ref mut trait_) => &mut trait_.attrs,
+          syn::Item::Mod(mod_) => {
+            if let Some(index) = mod_
+              .attrs
+              .iter()
+              .position(|m| m.path.segments[0].ident == "napi")
+            {
+              // the nested namespace `outer.inner`
+              let napi_attr = mod_.attrs.remove(index);
+              let mod_opts: BindgenAttrs = if napi_attr.tokens.is_empty() {
+                syn::parse2(quote! {})?
+              } else {
+                napi_attr.parse_args()?
+              };
+              let nested_js_name = mod_opts
+                .js_name()
+                .map_or_else(|| mod_.ident.to_string(), |(js_name, _)| js_name.to_owned());
+              expand_mod(format!("{}.{}", js_name, nested_js_name), mod_.clone())?
+                .to_tokens(&mut tokens);
+              continue;
+            }
+            &mut empty_attrs
+          }
+          _ => &mut empty_attrs,
+        },
+      ) {
+        let napi = item.parse_napi(&mut tokens, item_opts)?;
+        napi.try_to_tokens(&mut tokens)?;
+        #[cfg(feature = "type-def")]
+        if let Err(e) = write_type_defs(&napi.type_defs()) {
+          println!("Failed to write type def file: {:?}", e);
+        }
+      } else {
+        item.to_tokens(&mut tokens);
+      };
+    }
+    js_mod.content = None;
+  };
+  let js_mod_attrs: Vec<Attribute> = js_mod
+    .attrs
+    .clone()
+    .into_iter()
+    .filter(|attr| attr.path.segments[0].ident != "napi")
+    .collect();
+  let mod_name = js_mod.ident;
+  let visible = js_mod.vis;
+  let mod_tokens = quote! { #(#js_mod_attrs)* #visible mod #mod_name { #tokens } };
+  Ok(mod_tokens)
+}
 
-    let mut writer = BufWriter::<fs::File>::new(file);
-    writer.write_all(type_def.unwrap().to_string().as_bytes())?;
-    writer.write_all("\n".as_bytes())
-  } else {
-    IOResult::Ok(())
+#[cfg(not(feature = "noop"))]
+fn strip_napi_attrs(item: &mut Item) {
+  fn retain(attrs: &mut Vec<Attribute>) {
+    attrs.retain(|attr| attr.path.segments[0].ident != "napi");
+  }
+
+  fn retain_args(sig: &mut syn::Signature) {
+    for arg in sig.inputs.iter_mut() {
+      if let syn::FnArg::Typed(p) = arg {
+        retain(&mut p.attrs);
+      }
+    }
+  }
+
+  match item {
+    Item::Fn(function) => {
+      retain(&mut function.attrs);
+      retain_args(&mut function.sig);
+    }
+    Item::Struct(struct_) => {
+      retain(&mut struct_.attrs);
+      struct_
+        .fields
+        .iter_mut()
+        .for_each(|field| retain(&mut field.attrs));
+    }
+    Item::Enum(enum_) => retain(&mut enum_.attrs),
+    Item::Const(const_) => retain(&mut const_.attrs),
+    Item::Static(static_) => retain(&mut static_.attrs),
+    Item::Impl(impl_) => {
+      retain(&mut impl_.attrs);
+      for item in impl_.items.iter_mut() {
+        if let syn::ImplItem::Method(method) = item {
+          retain(&mut method.attrs);
+          retain_args(&mut method.sig);
+        }
+      }
+    }
+    Item::Trait(trait_) => {
+      retain(&mut trait_.attrs);
+      for item in trait_.items.iter_mut() {
+        if let syn::TraitItem::Method(method) = item {
+          retain(&mut method.attrs);
+        }
+      }
+    }
+    Item::Mod(mod_) => {
+      retain(&mut mod_.attrs);
+      if let Some((_, items)) = mod_.content.as_mut() {
+        items.iter_mut().for_each(strip_napi_attrs);
+      }
+    }
+    _ => {}
   }
 }
 
@@ -1,7 +1,7 @@
 use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
 
-use napi_derive_backend::{bail_span, BindgenResult, Diagnostic};
+use napi_derive_backend::{bail_span, BindgenResult, Diagnostic, NapiBench};
 use proc_macro2::{Delimiter, Ident, Span, TokenTree};
 use syn::spanned::Spanned;
 
@@ -17,6 +17,8 @@ struct StructParseState {
 
 struct ParsedStruct {
   js_name: String,
+  js_mod: Option<String>,
+  is_class: bool,
   ctor_defined: bool,
 }
 
@@ -45,19 +47,45 @@ macro_rules! attrgen {
       (js_name, JsName(Span, String, Span)),
       (constructor, Constructor(Span)),
       (factory, Factory(Span)),
+      (extends, Extends(Span, Ident)),
       (getter, Getter(Span, Option<Ident>)),
       (setter, Setter(Span, Option<Ident>)),
       (readonly, Readonly(Span)),
+      (writable, Writable(Span, bool)),
+      (enumerable, Enumerable(Span, bool)),
+      (configurable, Configurable(Span, bool)),
       (skip, Skip(Span)),
       (strict, Strict(Span)),
       (object, Object(Span)),
+      (columnar, Columnar(Span)),
+      (use_nullable, UseNullable(Span, bool)),
+      (deny_unknown_fields, DenyUnknownFields(Span)),
+      (rename_all, RenameAll(Span, String, Span)),
       (namespace, Namespace(Span, String, Span)),
       (ts_args_type, TsArgsType(Span, String, Span)),
+      (ts_arg_type, TsArgType(Span, String, Span)),
+      (default, Default(Span, String, Span)),
+      (variadic, Variadic(Span)),
       (ts_return_type, TsReturnType(Span, String, Span)),
       (ts_type, TsType(Span, String, Span)),
+      (also_async, AlsoAsync(Span, String, Span)),
+      (execution, Execution(Span, String, Span)),
+      (module_function, ModuleFunction(Span, Option<Ident>)),
+      (static_method, StaticMethod(Span, Ident)),
+      (module_init, ModuleInit(Span)),
+      (lazy, Lazy(Span)),
+      (readable_stream, ReadableStream(Span, Option<Ident>)),
+      (async_iterator, AsyncIterator(Span)),
+      (catch_unwind, CatchUnwind(Span)),
+      (bench, Bench(Span, NapiBench)),
+      (string_enum, StringEnum(Span, Option<(String, Span)>)),
+      (value, Value(Span, String, Span)),
+      (error, Error(Span)),
+      (to_string, ToString(Span)),
+      (to_json, ToJson(Span)),
+      (inspect, Inspect(Span)),
 
       // impl later
-      // (inspectable, Inspectable(Span)),
       // (typescript_custom_section, TypescriptCustomSection(Span)),
       (skip_typescript, SkipTypescript(Span)),
       // (getter_with_clone, GetterWithClone(Span)),
@@ -128,6 +156,22 @@ macro_rules! methods {
       }
   };
 
+  // could be repeated, all of them are returned
+  (@method $name:ident, $variant:ident(Span, NapiBench)) => {
+    pub fn $name(&self) -> Vec<&NapiBench> {
+      self.attrs
+        .iter()
+        .filter_map(|a| match &a.1 {
+          BindgenAttr::$variant(_, bench) => {
+            a.0.set(true);
+            Some(bench)
+          }
+          _ => None,
+        })
+        .collect()
+    }
+  };
+
   (@method $name:ident, $variant:ident(Span, $($other:tt)*)) => {
     #[allow(unused)]
     pub fn $name(&self) -> Option<&$($other)*> {
@@ -164,7 +208,8 @@ macro_rules! methods {
 impl BindgenAttrs {
   /// Find and parse the napi attributes.
   pub fn find(attrs: &mut Vec<syn::Attribute>) -> Result<BindgenAttrs, Diagnostic> {
-    let mut ret = BindgenAttrs::default();
+    // `BindgenAttrs::default` is the accessor of `#[napi(default)]`
+    let mut ret: BindgenAttrs = Default::default();
     loop {
       let napi_attr = attrs
         .iter()
@@ -237,12 +282,43 @@ pub fn record_struct(ident: &Ident, js_name: String, opts: &BindgenAttrs) {
       struct_name,
       ParsedStruct {
         js_name,
+        js_mod: opts.namespace().map(|(js_mod, _)| js_mod.to_owned()),
+        is_class: opts.object().is_none(),
         ctor_defined: opts.constructor().is_some(),
       },
     );
   });
 }
 
+/// The TypeScript name of the class parsed before, referenced from the namespace `js_mod`
+pub fn recorded_class_ts_name(ident: &Ident, js_mod: Option<&str>) -> Option<String> {
+  STRUCTS.with(|state| {
+    state
+      .parsed
+      .borrow()
+      .get(&ident.to_string())
+      .filter(|parsed| parsed.is_class)
+      .map(|parsed| match &parsed.js_mod {
+        Some(parent_js_mod) if Some(parent_js_mod.as_str()) != js_mod => {
+          format!("{}.{}", parent_js_mod, parsed.js_name)
+        }
+        _ => parsed.js_name.clone(),
+      })
+  })
+}
+
+/// The js name and the namespace of the class parsed before
+pub fn recorded_class(ident: &Ident) -> Option<(String, Option<String>)> {
+  STRUCTS.with(|state| {
+    state
+      .parsed
+      .borrow()
+      .get(&ident.to_string())
+      .filter(|parsed| parsed.is_class)
+      .map(|parsed| (parsed.js_name.clone(), parsed.js_mod.clone()))
+  })
+}
+
 pub fn check_recorded_struct_for_impl(ident: &Ident, opts: &BindgenAttrs) -> BindgenResult<String> {
   STRUCTS.with(|state| {
     let struct_name = ident.to_string();
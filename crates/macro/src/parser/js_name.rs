@@ -0,0 +1,182 @@
+//! The JavaScript names of the `#[napi]` items, checked against the names breaking the exports or the prototypes.
+//!
+//! The names derived from the Rust idents are escaped with the `_` suffix, the names set by `js_name` are rejected.
+
+use napi_derive_backend::{BindgenResult, Diagnostic};
+use proc_macro2::Span;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum JsNameKind {
+  /// Exported by the module, declared by `const { name } = nativeBinding` in the JS binding
+  Export,
+  /// Field of the objects and the classes, and the variant of the enums
+  Property,
+  /// Method and accessor of the class instances
+  Method,
+  /// Static method of the class
+  StaticMethod,
+}
+
+/// Can't be declared by the JS binding or the `.d.ts`
+const RESERVED_WORDS: &[&str] = &[
+  "arguments",
+  "await",
+  "break",
+  "case",
+  "catch",
+  "class",
+  "const",
+  "continue",
+  "debugger",
+  "default",
+  "delete",
+  "do",
+  "else",
+  "enum",
+  "eval",
+  "export",
+  "extends",
+  "false",
+  "finally",
+  "for",
+  "function",
+  "if",
+  "implements",
+  "import",
+  "in",
+  "instanceof",
+  "interface",
+  "let",
+  "new",
+  "null",
+  "package",
+  "private",
+  "protected",
+  "public",
+  "return",
+  "static",
+  "super",
+  "switch",
+  "this",
+  "throw",
+  "true",
+  "try",
+  "typeof",
+  "var",
+  "void",
+  "while",
+  "with",
+  "yield",
+];
+
+const OBJECT_PROTOTYPE: &[&str] = &[
+  "constructor",
+  "hasOwnProperty",
+  "isPrototypeOf",
+  "propertyIsEnumerable",
+  "toLocaleString",
+  "toString",
+  "valueOf",
+  "__defineGetter__",
+  "__defineSetter__",
+  "__lookupGetter__",
+  "__lookupSetter__",
+];
+
+/// `(problem, breaks)` of the name, the names not breaking anything are only warned
+fn find_problem(name: &str, kind: JsNameKind) -> Option<(String, bool)> {
+  if name == "__proto__" {
+    return Some((
+      "replaces the prototype instead of defining the property".to_owned(),
+      true,
+    ));
+  }
+  match kind {
+    JsNameKind::Export if RESERVED_WORDS.contains(&name) => Some((
+      "is a reserved word of JavaScript, which can't be declared by the JS binding".to_owned(),
+      true,
+    )),
+    JsNameKind::Method if name == "constructor" => Some((
+      "overrides the `constructor` of the class prototype".to_owned(),
+      true,
+    )),
+    JsNameKind::StaticMethod if name == "prototype" => Some((
+      "can't be defined on the class, `prototype` of it is not configurable".to_owned(),
+      true,
+    )),
+    JsNameKind::Property | JsNameKind::Method if OBJECT_PROTOTYPE.contains(&name) => {
+      Some((format!("shadows `Object.prototype.{}`", name), false))
+    }
+    _ => None,
+  }
+}
+
+/// `js_name` is the span of `#[napi(js_name)]` if the name is set by it
+pub(crate) fn check_js_name(
+  name: String,
+  kind: JsNameKind,
+  span: Span,
+  js_name: Option<Span>,
+) -> BindgenResult<String> {
+  let (problem, breaks) = match find_problem(&name, kind) {
+    Some(problem) => problem,
+    None => return Ok(name),
+  };
+  if !breaks {
+    warn(span, format!("`{}` {}", name, problem));
+    return Ok(name);
+  }
+  if let Some(js_name) = js_name {
+    return Err(Diagnostic::span_error(
+      js_name,
+      format!("`{}` {}, rename it by `js_name`", name, problem),
+    ));
+  }
+  let escaped = format!("{}_", name);
+  warn(
+    span,
+    format!(
+      "`{}` {}, it's renamed to `{}`, set `js_name` to rename it",
+      name, problem, escaped
+    ),
+  );
+  Ok(escaped)
+}
+
+/// The namespaces are always set by `#[napi(namespace)]`, so they are never escaped.
+/// `a.b` is the nested namespace `b` of `a`, only `a` is declared by the JS binding.
+pub(crate) fn check_namespace(namespace: Option<(&str, Span)>) -> BindgenResult<()> {
+  if let Some((namespace, span)) = namespace {
+    for (index, name) in namespace.split('.').enumerate() {
+      if name.is_empty() {
+        return Err(Diagnostic::span_error(
+          span,
+          format!(
+            "`{}` has an empty name of the nested namespaces, separate them by a single `.`",
+            namespace
+          ),
+        ));
+      }
+      let kind = if index == 0 {
+        JsNameKind::Export
+      } else {
+        JsNameKind::Property
+      };
+      if let Some((problem, true)) = find_problem(name, kind) {
+        return Err(Diagnostic::span_error(
+          span,
+          format!("`{}` {}, rename the namespace", name, problem),
+        ));
+      }
+    }
+  }
+  Ok(())
+}
+
+#[cfg(feature = "type-def")]
+fn warn(span: Span, message: String) {
+  napi_derive_backend::add_warning("napi::reserved_name", span, message);
+}
+
+#[cfg(not(feature = "type-def"))]
+fn warn(_span: Span, _message: String) {}
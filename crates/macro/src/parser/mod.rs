@@ -1,5 +1,6 @@
 #[macro_use]
 pub mod attrs;
+mod js_name;
 
 use std::cell::Cell;
 use std::collections::HashMap;
@@ -9,17 +10,22 @@ use attrs::{BindgenAttr, BindgenAttrs};
 
 use convert_case::{Case, Casing};
 use napi_derive_backend::{
-  BindgenResult, CallbackArg, Diagnostic, FnKind, FnSelf, Napi, NapiConst, NapiEnum,
-  NapiEnumVariant, NapiFn, NapiFnArgKind, NapiImpl, NapiItem, NapiStruct, NapiStructField,
-  NapiStructKind,
+  extract_this_ty, extract_vec_elem_ty, BindgenResult, CallbackArg, Diagnostic, FnExecution,
+  FnKind, FnSelf, ModuleFunction, ModuleInitArg, Napi, NapiBench, NapiClassParent, NapiConst,
+  NapiEnum, NapiEnumValue, NapiEnumVariant, NapiFn, NapiFnArg, NapiFnArgKind, NapiImpl, NapiItem,
+  NapiModuleInit, NapiStruct, NapiStructField, NapiStructKind, NapiTrait, NapiTraitMethod,
+  StaticMethodOf,
 };
-use proc_macro2::{Ident, TokenStream, TokenTree};
+use proc_macro2::{Ident, Span, TokenStream, TokenTree};
 use quote::ToTokens;
 use syn::ext::IdentExt;
-use syn::parse::{Parse, ParseStream, Result as SynResult};
+use syn::parse::{Parse, ParseStream, Parser, Result as SynResult};
 use syn::{Attribute, Signature, Type, Visibility};
 
-use crate::parser::attrs::{check_recorded_struct_for_impl, record_struct};
+use crate::parser::attrs::{
+  check_recorded_struct_for_impl, record_struct, recorded_class, recorded_class_ts_name,
+};
+use crate::parser::js_name::{check_js_name, check_namespace, JsNameKind};
 
 struct AnyIdent(Ident);
 
@@ -34,7 +40,7 @@ impl Parse for AnyIdent {
 
 impl Parse for BindgenAttrs {
   fn parse(input: ParseStream) -> SynResult<Self> {
-    let mut attrs = BindgenAttrs::default();
+    let mut attrs: BindgenAttrs = Default::default();
     if input.is_empty() {
       return Ok(attrs);
     }
@@ -76,6 +82,16 @@ impl Parse for BindgenAttr {
         return Ok(BindgenAttr::$variant(attr_span, ident))
       });
 
+      // `name` is the same as `name = true`
+      (@parser $variant:ident(Span, bool)) => ({
+        if input.parse::<Token![=]>().is_ok() {
+          let value = input.parse::<syn::LitBool>()?.value;
+          return Ok(BindgenAttr::$variant(attr_span, value))
+        } else {
+          return Ok(BindgenAttr::$variant(attr_span, true));
+        }
+      });
+
       (@parser $variant:ident(Span, Option<Ident>)) => ({
         if input.parse::<Token![=]>().is_ok() {
           let ident = input.parse::<AnyIdent>()?.0;
@@ -85,6 +101,22 @@ impl Parse for BindgenAttr {
         }
       });
 
+      // `name`, `name = ident` or `name = "string"`
+      (@parser $variant:ident(Span, Option<(String, Span)>)) => ({
+        if input.parse::<Token![=]>().is_ok() {
+          let value = match input.parse::<syn::LitStr>() {
+            Ok(str) => (str.value(), str.span()),
+            Err(_) => {
+              let ident = input.parse::<AnyIdent>()?.0;
+              (ident.to_string(), ident.span())
+            }
+          };
+          return Ok(BindgenAttr::$variant(attr_span, Some(value)))
+        } else {
+          return Ok(BindgenAttr::$variant(attr_span, None));
+        }
+      });
+
         (@parser $variant:ident(Span, syn::Path)) => ({
             input.parse::<Token![=]>()?;
             return Ok(BindgenAttr::$variant(attr_span, input.parse()?));
@@ -95,6 +127,31 @@ impl Parse for BindgenAttr {
             return Ok(BindgenAttr::$variant(attr_span, input.parse()?));
         });
 
+        // `bench(args = "...", name = "...")`
+        (@parser $variant:ident(Span, NapiBench)) => ({
+          let content;
+          syn::parenthesized!(content in input);
+          let pairs = content.parse_terminated::<_, Token![,]>(|input: ParseStream| {
+            let key = input.parse::<AnyIdent>()?.0;
+            input.parse::<Token![=]>()?;
+            Ok((key, input.parse::<syn::LitStr>()?))
+          })?;
+          let mut args = None;
+          let mut name = None;
+          for (key, value) in pairs {
+            match key.to_string().as_str() {
+              "args" => args = Some(value.value()),
+              "name" => name = Some(value.value()),
+              _ => return Err(syn::Error::new(key.span(), "#[napi(bench)] only accepts `args` and `name`")),
+            }
+          }
+          let args = args.ok_or_else(|| syn::Error::new(attr_span, "#[napi(bench)] requires `args`, the JS expressions of the arguments"))?;
+          return Ok(BindgenAttr::$variant(attr_span, NapiBench {
+            name: name.unwrap_or_else(|| args.clone()),
+            args,
+          }))
+        });
+
         (@parser $variant:ident(Span, String, Span)) => ({
           input.parse::<Token![=]>()?;
           let (val, span) = match input.parse::<syn::LitStr>() {
@@ -196,6 +253,23 @@ fn extract_path_ident(path: &syn::Path) -> BindgenResult<Ident> {
   }
 }
 
+/// The arguments of `Fn` in `impl Fn(A, B) -> Result<R>`
+fn extract_impl_fn_trait(ty: &syn::Type) -> Option<&syn::PathArguments> {
+  match ty {
+    syn::Type::ImplTrait(impl_trait) => impl_trait.bounds.iter().find_map(|bound| match bound {
+      syn::TypeParamBound::Trait(t) => t.path.segments.last().and_then(|segment| {
+        matches!(
+          segment.ident.to_string().as_str(),
+          "Fn" | "FnOnce" | "FnMut"
+        )
+        .then(|| &segment.arguments)
+      }),
+      _ => None,
+    }),
+    _ => None,
+  }
+}
+
 fn extract_callback_trait_types(
   arguments: &syn::PathArguments,
 ) -> BindgenResult<(Vec<syn::Type>, Option<syn::Type>)> {
@@ -293,11 +367,52 @@ fn extract_doc_comments(attrs: &[syn::Attribute]) -> Vec<String> {
     })
     //Fold up the [[String]] iter we created into Vec<String>
     .fold(vec![], |mut acc, a| {
-      acc.extend(a);
+      acc.extend(a.flat_map(doc_comment_lines));
       acc
     })
 }
 
+/// The lines of the `/** */` and multi-line `#[doc = "..."]` comments, like the `///` ones.
+/// The leading `*`s of the block comments and the common indent are removed, the same as rustdoc.
+fn doc_comment_lines(doc: String) -> Vec<String> {
+  if !doc.contains('\n') {
+    return vec![doc];
+  }
+  let mut lines = doc.lines().collect::<Vec<_>>();
+  while matches!(lines.first(), Some(line) if line.trim().is_empty()) {
+    lines.remove(0);
+  }
+  while matches!(lines.last(), Some(line) if line.trim().is_empty()) {
+    lines.pop();
+  }
+  let is_starred = lines
+    .iter()
+    .all(|line| line.trim().is_empty() || line.trim_start().starts_with('*'));
+  let lines = lines
+    .into_iter()
+    .map(|line| {
+      if is_starred {
+        line.trim_start().strip_prefix('*').unwrap_or_default()
+      } else {
+        line
+      }
+    })
+    .collect::<Vec<_>>();
+  let indent = lines
+    .iter()
+    .filter(|line| !line.trim().is_empty())
+    .map(|line| line.len() - line.trim_start().len())
+    .min()
+    .unwrap_or_default();
+  lines
+    .into_iter()
+    .map(|line| match line.get(indent..) {
+      Some(line) if !line.trim().is_empty() => format!(" {}", line.trim_end()),
+      _ => String::new(),
+    })
+    .collect()
+}
+
 // Unescaped a quoted string. char::escape_debug() was used to escape the text.
 fn try_unescape(s: &str) -> Option<String> {
   if s.is_empty() {
@@ -457,6 +572,56 @@ fn extract_fn_closure_generics(
   Diagnostic::from_vec(errors).and(Ok(map))
 }
 
+/// The type params other than the callbacks are converted as `JsUnknown`,
+/// so they can only be bounded by the conversions implemented by it
+fn check_type_param_bounds(generics: &syn::Generics, type_params: &[Ident]) -> BindgenResult<()> {
+  let mut errors = vec![];
+  let mut check_bounds = |bounds: &syn::punctuated::Punctuated<syn::TypeParamBound, Token![+]>| {
+    for bound in bounds {
+      if let syn::TypeParamBound::Trait(t) = bound {
+        let is_conversion = t
+          .path
+          .segments
+          .last()
+          .map(|segment| segment.ident == "FromNapiValue" || segment.ident == "ToNapiValue")
+          .unwrap_or(false);
+        if !is_conversion {
+          errors.push(err_span!(
+            bound,
+            "the type params of #[napi] fn are converted as `JsUnknown`, they can only be bounded by `FromNapiValue` or `ToNapiValue`"
+          ));
+        }
+      }
+    }
+  };
+
+  for param in generics.type_params() {
+    if type_params.contains(&param.ident) {
+      check_bounds(&param.bounds);
+    }
+  }
+  if let Some(where_clause) = &generics.where_clause {
+    for predicate in where_clause.predicates.iter() {
+      if let syn::WherePredicate::Type(syn::PredicateType {
+        bounded_ty: syn::Type::Path(syn::TypePath { qself: None, path }),
+        bounds,
+        ..
+      }) = predicate
+      {
+        if path
+          .get_ident()
+          .map(|ident| type_params.contains(ident))
+          .unwrap_or(false)
+        {
+          check_bounds(bounds);
+        }
+      }
+    }
+  }
+
+  Diagnostic::from_vec(errors)
+}
+
 fn napi_fn_from_decl(
   sig: Signature,
   opts: &BindgenAttrs,
@@ -466,6 +631,13 @@ fn napi_fn_from_decl(
 ) -> BindgenResult<NapiFn> {
   let mut errors = vec![];
 
+  if let Some(span) = opts.module_init() {
+    return Err(Diagnostic::span_error(
+      *span,
+      "#[napi(module_init)] can only be applied to a free fn",
+    ));
+  }
+
   let syn::Signature {
     ident,
     asyncness,
@@ -477,29 +649,107 @@ fn napi_fn_from_decl(
 
   let mut fn_self = None;
   let callback_traits = extract_fn_closure_generics(&generics)?;
+  let type_params = generics
+    .type_params()
+    .map(|param| param.ident.clone())
+    .filter(|ident| !callback_traits.contains_key(&ident.to_string()))
+    .collect::<Vec<_>>();
+  check_type_param_bounds(&generics, &type_params)?;
 
+  let inputs_len = inputs.len();
+  let mut variadic_spans = vec![];
   let args = inputs
     .into_iter()
-    .filter_map(|arg| match arg {
+    .enumerate()
+    .filter_map(|(index, arg)| match arg {
       syn::FnArg::Typed(mut p) => {
+        let arg_opts = match BindgenAttrs::find(&mut p.attrs) {
+          Ok(arg_opts) => arg_opts,
+          Err(e) => {
+            errors.push(e);
+            return None;
+          }
+        };
+        let ts_arg_type = arg_opts.ts_arg_type().map(|(ty, span)| {
+          if opts.ts_args_type().is_some() {
+            errors.push(Diagnostic::span_error(
+              span,
+              "#[napi(ts_arg_type)] is ignored by the #[napi(ts_args_type)] of the fn",
+            ));
+          }
+          ty.to_owned()
+        });
         let ty_str = p.ty.to_token_stream().to_string();
-        if let Some(path_arguments) = callback_traits.get(&ty_str) {
+        let callback_trait = callback_traits
+          .get(&ty_str)
+          .or_else(|| extract_impl_fn_trait(&p.ty));
+        let default = arg_opts.default().and_then(|(expr, span)| {
+          let message = match p.ty.as_ref() {
+            syn::Type::Reference(_) => Some("the param with #[napi(default)] must be an owned value"),
+            syn::Type::Path(path) if matches!(path.path.segments.last(), Some(s) if s.ident == "Option") => Some(
+              "the param with #[napi(default)] is never `None`, use the inner type of the `Option` instead",
+            ),
+            _ if callback_trait.is_some() => {
+              Some("callbacks can't have #[napi(default)]")
+            }
+            _ => None,
+          };
+          if let Some(message) = message {
+            errors.push(Diagnostic::span_error(span, message));
+            return None;
+          }
+          match syn::parse_str::<syn::Expr>(expr) {
+            Ok(expr) => Some(expr),
+            Err(e) => {
+              errors.push(Diagnostic::span_error(
+                span,
+                format!("#[napi(default)] must be a Rust expression: {}", e),
+              ));
+              None
+            }
+          }
+        });
+        let variadic = arg_opts.variadic().map(|span| {
+          let message = if default.is_some() {
+            Some("the param with #[napi(variadic)] is `[]` if no argument is passed, it can't have #[napi(default)]")
+          } else if callback_trait.is_some() {
+            Some("callbacks can't be #[napi(variadic)]")
+          } else if extract_vec_elem_ty(&p.ty).is_none() {
+            Some("the param with #[napi(variadic)] must be a `Vec<T>`")
+          } else {
+            None
+          };
+          if let Some(message) = message {
+            errors.push(Diagnostic::span_error(*span, message));
+          }
+          *span
+        });
+        let kind = if let Some(path_arguments) = callback_trait {
           match extract_callback_trait_types(path_arguments) {
-            Ok((fn_args, fn_ret)) => Some(NapiFnArgKind::Callback(Box::new(CallbackArg {
+            Ok((fn_args, fn_ret)) => NapiFnArgKind::Callback(Box::new(CallbackArg {
               pat: p.pat,
               args: fn_args,
               ret: fn_ret,
-            }))),
+            })),
             Err(e) => {
               errors.push(e);
-              None
+              return None;
             }
           }
         } else {
           let ty = replace_self(*p.ty, parent);
           p.ty = Box::new(ty);
-          Some(NapiFnArgKind::PatType(Box::new(p)))
+          NapiFnArgKind::PatType(Box::new(p))
+        };
+        if let Some(span) = variadic {
+          variadic_spans.push((index, span));
         }
+        Some(NapiFnArg {
+          kind,
+          ts_arg_type,
+          default,
+          variadic: variadic.is_some(),
+        })
       }
       syn::FnArg::Receiver(r) => {
         if parent.is_some() {
@@ -522,6 +772,36 @@ fn napi_fn_from_decl(
     })
     .collect::<Vec<_>>();
 
+  // `this` is the first param of the fn in TypeScript
+  args
+    .iter()
+    .filter_map(|arg| match &arg.kind {
+      NapiFnArgKind::PatType(p) if p.ty.to_token_stream().to_string() == "Env" => None,
+      NapiFnArgKind::PatType(p) => Some(Some(&p.ty)),
+      NapiFnArgKind::Callback(_) => Some(None),
+    })
+    .enumerate()
+    .filter_map(|(index, ty)| ty.filter(|ty| extract_this_ty(ty).is_some()).map(|ty| (index, ty)))
+    .for_each(|(index, ty)| {
+      if parent.is_some() {
+        errors.push(err_span!(
+          ty,
+          "the methods get the JavaScript `this` by `&self`, `This` is only for the module level fns"
+        ));
+      } else if index != 0 {
+        errors.push(err_span!(ty, "`This` must be the first param of the fn"));
+      }
+    });
+
+  for (index, span) in variadic_spans {
+    if index + 1 != inputs_len {
+      errors.push(Diagnostic::span_error(
+        span,
+        "#[napi(variadic)] can only be applied to the last param",
+      ));
+    }
+  }
+
   let (ret, is_ret_result) = match output {
     syn::ReturnType::Default => (None, false),
     syn::ReturnType::Type(_, ty) => {
@@ -534,8 +814,208 @@ fn napi_fn_from_decl(
     }
   };
 
-  Diagnostic::from_vec(errors).map(|_| {
-    let js_name = if let Some(prop_name) = opts.getter() {
+  let execution = match opts.execution() {
+    None if asyncness.is_some() => FnExecution::Tokio,
+    None => FnExecution::JsThread,
+    Some(("js_thread", _)) => FnExecution::JsThread,
+    Some(("blocking_pool", _)) => FnExecution::BlockingPool,
+    Some(("tokio", _)) => FnExecution::Tokio,
+    Some((_, span)) => {
+      return Err(Diagnostic::span_error(
+        span,
+        "#[napi(execution)] must be one of \"js_thread\", \"blocking_pool\" or \"tokio\"",
+      ))
+    }
+  };
+
+  if asyncness.is_some() && execution != FnExecution::Tokio {
+    if let Some((_, span)) = opts.execution() {
+      errors.push(Diagnostic::span_error(
+        span,
+        "async fn is always executed in the tokio runtime",
+      ));
+    }
+  }
+
+  if let Some(asyncness) = &asyncness {
+    if !matches!(fn_kind(opts), FnKind::Normal | FnKind::Factory) {
+      errors.push(err_span!(
+        asyncness,
+        "constructor, getter and setter can't be async, construct the class by an async #[napi(factory)] instead"
+      ));
+    }
+  }
+
+  if let Some((_, span)) = opts.also_async() {
+    if asyncness.is_some() || execution != FnExecution::JsThread {
+      errors.push(Diagnostic::span_error(
+        span,
+        "#[napi(also_async)] can only be applied to a fn executed in the JavaScript thread, it already returns a Promise",
+      ));
+    }
+    check_off_thread(
+      "also_async",
+      span,
+      fn_kind(opts),
+      &fn_self,
+      &args,
+      ret.as_ref(),
+      &mut errors,
+    );
+  }
+
+  if let Some((execution_str, span)) = opts.execution() {
+    if asyncness.is_none() && execution != FnExecution::JsThread {
+      check_off_thread(
+        &format!("execution = \"{}\"", execution_str),
+        span,
+        fn_kind(opts),
+        &fn_self,
+        &args,
+        ret.as_ref(),
+        &mut errors,
+      );
+    }
+  }
+
+  let module_function = match opts.module_function() {
+    None => None,
+    Some(None) => Some(ModuleFunction::Also),
+    Some(Some(only)) if only == "only" => Some(ModuleFunction::Only),
+    Some(Some(other)) => {
+      errors.push(err_span!(
+        other,
+        "#[napi(module_function)] only accepts `only`, to export the module function without the static method"
+      ));
+      None
+    }
+  };
+
+  if module_function.is_some()
+    && (parent.is_none() || fn_self.is_some() || fn_kind(opts) != FnKind::Normal)
+  {
+    errors.push(Diagnostic::span_error(
+      ident.span(),
+      "#[napi(module_function)] can only be applied to a static method in #[napi] impl",
+    ));
+  }
+
+  let static_method = opts.static_method().and_then(|class| {
+    if parent.is_some() || fn_kind(opts) != FnKind::Normal {
+      errors.push(err_span!(
+        class,
+        "#[napi(static_method)] can only be applied to a module level fn, the static methods of #[napi] impl are exported by #[napi(module_function)]"
+      ));
+      return None;
+    }
+    match recorded_class(class) {
+      Some((js_name, js_mod)) => Some(StaticMethodOf {
+        class: class.clone(),
+        js_name,
+        js_mod,
+      }),
+      None => {
+        errors.push(err_span!(
+          class,
+          "Did not find the #[napi] class `{}` parsed before expand #[napi(static_method)], declare the class before the fn",
+          class
+        ));
+        None
+      }
+    }
+  });
+
+  let readable_stream = match opts.readable_stream() {
+    None => None,
+    Some(None) => Some(false),
+    Some(Some(object_mode)) if object_mode == "object_mode" => Some(true),
+    Some(Some(other)) => {
+      errors.push(err_span!(
+        other,
+        "#[napi(readable_stream)] only accepts `object_mode`, to push the chunks which are not `Buffer` or `String`"
+      ));
+      None
+    }
+  };
+
+  if readable_stream.is_some() {
+    if asyncness.is_some() || execution != FnExecution::JsThread || opts.also_async().is_some() {
+      errors.push(Diagnostic::span_error(
+        ident.span(),
+        "#[napi(readable_stream)] can only be applied to a sync fn executed in the JavaScript thread, the Stream is polled in the tokio runtime by itself",
+      ));
+    }
+    if fn_kind(opts) != FnKind::Normal {
+      errors.push(Diagnostic::span_error(
+        ident.span(),
+        "#[napi(readable_stream)] can't be applied to a constructor, factory, getter or setter",
+      ));
+    }
+    if ret.is_none() {
+      errors.push(Diagnostic::span_error(
+        ident.span(),
+        "#[napi(readable_stream)] fn must return `impl Stream<Item = Result<T>>`",
+      ));
+    }
+  }
+
+  let async_iterator = opts.async_iterator().is_some();
+  if async_iterator {
+    if readable_stream.is_some() {
+      errors.push(Diagnostic::span_error(
+        ident.span(),
+        "#[napi(async_iterator)] and #[napi(readable_stream)] can't be applied to the same fn",
+      ));
+    }
+    if asyncness.is_some() || execution != FnExecution::JsThread || opts.also_async().is_some() {
+      errors.push(Diagnostic::span_error(
+        ident.span(),
+        "#[napi(async_iterator)] can only be applied to a sync fn executed in the JavaScript thread, the Stream is polled in the tokio runtime by itself",
+      ));
+    }
+    if fn_kind(opts) != FnKind::Normal {
+      errors.push(Diagnostic::span_error(
+        ident.span(),
+        "#[napi(async_iterator)] can't be applied to a constructor, factory, getter or setter",
+      ));
+    }
+    if ret.is_none() {
+      errors.push(Diagnostic::span_error(
+        ident.span(),
+        "#[napi(async_iterator)] fn must return `impl Stream<Item = Result<T>>`",
+      ));
+    }
+  }
+
+  if opts.writable().is_some() {
+    errors.push(Diagnostic::span_error(
+      ident.span(),
+      "#[napi(writable)] can only be applied to the fields, the getter is writable if the setter of it is defined",
+    ));
+  }
+  let enumerable = opts.enumerable().copied();
+  let configurable = opts.configurable().copied();
+  if (enumerable.is_some() || configurable.is_some())
+    && !matches!(fn_kind(opts), FnKind::Getter | FnKind::Setter)
+  {
+    errors.push(Diagnostic::span_error(
+      ident.span(),
+      "#[napi(enumerable)] and #[napi(configurable)] can only be applied to the getters, the setters and the fields of the classes",
+    ));
+  }
+
+  let benches = opts.bench().into_iter().cloned().collect::<Vec<_>>();
+  if !benches.is_empty() && parent.is_some() {
+    errors.push(Diagnostic::span_error(
+      ident.span(),
+      "#[napi(bench)] can only be applied to the module level fns, the methods are called on the instances",
+    ));
+  }
+
+  check_namespace(opts.namespace())?;
+
+  Diagnostic::from_vec(errors).and_then(|_| {
+    let mut js_name = if let Some(prop_name) = opts.getter() {
       if let Some(ident) = prop_name {
         ident.to_string()
       } else {
@@ -562,7 +1042,41 @@ fn napi_fn_from_decl(
       )
     };
 
-    NapiFn {
+    if opts.constructor().is_none() {
+      let explicit_span = opts
+        .js_name()
+        .map(|(_, span)| span)
+        .or_else(|| {
+          opts
+            .getter()
+            .and_then(|name| name.as_ref().map(Ident::span))
+        })
+        .or_else(|| {
+          opts
+            .setter()
+            .and_then(|name| name.as_ref().map(Ident::span))
+        });
+      let kind = match (parent, &fn_self) {
+        (None, _) => JsNameKind::Export,
+        (Some(_), None) => JsNameKind::StaticMethod,
+        (Some(_), Some(_)) => JsNameKind::Method,
+      };
+      js_name = check_js_name(js_name, kind, ident.span(), explicit_span)?;
+      // the module function is exported by the same name as the static method
+      if module_function.is_some() {
+        js_name = check_js_name(js_name, JsNameKind::Export, ident.span(), explicit_span)?;
+      }
+      if static_method.is_some() {
+        js_name = check_js_name(
+          js_name,
+          JsNameKind::StaticMethod,
+          ident.span(),
+          explicit_span,
+        )?;
+      }
+    }
+
+    Ok(NapiFn {
       name: ident,
       js_name,
       args,
@@ -580,7 +1094,18 @@ fn napi_fn_from_decl(
       ts_args_type: opts.ts_args_type().map(|(m, _)| m.to_owned()),
       ts_return_type: opts.ts_return_type().map(|(m, _)| m.to_owned()),
       skip_typescript: opts.skip_typescript().is_some(),
-    }
+      also_async: opts.also_async().map(|(m, _)| m.to_owned()),
+      execution,
+      module_function,
+      static_method,
+      readable_stream,
+      async_iterator,
+      catch_unwind: opts.catch_unwind().is_some(),
+      type_params,
+      enumerable: enumerable.unwrap_or(false),
+      configurable: configurable.unwrap_or(false),
+      benches,
+    })
   })
 }
 
@@ -592,9 +1117,11 @@ impl ParseNapi for syn::Item {
       syn::Item::Impl(i) => i.parse_napi(tokens, opts),
       syn::Item::Enum(e) => e.parse_napi(tokens, opts),
       syn::Item::Const(c) => c.parse_napi(tokens, opts),
+      syn::Item::Static(s) => s.parse_napi(tokens, opts),
+      syn::Item::Trait(t) => t.parse_napi(tokens, opts),
       _ => bail_span!(
         self,
-        "#[napi] can only be applied to a function, struct, enum, const, mod or impl."
+        "#[napi] can only be applied to a function, struct, enum, const, static, trait, mod or impl."
       ),
     }
   }
@@ -608,8 +1135,145 @@ impl ParseNapi for syn::ItemFn {
         "#[napi] can't be applied to a function with #[napi(ts_type)]"
       );
     }
+    if opts.module_init().is_some() {
+      self.to_tokens(tokens);
+      return Ok(Napi {
+        item: NapiItem::ModuleInit(napi_module_init_from_fn(self)?),
+      });
+    }
+    // the `#[napi]` of the params are removed while converting
+    let napi = self.convert_to_ast(opts);
     self.to_tokens(tokens);
-    self.convert_to_ast(opts)
+    napi
+  }
+}
+
+/// Remove the `#[napi(...)]` of the params, they are parsed from the cloned signature
+fn remove_arg_napi_attrs(sig: &mut syn::Signature) {
+  for arg in sig.inputs.iter_mut() {
+    if let syn::FnArg::Typed(p) = arg {
+      p.attrs.retain(|attr| !attr.path.is_ident("napi"));
+    }
+  }
+}
+
+/// The fn is called with `Env` and the exports `JsObject` in the order of the params, both of them are optional
+fn napi_module_init_from_fn(item: &syn::ItemFn) -> BindgenResult<NapiModuleInit> {
+  let sig = &item.sig;
+  if let Some(asyncness) = &sig.asyncness {
+    bail_span!(
+      asyncness,
+      "#[napi(module_init)] fn can't be async, it's called synchronously while registering the module"
+    );
+  }
+  if !sig.generics.params.is_empty() {
+    bail_span!(sig.generics, "#[napi(module_init)] fn can't be generic");
+  }
+  let mut args = vec![];
+  for input in sig.inputs.iter() {
+    let arg = match input {
+      syn::FnArg::Typed(p) => match &*p.ty {
+        syn::Type::Path(path) => match path.path.segments.last() {
+          Some(segment) if segment.ident == "Env" => Some(ModuleInitArg::Env),
+          Some(segment) if segment.ident == "JsObject" => Some(ModuleInitArg::Exports),
+          _ => None,
+        },
+        _ => None,
+      },
+      syn::FnArg::Receiver(_) => None,
+    };
+    match arg {
+      Some(arg) if !args.contains(&arg) => args.push(arg),
+      _ => bail_span!(
+        input,
+        "the params of #[napi(module_init)] fn can only be `Env` and the exports `JsObject`"
+      ),
+    }
+  }
+  let returns_result = match &sig.output {
+    syn::ReturnType::Default => false,
+    syn::ReturnType::Type(_, ty) => match &**ty {
+      syn::Type::Path(path) if matches!(path.path.segments.last(), Some(segment) if segment.ident == "Result") => {
+        true
+      }
+      _ => bail_span!(
+        ty,
+        "#[napi(module_init)] fn can only return `()` or `Result<()>`"
+      ),
+    },
+  };
+  for stmt in item.block.stmts.iter() {
+    match stmt {
+      syn::Stmt::Item(syn::Item::Static(item_static)) => {
+        check_env_independent(&item_static.ty, "the statics in #[napi(module_init)] fn")?
+      }
+      syn::Stmt::Item(syn::Item::Macro(item_macro))
+        if item_macro.mac.path.is_ident("thread_local") =>
+      {
+        // the `const` initializers can't be parsed as the statics, they can't be the JS values either
+        let parse_statics = |input: ParseStream| -> SynResult<Vec<syn::ItemStatic>> {
+          let mut statics = vec![];
+          while !input.is_empty() {
+            statics.push(input.parse::<syn::ItemStatic>()?);
+          }
+          Ok(statics)
+        };
+        if let Ok(statics) = parse_statics.parse2(item_macro.mac.tokens.clone()) {
+          for item_static in statics {
+            check_env_independent(&item_static.ty, "the statics in #[napi(module_init)] fn")?;
+          }
+        }
+      }
+      _ => {}
+    }
+  }
+  Ok(NapiModuleInit {
+    name: sig.ident.clone(),
+    args,
+    returns_result,
+  })
+}
+
+/// The JS values and the env are only valid on the env they are created on,
+/// `#[napi(module_init)]` is called for every env the module is registered on, e.g. the Workers and the restored startup snapshots.
+fn check_env_independent(ty: &syn::Type, outlive: &str) -> BindgenResult<()> {
+  match ty {
+    syn::Type::Path(syn::TypePath { path, .. }) => {
+      for segment in path.segments.iter() {
+        let name = segment.ident.to_string();
+        if matches!(
+          name.as_str(),
+          "Env" | "Ref" | "napi_env" | "napi_value" | "napi_ref"
+        ) || (name.starts_with("Js") && name[2..].starts_with(|c: char| c.is_ascii_uppercase()))
+        {
+          bail_span!(
+            segment,
+            "`{}` is only valid on the env it's created on, {} outlive the env. Keep it by `Env::set_instance_data` in #[napi(module_init)], which is called for every env",
+            name,
+            outlive
+          );
+        }
+        if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+          for arg in args.args.iter() {
+            if let syn::GenericArgument::Type(ty) = arg {
+              check_env_independent(ty, outlive)?;
+            }
+          }
+        }
+      }
+      Ok(())
+    }
+    syn::Type::Reference(syn::TypeReference { elem, .. })
+    | syn::Type::Array(syn::TypeArray { elem, .. })
+    | syn::Type::Slice(syn::TypeSlice { elem, .. })
+    | syn::Type::Paren(syn::TypeParen { elem, .. })
+    | syn::Type::Group(syn::TypeGroup { elem, .. })
+    | syn::Type::Ptr(syn::TypePtr { elem, .. }) => check_env_independent(elem, outlive),
+    syn::Type::Tuple(tuple) => tuple
+      .elems
+      .iter()
+      .try_for_each(|elem| check_env_independent(elem, outlive)),
+    _ => Ok(()),
   }
 }
 impl ParseNapi for syn::ItemStruct {
@@ -673,12 +1337,138 @@ impl ParseNapi for syn::ItemConst {
         "#[napi] can't be applied to a const with #[napi(ts_args_type)], #[napi(ts_return_type)] or #[napi(ts_type)]"
       );
     }
+    if let Some(lazy) = opts.lazy() {
+      return Err(Diagnostic::span_error(
+        *lazy,
+        "#[napi(lazy)] can only be applied to a static, the consts are inlined",
+      ));
+    }
     let napi = self.convert_to_ast(opts);
     self.to_tokens(tokens);
     napi
   }
 }
 
+impl ParseNapi for syn::ItemStatic {
+  fn parse_napi(&mut self, tokens: &mut TokenStream, opts: BindgenAttrs) -> BindgenResult<Napi> {
+    if opts.ts_args_type().is_some() || opts.ts_return_type().is_some() || opts.ts_type().is_some()
+    {
+      bail_span!(
+        self,
+        "#[napi] can't be applied to a static with #[napi(ts_args_type)], #[napi(ts_return_type)] or #[napi(ts_type)]"
+      );
+    }
+    let is_lazy = opts.lazy().is_some();
+    let napi = self.convert_to_ast(opts);
+    if is_lazy {
+      // the value is computed by the initializer on the first read, the initializer needn't be a const
+      let syn::ItemStatic {
+        attrs,
+        vis,
+        ident,
+        ty,
+        expr,
+        ..
+      } = &*self;
+      (quote! {
+        napi::__lazy_static! {
+          #(#attrs)*
+          #vis static ref #ident: #ty = #expr;
+        }
+      })
+      .to_tokens(tokens);
+    } else {
+      self.to_tokens(tokens);
+    }
+    napi
+  }
+}
+
+impl ParseNapi for syn::ItemTrait {
+  fn parse_napi(&mut self, tokens: &mut TokenStream, opts: BindgenAttrs) -> BindgenResult<Napi> {
+    if opts.ts_args_type().is_some() || opts.ts_return_type().is_some() || opts.ts_type().is_some()
+    {
+      bail_span!(
+        self,
+        "#[napi] can't be applied to a trait with #[napi(ts_args_type)], #[napi(ts_return_type)] or #[napi(ts_type)]"
+      );
+    }
+    // the #[napi] attributes of the methods are removed while converting
+    let napi = self.convert_to_ast(opts);
+    self.to_tokens(tokens);
+    napi
+  }
+}
+
+/// Functions executed off the JavaScript thread are called in the libuv thread pool or the tokio runtime,
+/// so everything they capture must be owned and must not touch the JavaScript thread.
+fn check_off_thread(
+  attr: &str,
+  span: Span,
+  kind: FnKind,
+  fn_self: &Option<FnSelf>,
+  args: &[NapiFnArg],
+  ret: Option<&syn::Type>,
+  errors: &mut Vec<Diagnostic>,
+) {
+  if kind != FnKind::Normal {
+    errors.push(Diagnostic::span_error(
+      span,
+      format!(
+        "#[napi({})] can't be applied to a constructor, factory, getter or setter",
+        attr
+      ),
+    ));
+  }
+  // the instance could be mutated by the JavaScript thread while `self` is read in the other thread
+  if fn_self.is_some() {
+    errors.push(Diagnostic::span_error(
+      span,
+      format!(
+        "#[napi({})] can't be applied to a method taking `self`, make it a static method taking the owned values",
+        attr
+      ),
+    ));
+  }
+  for arg in args {
+    match &arg.kind {
+      NapiFnArgKind::PatType(p) => match p.ty.as_ref() {
+        syn::Type::Reference(_) => errors.push(err_span!(
+          p.ty,
+          "#[napi({})] arguments must be owned values, references can't be sent to another thread",
+          attr
+        )),
+        ty if ty.to_token_stream().to_string() == "Env" => errors.push(err_span!(
+          p.ty,
+          "#[napi({})] functions can't take `Env`, it's only available in the JavaScript thread",
+          attr
+        )),
+        ty if extract_this_ty(ty).is_some() => errors.push(err_span!(
+          p.ty,
+          "#[napi({})] functions can't take `This`, it's only available in the JavaScript thread",
+          attr
+        )),
+        _ => {}
+      },
+      NapiFnArgKind::Callback(cb) => errors.push(err_span!(
+        cb.pat,
+        "#[napi({})] functions can't take callbacks, use a ThreadsafeFunction instead",
+        attr
+      )),
+    }
+  }
+  if let Some(ty) = ret {
+    let ty_str = ty.to_token_stream().to_string();
+    if ty_str == "& Self" || ty_str == "&mut Self" || matches!(ty, syn::Type::Reference(_)) {
+      errors.push(err_span!(
+        ty,
+        "#[napi({})] functions must return an owned value",
+        attr
+      ));
+    }
+  }
+}
+
 fn fn_kind(opts: &BindgenAttrs) -> FnKind {
   let mut kind = FnKind::Normal;
 
@@ -709,7 +1499,9 @@ impl ConvertToAST for syn::ItemFn {
       self.attrs.clone(),
       self.vis.clone(),
       None,
-    )?;
+    );
+    remove_arg_napi_attrs(&mut self.sig);
+    let func = func?;
 
     Ok(Napi {
       item: NapiItem::Fn(func),
@@ -723,10 +1515,16 @@ impl ConvertToAST for syn::ItemStruct {
 
     let vis = self.vis.clone();
     let struct_name = self.ident.clone();
-    let js_name = opts.js_name().map_or_else(
-      || self.ident.to_string().to_case(Case::Pascal),
-      |(js_name, _)| js_name.to_owned(),
-    );
+    check_namespace(opts.namespace())?;
+    let js_name = check_js_name(
+      opts.js_name().map_or_else(
+        || self.ident.to_string().to_case(Case::Pascal),
+        |(js_name, _)| js_name.to_owned(),
+      ),
+      JsNameKind::Export,
+      self.ident.span(),
+      opts.js_name().map(|(_, span)| span),
+    )?;
     let mut fields = vec![];
     let mut is_tuple = false;
     let struct_kind = if opts.constructor().is_some() {
@@ -737,6 +1535,72 @@ impl ConvertToAST for syn::ItemStruct {
       NapiStructKind::None
     };
 
+    let parent = opts.extends().and_then(|parent| {
+      if struct_kind != NapiStructKind::None {
+        errors.push(err_span!(
+          parent,
+          "#[napi(extends)] can't be applied to the #[napi(constructor)] or #[napi(object)] struct, construct the class with the parent by a #[napi(constructor)] fn"
+        ));
+      }
+      let is_parent_field = |field: &syn::Field| {
+        matches!(field.vis, syn::Visibility::Inherited)
+          && matches!(&field.ty, syn::Type::Path(path) if path.path.segments.last().map(|segment| &segment.ident) == Some(parent))
+      };
+      if !matches!(self.fields.iter().next(), Some(field) if is_parent_field(field)) {
+        errors.push(err_span!(
+          parent,
+          "the first field of the #[napi(extends = {0})] class must be the private `{0}`, the methods of `{0}` are called with it",
+          parent
+        ));
+      }
+      match recorded_class_ts_name(parent, opts.namespace().map(|(js_mod, _)| js_mod)) {
+        Some(ts_name) => Some(NapiClassParent {
+          name: parent.clone(),
+          ts_name,
+        }),
+        None => {
+          errors.push(err_span!(
+            parent,
+            "Did not find the #[napi] class `{}` parsed before, declare it before the classes extending it",
+            parent
+          ));
+          None
+        }
+      }
+    });
+    let field_case = match opts.rename_all() {
+      None => Case::Camel,
+      Some((case, span)) => {
+        if struct_kind != NapiStructKind::Object {
+          errors.push(Diagnostic::span_error(
+            span,
+            "#[napi(rename_all)] can only be applied to a #[napi(object)] struct",
+          ));
+        }
+        match case {
+          "camelCase" => Case::Camel,
+          "PascalCase" => Case::Pascal,
+          "snake_case" => Case::Snake,
+          "SCREAMING_SNAKE_CASE" => Case::UpperSnake,
+          "kebab-case" => Case::Kebab,
+          "SCREAMING-KEBAB-CASE" => Case::Cobol,
+          "lowercase" => Case::Flat,
+          "UPPERCASE" => Case::UpperFlat,
+          _ => {
+            return Err(Diagnostic::span_error(
+              span,
+              "#[napi(rename_all)] only accepts `camelCase`, `PascalCase`, `snake_case`, `SCREAMING_SNAKE_CASE`, `kebab-case`, `SCREAMING-KEBAB-CASE`, `lowercase` or `UPPERCASE`",
+            ))
+          }
+        }
+      }
+    };
+
+    if parent.is_some() {
+      // the parent field is at the same address as the class, so `this` of the parent methods is the parent
+      self.attrs.push(syn::parse_quote!(#[repr(C)]));
+    }
+
     for (i, field) in self.fields.iter_mut().enumerate() {
       match field.vis {
         syn::Visibility::Public(..) => {}
@@ -755,10 +1619,24 @@ impl ConvertToAST for syn::ItemStruct {
 
       let (js_name, name) = match &field.ident {
         Some(ident) => (
-          field_opts.js_name().map_or_else(
-            || ident.unraw().to_string().to_case(Case::Camel),
-            |(js_name, _)| js_name.to_owned(),
-          ),
+          check_js_name(
+            field_opts.js_name().map_or_else(
+              || match field_case {
+                // the Rust names are already snake_case, `to_case` would split the digits
+                Case::Snake => ident.unraw().to_string(),
+                case => ident.unraw().to_string().to_case(case),
+              },
+              |(js_name, _)| js_name.to_owned(),
+            ),
+            // the fields of the classes are the accessors on the prototype
+            if struct_kind == NapiStructKind::Object {
+              JsNameKind::Property
+            } else {
+              JsNameKind::Method
+            },
+            ident.span(),
+            field_opts.js_name().map(|(_, span)| span),
+          )?,
           syn::Member::Named(ident.clone()),
         ),
         None => {
@@ -768,9 +1646,24 @@ impl ConvertToAST for syn::ItemStruct {
       };
 
       let ignored = field_opts.skip().is_some();
-      let readonly = field_opts.readonly().is_some();
+      let writable = field_opts.writable().copied();
+      if field_opts.readonly().is_some() && writable == Some(true) {
+        errors.push(err_span!(
+          field,
+          "#[napi(readonly)] conflicts with #[napi(writable = true)]"
+        ));
+      }
+      let readonly = field_opts.readonly().is_some() || writable == Some(false);
       let skip_typescript = field_opts.skip_typescript().is_some();
       let ts_type = field_opts.ts_type().map(|e| e.0.to_string());
+      let enumerable = field_opts.enumerable().copied();
+      let configurable = field_opts.configurable().copied();
+      if (enumerable.is_some() || configurable.is_some()) && struct_kind == NapiStructKind::Object {
+        errors.push(err_span!(
+          field,
+          "the fields of #[napi(object)] are converted to the plain properties, #[napi(enumerable)] and #[napi(configurable)] only apply to the accessors of the classes"
+        ));
+      }
 
       fields.push(NapiStructField {
         name,
@@ -781,9 +1674,64 @@ impl ConvertToAST for syn::ItemStruct {
         comments: extract_doc_comments(&field.attrs),
         skip_typescript,
         ts_type,
+        enumerable: enumerable.unwrap_or(false),
+        configurable: configurable.unwrap_or(false),
       })
     }
 
+    if self
+      .generics
+      .params
+      .iter()
+      .any(|param| !matches!(param, syn::GenericParam::Type(_)))
+    {
+      errors.push(err_span!(
+        self.generics,
+        "#[napi] struct only supports the type parameters"
+      ));
+    } else if !self.generics.params.is_empty() && struct_kind != NapiStructKind::Object {
+      errors.push(err_span!(
+        self.generics,
+        "#[napi] class can't be generic, the generic struct must be #[napi(object)]"
+      ));
+    }
+
+    let columnar = opts.columnar().is_some();
+    if columnar
+      && (struct_kind != NapiStructKind::Object || is_tuple || !self.generics.params.is_empty())
+    {
+      errors.push(err_span!(
+        struct_name,
+        "#[napi(columnar)] can only be applied to a #[napi(object)] struct with named fields and not generic"
+      ));
+    }
+
+    let use_nullable = opts.use_nullable().copied().unwrap_or(false);
+    if use_nullable && struct_kind != NapiStructKind::Object {
+      errors.push(err_span!(
+        struct_name,
+        "#[napi(use_nullable)] can only be applied to a #[napi(object)] struct"
+      ));
+    }
+
+    let deny_unknown_fields = opts.deny_unknown_fields().is_some();
+    if deny_unknown_fields && (struct_kind != NapiStructKind::Object || is_tuple) {
+      errors.push(err_span!(
+        struct_name,
+        "#[napi(deny_unknown_fields)] can only be applied to a #[napi(object)] struct with named fields"
+      ));
+    }
+
+    let to_string = opts.to_string().is_some();
+    let to_json = opts.to_json().is_some();
+    let inspect = opts.inspect().is_some();
+    if (to_string || to_json || inspect) && struct_kind == NapiStructKind::Object {
+      errors.push(err_span!(
+        struct_name,
+        "#[napi(to_string)], #[napi(to_json)] and #[napi(inspect)] can only be applied to a class, #[napi(object)] is converted to a plain object"
+      ));
+    }
+
     record_struct(&struct_name, js_name.clone(), &opts);
 
     Diagnostic::from_vec(errors).map(|()| Napi {
@@ -796,6 +1744,14 @@ impl ConvertToAST for syn::ItemStruct {
         kind: struct_kind,
         js_mod: opts.namespace().map(|(m, _)| m.to_owned()),
         comments: extract_doc_comments(&self.attrs),
+        columnar,
+        use_nullable,
+        deny_unknown_fields,
+        generics: self.generics.clone(),
+        parent,
+        to_string,
+        to_json,
+        inspect,
       }),
     })
   }
@@ -814,10 +1770,16 @@ impl ConvertToAST for syn::ItemImpl {
     };
 
     let struct_name = extract_path_ident(struct_name)?;
+    check_namespace(impl_opts.namespace())?;
 
     let mut struct_js_name = struct_name.to_string();
     let mut items = vec![];
     let mut task_output_type = None;
+    let is_iterator = matches!(
+      &self.trait_,
+      Some((None, path, _)) if path.segments.last().map(|s| s.ident == "Iterator") == Some(true)
+    );
+    let mut iterator_item = None;
     for item in self.items.iter_mut() {
       if let Some(method) = match item {
         syn::ImplItem::Method(m) => Some(m),
@@ -827,6 +1789,9 @@ impl ConvertToAST for syn::ItemImpl {
               task_output_type = Some(m.ty.clone());
             }
           }
+          if is_iterator && m.ident == *"Item" {
+            iterator_item = Some(m.ty.clone());
+          }
           None
         }
         _ => {
@@ -859,7 +1824,14 @@ impl ConvertToAST for syn::ItemImpl {
           method.attrs.clone(),
           vis,
           Some(&struct_name),
-        )?;
+        );
+        remove_arg_napi_attrs(&mut method.sig);
+        let mut func = func?;
+
+        // the module function is exported next to the class
+        if func.module_function.is_some() && func.js_mod.is_none() {
+          func.js_mod = impl_opts.namespace().map(|(m, _)| m.to_owned());
+        }
 
         items.push(func);
       }
@@ -871,6 +1843,7 @@ impl ConvertToAST for syn::ItemImpl {
         js_name: struct_js_name,
         items,
         task_output_type,
+        iterator_item,
         js_mod: impl_opts.namespace().map(|(m, _)| m.to_owned()),
         comments: extract_doc_comments(&self.attrs),
       }),
@@ -885,27 +1858,166 @@ impl ConvertToAST for syn::ItemEnum {
       _ => bail_span!(self, "only public enum allowed"),
     }
 
-    self.attrs.push(Attribute {
-      pound_token: Default::default(),
-      style: syn::AttrStyle::Outer,
-      bracket_token: Default::default(),
-      path: syn::parse_quote! { derive },
-      tokens: quote! { (Copy, Clone) },
-    });
+    let is_error = opts.error().is_some();
+    // the fields of the data enums are not always `Copy`
+    let is_data_enum = !is_error
+      && self
+        .variants
+        .iter()
+        .any(|v| !matches!(v.fields, syn::Fields::Unit));
+
+    if !is_data_enum && !is_error {
+      self.attrs.push(Attribute {
+        pound_token: Default::default(),
+        style: syn::AttrStyle::Outer,
+        bracket_token: Default::default(),
+        path: syn::parse_quote! { derive },
+        tokens: quote! { (Copy, Clone) },
+      });
+    }
 
-    let js_name = opts
-      .js_name()
-      .map_or_else(|| self.ident.to_string(), |(s, _)| s.to_string());
+    check_namespace(opts.namespace())?;
+    let js_name = check_js_name(
+      opts
+        .js_name()
+        .map_or_else(|| self.ident.to_string(), |(s, _)| s.to_string()),
+      JsNameKind::Export,
+      self.ident.span(),
+      opts.js_name().map(|(_, span)| span),
+    )?;
+
+    let string_case = match opts.string_enum() {
+      None => None,
+      Some(None) => Some(None),
+      Some(Some((case, span))) => Some(Some(match case.as_str() {
+        "lowercase" => Case::Flat,
+        "UPPERCASE" => Case::UpperFlat,
+        "camelCase" => Case::Camel,
+        "snake_case" => Case::Snake,
+        // the same as `#[napi(rename_all)]`, `kebab_case` is accepted as the ident
+        "kebab-case" | "kebab_case" => Case::Kebab,
+        _ => {
+          return Err(Diagnostic::span_error(
+            *span,
+            "#[napi(string_enum)] only accepts `lowercase`, `UPPERCASE`, `camelCase`, `snake_case` or `kebab-case`",
+          ))
+        }
+      })),
+    };
+
+    if let (Some(span), Some(_)) = (opts.error(), &string_case) {
+      return Err(Diagnostic::span_error(
+        *span,
+        "#[napi(error)] can't be #[napi(string_enum)], the variants are the error classes",
+      ));
+    }
+
+    if is_data_enum && string_case.is_some() {
+      bail_span!(
+        self.ident,
+        "#[napi(string_enum)] can't be applied to the enum with the fields, the variants are converted to `{{ type: 'Variant', ...fields }}`"
+      );
+    }
 
     let mut last_variant_val: i32 = -1;
     let variants = self
       .variants
-      .iter()
+      .iter_mut()
       .map(|v| {
-        match v.fields {
-          syn::Fields::Unit => {}
-          _ => bail_span!(v.fields, "Structured enum is not supported in #[napi]"),
-        };
+        let variant_opts = BindgenAttrs::find(&mut v.attrs)?;
+
+        if is_error {
+          return convert_error_variant(v, &variant_opts, &js_name);
+        }
+
+        if is_data_enum {
+          if let Some((_, expr)) = &v.discriminant {
+            bail_span!(
+              expr,
+              "#[napi] enum with the fields can't have discriminants, set the `type` of the variant by #[napi(value = \"...\")]"
+            );
+          }
+          let val = variant_opts
+            .value()
+            .map_or_else(|| v.ident.to_string(), |(value, _)| value.to_owned());
+          let is_tuple = matches!(v.fields, syn::Fields::Unnamed(_));
+          let mut fields = vec![];
+          for (i, field) in v.fields.iter_mut().enumerate() {
+            let field_opts = BindgenAttrs::find(&mut field.attrs)?;
+            let (js_name, name) = match &field.ident {
+              Some(ident) => (
+                check_js_name(
+                  field_opts.js_name().map_or_else(
+                    || ident.unraw().to_string().to_case(Case::Camel),
+                    |(js_name, _)| js_name.to_owned(),
+                  ),
+                  JsNameKind::Property,
+                  ident.span(),
+                  field_opts.js_name().map(|(_, span)| span),
+                )?,
+                syn::Member::Named(ident.clone()),
+              ),
+              None => (format!("field{}", i), syn::Member::Unnamed(i.into())),
+            };
+            if js_name == "type" {
+              bail_span!(
+                field,
+                "`type` is the discriminant of the variants, rename the field by `js_name`"
+              );
+            }
+            fields.push(NapiStructField {
+              name,
+              js_name,
+              ty: field.ty.clone(),
+              getter: true,
+              setter: true,
+              comments: extract_doc_comments(&field.attrs),
+              skip_typescript: field_opts.skip_typescript().is_some(),
+              ts_type: field_opts.ts_type().map(|e| e.0.to_string()),
+              enumerable: false,
+              configurable: false,
+            });
+          }
+          return Ok(NapiEnumVariant {
+            name: v.ident.clone(),
+            val: NapiEnumValue::String(val),
+            comments: extract_doc_comments(&v.attrs),
+            fields,
+            is_tuple,
+            error_class: None,
+          });
+        }
+
+        if let Some(case) = string_case {
+          if let Some((_, expr)) = &v.discriminant {
+            bail_span!(
+              expr,
+              "#[napi(string_enum)] variants can't have discriminants, set the values by #[napi(value = \"...\")]"
+            );
+          }
+          let val = match variant_opts.value() {
+            Some((value, _)) => value.to_owned(),
+            None => match case {
+              Some(case) => v.ident.to_string().to_case(case),
+              None => v.ident.to_string(),
+            },
+          };
+          return Ok(NapiEnumVariant {
+            name: v.ident.clone(),
+            val: NapiEnumValue::String(val),
+            comments: extract_doc_comments(&v.attrs),
+            fields: vec![],
+            is_tuple: false,
+            error_class: None,
+          });
+        }
+
+        if let Some((_, span)) = variant_opts.value() {
+          return Err(Diagnostic::span_error(
+            span,
+            "#[napi(value)] is only supported in #[napi(string_enum)]",
+          ));
+        }
 
         let val = match &v.discriminant {
           Some((_, expr)) => {
@@ -949,8 +2061,11 @@ impl ConvertToAST for syn::ItemEnum {
 
         Ok(NapiEnumVariant {
           name: v.ident.clone(),
-          val,
+          val: NapiEnumValue::Number(val),
           comments: extract_doc_comments(&v.attrs),
+          fields: vec![],
+          is_tuple: false,
+          error_class: None,
         })
       })
       .collect::<BindgenResult<Vec<NapiEnumVariant>>>()?;
@@ -960,6 +2075,9 @@ impl ConvertToAST for syn::ItemEnum {
         name: self.ident.clone(),
         js_name,
         variants,
+        is_string_enum: string_case.is_some(),
+        is_data_enum,
+        is_error,
         js_mod: opts.namespace().map(|(m, _)| m.to_owned()),
         comments: extract_doc_comments(&self.attrs),
         skip_typescript: opts.skip_typescript().is_some(),
@@ -968,23 +2086,292 @@ impl ConvertToAST for syn::ItemEnum {
   }
 }
 
+/// The variant of `#[napi(error)]` is the class `js_name` or `{Enum}{Variant}Error`,
+/// the named fields are the properties of the thrown errors
+fn convert_error_variant(
+  v: &mut syn::Variant,
+  variant_opts: &BindgenAttrs,
+  enum_js_name: &str,
+) -> BindgenResult<NapiEnumVariant> {
+  if let Some((_, expr)) = &v.discriminant {
+    bail_span!(
+      expr,
+      "#[napi(error)] variants can't have discriminants, set the `code` of the errors by #[napi(value = \"...\")]"
+    );
+  }
+  let code = variant_opts.value().map_or_else(
+    || v.ident.to_string().to_case(Case::UpperSnake),
+    |(value, _)| value.to_owned(),
+  );
+  let error_class = check_js_name(
+    variant_opts.js_name().map_or_else(
+      || {
+        format!(
+          "{}{}Error",
+          enum_js_name.strip_suffix("Error").unwrap_or(enum_js_name),
+          v.ident
+        )
+      },
+      |(js_name, _)| js_name.to_owned(),
+    ),
+    JsNameKind::Export,
+    v.ident.span(),
+    variant_opts.js_name().map(|(_, span)| span),
+  )?;
+  let is_tuple = matches!(v.fields, syn::Fields::Unnamed(_));
+  let mut fields = vec![];
+  for field in v.fields.iter_mut() {
+    let field_opts = BindgenAttrs::find(&mut field.attrs)?;
+    if field_opts.skip().is_some() {
+      continue;
+    }
+    let ident = match &field.ident {
+      Some(ident) => ident.clone(),
+      None => bail_span!(
+        field,
+        "the fields of #[napi(error)] are the properties of the errors, name it or #[napi(skip)] it"
+      ),
+    };
+    let js_name = check_js_name(
+      field_opts.js_name().map_or_else(
+        || ident.unraw().to_string().to_case(Case::Camel),
+        |(js_name, _)| js_name.to_owned(),
+      ),
+      JsNameKind::Property,
+      ident.span(),
+      field_opts.js_name().map(|(_, span)| span),
+    )?;
+    if matches!(js_name.as_str(), "name" | "message" | "code" | "stack") {
+      bail_span!(
+        field,
+        "`{}` is set on the errors already, rename the field by `js_name`",
+        js_name
+      );
+    }
+    fields.push(NapiStructField {
+      name: syn::Member::Named(ident),
+      js_name,
+      ty: field.ty.clone(),
+      getter: true,
+      setter: false,
+      comments: extract_doc_comments(&field.attrs),
+      skip_typescript: field_opts.skip_typescript().is_some(),
+      ts_type: field_opts.ts_type().map(|e| e.0.to_string()),
+      enumerable: false,
+      configurable: false,
+    });
+  }
+  Ok(NapiEnumVariant {
+    name: v.ident.clone(),
+    val: NapiEnumValue::String(code),
+    comments: extract_doc_comments(&v.attrs),
+    fields,
+    is_tuple,
+    error_class: Some(error_class),
+  })
+}
+
 impl ConvertToAST for syn::ItemConst {
   fn convert_to_ast(&mut self, opts: BindgenAttrs) -> BindgenResult<Napi> {
+    check_namespace(opts.namespace())?;
     match self.vis {
       Visibility::Public(_) => Ok(Napi {
         item: NapiItem::Const(NapiConst {
           name: self.ident.clone(),
-          js_name: opts
-            .js_name()
-            .map_or_else(|| self.ident.to_string(), |(s, _)| s.to_string()),
+          js_name: check_js_name(
+            opts
+              .js_name()
+              .map_or_else(|| self.ident.to_string(), |(s, _)| s.to_string()),
+            JsNameKind::Export,
+            self.ident.span(),
+            opts.js_name().map(|(_, span)| span),
+          )?,
           type_name: *self.ty.clone(),
           value: *self.expr.clone(),
           js_mod: opts.namespace().map(|(m, _)| m.to_owned()),
           comments: extract_doc_comments(&self.attrs),
           skip_typescript: opts.skip_typescript().is_some(),
+          is_static: false,
+          is_lazy: false,
         }),
       }),
       _ => bail_span!(self, "only public const allowed"),
     }
   }
 }
+
+impl ConvertToAST for syn::ItemStatic {
+  fn convert_to_ast(&mut self, opts: BindgenAttrs) -> BindgenResult<Napi> {
+    check_namespace(opts.namespace())?;
+    if let Some(mutability) = &self.mutability {
+      bail_span!(
+        mutability,
+        "#[napi] can't be applied to a `static mut`, it's read once while registering the module"
+      );
+    }
+    check_env_independent(
+      &self.ty,
+      "the #[napi] static is shared by all the envs and would",
+    )?;
+    match self.vis {
+      Visibility::Public(_) => Ok(Napi {
+        item: NapiItem::Const(NapiConst {
+          name: self.ident.clone(),
+          js_name: check_js_name(
+            opts
+              .js_name()
+              .map_or_else(|| self.ident.to_string(), |(s, _)| s.to_string()),
+            JsNameKind::Export,
+            self.ident.span(),
+            opts.js_name().map(|(_, span)| span),
+          )?,
+          type_name: *self.ty.clone(),
+          value: *self.expr.clone(),
+          js_mod: opts.namespace().map(|(m, _)| m.to_owned()),
+          comments: extract_doc_comments(&self.attrs),
+          skip_typescript: opts.skip_typescript().is_some(),
+          is_static: true,
+          is_lazy: opts.lazy().is_some(),
+        }),
+      }),
+      _ => bail_span!(self, "only public static allowed"),
+    }
+  }
+}
+
+impl ConvertToAST for syn::ItemTrait {
+  fn convert_to_ast(&mut self, opts: BindgenAttrs) -> BindgenResult<Napi> {
+    check_namespace(opts.namespace())?;
+    if !self.generics.params.is_empty() {
+      bail_span!(
+        self.generics,
+        "#[napi] trait can't be generic, the JavaScript objects are converted into `Box<dyn Trait>`"
+      );
+    }
+    for supertrait in self.supertraits.iter() {
+      let is_marker = match supertrait {
+        syn::TypeParamBound::Trait(bound) => matches!(
+          bound.path.segments.last(),
+          Some(segment) if segment.ident == "Send" || segment.ident == "Sync"
+        ),
+        syn::TypeParamBound::Lifetime(lifetime) => lifetime.ident == "static",
+      };
+      if !is_marker {
+        bail_span!(
+          supertrait,
+          "the supertraits of #[napi] trait can only be `Send` and `Sync`, the JavaScript objects implement the trait"
+        );
+      }
+    }
+    let js_name = check_js_name(
+      opts
+        .js_name()
+        .map_or_else(|| self.ident.to_string(), |(s, _)| s.to_string()),
+      JsNameKind::Export,
+      self.ident.span(),
+      opts.js_name().map(|(_, span)| span),
+    )?;
+
+    let mut methods = vec![];
+    for item in self.items.iter_mut() {
+      let method = match item {
+        syn::TraitItem::Method(method) => method,
+        _ => bail_span!(
+          item,
+          "#[napi] trait can only have methods, the JavaScript objects implement the trait"
+        ),
+      };
+      let method_opts = BindgenAttrs::find(&mut method.attrs)?;
+      // the default methods are not implemented by the JavaScript objects
+      if method.default.is_some() {
+        continue;
+      }
+      let sig = &method.sig;
+      if let Some(asyncness) = &sig.asyncness {
+        bail_span!(asyncness, "the methods of #[napi] trait can't be async");
+      }
+      if !sig.generics.params.is_empty() {
+        bail_span!(
+          sig.generics,
+          "the methods of #[napi] trait can't be generic"
+        );
+      }
+      match sig.receiver() {
+        Some(syn::FnArg::Receiver(syn::Receiver {
+          reference: Some(_),
+          mutability: None,
+          ..
+        })) => {}
+        _ => bail_span!(
+          sig,
+          "the methods of #[napi] trait must take `&self`, they are called on the shared JavaScript object"
+        ),
+      }
+      let ret = match &sig.output {
+        syn::ReturnType::Type(_, ty) => match extract_result_ty(ty)? {
+          Some(syn::Type::Tuple(tuple)) if tuple.elems.is_empty() => None,
+          Some(ty) => Some(ty),
+          None => bail_span!(
+            ty,
+            "the methods of #[napi] trait must return `Result<T>`, the JavaScript implementation could throw"
+          ),
+        },
+        syn::ReturnType::Default => bail_span!(
+          sig,
+          "the methods of #[napi] trait must return `Result<T>`, the JavaScript implementation could throw"
+        ),
+      };
+      let mut sig = sig.clone();
+      let mut args = vec![];
+      for (i, arg) in sig.inputs.iter_mut().skip(1).enumerate() {
+        if let syn::FnArg::Typed(pat_type) = arg {
+          if let syn::Type::Reference(_) = *pat_type.ty {
+            bail_span!(
+              pat_type.ty,
+              "the args of #[napi] trait methods must be owned, they could be sent to the JavaScript thread"
+            );
+          }
+          let arg_name = match &*pat_type.pat {
+            syn::Pat::Ident(pat) => pat.ident.unraw().to_string().to_case(Case::Camel),
+            _ => format!("arg{}", i),
+          };
+          args.push((arg_name, (*pat_type.ty).clone()));
+          *pat_type.pat = syn::Pat::Ident(syn::PatIdent {
+            attrs: vec![],
+            by_ref: None,
+            mutability: None,
+            ident: Ident::new(&format!("arg{}", i), Span::call_site()),
+            subpat: None,
+          });
+        }
+      }
+      methods.push(NapiTraitMethod {
+        name: sig.ident.clone(),
+        js_name: check_js_name(
+          method_opts.js_name().map_or_else(
+            || sig.ident.unraw().to_string().to_case(Case::Camel),
+            |(js_name, _)| js_name.to_owned(),
+          ),
+          JsNameKind::Property,
+          sig.ident.span(),
+          method_opts.js_name().map(|(_, span)| span),
+        )?,
+        sig,
+        args,
+        ret,
+        comments: extract_doc_comments(&method.attrs),
+      });
+    }
+
+    Ok(Napi {
+      item: NapiItem::Trait(NapiTrait {
+        name: self.ident.clone(),
+        js_name,
+        methods,
+        js_mod: opts.namespace().map(|(m, _)| m.to_owned()),
+        comments: extract_doc_comments(&self.attrs),
+        skip_typescript: opts.skip_typescript().is_some(),
+      }),
+    })
+  }
+}
@@ -0,0 +1,166 @@
+//! The global allocator of the addon, swapped by the `allocator_mimalloc` or `allocator_jemalloc` feature.
+//!
+//! The feature installs the `#[global_allocator]` in the `cdylib` linking `napi`, so the crate of the addon must not declare another one.
+//! `allocator_mimalloc` takes precedence if both of them are enabled, e.g. by `--all-features`.
+//! The memory held by the allocator is invisible to V8, [`report_external_memory`] reports it, so the GC is triggered by the native allocations as well.
+//!
+//! ```ignore
+//! use napi::allocator::AllocatorStats;
+//! use napi::bindgen_prelude::*;
+//!
+//! #[napi]
+//! pub fn allocator_stats(mut env: Env) -> Result<AllocatorStats> {
+//!   napi::allocator::report_external_memory(&mut env)
+//! }
+//! ```
+
+// `const` initializer of `thread_local!` requires Rust 1.59
+#![allow(clippy::missing_const_for_thread_local)]
+
+use std::cell::Cell;
+
+use crate::bindgen_runtime::{ToNapiValue, TypeName};
+use crate::{sys, Env, NapiRaw, Result, ValueType};
+
+#[cfg(all(feature = "allocator_jemalloc", target_env = "msvc"))]
+compile_error!("jemalloc doesn't support the msvc targets, use `allocator_mimalloc` instead");
+
+#[cfg(feature = "allocator_mimalloc")]
+#[global_allocator]
+static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
+
+#[cfg(all(
+  feature = "allocator_jemalloc",
+  not(feature = "allocator_mimalloc"),
+  not(target_env = "msvc")
+))]
+#[global_allocator]
+static GLOBAL: tikv_jemallocator::Jemalloc = tikv_jemallocator::Jemalloc;
+
+/// `mimalloc`, `jemalloc` or `system`
+#[cfg(feature = "allocator_mimalloc")]
+pub const ALLOCATOR: &str = "mimalloc";
+#[cfg(all(feature = "allocator_jemalloc", not(feature = "allocator_mimalloc")))]
+pub const ALLOCATOR: &str = "jemalloc";
+#[cfg(not(any(feature = "allocator_mimalloc", feature = "allocator_jemalloc")))]
+pub const ALLOCATOR: &str = "system";
+
+/// Passed to JavaScript as `{ allocator, allocated, active, resident }`, in bytes.
+///
+/// The stats not provided by the allocator are `undefined`, all of them for the system allocator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllocatorStats {
+  pub allocator: &'static str,
+  /// Allocated by the application, only provided by jemalloc
+  pub allocated: Option<u64>,
+  /// In the pages held by the allocator, reported to V8 as the external memory
+  pub active: Option<u64>,
+  /// Mapped in the physical memory, including the metadata of the allocator
+  pub resident: Option<u64>,
+}
+
+#[cfg(feature = "allocator_mimalloc")]
+pub fn stats() -> Result<AllocatorStats> {
+  let (mut elapsed, mut user, mut system) = (0, 0, 0);
+  let (mut rss, mut peak_rss, mut commit, mut peak_commit, mut page_faults) = (0, 0, 0, 0, 0);
+  unsafe {
+    libmimalloc_sys::mi_process_info(
+      &mut elapsed,
+      &mut user,
+      &mut system,
+      &mut rss,
+      &mut peak_rss,
+      &mut commit,
+      &mut peak_commit,
+      &mut page_faults,
+    )
+  };
+  Ok(AllocatorStats {
+    allocator: ALLOCATOR,
+    allocated: None,
+    active: Some(commit as u64),
+    resident: Some(rss as u64),
+  })
+}
+
+#[cfg(all(
+  feature = "allocator_jemalloc",
+  not(feature = "allocator_mimalloc"),
+  not(target_env = "msvc")
+))]
+pub fn stats() -> Result<AllocatorStats> {
+  use tikv_jemalloc_ctl::{epoch, stats};
+
+  let to_napi_error = |err: tikv_jemalloc_ctl::Error| {
+    crate::Error::new(
+      crate::Status::GenericFailure,
+      format!("Failed to read the stats of jemalloc, {}", err),
+    )
+  };
+  // the stats are cached by jemalloc until the epoch is advanced
+  epoch::advance().map_err(to_napi_error)?;
+  Ok(AllocatorStats {
+    allocator: ALLOCATOR,
+    allocated: Some(stats::allocated::read().map_err(to_napi_error)? as u64),
+    active: Some(stats::active::read().map_err(to_napi_error)? as u64),
+    resident: Some(stats::resident::read().map_err(to_napi_error)? as u64),
+  })
+}
+
+#[cfg(not(any(feature = "allocator_mimalloc", feature = "allocator_jemalloc")))]
+pub fn stats() -> Result<AllocatorStats> {
+  Ok(AllocatorStats {
+    allocator: ALLOCATOR,
+    allocated: None,
+    active: None,
+    resident: None,
+  })
+}
+
+thread_local! {
+  // every JavaScript thread has its own V8 heap, the worker threads report separately
+  static REPORTED: Cell<i64> = Cell::new(0);
+}
+
+/// Adjusts the external memory of V8 by the change of `active` since the last report on this thread, and returns the stats.
+///
+/// The memory is held by the whole process, so only one JavaScript thread should report it. No-op for the system allocator.
+pub fn report_external_memory(env: &mut Env) -> Result<AllocatorStats> {
+  let stats = stats()?;
+  if let Some(active) = stats.active {
+    let active = active as i64;
+    let change = active - REPORTED.with(|reported| reported.replace(active));
+    if change != 0 {
+      env.adjust_external_memory(change)?;
+    }
+  }
+  Ok(stats)
+}
+
+impl TypeName for AllocatorStats {
+  fn type_name() -> &'static str {
+    "AllocatorStats"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl ToNapiValue for AllocatorStats {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    let env = unsafe { Env::from_raw(env) };
+    let mut stats = env.create_object()?;
+    stats.set_named_property("allocator", env.create_string(val.allocator)?)?;
+    for (key, value) in [
+      ("allocated", val.allocated),
+      ("active", val.active),
+      ("resident", val.resident),
+    ] {
+      if let Some(value) = value {
+        stats.set_named_property(key, env.create_double(value as f64)?)?;
+      }
+    }
+    Ok(unsafe { stats.raw() })
+  }
+}
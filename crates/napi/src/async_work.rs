@@ -2,8 +2,8 @@ use std::ffi::CStr;
 use std::mem;
 use std::os::raw::c_void;
 use std::ptr;
-use std::rc::Rc;
 use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Arc;
 
 use crate::{
   bindgen_runtime::ToNapiValue, check_status, js_values::NapiValue, sys, Env, JsError, JsObject,
@@ -15,7 +15,7 @@ struct AsyncWork<T: Task> {
   deferred: sys::napi_deferred,
   value: Result<mem::MaybeUninit<T::Output>>,
   napi_async_work: sys::napi_async_work,
-  status: Rc<AtomicU8>,
+  status: Arc<AtomicU8>,
 }
 
 pub struct AsyncWorkPromise {
@@ -27,7 +27,7 @@ pub struct AsyncWorkPromise {
   /// 0: not started
   /// 1: completed
   /// 2: canceled
-  pub(crate) status: Rc<AtomicU8>,
+  pub(crate) status: Arc<AtomicU8>,
 }
 
 impl AsyncWorkPromise {
@@ -45,14 +45,14 @@ impl AsyncWorkPromise {
 pub fn run<T: Task>(
   env: sys::napi_env,
   task: T,
-  abort_status: Option<Rc<AtomicU8>>,
+  abort_status: Option<Arc<AtomicU8>>,
 ) -> Result<AsyncWorkPromise> {
   let mut raw_resource = ptr::null_mut();
   check_status!(unsafe { sys::napi_create_object(env, &mut raw_resource) })?;
   let mut raw_promise = ptr::null_mut();
   let mut deferred = ptr::null_mut();
   check_status!(unsafe { sys::napi_create_promise(env, &mut deferred, &mut raw_promise) })?;
-  let task_status = abort_status.unwrap_or_else(|| Rc::new(AtomicU8::new(0)));
+  let task_status = abort_status.unwrap_or_else(|| Arc::new(AtomicU8::new(0)));
   let result = Box::leak(Box::new(AsyncWork {
     inner_task: task,
     deferred,
@@ -4,6 +4,7 @@ use std::ptr;
 use std::rc::Rc;
 use std::sync::atomic::{AtomicBool, Ordering};
 
+use crate::js_values::raw_scope::HandleScopeGuard;
 use crate::{bindgen_prelude::*, check_status, sys, Result};
 
 thread_local! {
@@ -14,8 +15,11 @@ thread_local! {
 
 pub struct CallbackInfo<const N: usize> {
   env: sys::napi_env,
+  callback_info: sys::napi_callback_info,
   pub this: sys::napi_value,
   pub args: [sys::napi_value; N],
+  // the handle scope of the callback
+  _scope: HandleScopeGuard,
 }
 
 impl<const N: usize> CallbackInfo<N> {
@@ -25,6 +29,7 @@ impl<const N: usize> CallbackInfo<N> {
     callback_info: sys::napi_callback_info,
     required_argc: Option<usize>,
   ) -> Result<Self> {
+    let scope = HandleScopeGuard::open();
     let mut this = ptr::null_mut();
     let mut args = [ptr::null_mut(); N];
     let mut argc = N;
@@ -55,18 +60,60 @@ impl<const N: usize> CallbackInfo<N> {
       }
     }
 
-    Ok(Self { env, this, args })
+    Ok(Self {
+      env,
+      callback_info,
+      this,
+      args,
+      _scope: scope,
+    })
   }
 
   pub fn get_arg(&self, index: usize) -> sys::napi_value {
     self.args[index]
   }
 
+  /// The arguments from `index` to the last one passed, collected by the `#[napi(variadic)]` param
+  pub fn get_rest_args(&self, index: usize) -> Result<Vec<sys::napi_value>> {
+    let mut argc = 0;
+    check_status!(
+      unsafe {
+        sys::napi_get_cb_info(
+          self.env,
+          self.callback_info,
+          &mut argc,
+          ptr::null_mut(),
+          ptr::null_mut(),
+          ptr::null_mut(),
+        )
+      },
+      "Failed to get the count of the arguments."
+    )?;
+    if argc <= index {
+      return Ok(vec![]);
+    }
+    let mut args = vec![ptr::null_mut(); argc];
+    check_status!(
+      unsafe {
+        sys::napi_get_cb_info(
+          self.env,
+          self.callback_info,
+          &mut argc,
+          args.as_mut_ptr(),
+          ptr::null_mut(),
+          ptr::null_mut(),
+        )
+      },
+      "Failed to get the rest arguments."
+    )?;
+    Ok(args.split_off(index))
+  }
+
   pub fn this(&self) -> sys::napi_value {
     self.this
   }
 
-  pub fn construct<T: 'static>(&self, js_name: &str, obj: T) -> Result<sys::napi_value> {
+  pub fn construct<T: TypeName + 'static>(&self, js_name: &str, obj: T) -> Result<sys::napi_value> {
     let obj = Box::new(obj);
     let this = self.this();
     let value_ref = Box::into_raw(obj) as *mut c_void;
@@ -86,13 +133,14 @@ impl<const N: usize> CallbackInfo<N> {
         "Failed to initialize class `{}`",
         js_name,
       )?;
+      tag_class_instance::<T>(self.env, this)?;
     };
 
     Reference::<T>::add_ref(value_ref, (value_ref, object_ref, finalize_callbacks_ptr));
     Ok(this)
   }
 
-  pub fn factory<T: 'static>(&self, js_name: &str, obj: T) -> Result<sys::napi_value> {
+  pub fn factory<T: TypeName + 'static>(&self, js_name: &str, obj: T) -> Result<sys::napi_value> {
     let this = self.this();
     let mut instance = ptr::null_mut();
     unsafe {
@@ -124,6 +172,7 @@ impl<const N: usize> CallbackInfo<N> {
         "Failed to initialize class `{}`",
         js_name,
       )?;
+      tag_class_instance::<T>(self.env, instance)?;
 
       Reference::<T>::add_ref(value_ref, (value_ref, object_ref, finalize_callbacks_ptr));
     };
@@ -155,6 +204,7 @@ impl<const N: usize> CallbackInfo<N> {
     let mut wrapped_val: *mut c_void = std::ptr::null_mut();
 
     unsafe {
+      check_class_instance::<T>(self.env, self.this)?;
       check_status!(
         sys::napi_unwrap(self.env, self.this, &mut wrapped_val),
         "Failed to unwrap exclusive reference of `{}` type from napi value",
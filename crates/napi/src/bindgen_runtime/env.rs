@@ -7,6 +7,7 @@ use super::Array;
 
 pub use crate::Env;
 
+// the thread could have the other env after the one cached is torn down, the values are only reused on the same env
 thread_local! {
   static JS_UNDEFINED: RefCell<Option<JsUndefined>> = RefCell::default();
   static JS_NULL: RefCell<Option<JsNull>> = RefCell::default();
@@ -20,7 +21,9 @@ impl Env {
   /// Get [JsUndefined](./struct.JsUndefined.html) value
   pub fn get_undefined(&self) -> Result<JsUndefined> {
     if let Some(js_undefined) = JS_UNDEFINED.with(|x| *x.borrow()) {
-      return Ok(js_undefined);
+      if js_undefined.0.env == self.0 {
+        return Ok(js_undefined);
+      }
     }
     let mut raw_value = ptr::null_mut();
     check_status!(unsafe { sys::napi_get_undefined(self.0, &mut raw_value) })?;
@@ -31,7 +34,9 @@ impl Env {
 
   pub fn get_null(&self) -> Result<JsNull> {
     if let Some(js_null) = JS_NULL.with(|cell| *cell.borrow()) {
-      return Ok(js_null);
+      if js_null.0.env == self.0 {
+        return Ok(js_null);
+      }
     }
     let mut raw_value = ptr::null_mut();
     check_status!(unsafe { sys::napi_get_null(self.0, &mut raw_value) })?;
@@ -52,6 +57,7 @@ impl Env {
       value: global,
       env: self.0,
       value_type: crate::ValueType::Object,
+      scope: crate::HandleScopeId::current(),
     }))
   }
 }
@@ -0,0 +1,120 @@
+//! The JavaScript values created once per env and kept by the references, like the classes evaluated from the scripts.
+//!
+//! The references are deleted by the cleanup hook of the env when it's torn down,
+//! so the next env created at the same address never gets the values of the torn down one.
+//! The teardown can't be observed without `napi3`, the values are kept until the thread exits then,
+//! every worker thread has its own cache.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+#[cfg(feature = "napi3")]
+use std::ffi::c_void;
+use std::ptr;
+
+use crate::{check_status, sys, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum EnvCacheKey {
+  /// The value of the script, by the name of it
+  Script(&'static str),
+  /// The constructor of the `#[napi(error)]` class, by the name of it
+  ErrorClass(&'static str),
+  /// The cache of a `Memoized` argument, by the address of the `MemoCache` of it
+  MemoCache(usize),
+}
+
+thread_local! {
+  // the env is only used on the thread it's created on
+  static ENV_CACHE: RefCell<HashMap<usize, HashMap<EnvCacheKey, sys::napi_ref>>> = Default::default();
+}
+
+/// The value of `key` on the env, created by `create` on the first call
+///
+/// # Safety
+///
+/// `env` must be the env of the current JavaScript thread
+pub(crate) unsafe fn get_or_create<F>(
+  env: sys::napi_env,
+  key: EnvCacheKey,
+  create: F,
+) -> Result<sys::napi_value>
+where
+  F: FnOnce() -> Result<sys::napi_value>,
+{
+  let cached = ENV_CACHE.with(|cache| {
+    cache
+      .borrow()
+      .get(&(env as usize))
+      .and_then(|values| values.get(&key))
+      .copied()
+  });
+  let mut value = ptr::null_mut();
+  if let Some(value_ref) = cached {
+    check_status!(unsafe { sys::napi_get_reference_value(env, value_ref, &mut value) })?;
+    return Ok(value);
+  }
+  // `create` could cache the other values of the env, e.g. the parent of an error class
+  value = create()?;
+  let mut value_ref = ptr::null_mut();
+  check_status!(unsafe { sys::napi_create_reference(env, value, 1, &mut value_ref) })?;
+  #[cfg(feature = "napi3")]
+  if !ENV_CACHE.with(|cache| cache.borrow().contains_key(&(env as usize))) {
+    if let Err(e) = check_status!(
+      unsafe { sys::napi_add_env_cleanup_hook(env, Some(clear_env_cache), env as *mut c_void) },
+      "Failed to add the cleanup hook of the cached values"
+    ) {
+      unsafe { sys::napi_delete_reference(env, value_ref) };
+      return Err(e);
+    }
+  }
+  ENV_CACHE.with(|cache| {
+    cache
+      .borrow_mut()
+      .entry(env as usize)
+      .or_default()
+      .insert(key, value_ref)
+  });
+  Ok(value)
+}
+
+/// The value of the script `source` evaluated once per env, `name` is the name of it in the errors
+///
+/// # Safety
+///
+/// `env` must be the env of the current JavaScript thread
+pub(crate) unsafe fn get_or_run_script(
+  env: sys::napi_env,
+  name: &'static str,
+  source: &'static str,
+) -> Result<sys::napi_value> {
+  unsafe {
+    get_or_create(env, EnvCacheKey::Script(name), || {
+      let mut script = ptr::null_mut();
+      check_status!(sys::napi_create_string_utf8(
+        env,
+        source.as_ptr() as *const _,
+        source.len(),
+        &mut script,
+      ))?;
+      let mut value = ptr::null_mut();
+      check_status!(
+        sys::napi_run_script(env, script, &mut value),
+        "Failed to evaluate {}",
+        name
+      )?;
+      Ok(value)
+    })
+  }
+}
+
+#[cfg(feature = "napi3")]
+unsafe extern "C" fn clear_env_cache(env: *mut c_void) {
+  // the thread local could be destroyed already if the env is torn down while the thread exits
+  let values = ENV_CACHE
+    .try_with(|cache| cache.borrow_mut().remove(&(env as usize)))
+    .ok()
+    .flatten();
+  for value_ref in values.into_iter().flat_map(|values| values.into_values()) {
+    unsafe { sys::napi_delete_reference(env as sys::napi_env, value_ref) };
+  }
+}
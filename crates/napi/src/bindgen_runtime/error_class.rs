@@ -0,0 +1,70 @@
+use std::ptr;
+
+use super::env_cache::{get_or_create, get_or_run_script, EnvCacheKey};
+use super::{FromNapiValue, Object, ToNapiValue, Undefined};
+use crate::{check_status, sys, ErrorClass, Result};
+
+/// Creates the class `name` extending `Parent`, or `Error` if `Parent` is `undefined`.
+///
+/// The class keeps the `name`, so `error.name` and the first line of `error.stack` are the name of the class.
+const ERROR_CLASS_FACTORY: &str = r#"(function defineErrorClass(name, Parent) {
+  const ErrorClass = { [name]: class extends (Parent || Error) {} }[name]
+  Object.defineProperty(ErrorClass.prototype, 'name', {
+    value: name,
+    writable: true,
+    configurable: true,
+  })
+  return ErrorClass
+})"#;
+
+/// The constructor of the error class `name` on the env, created on the first call.
+/// `base` is the class extended by it, `Error` is extended if it's `None`.
+///
+/// # Safety
+///
+/// `env` must be the env of the current JavaScript thread
+#[doc(hidden)]
+pub unsafe fn get_error_class(
+  env: sys::napi_env,
+  name: &'static str,
+  base: Option<&'static str>,
+) -> Result<sys::napi_value> {
+  unsafe {
+    get_or_create(env, EnvCacheKey::ErrorClass(name), || {
+      let parent = match base {
+        Some(base) => get_error_class(env, base, None)?,
+        None => Undefined::to_napi_value(env, ())?,
+      };
+      let factory =
+        get_or_run_script(env, "the factory of the error classes", ERROR_CLASS_FACTORY)?;
+      let args = [<&str>::to_napi_value(env, name)?, parent];
+      let mut global = ptr::null_mut();
+      check_status!(sys::napi_get_global(env, &mut global))?;
+      let mut class = ptr::null_mut();
+      check_status!(
+        sys::napi_call_function(env, global, factory, 2, args.as_ptr(), &mut class),
+        "Failed to create the error class `{}`",
+        name
+      )?;
+      Ok(class)
+    })
+  }
+}
+
+/// `new ErrorClass(message)` with the `code` and the properties of `error`
+pub(crate) unsafe fn create_class_error(
+  env: sys::napi_env,
+  error: &dyn ErrorClass,
+  message: sys::napi_value,
+) -> Result<sys::napi_value> {
+  let class = unsafe { get_error_class(env, error.class_name(), Some(error.base_class_name())) }?;
+  let mut js_error = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_new_instance(env, class, 1, [message].as_ptr(), &mut js_error) },
+    "Failed to create the instance of `{}`",
+    error.class_name()
+  )?;
+  unsafe { Object::from_napi_value(env, js_error) }?.set("code", error.code())?;
+  unsafe { error.set_properties(env, js_error) }?;
+  Ok(js_error)
+}
@@ -4,46 +4,64 @@ use crate::{check_status, sys, Error, JsUnknown, NapiRaw, NapiValue, Result, Sta
 
 mod array;
 mod arraybuffer;
+#[cfg(all(feature = "tokio_rt", feature = "napi4"))]
+mod async_iterator;
 #[cfg(feature = "napi6")]
 mod bigint;
 mod boolean;
 mod buffer;
+mod columnar;
 #[cfg(all(feature = "chrono_date", feature = "napi5"))]
 mod date;
 mod either;
 mod external;
 mod function;
+mod iterator;
 mod map;
+mod memoized;
 mod nil;
 mod number;
 mod object;
 #[cfg(all(feature = "tokio_rt", feature = "napi4"))]
 mod promise;
+#[cfg(all(feature = "tokio_rt", feature = "napi4"))]
+mod readable_stream;
 #[cfg(feature = "serde-json")]
 mod serde;
 mod string;
 mod symbol;
 mod task;
+mod this;
+mod trait_object;
+mod tuple;
 mod value_ref;
 
 #[cfg(feature = "napi5")]
 pub use crate::JsDate as Date;
 pub use array::*;
 pub use arraybuffer::*;
+#[cfg(all(feature = "tokio_rt", feature = "napi4"))]
+pub use async_iterator::*;
 #[cfg(feature = "napi6")]
 pub use bigint::*;
 pub use buffer::*;
+pub use columnar::*;
 pub use either::*;
 pub use external::*;
-#[cfg(feature = "napi4")]
 pub use function::*;
+pub use iterator::*;
+pub use memoized::*;
 pub use nil::*;
 pub use object::*;
 #[cfg(all(feature = "tokio_rt", feature = "napi4"))]
 pub use promise::*;
+#[cfg(all(feature = "tokio_rt", feature = "napi4"))]
+pub use readable_stream::*;
 pub use string::*;
 pub use symbol::*;
 pub use task::*;
+pub use this::*;
+pub use trait_object::*;
 pub use value_ref::*;
 
 #[cfg(feature = "latin1")]
@@ -84,6 +102,89 @@ impl<T: NapiValue> FromNapiValue for T {
   }
 }
 
+/// Track the path of the nested value failed to convert in the error, e.g. `options.points[1].x`, see [`Error::path`].
+///
+/// `segment` is only called if the conversion fails.
+#[doc(hidden)]
+#[inline]
+pub fn with_value_path<T, S: AsRef<str>>(
+  result: Result<T>,
+  segment: impl FnOnce() -> S,
+) -> Result<T> {
+  result.map_err(|err| prefix_value_path(err, segment().as_ref(), false))
+}
+
+/// Like [`with_value_path`], for the arguments of the functions, the errors of the arguments themselves are kept as is.
+#[doc(hidden)]
+#[inline]
+pub fn with_arg_path<T>(result: Result<T>, arg_name: &str) -> Result<T> {
+  result.map_err(|err| prefix_value_path(err, arg_name, true))
+}
+
+fn prefix_value_path(mut err: Error, segment: &str, nested_only: bool) -> Error {
+  err.path = match err.path.take() {
+    // the error of the value at `segment` itself, e.g. the unknown fields of it
+    Some(path) if path.is_empty() => Some(segment.to_owned()),
+    Some(path) if path.starts_with('[') => Some(format!("{}{}", segment, path)),
+    Some(path) => Some(format!("{}.{}", segment, path)),
+    None if nested_only => return err,
+    None => Some(segment.to_owned()),
+  };
+  err
+}
+
+/// Check `napi_val` is an object before reading the fields of it, instead of failing deep in the napi calls.
+///
+/// Only checked in the debug builds.
+#[doc(hidden)]
+#[inline]
+pub unsafe fn debug_validate_object(env: sys::napi_env, napi_val: sys::napi_value) -> Result<()> {
+  #[cfg(debug_assertions)]
+  {
+    let mut result = -1;
+    check_status!(
+      unsafe { sys::napi_typeof(env, napi_val, &mut result) },
+      "Failed to detect napi value type",
+    )?;
+    let received_type = ValueType::from(result);
+    if received_type != ValueType::Object && received_type != ValueType::Function {
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!("Expect value to be Object, but received {}", received_type),
+      ));
+    }
+  }
+  #[cfg(not(debug_assertions))]
+  {
+    let _ = (env, napi_val);
+  }
+  Ok(())
+}
+
+/// The error of the `napi_val` not readable as the `expected` type, reported the same as the errors of [`ValidateNapiValue`],
+/// `Expect value to be Number, but received String`.
+pub(crate) unsafe fn unexpected_type_error(
+  env: sys::napi_env,
+  napi_val: sys::napi_value,
+  status: sys::napi_status,
+  expected: ValueType,
+) -> Error {
+  let mut result = -1;
+  let received_type =
+    if unsafe { sys::napi_typeof(env, napi_val, &mut result) } == sys::Status::napi_ok {
+      ValueType::from(result)
+    } else {
+      ValueType::Unknown
+    };
+  Error::new(
+    Status::from(status),
+    format!(
+      "Expect value to be {}, but received {}",
+      expected, received_type
+    ),
+  )
+}
+
 pub trait FromNapiValue: Sized {
   /// # Safety
   ///
@@ -210,7 +311,7 @@ where
       Ok(v) => unsafe { T::to_napi_value(env, v) },
       Err(e) => {
         let error_code = unsafe { String::to_napi_value(env, format!("{:?}", e.status))? };
-        let reason = unsafe { String::to_napi_value(env, e.reason)? };
+        let reason = unsafe { String::to_napi_value(env, e.message().into_owned())? };
         let mut error = ptr::null_mut();
         check_status!(
           unsafe { sys::napi_create_error(env, error_code, reason, &mut error) },
@@ -1,6 +1,6 @@
 use std::ptr;
 
-use crate::{bindgen_prelude::*, check_status, sys, JsObject, Value, ValueType};
+use crate::{bindgen_prelude::*, check_status, sys, HandleScopeId, JsObject, Value, ValueType};
 
 pub struct Array {
   env: sys::napi_env,
@@ -38,7 +38,10 @@ impl Array {
         index,
       )?;
 
-      Ok(Some(T::from_napi_value(self.env, ret)?))
+      Ok(Some(with_value_path(
+        T::from_napi_value(self.env, ret),
+        || format!("[{}]", index),
+      )?))
     }
   }
 
@@ -77,6 +80,7 @@ impl Array {
       env: self.env,
       value: new_raw_value,
       value_type: ValueType::Object,
+      scope: HandleScopeId::current(),
     }))
   }
 }
@@ -4,7 +4,9 @@ use std::ops::{Deref, DerefMut};
 use std::ptr;
 
 pub use crate::js_values::TypedArrayType;
-use crate::{check_status, sys, Error, Result, Status};
+use crate::{
+  bindgen_runtime::is_external_buffer_disallowed, check_status, sys, Error, Result, Status,
+};
 
 use super::{FromNapiValue, ToNapiValue, TypeName, ValidateNapiValue};
 
@@ -176,8 +178,19 @@ macro_rules! impl_typed_array {
           val.length,
           val.finalizer_notify,
         )));
-        check_status!(
-          unsafe {
+        if is_external_buffer_disallowed() {
+          let mut data = ptr::null_mut();
+          let status =
+            unsafe { sys::napi_create_arraybuffer(env, length, &mut data, &mut arraybuffer_value) };
+          // `data` could be null for the empty arraybuffer
+          if status == sys::Status::napi_ok && length > 0 {
+            unsafe { ptr::copy_nonoverlapping(val.data as *const u8, data as *mut u8, length) };
+          }
+          // the data is copied, or not taken by the failed arraybuffer
+          unsafe { finalizer::<$rust_type>(env, val.data as *mut c_void, hint_ptr as *mut c_void) };
+          check_status!(status, "Create arraybuffer failed")?;
+        } else {
+          let status = unsafe {
             sys::napi_create_external_arraybuffer(
               env,
               val.data as *mut c_void,
@@ -186,9 +199,14 @@ macro_rules! impl_typed_array {
               hint_ptr as *mut c_void,
               &mut arraybuffer_value,
             )
-          },
-          "Create external arraybuffer failed"
-        )?;
+          };
+          if status != sys::Status::napi_ok {
+            unsafe {
+              finalizer::<$rust_type>(env, val.data as *mut c_void, hint_ptr as *mut c_void)
+            };
+          }
+          check_status!(status, "Create external arraybuffer failed")?;
+        }
         let mut napi_val = ptr::null_mut();
         check_status!(
           unsafe {
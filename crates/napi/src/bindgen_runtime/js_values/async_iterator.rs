@@ -0,0 +1,236 @@
+use std::ffi::c_void;
+use std::pin::Pin;
+use std::ptr;
+use std::sync::Arc;
+
+use futures_core::Stream;
+use tokio::sync::Mutex;
+
+use super::readable_stream::NextChunk;
+use super::{IteratorResult, ToNapiValue, TypeName};
+use crate::bindgen_runtime::env_cache::get_or_run_script;
+use crate::{
+  check_status, sys, tokio_runtime::execute_tokio_future, Error, JsError, Result, Status, ValueType,
+};
+
+/// Creates the async iterator calling `pull(state)` for the next item, evaluated once per env.
+///
+/// The calls of `next()` are queued, so the items are pulled one by one in order even if `next()` is not awaited.
+const ASYNC_ITERATOR_FACTORY: &str = r#"(function createAsyncIterator(pull, close, state) {
+  let done = false
+  let queue = Promise.resolve()
+  return {
+    next() {
+      const result = queue.then(() =>
+        done
+          ? { done: true, value: undefined }
+          : pull(state).then(
+              (result) => {
+                done = result.done
+                return result
+              },
+              (err) => {
+                done = true
+                throw err
+              },
+            ),
+      )
+      queue = result.catch(() => {})
+      return result
+    },
+    return(value) {
+      done = true
+      const result = queue.then(() => close(state)).then(() => ({ done: true, value }))
+      queue = result.catch(() => {})
+      return result
+    },
+    [Symbol.asyncIterator]() {
+      return this
+    },
+  }
+})"#;
+
+/// `None` after the iterator is closed by `return()`, which is called by `break` of `for await`
+type ItemStream<T> = Arc<Mutex<Option<Pin<Box<dyn Stream<Item = Result<T>> + Send>>>>>;
+
+/// The async iterator of the items of a `Stream`, which works with `for await`.
+///
+/// The `Stream` is polled in the tokio runtime only when `next()` is called, so the items are not buffered.
+/// The iteration ends when the `Stream` ends, or is thrown the error of the first `Err` item.
+/// Returned by the functions of `#[napi(async_iterator)]`, it could also be returned directly.
+pub struct AsyncIterator<T> {
+  stream: ItemStream<T>,
+}
+
+impl<T: 'static + Send + ToNapiValue> AsyncIterator<T> {
+  pub fn new<S>(stream: S) -> Self
+  where
+    S: 'static + Send + Stream<Item = Result<T>>,
+  {
+    Self {
+      stream: Arc::new(Mutex::new(Some(Box::pin(stream)))),
+    }
+  }
+}
+
+impl<T> TypeName for AsyncIterator<T> {
+  fn type_name() -> &'static str {
+    "AsyncIterator"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl<T: 'static + Send + ToNapiValue> ToNapiValue for AsyncIterator<T> {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    let factory =
+      unsafe { get_or_run_script(env, "the factory of AsyncIterator", ASYNC_ITERATOR_FACTORY) }?;
+    let pull = unsafe { create_function(env, "pull", pull_item::<T>) }?;
+    let close = unsafe { create_function(env, "close", close_stream::<T>) }?;
+    let mut state = ptr::null_mut();
+    check_status!(
+      unsafe {
+        sys::napi_create_external(
+          env,
+          Box::into_raw(Box::new(val.stream)) as *mut c_void,
+          Some(drop_stream::<T>),
+          ptr::null_mut(),
+          &mut state,
+        )
+      },
+      "Failed to create the state of AsyncIterator"
+    )?;
+    let mut global = ptr::null_mut();
+    check_status!(unsafe { sys::napi_get_global(env, &mut global) })?;
+    let mut iterator = ptr::null_mut();
+    check_status!(
+      unsafe {
+        sys::napi_call_function(
+          env,
+          global,
+          factory,
+          3,
+          [pull, close, state].as_ptr(),
+          &mut iterator,
+        )
+      },
+      "Failed to create AsyncIterator"
+    )?;
+    Ok(iterator)
+  }
+}
+
+unsafe fn create_function(
+  env: sys::napi_env,
+  name: &str,
+  callback: unsafe extern "C" fn(sys::napi_env, sys::napi_callback_info) -> sys::napi_value,
+) -> Result<sys::napi_value> {
+  let mut function = ptr::null_mut();
+  check_status!(
+    unsafe {
+      sys::napi_create_function(
+        env,
+        name.as_ptr() as *const _,
+        name.len(),
+        Some(callback),
+        ptr::null_mut(),
+        &mut function,
+      )
+    },
+    "Failed to create the {} function of AsyncIterator",
+    name
+  )?;
+  Ok(function)
+}
+
+/// The `Stream` of `state`, the only argument of `pull` and `close`
+unsafe fn get_stream<T>(env: sys::napi_env, cb: sys::napi_callback_info) -> Result<ItemStream<T>> {
+  let mut argc = 1;
+  let mut state = ptr::null_mut();
+  check_status!(
+    unsafe {
+      sys::napi_get_cb_info(
+        env,
+        cb,
+        &mut argc,
+        &mut state,
+        ptr::null_mut(),
+        ptr::null_mut(),
+      )
+    },
+    "Failed to get the state of AsyncIterator"
+  )?;
+  let mut stream = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_get_value_external(env, state, &mut stream) },
+    "Failed to get the state of AsyncIterator"
+  )?;
+  if stream.is_null() {
+    return Err(Error::new(
+      Status::InvalidArg,
+      "Invalid state of AsyncIterator".to_owned(),
+    ));
+  }
+  Ok(Arc::clone(unsafe { &*(stream as *const ItemStream<T>) }))
+}
+
+/// `pull(state)`, returns the `Promise` of `{ done, value }` of the next item
+unsafe extern "C" fn pull_item<T: 'static + Send + ToNapiValue>(
+  env: sys::napi_env,
+  cb: sys::napi_callback_info,
+) -> sys::napi_value {
+  let result = unsafe { get_stream::<T>(env, cb) }.and_then(|stream| {
+    execute_tokio_future(
+      env,
+      async move {
+        let mut stream = stream.lock().await;
+        match stream.as_mut() {
+          Some(stream) => NextChunk(stream.as_mut()).await.transpose(),
+          None => Ok(None),
+        }
+      },
+      |env, item| unsafe { IteratorResult::<T>::to_napi_value(env, IteratorResult(item)) },
+    )
+  });
+  match result {
+    Ok(promise) => promise,
+    Err(err) => {
+      unsafe { JsError::from(err).throw_into(env) };
+      ptr::null_mut()
+    }
+  }
+}
+
+/// `close(state)`, drops the `Stream` before the iterator is garbage collected
+unsafe extern "C" fn close_stream<T: 'static + Send + ToNapiValue>(
+  env: sys::napi_env,
+  cb: sys::napi_callback_info,
+) -> sys::napi_value {
+  let result = unsafe { get_stream::<T>(env, cb) }.and_then(|stream| {
+    execute_tokio_future(
+      env,
+      async move {
+        stream.lock().await.take();
+        Ok(())
+      },
+      |env, _| unsafe { <()>::to_napi_value(env, ()) },
+    )
+  });
+  match result {
+    Ok(promise) => promise,
+    Err(err) => {
+      unsafe { JsError::from(err).throw_into(env) };
+      ptr::null_mut()
+    }
+  }
+}
+
+unsafe extern "C" fn drop_stream<T>(
+  _env: sys::napi_env,
+  finalize_data: *mut c_void,
+  _finalize_hint: *mut c_void,
+) {
+  drop(unsafe { Box::from_raw(finalize_data as *mut ItemStream<T>) });
+}
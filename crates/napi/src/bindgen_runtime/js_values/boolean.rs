@@ -1,3 +1,4 @@
+use super::unexpected_type_error;
 use crate::{bindgen_prelude::*, check_status, sys, ValueType};
 
 impl TypeName for bool {
@@ -33,10 +34,11 @@ impl FromNapiValue for bool {
   unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
     let mut ret = false;
 
-    check_status!(
-      unsafe { sys::napi_get_value_bool(env, napi_val, &mut ret) },
-      "Failed to convert napi value into rust type `bool`",
-    )?;
+    let status = unsafe { sys::napi_get_value_bool(env, napi_val, &mut ret) };
+    if status == sys::Status::napi_boolean_expected {
+      return Err(unsafe { unexpected_type_error(env, napi_val, status, ValueType::Boolean) });
+    }
+    check_status!(status, "Failed to convert napi value into rust type `bool`",)?;
 
     Ok(ret)
   }
@@ -171,6 +171,28 @@ impl ToNapiValue for Buffer {
     }
     let len = val.inner.len();
     let mut ret = ptr::null_mut();
+    if is_external_buffer_disallowed() {
+      check_status!(
+        unsafe {
+          sys::napi_create_buffer_copy(
+            env,
+            len,
+            val.inner.as_ptr() as *const _,
+            ptr::null_mut(),
+            &mut ret,
+          )
+        },
+        "Failed to create napi buffer"
+      )?;
+      unsafe {
+        drop_buffer(
+          env,
+          val.inner.as_mut_ptr() as *mut _,
+          Box::into_raw(Box::new((len, val.capacity))) as *mut _,
+        )
+      };
+      return Ok(ret);
+    }
     check_status!(
       unsafe {
         sys::napi_create_external_buffer(
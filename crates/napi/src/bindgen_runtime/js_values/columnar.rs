@@ -0,0 +1,104 @@
+use std::ffi::CString;
+use std::ptr;
+
+use crate::{bindgen_runtime::env_cache::get_or_run_script, check_status, sys, Result};
+
+use super::ToNapiValue;
+
+/// The accessor class of the columns, evaluated once per env
+const COLUMNAR_ROWS_CLASS: &str = r#"(class ColumnarRows {
+  constructor(columns, length) {
+    Object.defineProperty(this, 'fields', { value: Object.keys(columns) })
+    this.columns = columns
+    this.length = length
+  }
+
+  get(index) {
+    if (index < 0 || index >= this.length) {
+      return undefined
+    }
+    const row = {}
+    for (const field of this.fields) {
+      row[field] = this.columns[field][index]
+    }
+    return row
+  }
+
+  toArray() {
+    const rows = new Array(this.length)
+    for (let i = 0; i < this.length; i++) {
+      rows[i] = this.get(i)
+    }
+    return rows
+  }
+
+  *[Symbol.iterator]() {
+    for (let i = 0; i < this.length; i++) {
+      yield this.get(i)
+    }
+  }
+})"#;
+
+/// Rows of a `#[napi(object, columnar)]` struct transferred as one column per field.
+///
+/// Numeric fields become typed arrays and the others become arrays, so only a few JavaScript values are created no matter how many rows there are.
+/// The JavaScript side receives a `ColumnarRows` with `columns`, `length`, `get(index)`, `toArray()` and iterates the rows lazily.
+pub struct Columnar<T>(pub Vec<T>);
+
+impl<T> From<Vec<T>> for Columnar<T> {
+  fn from(rows: Vec<T>) -> Self {
+    Columnar(rows)
+  }
+}
+
+#[doc(hidden)]
+/// Implemented by `#[napi(object, columnar)]`
+pub trait ToColumns: Sized {
+  /// JavaScript names of the fields, in the order of the columns
+  const FIELDS: &'static [&'static str];
+
+  /// # Safety
+  ///
+  /// `env` must be the env of the current JavaScript thread
+  unsafe fn to_columns(env: sys::napi_env, rows: Vec<Self>) -> Result<Vec<sys::napi_value>>;
+}
+
+impl<T: ToColumns> ToNapiValue for Columnar<T> {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    let length = val.0.len();
+    let columns = unsafe { T::to_columns(env, val.0) }?;
+    let mut columns_object = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_create_object(env, &mut columns_object) },
+      "Failed to create the columns object"
+    )?;
+    for (field, column) in T::FIELDS.iter().zip(columns) {
+      let field = CString::new(*field)?;
+      check_status!(
+        unsafe { sys::napi_set_named_property(env, columns_object, field.as_ptr(), column) },
+        "Failed to set column `{}`",
+        field.to_string_lossy(),
+      )?;
+    }
+    let length = unsafe { u32::to_napi_value(env, length as u32) }?;
+    let constructor = unsafe { columnar_rows_constructor(env) }?;
+    let mut rows = ptr::null_mut();
+    check_status!(
+      unsafe {
+        sys::napi_new_instance(
+          env,
+          constructor,
+          2,
+          [columns_object, length].as_ptr(),
+          &mut rows,
+        )
+      },
+      "Failed to create ColumnarRows"
+    )?;
+    Ok(rows)
+  }
+}
+
+unsafe fn columnar_rows_constructor(env: sys::napi_env) -> Result<sys::napi_value> {
+  unsafe { get_or_run_script(env, "ColumnarRows", COLUMNAR_ROWS_CLASS) }
+}
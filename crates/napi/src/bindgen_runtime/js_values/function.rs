@@ -1,4 +1,8 @@
-use super::ValidateNapiValue;
+use std::marker::PhantomData;
+use std::ptr;
+
+use super::{FromNapiValue, ToNapiValue, TypeName, ValidateNapiValue};
+use crate::{check_status, sys, type_of, Error, Result, Status, ValueType};
 
 pub use crate::JsFunction;
 
@@ -7,3 +11,127 @@ impl ValidateNapiValue for JsFunction {
     vec![crate::ValueType::Function]
   }
 }
+
+/// The arguments of [`Function`], the tuples of the values converted to JavaScript in order.
+pub trait FunctionArgs {
+  /// # Safety
+  ///
+  /// `env` must be the env of the current JavaScript thread
+  unsafe fn into_napi_values(self, env: sys::napi_env) -> Result<Vec<sys::napi_value>>;
+}
+
+macro_rules! impl_function_args {
+  ($($ident:ident),*) => {
+    impl<$($ident: ToNapiValue),*> FunctionArgs for ($($ident,)*) {
+      #[allow(non_snake_case, unused_variables)]
+      unsafe fn into_napi_values(self, env: sys::napi_env) -> Result<Vec<sys::napi_value>> {
+        let ($($ident,)*) = self;
+        Ok(vec![$(unsafe { $ident::to_napi_value(env, $ident) }?),*])
+      }
+    }
+  };
+}
+
+impl_function_args!();
+impl_function_args!(A);
+impl_function_args!(A, B);
+impl_function_args!(A, B, C);
+impl_function_args!(A, B, C, D);
+impl_function_args!(A, B, C, D, E);
+impl_function_args!(A, B, C, D, E, F);
+impl_function_args!(A, B, C, D, E, F, G);
+impl_function_args!(A, B, C, D, E, F, G, H);
+
+/// The JavaScript function called with the `Args` tuple, returning `Return`.
+///
+/// Typed as `(arg0: A, arg1: B) => Return` in the `.d.ts` for `Function<(A, B), Return>`.
+/// It's only valid in the call of the `#[napi]` fn it's passed to,
+/// use a `ThreadsafeFunction` to call it later or from other threads.
+///
+/// ```ignore
+/// #[napi]
+/// fn map_sum(values: Vec<u32>, map: Function<(u32, u32), u32>) -> Result<u32> {
+///   values.into_iter().enumerate().try_fold(0, |sum, (i, value)| Ok(sum + map.call((value, i as u32))?))
+/// }
+/// ```
+pub struct Function<Args: FunctionArgs = (), Return = ()> {
+  env: sys::napi_env,
+  value: sys::napi_value,
+  _marker: PhantomData<fn(Args) -> Return>,
+}
+
+impl<Args: FunctionArgs, Return: FromNapiValue> Function<Args, Return> {
+  /// Call the function with `undefined` as `this`
+  pub fn call(&self, args: Args) -> Result<Return> {
+    let mut this = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_get_undefined(self.env, &mut this) },
+      "Failed to get undefined"
+    )?;
+    self.call_with_this(this, args)
+  }
+
+  /// Call the function with the JavaScript value `this` as `this`
+  pub fn apply<This: ToNapiValue>(&self, this: This, args: Args) -> Result<Return> {
+    let this = unsafe { This::to_napi_value(self.env, this) }?;
+    self.call_with_this(this, args)
+  }
+
+  fn call_with_this(&self, this: sys::napi_value, args: Args) -> Result<Return> {
+    let args = unsafe { args.into_napi_values(self.env) }?;
+    let mut ret = ptr::null_mut();
+    check_status!(
+      unsafe {
+        sys::napi_call_function(
+          self.env,
+          this,
+          self.value,
+          args.len(),
+          args.as_ptr(),
+          &mut ret,
+        )
+      },
+      "Failed to call the function"
+    )?;
+    unsafe { Return::from_napi_value(self.env, ret) }
+  }
+}
+
+impl<Args: FunctionArgs, Return> TypeName for Function<Args, Return> {
+  fn type_name() -> &'static str {
+    "Function"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Function
+  }
+}
+
+impl<Args: FunctionArgs, Return> ValidateNapiValue for Function<Args, Return> {
+  fn type_of() -> Vec<ValueType> {
+    vec![ValueType::Function]
+  }
+}
+
+impl<Args: FunctionArgs, Return> FromNapiValue for Function<Args, Return> {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let value_type = type_of!(env, napi_val)?;
+    if value_type != ValueType::Function {
+      return Err(Error::new(
+        Status::FunctionExpected,
+        format!("Expect value to be Function, but received {}", value_type),
+      ));
+    }
+    Ok(Function {
+      env,
+      value: napi_val,
+      _marker: PhantomData,
+    })
+  }
+}
+
+impl<Args: FunctionArgs, Return> ToNapiValue for Function<Args, Return> {
+  unsafe fn to_napi_value(_env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    Ok(val.value)
+  }
+}
@@ -0,0 +1,35 @@
+use std::ptr;
+
+use super::{Object, ToNapiValue, TypeName};
+use crate::{check_status, sys, Result, ValueType};
+
+/// `{ done, value }` of the JavaScript iterator protocol, `done` if it's `None`.
+///
+/// It's returned by the `next()` of the classes implementing `Iterator` with `#[napi]`.
+pub struct IteratorResult<T>(pub Option<T>);
+
+impl<T> TypeName for IteratorResult<T> {
+  fn type_name() -> &'static str {
+    "IteratorResult"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl<T: ToNapiValue> ToNapiValue for IteratorResult<T> {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    let mut result = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_create_object(env, &mut result) },
+      "Failed to create the iterator result"
+    )?;
+    let mut object = unsafe { <Object as super::FromNapiValue>::from_napi_value(env, result) }?;
+    object.set("done", val.0.is_none())?;
+    if let Some(value) = val.0 {
+      object.set("value", value)?;
+    }
+    Ok(result)
+  }
+}
@@ -41,6 +41,7 @@ where
   V: FromNapiValue,
 {
   unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    unsafe { debug_validate_object(env, napi_val)? };
     let obj = unsafe { Object::from_napi_value(env, napi_val)? };
     let mut map = HashMap::new();
     for key in Object::keys(&obj)?.into_iter() {
@@ -0,0 +1,203 @@
+use std::ffi::c_void;
+use std::ops::Deref;
+use std::ptr;
+use std::sync::Arc;
+
+use crate::bindgen_runtime::env_cache::{get_or_create, get_or_run_script, EnvCacheKey};
+use crate::{check_status, sys, type_of, Result, ValueType};
+
+use super::{FromNapiValue, TypeName, ValidateNapiValue};
+
+/// Creates the caches of the converted values keyed by the JavaScript objects, evaluated once per env.
+///
+/// The cached value is dropped once the revision of the object is changed, or the object is garbage collected.
+const MEMO_CACHE_FACTORY: &str = r#"(function () {
+  const revision = Symbol.for('napi-rs.revision')
+  return function createMemoCache() {
+    const entries = new WeakMap()
+    return {
+      get(value) {
+        const entry = entries.get(value)
+        return entry !== undefined && entry.revision === (value[revision] || 0)
+          ? entry.converted
+          : undefined
+      },
+      set(value, converted) {
+        entries.set(value, { revision: value[revision] || 0, converted })
+      },
+    }
+  }
+})()"#;
+
+/// The argument of the `#[napi]` functions converted once per JavaScript object.
+///
+/// Every `Memoized` argument caches the converted values keyed by the identities of the objects passed to it,
+/// so a large config object passed repeatedly is converted only once.
+/// The objects must not be mutated after passed, unless the `Symbol.for('napi-rs.revision')` property of them is bumped.
+///
+/// ```js
+/// config.threshold = 10
+/// config[Symbol.for('napi-rs.revision')] = (config[Symbol.for('napi-rs.revision')] || 0) + 1
+/// ```
+///
+/// Values other than the objects are converted every time.
+///
+/// The converted values outlive the calls they are converted in, so they can't hold the handles of the JavaScript values,
+/// see [`MemoizedValue`].
+pub struct Memoized<T>(Arc<T>);
+
+impl<T> Clone for Memoized<T> {
+  fn clone(&self) -> Self {
+    Self(self.0.clone())
+  }
+}
+
+impl<T> Deref for Memoized<T> {
+  type Target = T;
+
+  fn deref(&self) -> &T {
+    &self.0
+  }
+}
+
+impl<T> Memoized<T> {
+  pub fn into_inner(self) -> Arc<T> {
+    self.0
+  }
+}
+
+impl<T: TypeName> TypeName for Memoized<T> {
+  fn type_name() -> &'static str {
+    T::type_name()
+  }
+
+  fn value_type() -> ValueType {
+    T::value_type()
+  }
+}
+
+impl<T: MemoizedValue + ValidateNapiValue> ValidateNapiValue for Memoized<T> {
+  fn type_of() -> Vec<ValueType> {
+    T::type_of()
+  }
+}
+
+/// The values kept by the caches of the `Memoized` arguments.
+///
+/// The handles of the JavaScript values, e.g. `JsObject` or `Object`, are only valid in the call they are passed to,
+/// none of them is `Send`. The JavaScript values are kept by the references instead, e.g. `Reference` or `Buffer`.
+pub trait MemoizedValue: 'static + Send {}
+
+impl<T: 'static + Send> MemoizedValue for T {}
+
+/// Converted without the cache outside of the arguments of the `#[napi]` functions
+impl<T: MemoizedValue + FromNapiValue> FromNapiValue for Memoized<T> {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    Ok(Self(Arc::new(unsafe {
+      T::from_napi_value(env, napi_val)
+    }?)))
+  }
+}
+
+/// The cache of one `Memoized` argument, generated by `#[napi]` as a `static`.
+///
+/// The caches of the envs are keyed by the address of it.
+#[doc(hidden)]
+pub struct MemoCache {
+  // not zero sized, so the addresses of the `static`s are distinct
+  _address: u8,
+}
+
+impl MemoCache {
+  #[allow(clippy::new_without_default)]
+  pub const fn new() -> Self {
+    Self { _address: 0 }
+  }
+}
+
+impl<T: MemoizedValue + FromNapiValue> Memoized<T> {
+  /// # Safety
+  ///
+  /// `env` must be the env of the current JavaScript thread
+  #[doc(hidden)]
+  pub unsafe fn from_napi_value_cached(
+    env: sys::napi_env,
+    napi_val: sys::napi_value,
+    cache: &'static MemoCache,
+  ) -> Result<Self> {
+    let ty = type_of!(env, napi_val)?;
+    if ty != ValueType::Object && ty != ValueType::Function {
+      return unsafe { Self::from_napi_value(env, napi_val) };
+    }
+    let cache = unsafe { get_cache(env, cache) }?;
+    let cached = unsafe { call_method(env, cache, "get\0", &[napi_val]) }?;
+    if type_of!(env, cached)? == ValueType::External {
+      let mut data = ptr::null_mut();
+      check_status!(
+        unsafe { sys::napi_get_value_external(env, cached, &mut data) },
+        "Failed to get the memoized value"
+      )?;
+      return Ok(unsafe { &*(data as *const Self) }.clone());
+    }
+    let converted = unsafe { Self::from_napi_value(env, napi_val) }?;
+    let mut external = ptr::null_mut();
+    check_status!(
+      unsafe {
+        sys::napi_create_external(
+          env,
+          Box::into_raw(Box::new(converted.clone())) as *mut c_void,
+          Some(drop_memoized::<T>),
+          ptr::null_mut(),
+          &mut external,
+        )
+      },
+      "Failed to create the memoized value"
+    )?;
+    unsafe { call_method(env, cache, "set\0", &[napi_val, external]) }?;
+    Ok(converted)
+  }
+}
+
+unsafe fn get_cache(env: sys::napi_env, cache: &'static MemoCache) -> Result<sys::napi_value> {
+  let key = EnvCacheKey::MemoCache(cache as *const MemoCache as usize);
+  unsafe {
+    get_or_create(env, key, || {
+      let factory = get_or_run_script(env, "the factory of the memo cache", MEMO_CACHE_FACTORY)?;
+      let mut global = ptr::null_mut();
+      check_status!(sys::napi_get_global(env, &mut global))?;
+      let mut value = ptr::null_mut();
+      check_status!(
+        sys::napi_call_function(env, global, factory, 0, ptr::null(), &mut value),
+        "Failed to create the memo cache"
+      )?;
+      Ok(value)
+    })
+  }
+}
+
+/// `name` must be nul terminated
+unsafe fn call_method(
+  env: sys::napi_env,
+  object: sys::napi_value,
+  name: &str,
+  args: &[sys::napi_value],
+) -> Result<sys::napi_value> {
+  let mut method = ptr::null_mut();
+  check_status!(unsafe {
+    sys::napi_get_named_property(env, object, name.as_ptr() as *const _, &mut method)
+  })?;
+  let mut ret = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_call_function(env, object, method, args.len(), args.as_ptr(), &mut ret) },
+    "Failed to call the memo cache"
+  )?;
+  Ok(ret)
+}
+
+unsafe extern "C" fn drop_memoized<T>(
+  _env: sys::napi_env,
+  finalize_data: *mut c_void,
+  _finalize_hint: *mut c_void,
+) {
+  drop(unsafe { Box::from_raw(finalize_data as *mut Memoized<T>) });
+}
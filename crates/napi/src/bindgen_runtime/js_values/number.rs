@@ -1,4 +1,4 @@
-use super::{check_status, sys, Result};
+use super::{check_status, sys, unexpected_type_error, Result};
 use crate::type_of;
 
 macro_rules! impl_number_conversions {
@@ -42,8 +42,12 @@ macro_rules! impl_number_conversions {
 				unsafe fn from_napi_value(env: $crate::sys::napi_env, napi_val: $crate::sys::napi_value) -> Result<Self> {
 					let mut ret = 0 as $t;
 
+          let status = unsafe { sys::$get(env, napi_val, &mut ret) };
+          if status == sys::Status::napi_number_expected {
+            return Err(unsafe { unexpected_type_error(env, napi_val, status, $crate::ValueType::Number) });
+          }
           check_status!(
-            unsafe { sys::$get(env, napi_val, &mut ret) },
+            status,
             "Failed to convert napi value {:?} into rust type `{}`",
             type_of!(env, napi_val),
             $name,
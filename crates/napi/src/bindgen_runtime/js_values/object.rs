@@ -1,4 +1,6 @@
-use crate::{bindgen_prelude::*, check_status, sys, type_of, JsObject, ValueType};
+use crate::{
+  bindgen_prelude::*, check_status, sys, type_of, Env, JsObject, JsUnknown, NapiValue, ValueType,
+};
 use std::{ffi::CString, ptr};
 
 pub type Object = JsObject;
@@ -18,9 +20,23 @@ impl Object {
       env,
       value: ptr,
       value_type: ValueType::Object,
+      scope: crate::HandleScopeId::current(),
     }))
   }
 
+  /// The instance of the class `T` or the classes extending it, `None` if the object is not created by them,
+  /// including the instances of the class with the same name from the other addons.
+  #[cfg(feature = "napi8")]
+  pub fn downcast<T: FromNapiRef + TypeName>(&self) -> Result<Option<&'static T>> {
+    unsafe {
+      if is_class_instance::<T>(self.0.env, self.0.value)? {
+        T::from_napi_ref(self.0.env, self.0.value).map(Some)
+      } else {
+        Ok(None)
+      }
+    }
+  }
+
   pub fn get<K: AsRef<str>, V: FromNapiValue>(&self, field: K) -> Result<Option<V>> {
     let c_field = CString::new(field.as_ref())?;
 
@@ -38,7 +54,37 @@ impl Object {
       Ok(if ty == ValueType::Undefined || ty == ValueType::Null {
         None
       } else {
-        Some(V::from_napi_value(self.0.env, ret)?)
+        Some(with_value_path(
+          V::from_napi_value(self.0.env, ret),
+          || field_segment(field.as_ref()),
+        )?)
+      })
+    }
+  }
+
+  /// Get the field which could be `null` but not missing, `None` if it's `undefined` and `Some(None)` if it's `null`.
+  pub fn get_nullable<K: AsRef<str>, V: FromNapiValue>(
+    &self,
+    field: K,
+  ) -> Result<Option<Option<V>>> {
+    let c_field = CString::new(field.as_ref())?;
+
+    unsafe {
+      let mut ret = ptr::null_mut();
+
+      check_status!(
+        sys::napi_get_named_property(self.0.env, self.0.value, c_field.as_ptr(), &mut ret),
+        "Failed to get property with field `{}`",
+        c_field.to_string_lossy(),
+      )?;
+
+      Ok(if type_of!(self.0.env, ret)? == ValueType::Undefined {
+        None
+      } else {
+        Some(with_value_path(
+          Option::<V>::from_napi_value(self.0.env, ret),
+          || field_segment(field.as_ref()),
+        )?)
       })
     }
   }
@@ -59,6 +105,76 @@ impl Object {
     }
   }
 
+  /// Get the value at the dot separated `path`, like `"compilerOptions.paths"`.
+  ///
+  /// `None` if the value or any of the objects on the path is `undefined` or `null`,
+  /// fails if any of the other values on the path is not an object.
+  pub fn get_nested<V: FromNapiValue>(&self, path: &str) -> Result<Option<V>> {
+    let segments = split_path(path)?;
+    let (field, parents) = segments.split_last().unwrap();
+    let mut obj = unsafe { Object::from_raw_unchecked(self.0.env, self.0.value) };
+    for (i, parent) in parents.iter().enumerate() {
+      obj = match obj.get::<_, JsUnknown>(parent)? {
+        Some(value) => expect_object(value, "get", path, &segments[..=i])?,
+        None => return Ok(None),
+      };
+    }
+    if parents.is_empty() {
+      return obj.get(field);
+    }
+    with_value_path(obj.get(field), || {
+      parents
+        .iter()
+        .fold(String::new(), |mut value_path, parent| {
+          let segment = field_segment(parent);
+          if !value_path.is_empty() && !segment.starts_with('[') {
+            value_path.push('.');
+          }
+          value_path.push_str(&segment);
+          value_path
+        })
+    })
+  }
+
+  /// Set the value at the dot separated `path`, the missing objects on the path are created.
+  ///
+  /// Fails if any of the existing values on the path is not an object.
+  pub fn set_nested<V: ToNapiValue>(&mut self, path: &str, val: V) -> Result<()> {
+    let segments = split_path(path)?;
+    let (field, parents) = segments.split_last().unwrap();
+    let mut obj = unsafe { Object::from_raw_unchecked(self.0.env, self.0.value) };
+    for (i, parent) in parents.iter().enumerate() {
+      obj = match obj.get::<_, JsUnknown>(parent)? {
+        Some(value) => expect_object(value, "set", path, &segments[..=i])?,
+        None => {
+          let child = Env::from(self.0.env).create_object()?;
+          obj.set(parent, unsafe {
+            Object::from_raw_unchecked(self.0.env, child.0.value)
+          })?;
+          child
+        }
+      };
+    }
+    obj.set(field, val)
+  }
+
+  /// The `Unknown field` error of the first enumerable property not in `fields`, `#[napi(object, deny_unknown_fields)]`
+  #[doc(hidden)]
+  pub fn deny_unknown_fields(&self, fields: &[&str]) -> Result<()> {
+    match Object::keys(self)?
+      .into_iter()
+      .find(|key| !fields.contains(&key.as_str()))
+    {
+      Some(key) => {
+        let mut err = Error::new(Status::InvalidArg, format!("Unknown field `{}`", key));
+        // the error of the object itself, prefixed by the path of it
+        err.path = Some(String::new());
+        Err(err)
+      }
+      None => Ok(()),
+    }
+  }
+
   pub fn keys(obj: &Object) -> Result<Vec<String>> {
     let mut names = ptr::null_mut();
     unsafe {
@@ -94,3 +210,46 @@ impl ValidateNapiValue for Object {
     vec![ValueType::Object]
   }
 }
+
+fn expect_object(value: JsUnknown, action: &str, path: &str, segments: &[&str]) -> Result<Object> {
+  let ty = value.get_type()?;
+  if ty == ValueType::Object || ty == ValueType::Function {
+    Ok(unsafe { value.cast() })
+  } else {
+    Err(Error::new(
+      Status::InvalidArg,
+      format!(
+        "Failed to {} `{}`, `{}` is {}, not an object",
+        action,
+        path,
+        segments.join("."),
+        ty
+      ),
+    ))
+  }
+}
+
+fn split_path(path: &str) -> Result<Vec<&str>> {
+  let segments = path.split('.').collect::<Vec<_>>();
+  if segments.iter().any(|segment| segment.is_empty()) {
+    return Err(Error::new(
+      Status::InvalidArg,
+      format!("Invalid path `{}`", path),
+    ));
+  }
+  Ok(segments)
+}
+
+/// The segment of the value path of `field`, `[".."]` if it's not an identifier
+fn field_segment(field: &str) -> String {
+  let is_ident = !field.is_empty()
+    && !field.starts_with(|c: char| c.is_ascii_digit())
+    && field
+      .chars()
+      .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+  if is_ident {
+    field.to_owned()
+  } else {
+    format!("[{:?}]", field)
+  }
+}
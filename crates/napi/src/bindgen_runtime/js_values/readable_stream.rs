@@ -0,0 +1,208 @@
+use std::ffi::c_void;
+use std::future::Future;
+use std::pin::Pin;
+use std::ptr;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use futures_core::Stream;
+use tokio::sync::Mutex;
+
+use crate::{
+  bindgen_runtime::env_cache::get_or_run_script, check_status, sys,
+  tokio_runtime::execute_tokio_future, Error, JsError, Result, Status, ValueType,
+};
+
+use super::{ToNapiValue, TypeName};
+
+/// Creates the `stream.Readable` pulling the chunks by calling `pull(state)`, evaluated once per env
+const READABLE_FACTORY: &str = r#"(function () {
+  const stream =
+    typeof process.getBuiltinModule === 'function'
+      ? process.getBuiltinModule('stream')
+      : process.mainModule && process.mainModule.require('stream')
+  if (!stream) {
+    throw new Error(
+      'stream.Readable is not available, it requires process.getBuiltinModule of Node.js >= 20.16 or a CommonJS entry',
+    )
+  }
+  return function createReadable(pull, state, objectMode) {
+    return new stream.Readable({
+      objectMode,
+      read() {
+        pull(state).then(
+          (chunk) => {
+            this.push(chunk)
+          },
+          (err) => {
+            this.destroy(err)
+          },
+        )
+      },
+    })
+  }
+})()"#;
+
+type ChunkStream<T> = Arc<Mutex<Pin<Box<dyn Stream<Item = Result<T>> + Send>>>>;
+
+/// `stream.Readable` of the chunks of a `Stream`, which is polled in the tokio runtime whenever the `Readable` reads.
+///
+/// The chunks must be `Buffer` or `String`, unless it's in the object mode. The stream ends when the `Stream` ends,
+/// and is destroyed with the error of the first `Err` chunk.
+/// Returned by the functions of `#[napi(readable_stream)]`, it could also be returned directly.
+pub struct ReadableStream<T> {
+  stream: ChunkStream<T>,
+  object_mode: bool,
+}
+
+impl<T: 'static + Send + ToNapiValue> ReadableStream<T> {
+  pub fn new<S>(stream: S) -> Self
+  where
+    S: 'static + Send + Stream<Item = Result<T>>,
+  {
+    Self {
+      stream: Arc::new(Mutex::new(Box::pin(stream))),
+      object_mode: false,
+    }
+  }
+
+  /// Push the chunks as they are, instead of requiring them to be `Buffer` or `String`
+  pub fn object_mode(mut self) -> Self {
+    self.object_mode = true;
+    self
+  }
+}
+
+impl<T> TypeName for ReadableStream<T> {
+  fn type_name() -> &'static str {
+    "ReadableStream"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl<T: 'static + Send + ToNapiValue> ToNapiValue for ReadableStream<T> {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    let factory = unsafe { readable_factory(env) }?;
+    let mut pull = ptr::null_mut();
+    check_status!(
+      unsafe {
+        sys::napi_create_function(
+          env,
+          "pull".as_ptr() as *const _,
+          4,
+          Some(pull_chunk::<T>),
+          ptr::null_mut(),
+          &mut pull,
+        )
+      },
+      "Failed to create the pull function of ReadableStream"
+    )?;
+    let mut state = ptr::null_mut();
+    check_status!(
+      unsafe {
+        sys::napi_create_external(
+          env,
+          Box::into_raw(Box::new(val.stream)) as *mut c_void,
+          Some(drop_stream::<T>),
+          ptr::null_mut(),
+          &mut state,
+        )
+      },
+      "Failed to create the state of ReadableStream"
+    )?;
+    let object_mode = unsafe { bool::to_napi_value(env, val.object_mode) }?;
+    let mut global = ptr::null_mut();
+    check_status!(unsafe { sys::napi_get_global(env, &mut global) })?;
+    let mut readable = ptr::null_mut();
+    check_status!(
+      unsafe {
+        sys::napi_call_function(
+          env,
+          global,
+          factory,
+          3,
+          [pull, state, object_mode].as_ptr(),
+          &mut readable,
+        )
+      },
+      "Failed to create Readable"
+    )?;
+    Ok(readable)
+  }
+}
+
+unsafe fn readable_factory(env: sys::napi_env) -> Result<sys::napi_value> {
+  unsafe { get_or_run_script(env, "the factory of Readable", READABLE_FACTORY) }
+}
+
+/// `pull(state)`, returns the `Promise` of the next chunk, or `null` after the end of the stream
+unsafe extern "C" fn pull_chunk<T: 'static + Send + ToNapiValue>(
+  env: sys::napi_env,
+  cb: sys::napi_callback_info,
+) -> sys::napi_value {
+  let mut argc = 1;
+  let mut state = ptr::null_mut();
+  let result = check_status!(
+    unsafe {
+      sys::napi_get_cb_info(
+        env,
+        cb,
+        &mut argc,
+        &mut state,
+        ptr::null_mut(),
+        ptr::null_mut(),
+      )
+    },
+    "Failed to get the state of ReadableStream"
+  )
+  .and_then(|_| {
+    let mut stream = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_get_value_external(env, state, &mut stream) },
+      "Failed to get the state of ReadableStream"
+    )?;
+    if stream.is_null() {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "Invalid state of ReadableStream".to_owned(),
+      ));
+    }
+    let stream = Arc::clone(unsafe { &*(stream as *const ChunkStream<T>) });
+    execute_tokio_future(
+      env,
+      async move {
+        let mut stream = stream.lock().await;
+        NextChunk(stream.as_mut()).await.transpose()
+      },
+      |env, chunk| unsafe { Option::<T>::to_napi_value(env, chunk) },
+    )
+  });
+  match result {
+    Ok(promise) => promise,
+    Err(err) => {
+      unsafe { JsError::from(err).throw_into(env) };
+      ptr::null_mut()
+    }
+  }
+}
+
+pub(super) struct NextChunk<'a, T>(pub(super) Pin<&'a mut (dyn Stream<Item = Result<T>> + Send)>);
+
+impl<T> Future for NextChunk<'_, T> {
+  type Output = Option<Result<T>>;
+
+  fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+    self.0.as_mut().poll_next(cx)
+  }
+}
+
+unsafe extern "C" fn drop_stream<T>(
+  _env: sys::napi_env,
+  finalize_data: *mut c_void,
+  _finalize_hint: *mut c_void,
+) {
+  drop(unsafe { Box::from_raw(finalize_data as *mut ChunkStream<T>) });
+}
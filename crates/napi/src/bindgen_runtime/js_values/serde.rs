@@ -96,6 +96,7 @@ impl FromNapiValue for Map<String, Value> {
       env,
       value: napi_val,
       value_type: ValueType::Object,
+      scope: crate::HandleScopeId::current(),
     });
 
     let mut map = Map::new();
@@ -1,3 +1,4 @@
+use super::unexpected_type_error;
 use crate::{bindgen_prelude::*, check_status, sys, Error, Result, Status};
 
 use std::ffi::{c_void, CStr};
@@ -39,8 +40,13 @@ impl FromNapiValue for String {
   unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
     let mut len = 0;
 
+    let status =
+      unsafe { sys::napi_get_value_string_utf8(env, napi_val, ptr::null_mut(), 0, &mut len) };
+    if status == sys::Status::napi_string_expected {
+      return Err(unsafe { unexpected_type_error(env, napi_val, status, ValueType::String) });
+    }
     check_status!(
-      unsafe { sys::napi_get_value_string_utf8(env, napi_val, ptr::null_mut(), 0, &mut len) },
+      status,
       "Failed to convert napi `string` into rust type `String`",
     )?;
 
@@ -91,8 +97,13 @@ impl FromNapiValue for &str {
   unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
     let mut len = 0;
 
+    let status =
+      unsafe { sys::napi_get_value_string_utf8(env, napi_val, ptr::null_mut(), 0, &mut len) };
+    if status == sys::Status::napi_string_expected {
+      return Err(unsafe { unexpected_type_error(env, napi_val, status, ValueType::String) });
+    }
     check_status!(
-      unsafe { sys::napi_get_value_string_utf8(env, napi_val, ptr::null_mut(), 0, &mut len) },
+      status,
       "Failed to convert napi `string` into rust type `String`",
     )?;
 
@@ -186,8 +197,13 @@ impl FromNapiValue for Utf16String {
   unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
     let mut len = 0;
 
+    let status =
+      unsafe { sys::napi_get_value_string_utf16(env, napi_val, ptr::null_mut(), 0, &mut len) };
+    if status == sys::Status::napi_string_expected {
+      return Err(unsafe { unexpected_type_error(env, napi_val, status, ValueType::String) });
+    }
     check_status!(
-      unsafe { sys::napi_get_value_string_utf16(env, napi_val, ptr::null_mut(), 0, &mut len) },
+      status,
       "Failed to convert napi `utf16 string` into rust type `String`",
     )?;
 
@@ -285,8 +301,13 @@ pub mod latin1_string {
     unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
       let mut len = 0;
 
+      let status =
+        unsafe { sys::napi_get_value_string_latin1(env, napi_val, ptr::null_mut(), 0, &mut len) };
+      if status == sys::Status::napi_string_expected {
+        return Err(unsafe { unexpected_type_error(env, napi_val, status, ValueType::String) });
+      }
       check_status!(
-        unsafe { sys::napi_get_value_string_latin1(env, napi_val, ptr::null_mut(), 0, &mut len) },
+        status,
         "Failed to convert napi `latin1 string` into rust type `String`",
       )?;
 
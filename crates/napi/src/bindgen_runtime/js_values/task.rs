@@ -1,10 +1,16 @@
 use std::ffi::c_void;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
 use std::ptr;
-use std::rc::Rc;
-use std::sync::atomic::{AtomicPtr, AtomicU8, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicPtr, AtomicU8, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
 
 use super::{FromNapiValue, ToNapiValue, TypeName};
-use crate::{async_work, check_status, Env, Error, JsError, JsObject, NapiValue, Status, Task};
+use crate::{
+  async_work, check_status, Env, Error, JsBoolean, JsError, JsObject, NapiValue, Status, Task,
+};
 
 pub struct AsyncTask<T: Task> {
   inner: T,
@@ -44,11 +50,147 @@ impl<T: Task> AsyncTask<T> {
   }
 }
 
+/// Run a sync closure in the libuv thread pool, used by the `#[napi(also_async = "...")]` generated functions.
+///
+/// The closure only owns the converted arguments, the methods taking `self` are rejected by the macro.
+#[doc(hidden)]
+pub struct BlockingTask<T, F> {
+  inner: Option<F>,
+  _output: PhantomData<T>,
+}
+
+impl<T, F> BlockingTask<T, F>
+where
+  T: ToNapiValue + TypeName + Send + 'static,
+  F: FnOnce() -> crate::Result<T> + Send,
+{
+  pub fn new(inner: F) -> Self {
+    Self {
+      inner: Some(inner),
+      _output: PhantomData,
+    }
+  }
+}
+
+impl<T, F> Task for BlockingTask<T, F>
+where
+  T: ToNapiValue + TypeName + Send + 'static,
+  F: FnOnce() -> crate::Result<T> + Send,
+{
+  type Output = T;
+  type JsValue = T;
+
+  fn compute(&mut self) -> crate::Result<Self::Output> {
+    match self.inner.take() {
+      Some(inner) => inner(),
+      None => Err(Error::new(
+        Status::GenericFailure,
+        "BlockingTask has been computed".to_owned(),
+      )),
+    }
+  }
+
+  fn resolve(&mut self, _env: Env, output: Self::Output) -> crate::Result<Self::JsValue> {
+    Ok(output)
+  }
+}
+
 /// <https://developer.mozilla.org/zh-CN/docs/Web/API/AbortController>
+///
+/// Passed to an `AsyncTask`, the task is cancelled once it's aborted.
+/// The `async fn`s taking it are rejected with `AbortError` once it's aborted, and the future is dropped.
 pub struct AbortSignal {
-  raw_work: Rc<AtomicPtr<napi_sys::napi_async_work__>>,
-  raw_deferred: Rc<AtomicPtr<napi_sys::napi_deferred__>>,
-  status: Rc<AtomicU8>,
+  raw_work: Arc<AtomicPtr<napi_sys::napi_async_work__>>,
+  raw_deferred: Arc<AtomicPtr<napi_sys::napi_deferred__>>,
+  status: Arc<AtomicU8>,
+  aborted: Arc<AbortState>,
+}
+
+#[derive(Default)]
+struct AbortState {
+  aborted: AtomicBool,
+  /// The `Abortable` futures waiting for the abort
+  wakers: Mutex<Vec<Waker>>,
+}
+
+impl AbortState {
+  fn abort(&self) {
+    self.aborted.store(true, Ordering::Release);
+    if let Ok(mut wakers) = self.wakers.lock() {
+      wakers.drain(..).for_each(Waker::wake);
+    }
+  }
+}
+
+impl AbortSignal {
+  /// Whether the `AbortController` has been aborted
+  pub fn aborted(&self) -> bool {
+    self.aborted.aborted.load(Ordering::Acquire)
+  }
+
+  /// The `Send` handle of the aborted state, which could be checked in the other threads
+  pub fn handle(&self) -> AbortHandle {
+    AbortHandle(self.aborted.clone())
+  }
+}
+
+/// Created by [`AbortSignal::handle`]
+#[derive(Clone)]
+pub struct AbortHandle(Arc<AbortState>);
+
+impl AbortHandle {
+  pub fn aborted(&self) -> bool {
+    self.0.aborted.load(Ordering::Acquire)
+  }
+
+  /// `Err` of the same `AbortError` the aborted `AsyncTask` rejects with, if it has been aborted
+  pub fn check(&self) -> crate::Result<()> {
+    if self.aborted() {
+      Err(Error::new(Status::Cancelled, "AbortError".to_owned()))
+    } else {
+      Ok(())
+    }
+  }
+}
+
+/// Reject with `AbortError` once the `handle` is aborted, the `future` is dropped then.
+/// Used by the `async fn`s taking the `AbortSignal`.
+#[doc(hidden)]
+pub fn abortable<T, F>(handle: Option<AbortHandle>, future: F) -> Abortable<F>
+where
+  F: Future<Output = crate::Result<T>>,
+{
+  Abortable {
+    future: Box::pin(future),
+    handle,
+  }
+}
+
+#[doc(hidden)]
+pub struct Abortable<F> {
+  future: Pin<Box<F>>,
+  handle: Option<AbortHandle>,
+}
+
+impl<T, F> Future for Abortable<F>
+where
+  F: Future<Output = crate::Result<T>>,
+{
+  type Output = crate::Result<T>;
+
+  fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+    if let Some(handle) = &self.handle {
+      handle.check()?;
+      if let Ok(mut wakers) = handle.0.wakers.lock() {
+        if !wakers.iter().any(|waker| waker.will_wake(cx.waker())) {
+          wakers.push(cx.waker().clone());
+        }
+      }
+      // aborted before the waker is registered
+      handle.check()?;
+    }
+    self.future.as_mut().poll(cx)
+  }
 }
 
 impl FromNapiValue for AbortSignal {
@@ -57,15 +199,24 @@ impl FromNapiValue for AbortSignal {
     napi_val: napi_sys::napi_value,
   ) -> crate::Result<Self> {
     let mut signal = unsafe { JsObject::from_raw_unchecked(env, napi_val) };
-    let async_work_inner: Rc<AtomicPtr<napi_sys::napi_async_work__>> =
-      Rc::new(AtomicPtr::new(ptr::null_mut()));
-    let raw_promise: Rc<AtomicPtr<napi_sys::napi_deferred__>> =
-      Rc::new(AtomicPtr::new(ptr::null_mut()));
-    let task_status = Rc::new(AtomicU8::new(0));
+    let async_work_inner: Arc<AtomicPtr<napi_sys::napi_async_work__>> =
+      Arc::new(AtomicPtr::new(ptr::null_mut()));
+    let raw_promise: Arc<AtomicPtr<napi_sys::napi_deferred__>> =
+      Arc::new(AtomicPtr::new(ptr::null_mut()));
+    let task_status = Arc::new(AtomicU8::new(0));
+    let aborted = Arc::new(AbortState {
+      aborted: AtomicBool::new(
+        signal
+          .get_named_property::<JsBoolean>("aborted")?
+          .get_value()?,
+      ),
+      wakers: Default::default(),
+    });
     let abort_controller = AbortSignal {
       raw_work: async_work_inner.clone(),
       raw_deferred: raw_promise.clone(),
       status: task_status.clone(),
+      aborted: aborted.clone(),
     };
     let js_env = unsafe { Env::from_raw(env) };
     check_status!(unsafe {
@@ -83,6 +234,7 @@ impl FromNapiValue for AbortSignal {
       raw_work: async_work_inner,
       raw_deferred: raw_promise,
       status: task_status,
+      aborted,
     })
   }
 }
@@ -116,11 +268,16 @@ extern "C" fn on_abort(
       "Unwrap async_task from AbortSignal failed"
     );
     let abort_controller = Box::leak(Box::from_raw(async_task as *mut AbortSignal));
+    abort_controller.aborted.abort();
     // Task Completed, return now
     if abort_controller.status.load(Ordering::Relaxed) == 1 {
       return ptr::null_mut();
     }
     let raw_async_work = abort_controller.raw_work.load(Ordering::Relaxed);
+    // Not passed to an `AsyncTask`
+    if raw_async_work.is_null() {
+      return ptr::null_mut();
+    }
     let deferred = abort_controller.raw_deferred.load(Ordering::Relaxed);
     napi_sys::napi_cancel_async_work(env, raw_async_work);
     // abort function must be called from JavaScript main thread, so Relaxed Ordering is ok.
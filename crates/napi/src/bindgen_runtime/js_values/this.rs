@@ -0,0 +1,44 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::{sys, JsObject, Result};
+
+use super::FromNapiValue;
+
+/// The JavaScript `this` of the `#[napi]` fn, declared as the first param of it.
+///
+/// The fns assigned to the user objects, like the hooks of a plugin, get the objects they are called on.
+/// It's not an argument of the fn in JavaScript, the TypeScript type of it is the `this` param of the fn.
+///
+/// ```ignore
+/// #[napi]
+/// fn plugin_name(this: This) -> Result<Option<String>> {
+///   this.get("name")
+/// }
+/// ```
+pub struct This<T = JsObject>(T);
+
+impl<T> This<T> {
+  pub fn into_inner(self) -> T {
+    self.0
+  }
+}
+
+impl<T> Deref for This<T> {
+  type Target = T;
+
+  fn deref(&self) -> &T {
+    &self.0
+  }
+}
+
+impl<T> DerefMut for This<T> {
+  fn deref_mut(&mut self) -> &mut T {
+    &mut self.0
+  }
+}
+
+impl<T: FromNapiValue> FromNapiValue for This<T> {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    Ok(Self(unsafe { T::from_napi_value(env, napi_val) }?))
+  }
+}
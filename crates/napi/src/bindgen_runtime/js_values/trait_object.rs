@@ -0,0 +1,239 @@
+use std::ptr;
+#[cfg(feature = "napi4")]
+use std::sync::mpsc;
+use std::thread::{self, ThreadId};
+
+use super::{FromNapiValue, FunctionArgs, Object};
+#[cfg(feature = "napi4")]
+use crate::threadsafe_function::{
+  ErrorStrategy, ThreadSafeCallContext, ThreadsafeFunction, ThreadsafeFunctionCallMode,
+};
+use crate::{check_status, sys, type_of, Error, Result, Status, ValueType};
+
+#[cfg(feature = "napi4")]
+type Job = Box<dyn FnOnce(sys::napi_env) + Send>;
+
+/// Moved into the jobs run on the JavaScript thread, only dereferenced there
+#[derive(Clone, Copy)]
+struct ObjectRef(sys::napi_ref);
+
+unsafe impl Send for ObjectRef {}
+
+impl ObjectRef {
+  unsafe fn delete(self, env: sys::napi_env) {
+    unsafe { sys::napi_delete_reference(env, self.0) };
+  }
+}
+
+/// The JavaScript object implementing a `#[napi] trait`, the methods of the trait call the methods of it.
+///
+/// The methods are called directly on the JavaScript thread.
+/// On the other threads they are queued to the JavaScript thread by a threadsafe function,
+/// and the calling thread is blocked until they return,
+/// so they must not be called from the threads the JavaScript thread is waiting for.
+pub struct JsTraitObject {
+  env: sys::napi_env,
+  object: ObjectRef,
+  thread: ThreadId,
+  #[cfg(feature = "napi4")]
+  tsfn: ThreadsafeFunction<Job, ErrorStrategy::Fatal>,
+}
+
+unsafe impl Send for JsTraitObject {}
+unsafe impl Sync for JsTraitObject {}
+
+impl JsTraitObject {
+  /// # Safety
+  ///
+  /// `env` must be the env of the current JavaScript thread
+  pub unsafe fn new(env: sys::napi_env, object: sys::napi_value) -> Result<Self> {
+    let value_type = type_of!(env, object)?;
+    if value_type != ValueType::Object && value_type != ValueType::Function {
+      return Err(Error::new(
+        Status::ObjectExpected,
+        format!("Expect value to be Object, but received {}", value_type),
+      ));
+    }
+    let mut object_ref = ptr::null_mut();
+    check_status!(unsafe { sys::napi_create_reference(env, object, 1, &mut object_ref) })?;
+    Ok(JsTraitObject {
+      env,
+      object: ObjectRef(object_ref),
+      thread: thread::current().id(),
+      #[cfg(feature = "napi4")]
+      tsfn: unsafe { create_job_runner(env) }?,
+    })
+  }
+
+  /// Call the method `name` of the object with the `args`, `this` is the object
+  pub fn call_method<Args, Return>(&self, name: &'static str, args: Args) -> Result<Return>
+  where
+    Args: FunctionArgs + Send + 'static,
+    Return: FromNapiValue + Send + 'static,
+  {
+    self.call(name, args, |env, ret| unsafe {
+      Return::from_napi_value(env, ret)
+    })
+  }
+
+  /// Call the method `name` of the object, whatever it returns is ignored
+  pub fn call_void_method<Args>(&self, name: &'static str, args: Args) -> Result<()>
+  where
+    Args: FunctionArgs + Send + 'static,
+  {
+    self.call(name, args, |_, _| Ok(()))
+  }
+
+  fn call<Args, Return>(
+    &self,
+    name: &'static str,
+    args: Args,
+    convert: fn(sys::napi_env, sys::napi_value) -> Result<Return>,
+  ) -> Result<Return>
+  where
+    Args: FunctionArgs + Send + 'static,
+    Return: Send + 'static,
+  {
+    if thread::current().id() == self.thread {
+      return unsafe { call_method(self.env, self.object, name, args) }
+        .and_then(|ret| convert(self.env, ret));
+    }
+    #[cfg(feature = "napi4")]
+    {
+      let (sender, receiver) = mpsc::channel();
+      let object = self.object;
+      let job: Job = Box::new(move |env| {
+        let ret = unsafe { call_method(env, object, name, args) }
+          .map_err(|err| match err.status {
+            Status::PendingException => unsafe { take_exception(env) },
+            _ => err,
+          })
+          .and_then(|ret| convert(env, ret));
+        sender.send(ret).ok();
+      });
+      let status = self.tsfn.call(job, ThreadsafeFunctionCallMode::Blocking);
+      if status != Status::Ok {
+        return Err(Error::new(
+          status,
+          format!("Failed to call `{}` on the JavaScript thread", name),
+        ));
+      }
+      receiver.recv().unwrap_or_else(|_| {
+        Err(Error::new(
+          Status::Closing,
+          format!("The env is closing, `{}` is not called", name),
+        ))
+      })
+    }
+    #[cfg(not(feature = "napi4"))]
+    Err(Error::new(
+      Status::GenericFailure,
+      format!(
+        "`{}` of the JavaScript object can only be called on the JavaScript thread without the `napi4` feature",
+        name
+      ),
+    ))
+  }
+}
+
+impl Drop for JsTraitObject {
+  fn drop(&mut self) {
+    let object = self.object;
+    if thread::current().id() == self.thread {
+      unsafe { object.delete(self.env) };
+      return;
+    }
+    #[cfg(feature = "napi4")]
+    self.tsfn.call(
+      Box::new(move |env| unsafe { object.delete(env) }),
+      ThreadsafeFunctionCallMode::NonBlocking,
+    );
+  }
+}
+
+unsafe fn call_method<Args: FunctionArgs>(
+  env: sys::napi_env,
+  object: ObjectRef,
+  name: &str,
+  args: Args,
+) -> Result<sys::napi_value> {
+  let mut this = ptr::null_mut();
+  check_status!(unsafe { sys::napi_get_reference_value(env, object.0, &mut this) })?;
+  let method = unsafe { Object::from_napi_value(env, this) }?
+    .get::<_, crate::JsUnknown>(name)?
+    .filter(|method| method.get_type().ok() == Some(ValueType::Function))
+    .ok_or_else(|| {
+      Error::new(
+        Status::FunctionExpected,
+        format!("`{}` is not a function of the JavaScript object", name),
+      )
+    })?;
+  let args = unsafe { args.into_napi_values(env) }?;
+  let mut ret = ptr::null_mut();
+  check_status!(
+    unsafe {
+      sys::napi_call_function(
+        env,
+        this,
+        crate::NapiRaw::raw(&method),
+        args.len(),
+        args.as_ptr(),
+        &mut ret,
+      )
+    },
+    "Failed to call `{}` of the JavaScript object",
+    name
+  )?;
+  Ok(ret)
+}
+
+/// The exception thrown off the JavaScript thread, the `message` of it is the reason of the `Error`
+#[cfg(feature = "napi4")]
+unsafe fn take_exception(env: sys::napi_env) -> Error {
+  let mut exception = ptr::null_mut();
+  unsafe { sys::napi_get_and_clear_last_exception(env, &mut exception) };
+  let message = unsafe { Object::from_napi_value(env, exception) }
+    .and_then(|exception| exception.get::<_, String>("message"))
+    .ok()
+    .flatten()
+    .or_else(|| {
+      let mut message = ptr::null_mut();
+      check_status!(unsafe { sys::napi_coerce_to_string(env, exception, &mut message) })
+        .and_then(|_| unsafe { String::from_napi_value(env, message) })
+        .ok()
+    })
+    .unwrap_or_default();
+  Error::new(Status::GenericFailure, message)
+}
+
+/// The threadsafe function running the jobs on the JavaScript thread, it doesn't keep the event loop alive
+#[cfg(feature = "napi4")]
+unsafe fn create_job_runner(
+  env: sys::napi_env,
+) -> Result<ThreadsafeFunction<Job, ErrorStrategy::Fatal>> {
+  let mut noop = ptr::null_mut();
+  check_status!(unsafe {
+    sys::napi_create_function(
+      env,
+      "runJob".as_ptr() as *const _,
+      6,
+      Some(noop_callback),
+      ptr::null_mut(),
+      &mut noop,
+    )
+  })?;
+  let tsfn = ThreadsafeFunction::create(env, noop, 0, |ctx: ThreadSafeCallContext<Job>| {
+    (ctx.value)(ctx.env.raw());
+    Ok(Vec::<crate::JsUnknown>::new())
+  })?;
+  check_status!(unsafe { sys::napi_unref_threadsafe_function(env, tsfn.raw()) })?;
+  Ok(tsfn)
+}
+
+#[cfg(feature = "napi4")]
+unsafe extern "C" fn noop_callback(
+  _env: sys::napi_env,
+  _info: sys::napi_callback_info,
+) -> sys::napi_value {
+  ptr::null_mut()
+}
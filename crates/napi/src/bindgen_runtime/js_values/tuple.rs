@@ -0,0 +1,73 @@
+use std::ptr;
+
+use super::{Array, FromNapiValue, ToNapiValue, TypeName, ValidateNapiValue};
+use crate::{check_status, sys, Error, Result, Status, ValueType};
+
+/// The tuples are converted from and to the arrays of the same length, `[number, string]` of `(u32, String)`.
+macro_rules! impl_tuple {
+  ($len:literal; $($ident:ident $index:tt),+) => {
+    impl<$($ident),+> TypeName for ($($ident,)+) {
+      fn type_name() -> &'static str {
+        concat!("Tuple", $len)
+      }
+
+      fn value_type() -> ValueType {
+        ValueType::Object
+      }
+    }
+
+    impl<$($ident: ToNapiValue),+> ToNapiValue for ($($ident,)+) {
+      unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+        let mut arr = Array::new(env, $len)?;
+        $(arr.set($index, val.$index)?;)+
+        unsafe { Array::to_napi_value(env, arr) }
+      }
+    }
+
+    impl<$($ident: FromNapiValue),+> FromNapiValue for ($($ident,)+) {
+      unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+        unsafe { validate_tuple(env, napi_val) }?;
+        let arr = unsafe { Array::from_napi_value(env, napi_val) }?;
+        if arr.len() != $len {
+          return Err(Error::new(
+            Status::InvalidArg,
+            format!("Expect an array of {} elements, but received {} elements", $len, arr.len()),
+          ));
+        }
+        // in the bounds, checked by the length
+        Ok(($(arr.get::<$ident>($index)?.unwrap(),)+))
+      }
+    }
+
+    impl<$($ident: FromNapiValue),+> ValidateNapiValue for ($($ident,)+) {
+      unsafe fn validate(env: sys::napi_env, napi_val: sys::napi_value) -> Result<sys::napi_value> {
+        unsafe { validate_tuple(env, napi_val) }?;
+        Ok(ptr::null_mut())
+      }
+    }
+  };
+}
+
+unsafe fn validate_tuple(env: sys::napi_env, napi_val: sys::napi_value) -> Result<()> {
+  let mut is_array = false;
+  check_status!(
+    unsafe { sys::napi_is_array(env, napi_val, &mut is_array) },
+    "Failed to check given napi value is array"
+  )?;
+  if !is_array {
+    return Err(Error::new(
+      Status::ArrayExpected,
+      "Expect the tuple to be an array".to_owned(),
+    ));
+  }
+  Ok(())
+}
+
+impl_tuple!(1; A 0);
+impl_tuple!(2; A 0, B 1);
+impl_tuple!(3; A 0, B 1, C 2);
+impl_tuple!(4; A 0, B 1, C 2, D 3);
+impl_tuple!(5; A 0, B 1, C 2, D 3, E 4);
+impl_tuple!(6; A 0, B 1, C 2, D 3, E 4, F 5);
+impl_tuple!(7; A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_tuple!(8; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
@@ -1,10 +1,16 @@
 use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
 use std::ffi::c_void;
+use std::mem::{self, ManuallyDrop};
 use std::ops::{Deref, DerefMut};
+use std::ptr;
 use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread::{self, ThreadId};
 
-use crate::{check_status, Env, Error, Result, Status};
+use super::{FromNapiRef, FromNapiValue, ToNapiValue, TypeName, ValidateNapiValue};
+use crate::{check_status, sys, Env, Error, Result, Status, ValueType};
 
 type RefInformation = (
   *mut c_void,
@@ -12,19 +18,94 @@ type RefInformation = (
   *const Cell<*mut dyn FnOnce()>,
 );
 
+/// `RefInformation` and the id of the instance, the addresses of the finalized instances may be reused
+pub(crate) type InstanceInformation = (RefInformation, usize);
+
+static NEXT_INSTANCE_ID: AtomicUsize = AtomicUsize::new(0);
+
 thread_local! {
-  pub(crate) static REFERENCE_MAP: RefCell<HashMap<*mut c_void, RefInformation>> = Default::default();
+  pub(crate) static REFERENCE_MAP: RefCell<HashMap<*mut c_void, InstanceInformation>> = Default::default();
+}
+
+/// The `Reference` dropped off the JavaScript thread, unref on the JavaScript thread it's created on
+struct DroppedReference {
+  thread: ThreadId,
+  env: sys::napi_env,
+  raw: *mut c_void,
+  id: usize,
+  napi_ref: sys::napi_ref,
+  finalize_callbacks: Rc<Cell<*mut dyn FnOnce()>>,
+}
+
+// only touched on the JavaScript thread of it, it's moved but not cloned on the other threads
+unsafe impl Send for DroppedReference {}
+
+lazy_static::lazy_static! {
+  static ref DROPPED_REFERENCES: Mutex<Vec<DroppedReference>> = Default::default();
+}
+
+/// Unref the `Reference`s of `env` dropped on the other threads, e.g. in the tokio runtime after the `async fn` returned.
+///
+/// It's called while creating and dropping the `Reference`s, and settling the `Promise`s of the `async fn`s.
+pub(crate) fn unref_dropped_references(env: sys::napi_env) {
+  let current = thread::current().id();
+  let dropped = match DROPPED_REFERENCES.lock() {
+    Ok(mut dropped) if !dropped.is_empty() => {
+      let (current_env, others) = mem::take(&mut *dropped)
+        .into_iter()
+        .partition::<Vec<_>, _>(|dropped| dropped.thread == current && dropped.env == env);
+      *dropped = others;
+      current_env
+    }
+    _ => return,
+  };
+  for DroppedReference {
+    raw,
+    id,
+    napi_ref,
+    finalize_callbacks,
+    ..
+  } in dropped
+  {
+    // the `Rc` is only touched on the JavaScript thread
+    if is_alive(raw, id) {
+      unsafe { sys::napi_reference_unref(env, napi_ref, &mut 0) };
+    } else {
+      finalize_if_last(finalize_callbacks);
+    }
+  }
+}
+
+/// The `napi_ref` of the instance is deleted after it's finalized,
+/// which is possible while it's referenced if the env is torn down.
+fn is_alive(raw: *mut c_void, id: usize) -> bool {
+  REFERENCE_MAP
+    .with(|map| matches!(map.borrow().get(&raw), Some((_, instance_id)) if *instance_id == id))
+}
+
+/// The instance finalized while it's referenced is dropped with the last `Reference` of it
+fn finalize_if_last(finalize_callbacks: Rc<Cell<*mut dyn FnOnce()>>) {
+  if Rc::strong_count(&finalize_callbacks) == 1 {
+    let finalize = unsafe { Box::from_raw(finalize_callbacks.get()) };
+    finalize();
+  }
 }
 
 /// ### Experimental feature
 ///
 /// Create a `reference` from `Class` instance.
 /// Unref the `Reference` when the `Reference` is dropped.
+///
+/// Keeps the instance alive until it's dropped,
+/// it's received from the instance of the class `T` passed from JavaScript, and returned as the instance.
+/// It's unref on the JavaScript thread even if it's dropped on the other threads.
 pub struct Reference<T> {
   raw: *mut T,
+  id: usize,
   napi_ref: crate::sys::napi_ref,
   env: *mut c_void,
-  finalize_callbacks: Rc<Cell<*mut dyn FnOnce()>>,
+  thread: ThreadId,
+  finalize_callbacks: ManuallyDrop<Rc<Cell<*mut dyn FnOnce()>>>,
 }
 
 unsafe impl<T: Send> Send for Reference<T> {}
@@ -32,27 +113,47 @@ unsafe impl<T: Sync> Sync for Reference<T> {}
 
 impl<T> Drop for Reference<T> {
   fn drop(&mut self) {
-    let status = unsafe {
-      crate::sys::napi_reference_unref(self.env as crate::sys::napi_env, self.napi_ref, &mut 0)
-    };
-    debug_assert!(
-      status == crate::sys::Status::napi_ok,
-      "Reference unref failed"
-    );
+    let finalize_callbacks = unsafe { ManuallyDrop::take(&mut self.finalize_callbacks) };
+    let env = self.env as crate::sys::napi_env;
+    if thread::current().id() != self.thread {
+      if let Ok(mut dropped) = DROPPED_REFERENCES.lock() {
+        dropped.push(DroppedReference {
+          thread: self.thread,
+          env,
+          raw: self.raw as *mut c_void,
+          id: self.id,
+          napi_ref: self.napi_ref,
+          finalize_callbacks,
+        });
+      }
+      return;
+    }
+    if is_alive(self.raw as *mut c_void, self.id) {
+      let status = unsafe { crate::sys::napi_reference_unref(env, self.napi_ref, &mut 0) };
+      debug_assert!(
+        status == crate::sys::Status::napi_ok,
+        "Reference unref failed"
+      );
+    } else {
+      finalize_if_last(finalize_callbacks);
+    }
+    unref_dropped_references(env);
   }
 }
 
 impl<T> Reference<T> {
   #[doc(hidden)]
   pub fn add_ref(t: *mut c_void, value: RefInformation) {
+    let id = NEXT_INSTANCE_ID.fetch_add(1, Ordering::Relaxed);
     REFERENCE_MAP.with(|map| {
-      map.borrow_mut().insert(t, value);
+      map.borrow_mut().insert(t, (value, id));
     });
   }
 
   #[doc(hidden)]
   pub unsafe fn from_value_ptr(t: *mut c_void, env: crate::sys::napi_env) -> Result<Self> {
-    if let Some((wrapped_value, napi_ref, finalize_callbacks_ptr)) =
+    unref_dropped_references(env);
+    if let Some(((wrapped_value, napi_ref, finalize_callbacks_ptr), id)) =
       REFERENCE_MAP.with(|map| map.borrow().get(&t).cloned())
     {
       check_status!(
@@ -65,9 +166,11 @@ impl<T> Reference<T> {
       Rc::into_raw(finalize_callbacks_raw);
       Ok(Self {
         raw: wrapped_value as *mut T,
+        id,
         napi_ref,
         env: env as *mut c_void,
-        finalize_callbacks,
+        thread: thread::current().id(),
+        finalize_callbacks: ManuallyDrop::new(finalize_callbacks),
       })
     } else {
       Err(Error::new(
@@ -76,19 +179,33 @@ impl<T> Reference<T> {
       ))
     }
   }
+
+  /// The `WeakReference` doesn't keep the instance alive
+  pub fn downgrade(&self) -> WeakReference<T> {
+    WeakReference {
+      raw: self.raw,
+      id: self.id,
+      napi_ref: self.napi_ref,
+    }
+  }
 }
 
 impl<T: 'static> Reference<T> {
   pub fn clone(&self, env: Env) -> Result<Self> {
-    let mut ref_count = 0;
-    check_status!(
-      unsafe { crate::sys::napi_reference_ref(env.0, self.napi_ref, &mut ref_count) },
-      "Failed to ref napi reference"
-    )?;
+    // the finalized instance is kept by the `Rc` only
+    if is_alive(self.raw as *mut c_void, self.id) {
+      let mut ref_count = 0;
+      check_status!(
+        unsafe { crate::sys::napi_reference_ref(env.0, self.napi_ref, &mut ref_count) },
+        "Failed to ref napi reference"
+      )?;
+    }
     Ok(Self {
       raw: self.raw,
+      id: self.id,
       napi_ref: self.napi_ref,
       env: env.0 as *mut c_void,
+      thread: thread::current().id(),
       finalize_callbacks: self.finalize_callbacks.clone(),
     })
   }
@@ -114,6 +231,46 @@ impl<T: 'static> Reference<T> {
   }
 }
 
+impl<T: TypeName> TypeName for Reference<T> {
+  fn type_name() -> &'static str {
+    T::type_name()
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl<T: FromNapiRef + 'static> FromNapiValue for Reference<T> {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let instance = unsafe { T::from_napi_ref(env, napi_val) }? as *const T;
+    unsafe { Self::from_value_ptr(instance as *mut c_void, env) }
+  }
+}
+
+impl<T: FromNapiRef + TypeName + 'static> ValidateNapiValue for Reference<T> {
+  fn type_of() -> Vec<ValueType> {
+    vec![ValueType::Object]
+  }
+}
+
+impl<T> ToNapiValue for Reference<T> {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    if !is_alive(val.raw as *mut c_void, val.id) {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "The instance of the reference is finalized".to_owned(),
+      ));
+    }
+    let mut instance = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_get_reference_value(env, val.napi_ref, &mut instance) },
+      "Failed to get the instance of the reference"
+    )?;
+    Ok(instance)
+  }
+}
+
 impl<T> Deref for Reference<T> {
   type Target = T;
 
@@ -128,6 +285,43 @@ impl<T> DerefMut for Reference<T> {
   }
 }
 
+/// The instance of the class `T` which may be garbage collected, created by `Reference::downgrade`.
+///
+/// It can only be used on the JavaScript thread.
+pub struct WeakReference<T> {
+  raw: *mut T,
+  id: usize,
+  napi_ref: crate::sys::napi_ref,
+}
+
+impl<T> Clone for WeakReference<T> {
+  fn clone(&self) -> Self {
+    Self {
+      raw: self.raw,
+      id: self.id,
+      napi_ref: self.napi_ref,
+    }
+  }
+}
+
+impl<T> WeakReference<T> {
+  /// The `Reference` keeping the instance alive, `None` if the instance is garbage collected
+  pub fn upgrade(&self, env: Env) -> Result<Option<Reference<T>>> {
+    if !is_alive(self.raw as *mut c_void, self.id) {
+      return Ok(None);
+    }
+    let mut instance = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_get_reference_value(env.0, self.napi_ref, &mut instance) },
+      "Failed to get the instance of the weak reference"
+    )?;
+    if instance.is_null() {
+      return Ok(None);
+    }
+    unsafe { Reference::from_value_ptr(self.raw as *mut c_void, env.0) }.map(Some)
+  }
+}
+
 /// ### Experimental feature
 ///
 /// Create a `SharedReference` from an existed `Reference`.
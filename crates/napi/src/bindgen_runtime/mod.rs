@@ -1,21 +1,92 @@
-use std::ffi::c_void;
+use std::ffi::{c_void, CString};
 use std::mem;
+use std::ptr;
 use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 pub use callback_info::*;
 pub use ctor::ctor;
 pub use env::*;
+pub use error_class::*;
 pub use js_values::*;
 pub use module_register::*;
+pub use panic::*;
+pub use type_tag::*;
 
 use super::sys;
 use crate::Status;
 
 mod callback_info;
 mod env;
+mod env_cache;
 mod error;
+mod error_class;
 mod js_values;
 mod module_register;
+mod panic;
+mod type_tag;
+
+/// The first Electron version which enables the V8 memory cage
+const ELECTRON_MEMORY_CAGE_VERSION: u32 = 21;
+
+static EXTERNAL_BUFFER_DISALLOWED: AtomicBool = AtomicBool::new(false);
+
+/// Electron >= 21 enables the V8 memory cage, creating external `Buffer`/`ArrayBuffer` would crash the process.
+///
+/// In this mode the data is copied into the memory allocated by V8 instead.
+pub fn is_external_buffer_disallowed() -> bool {
+  EXTERNAL_BUFFER_DISALLOWED.load(Ordering::Relaxed)
+}
+
+fn is_electron_with_memory_cage(version: &str) -> bool {
+  version
+    .split('.')
+    .next()
+    .and_then(|major| major.parse::<u32>().ok())
+    .map(|major| major >= ELECTRON_MEMORY_CAGE_VERSION)
+    .unwrap_or(false)
+}
+
+/// Detect the memory cage from `process.versions.electron`,
+/// or the `NAPI_RS_ELECTRON_VERSION` env passed in by `napi build --electron-version`.
+pub(crate) unsafe fn detect_external_buffer_support(env: sys::napi_env) {
+  if option_env!("NAPI_RS_ELECTRON_VERSION")
+    .map(is_electron_with_memory_cage)
+    .unwrap_or(false)
+  {
+    EXTERNAL_BUFFER_DISALLOWED.store(true, Ordering::Relaxed);
+    return;
+  }
+  let mut value = ptr::null_mut();
+  if unsafe { sys::napi_get_global(env, &mut value) } != sys::Status::napi_ok {
+    return;
+  }
+  for key in ["process", "versions", "electron"] {
+    let key = CString::new(key).unwrap();
+    let mut has_property = false;
+    let mut next = ptr::null_mut();
+    if unsafe { sys::napi_has_named_property(env, value, key.as_ptr(), &mut has_property) }
+      != sys::Status::napi_ok
+      || !has_property
+      || unsafe { sys::napi_get_named_property(env, value, key.as_ptr(), &mut next) }
+        != sys::Status::napi_ok
+    {
+      return;
+    }
+    value = next;
+  }
+  let mut len = 0;
+  let mut buf = [0u8; 32];
+  if unsafe {
+    sys::napi_get_value_string_utf8(env, value, buf.as_mut_ptr() as *mut _, buf.len(), &mut len)
+  } != sys::Status::napi_ok
+  {
+    return;
+  }
+  if let Ok(version) = std::str::from_utf8(&buf[..len]) {
+    EXTERNAL_BUFFER_DISALLOWED.store(is_electron_with_memory_cage(version), Ordering::Relaxed);
+  }
+}
 
 /// # Safety
 ///
@@ -25,24 +96,42 @@ pub unsafe extern "C" fn raw_finalize_unchecked<T>(
   finalize_data: *mut c_void,
   _finalize_hint: *mut c_void,
 ) {
-  unsafe { Box::from_raw(finalize_data as *mut T) };
-  if let Some((_, ref_val, finalize_callbacks_ptr)) =
+  if let Some(((_, ref_val, finalize_callbacks_ptr), _)) =
     REFERENCE_MAP.with(|reference_map| reference_map.borrow_mut().remove(&finalize_data))
   {
     let finalize_callbacks_rc = unsafe { Rc::from_raw(finalize_callbacks_ptr) };
-    debug_assert!(Rc::strong_count(&finalize_callbacks_rc) == 1);
-    debug_assert!(Rc::weak_count(&finalize_callbacks_rc) == 0);
-    let finalize = unsafe { Box::from_raw(finalize_callbacks_rc.get()) };
-    finalize();
     let delete_reference_status = unsafe { sys::napi_delete_reference(env, ref_val) };
     debug_assert!(
       delete_reference_status == sys::Status::napi_ok,
       "Delete reference in finalize callback failed {}",
       Status::from(delete_reference_status)
     );
+    if Rc::strong_count(&finalize_callbacks_rc) > 1 {
+      // the `Reference`s kept by the other instances may be still alive while the env is torn down,
+      // the instance is dropped with the last of them, which may still deref it
+      let drop_instance: unsafe fn(*mut c_void) = drop_instance::<T>;
+      let prev_finalize = unsafe { Box::from_raw(finalize_callbacks_rc.get()) };
+      let finalize: Box<dyn FnOnce()> = Box::new(move || {
+        unsafe { drop_instance(finalize_data) };
+        prev_finalize();
+      });
+      finalize_callbacks_rc.set(Box::into_raw(finalize));
+      return;
+    }
+    debug_assert!(Rc::strong_count(&finalize_callbacks_rc) == 1);
+    debug_assert!(Rc::weak_count(&finalize_callbacks_rc) == 0);
+    unsafe { drop_instance::<T>(finalize_data) };
+    let finalize = unsafe { Box::from_raw(finalize_callbacks_rc.get()) };
+    finalize();
+  } else {
+    unsafe { drop_instance::<T>(finalize_data) };
   }
 }
 
+unsafe fn drop_instance<T>(instance: *mut c_void) {
+  mem::drop(unsafe { Box::from_raw(instance as *mut T) });
+}
+
 /// # Safety
 ///
 /// called when node buffer is ready for gc
@@ -10,9 +10,10 @@ use std::sync::{atomic::Ordering, Mutex};
 
 use lazy_static::lazy_static;
 
+use super::detect_external_buffer_support;
 use crate::{
-  check_status, check_status_or_throw, sys, Env, JsError, JsFunction, Property, Result, Value,
-  ValueType,
+  check_status, check_status_or_throw, sys, Callback, Env, HandleScopeId, JsError, JsFunction,
+  Property, Result, Value, ValueType,
 };
 
 pub type ExportRegisterCallback = unsafe fn(sys::napi_env) -> Result<sys::napi_value>;
@@ -94,6 +95,23 @@ lazy_static! {
   static ref MODULE_CLASS_PROPERTIES: ModuleClassProperty = Default::default();
   static ref MODULE_REGISTER_LOCK: Mutex<()> = Mutex::new(());
   static ref REGISTERED: AtomicBool = AtomicBool::new(false);
+  static ref MODULE_INIT: PersistedSingleThreadVec<ModuleExportsCallback> = Default::default();
+  /// `(class, parent)` of `#[napi(extends)]`, by the Rust names
+  static ref MODULE_CLASS_PARENTS: PersistedSingleThreadVec<(&'static str, &'static str)> =
+    Default::default();
+  /// `(class, symbol, method)` defined on the prototypes after the classes are defined, by the Rust names
+  static ref MODULE_CLASS_SYMBOL_METHODS: PersistedSingleThreadVec<(&'static str, ClassSymbol, Callback)> =
+    Default::default();
+}
+
+/// The symbols of the methods defined by `register_class_symbol_method`, `Property` only takes the string names
+#[doc(hidden)]
+#[derive(Debug, Clone, Copy)]
+pub enum ClassSymbol {
+  /// `Symbol.iterator`
+  Iterator,
+  /// `Symbol.for('nodejs.util.inspect.custom')`, the `util.inspect.custom` of Node.js
+  InspectCustom,
 }
 
 #[inline]
@@ -133,6 +151,12 @@ pub fn register_module_exports(callback: ModuleExportsCallback) {
   MODULE_EXPORTS.push(callback);
 }
 
+#[doc(hidden)]
+/// `#[napi(module_init)]`, called for every env before the other exports are registered on it
+pub fn register_module_init(callback: ModuleExportsCallback) {
+  MODULE_INIT.push(callback);
+}
+
 #[doc(hidden)]
 pub fn register_module_export(
   js_mod: Option<&'static str>,
@@ -168,6 +192,51 @@ pub fn register_class(
   });
 }
 
+#[doc(hidden)]
+pub fn register_class_parent(rust_name: &'static str, parent_rust_name: &'static str) {
+  MODULE_CLASS_PARENTS.push((rust_name, parent_rust_name));
+}
+
+/// The classes extending `rust_name` directly or indirectly, by the Rust names
+#[cfg(feature = "napi8")]
+pub(crate) fn class_descendants(rust_name: &str) -> Vec<&'static str> {
+  let mut descendants = vec![];
+  MODULE_CLASS_PARENTS.borrow_mut(|class_parents| {
+    let mut index = 0;
+    let mut ancestor = rust_name;
+    loop {
+      descendants.extend(
+        class_parents
+          .iter()
+          .filter(|(_, parent)| *parent == ancestor)
+          .map(|(class, _)| *class),
+      );
+      match descendants.get(index) {
+        Some(class) => ancestor = class,
+        None => break,
+      }
+      index += 1;
+    }
+  });
+  descendants
+}
+
+#[doc(hidden)]
+pub fn register_class_symbol_method(
+  rust_name: &'static str,
+  symbol: ClassSymbol,
+  method: Callback,
+) {
+  MODULE_CLASS_SYMBOL_METHODS.push((rust_name, symbol, method));
+}
+
+/// `[Symbol.iterator]()` of the classes implementing `Iterator` returns the instance,
+/// the instances are the iterators of themselves, the same as the Rust iterators.
+#[doc(hidden)]
+pub fn register_class_iterator(rust_name: &'static str) {
+  register_class_symbol_method(rust_name, ClassSymbol::Iterator, return_this);
+}
+
 #[inline]
 /// Get `JsFunction` from defined Rust `fn`
 /// ```rust
@@ -211,6 +280,7 @@ pub fn get_js_function(env: &Env, raw_fn: ExportRegisterCallback) -> Result<JsFu
           env: env.0,
           value: function,
           value_type: ValueType::Function,
+          scope: HandleScopeId::current(),
         }))
       })
       .ok_or_else(|| {
@@ -257,6 +327,173 @@ pub fn get_c_callback(raw_fn: ExportRegisterCallback) -> Result<crate::Callback>
   })
 }
 
+/// The exports object of `#[napi(namespace)]`, `a.b` is the `b` of `a` under the exports.
+/// The objects are created on the first use and shared by the exports and the classes of the namespace.
+fn get_js_mod_exports(
+  env: sys::napi_env,
+  exports: sys::napi_value,
+  // nul terminated
+  js_mod: &str,
+  exports_objects: &mut HashMap<String, sys::napi_value>,
+) -> Result<sys::napi_value> {
+  let js_mod = js_mod.trim_end_matches('\0');
+  let mut parent = exports;
+  let mut path_len = 0;
+  for name in js_mod.split('.') {
+    path_len += name.len();
+    let path = &js_mod[..path_len];
+    path_len += 1;
+    parent = match exports_objects.get(path) {
+      Some(object) => *object,
+      None => {
+        let mut object = ptr::null_mut();
+        check_status!(
+          unsafe { sys::napi_create_object(env, &mut object) },
+          "Create export JavaScript Object [{}] failed",
+          path
+        )?;
+        let name_c_string = CString::new(name)?;
+        check_status!(
+          unsafe { sys::napi_set_named_property(env, parent, name_c_string.as_ptr(), object) },
+          "Set exports Object [{}] into exports object failed",
+          path
+        )?;
+        exports_objects.insert(path.to_owned(), object);
+        object
+      }
+    };
+  }
+  Ok(parent)
+}
+
+fn get_named_property(
+  env: sys::napi_env,
+  object: sys::napi_value,
+  name: &str,
+) -> Result<sys::napi_value> {
+  let name_c_string = CString::new(name)?;
+  let mut value = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_get_named_property(env, object, name_c_string.as_ptr(), &mut value) },
+    "Get `{}` failed",
+    name
+  )?;
+  Ok(value)
+}
+
+/// `Object.setPrototypeOf(Class.prototype, Parent.prototype)` of `#[napi(extends)]`.
+fn inherit_prototype(
+  env: sys::napi_env,
+  class: sys::napi_value,
+  parent: sys::napi_value,
+) -> Result<()> {
+  let mut global = ptr::null_mut();
+  check_status!(unsafe { sys::napi_get_global(env, &mut global) })?;
+  let object = get_named_property(env, global, "Object")?;
+  let set_prototype_of = get_named_property(env, object, "setPrototypeOf")?;
+  // the statics are looked up from the parent as well, the same as `class Child extends Parent`
+  for (object_value, parent_value) in [
+    (
+      get_named_property(env, class, "prototype")?,
+      get_named_property(env, parent, "prototype")?,
+    ),
+    (class, parent),
+  ] {
+    let args = [object_value, parent_value];
+    check_status!(
+      unsafe {
+        sys::napi_call_function(
+          env,
+          object,
+          set_prototype_of,
+          args.len(),
+          args.as_ptr(),
+          ptr::null_mut(),
+        )
+      },
+      "Set the prototype of the parent class failed"
+    )?;
+  }
+  Ok(())
+}
+
+/// `Class.prototype[symbol] = method`
+fn define_symbol_method(
+  env: sys::napi_env,
+  class: sys::napi_value,
+  symbol: ClassSymbol,
+  method: Callback,
+) -> Result<()> {
+  let mut global = ptr::null_mut();
+  check_status!(unsafe { sys::napi_get_global(env, &mut global) })?;
+  let symbol_constructor = get_named_property(env, global, "Symbol")?;
+  let (symbol_value, fn_name) = match symbol {
+    ClassSymbol::Iterator => (
+      get_named_property(env, symbol_constructor, "iterator")?,
+      "[Symbol.iterator]",
+    ),
+    ClassSymbol::InspectCustom => {
+      let symbol_for = get_named_property(env, symbol_constructor, "for")?;
+      let key = "nodejs.util.inspect.custom";
+      let mut key_value = ptr::null_mut();
+      check_status!(unsafe {
+        sys::napi_create_string_utf8(env, key.as_ptr() as *const _, key.len(), &mut key_value)
+      })?;
+      let mut symbol_value = ptr::null_mut();
+      check_status!(
+        unsafe {
+          sys::napi_call_function(
+            env,
+            symbol_constructor,
+            symbol_for,
+            1,
+            [key_value].as_ptr(),
+            &mut symbol_value,
+          )
+        },
+        "Get `Symbol.for('{}')` failed",
+        key
+      )?;
+      (symbol_value, "[nodejs.util.inspect.custom]")
+    }
+  };
+  let prototype = get_named_property(env, class, "prototype")?;
+  let mut method_fn = ptr::null_mut();
+  check_status!(unsafe {
+    sys::napi_create_function(
+      env,
+      fn_name.as_ptr() as *const _,
+      fn_name.len(),
+      Some(method),
+      ptr::null_mut(),
+      &mut method_fn,
+    )
+  })?;
+  check_status!(
+    unsafe { sys::napi_set_property(env, prototype, symbol_value, method_fn) },
+    "Define `{}` of the class failed",
+    fn_name
+  )
+}
+
+unsafe extern "C" fn return_this(
+  env: sys::napi_env,
+  cb_info: sys::napi_callback_info,
+) -> sys::napi_value {
+  let mut this = ptr::null_mut();
+  unsafe {
+    sys::napi_get_cb_info(
+      env,
+      cb_info,
+      ptr::null_mut(),
+      ptr::null_mut(),
+      &mut this,
+      ptr::null_mut(),
+    )
+  };
+  this
+}
+
 #[no_mangle]
 unsafe extern "C" fn napi_register_module_v1(
   env: sys::napi_env,
@@ -265,7 +502,22 @@ unsafe extern "C" fn napi_register_module_v1(
   let lock = MODULE_REGISTER_LOCK
     .lock()
     .expect("Failed to acquire module register lock");
-  let mut exports_objects: HashSet<String> = HashSet::default();
+  unsafe { detect_external_buffer_support(env) };
+  crate::js_values::raw_scope::register_env_thread(env);
+  let mut module_init_result = Ok(());
+  MODULE_INIT.borrow_mut(|inner| {
+    module_init_result = inner
+      .iter()
+      .try_for_each(|callback| unsafe { callback(env, exports) });
+  });
+  if let Err(e) = module_init_result {
+    // the exports depending on the init are not registered, `require` throws the error
+    unsafe { JsError::from(e).throw_into(env) };
+    mem::drop(lock);
+    REGISTERED.store(true, Ordering::SeqCst);
+    return exports;
+  }
+  let mut exports_objects: HashMap<String, sys::napi_value> = HashMap::default();
   MODULE_REGISTER_CALLBACK.borrow_mut(|inner| {
     inner
       .iter_mut()
@@ -282,51 +534,22 @@ unsafe extern "C" fn napi_register_module_v1(
       )
       .iter()
       .for_each(|(js_mod, items)| {
-        let mut exports_js_mod = ptr::null_mut();
-        if let Some(js_mod_str) = js_mod {
-          let mod_name_c_str =
-            unsafe { CStr::from_bytes_with_nul_unchecked(js_mod_str.as_bytes()) };
-          if exports_objects.contains(*js_mod_str) {
-            check_status_or_throw!(
-              env,
-              unsafe {
-                sys::napi_get_named_property(
-                  env,
-                  exports,
-                  mod_name_c_str.as_ptr(),
-                  &mut exports_js_mod,
-                )
-              },
-              "Get mod {} from exports failed",
-              js_mod_str,
-            );
-          } else {
-            check_status_or_throw!(
-              env,
-              unsafe { sys::napi_create_object(env, &mut exports_js_mod) },
-              "Create export JavaScript Object [{}] failed",
-              js_mod_str
-            );
-            check_status_or_throw!(
-              env,
-              unsafe {
-                sys::napi_set_named_property(env, exports, mod_name_c_str.as_ptr(), exports_js_mod)
-              },
-              "Set exports Object [{}] into exports object failed",
-              js_mod_str
-            );
-            exports_objects.insert(js_mod_str.to_string());
+        let exported_object = match js_mod {
+          Some(js_mod_str) => {
+            match get_js_mod_exports(env, exports, js_mod_str, &mut exports_objects) {
+              Ok(exports_js_mod) => exports_js_mod,
+              Err(e) => {
+                unsafe { JsError::from(e).throw_into(env) };
+                return;
+              }
+            }
           }
-        }
+          None => exports,
+        };
         for (name, callback) in items {
           unsafe {
             let js_name = CStr::from_bytes_with_nul_unchecked(name.as_bytes());
             if let Err(e) = callback(env).and_then(|v| {
-              let exported_object = if exports_js_mod.is_null() {
-                exports
-              } else {
-                exports_js_mod
-              };
               check_status!(
                 sys::napi_set_named_property(env, exported_object, js_name.as_ptr(), v),
                 "Failed to register export `{}`",
@@ -340,41 +563,48 @@ unsafe extern "C" fn napi_register_module_v1(
       })
   });
 
+  let mut class_parents: HashMap<&'static str, &'static str> = HashMap::default();
+  MODULE_CLASS_PARENTS.borrow_mut(|inner| class_parents.extend(inner.iter().copied()));
+  // linked by `#[napi(extends)]` after all of them are defined
+  let mut defined_classes: HashMap<&'static str, sys::napi_value> = HashMap::default();
   MODULE_CLASS_PROPERTIES.borrow_mut(|inner| {
+    let inner = &*inner;
     inner.iter().for_each(|(rust_name, js_mods)| {
       for (js_mod, (js_name, props)) in js_mods {
-        let mut exports_js_mod = ptr::null_mut();
-        unsafe {
-          if let Some(js_mod_str) = js_mod {
-            let mod_name_c_str = CStr::from_bytes_with_nul_unchecked(js_mod_str.as_bytes());
-            if exports_objects.contains(*js_mod_str) {
-              check_status_or_throw!(
-                env,
-                sys::napi_get_named_property(
-                  env,
-                  exports,
-                  mod_name_c_str.as_ptr(),
-                  &mut exports_js_mod,
-                ),
-                "Get mod {} from exports failed",
-                js_mod_str,
-              );
-            } else {
-              check_status_or_throw!(
-                env,
-                sys::napi_create_object(env, &mut exports_js_mod),
-                "Create export JavaScript Object [{}] failed",
-                js_mod_str
-              );
-              check_status_or_throw!(
-                env,
-                sys::napi_set_named_property(env, exports, mod_name_c_str.as_ptr(), exports_js_mod),
-                "Set exports Object [{}] into exports object failed",
-                js_mod_str
-              );
-              exports_objects.insert(js_mod_str.to_string());
+        let exported_object = match js_mod {
+          Some(js_mod_str) => {
+            match get_js_mod_exports(env, exports, js_mod_str, &mut exports_objects) {
+              Ok(exports_js_mod) => exports_js_mod,
+              Err(e) => {
+                unsafe { JsError::from(e).throw_into(env) };
+                return;
+              }
             }
           }
+          None => exports,
+        };
+        unsafe {
+          // V8 checks `this` of the methods is created by the class defining them,
+          // so the instance members of the parents are defined by the class as well
+          let mut props = props.clone();
+          let mut defined_names: HashSet<CString> =
+            props.iter().map(|prop| prop.name.clone()).collect();
+          let mut parent = class_parents.get(rust_name);
+          while let Some(parent_rust_name) = parent {
+            let parent_props = inner
+              .get(parent_rust_name)
+              .into_iter()
+              .flat_map(|js_mods| js_mods.values())
+              .flat_map(|(_, parent_props)| parent_props.iter())
+              .filter(|prop| {
+                !prop.is_ctor && !prop.is_static() && !defined_names.contains(&prop.name)
+              })
+              .cloned()
+              .collect::<Vec<_>>();
+            defined_names.extend(parent_props.iter().map(|prop| prop.name.clone()));
+            props.extend(parent_props);
+            parent = class_parents.get(parent_rust_name);
+          }
           let (ctor, props): (Vec<_>, Vec<_>) = props.iter().partition(|prop| prop.is_ctor);
           // one or more or zero?
           // zero is for `#[napi(task)]`
@@ -404,6 +634,8 @@ unsafe extern "C" fn napi_register_module_v1(
             &rust_name
           );
 
+          defined_classes.insert(rust_name, class_ptr);
+
           let mut ctor_ref = ptr::null_mut();
           sys::napi_create_reference(env, class_ptr, 1, &mut ctor_ref);
 
@@ -414,16 +646,7 @@ unsafe extern "C" fn napi_register_module_v1(
 
           check_status_or_throw!(
             env,
-            sys::napi_set_named_property(
-              env,
-              if exports_js_mod.is_null() {
-                exports
-              } else {
-                exports_js_mod
-              },
-              js_class_name.as_ptr(),
-              class_ptr
-            ),
+            sys::napi_set_named_property(env, exported_object, js_class_name.as_ptr(), class_ptr),
             "Failed to register class `{}` generate by struct `{}`",
             &js_name,
             &rust_name
@@ -433,6 +656,40 @@ unsafe extern "C" fn napi_register_module_v1(
     })
   });
 
+  MODULE_CLASS_PARENTS.borrow_mut(|inner| {
+    for (rust_name, parent_rust_name) in inner.iter() {
+      let linked = match (
+        defined_classes.get(rust_name),
+        defined_classes.get(parent_rust_name),
+      ) {
+        (Some(class), Some(parent)) => inherit_prototype(env, *class, *parent),
+        // the class without any members is not defined
+        _ => Err(crate::Error::new(
+          crate::Status::GenericFailure,
+          format!(
+            "The parent class `{}` of `{}` is not defined, it has no constructor, methods or fields",
+            parent_rust_name, rust_name
+          ),
+        )),
+      };
+      if let Err(e) = linked {
+        unsafe { JsError::from(e).throw_into(env) };
+        return;
+      }
+    }
+  });
+
+  MODULE_CLASS_SYMBOL_METHODS.borrow_mut(|inner| {
+    for (rust_name, symbol, method) in inner.iter() {
+      if let Some(class) = defined_classes.get(rust_name) {
+        if let Err(e) = define_symbol_method(env, *class, *symbol, *method) {
+          unsafe { JsError::from(e).throw_into(env) };
+          return;
+        }
+      }
+    }
+  });
+
   #[cfg(feature = "compat-mode")]
   MODULE_EXPORTS.borrow_mut(|inner| {
     inner.iter().for_each(|callback| unsafe {
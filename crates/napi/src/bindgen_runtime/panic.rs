@@ -0,0 +1,92 @@
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+
+use super::{FromNapiValue, Object};
+use crate::{sys, Error, JsError, Result, Status};
+
+static INSTALL_PANIC_HOOK: Once = Once::new();
+
+thread_local! {
+  /// The depth of the `catch_panic` calls on the thread, the panics are only captured inside them
+  static CATCHING: Cell<usize> = Default::default();
+  static PANIC_BACKTRACE: RefCell<Option<String>> = Default::default();
+}
+
+/// Call `call`, the panic in it is thrown as the JavaScript error instead of aborting the process,
+/// if `catch` is set by `#[napi(catch_unwind)]` or the `catch_unwind` feature is enabled.
+///
+/// The `message` of the error is the message of the panic,
+/// the `backtrace` of it is the backtrace of the panic with the `catch_unwind` or the `backtrace` feature.
+///
+/// # Safety
+///
+/// `env` must be the env of the current JavaScript thread
+#[doc(hidden)]
+pub unsafe fn catch_panic<F>(env: sys::napi_env, catch: bool, call: F) -> Result<sys::napi_value>
+where
+  F: FnOnce() -> Result<sys::napi_value>,
+{
+  if !catch && !cfg!(feature = "catch_unwind") {
+    return call();
+  }
+  INSTALL_PANIC_HOOK.call_once(install_panic_hook);
+  CATCHING.with(|depth| depth.set(depth.get() + 1));
+  let ret = panic::catch_unwind(AssertUnwindSafe(call));
+  CATCHING.with(|depth| depth.set(depth.get() - 1));
+  ret.unwrap_or_else(|payload| {
+    let backtrace = PANIC_BACKTRACE.with(|backtrace| backtrace.borrow_mut().take());
+    unsafe { throw_panic(env, payload, backtrace) }
+  })
+}
+
+/// The panics caught by `catch_panic` are reported by the thrown errors,
+/// the others are still reported by the previous hook.
+fn install_panic_hook() {
+  let previous_hook = panic::take_hook();
+  panic::set_hook(Box::new(move |info| {
+    if CATCHING.with(Cell::get) == 0 {
+      previous_hook(info);
+      return;
+    }
+    let backtrace = capture_backtrace();
+    PANIC_BACKTRACE.with(|captured| *captured.borrow_mut() = backtrace);
+  }));
+}
+
+#[cfg(feature = "backtrace")]
+fn capture_backtrace() -> Option<String> {
+  Some(format!("{:?}", backtrace::Backtrace::new()))
+}
+
+#[cfg(not(feature = "backtrace"))]
+fn capture_backtrace() -> Option<String> {
+  None
+}
+
+unsafe fn throw_panic(
+  env: sys::napi_env,
+  payload: Box<dyn Any + Send>,
+  backtrace: Option<String>,
+) -> Result<sys::napi_value> {
+  let message = if let Some(message) = payload.downcast_ref::<String>() {
+    message.as_str()
+  } else if let Some(message) = payload.downcast_ref::<&str>() {
+    message
+  } else {
+    "<no error message>"
+  };
+  let js_error = unsafe {
+    JsError::from(Error::from_reason(format!(
+      "panic from Rust code: {}",
+      message
+    )))
+    .into_value(env)
+  };
+  if let Some(backtrace) = backtrace {
+    unsafe { Object::from_napi_value(env, js_error) }?.set("backtrace", backtrace)?;
+  }
+  unsafe { sys::napi_throw(env, js_error) };
+  Err(Error::from_status(Status::PendingException))
+}
@@ -0,0 +1,109 @@
+//! The instances of the `#[napi]` classes are tagged by `napi_type_tag_object` when they are wrapped,
+//! the tags are checked before they are unwrapped, so the objects not created by the class,
+//! or created by the class of the same name in the other addon, are rejected instead of being read as the class.
+//!
+//! The tags are only checked with the `napi8` feature.
+
+use super::TypeName;
+use crate::{sys, Result};
+
+/// Tag the `instance` wrapping the class `T`
+///
+/// # Safety
+///
+/// `env` must be the env of the current JavaScript thread
+#[doc(hidden)]
+#[cfg(feature = "napi8")]
+pub unsafe fn tag_class_instance<T: TypeName>(
+  env: sys::napi_env,
+  instance: sys::napi_value,
+) -> Result<()> {
+  let type_tag = class_type_tag(T::type_name());
+  crate::check_status!(
+    unsafe { sys::napi_type_tag_object(env, instance, &type_tag) },
+    "Failed to tag the instance of class `{}`",
+    T::type_name(),
+  )
+}
+
+#[doc(hidden)]
+#[cfg(not(feature = "napi8"))]
+pub unsafe fn tag_class_instance<T: TypeName>(
+  _env: sys::napi_env,
+  _instance: sys::napi_value,
+) -> Result<()> {
+  Ok(())
+}
+
+/// `InvalidArg` error if the `value` is not the instance of the class `T` or the classes extending it
+///
+/// # Safety
+///
+/// `env` must be the env of the current JavaScript thread
+#[doc(hidden)]
+#[cfg(feature = "napi8")]
+pub unsafe fn check_class_instance<T: TypeName>(
+  env: sys::napi_env,
+  value: sys::napi_value,
+) -> Result<()> {
+  if unsafe { is_class_instance::<T>(env, value) }? {
+    Ok(())
+  } else {
+    Err(crate::Error::new(
+      crate::Status::InvalidArg,
+      format!("Value is not an instance of class `{}`", T::type_name()),
+    ))
+  }
+}
+
+#[doc(hidden)]
+#[cfg(not(feature = "napi8"))]
+pub unsafe fn check_class_instance<T: TypeName>(
+  _env: sys::napi_env,
+  _value: sys::napi_value,
+) -> Result<()> {
+  Ok(())
+}
+
+/// Whether the `value` is tagged by the class `T` or the classes extending it
+#[cfg(feature = "napi8")]
+pub(crate) unsafe fn is_class_instance<T: TypeName>(
+  env: sys::napi_env,
+  value: sys::napi_value,
+) -> Result<bool> {
+  let is_tagged = |rust_name: &str| {
+    let type_tag = class_type_tag(rust_name);
+    let mut result = false;
+    crate::check_status!(
+      unsafe { sys::napi_check_object_type_tag(env, value, &type_tag, &mut result) },
+      "Failed to check the type tag of class `{}`",
+      T::type_name(),
+    )
+    .map(|_| result)
+  };
+  if is_tagged(T::type_name())? {
+    return Ok(true);
+  }
+  for class in super::class_descendants(T::type_name()) {
+    if is_tagged(class)? {
+      return Ok(true);
+    }
+  }
+  Ok(false)
+}
+
+/// The Rust name of the class hashed by FNV-1a, and the address of a static of this addon,
+/// which is different in the other addons loaded in the same process
+#[cfg(feature = "napi8")]
+fn class_type_tag(rust_name: &str) -> sys::napi_type_tag {
+  static ADDON_SEED: u8 = 0;
+  let lower = rust_name
+    .bytes()
+    .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+      (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    });
+  sys::napi_type_tag {
+    lower,
+    upper: &ADDON_SEED as *const u8 as u64,
+  }
+}
@@ -2,6 +2,7 @@ use std::ptr;
 
 use crate::bindgen_runtime::{FromNapiValue, TypeName};
 use crate::check_status;
+use crate::js_values::raw_scope::HandleScopeGuard;
 use crate::{sys, Either, Env, Error, JsUndefined, NapiValue, Result, Status};
 
 /// Function call context
@@ -12,6 +13,8 @@ pub struct CallContext<'env> {
   args: &'env [sys::napi_value],
   /// arguments.length
   pub length: usize,
+  // the handle scope of the callback
+  _scope: HandleScopeGuard,
 }
 
 impl<'env> CallContext<'env> {
@@ -43,6 +46,7 @@ impl<'env> CallContext<'env> {
       callback_info,
       args,
       length,
+      _scope: HandleScopeGuard::open(),
     }
   }
 
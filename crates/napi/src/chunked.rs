@@ -0,0 +1,167 @@
+use std::ffi::CString;
+use std::os::raw::c_void;
+use std::ptr;
+use std::time::{Duration, Instant};
+
+use crate::{bindgen_runtime::ToNapiValue, check_status, sys, Env, Error, JsError, Result, Status};
+
+/// Returned by every step of [`Env::run_chunked`](./struct.Env.html#method.run_chunked).
+pub enum Chunk<T> {
+  /// There is more work to do, call the step again.
+  Continue,
+  /// The work is done, resolve the promise with the value.
+  Done(T),
+}
+
+struct ChunkedWork<T, F> {
+  step: F,
+  budget: Duration,
+  deferred: sys::napi_deferred,
+  /// reference to the function passed to `setImmediate`, reused for rescheduling
+  callback_ref: sys::napi_ref,
+  _output: std::marker::PhantomData<T>,
+}
+
+pub(crate) fn run<T, F>(env: sys::napi_env, budget: Duration, step: F) -> Result<sys::napi_value>
+where
+  T: ToNapiValue,
+  F: 'static + FnMut(Env) -> Result<Chunk<T>>,
+{
+  let name = CString::new("napi_rs_chunked_work")?;
+  let mut raw_promise = ptr::null_mut();
+  let mut deferred = ptr::null_mut();
+  check_status!(unsafe { sys::napi_create_promise(env, &mut deferred, &mut raw_promise) })?;
+  let work = Box::into_raw(Box::new(ChunkedWork::<T, F> {
+    step,
+    budget,
+    deferred,
+    callback_ref: ptr::null_mut(),
+    _output: std::marker::PhantomData,
+  }));
+  let mut callback = ptr::null_mut();
+  if let Err(e) = check_status!(unsafe {
+    sys::napi_create_function(
+      env,
+      name.as_ptr(),
+      name.as_bytes().len(),
+      Some(run_chunk::<T, F>),
+      work as *mut c_void,
+      &mut callback,
+    )
+  })
+  .and_then(|_| {
+    check_status!(unsafe {
+      sys::napi_create_reference(env, callback, 1, &mut (*work).callback_ref)
+    })
+  })
+  .and_then(|_| unsafe { schedule(env, callback) })
+  {
+    // nothing is scheduled, the work will never run
+    let work = unsafe { Box::from_raw(work) };
+    if !work.callback_ref.is_null() {
+      unsafe { sys::napi_delete_reference(env, work.callback_ref) };
+    }
+    return Err(e);
+  }
+  Ok(raw_promise)
+}
+
+/// Call `setImmediate(callback)`, so the next chunk runs after the pending I/O callbacks.
+unsafe fn schedule(env: sys::napi_env, callback: sys::napi_value) -> Result<()> {
+  let mut global = ptr::null_mut();
+  check_status!(unsafe { sys::napi_get_global(env, &mut global) })?;
+  let mut set_immediate = ptr::null_mut();
+  let key = CString::new("setImmediate")?;
+  check_status!(unsafe {
+    sys::napi_get_named_property(env, global, key.as_ptr(), &mut set_immediate)
+  })?;
+  check_status!(unsafe {
+    sys::napi_call_function(
+      env,
+      global,
+      set_immediate,
+      1,
+      [callback].as_ptr(),
+      ptr::null_mut(),
+    )
+  })
+}
+
+unsafe extern "C" fn run_chunk<T, F>(
+  env: sys::napi_env,
+  cb_info: sys::napi_callback_info,
+) -> sys::napi_value
+where
+  T: ToNapiValue,
+  F: 'static + FnMut(Env) -> Result<Chunk<T>>,
+{
+  let mut data = ptr::null_mut();
+  let mut callback = ptr::null_mut();
+  let status = unsafe {
+    sys::napi_get_cb_info(
+      env,
+      cb_info,
+      &mut 0,
+      ptr::null_mut(),
+      ptr::null_mut(),
+      &mut data,
+    )
+  };
+  let work = data as *mut ChunkedWork<T, F>;
+  if status != sys::Status::napi_ok || work.is_null() {
+    let e = Error::new(
+      Status::from(status),
+      "Failed to get the chunked work".to_owned(),
+    );
+    if work.is_null() {
+      unsafe { JsError::from(e).throw_into(env) };
+    } else {
+      unsafe { finish(env, work, Err(e)) };
+    }
+    return ptr::null_mut();
+  }
+  let started = Instant::now();
+  let output = loop {
+    match unsafe { ((*work).step)(Env::from_raw(env)) } {
+      Ok(Chunk::Continue) => {
+        if started.elapsed() < unsafe { (*work).budget } {
+          continue;
+        }
+        match check_status!(unsafe {
+          sys::napi_get_reference_value(env, (*work).callback_ref, &mut callback)
+        })
+        .and_then(|_| unsafe { schedule(env, callback) })
+        {
+          Ok(_) => return ptr::null_mut(),
+          Err(e) => break Err(e),
+        }
+      }
+      Ok(Chunk::Done(value)) => break unsafe { ToNapiValue::to_napi_value(env, value) },
+      Err(e) => break Err(e),
+    }
+  };
+  unsafe { finish(env, work, output) };
+  ptr::null_mut()
+}
+
+/// Free the `work` and settle the promise of it with `output`
+unsafe fn finish<T, F>(
+  env: sys::napi_env,
+  work: *mut ChunkedWork<T, F>,
+  output: Result<sys::napi_value>,
+) {
+  let work = unsafe { Box::from_raw(work) };
+  let status = unsafe { sys::napi_delete_reference(env, work.callback_ref) };
+  debug_assert!(status == sys::Status::napi_ok, "Delete reference failed");
+  match output {
+    Ok(v) => {
+      let status = unsafe { sys::napi_resolve_deferred(env, work.deferred, v) };
+      debug_assert!(status == sys::Status::napi_ok, "Resolve promise failed");
+    }
+    Err(e) => {
+      let status =
+        unsafe { sys::napi_reject_deferred(env, work.deferred, JsError::from(e).into_value(env)) };
+      debug_assert!(status == sys::Status::napi_ok, "Reject promise failed");
+    }
+  }
+}
@@ -7,7 +7,9 @@ use std::ptr;
 
 use crate::{
   async_work::{self, AsyncWorkPromise},
+  bindgen_runtime::{is_external_buffer_disallowed, ToNapiValue},
   check_status,
+  chunked::{self, Chunk},
   js_values::*,
   sys,
   task::Task,
@@ -18,6 +20,7 @@ use crate::{
 use crate::async_cleanup_hook::AsyncCleanupHook;
 #[cfg(feature = "napi3")]
 use crate::cleanup_env::{CleanupEnvHook, CleanupEnvHookData};
+use crate::js_values::raw_scope::HandleScopeGuard;
 #[cfg(all(feature = "serde-json"))]
 use crate::js_values::{De, Ser};
 #[cfg(feature = "napi4")]
@@ -246,6 +249,7 @@ impl Env {
         env: self.0,
         value: raw_value,
         value_type: ValueType::Object,
+        scope: HandleScopeId::current(),
       }),
       mem::ManuallyDrop::new(unsafe { Vec::from_raw_parts(data_ptr as *mut _, length, length) }),
     ))
@@ -255,6 +259,9 @@ impl Env {
   ///
   /// While this is still a fully-supported data structure, in most cases using a TypedArray will suffice.
   pub fn create_buffer_with_data(&self, mut data: Vec<u8>) -> Result<JsBufferValue> {
+    if is_external_buffer_disallowed() {
+      return self.create_buffer_copy(data);
+    }
     let length = data.len();
     let mut raw_value = ptr::null_mut();
     let data_ptr = data.as_mut_ptr();
@@ -273,6 +280,7 @@ impl Env {
         env: self.0,
         value: raw_value,
         value_type: ValueType::Object,
+        scope: HandleScopeId::current(),
       }),
       mem::ManuallyDrop::new(data),
     ))
@@ -294,6 +302,11 @@ impl Env {
   where
     Finalize: FnOnce(Hint, Env),
   {
+    if is_external_buffer_disallowed() {
+      let buffer = self.create_buffer_copy(unsafe { std::slice::from_raw_parts(data, length) })?;
+      finalize_callback(hint, *self);
+      return Ok(buffer);
+    }
     let mut raw_value = ptr::null_mut();
     check_status!(unsafe {
       sys::napi_create_external_buffer(
@@ -317,6 +330,7 @@ impl Env {
         env: self.0,
         value: raw_value,
         value_type: ValueType::Object,
+        scope: HandleScopeId::current(),
       }),
       mem::ManuallyDrop::new(unsafe { Vec::from_raw_parts(data as *mut u8, length, length) }),
     ))
@@ -358,6 +372,7 @@ impl Env {
         env: self.0,
         value: raw_value,
         value_type: ValueType::Object,
+        scope: HandleScopeId::current(),
       }),
       mem::ManuallyDrop::new(unsafe { Vec::from_raw_parts(copy_data as *mut u8, length, length) }),
     ))
@@ -377,7 +392,16 @@ impl Env {
     ))
   }
 
+  fn create_arraybuffer_copy(&self, data_to_copy: &[u8]) -> Result<JsArrayBufferValue> {
+    let mut arraybuffer = self.create_arraybuffer(data_to_copy.len())?;
+    arraybuffer.copy_from_slice(data_to_copy);
+    Ok(arraybuffer)
+  }
+
   pub fn create_arraybuffer_with_data(&self, data: Vec<u8>) -> Result<JsArrayBufferValue> {
+    if is_external_buffer_disallowed() {
+      return self.create_arraybuffer_copy(&data);
+    }
     let length = data.len();
     let mut raw_value = ptr::null_mut();
     let data_ptr = data.as_ptr();
@@ -398,6 +422,7 @@ impl Env {
         env: self.0,
         value: raw_value,
         value_type: ValueType::Object,
+        scope: HandleScopeId::current(),
       }),
       data_ptr as *mut c_void,
       length,
@@ -420,6 +445,12 @@ impl Env {
   where
     Finalize: FnOnce(Hint, Env),
   {
+    if is_external_buffer_disallowed() {
+      let arraybuffer =
+        self.create_arraybuffer_copy(unsafe { std::slice::from_raw_parts(data, length) })?;
+      finalize_callback(hint, *self);
+      return Ok(arraybuffer);
+    }
     let mut raw_value = ptr::null_mut();
     check_status!(unsafe {
       sys::napi_create_external_arraybuffer(
@@ -443,6 +474,7 @@ impl Env {
         env: self.0,
         value: raw_value,
         value_type: ValueType::Object,
+        scope: HandleScopeId::current(),
       }),
       data as *mut c_void,
       length,
@@ -954,8 +986,7 @@ impl Env {
   }
 
   pub fn create_error(&self, e: Error) -> Result<JsObject> {
-    let reason = e.reason;
-    let reason_string = self.create_string(reason.as_str())?;
+    let reason_string = self.create_string(&e.message())?;
     let mut result = ptr::null_mut();
     check_status!(unsafe {
       sys::napi_create_error(self.0, ptr::null_mut(), reason_string.0.value, &mut result)
@@ -968,6 +999,18 @@ impl Env {
     async_work::run(self.0, task, None)
   }
 
+  /// Run a long computation which must stay on the JS thread in time sliced chunks, return a `Promise`.
+  ///
+  /// `step` is called repeatedly until it returns `Chunk::Done`. Once a chunk has taken longer than `budget`, the next one is rescheduled with `setImmediate`, so the event loop keeps processing I/O and timers in between.
+  pub fn run_chunked<T, F>(&self, budget: std::time::Duration, step: F) -> Result<JsObject>
+  where
+    T: ToNapiValue,
+    F: 'static + FnMut(Env) -> Result<Chunk<T>>,
+  {
+    let promise = chunked::run(self.0, budget, step)?;
+    Ok(unsafe { JsObject::from_raw_unchecked(self.0, promise) })
+  }
+
   pub fn run_in_scope<T, F>(&self, executor: F) -> Result<T>
   where
     F: FnOnce() -> Result<T>,
@@ -975,7 +1018,9 @@ impl Env {
     let mut handle_scope = ptr::null_mut();
     check_status!(unsafe { sys::napi_open_handle_scope(self.0, &mut handle_scope) })?;
 
+    let scope = HandleScopeGuard::open();
     let result = executor();
+    drop(scope);
 
     check_status!(unsafe { sys::napi_close_handle_scope(self.0, handle_scope) })?;
     result
@@ -1251,6 +1296,7 @@ impl Env {
       env: self.0,
       value: unsafe { value.raw() },
       value_type: ValueType::Unknown,
+      scope: HandleScopeId::current(),
     };
     let mut de = De(&value);
     T::deserialize(&mut de)
@@ -1274,6 +1320,17 @@ impl Env {
   pub fn raw(&self) -> sys::napi_env {
     self.0
   }
+
+  /// Call `f` with the raw env, for the Node-API functions not wrapped by napi-rs.
+  ///
+  /// In the debug builds, it fails if called on the thread other than the JavaScript thread of the env.
+  pub fn with_raw<R, F>(&self, f: F) -> Result<R>
+  where
+    F: FnOnce(sys::napi_env) -> Result<R>,
+  {
+    crate::js_values::raw_scope::validate_env(self.0)?;
+    f(self.0)
+  }
 }
 
 /// This function could be used for `create_buffer_with_borrowed_data` and want do noting when Buffer finalized.
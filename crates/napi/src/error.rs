@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::convert::{From, TryFrom};
 use std::error;
 use std::ffi::{CStr, CString};
@@ -6,6 +7,7 @@ use std::fmt;
 use std::fmt::Display;
 use std::os::raw::{c_char, c_void};
 use std::ptr;
+use std::sync::Arc;
 
 #[cfg(feature = "serde-json")]
 use serde::{de, ser};
@@ -27,6 +29,33 @@ pub struct Error {
   // Only be used in `async fn(p: Promise<T>)` scenario
   #[cfg(all(feature = "tokio_rt", feature = "napi4"))]
   pub(crate) maybe_raw: sys::napi_ref,
+  // Thrown as the instance of the JavaScript class of it, see `Error::from_class`
+  pub(crate) class: Option<Arc<dyn ErrorClass>>,
+  // The path of the nested value failed to convert, see `Error::path`
+  pub(crate) path: Option<String>,
+}
+
+/// The Rust errors thrown as the instances of their own JavaScript classes,
+/// implemented by `#[napi(error)]` for the enums.
+///
+/// The class `class_name` extends the class `base_class_name`, which extends `Error`.
+/// Both of them must be exported from the module, they are looked up by the names while throwing.
+pub trait ErrorClass: fmt::Debug + fmt::Display + Send + Sync + 'static {
+  fn class_name(&self) -> &'static str;
+
+  fn base_class_name(&self) -> &'static str;
+
+  /// `error.code` of the thrown error
+  fn code(&self) -> &'static str;
+
+  /// Set the extra properties of the thrown `error`, after the `message` and the `code`
+  ///
+  /// # Safety
+  ///
+  /// `env` must be the env of the current JavaScript thread
+  unsafe fn set_properties(&self, _env: sys::napi_env, _error: sys::napi_value) -> Result<()> {
+    Ok(())
+  }
 }
 
 unsafe impl Send for Error {}
@@ -62,14 +91,16 @@ impl From<sys::napi_ref> for Error {
       status: Status::InvalidArg,
       reason: "".to_string(),
       maybe_raw: value,
+      class: None,
+      path: None,
     }
   }
 }
 
 impl fmt::Display for Error {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-    if !self.reason.is_empty() {
-      write!(f, "{:?}, {}", self.status, self.reason)
+    if !self.reason.is_empty() || self.path.is_some() {
+      write!(f, "{:?}, {}", self.status, self.message())
     } else {
       write!(f, "{:?}", self.status)
     }
@@ -83,6 +114,8 @@ impl Error {
       reason,
       #[cfg(all(feature = "tokio_rt", feature = "napi4"))]
       maybe_raw: ptr::null_mut(),
+      class: None,
+      path: None,
     }
   }
 
@@ -92,6 +125,8 @@ impl Error {
       reason: "".to_owned(),
       #[cfg(all(feature = "tokio_rt", feature = "napi4"))]
       maybe_raw: ptr::null_mut(),
+      class: None,
+      path: None,
     }
   }
 
@@ -101,6 +136,34 @@ impl Error {
       reason,
       #[cfg(all(feature = "tokio_rt", feature = "napi4"))]
       maybe_raw: ptr::null_mut(),
+      class: None,
+      path: None,
+    }
+  }
+
+  /// The path of the nested value failed to convert, e.g. `options.points[1].x`,
+  /// prefixed to the message of the thrown JavaScript error.
+  pub fn path(&self) -> Option<&str> {
+    self.path.as_deref().filter(|path| !path.is_empty())
+  }
+
+  /// The message of the thrown JavaScript error, `` `options.points[1].x`: reason`` if the path is tracked
+  pub(crate) fn message(&self) -> Cow<'_, str> {
+    match self.path() {
+      Some(path) => Cow::Owned(format!("`{}`: {}", path, self.reason)),
+      None => Cow::Borrowed(&self.reason),
+    }
+  }
+
+  /// Thrown as the instance of the JavaScript class of `error`, with `error.to_string()` as the message.
+  pub fn from_class<E: ErrorClass>(error: E) -> Self {
+    Error {
+      status: Status::GenericFailure,
+      reason: error.to_string(),
+      #[cfg(all(feature = "tokio_rt", feature = "napi4"))]
+      maybe_raw: ptr::null_mut(),
+      class: Some(Arc::new(error)),
+      path: None,
     }
   }
 }
@@ -112,6 +175,8 @@ impl From<std::ffi::NulError> for Error {
       reason: format!("{}", error),
       #[cfg(all(feature = "tokio_rt", feature = "napi4"))]
       maybe_raw: ptr::null_mut(),
+      class: None,
+      path: None,
     }
   }
 }
@@ -123,6 +188,8 @@ impl From<std::io::Error> for Error {
       reason: format!("{}", error),
       #[cfg(all(feature = "tokio_rt", feature = "napi4"))]
       maybe_raw: ptr::null_mut(),
+      class: None,
+      path: None,
     }
   }
 }
@@ -171,8 +238,8 @@ macro_rules! impl_object_methods {
         let error_status = format!("{:?}", self.0.status);
         let status_len = error_status.len();
         let error_code_string = CString::new(error_status).unwrap();
-        let reason_len = self.0.reason.len();
-        let reason = CString::new(self.0.reason).unwrap();
+        let reason = CString::new(self.0.message().into_owned()).unwrap();
+        let reason_len = reason.as_bytes().len();
         let mut error_code = ptr::null_mut();
         let mut reason_string = ptr::null_mut();
         let mut js_error = ptr::null_mut();
@@ -184,6 +251,13 @@ macro_rules! impl_object_methods {
           sys::napi_create_string_utf8(env, reason.as_ptr(), reason_len, &mut reason_string)
         };
         debug_assert!(create_reason_status == sys::Status::napi_ok);
+        if let Some(class) = self.0.class.as_deref() {
+          if let Ok(js_error) =
+            unsafe { crate::bindgen_runtime::create_class_error(env, class, reason_string) }
+          {
+            return js_error;
+          }
+        }
         let create_error_status = unsafe { $kind(env, error_code, reason_string, &mut js_error) };
         debug_assert!(create_error_status == sys::Status::napi_ok);
         js_error
@@ -194,7 +268,7 @@ macro_rules! impl_object_methods {
       /// This function is safety if env is not null ptr.
       pub unsafe fn throw_into(self, env: sys::napi_env) {
         #[cfg(debug_assertions)]
-        let reason = self.0.reason.clone();
+        let reason = self.0.message().into_owned();
         let status = self.0.status;
         if status == Status::PendingException {
           return;
@@ -219,8 +293,8 @@ macro_rules! impl_object_methods {
         let status_len = error_status.len();
         let error_code_string =
           unsafe { CStr::from_bytes_with_nul_unchecked(error_status.as_bytes()) };
-        let reason_len = self.0.reason.len();
-        let reason_c_string = format!("{}\0", self.0.reason.clone());
+        let reason_c_string = format!("{}\0", self.0.message());
+        let reason_len = reason_c_string.len() - 1;
         let reason = unsafe { CStr::from_bytes_with_nul_unchecked(reason_c_string.as_bytes()) };
         let mut error_code = ptr::null_mut();
         let mut reason_string = ptr::null_mut();
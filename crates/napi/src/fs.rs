@@ -0,0 +1,261 @@
+//! Promise based file helpers running in the tokio runtime.
+//!
+//! The files are read and written in chunks, so the `AbortSignal` is checked and the progress is reported between them.
+//!
+//! ```ignore
+//! use napi::bindgen_prelude::*;
+//!
+//! #[napi(ts_args_type = "path: string, signal?: AbortSignal, onProgress?: (progress: FsProgress) => void")]
+//! pub fn read_asset(
+//!   path: String,
+//!   signal: Option<AbortSignal>,
+//!   on_progress: Option<JsFunction>,
+//! ) -> napi::fs::FsTask<Buffer> {
+//!   napi::fs::read_file(path)
+//!     .with_signal(signal)
+//!     .on_progress(on_progress)
+//! }
+//! ```
+
+use std::future::Future;
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use tokio::fs::{self, File};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+use crate::bindgen_runtime::{AbortHandle, AbortSignal, Buffer, ToNapiValue, TypeName};
+use crate::threadsafe_function::{
+  ErrorStrategy, ThreadSafeCallContext, ThreadsafeFunction, ThreadsafeFunctionCallMode,
+};
+use crate::{
+  sys, tokio_runtime::execute_tokio_future, Error, JsFunction, Result, Status, ValueType,
+};
+
+const CHUNK_SIZE: usize = 64 * 1024;
+
+static TEMP_FILE_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Passed to the `onProgress` callback as `{ transferred, total }`, in bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FsProgress {
+  pub transferred: u64,
+  pub total: u64,
+}
+
+/// Checks the `AbortSignal` and reports the progress inside the operation of [`FsTask`]
+pub struct FsContext {
+  signal: Option<AbortHandle>,
+  on_progress: Option<ThreadsafeFunction<FsProgress, ErrorStrategy::Fatal>>,
+}
+
+impl FsContext {
+  /// `Err` of `AbortError` if the `AbortSignal` has been aborted
+  pub fn check_aborted(&self) -> Result<()> {
+    match &self.signal {
+      Some(signal) => signal.check(),
+      None => Ok(()),
+    }
+  }
+
+  #[cfg(feature = "net")]
+  pub(crate) fn abort_handle(&self) -> Option<AbortHandle> {
+    self.signal.clone()
+  }
+
+  pub fn report(&self, progress: FsProgress) {
+    if let Some(on_progress) = &self.on_progress {
+      on_progress.call(progress, ThreadsafeFunctionCallMode::NonBlocking);
+    }
+  }
+}
+
+type Operation<T> =
+  Box<dyn FnOnce(FsContext) -> Pin<Box<dyn Future<Output = Result<T>> + Send>> + Send>;
+
+/// `Promise` of a file operation, resolved with `T`
+pub struct FsTask<T> {
+  operation: Operation<T>,
+  signal: Option<AbortHandle>,
+  on_progress: Option<JsFunction>,
+  _output: PhantomData<T>,
+}
+
+impl<T: 'static + Send + ToNapiValue> FsTask<T> {
+  pub fn new<F, Fut>(operation: F) -> Self
+  where
+    F: 'static + Send + FnOnce(FsContext) -> Fut,
+    Fut: 'static + Send + Future<Output = Result<T>>,
+  {
+    Self {
+      operation: Box::new(move |ctx| Box::pin(operation(ctx))),
+      signal: None,
+      on_progress: None,
+      _output: PhantomData,
+    }
+  }
+
+  /// Reject with `AbortError` once the `AbortSignal` is aborted
+  pub fn with_signal(mut self, signal: Option<AbortSignal>) -> Self {
+    self.signal = signal.map(|signal| signal.handle());
+    self
+  }
+
+  /// Call `callback` with the [`FsProgress`] after every chunk
+  pub fn on_progress(mut self, callback: Option<JsFunction>) -> Self {
+    self.on_progress = callback;
+    self
+  }
+
+  /// Run the operation inside of the other `FsTask`, with the `AbortSignal` and the progress callback of it.
+  /// The signal and the callback of this task are ignored.
+  pub fn run(self, ctx: FsContext) -> impl Future<Output = Result<T>> + Send {
+    let operation = self.operation;
+    async move {
+      ctx.check_aborted()?;
+      operation(ctx).await
+    }
+  }
+}
+
+impl<T> TypeName for FsTask<T> {
+  fn type_name() -> &'static str {
+    "FsTask"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl<T: 'static + Send + ToNapiValue> ToNapiValue for FsTask<T> {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    let on_progress = val
+      .on_progress
+      .map(|callback| {
+        callback.create_threadsafe_function(0, |ctx: ThreadSafeCallContext<FsProgress>| {
+          let mut progress = ctx.env.create_object()?;
+          progress.set_named_property(
+            "transferred",
+            ctx.env.create_double(ctx.value.transferred as f64)?,
+          )?;
+          progress.set_named_property("total", ctx.env.create_double(ctx.value.total as f64)?)?;
+          Ok(vec![progress])
+        })
+      })
+      .transpose()?;
+    let ctx = FsContext {
+      signal: val.signal,
+      on_progress,
+    };
+    let operation = val.operation;
+    execute_tokio_future(
+      env,
+      async move {
+        ctx.check_aborted()?;
+        operation(ctx).await
+      },
+      |env, output| unsafe { T::to_napi_value(env, output) },
+    )
+  }
+}
+
+fn fs_error(err: std::io::Error, action: &str, path: &Path) -> Error {
+  Error::new(
+    Status::GenericFailure,
+    format!("Failed to {} [{}]: {}", action, path.display(), err),
+  )
+}
+
+/// Read the whole file into a `Buffer`
+pub fn read_file<P: Into<PathBuf>>(path: P) -> FsTask<Buffer> {
+  let path = path.into();
+  FsTask::new(move |ctx| async move {
+    let mut file = File::open(&path)
+      .await
+      .map_err(|e| fs_error(e, "open", &path))?;
+    let total = file
+      .metadata()
+      .await
+      .map_err(|e| fs_error(e, "read", &path))?
+      .len();
+    let mut data = Vec::with_capacity(total as usize);
+    let mut chunk = vec![0; CHUNK_SIZE];
+    loop {
+      ctx.check_aborted()?;
+      let n = file
+        .read(&mut chunk)
+        .await
+        .map_err(|e| fs_error(e, "read", &path))?;
+      if n == 0 {
+        break;
+      }
+      data.extend_from_slice(&chunk[..n]);
+      ctx.report(FsProgress {
+        transferred: data.len() as u64,
+        // the file may grow while reading
+        total: total.max(data.len() as u64),
+      });
+    }
+    Ok(data.into())
+  })
+}
+
+/// Write `data` into a temporary file next to `path`, and rename it to `path` after all of the chunks are written.
+///
+/// So `path` is either untouched or completely replaced, even if it's aborted or failed in the middle.
+pub fn write_file<P: Into<PathBuf>, D: Into<Vec<u8>>>(path: P, data: D) -> FsTask<()> {
+  let path = path.into();
+  let data = data.into();
+  FsTask::new(move |ctx| async move {
+    let file_name = path.file_name().ok_or_else(|| {
+      Error::new(
+        Status::InvalidArg,
+        format!("[{}] is not a file path", path.display()),
+      )
+    })?;
+    // the concurrent writes of the same path never share the temporary file
+    let mut temp_name = std::ffi::OsString::from(".");
+    temp_name.push(file_name);
+    temp_name.push(format!(
+      ".{}.{}.tmp",
+      std::process::id(),
+      TEMP_FILE_ID.fetch_add(1, Ordering::Relaxed)
+    ));
+    let temp_path = path.with_file_name(temp_name);
+    let written = write_chunks(&ctx, &temp_path, &data).await;
+    let renamed = match written {
+      Ok(()) => fs::rename(&temp_path, &path)
+        .await
+        .map_err(|e| fs_error(e, "write", &path)),
+      Err(e) => Err(e),
+    };
+    if renamed.is_err() {
+      let _ = fs::remove_file(&temp_path).await;
+    }
+    renamed
+  })
+}
+
+async fn write_chunks(ctx: &FsContext, path: &Path, data: &[u8]) -> Result<()> {
+  let mut file = File::create(path)
+    .await
+    .map_err(|e| fs_error(e, "create", path))?;
+  let total = data.len() as u64;
+  let mut transferred = 0;
+  for chunk in data.chunks(CHUNK_SIZE) {
+    ctx.check_aborted()?;
+    file
+      .write_all(chunk)
+      .await
+      .map_err(|e| fs_error(e, "write", path))?;
+    transferred += chunk.len() as u64;
+    ctx.report(FsProgress { transferred, total });
+  }
+  file
+    .sync_all()
+    .await
+    .map_err(|e| fs_error(e, "write", path))
+}
@@ -4,7 +4,9 @@ use std::ptr;
 use std::slice;
 
 use crate::bindgen_runtime::TypeName;
-use crate::{check_status, sys, JsUnknown, NapiValue, Ref, Result, Value, ValueType};
+use crate::{
+  check_status, sys, HandleScopeId, JsUnknown, NapiValue, Ref, Result, Value, ValueType,
+};
 
 pub struct JsArrayBuffer(pub(crate) Value);
 
@@ -161,6 +163,7 @@ impl JsArrayBuffer {
       env: self.0.env,
       value: typedarray_value,
       value_type: ValueType::Object,
+      scope: HandleScopeId::current(),
     }))
   }
 
@@ -179,6 +182,7 @@ impl JsArrayBuffer {
       env: self.0.env,
       value: dataview_value,
       value_type: ValueType::Object,
+      scope: HandleScopeId::current(),
     }))
   }
 
@@ -31,6 +31,7 @@ impl JsBigInt {
         env,
         value,
         value_type: ValueType::Object,
+        scope: HandleScopeId::current(),
       },
       word_count,
     }
@@ -49,6 +50,7 @@ impl JsBigInt {
       env: self.raw.env,
       value: new_raw_value,
       value_type: ValueType::Number,
+      scope: HandleScopeId::current(),
     }))
   }
 
@@ -61,6 +63,7 @@ impl JsBigInt {
       env: self.raw.env,
       value: new_raw_value,
       value_type: ValueType::String,
+      scope: HandleScopeId::current(),
     }))
   }
 
@@ -73,6 +76,7 @@ impl JsBigInt {
       env: self.raw.env,
       value: new_raw_value,
       value_type: ValueType::Object,
+      scope: HandleScopeId::current(),
     }))
   }
 
@@ -150,6 +154,7 @@ impl NapiValue for JsBigInt {
         env,
         value,
         value_type: ValueType::BigInt,
+        scope: HandleScopeId::current(),
       },
       word_count,
     })
@@ -175,6 +180,7 @@ impl NapiValue for JsBigInt {
         env,
         value,
         value_type: ValueType::BigInt,
+        scope: HandleScopeId::current(),
       },
       word_count,
     }
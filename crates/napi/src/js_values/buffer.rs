@@ -53,6 +53,7 @@ impl JsBufferValue {
         env,
         value,
         value_type: ValueType::Object,
+        scope: super::HandleScopeId::current(),
       }),
       data: mem::ManuallyDrop::new(unsafe { Vec::from_raw_parts(data as *mut _, len, len) }),
     })
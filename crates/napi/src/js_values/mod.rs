@@ -26,6 +26,7 @@ mod global;
 mod number;
 mod object;
 mod object_property;
+pub(crate) mod raw_scope;
 mod string;
 mod tagged_object;
 mod undefined;
@@ -48,6 +49,7 @@ pub use global::*;
 pub use number::JsNumber;
 pub use object::*;
 pub use object_property::*;
+pub(crate) use raw_scope::HandleScopeId;
 #[cfg(feature = "serde-json")]
 pub(crate) use ser::Ser;
 pub use string::*;
@@ -119,6 +121,7 @@ macro_rules! impl_napi_value_trait {
             env,
             value,
             value_type: $value_type,
+            scope: HandleScopeId::current(),
           }))
         }
       }
@@ -128,6 +131,7 @@ macro_rules! impl_napi_value_trait {
           env,
           value,
           value_type: $value_type,
+          scope: HandleScopeId::current(),
         })
       }
     }
@@ -169,6 +173,7 @@ macro_rules! impl_js_value_methods {
           env: self.0.env,
           value: new_raw_value,
           value_type: ValueType::Boolean,
+          scope: HandleScopeId::current(),
         }))
       }
 
@@ -181,6 +186,7 @@ macro_rules! impl_js_value_methods {
           env: self.0.env,
           value: new_raw_value,
           value_type: ValueType::Number,
+          scope: HandleScopeId::current(),
         }))
       }
 
@@ -193,6 +199,7 @@ macro_rules! impl_js_value_methods {
           env: self.0.env,
           value: new_raw_value,
           value_type: ValueType::String,
+          scope: HandleScopeId::current(),
         }))
       }
 
@@ -205,6 +212,7 @@ macro_rules! impl_js_value_methods {
           env: self.0.env,
           value: new_raw_value,
           value_type: ValueType::Object,
+          scope: HandleScopeId::current(),
         }))
       }
 
@@ -215,6 +223,18 @@ macro_rules! impl_js_value_methods {
         Ok(is_date)
       }
 
+      /// Call `f` with the raw env and handle, for the Node-API functions not wrapped by napi-rs.
+      ///
+      /// In the debug builds, it fails if called on the thread other than the JavaScript thread of the env,
+      /// or the handle is not alive anymore. The raw handle must not escape from `f`.
+      pub fn with_raw<R, F>(&self, f: F) -> Result<R>
+      where
+        F: FnOnce(sys::napi_env, sys::napi_value) -> Result<R>,
+      {
+        raw_scope::validate_value(self.0.env, self.0.value_type, self.0.scope)?;
+        f(self.0.env, self.0.value)
+      }
+
       pub fn is_promise(&self) -> Result<bool> {
         let mut is_promise = true;
         check_status!(unsafe { sys::napi_is_promise(self.0.env, self.0.value, &mut is_promise) })?;
@@ -648,6 +668,7 @@ impl NapiValue for JsUnknown {
       env,
       value,
       value_type: Unknown,
+      scope: HandleScopeId::current(),
     }))
   }
 
@@ -656,6 +677,7 @@ impl NapiValue for JsUnknown {
       env,
       value,
       value_type: Unknown,
+      scope: HandleScopeId::current(),
     })
   }
 }
@@ -10,7 +10,7 @@ pub struct Property {
   getter: sys::napi_callback,
   setter: sys::napi_callback,
   method: sys::napi_callback,
-  attrs: PropertyAttributes,
+  attrs: sys::napi_property_attributes,
   value: sys::napi_value,
   pub(crate) is_ctor: bool,
 }
@@ -22,7 +22,7 @@ impl Default for Property {
       getter: Default::default(),
       setter: Default::default(),
       method: Default::default(),
-      attrs: Default::default(),
+      attrs: PropertyAttributes::Default.into(),
       value: ptr::null_mut(),
       is_ctor: Default::default(),
     }
@@ -86,7 +86,30 @@ impl Property {
   }
 
   pub fn with_property_attributes(mut self, attributes: PropertyAttributes) -> Self {
-    self.attrs = attributes;
+    self.attrs = attributes.into();
+    self
+  }
+
+  /// Ignored by the accessors, they are writable if they have the setter
+  pub fn with_writable(self, writable: bool) -> Self {
+    self.with_attribute(PropertyAttributes::Writable, writable)
+  }
+
+  pub fn with_enumerable(self, enumerable: bool) -> Self {
+    self.with_attribute(PropertyAttributes::Enumerable, enumerable)
+  }
+
+  pub fn with_configurable(self, configurable: bool) -> Self {
+    self.with_attribute(PropertyAttributes::Configurable, configurable)
+  }
+
+  fn with_attribute(mut self, attribute: PropertyAttributes, enabled: bool) -> Self {
+    let attribute = sys::napi_property_attributes::from(attribute);
+    if enabled {
+      self.attrs |= attribute;
+    } else {
+      self.attrs &= !attribute;
+    }
     self
   }
 
@@ -103,7 +126,7 @@ impl Property {
       getter: self.getter,
       setter: self.setter,
       value: self.value,
-      attributes: self.attrs.into(),
+      attributes: self.attrs,
       data: ptr::null_mut(),
     }
   }
@@ -113,4 +136,8 @@ impl Property {
     self.is_ctor = true;
     self
   }
+
+  pub(crate) fn is_static(&self) -> bool {
+    self.attrs & sys::napi_property_attributes::from(PropertyAttributes::Static) != 0
+  }
 }
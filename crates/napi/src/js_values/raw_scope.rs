@@ -0,0 +1,181 @@
+//! Validation of the raw handles passed to the closures of `with_raw`, only in the debug builds.
+//!
+//! The `napi_env` is only usable on the thread the module is registered on, and the `napi_value` is only alive in the handle scope it's created in.
+//! Neither of them is checked by Node-API, using them elsewhere crashes the process or reads the garbage.
+//! The env is not usable after it's torn down either, e.g. kept by a static after the worker exits, or restored from a startup snapshot.
+//!
+//! The dead handles can't be passed to Node-API even to check them, so every value records the generation of the innermost handle scope
+//! it's created in, and it's alive as long as the scope of the generation is still open on the thread.
+//! The scopes of the `#[napi]` and `#[js_function]` callbacks, and the ones opened by napi-rs are tracked,
+//! the values created outside of them, e.g. in the callbacks of the finalizers, are not checked.
+
+#[cfg(debug_assertions)]
+use std::cell::{Cell, RefCell};
+#[cfg(debug_assertions)]
+use std::collections::HashSet;
+#[cfg(all(debug_assertions, feature = "napi3"))]
+use std::ffi::c_void;
+#[cfg(debug_assertions)]
+use std::sync::Mutex;
+
+use crate::{sys, Result, ValueType};
+
+#[cfg(debug_assertions)]
+lazy_static::lazy_static! {
+  // the addresses of the envs torn down, until they are reused by the next envs
+  static ref TORN_DOWN_ENVS: Mutex<HashSet<usize>> = Default::default();
+}
+
+#[cfg(debug_assertions)]
+thread_local! {
+  // the addresses of the envs registered the module on the current thread, dropped once the thread exits
+  static ENVS: RefCell<HashSet<usize>> = Default::default();
+  // the generations of the handle scopes open on the current thread, the innermost is the last one
+  static HANDLE_SCOPES: RefCell<Vec<u64>> = Default::default();
+  static LAST_GENERATION: Cell<u64> = Default::default();
+}
+
+/// The generation of the handle scope a value is created in, `0` if the scope is not tracked
+#[derive(Debug, Clone, Copy)]
+pub struct HandleScopeId(#[cfg(debug_assertions)] u64);
+
+impl HandleScopeId {
+  pub(crate) fn current() -> Self {
+    #[cfg(debug_assertions)]
+    {
+      let generation = HANDLE_SCOPES
+        .try_with(|scopes| scopes.borrow().last().copied())
+        .ok()
+        .flatten()
+        .unwrap_or(0);
+      HandleScopeId(generation)
+    }
+    #[cfg(not(debug_assertions))]
+    HandleScopeId()
+  }
+}
+
+/// Tracks the handle scope from it's created until it's dropped, it must be dropped once the scope is closed
+pub(crate) struct HandleScopeGuard(#[cfg(debug_assertions)] u64);
+
+impl HandleScopeGuard {
+  pub(crate) fn open() -> Self {
+    #[cfg(debug_assertions)]
+    {
+      let generation = LAST_GENERATION.with(|last| {
+        last.set(last.get() + 1);
+        last.get()
+      });
+      let _ = HANDLE_SCOPES.try_with(|scopes| scopes.borrow_mut().push(generation));
+      HandleScopeGuard(generation)
+    }
+    #[cfg(not(debug_assertions))]
+    HandleScopeGuard()
+  }
+}
+
+#[cfg(debug_assertions)]
+impl Drop for HandleScopeGuard {
+  fn drop(&mut self) {
+    let generation = self.0;
+    let _ = HANDLE_SCOPES.try_with(|scopes| {
+      let mut scopes = scopes.borrow_mut();
+      // the scopes are closed in the reverse order, unless a guard is leaked
+      if let Some(index) = scopes.iter().rposition(|g| *g == generation) {
+        scopes.truncate(index);
+      }
+    });
+  }
+}
+
+/// Called while registering the module, the env of the terminated worker could be reused by the next one
+pub(crate) fn register_env_thread(env: sys::napi_env) {
+  #[cfg(debug_assertions)]
+  {
+    if let Ok(mut envs) = TORN_DOWN_ENVS.lock() {
+      envs.remove(&(env as usize));
+    }
+    let registered = ENVS
+      .try_with(|envs| !envs.borrow_mut().insert(env as usize))
+      .unwrap_or(true);
+    // the cleanup hook can't be added twice, the module is registered on the env again if it's required from another path
+    #[cfg(feature = "napi3")]
+    if !registered {
+      unsafe { sys::napi_add_env_cleanup_hook(env, Some(tear_down_env), env as *mut c_void) };
+    }
+    #[cfg(not(feature = "napi3"))]
+    let _ = registered;
+  }
+  #[cfg(not(debug_assertions))]
+  let _ = env;
+}
+
+#[cfg(all(debug_assertions, feature = "napi3"))]
+unsafe extern "C" fn tear_down_env(env: *mut c_void) {
+  // called on the thread of the env, the thread local could be destroyed already if the thread is exiting
+  let _ = ENVS.try_with(|envs| envs.borrow_mut().remove(&(env as usize)));
+  if let Ok(mut envs) = TORN_DOWN_ENVS.lock() {
+    envs.insert(env as usize);
+  }
+}
+
+/// `Err` if the env is used on the other thread or torn down, no-op in the release builds
+pub(crate) fn validate_env(env: sys::napi_env) -> Result<()> {
+  #[cfg(debug_assertions)]
+  {
+    if let Ok(envs) = TORN_DOWN_ENVS.lock() {
+      if envs.contains(&(env as usize)) {
+        return Err(crate::Error::new(
+          crate::Status::GenericFailure,
+          "The env is torn down, it or the values of it are kept after the module is registered on it. Keep the state of the env by `Env::set_instance_data` or create it in `#[napi(module_init)]`, which is called for every env instead of once".to_owned(),
+        ));
+      }
+    }
+    let registered = ENVS
+      .try_with(|envs| envs.borrow().contains(&(env as usize)))
+      .unwrap_or(false);
+    if !registered {
+      let current = std::thread::current();
+      return Err(crate::Error::new(
+        crate::Status::GenericFailure,
+        format!(
+          "The env is used on the thread {:?} [{}], but it belongs to another JavaScript thread, pass the values to the JavaScript thread by ThreadsafeFunction",
+          current.id(),
+          current.name().unwrap_or("unnamed"),
+        ),
+      ));
+    }
+  }
+  #[cfg(not(debug_assertions))]
+  let _ = env;
+  Ok(())
+}
+
+/// `Err` if the env is used on the other thread, or the handle scope of the value is closed, no-op in the release builds.
+pub(crate) fn validate_value(
+  env: sys::napi_env,
+  value_type: ValueType,
+  scope: HandleScopeId,
+) -> Result<()> {
+  validate_env(env)?;
+  #[cfg(debug_assertions)]
+  {
+    let generation = scope.0;
+    let alive = generation == 0
+      || HANDLE_SCOPES
+        .try_with(|scopes| scopes.borrow().contains(&generation))
+        .unwrap_or(false);
+    if !alive {
+      return Err(crate::Error::new(
+        crate::Status::InvalidArg,
+        format!(
+          "The handle of {} is not alive anymore, it's only valid in the handle scope it's created in, keep it by Ref across the scopes",
+          value_type
+        ),
+      ));
+    }
+  }
+  #[cfg(not(debug_assertions))]
+  let _ = (value_type, scope);
+  Ok(())
+}
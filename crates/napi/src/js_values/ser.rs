@@ -197,6 +197,7 @@ impl<'env> Serializer for Ser<'env> {
         value: array.0.value,
         env: array.0.env,
         value_type: ValueType::Object,
+        scope: HandleScopeId::current(),
       }),
     )?;
     Ok(SeqSerializer {
@@ -279,6 +280,7 @@ impl<'env> Serializer for Ser<'env> {
         env: inner.0.env,
         value: inner.0.value,
         value_type: ValueType::Object,
+        scope: HandleScopeId::current(),
       }),
     )?;
     Ok(StructSerializer {
@@ -412,6 +414,7 @@ impl ser::SerializeMap for MapSerializer {
         env: self.key.0.env,
         value: self.key.0.value,
         value_type: ValueType::String,
+        scope: HandleScopeId::current(),
       }),
       JsUnknown(value.serialize(Ser::new(&env))?),
     )?;
@@ -1,10 +1,12 @@
 use crate::sys;
 
-use super::ValueType;
+use super::{HandleScopeId, ValueType};
 
 #[derive(Clone, Copy)]
 pub struct Value {
   pub env: sys::napi_env,
   pub value: sys::napi_value,
   pub value_type: ValueType,
+  /// The handle scope the value is created in, checked by `with_raw`
+  pub scope: HandleScopeId,
 }
@@ -5,6 +5,30 @@
 //!
 //! **napi-rs** provides minimal overhead to write N-API modules in `Rust`.
 //!
+//! ## Module init
+//!
+//! The module is registered on every env it's required on: the main thread, each `Worker`, and the env restored from a startup snapshot.
+//! The JS values and the `Env` are only valid on the env they are created on, so they must not be kept in the statics or the `thread_local!`s.
+//! Create the state of the env in a `#[napi(module_init)]` fn, which is called for every env, and keep it by `Env::set_instance_data`:
+//!
+//! ```ignore
+//! #[napi(module_init)]
+//! fn init(env: Env) -> Result<()> {
+//!   env.set_instance_data(State::default(), 0, |_| {})
+//! }
+//! ```
+//!
+//! `#[napi]` rejects the JS values in the `#[napi] static`s and the statics of the `#[napi(module_init)]` fn,
+//! the debug builds also fail the `with_raw` calls on the torn down envs.
+//!
+//! The `#[napi(lazy)] static`s are computed once, when the module is registered on the first env,
+//! the other envs get the clones of the value:
+//!
+//! ```ignore
+//! #[napi(lazy)]
+//! pub static PRIMES: Vec<u32> = (2..100).filter(|n| (2..*n).all(|d| n % d != 0)).collect();
+//! ```
+//!
 //! ## Feature flags
 //!
 //! ### napi1 ~ napi8
@@ -14,6 +38,21 @@
 //!
 //! The details of N-API versions and support matrix: [n_api_version_matrix](https://nodejs.org/api/n-api.html#n_api_n_api_version_matrix)
 //!
+//! With `napi8`, the instances of the `#[napi]` classes are tagged by `napi_type_tag_object`,
+//! passing the objects not created by the class throws the `InvalidArg` error instead of reading them as the class,
+//! and `Object::downcast` returns the instance of the class if it's created by the class.
+//!
+//! ### minimal
+//!
+//! The runtime of the sync only addons, without tokio, the async machinery and the threadsafe functions, builds faster and smaller:
+//!
+//! ```toml
+//! napi = { version = "2", default-features = false, features = ["minimal"] }
+//! ```
+//!
+//! The `async fn`s, `#[napi(execution = "tokio")]`, `#[napi(readable_stream)]` and `#[napi(async_iterator)]` fail the build with the feature to enable,
+//! `#[napi(also_async)]`, `#[napi(execution = "blocking_pool")]` and `AsyncTask` still run in the libuv thread pool.
+//!
 //! ### tokio_rt
 //! With `tokio_rt` feature, `napi-rs` provides a ***tokio runtime*** in an additional thread.
 //! And you can easily run tokio `future` in it and return `promise`.
@@ -33,6 +72,17 @@
 //! }
 //! ```
 //!
+//! The `async fn` taking the `AbortSignal` or `Option<AbortSignal>` is rejected with `AbortError` once it's aborted,
+//! the future is dropped then:
+//!
+//! ```ignore
+//! #[napi]
+//! async fn wait_for_abort(signal: AbortSignal) -> Result<bool> {
+//!   futures::future::pending::<()>().await;
+//!   Ok(signal.aborted())
+//! }
+//! ```
+//!
 //! ***Tokio channel in `napi-rs` buffer size is default `100`.***
 //!
 //! ***You can adjust it via `NAPI_RS_TOKIO_CHANNEL_BUFFER_SIZE` environment variable***
@@ -41,6 +91,36 @@
 //! NAPI_RS_TOKIO_CHANNEL_BUFFER_SIZE=1000 node ./app.js
 //! ```
 //!
+//! ### fs
+//!
+//! `napi::fs` reads and writes the files in the tokio runtime and returns `Promise`, with the `AbortSignal` and the progress callback.
+//!
+//! ### net
+//!
+//! `napi::net::fetch` sends the HTTP requests by [reqwest](https://docs.rs/reqwest), the same as `napi::fs` otherwise.
+//!
+//! ### allocator_mimalloc / allocator_jemalloc
+//!
+//! Replace the global allocator of the addon with [mimalloc](https://docs.rs/mimalloc) or [jemalloc](https://docs.rs/tikv-jemallocator), `napi build --allocator` enables them per build.
+//! `napi::allocator` reads the stats of the allocator and reports the memory held by it to V8.
+//!
+//! ### catch_unwind
+//!
+//! The panics of the `#[napi]` fns abort the process by default.
+//! With this feature, the panics of all the `#[napi]` fns on the JavaScript thread are caught and thrown as the JavaScript errors,
+//! `#[napi(catch_unwind)]` does the same for one fn:
+//!
+//! ```ignore
+//! #[napi(catch_unwind)]
+//! fn item_at(items: Vec<String>, index: u32) -> String {
+//!   items[index as usize].clone()
+//! }
+//! ```
+//!
+//! The `message` of the error is `panic from Rust code: {message of the panic}`,
+//! the `backtrace` of it is the Rust backtrace of the panic with this feature or the `backtrace` feature.
+//! The panics in the thread pools and the tokio runtime are not caught.
+//!
 //! ### latin1
 //!
 //! Decode latin1 string from JavaScript using [encoding_rs](https://docs.rs/encoding_rs).
@@ -78,14 +158,20 @@ mod async_cleanup_hook;
 
 #[cfg(feature = "napi8")]
 pub use async_cleanup_hook::AsyncCleanupHook;
+pub mod allocator;
 mod async_work;
 mod bindgen_runtime;
 mod call_context;
+mod chunked;
 #[cfg(feature = "napi3")]
 mod cleanup_env;
 mod env;
 mod error;
+#[cfg(feature = "fs")]
+pub mod fs;
 mod js_values;
+#[cfg(feature = "net")]
+pub mod net;
 
 #[cfg(all(feature = "tokio_rt", feature = "napi4"))]
 mod promise;
@@ -107,6 +193,7 @@ pub use napi_sys as sys;
 
 pub use async_work::AsyncWorkPromise;
 pub use call_context::CallContext;
+pub use chunked::Chunk;
 
 pub use env::*;
 pub use error::*;
@@ -154,6 +241,28 @@ macro_rules! assert_type_of {
   };
 }
 
+/// The `&'static str` value of the build constant in `napi.constants` of the napi config,
+/// the same one exported as `BUILD_CONSTANTS` by the JS binding of the build.
+///
+/// ```ignore
+/// const API_URL: &str = napi::build_constant!("API_URL");
+/// ```
+///
+/// Fails to compile when the crate is not built by `napi build`, or the name is not in the config.
+#[macro_export]
+macro_rules! build_constant {
+  ($name:literal) => {
+    env!(
+      concat!("NAPI_RS_CONSTANT_", $name),
+      concat!(
+        "`",
+        $name,
+        "` is not in `napi.constants`, build by `napi build`"
+      )
+    )
+  };
+}
+
 #[allow(dead_code)]
 pub(crate) unsafe fn log_js_value<V: AsRef<[sys::napi_value]>>(
   // `info`, `log`, `warning` or `error`
@@ -188,6 +297,50 @@ pub(crate) unsafe fn log_js_value<V: AsRef<[sys::napi_value]>>(
 
 pub use crate::bindgen_runtime::ctor as module_init;
 
+/// Emitted by `#[napi(lazy)]` for the statics initialized on the first read
+#[doc(hidden)]
+pub use lazy_static::lazy_static as __lazy_static;
+
+#[doc(hidden)]
+#[cfg(feature = "tokio_rt")]
+#[macro_export]
+macro_rules! __assert_tokio_rt {
+  ($item:literal) => {};
+}
+
+/// Emitted by `#[napi]` for the items running in the tokio runtime
+#[doc(hidden)]
+#[cfg(not(feature = "tokio_rt"))]
+#[macro_export]
+macro_rules! __assert_tokio_rt {
+  ($item:literal) => {
+    compile_error!(concat!(
+      $item,
+      " runs in the tokio runtime, which is not in the `minimal` feature set, enable the `async` feature of napi"
+    ));
+  };
+}
+
+#[doc(hidden)]
+#[cfg(feature = "serde-json")]
+#[macro_export]
+macro_rules! __assert_serde_json {
+  ($item:literal) => {};
+}
+
+/// Emitted by `#[napi]` for the items converted by serde
+#[doc(hidden)]
+#[cfg(not(feature = "serde-json"))]
+#[macro_export]
+macro_rules! __assert_serde_json {
+  ($item:literal) => {
+    compile_error!(concat!(
+      $item,
+      " is converted by serde, enable the `serde-json` feature of napi"
+    ));
+  };
+}
+
 pub mod bindgen_prelude {
   #[cfg(feature = "compat-mode")]
   pub use crate::bindgen_runtime::register_module_exports;
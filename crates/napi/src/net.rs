@@ -0,0 +1,149 @@
+//! Promise based HTTP requests by `reqwest`, running in the tokio runtime.
+//!
+//! The body of the response is read in chunks, so the `AbortSignal` is checked and the progress is reported between them,
+//! the same as the [`FsTask`] of `napi::fs`.
+//!
+//! ```ignore
+//! use napi::bindgen_prelude::*;
+//! use napi::net::{fetch, FetchInit, FetchResponse};
+//!
+//! #[napi(ts_args_type = "url: string, signal?: AbortSignal, onProgress?: (progress: FsProgress) => void")]
+//! pub fn download(
+//!   url: String,
+//!   signal: Option<AbortSignal>,
+//!   on_progress: Option<JsFunction>,
+//! ) -> napi::fs::FsTask<FetchResponse> {
+//!   fetch(url, FetchInit::default())
+//!     .with_signal(signal)
+//!     .on_progress(on_progress)
+//! }
+//! ```
+
+use std::collections::HashMap;
+
+use crate::bindgen_runtime::{abortable, Buffer, ToNapiValue, TypeName};
+use crate::fs::{FsProgress, FsTask};
+use crate::{sys, Env, Error, Result, Status, ValueType};
+
+lazy_static::lazy_static! {
+  // the connections are reused by all of the requests
+  static ref CLIENT: reqwest::Client = reqwest::Client::new();
+}
+
+/// The `RequestInit` of `fetch`
+#[derive(Debug, Clone, Default)]
+pub struct FetchInit {
+  /// `GET` if it's `None`
+  pub method: Option<String>,
+  pub headers: Vec<(String, String)>,
+  pub body: Option<Vec<u8>>,
+}
+
+/// Resolved as `{ status, statusText, url, headers, body }`, the whole body is read into the `Buffer`.
+/// The names of the headers are lowercase, the values of the repeated headers are joined by `, `.
+#[derive(Debug, Clone)]
+pub struct FetchResponse {
+  pub status: u16,
+  pub status_text: String,
+  /// The url after the redirects
+  pub url: String,
+  pub headers: HashMap<String, String>,
+  pub body: Vec<u8>,
+}
+
+impl TypeName for FetchResponse {
+  fn type_name() -> &'static str {
+    "FetchResponse"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl ToNapiValue for FetchResponse {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    let mut response = unsafe { Env::from_raw(env) }.create_object()?;
+    response.set("status", val.status as u32)?;
+    response.set("statusText", val.status_text)?;
+    response.set("url", val.url)?;
+    response.set("headers", val.headers)?;
+    response.set("body", Buffer::from(val.body))?;
+    Ok(response.0.value)
+  }
+}
+
+fn net_error(err: reqwest::Error, url: &str) -> Error {
+  Error::new(
+    Status::GenericFailure,
+    format!("Failed to fetch [{}]: {}", url, err),
+  )
+}
+
+/// Send the request once the returned `FsTask` is converted to the `Promise`.
+///
+/// Rejected if the request fails or the `AbortSignal` is aborted, the responses of the error statuses are resolved as well.
+pub fn fetch<U: Into<String>>(url: U, init: FetchInit) -> FsTask<FetchResponse> {
+  let url = url.into();
+  FsTask::new(move |ctx| async move {
+    let method = match init.method {
+      Some(method) => reqwest::Method::from_bytes(method.as_bytes()).map_err(|_| {
+        Error::new(
+          Status::InvalidArg,
+          format!("[{}] is not a valid http method", method),
+        )
+      })?,
+      None => reqwest::Method::GET,
+    };
+    let mut request = CLIENT.request(method, &url);
+    for (name, value) in init.headers {
+      request = request.header(name, value);
+    }
+    if let Some(body) = init.body {
+      request = request.body(body);
+    }
+    // the response could take long, waiting for it is aborted as well
+    let mut response = abortable(ctx.abort_handle(), async {
+      request.send().await.map_err(|e| net_error(e, &url))
+    })
+    .await?;
+    let status = response.status();
+    let mut headers: HashMap<String, String> = HashMap::new();
+    for (name, value) in response.headers() {
+      let value = String::from_utf8_lossy(value.as_bytes());
+      headers
+        .entry(name.as_str().to_owned())
+        .and_modify(|joined| {
+          joined.push_str(", ");
+          joined.push_str(&value);
+        })
+        .or_insert_with(|| value.into_owned());
+    }
+    let total = response.content_length();
+    let mut body = Vec::with_capacity(total.unwrap_or(0) as usize);
+    loop {
+      ctx.check_aborted()?;
+      let chunk = abortable(ctx.abort_handle(), async {
+        response.chunk().await.map_err(|e| net_error(e, &url))
+      })
+      .await?;
+      let chunk = match chunk {
+        Some(chunk) => chunk,
+        None => break,
+      };
+      body.extend_from_slice(&chunk);
+      ctx.report(FsProgress {
+        transferred: body.len() as u64,
+        // the transferred bytes if the length is unknown, e.g. the chunked encoding
+        total: total.unwrap_or(0).max(body.len() as u64),
+      });
+    }
+    Ok(FetchResponse {
+      status: status.as_u16(),
+      status_text: status.canonical_reason().unwrap_or("").to_owned(),
+      url: response.url().to_string(),
+      headers,
+      body,
+    })
+  })
+}
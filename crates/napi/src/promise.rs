@@ -99,6 +99,8 @@ unsafe extern "C" fn call_js_cb<
   data: *mut c_void,
 ) {
   let future_promise = unsafe { Box::from_raw(context as *mut FuturePromise<Data, Resolver>) };
+  // the `Reference`s moved into the future are dropped in the tokio runtime
+  crate::bindgen_runtime::unref_dropped_references(env);
   let value = unsafe { Box::from_raw(data as *mut Result<Data>) };
   let resolver = future_promise.resolver;
   let deferred = future_promise.deferred;
@@ -9,7 +9,7 @@ use tokio::{
   sync::mpsc::{self, error::TrySendError},
 };
 
-use crate::{check_status, promise, sys, Result};
+use crate::{bindgen_runtime::ToNapiValue, check_status, promise, sys, Error, Result, Status};
 
 lazy_static! {
   pub(crate) static ref RT: (Handle, mpsc::Sender<()>) = {
@@ -82,3 +82,30 @@ pub fn execute_tokio_future<
 
   Ok(promise)
 }
+
+/// Call a sync closure in the blocking pool of the tokio runtime and return a `Promise`,
+/// used by the `#[napi(execution = "tokio")]` generated functions.
+///
+/// # Safety
+///
+/// `env` must be valid in current JavaScript thread
+#[doc(hidden)]
+pub unsafe fn execute_tokio_blocking<T, F>(env: sys::napi_env, f: F) -> Result<sys::napi_value>
+where
+  T: 'static + Send + ToNapiValue,
+  F: 'static + Send + FnOnce() -> Result<T>,
+{
+  execute_tokio_future(
+    env,
+    async move { Ok(RT.0.spawn_blocking(f).await) },
+    move |env, ret| {
+      let ret = ret.map_err(|e| {
+        Error::new(
+          Status::GenericFailure,
+          format!("Tokio blocking task failed: {}", e),
+        )
+      })??;
+      unsafe { T::to_napi_value(env, ret) }
+    },
+  )
+}
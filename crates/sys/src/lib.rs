@@ -207,6 +207,13 @@ pub type napi_async_cleanup_hook_handle = *mut napi_async_cleanup_hook_handle__;
 #[cfg(feature = "napi8")]
 pub type napi_async_cleanup_hook =
   Option<unsafe extern "C" fn(handle: napi_async_cleanup_hook_handle, data: *mut c_void)>;
+#[cfg(feature = "napi8")]
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct napi_type_tag {
+  pub lower: u64,
+  pub upper: u64,
+}
 
 extern "C" {
   pub fn napi_get_last_error_info(
@@ -845,6 +852,19 @@ extern "C" {
   pub fn napi_object_freeze(env: napi_env, object: napi_value) -> napi_status;
 
   pub fn napi_object_seal(env: napi_env, object: napi_value) -> napi_status;
+
+  pub fn napi_type_tag_object(
+    env: napi_env,
+    value: napi_value,
+    type_tag: *const napi_type_tag,
+  ) -> napi_status;
+
+  pub fn napi_check_object_type_tag(
+    env: napi_env,
+    value: napi_value,
+    type_tag: *const napi_type_tag,
+    result: *mut bool,
+  ) -> napi_status;
 }
 
 #[cfg(feature = "experimental")]
@@ -1,10 +1,19 @@
 import { exec } from 'child_process'
+import { mkdtempSync, readdirSync, readFileSync } from 'fs'
+import { createServer } from 'http'
+import { AddressInfo } from 'net'
+import { tmpdir } from 'os'
 import { join } from 'path'
+import { Readable } from 'stream'
+import { inspect } from 'util'
 
 import test from 'ava'
 
 import {
   DEFAULT_COST,
+  MAX_PAGE_SIZE,
+  GREETING,
+  SQUARES,
   add,
   fibonacci,
   contains,
@@ -15,6 +24,10 @@ import {
   getNums,
   getWords,
   sumNums,
+  pluginName,
+  distanceFromOrigin,
+  sumAll,
+  joinWords,
   getMapping,
   sumMapping,
   getCwd,
@@ -24,11 +37,40 @@ import {
   CustomNumEnum,
   Context,
   enumToI32,
+  LogLevel,
+  ContentKind,
+  isQuiet,
+  nextLogLevel,
+  shapeArea,
+  firstItem,
+  paginate,
+  scaleShape,
+  echoSetting,
   listObjKeys,
   createObj,
   mapOption,
+  repeatWithDefault,
   readFile,
+  mapSum,
+  callOnObject,
+  greetWith,
   throwError,
+  itemAt,
+  parseConfig,
+  readConfig,
+  ConfigError,
+  ConfigParseError,
+  ConfigMissingKeyError,
+  ConfigIoError,
+  Fib,
+  Lines,
+  tokenize,
+  spanLen,
+  minMax,
+  swapPair,
+  resolveSpecifiers,
+  resolveSpecifierOffThread,
+  Resolver,
   readPackageJson,
   getPackageJsonName,
   getBuffer,
@@ -40,6 +82,8 @@ import {
   withoutAbortController,
   withAbortController,
   asyncMultiTwo,
+  waitForAbort,
+  abortableMultiTwo,
   bigintAdd,
   createBigInt,
   createBigIntI64,
@@ -61,10 +105,13 @@ import {
   xxh3,
   xxh64Alias,
   tsRename,
+  overrideIndividualArgOnFunction,
   convertU32Array,
   createExternalTypedArray,
   mutateTypedArray,
   receiveAllOptionalObject,
+  echoNullableObject,
+  echoHttpHeaders,
   fnReceivedAliased,
   ALIAS,
   AliasedStruct,
@@ -82,17 +129,67 @@ import {
   returnJsFunction,
   testSerdeRoundtrip,
   createObjWithProperty,
+  getNestedPort,
+  setNestedPort,
   dateToNumber,
   chronoDateToMillis,
   derefUint8Array,
   chronoDateAdd1Minute,
   bufferPassThrough,
   JsRepo,
+  RepoRegistry,
+  repoDir,
   asyncReduceBuffer,
+  divide,
+  divideAsync,
+  sumInBlockingPool,
+  sumInTokio,
+  sumInChunks,
+  kindOf,
+  createBird,
+  isAnimalName,
+  createSamples,
+  polylineLength,
+  countPorts,
+  memoizedPolylineLength,
+  countOwnKeys,
+  useEnvOnOtherThread,
+  useHandleOutOfScope,
+  delete_,
+  readInChunks,
+  readFileWithProgress,
+  writeFileWithProgress,
+  writeFileWhenReady,
+  fetchWithProgress,
+  countTo,
+  failAfterFirstChunk,
+  ChunkedData,
+  countFrom,
+  failAfterFirstItem,
+  iterateWords,
+  IS_DEBUG_BUILD,
+  allocatorStats,
+  napiVersionAtInit,
+  Node,
+  Identifier,
+  nodeStart,
+  describeNode,
+  Point,
+  Version,
 } from '../'
+import * as bindings from '../'
 
 test('export const', (t) => {
   t.is(DEFAULT_COST, 12)
+  t.is(MAX_PAGE_SIZE, 1024)
+})
+
+test('export static', (t) => {
+  t.is(GREETING, 'Hello from static')
+})
+
+test('export lazy static', (t) => {
+  t.deepEqual(SQUARES, [0, 1, 4, 9, 16, 25, 36, 49, 64, 81])
 })
 
 test('number', (t) => {
@@ -130,6 +227,38 @@ test('array', (t) => {
   t.is(sumNums([1, 2, 3, 4, 5]), 15)
 })
 
+test('tuple', (t) => {
+  t.deepEqual(swapPair([1, 'a']), ['a', 1])
+  t.deepEqual(minMax([3, 1, 2]), [1, 3])
+  t.is(minMax([]), null)
+  t.deepEqual(tokenize('ab cd\nef'), [
+    { text: 'ab', span: [0, 2], position: [1, 1] },
+    { text: 'cd', span: [3, 5], position: [1, 4] },
+    { text: 'ef', span: [6, 8], position: [2, 1] },
+  ])
+  t.is(spanLen([2, 7]), 5)
+  t.throws(() => spanLen([1] as any), {
+    code: 'InvalidArg',
+    message: 'Expect an array of 2 elements, but received 1 elements',
+  })
+  t.throws(() => spanLen({ 0: 1, 1: 2 } as any), { code: 'ArrayExpected' })
+  t.throws(() => swapPair([1, 2] as any), { code: 'StringExpected' })
+})
+
+test('variadic args', (t) => {
+  t.is(sumAll(1), 1)
+  t.is(sumAll(1, 2, 3, 4, 5), 15)
+  t.is(joinWords(undefined, 'foo', 'bar'), 'foo bar')
+  t.is(joinWords('-', ...getWords()), 'foo-bar')
+  t.is(joinWords(), '')
+  // @ts-expect-error
+  const err = t.throws(() => sumAll(1, 2, '3'))
+  t.is(
+    err!.message,
+    '`rest[1]`: Expect value to be Number, but received String',
+  )
+})
+
 test('map', (t) => {
   t.deepEqual(getMapping(), { a: 101, b: 102 })
   t.is(sumMapping({ a: 101, b: 102 }), 203)
@@ -140,6 +269,83 @@ test('enum', (t) => {
   t.is(enumToI32(CustomNumEnum.Eight), 8)
 })
 
+test('string enum', (t) => {
+  t.deepEqual(
+    [LogLevel.Debug, LogLevel.Info, LogLevel.Warn, LogLevel.Error],
+    ['debug', 'info', 'warn', 'fatal-error'],
+  )
+  t.false(isQuiet(LogLevel.Info))
+  // @ts-expect-error
+  t.true(isQuiet('fatal-error'))
+  t.is(nextLogLevel(LogLevel.Info), LogLevel.Warn)
+  // @ts-expect-error
+  t.throws(() => isQuiet('verbose'), {
+    code: 'InvalidArg',
+    message: 'value `verbose` does not match any variant of enum `LogLevel`',
+  })
+  t.deepEqual(
+    [ContentKind.PlainText, ContentKind.RichText],
+    ['plain-text', 'rich-text'],
+  )
+})
+
+test('module init', (t) => {
+  t.is(napiVersionAtInit(), Number(process.versions.napi))
+  t.is((bindings as Record<string, unknown>).registeredByInit, 'module_init')
+})
+
+test('allocator stats', (t) => {
+  // the example is built with the system allocator, which provides no stats
+  t.deepEqual(allocatorStats(), { allocator: 'system' })
+})
+
+test('generic', (t) => {
+  const item = { name: 'napi' }
+  t.is(firstItem({ items: [item, 2], total: 2 }), item)
+  t.is(firstItem({ items: [], total: 0 }), null)
+  const pages = paginate(['a', 'b', 1], 2)
+  t.deepEqual(pages, [
+    { items: ['a', 'b'], total: 3 },
+    { items: [1], total: 3 },
+  ])
+  const first: string | number | null | undefined = firstItem(pages[0])
+  t.is(first, 'a')
+})
+
+test('data enum', (t) => {
+  t.is(shapeArea({ type: 'Rect', width: 2, height: 3 }), 6)
+  t.is(shapeArea({ type: 'Polygon', field0: [0, 0, 4, 0, 4, 3] }), 6)
+  t.deepEqual(scaleShape({ type: 'Circle', radius: 1 }, 3), {
+    type: 'Circle',
+    radius: 3,
+  })
+  t.deepEqual(scaleShape({ type: 'label', content: 'napi', fontSize: 10 }, 2), {
+    type: 'label',
+    content: 'napi',
+    fontSize: 20,
+  })
+  t.deepEqual(scaleShape({ type: 'Empty' }, 2), { type: 'Empty' })
+  t.deepEqual(echoSetting({ type: 'Value', val: 'on' }), {
+    type: 'Value',
+    val: 'on',
+  })
+  t.deepEqual(echoSetting({ type: 'Nested', obj: ['a'], env: 1 }), {
+    type: 'Nested',
+    obj: ['a'],
+    env: 1,
+  })
+  // @ts-expect-error
+  t.throws(() => shapeArea({ type: 'Circle' }), {
+    code: 'InvalidArg',
+    message: 'Missing field `radius` of the variant `Circle`',
+  })
+  // @ts-expect-error
+  t.throws(() => shapeArea({ type: 'Square', size: 1 }), {
+    code: 'InvalidArg',
+    message: 'value `Square` does not match any variant of enum `Shape`',
+  })
+})
+
 test('class', (t) => {
   const dog = new Animal(Kind.Dog, '旺财')
 
@@ -181,6 +387,90 @@ test('class factory', (t) => {
   )
 })
 
+test('fallible and async class factory', async (t) => {
+  t.is(ClassWithFactory.parse('Duck').name, 'Duck')
+  t.throws(() => ClassWithFactory.parse(''), {
+    message: "name can't be empty",
+  })
+  const duck = await ClassWithFactory.load('Duck')
+  t.true(duck instanceof ClassWithFactory)
+  t.is(duck.setName('D').name, 'D')
+  await t.throwsAsync(() => ClassWithFactory.load(''), {
+    message: "name can't be empty",
+  })
+})
+
+test('class static method as module function', (t) => {
+  t.is(kindOf('cat'), Kind.Cat)
+  t.is(Animal.kindOf('duck'), Kind.Duck)
+  t.is(kindOf('bird'), null)
+  const bird = createBird('parrot')
+  t.true(bird instanceof Bird)
+  t.is(bird.getCount(), 1234)
+  t.false('createBird' in Bird)
+})
+
+test('module function as class static method', (t) => {
+  t.true(isAnimalName('dog'))
+  t.true(Animal.isAnimalName('cat'))
+  t.false(Animal.isAnimalName('bird'))
+})
+
+test('class extends', (t) => {
+  const id = new Identifier('foo', 3)
+  t.true(id instanceof Identifier)
+  t.true(id instanceof Node)
+  t.is(Object.getPrototypeOf(Identifier), Node)
+  t.is(id.kind, 'Identifier')
+  t.is(id.name, 'foo')
+  id.shift(2)
+  t.is(id.describe(), 'Identifier@5 foo')
+  t.is(new Node('Program', 0).describe(), 'Program@0')
+})
+
+test('class instance type tag', (t) => {
+  t.is(nodeStart(new Node('Program', 4)), 4)
+  t.is(nodeStart(new Identifier('foo', 7)), 7)
+  for (const value of [{}, new Point(1, 2)]) {
+    // @ts-expect-error
+    t.throws(() => nodeStart(value), {
+      code: 'InvalidArg',
+      message: 'Value is not an instance of class `Node`',
+    })
+  }
+  t.is(describeNode(new Identifier('foo', 7)), 'Identifier@7')
+  t.is(describeNode({}), null)
+  t.is(describeNode(new Point(1, 2)), null)
+})
+
+test('class property attributes', (t) => {
+  const point = new Point(3, 4)
+  const x = Object.getOwnPropertyDescriptor(Point.prototype, 'x')!
+  t.is(x.set, undefined)
+  t.true(x.enumerable)
+  t.false(x.configurable)
+  t.true(Object.getOwnPropertyDescriptor(Point.prototype, 'length')!.enumerable)
+  t.throws(() => {
+    // @ts-expect-error
+    point.x = 5
+  })
+  t.throws(() => Object.defineProperty(Point.prototype, 'y', { value: 0 }))
+  t.is(point.x, 3)
+  t.is(point.length, 5)
+})
+
+test('class toString, toJSON and inspect', (t) => {
+  const version = Version.parse('1.2.3')
+  t.is(String(version), '1.2.3')
+  t.is(`v${version}`, 'v1.2.3')
+  t.is(
+    JSON.stringify({ version }),
+    '{"version":{"major":1,"minor":2,"patch":3}}',
+  )
+  t.is(inspect(version), 'Version { major: 1, minor: 2, patch: 3 }')
+  t.throws(() => Version.parse('1.2'), { message: 'Invalid version `1.2`' })
+})
+
 test('class constructor return Result', (t) => {
   const c = new Context()
   t.is(c.method(), 'not empty')
@@ -196,6 +486,22 @@ test('should be able to create object reference and shared reference', (t) => {
   t.is(repo.remote().name(), 'origin')
 })
 
+test('references of the class instances', async (t) => {
+  const registry = new RepoRegistry()
+  const repo = new JsRepo('a')
+  registry.add(repo)
+  registry.add(new JsRepo('b'))
+  t.is(registry.get(0), repo)
+  t.true(registry.get(1) instanceof JsRepo)
+  t.is(registry.get(2), null)
+  t.throws(() => registry.add({} as any))
+  t.is(registry.lastRemoved(), null)
+  const removed = registry.removeLast()
+  t.is(registry.lastRemoved(), removed)
+  t.is(await repoDir(new JsRepo('c')), 'c')
+  t.is(await repo.dir(), 'a')
+})
+
 test('callback', (t) => {
   getCwd((cwd) => {
     t.is(cwd, process.cwd())
@@ -214,6 +520,34 @@ test('callback', (t) => {
   })
 })
 
+test('typed callback', (t) => {
+  t.is(
+    mapSum([1, 2, 3], (value, index) => value * index),
+    0 * 1 + 1 * 2 + 2 * 3,
+  )
+  // @ts-expect-error
+  t.throws(() => mapSum([1], () => 'not a number'), {
+    code: 'NumberExpected',
+  })
+  t.throws(
+    () =>
+      mapSum([1], () => {
+        throw new Error('boom')
+      }),
+    { message: 'boom' },
+  )
+  t.is(
+    callOnObject({ prefix: 'hello ' }, function (this: any, name) {
+      return this.prefix + name
+    }),
+    'hello world',
+  )
+  t.is(
+    greetWith('napi', (name, times) => `hi ${name} ${times}`),
+    'hi napi null',
+  )
+})
+
 test('return function', (t) => {
   return new Promise<void>((resolve) => {
     returnJsFunction()((err: Error | undefined, content: string) => {
@@ -229,6 +563,16 @@ test('object', (t) => {
   t.deepEqual(createObj(), { test: 1 })
 })
 
+test('this of the fns', (t) => {
+  const plugin = { name: 'foo', pluginName }
+  t.is(plugin.pluginName('plugin:'), 'plugin:foo')
+  t.is(pluginName.call({ name: 'bar' }, ''), 'bar')
+  t.is(distanceFromOrigin.call({ x: 3, y: 4 }), 5)
+  t.throws(() => distanceFromOrigin.call({ x: 3 }), {
+    message: 'Missing field `y`',
+  })
+})
+
 test('get str from object', (t) => {
   t.notThrows(() => getStrFromObject())
 })
@@ -239,6 +583,64 @@ test('create object from Property', (t) => {
   t.is(obj.getter, 42)
 })
 
+test('get and set nested values of object', (t) => {
+  t.is(getNestedPort({ server: { http: { port: 8080 } } }), 8080)
+  t.is(getNestedPort({ server: null }), null)
+  t.throws(() => getNestedPort({ server: { http: 1 } }), {
+    message: 'Failed to get `server.http.port`, `server.http` is Number, not an object',
+  })
+  t.deepEqual(setNestedPort({ server: { tls: true } }, 443), {
+    server: { tls: true, http: { port: 443 } },
+  })
+  t.throws(() => setNestedPort({ server: 'localhost' }, 443), {
+    message: 'Failed to set `server.http.port`, `server` is String, not an object',
+  })
+})
+
+test('memoized argument', (t) => {
+  const polyline = {
+    name: 'route',
+    points: [
+      { x: 0, y: 0 },
+      { x: 3, y: 4 },
+    ],
+  }
+  t.is(memoizedPolylineLength(polyline), 5)
+  polyline.points.push({ x: 3, y: 8 })
+  // converted once per object, until the revision is bumped
+  t.is(memoizedPolylineLength(polyline), 5)
+  t.is(memoizedPolylineLength({ ...polyline }), 9)
+  const revision = Symbol.for('napi-rs.revision')
+  Object.assign(polyline, { [revision]: 1 })
+  t.is(memoizedPolylineLength(polyline), 9)
+  t.throws(() => memoizedPolylineLength({ name: 'route' } as any), {
+    code: 'InvalidArg',
+  })
+})
+
+test('with raw', (t) => {
+  t.is(countOwnKeys({ a: 1, b: 2, [Symbol('c')]: 3 }), 2)
+  if (IS_DEBUG_BUILD) {
+    t.throws(() => useEnvOnOtherThread(), {
+      message:
+        /^The env is used on the thread .*, but it belongs to another JavaScript thread/,
+    })
+    t.throws(() => useHandleOutOfScope(), {
+      code: 'InvalidArg',
+      message: /^The handle of Object is not alive anymore/,
+    })
+  } else {
+    t.notThrows(() => useEnvOnOtherThread())
+    t.notThrows(() => useHandleOutOfScope())
+  }
+})
+
+test('reserved js name', (t) => {
+  const obj = { a: 1, b: 2 }
+  t.true(delete_(obj, 'a'))
+  t.deepEqual(obj, { b: 2 })
+})
+
 test('global', (t) => {
   t.is(getGlobal(), global)
 })
@@ -278,28 +680,261 @@ test('Option', (t) => {
   t.is(mapOption(3), 4)
 })
 
+test('default param', (t) => {
+  t.is(repeatWithDefault('a'), 'a, a')
+  t.is(repeatWithDefault('a', 3), 'a, a, a')
+  t.is(repeatWithDefault('a', undefined, '-'), 'a-a')
+  t.is(repeatWithDefault('a', null, null), 'a, a')
+  t.throws(() => repeatWithDefault('a', '3' as any), {
+    code: 'InvalidArg',
+    message: 'Expect value to be Number, but received String',
+  })
+})
+
 test('Result', (t) => {
   t.throws(() => throwError(), void 0, 'Manual Error')
 })
 
+test('panic thrown as the error', (t) => {
+  t.is(itemAt(['a', 'b'], 1), 'b')
+  const error = t.throws<Error & { backtrace: string }>(() => itemAt(['a'], 3), {
+    instanceOf: Error,
+    code: 'GenericFailure',
+    message:
+      'panic from Rust code: index out of bounds: the len is 1 but the index is 3',
+  })
+  t.true(error.backtrace.includes('item_at'))
+  // the module is still usable after the panic
+  t.is(itemAt(['c'], 0), 'c')
+})
+
+test('error classes', async (t) => {
+  t.deepEqual(parseConfig('a = 1', ['a']), { a: '1' })
+  const parseError = t.throws<ConfigParseError>(
+    () => parseConfig('a = 1\nb', []),
+    {
+      instanceOf: ConfigParseError,
+      code: 'PARSE',
+      message: 'Invalid line 2',
+    },
+  )
+  t.true(parseError instanceof ConfigError)
+  t.is(parseError.name, 'ConfigParseError')
+  t.is(parseError.line, 2)
+  t.false('column' in parseError)
+  t.is(t.throws<ConfigParseError>(() => parseConfig(' = 1', [])).column, 1)
+  const missingKey = t.throws<ConfigMissingKeyError>(
+    () => parseConfig('a = 1', ['a', 'b']),
+    { instanceOf: ConfigMissingKeyError, code: 'ENOKEY' },
+  )
+  t.is(missingKey.key, 'b')
+  await t.throwsAsync(() => readConfig('some_nonexist_path.conf'), {
+    instanceOf: ConfigIoError,
+    code: 'IO',
+  })
+})
+
+test('js implemented trait', async (t) => {
+  const resolved: string[] = []
+  const resolver: Resolver & { prefix: string } = {
+    prefix: '/lib/',
+    resolve(specifier, fromDir) {
+      if (specifier === 'missing') {
+        throw new Error(`Cannot resolve ${specifier}`)
+      }
+      return (fromDir ?? this.prefix) + specifier
+    },
+    onResolved(path) {
+      resolved.push(path)
+    },
+  }
+  t.deepEqual(resolveSpecifiers(resolver, ['a', 'b']), ['/lib/a', '/lib/b'])
+  t.deepEqual(resolved, ['/lib/a', '/lib/b'])
+  t.throws(() => resolveSpecifiers(resolver, ['missing']), {
+    message: 'Cannot resolve missing',
+  })
+  t.throws(() => resolveSpecifiers({ resolve: () => 'a' } as any, ['a']), {
+    code: 'FunctionExpected',
+    message: '`onResolved` is not a function of the JavaScript object',
+  })
+  t.is(await resolveSpecifierOffThread(resolver, 'c'), '/c')
+  await t.throwsAsync(() => resolveSpecifierOffThread(resolver, 'missing'), {
+    message: 'Cannot resolve missing',
+  })
+})
+
+test('iterator class', (t) => {
+  t.deepEqual([...new Fib(20)], [0, 1, 1, 2, 3, 5, 8, 13])
+  const lines = []
+  for (const line of Lines.split('a\nb')) {
+    lines.push(line)
+  }
+  t.deepEqual(lines, [
+    { number: 1, text: 'a' },
+    { number: 2, text: 'b' },
+  ])
+  const fib = new Fib(0)
+  t.is(fib[Symbol.iterator](), fib)
+  t.deepEqual(fib.next(), { done: false, value: 0 })
+  t.deepEqual(fib.next(), { done: true })
+  // the exhausted iterator
+  t.deepEqual([...fib], [])
+})
+
 test('function ts type override', (t) => {
   t.deepEqual(tsRename({ foo: 1, bar: 2, baz: 2 }), ['foo', 'bar', 'baz'])
 })
 
+test('function individual ts arg type override', (t) => {
+  t.is(
+    overrideIndividualArgOnFunction('someStr', () => 'anotherStr', 42),
+    'oia: someStr-42-anotherStr',
+  )
+  const dog = new Animal(Kind.Dog, '旺财')
+  t.is(dog.rename('小黄' as Parameters<Animal['rename']>[0]), '旺财')
+  t.is(dog.name, '小黄')
+  t.is(dog.repeatName(), '小黄 小黄')
+  t.is(dog.repeatName(3), '小黄 小黄 小黄')
+})
+
 test('option object', (t) => {
   t.notThrows(() => receiveAllOptionalObject())
   t.notThrows(() => receiveAllOptionalObject({}))
 })
 
+test('nullable object', (t) => {
+  t.deepEqual(echoNullableObject({ name: null, age: 1 }), {
+    name: null,
+    age: 1,
+  })
+  t.deepEqual(echoNullableObject({ name: 'foo', age: 1 }), {
+    name: 'foo',
+    age: 1,
+  })
+  t.throws(() => echoNullableObject({ age: 1 } as any), {
+    message: 'Missing field `name`',
+  })
+})
+
+test('object rename_all', (t) => {
+  const headers = {
+    'content-type': 'text/plain',
+    'content-length': 2,
+    'x-request-id': 'foo',
+  }
+  t.deepEqual(echoHttpHeaders(headers), headers)
+  t.deepEqual(
+    Object.keys(
+      echoHttpHeaders({ 'content-type': 'text/plain', 'x-request-id': 'foo' }),
+    ),
+    ['content-type', 'x-request-id'],
+  )
+})
+
+test('columnar object array', (t) => {
+  const samples = createSamples(1000)
+  t.is(samples.length, 1000)
+  t.true(samples.columns.id instanceof Uint32Array)
+  t.true(samples.columns.value instanceof Float64Array)
+  t.deepEqual(samples.columns.label.slice(0, 2), ['sample-0', 'sample-1'])
+  t.deepEqual(samples.get(3), { id: 3, value: 1.5, label: 'sample-3' })
+  t.is(samples.get(1000), undefined)
+  t.deepEqual(samples.toArray()[999], {
+    id: 999,
+    value: 499.5,
+    label: 'sample-999',
+  })
+  t.is([...samples].length, 1000)
+  t.deepEqual(createSamples(0).toArray(), [])
+})
+
 test('should throw if object type is not matched', (t) => {
   // @ts-expect-error
   const err1 = t.throws(() => receiveStrictObject({ name: 1 }))
-  t.is(err1!.message, 'Failed to convert napi `string` into rust type `String`')
+  t.is(
+    err1!.message,
+    '`strictObject.name`: Expect value to be String, but received Number',
+  )
   // @ts-expect-error
   const err2 = t.throws(() => receiveStrictObject({ bar: 1 }))
   t.is(err2!.message, 'Missing field `name`')
 })
 
+test('should report the path of the nested values', (t) => {
+  t.is(
+    polylineLength({
+      name: 'line',
+      points: [
+        { x: 0, y: 0 },
+        { x: 3, y: 4 },
+      ],
+    }),
+    5,
+  )
+  const err1 = t.throws(() =>
+    // @ts-expect-error
+    polylineLength({ name: 'line', points: [{ x: 0, y: 0 }, { x: 3 }] }),
+  )
+  t.is(err1!.message, '`polyline.points[1]`: Missing field `y`')
+  const err2 = t.throws(() =>
+    // @ts-expect-error
+    polylineLength({ name: 'line', points: [{ x: 0, y: 0 }, { x: 3, y: '4' }] }),
+  )
+  t.true(err2!.message.startsWith('`polyline.points[1].y`: '))
+  if (IS_DEBUG_BUILD) {
+    const err3 = t.throws(() =>
+      // @ts-expect-error
+      polylineLength({ name: 'line', points: [{ x: 0, y: 0 }, null] }),
+    )
+    t.is(
+      err3!.message,
+      '`polyline.points[1]`: Expect value to be Object, but received Null',
+    )
+  }
+})
+
+test('should reject the unknown fields of deny_unknown_fields objects', (t) => {
+  t.is(
+    countPorts({ name: 'app', server: { host: 'localhost', ports: [80, 443] } }),
+    2,
+  )
+  t.throws(
+    () =>
+      countPorts({
+        name: 'app',
+        // @ts-expect-error
+        server: { host: 'localhost', ports: [80, 443, '8080'] },
+      }),
+    {
+      code: 'NumberExpected',
+      message:
+        '`config.server.ports[2]`: Expect value to be Number, but received String',
+    },
+  )
+  t.throws(
+    () =>
+      countPorts({
+        name: 'app',
+        // @ts-expect-error
+        server: { host: 'localhost', ports: [80], tls: true },
+      }),
+    {
+      code: 'InvalidArg',
+      message: '`config.server`: Unknown field `tls`',
+    },
+  )
+  t.throws(
+    () =>
+      countPorts({
+        name: 'app',
+        server: { host: 'localhost', ports: [80] },
+        // @ts-expect-error
+        extra: 1,
+      }),
+    { code: 'InvalidArg', message: '`config`: Unknown field `extra`' },
+  )
+})
+
 test('aliased rust struct and enum', (t) => {
   const a: ALIAS = ALIAS.A
   const b: AliasedStruct = {
@@ -480,6 +1115,169 @@ AbortSignalTest('abort resolved task', async (t) => {
   t.pass('should not throw')
 })
 
+AbortSignalTest('abort async fn', async (t) => {
+  const ctrl = new AbortController()
+  const promise = waitForAbort(ctrl.signal)
+  setTimeout(() => ctrl.abort(), 10)
+  await t.throwsAsync(() => promise, { message: 'AbortError' })
+  const aborted = new AbortController()
+  aborted.abort()
+  await t.throwsAsync(() => abortableMultiTwo(2, aborted.signal), {
+    message: 'AbortError',
+  })
+  t.is(await abortableMultiTwo(2), 4)
+  const notAborted = new AbortController()
+  t.is(await abortableMultiTwo(3, notAborted.signal), 6)
+  notAborted.abort()
+})
+
+AbortSignalTest('write and read file with progress', async (t) => {
+  const dir = mkdtempSync(join(tmpdir(), 'napi-rs-fs-'))
+  const file = join(dir, 'data.bin')
+  const data = Buffer.alloc(200 * 1024, 7)
+  const written: number[] = []
+  await writeFileWithProgress(file, data, null, ({ transferred, total }) => {
+    t.is(total, data.length)
+    written.push(transferred)
+  })
+  t.deepEqual(written, [65536, 131072, 196608, 204800])
+  t.true(readFileSync(file).equals(data))
+  const read: number[] = []
+  const buffer = await readFileWithProgress(file, null, ({ transferred }) => {
+    read.push(transferred)
+  })
+  t.true(buffer.equals(data))
+  t.deepEqual(read, written)
+  await t.throwsAsync(() => readFileWithProgress(join(dir, 'missing.bin')), {
+    message: /^Failed to open/,
+  })
+})
+
+AbortSignalTest('abort file operations', async (t) => {
+  const dir = mkdtempSync(join(tmpdir(), 'napi-rs-fs-'))
+  const aborted = new AbortController()
+  aborted.abort()
+  await t.throwsAsync(
+    () =>
+      writeFileWithProgress(
+        join(dir, 'data.bin'),
+        Buffer.from('napi'),
+        aborted.signal,
+      ),
+    { message: 'AbortError' },
+  )
+  // aborted while the write is pending
+  let ready!: () => void
+  const pending = new Promise<void>((resolve) => {
+    ready = resolve
+  })
+  const ctrl = new AbortController()
+  const written = writeFileWhenReady(
+    join(dir, 'data.bin'),
+    Buffer.alloc(1024 * 1024),
+    pending,
+    ctrl.signal,
+  )
+  ctrl.abort()
+  ready()
+  await t.throwsAsync(written, { message: 'AbortError' })
+  // the temporary file is removed
+  t.deepEqual(readdirSync(dir), [])
+})
+
+AbortSignalTest('concurrent writes of the same file', async (t) => {
+  const dir = mkdtempSync(join(tmpdir(), 'napi-rs-fs-'))
+  const file = join(dir, 'data.bin')
+  const contents = [1, 2, 3, 4].map((n) => Buffer.alloc(200 * 1024, n))
+  await Promise.all(
+    contents.map((content) => writeFileWithProgress(file, content)),
+  )
+  const written = readFileSync(file)
+  t.true(contents.some((content) => content.equals(written)))
+  t.deepEqual(readdirSync(dir), ['data.bin'])
+})
+
+AbortSignalTest('fetch with progress', async (t) => {
+  const body = Buffer.alloc(200 * 1024, 7)
+  const server = createServer((req, res) => {
+    if (req.url === '/pending') {
+      // never responded, until the request is aborted
+      res.flushHeaders()
+      return
+    }
+    res.setHeader('x-napi', ['a', 'b'])
+    res.statusCode = req.method === 'POST' ? 201 : 200
+    res.end(req.method === 'HEAD' ? undefined : body)
+  })
+  await new Promise<void>((resolve) => server.listen(0, '127.0.0.1', resolve))
+  const { port } = server.address() as AddressInfo
+  try {
+    const progress: number[] = []
+    const response = await fetchWithProgress(
+      `http://127.0.0.1:${port}/data`,
+      null,
+      null,
+      ({ transferred, total }) => {
+        t.is(total, body.length)
+        progress.push(transferred)
+      },
+    )
+    t.is(response.status, 200)
+    t.is(response.statusText, 'OK')
+    t.is(response.headers['x-napi'], 'a, b')
+    t.true(response.body.equals(body))
+    t.is(progress[progress.length - 1], body.length)
+    t.is(
+      (await fetchWithProgress(`http://127.0.0.1:${port}/data`, 'POST'))
+        .status,
+      201,
+    )
+    await t.throwsAsync(
+      () => fetchWithProgress(`http://127.0.0.1:${port}/data`, 'NOT A METHOD'),
+      { message: '[NOT A METHOD] is not a valid http method' },
+    )
+    const ctrl = new AbortController()
+    const pending = fetchWithProgress(
+      `http://127.0.0.1:${port}/pending`,
+      null,
+      ctrl.signal,
+    )
+    setTimeout(() => ctrl.abort(), 50)
+    await t.throwsAsync(pending, { message: 'AbortError' })
+  } finally {
+    server.closeAllConnections()
+    server.close()
+  }
+})
+
+test('also async', async (t) => {
+  t.is(divide(6, 3), 2)
+  t.is(await divideAsync(6, 3), 2)
+  t.throws(() => divide(1, 0), { message: 'Division by zero' })
+  await t.throwsAsync(() => divideAsync(1, 0), { message: 'Division by zero' })
+
+  t.is(Animal.greet('Hello', '旺财'), 'Hello, 旺财')
+  t.is(await Animal.greetAsync('Hello', '旺财'), 'Hello, 旺财')
+})
+
+test('execution', async (t) => {
+  t.is(await sumInBlockingPool(1, 2), 3)
+  t.is(await sumInTokio(1, 2), 3)
+  await t.throwsAsync(() => sumInTokio(0xffffffff, 1), { message: 'Overflow' })
+  t.is(await Animal.upperNameInTokio('tom'), 'TOM')
+})
+
+test('run in chunks', async (t) => {
+  let ticks = 0
+  const timer = setInterval(() => {
+    ticks++
+  }, 0)
+  t.is(await sumInChunks(10_000_000), 50_000_005_000_000)
+  clearInterval(timer)
+  t.true(ticks > 0)
+  t.is(await sumInChunks(0), 0)
+})
+
 const BigIntTest = typeof BigInt !== 'undefined' ? test : test.skip
 
 BigIntTest('BigInt add', (t) => {
@@ -510,6 +1308,13 @@ BigIntTest('js mod test', (t) => {
   t.is(xx3.digest(), BigInt('1116'))
 })
 
+test('nested js mod', (t) => {
+  t.is(xxh3.streaming.seedValue(xxh3.streaming.Seed.Default), 1116)
+  t.is(xxh3.streaming.seedValue(xxh3.streaming.Seed.Zero), 0)
+  t.is(xxh2.legacy.xxh2Minus(3, 1), 2)
+  t.is(xxh2.xxh2Plus(1, 2), 3)
+})
+
 const Napi4Test = Number(process.versions.napi) >= 4 ? test : test.skip
 
 Napi4Test('call thread safe function', (t) => {
@@ -583,6 +1388,85 @@ Napi4Test('Promise should reject raw error in rust', async (t) => {
   t.is(err, fxError)
 })
 
+Napi4Test('readable stream', async (t) => {
+  const readable = readInChunks(Buffer.from('hello world'), 4)
+  t.true(readable instanceof Readable)
+  const chunks: string[] = []
+  for await (const chunk of readable) {
+    chunks.push(chunk.toString())
+  }
+  t.deepEqual(chunks, ['hell', 'o wo', 'rld'])
+  t.throws(() => readInChunks(Buffer.from('hello'), 0), {
+    message: 'size must be greater than 0',
+  })
+})
+
+Napi4Test('readable stream in object mode', async (t) => {
+  const values: number[] = []
+  for await (const value of countTo(3)) {
+    values.push(value)
+  }
+  t.deepEqual(values, [1, 2, 3])
+})
+
+Napi4Test('readable stream should be destroyed by the error', async (t) => {
+  const chunks: string[] = []
+  await t.throwsAsync(
+    async () => {
+      for await (const chunk of failAfterFirstChunk()) {
+        chunks.push(chunk.toString())
+      }
+    },
+    { message: 'broken stream' },
+  )
+  t.deepEqual(chunks, ['first'])
+})
+
+Napi4Test('async iterator', async (t) => {
+  const chunks: string[] = []
+  for await (const chunk of new ChunkedData(Buffer.from('hello world'), 4).read()) {
+    chunks.push(chunk.toString())
+  }
+  t.deepEqual(chunks, ['hell', 'o wo', 'rld'])
+  const words: string[] = []
+  for await (const word of iterateWords('a b  c')) {
+    words.push(word)
+  }
+  t.deepEqual(words, ['a', 'b', 'c'])
+})
+
+Napi4Test('async iterator pulls the items on demand', async (t) => {
+  const values: number[] = []
+  // the stream never ends
+  for await (const value of countFrom(1)) {
+    values.push(value)
+    if (values.length === 3) {
+      break
+    }
+  }
+  t.deepEqual(values, [1, 2, 3])
+  const iterator = countFrom(1)
+  t.deepEqual(await Promise.all([iterator.next(), iterator.next()]), [
+    { done: false, value: 1 },
+    { done: false, value: 2 },
+  ])
+  t.deepEqual(await iterator.return!(), { done: true, value: undefined })
+  t.deepEqual(await iterator.next(), { done: true, value: undefined })
+})
+
+Napi4Test('async iterator should throw the error', async (t) => {
+  const items: string[] = []
+  await t.throwsAsync(
+    async () => {
+      for await (const item of failAfterFirstItem()) {
+        items.push(item)
+      }
+    },
+    { message: 'broken stream' },
+  )
+  t.deepEqual(items, ['first'])
+})
+
 const Napi5Test = Number(process.versions.napi) >= 5 ? test : test.skip
 
 Napi5Test('Date test', (t) => {
@@ -3,7 +3,7 @@ import { Worker } from 'worker_threads'
 
 import test from 'ava'
 
-import { Animal, Kind, DEFAULT_COST } from '../index'
+import { Animal, Kind, DEFAULT_COST, countEnvCalls } from '../index'
 
 test('should be able to require in worker thread', async (t) => {
   await Promise.all(
@@ -25,3 +25,60 @@ test('should be able to require in worker thread', async (t) => {
     }),
   )
 })
+
+test('should init the state of every env', async (t) => {
+  countEnvCalls()
+  const calls = await Promise.all(
+    Array.from({ length: 4 }).map(() => {
+      const w = new Worker(
+        `require('worker_threads').parentPort.postMessage(require(${JSON.stringify(
+          join(__dirname, '..', 'index'),
+        )}).countEnvCalls())`,
+        { eval: true },
+      )
+      return new Promise<number>((resolve, reject) => {
+        w.on('message', resolve)
+        w.on('error', reject)
+      }).finally(() => w.terminate())
+    }),
+  )
+  t.deepEqual(calls, [1, 1, 1, 1])
+  t.true(countEnvCalls() > 1)
+})
+
+test('should evaluate the cached classes on every env', async (t) => {
+  for (let i = 0; i < 4; i++) {
+    const w = new Worker(
+      `require('worker_threads').parentPort.postMessage(require(${JSON.stringify(
+        join(__dirname, '..', 'index'),
+      )}).createSamples(2).get(1))`,
+      { eval: true },
+    )
+    const row = await new Promise((resolve, reject) => {
+      w.on('message', resolve)
+      w.on('error', reject)
+    }).finally(() => w.terminate())
+    t.deepEqual(row, { id: 1, value: 0.5, label: 'sample-1' })
+  }
+})
+
+test('should create the error classes on every env', async (t) => {
+  for (let i = 0; i < 4; i++) {
+    const w = new Worker(
+      `const binding = require(${JSON.stringify(join(__dirname, '..', 'index'))})
+      try {
+        binding.parseConfig('a = 1\\nb', [])
+      } catch (e) {
+        require('worker_threads').parentPort.postMessage(
+          e instanceof binding.ConfigParseError && e instanceof binding.ConfigError,
+        )
+      }`,
+      { eval: true },
+    )
+    const isClassError = await new Promise((resolve, reject) => {
+      w.on('message', resolve)
+      w.on('error', reject)
+    }).finally(() => w.terminate())
+    t.true(isClassError)
+  }
+})
@@ -11,52 +11,123 @@ export class ExternalObject<T> {
 }
 /** This is a const */
 export const DEFAULT_COST: number
-export function getWords(): Array<string>
-/** Gets some numbers */
-export function getNums(): Array<number>
-export function sumNums(nums: Array<number>): number
-export function toJsObj(): object
-export function readFileAsync(path: string): Promise<Buffer>
-export function asyncMultiTwo(arg: number): Promise<number>
-export function bigintAdd(a: bigint, b: bigint): bigint
-export function createBigInt(): bigint
-export function createBigIntI64(): bigint
-export function bigintGetU64AsString(bi: bigint): string
-export function getCwd(callback: (arg0: string) => void): void
-export function optionEnd(callback: (arg0: string, arg1?: string | undefined | null) => void): void
-export function optionStart(callback: (arg0: string | undefined | null, arg1: string) => void): void
-export function optionStartEnd(callback: (arg0: string | undefined | null, arg1: string, arg2?: string | undefined | null) => void): void
-export function optionOnly(callback: (arg0?: string | undefined | null) => void): void
-/** napi = { version = 2, features = ["serde-json"] } */
-export function readFile(callback: (arg0: Error | undefined, arg1?: string | undefined | null) => void): void
-export function returnJsFunction(): (...args: any[]) => any
-export function dateToNumber(input: Date): number
-export function chronoDateToMillis(input: Date): number
-export function chronoDateAdd1Minute(input: Date): Date
+/** This is a static */
+export const GREETING: string
+/** The `debug_validate_object` shape check of the objects and the thread and handle scope checks of the env are only in the debug builds */
+export const IS_DEBUG_BUILD: boolean
+export const MAX_PAGE_SIZE: number
+/** The squares of `0..10`, computed once when the module is loaded */
+export const SQUARES: Array<number>
+export interface AliasedStruct {
+  a: ALIAS
+  b: number
+}
+export interface AllOptionalObject {
+  name?: string | undefined | null
+  age?: number | undefined | null
+}
+/** The misspelled properties are rejected instead of being ignored */
+export interface AppConfig {
+  name: string
+  server: ServerConfig
+}
+export interface Coordinate {
+  x: number
+  y: number
+}
 export interface Dates {
   start: Date
   end?: Date | undefined | null
 }
-export function eitherStringOrNumber(input: string | number): number
-export function returnEither(input: number): string | number
-export function either3(input: string | number | boolean): number
+export interface HttpHeaders {
+  'content-type': string
+  'content-length'?: number | undefined | null
+  'x-request-id': string
+}
+export interface Line {
+  number: number
+  text: string
+}
+export interface NullableObject {
+  name: string | null
+  age: number
+}
 export interface Obj {
   v: string | number
 }
-export function either4(input: string | number | boolean | Obj): number
-export function receiveClassOrNumber(either: number | JsClassForEither): number
-export function receiveMutClassOrNumber(either: number | JsClassForEither): number
-/** default enum values are continuos i32s start from 0 */
-export const enum Kind {
-  /** Barks */
-  Dog = 0,
-  /** Kills birds */
-  Cat = 1,
-  /** Tasty */
-  Duck = 2
+/** This is an interface for package.json */
+export interface PackageJson {
+  name: string
+  /** The version of the package */
+  version: string
+  dependencies?: Record<string, any> | undefined | null
+  devDependencies?: Record<string, any> | undefined | null
 }
-export const enum Empty {
-  
+export interface Page<T> {
+  items: Array<T>
+  total: number
+}
+export interface Polyline {
+  name: string
+  points: Array<Coordinate>
+}
+/** Resolves the module specifiers, implemented by the JavaScript objects */
+export interface Resolver {
+  /** The path of `specifier`, relative to `from_dir` if it's passed */
+  resolve(specifier: string, fromDir?: string | undefined | null): string
+  onResolved(path: string): void
+}
+export interface Sample {
+  id: number
+  value: number
+  label: string
+}
+export interface SampleColumns {
+  readonly length: number
+  readonly columns: { id: Uint32Array; value: Float64Array; label: Array<string> }
+  get(index: number): Sample | undefined
+  toArray(): Array<Sample>
+  [Symbol.iterator](): Iterator<Sample>
+}
+export interface ServerConfig {
+  host: string
+  ports: Array<number>
+}
+export interface StrictObject {
+  name: string
+}
+export interface Token {
+  text: string
+  span: Span
+  /** `[line, column]` of the start */
+  position: [number, number]
+}
+export interface TsTypeChanged {
+  typeOverride: object
+  typeOverrideOptional?: object
+}
+/** The fields are named like the locals of the generated conversions */
+export type Setting =
+  | { type: 'Value'; val: string }
+  | { type: 'Nested'; obj: Array<string>; env?: number | undefined | null }
+/** Converted from and to `{ type: 'Circle', radius }` and so on */
+export type Shape =
+  | { type: 'Circle'; radius: number }
+  | { type: 'Rect'; width: number; height: number }
+  /** a polygon by its points, `[x0, y0, x1, y1, ...]` */
+  | { type: 'Polygon'; field0: Array<number> }
+  | { type: 'label'; content: string; fontSize?: number | undefined | null }
+  | { type: 'Empty' }
+/** `[start, end)` of the source text */
+export type Span =
+  [number, number]
+export const enum ALIAS {
+  A = 0,
+  B = 1
+}
+export const enum ContentKind {
+  PlainText = 'plain-text',
+  RichText = 'rich-text'
 }
 /** You could break the step and for an new continuous value. */
 export const enum CustomNumEnum {
@@ -69,100 +140,201 @@ export const enum CustomNumEnum {
   Nine = 9,
   Ten = 10
 }
-export function enumToI32(e: CustomNumEnum): number
-export function throwError(): void
+export const enum Empty {
+  
+}
+/** default enum values are continuos i32s start from 0 */
+export const enum Kind {
+  /** Barks */
+  Dog = 0,
+  /** Kills birds */
+  Cat = 1,
+  /** Tasty */
+  Duck = 2
+}
+/** The log levels of the config, mapped to the strings */
+export const enum LogLevel {
+  Debug = 'debug',
+  Info = 'info',
+  /** warnings and errors */
+  Warn = 'warn',
+  Error = 'fatal-error'
+}
+/** The `signal` is optional, it's rejected with `AbortError` if the `signal` is aborted */
+export function abortableMultiTwo(arg: number, signal?: AbortSignal | undefined | null): Promise<number>
+export function add(a: number, b: number): number
+export function allocatorStats(): { allocator: 'mimalloc' | 'jemalloc' | 'system'; allocated?: number; active?: number; resident?: number }
+export function appendBuffer(buf: Buffer): Buffer
+export function asyncMultiTwo(arg: number): Promise<number>
+export function asyncPlus100(p: Promise<number>): Promise<number>
+export function asyncReduceBuffer(buf: Buffer): Promise<number>
+export function bigintAdd(a: bigint, b: bigint): bigint
+export function bigintGetU64AsString(bi: bigint): string
+export function bufferPassThrough(buf: Buffer): Promise<Buffer>
+export function callOnObject(receiver: object, method: (arg0: string) => string | undefined | null): string
+export function callThreadsafeFunction(callback: (...args: any[]) => any): void
+export function chronoDateAdd1Minute(input: Date): Date
+export function chronoDateToMillis(input: Date): number
+export function concatLatin1(s: string): string
+export function concatStr(s: string): string
+export function concatUtf16(s: string): string
+export function contains(source: string, target: string): boolean
+export function convertU32Array(input: Uint32Array): Array<number>
+export function countEnvCalls(): number
+/** Never ends, the numbers are produced only when they are pulled */
+export function countFrom(start: number): AsyncIterableIterator<number>
+export function countOwnKeys(obj: object): number
+export function countPorts(config: AppConfig): number
+export function countTo(n: number): import('stream').Readable
+export function createBigInt(): bigint
+export function createBigIntI64(): bigint
+/** Only exported as the module level `createBird` */
+export function createBird(name: string): Bird
 export function createExternal(size: number): ExternalObject<number>
 export function createExternalString(content: string): ExternalObject<string>
+export function createExternalTypedArray(): Uint32Array
+export function createObj(): object
+export function createObjWithProperty(): { value: ArrayBuffer, get getter(): number }
+export function createSamples(count: number): SampleColumns
+export function createSymbol(): symbol
+export function dateToNumber(input: Date): number
+/** Exported as `delete_`, `delete` can't be declared by the JS binding */
+export function delete_(obj: object, key: string): boolean
+export function derefUint8Array(a: Uint8Array, b: Uint8ClampedArray): number
+/** `null` if the `value` is not a `Node` */
+export function describeNode(value: object): string | undefined | null
+export function distanceFromOrigin(this: Coordinate): number
+export function divide(a: number, b: number): number
+export function divideAsync(a: number, b: number): Promise<number>
+export function echoHttpHeaders(headers: HttpHeaders): HttpHeaders
+export function echoNullableObject(obj: NullableObject): NullableObject
+export function echoSetting(setting: Setting): Setting
+export function either3(input: string | number | boolean): number
+export function either4(input: string | number | boolean | Obj): number
+export function eitherStringOrNumber(input: string | number): number
+export function enumToI32(e: CustomNumEnum): number
+export function failAfterFirstChunk(): import('stream').Readable
+export function failAfterFirstItem(): AsyncIterableIterator<string>
+export function fetchWithProgress(url: string, method?: string | undefined | null, signal?: AbortSignal | undefined | null, onProgress?: (progress: { transferred: number, total: number }) => void): Promise<{ status: number; statusText: string; url: string; headers: Record<string, string>; body: Buffer }>
+export function fibonacci(n: number): number
+export function firstItem<T>(page: Page<T>): T | undefined | null
+export function fnReceivedAliased(s: AliasedStruct, e: ALIAS): void
+export function getBuffer(): Buffer
+export function getCwd(callback: (arg0: string) => void): void
 export function getExternal(external: ExternalObject<number>): number
+export function getGlobal(): typeof global
+export function getMapping(): Record<string, number>
+export function getNestedPort(config: object): number | undefined | null
+export function getNull(): JsNull
+/** Gets some numbers */
+export function getNums(): Array<number>
+export function getPackageJsonName(packageJson: PackageJson): string
+export function getStrFromObject(): void
+export function getUndefined(): void
+export function getWords(): Array<string>
+export function getterFromObj(): number
+export function greetWith(name: string, greeting: (arg0: string, arg1?: number | undefined | null) => string): string
+/** Exported as both `isAnimalName` and `Animal.isAnimalName` */
+export function isAnimalName(name: string): boolean
+export function isQuiet(level: LogLevel): boolean
+/** Panics if `index` is out of the bounds, which is thrown as the error */
+export function itemAt(items: Array<string>, index: number): string
+export function iterateWords(text: string): AsyncIterableIterator<string>
+export function joinWords(separator?: string | undefined | null, ...words: Array<string>): string
+/** Exported as both `Animal.kindOf` and `kindOf` */
+export function kindOf(name: string): Kind | undefined | null
+export function listObjKeys(obj: object): Array<string>
+export function mapOption(val?: number | undefined | null): number | undefined | null
+export function mapSum(values: Array<number>, map: (arg0: number, arg1: number) => number): number
+export function memoizedPolylineLength(polyline: Polyline): number
+/** `[min, max]` of the numbers, `null` if there is none */
+export function minMax(nums: Array<number>): [number, number] | undefined | null
 export function mutateExternal(external: ExternalObject<number>, newVal: number): void
+export function mutateTypedArray(input: Float32Array): void
+export function napiVersionAtInit(): number
+export function nextLogLevel(level: LogLevel): LogLevel
+export function nodeStart(node: Node): number
+export function optionEnd(callback: (arg0: string, arg1?: string | undefined | null) => void): void
+export function optionOnly(callback: (arg0?: string | undefined | null) => void): void
+export function optionStart(callback: (arg0: string | undefined | null, arg1: string) => void): void
+export function optionStartEnd(callback: (arg0: string | undefined | null, arg1: string, arg2?: string | undefined | null) => void): void
+export function overrideIndividualArgOnFunction(notOverridden: string, f: () => string, notOverridden2: number): string
+export function paginate<T>(items: Array<T>, size: number): Array<Page<T>>
+export function parseConfig(source: string, required: Array<string>): Record<string, string>
+export function pluginName(this: object, prefix: string): string
+export function polylineLength(polyline: Polyline): number
+export function readConfig(path: string): Promise<Record<string, string>>
+/** napi = { version = 2, features = ["serde-json"] } */
+export function readFile(callback: (arg0: Error | undefined, arg1?: string | undefined | null) => void): void
+export function readFileAsync(path: string): Promise<Buffer>
+export function readFileWithProgress(path: string, signal?: AbortSignal | undefined | null, onProgress?: (progress: { transferred: number, total: number }) => void): Promise<Buffer>
+export function readInChunks(data: Buffer, size: number): import('stream').Readable
+export function readPackageJson(): PackageJson
+export function receiveAllOptionalObject(obj?: AllOptionalObject | undefined | null): void
+export function receiveClassOrNumber(either: number | JsClassForEither): number
+export function receiveMutClassOrNumber(either: number | JsClassForEither): number
+export function receiveStrictObject(strictObject: StrictObject): void
+/**
+ * @param times - `2` if it's `undefined` or `null`
+ * @param separator - has a default if it's `undefined` or `null`
+ */
+export function repeatWithDefault(s: string, times?: number | undefined | null, separator?: string | undefined | null): string
+/** The dir of `repo`, which is kept alive until the `Promise` settled */
+export function repoDir(repo: JsRepo): Promise<string>
+export function resolveSpecifierOffThread(resolver: Resolver, specifier: string): Promise<string>
+export function resolveSpecifiers(resolver: Resolver, specifiers: Array<string>): Array<string>
+export function returnEither(input: number): string | number
+export function returnJsFunction(): (...args: any[]) => any
+export function returnNull(): null
+export function returnUndefined(): void
+export function roundtripStr(s: string): string
+export function scaleShape(shape: Shape, factor: number): Shape
+export function setNestedPort(config: object, port: number): object
+export function setSymbolInObj(symbol: symbol): object
+export function shapeArea(shape: Shape): number
+export function spanLen(span: Span): number
+export function sumAll(base: number, ...rest: Array<number>): number
+export function sumInBlockingPool(a: number, b: number): Promise<number>
+export function sumInChunks(n: number): Promise<number>
+export function sumInTokio(a: number, b: number): Promise<number>
+export function sumMapping(nums: Record<string, number>): number
+export function sumNums(nums: Array<number>): number
+export function swapPair(pair: [number, string]): [string, number]
+export function testSerdeRoundtrip(data: any): any
+export function threadsafeFunctionFatalMode(cb: (...args: any[]) => any): void
+export function threadsafeFunctionFatalModeError(cb: (...args: any[]) => any): void
+export function threadsafeFunctionThrowError(cb: (...args: any[]) => any): void
+export function throwError(): void
+export function toJsObj(): object
+/** The words separated by the spaces */
+export function tokenize(source: string): Array<Token>
+export function tsRename(a: { foo: number }): string[]
+export function useEnvOnOtherThread(): void
+export function useHandleOutOfScope(): void
 export function validateArray(arr: Array<number>): number
-export function validateBuffer(b: Buffer): number
-export function validateTypedArray(input: Uint8Array): number
 export function validateBigint(input: bigint): bigint
 export function validateBoolean(i: boolean): boolean
+export function validateBuffer(b: Buffer): number
 export function validateDate(d: Date): number
 export function validateDateTime(d: Date): number
 export function validateExternal(e: ExternalObject<number>): number
 export function validateFunction(cb: () => number): number
 export function validateHashMap(input: Record<string, number>): number
 export function validateNull(i: null): boolean
-export function validateUndefined(i: undefined): boolean
 export function validateNumber(i: number): number
 export function validatePromise(p: Promise<number>): Promise<number>
 export function validateString(s: string): string
 export function validateSymbol(s: symbol): boolean
-export function tsRename(a: { foo: number }): string[]
-export function xxh64Alias(input: Buffer): bigint
-export function getMapping(): Record<string, number>
-export function sumMapping(nums: Record<string, number>): number
-export function mapOption(val?: number | undefined | null): number | undefined | null
-export function returnNull(): null
-export function returnUndefined(): void
-export function add(a: number, b: number): number
-export function fibonacci(n: number): number
-export function listObjKeys(obj: object): Array<string>
-export function createObj(): object
-export function getGlobal(): typeof global
-export function getUndefined(): void
-export function getNull(): JsNull
-export interface AllOptionalObject {
-  name?: string | undefined | null
-  age?: number | undefined | null
-}
-export function receiveAllOptionalObject(obj?: AllOptionalObject | undefined | null): void
-export const enum ALIAS {
-  A = 0,
-  B = 1
-}
-export interface AliasedStruct {
-  a: ALIAS
-  b: number
-}
-export function fnReceivedAliased(s: AliasedStruct, e: ALIAS): void
-export interface StrictObject {
-  name: string
-}
-export function receiveStrictObject(strictObject: StrictObject): void
-export function getStrFromObject(): void
-export interface TsTypeChanged {
-  typeOverride: object
-  typeOverrideOptional?: object
-}
-export function createObjWithProperty(): { value: ArrayBuffer, get getter(): number }
-export function getterFromObj(): number
-export function asyncPlus100(p: Promise<number>): Promise<number>
-/** This is an interface for package.json */
-export interface PackageJson {
-  name: string
-  /** The version of the package */
-  version: string
-  dependencies?: Record<string, any> | undefined | null
-  devDependencies?: Record<string, any> | undefined | null
-}
-export function readPackageJson(): PackageJson
-export function getPackageJsonName(packageJson: PackageJson): string
-export function testSerdeRoundtrip(data: any): any
-export function contains(source: string, target: string): boolean
-export function concatStr(s: string): string
-export function concatUtf16(s: string): string
-export function concatLatin1(s: string): string
-export function roundtripStr(s: string): string
-export function setSymbolInObj(symbol: symbol): object
-export function createSymbol(): symbol
-export function withoutAbortController(a: number, b: number): Promise<number>
+export function validateTypedArray(input: Uint8Array): number
+export function validateUndefined(i: undefined): boolean
+/** Never resolved, it's rejected with `AbortError` once the `signal` is aborted */
+export function waitForAbort(signal: AbortSignal): Promise<boolean>
 export function withAbortController(a: number, b: number, signal: AbortSignal): Promise<number>
-export function callThreadsafeFunction(callback: (...args: any[]) => any): void
-export function threadsafeFunctionThrowError(cb: (...args: any[]) => any): void
-export function threadsafeFunctionFatalMode(cb: (...args: any[]) => any): void
-export function threadsafeFunctionFatalModeError(cb: (...args: any[]) => any): void
-export function getBuffer(): Buffer
-export function appendBuffer(buf: Buffer): Buffer
-export function convertU32Array(input: Uint32Array): Array<number>
-export function createExternalTypedArray(): Uint32Array
-export function mutateTypedArray(input: Float32Array): void
-export function derefUint8Array(a: Uint8Array, b: Uint8ClampedArray): number
-export function bufferPassThrough(buf: Buffer): Promise<Buffer>
-export function asyncReduceBuffer(buf: Buffer): Promise<number>
+export function withoutAbortController(a: number, b: number): Promise<number>
+/** Start writing after `ready` is resolved, so the `AbortSignal` could be aborted while the write is pending */
+export function writeFileWhenReady(path: string, data: Buffer, ready: Promise<void>, signal?: AbortSignal | undefined | null): Promise<undefined>
+export function writeFileWithProgress(path: string, data: Buffer, signal?: AbortSignal | undefined | null, onProgress?: (progress: { transferred: number, total: number }) => void): Promise<undefined>
+export function xxh64Alias(input: Buffer): bigint
 /**
  * `constructor` option for `struct` requires all fields to be public,
  * otherwise tag impl fn as constructor
@@ -183,8 +355,24 @@ export class Animal {
    * with an emoji 🚀
    */
   whoami(): string
+  /** Greet the animal by the name, also available as `greetAsync` */
+  static greet(greeting: string, name: string): string
+  /** Greet the animal by the name, also available as `greetAsync` */
+  static greetAsync(greeting: string, name: string): Promise<string>
+  /** Rename the animal by the branded name, which is a plain string in the Rust side */
+  rename(name: string & { readonly __brand: 'AnimalName' }): string
+  /**
+   * The name repeated `times` times,
+   * separated by the spaces
+   * @param times - `2` if it's `undefined` or `null`
+   */
+  repeatName(times?: number | undefined | null): string
+  /** Uppercase the name in the tokio runtime */
+  static upperNameInTokio(name: string): Promise<string>
   /** This is static... */
   static getDogKind(): Kind
+  /** Exported as both `Animal.kindOf` and `kindOf` */
+  static kindOf(name: string): Kind | undefined | null
   /**
    * Here are some characters and character sequences
    * that should be escaped correctly:
@@ -192,10 +380,23 @@ export class Animal {
    */
   returnOtherClass(): Dog
   returnOtherClassWithCustomConstructor(): Bird
+  /** Exported as both `isAnimalName` and `Animal.isAnimalName` */
+  static isAnimalName(name: string): boolean
 }
-export class Dog {
+export class AnimalWithDefaultConstructor {
   name: string
-  constructor(name: string)
+  kind: number
+  constructor(name: string, kind: number)
+}
+export type JsAsset = Asset
+export class Asset {
+  constructor()
+  get filePath(): number
+}
+export type JsAssets = Assets
+export class Assets {
+  constructor()
+  get(id: number): JsAsset | undefined | null
 }
 export class Bird {
   name: string
@@ -209,16 +410,73 @@ export class Blake2BHasher {
 }
 export type Blake2bKey = Blake2BKey
 export class Blake2BKey { }
+/** The data read by `read()` in chunks of `size` bytes */
+export class ChunkedData {
+  constructor(data: Buffer, size: number)
+  read(): AsyncIterableIterator<Buffer>
+}
+export class ClassWithFactory {
+  name: string
+  static withName(name: string): ClassWithFactory
+  static parse(name: string): ClassWithFactory
+  static load(name: string): Promise<ClassWithFactory>
+  setName(name: string): this
+}
+/** The errors of the config files */
+export class ConfigError extends Error {
+  readonly code: 'PARSE' | 'ENOKEY' | 'IO'
+}
+export class ConfigIoError extends ConfigError {
+  readonly code: 'IO'
+}
+export class ConfigMissingKeyError extends ConfigError {
+  readonly code: 'ENOKEY'
+  key: string
+}
+/** The line is not `key = value` */
+export class ConfigParseError extends ConfigError {
+  readonly code: 'PARSE'
+  line: number
+  column?: number | undefined | null
+}
 export class Context {
   maybeNeed?: boolean | undefined | null
   constructor()
   static withData(data: string): Context
   method(): string
 }
-export class AnimalWithDefaultConstructor {
+export class Dog {
   name: string
-  kind: number
-  constructor(name: string, kind: number)
+  constructor(name: string)
+}
+/** The Fibonacci numbers not greater than `max` */
+export class Fib {
+  constructor(max: number)
+  next(): IteratorResult<number, void>
+  [Symbol.iterator](): Iterator<number, void>
+}
+export class Identifier extends Node {
+  name: string
+  constructor(name: string, start: number)
+  /** Overrides `Node.prototype.describe` */
+  describe(): string
+}
+export class JsClassForEither {
+  constructor()
+}
+export class JsRemote {
+  name(): string
+}
+export class JsRepo {
+  constructor(dir: string)
+  dir(): Promise<string>
+  remote(): JsRemote
+}
+/** The lines of a text with the line numbers */
+export class Lines {
+  static split(text: string): Lines
+  next(): IteratorResult<Line, void>
+  [Symbol.iterator](): Iterator<Line, void>
 }
 export class NinjaTurtle {
   name: string
@@ -227,15 +485,12 @@ export class NinjaTurtle {
   getMaskColor(): string
   getName(): string
 }
-export type JsAssets = Assets
-export class Assets {
-  constructor()
-  get(id: number): JsAsset | undefined | null
-}
-export type JsAsset = Asset
-export class Asset {
-  constructor()
-  get filePath(): number
+/** The base class of the syntax nodes */
+export class Node {
+  constructor(kind: string, start: number)
+  get kind(): string
+  describe(): string
+  shift(offset: number): void
 }
 export class Optional {
   static optionEnd(required: string, optional?: string | undefined | null): string
@@ -243,26 +498,40 @@ export class Optional {
   static optionStartEnd(optional1: string | undefined | null, required: string, optional2?: string | undefined | null): string
   static optionOnly(optional?: string | undefined | null): string
 }
-export class ClassWithFactory {
-  name: string
-  static withName(name: string): ClassWithFactory
-  setName(name: string): this
+/** A value object, the fields of it can't be reassigned or redefined */
+export class Point {
+  readonly x: number
+  readonly y: number
+  constructor(x: number, y: number)
+  get length(): number
 }
-export class JsClassForEither {
+/** Keeps the `JsRepo`s alive until they are removed */
+export class RepoRegistry {
   constructor()
+  add(repo: JsRepo): void
+  get(index: number): JsRepo | undefined | null
+  removeLast(): JsRepo | undefined | null
+  /** The last removed repo, `null` if it's garbage collected */
+  lastRemoved(): JsRepo | undefined | null
 }
-export class JsRepo {
-  constructor(dir: string)
-  remote(): JsRemote
+/** Printed as `1.2.3`, serialized as `{ major, minor, patch }` */
+export class Version {
+  toString(): string
+  toJSON(): unknown
+  static parse(version: string): Version
 }
-export class JsRemote {
-  name(): string
+export namespace xxh2 {
+  export function xxh2Plus(a: number, b: number): number
+  export function xxh3Xxh64Alias(input: Buffer): bigint
+  export namespace legacy {
+    export function xxh2Minus(a: number, b: number): number
+  }
 }
 export namespace xxh3 {
   export const ALIGNMENT: number
-  export function xxh3_64(input: Buffer): bigint
   /** xxh128 function */
   export function xxh128(input: Buffer): bigint
+  export function xxh3_64(input: Buffer): bigint
   /** Xxh3 class */
   export class Xxh3 {
     constructor()
@@ -270,8 +539,11 @@ export namespace xxh3 {
     update(input: Buffer): void
     digest(): bigint
   }
-}
-export namespace xxh2 {
-  export function xxh2Plus(a: number, b: number): number
-  export function xxh3Xxh64Alias(input: Buffer): bigint
+  export namespace streaming {
+    export const enum Seed {
+      Zero = 0,
+      Default = 1
+    }
+    export function seedValue(seed: Seed): number
+  }
 }
@@ -0,0 +1,7 @@
+use napi::allocator::AllocatorStats;
+use napi::bindgen_prelude::*;
+
+#[napi]
+pub fn allocator_stats(mut env: Env) -> Result<AllocatorStats> {
+  napi::allocator::report_external_memory(&mut env)
+}
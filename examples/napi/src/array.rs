@@ -16,6 +16,16 @@ fn sum_nums(nums: Vec<u32>) -> u32 {
   nums.iter().sum()
 }
 
+#[napi(strict)]
+fn sum_all(base: u32, #[napi(variadic)] rest: Vec<u32>) -> u32 {
+  base + rest.iter().sum::<u32>()
+}
+
+#[napi]
+fn join_words(separator: Option<String>, #[napi(variadic)] words: Vec<String>) -> String {
+  words.join(separator.as_deref().unwrap_or(" "))
+}
+
 #[napi]
 fn to_js_obj(env: Env) -> napi::Result<JsObject> {
   let mut arr = env.create_array(0)?;
@@ -21,3 +21,26 @@ async fn async_multi_two(arg: u32) -> Result<u32> {
     .await
     .unwrap()
 }
+
+#[napi(execution = "tokio")]
+fn sum_in_tokio(a: u32, b: u32) -> Result<u32> {
+  a.checked_add(b)
+    .ok_or_else(|| Error::new(Status::InvalidArg, "Overflow".to_owned()))
+}
+
+/// Never resolved, it's rejected with `AbortError` once the `signal` is aborted
+#[napi]
+async fn wait_for_abort(signal: AbortSignal) -> Result<bool> {
+  future::pending::<()>().await;
+  Ok(signal.aborted())
+}
+
+/// The `signal` is optional, it's rejected with `AbortError` if the `signal` is aborted
+#[napi]
+async fn abortable_multi_two(arg: u32, signal: Option<AbortSignal>) -> Result<u32> {
+  tokio::task::yield_now().await;
+  if let Some(signal) = signal {
+    signal.handle().check()?;
+  }
+  Ok(arg * 2)
+}
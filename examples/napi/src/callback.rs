@@ -37,6 +37,31 @@ fn read_file<T: Fn(Result<()>, Option<String>) -> Result<()>>(callback: T) {
   .unwrap();
 }
 
+#[napi]
+fn map_sum(values: Vec<u32>, map: Function<(u32, u32), u32>) -> Result<u32> {
+  values
+    .into_iter()
+    .enumerate()
+    .try_fold(0, |sum, (i, value)| Ok(sum + map.call((value, i as u32))?))
+}
+
+#[napi]
+fn call_on_object(receiver: Object, method: Function<(String,), Option<String>>) -> Result<String> {
+  Ok(
+    method
+      .apply(receiver, ("world".to_owned(),))?
+      .unwrap_or_default(),
+  )
+}
+
+#[napi]
+fn greet_with(
+  name: String,
+  greeting: impl Fn(String, Option<u32>) -> Result<String>,
+) -> Result<String> {
+  greeting(name, None)
+}
+
 fn read_file_content() -> Result<String> {
   // serde_json::from_str(&s)?;
   Ok("hello world".to_string())
@@ -1,4 +1,7 @@
-use napi::{bindgen_prelude::Buffer, Result};
+use napi::{
+  bindgen_prelude::{Buffer, Object},
+  Result,
+};
 
 use crate::r#enum::Kind;
 
@@ -55,12 +58,53 @@ impl Animal {
     }
   }
 
+  /// Greet the animal by the name, also available as `greetAsync`
+  #[napi(also_async = "greetAsync")]
+  pub fn greet(greeting: String, name: String) -> String {
+    format!("{}, {}", greeting, name)
+  }
+
+  /// Rename the animal by the branded name, which is a plain string in the Rust side
+  #[napi]
+  pub fn rename(
+    &mut self,
+    #[napi(ts_arg_type = "string & { readonly __brand: 'AnimalName' }")] name: String,
+  ) -> String {
+    std::mem::replace(&mut self.name, name)
+  }
+
+  /**
+   * The name repeated `times` times,
+   * separated by the spaces
+   */
+  #[napi]
+  pub fn repeat_name(&self, #[napi(default = "2")] times: u32) -> String {
+    vec![self.name.as_str(); times as usize].join(" ")
+  }
+
+  /// Uppercase the name in the tokio runtime
+  #[napi(execution = "tokio")]
+  pub fn upper_name_in_tokio(name: String) -> String {
+    name.to_uppercase()
+  }
+
   #[napi]
   /// This is static...
   pub fn get_dog_kind() -> Kind {
     Kind::Dog
   }
 
+  /// Exported as both `Animal.kindOf` and `kindOf`
+  #[napi(module_function)]
+  pub fn kind_of(name: String) -> Option<Kind> {
+    match name.as_str() {
+      "dog" => Some(Kind::Dog),
+      "cat" => Some(Kind::Cat),
+      "duck" => Some(Kind::Duck),
+      _ => None,
+    }
+  }
+
   #[napi]
   /// Here are some characters and character sequences
   /// that should be escaped correctly:
@@ -77,6 +121,12 @@ impl Animal {
   }
 }
 
+/// Exported as both `isAnimalName` and `Animal.isAnimalName`
+#[napi(static_method = Animal)]
+pub fn is_animal_name(name: String) -> bool {
+  matches!(name.as_str(), "dog" | "cat" | "duck")
+}
+
 #[napi(constructor)]
 pub struct Dog {
   pub name: String,
@@ -98,6 +148,12 @@ impl Bird {
   pub fn get_count(&self) -> u32 {
     1234
   }
+
+  /// Only exported as the module level `createBird`
+  #[napi(module_function = only)]
+  pub fn create_bird(name: String) -> Bird {
+    Bird::new(name)
+  }
 }
 
 /// Smoking test for type generation
@@ -283,3 +339,118 @@ impl Optional {
     }
   }
 }
+
+/// The base class of the syntax nodes
+#[napi]
+pub struct Node {
+  kind: String,
+  start: u32,
+}
+
+#[napi]
+impl Node {
+  #[napi(constructor)]
+  pub fn new(kind: String, start: u32) -> Self {
+    Node { kind, start }
+  }
+
+  #[napi(getter)]
+  pub fn kind(&self) -> String {
+    self.kind.clone()
+  }
+
+  #[napi]
+  pub fn describe(&self) -> String {
+    format!("{}@{}", self.kind, self.start)
+  }
+
+  #[napi]
+  pub fn shift(&mut self, offset: u32) {
+    self.start += offset;
+  }
+}
+
+#[napi(extends = Node)]
+pub struct Identifier {
+  node: Node,
+  pub name: String,
+}
+
+#[napi]
+impl Identifier {
+  #[napi(constructor)]
+  pub fn new(name: String, start: u32) -> Self {
+    Identifier {
+      node: Node::new("Identifier".to_owned(), start),
+      name,
+    }
+  }
+
+  /// Overrides `Node.prototype.describe`
+  #[napi]
+  pub fn describe(&self) -> String {
+    format!("{} {}", self.node.describe(), self.name)
+  }
+}
+
+#[napi]
+pub fn node_start(node: &Node) -> u32 {
+  node.start
+}
+
+/// `null` if the `value` is not a `Node`
+#[napi]
+pub fn describe_node(value: Object) -> Result<Option<String>> {
+  Ok(value.downcast::<Node>()?.map(|node| node.describe()))
+}
+
+/// A value object, the fields of it can't be reassigned or redefined
+#[napi(constructor)]
+pub struct Point {
+  #[napi(writable = false, enumerable, configurable = false)]
+  pub x: f64,
+  #[napi(writable = false, enumerable)]
+  pub y: f64,
+}
+
+#[napi]
+impl Point {
+  #[napi(getter, enumerable)]
+  pub fn length(&self) -> f64 {
+    (self.x * self.x + self.y * self.y).sqrt()
+  }
+}
+
+/// Printed as `1.2.3`, serialized as `{ major, minor, patch }`
+#[napi(to_string, to_json, inspect)]
+#[derive(Debug, Serialize)]
+pub struct Version {
+  major: u32,
+  minor: u32,
+  patch: u32,
+}
+
+#[napi]
+impl Version {
+  #[napi(factory)]
+  pub fn parse(version: String) -> Result<Self> {
+    let mut parts = version.split('.').map(|part| part.parse::<u32>());
+    match (parts.next(), parts.next(), parts.next(), parts.next()) {
+      (Some(Ok(major)), Some(Ok(minor)), Some(Ok(patch)), None) => Ok(Version {
+        major,
+        minor,
+        patch,
+      }),
+      _ => Err(napi::Error::from_reason(format!(
+        "Invalid version `{}`",
+        version
+      ))),
+    }
+  }
+}
+
+impl std::fmt::Display for Version {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+  }
+}
@@ -1,3 +1,5 @@
+use napi::{tokio, Error, Result, Status};
+
 #[napi]
 pub struct ClassWithFactory {
   pub name: String,
@@ -10,6 +12,23 @@ impl ClassWithFactory {
     Self { name }
   }
 
+  #[napi(factory)]
+  pub fn parse(name: String) -> Result<Self> {
+    if name.is_empty() {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "name can't be empty".to_owned(),
+      ));
+    }
+    Ok(Self { name })
+  }
+
+  #[napi(factory)]
+  pub async fn load(name: String) -> Result<Self> {
+    tokio::task::yield_now().await;
+    Self::parse(name)
+  }
+
   #[napi]
   pub fn set_name(&mut self, name: String) -> &Self {
     self.name = name;
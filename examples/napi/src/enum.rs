@@ -1,5 +1,3 @@
-use napi::bindgen_prelude::*;
-
 /// default enum values are continuos i32s start from 0
 #[napi]
 pub enum Kind {
@@ -38,3 +36,104 @@ pub enum SkippedEnums {
   Two,
   Tree,
 }
+
+/// The log levels of the config, mapped to the strings
+#[napi(string_enum = lowercase)]
+pub enum LogLevel {
+  Debug,
+  Info,
+  /// warnings and errors
+  Warn,
+  #[napi(value = "fatal-error")]
+  Error,
+}
+
+#[napi]
+fn is_quiet(level: LogLevel) -> bool {
+  matches!(level, LogLevel::Warn | LogLevel::Error)
+}
+
+#[napi]
+fn next_log_level(level: LogLevel) -> LogLevel {
+  match level {
+    LogLevel::Debug => LogLevel::Info,
+    LogLevel::Info => LogLevel::Warn,
+    LogLevel::Warn | LogLevel::Error => LogLevel::Error,
+  }
+}
+
+#[napi(string_enum = "kebab-case")]
+pub enum ContentKind {
+  PlainText,
+  RichText,
+}
+
+/// Converted from and to `{ type: 'Circle', radius }` and so on
+#[napi]
+pub enum Shape {
+  Circle {
+    radius: f64,
+  },
+  Rect {
+    width: f64,
+    height: f64,
+  },
+  /// a polygon by its points, `[x0, y0, x1, y1, ...]`
+  Polygon(Vec<f64>),
+  #[napi(value = "label")]
+  Text {
+    content: String,
+    font_size: Option<u32>,
+  },
+  Empty,
+}
+
+#[napi]
+fn shape_area(shape: Shape) -> f64 {
+  match shape {
+    Shape::Circle { radius } => std::f64::consts::PI * radius * radius,
+    Shape::Rect { width, height } => width * height,
+    Shape::Polygon(points) => {
+      let len = points.len() / 2;
+      let area: f64 = (0..len)
+        .map(|i| {
+          let j = (i + 1) % len;
+          points[i * 2] * points[j * 2 + 1] - points[j * 2] * points[i * 2 + 1]
+        })
+        .sum();
+      area.abs() / 2.0
+    }
+    Shape::Text { .. } | Shape::Empty => 0.0,
+  }
+}
+
+#[napi]
+fn scale_shape(shape: Shape, factor: f64) -> Shape {
+  match shape {
+    Shape::Circle { radius } => Shape::Circle {
+      radius: radius * factor,
+    },
+    Shape::Rect { width, height } => Shape::Rect {
+      width: width * factor,
+      height: height * factor,
+    },
+    Shape::Polygon(points) => Shape::Polygon(points.into_iter().map(|p| p * factor).collect()),
+    Shape::Text { content, font_size } => Shape::Text {
+      content,
+      font_size: font_size.map(|size| (size as f64 * factor) as u32),
+    },
+    Shape::Empty => Shape::Empty,
+  }
+}
+
+/// The fields are named like the locals of the generated conversions
+#[napi]
+pub enum Setting {
+  Value { val: String },
+  Nested { obj: Vec<String>, env: Option<u32> },
+}
+
+#[napi]
+fn echo_setting(setting: Setting) -> Setting {
+  setting
+}
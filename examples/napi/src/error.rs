@@ -1,6 +1,80 @@
+use std::collections::HashMap;
+use std::fmt;
+
 use napi::bindgen_prelude::*;
+use napi::tokio::fs;
 
 #[napi]
 fn throw_error() -> Result<()> {
   Err(Error::new(Status::InvalidArg, "Manual Error".to_owned()))
 }
+
+/// Panics if `index` is out of the bounds, which is thrown as the error
+#[napi(catch_unwind)]
+fn item_at(items: Vec<String>, index: u32) -> String {
+  items[index as usize].clone()
+}
+
+/// The errors of the config files
+#[napi(error)]
+#[derive(Debug)]
+pub enum ConfigError {
+  /// The line is not `key = value`
+  Parse {
+    line: u32,
+    column: Option<u32>,
+  },
+  #[napi(value = "ENOKEY")]
+  MissingKey {
+    key: String,
+  },
+  Io(#[napi(skip)] std::io::Error),
+}
+
+impl fmt::Display for ConfigError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      ConfigError::Parse { line, .. } => write!(f, "Invalid line {}", line),
+      ConfigError::MissingKey { key } => write!(f, "Missing key `{}`", key),
+      ConfigError::Io(err) => write!(f, "Failed to read the config, {}", err),
+    }
+  }
+}
+
+fn parse_lines(
+  source: &str,
+  required: &[String],
+) -> std::result::Result<HashMap<String, String>, ConfigError> {
+  let mut config = HashMap::new();
+  for (i, line) in source.lines().enumerate() {
+    if line.trim().is_empty() {
+      continue;
+    }
+    let (key, value) = line.split_once('=').ok_or(ConfigError::Parse {
+      line: i as u32 + 1,
+      column: None,
+    })?;
+    if key.trim().is_empty() {
+      return Err(ConfigError::Parse {
+        line: i as u32 + 1,
+        column: Some(1),
+      });
+    }
+    config.insert(key.trim().to_owned(), value.trim().to_owned());
+  }
+  match required.iter().find(|key| !config.contains_key(*key)) {
+    Some(key) => Err(ConfigError::MissingKey { key: key.clone() }),
+    None => Ok(config),
+  }
+}
+
+#[napi]
+fn parse_config(source: String, required: Vec<String>) -> Result<HashMap<String, String>> {
+  Ok(parse_lines(&source, &required)?)
+}
+
+#[napi]
+async fn read_config(path: String) -> Result<HashMap<String, String>> {
+  let source = fs::read_to_string(path).await.map_err(ConfigError::Io)?;
+  Ok(parse_lines(&source, &[])?)
+}
@@ -1,6 +1,18 @@
 use napi::bindgen_prelude::{Object, Result};
+use napi::JsFunction;
 
 #[napi(ts_args_type = "a: { foo: number }", ts_return_type = "string[]")]
 fn ts_rename(a: Object) -> Result<Object> {
   a.get_property_names()
 }
+
+#[napi]
+fn override_individual_arg_on_function(
+  not_overridden: String,
+  #[napi(ts_arg_type = "() => string")] f: JsFunction,
+  not_overridden2: u32,
+) -> Result<String> {
+  let u = f.call_without_args(None)?;
+  let s = u.coerce_to_string()?.into_utf8()?.into_owned()?;
+  Ok(format!("oia: {}-{}-{}", not_overridden, not_overridden2, s))
+}
@@ -0,0 +1,68 @@
+use napi::{
+  bindgen_prelude::*,
+  fs::{read_file, write_file, FsTask},
+  net::{fetch, FetchInit, FetchResponse},
+};
+
+#[napi(
+  ts_args_type = "path: string, signal?: AbortSignal | undefined | null, onProgress?: (progress: { transferred: number, total: number }) => void"
+)]
+pub fn read_file_with_progress(
+  path: String,
+  signal: Option<AbortSignal>,
+  on_progress: Option<JsFunction>,
+) -> FsTask<Buffer> {
+  read_file(path).with_signal(signal).on_progress(on_progress)
+}
+
+#[napi(
+  ts_args_type = "path: string, data: Buffer, signal?: AbortSignal | undefined | null, onProgress?: (progress: { transferred: number, total: number }) => void"
+)]
+pub fn write_file_with_progress(
+  path: String,
+  data: Buffer,
+  signal: Option<AbortSignal>,
+  on_progress: Option<JsFunction>,
+) -> FsTask<()> {
+  write_file(path, data.to_vec())
+    .with_signal(signal)
+    .on_progress(on_progress)
+}
+
+/// Start writing after `ready` is resolved, so the `AbortSignal` could be aborted while the write is pending
+#[napi(
+  ts_args_type = "path: string, data: Buffer, ready: Promise<void>, signal?: AbortSignal | undefined | null"
+)]
+pub fn write_file_when_ready(
+  path: String,
+  data: Buffer,
+  ready: Promise<()>,
+  signal: Option<AbortSignal>,
+) -> FsTask<()> {
+  let data = data.to_vec();
+  FsTask::new(move |ctx| async move {
+    ready.await?;
+    write_file(path, data).run(ctx).await
+  })
+  .with_signal(signal)
+}
+
+#[napi(
+  ts_args_type = "url: string, method?: string | undefined | null, signal?: AbortSignal | undefined | null, onProgress?: (progress: { transferred: number, total: number }) => void"
+)]
+pub fn fetch_with_progress(
+  url: String,
+  method: Option<String>,
+  signal: Option<AbortSignal>,
+  on_progress: Option<JsFunction>,
+) -> FsTask<FetchResponse> {
+  fetch(
+    url,
+    FetchInit {
+      method,
+      ..Default::default()
+    },
+  )
+  .with_signal(signal)
+  .on_progress(on_progress)
+}
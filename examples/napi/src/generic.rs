@@ -0,0 +1,26 @@
+use napi::bindgen_prelude::*;
+
+#[napi(object)]
+pub struct Page<T> {
+  pub items: Vec<T>,
+  pub total: u32,
+}
+
+#[napi]
+fn first_item<T: FromNapiValue + ToNapiValue>(page: Page<T>) -> Option<T> {
+  page.items.into_iter().next()
+}
+
+#[napi]
+fn paginate<T: FromNapiValue + ToNapiValue>(items: Vec<T>, size: u32) -> Vec<Page<T>> {
+  let total = items.len() as u32;
+  let mut pages = vec![];
+  let mut items = items.into_iter().peekable();
+  while items.peek().is_some() {
+    pages.push(Page {
+      items: items.by_ref().take(size.max(1) as usize).collect(),
+      total,
+    });
+  }
+  pages
+}
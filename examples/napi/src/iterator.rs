@@ -0,0 +1,76 @@
+/// The Fibonacci numbers not greater than `max`
+#[napi]
+pub struct Fib {
+  current: u32,
+  next: u32,
+  max: u32,
+}
+
+#[napi]
+impl Fib {
+  #[napi(constructor)]
+  pub fn new(max: u32) -> Self {
+    Fib {
+      current: 0,
+      next: 1,
+      max,
+    }
+  }
+}
+
+#[napi]
+impl Iterator for Fib {
+  type Item = u32;
+
+  fn next(&mut self) -> Option<u32> {
+    if self.current > self.max {
+      return None;
+    }
+    let current = self.current;
+    self.current = self.next;
+    self.next = self.next.saturating_add(current);
+    Some(current)
+  }
+}
+
+#[napi(object)]
+pub struct Line {
+  pub number: u32,
+  pub text: String,
+}
+
+/// The lines of a text with the line numbers
+#[napi]
+pub struct Lines {
+  lines: std::vec::IntoIter<String>,
+  number: u32,
+}
+
+#[napi]
+impl Lines {
+  #[napi(factory)]
+  pub fn split(text: String) -> Self {
+    Lines {
+      lines: text
+        .lines()
+        .map(|line| line.to_owned())
+        .collect::<Vec<_>>()
+        .into_iter(),
+      number: 0,
+    }
+  }
+}
+
+#[napi]
+impl Iterator for Lines {
+  type Item = Line;
+
+  fn next(&mut self) -> Option<Line> {
+    let text = self.lines.next()?;
+    self.number += 1;
+    Some(Line {
+      number: self.number,
+      text,
+    })
+  }
+}
@@ -58,6 +58,23 @@ mod xxh3 {
       self.inner.clone()
     }
   }
+
+  #[napi]
+  mod streaming {
+    #[napi]
+    pub enum Seed {
+      Zero,
+      Default,
+    }
+
+    #[napi]
+    pub fn seed_value(seed: Seed) -> u32 {
+      match seed {
+        Seed::Zero => 0,
+        Seed::Default => 1116,
+      }
+    }
+  }
 }
 
 #[napi]
@@ -81,3 +98,8 @@ use napi::bindgen_prelude::Buffer;
 pub fn xxh64_alias(input: Buffer) -> u64 {
   xxh3::xxh64(input)
 }
+
+#[napi(namespace = "xxh2.legacy")]
+pub fn xxh2_minus(a: u32, b: u32) -> u32 {
+  a - b
+}
@@ -13,6 +13,22 @@ pub const DEFAULT_COST: u32 = 12;
 #[napi(skip_typescript)]
 pub const TYPE_SKIPPED_CONST: u32 = 12;
 
+#[napi(js_name = "MAX_PAGE_SIZE")]
+pub const PAGE_SIZE_LIMIT: u32 = 1024;
+
+#[napi]
+/// This is a static
+pub static GREETING: &str = "Hello from static";
+
+/// The squares of `0..10`, computed once when the module is loaded
+#[napi(lazy)]
+pub static SQUARES: Vec<u32> = (0..10).map(|n| n * n).collect();
+
+/// The `debug_validate_object` shape check of the objects and the thread and handle scope checks of the env are only in the debug builds
+#[napi]
+pub const IS_DEBUG_BUILD: bool = cfg!(debug_assertions);
+
+mod allocator;
 mod array;
 mod r#async;
 mod bigint;
@@ -26,16 +42,23 @@ mod error;
 mod external;
 mod fn_strict;
 mod fn_ts_override;
+mod fs;
+mod generic;
+mod iterator;
 mod js_mod;
 mod map;
+mod module_init;
 mod nullable;
 mod number;
 mod object;
 mod promise;
 mod reference;
 mod serde;
+mod stream;
 mod string;
 mod symbol;
 mod task;
 mod threadsafe_function;
+mod r#trait;
+mod tuple;
 mod typed_array;
@@ -0,0 +1,33 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use napi::{Env, Error, JsObject, Result};
+
+static NAPI_VERSION: AtomicU32 = AtomicU32::new(0);
+
+/// The state of an env, a static would be shared by the main thread and all the Workers
+struct EnvState {
+  calls: u32,
+}
+
+#[napi(module_init)]
+fn init(env: Env, mut exports: JsObject) -> Result<()> {
+  NAPI_VERSION.store(env.get_napi_version()?, Ordering::Relaxed);
+  env.set_instance_data(EnvState { calls: 0 }, 0, |_| {})?;
+  // not declared in the d.ts, it's registered at runtime
+  exports.set_named_property("registeredByInit", env.create_string("module_init")?)?;
+  Ok(())
+}
+
+#[napi]
+pub fn napi_version_at_init() -> u32 {
+  NAPI_VERSION.load(Ordering::Relaxed)
+}
+
+#[napi]
+pub fn count_env_calls(env: Env) -> Result<u32> {
+  let state = env
+    .get_instance_data::<EnvState>()?
+    .ok_or_else(|| Error::from_reason("The env is not initialized".to_owned()))?;
+  state.calls += 1;
+  Ok(state.calls)
+}
@@ -12,3 +12,12 @@ fn return_null() -> Null {
 
 #[napi]
 fn return_undefined() -> Undefined {}
+
+#[napi(strict)]
+fn repeat_with_default(
+  s: String,
+  #[napi(default = "2")] times: u32,
+  #[napi(default = r#"", ".to_owned()"#)] separator: String,
+) -> String {
+  vec![s; times as usize].join(&separator)
+}
@@ -1,9 +1,13 @@
-#[napi]
+#[napi(bench(args = "1, 2"))]
 fn add(a: u32, b: u32) -> u32 {
   a + b
 }
 
-#[napi(strict)]
+#[napi(
+  strict,
+  bench(args = "10", name = "small"),
+  bench(args = "25", name = "large")
+)]
 fn fibonacci(n: u32) -> u32 {
   match n {
     1 | 2 => 1,
@@ -43,6 +43,30 @@ fn receive_all_optional_object(obj: Option<AllOptionalObject>) -> Result<()> {
   Ok(())
 }
 
+#[napi(object, use_nullable)]
+struct NullableObject {
+  pub name: Option<String>,
+  pub age: u32,
+}
+
+#[napi]
+fn echo_nullable_object(obj: NullableObject) -> NullableObject {
+  obj
+}
+
+#[napi(object, rename_all = "kebab-case")]
+struct HttpHeaders {
+  pub content_type: String,
+  pub content_length: Option<u32>,
+  #[napi(js_name = "x-request-id")]
+  pub request: String,
+}
+
+#[napi]
+fn echo_http_headers(headers: HttpHeaders) -> HttpHeaders {
+  headers
+}
+
 #[napi(js_name = "ALIAS")]
 pub enum AliasedEnum {
   A,
@@ -101,3 +125,131 @@ pub fn create_obj_with_property(env: Env) -> Result<JsObject> {
 fn getter_from_obj() -> u32 {
   42
 }
+
+#[napi(object, columnar)]
+pub struct Sample {
+  pub id: u32,
+  pub value: f64,
+  pub label: String,
+}
+
+#[napi]
+pub fn create_samples(count: u32) -> Columnar<Sample> {
+  (0..count)
+    .map(|id| Sample {
+      id,
+      value: id as f64 / 2.0,
+      label: format!("sample-{}", id),
+    })
+    .collect::<Vec<_>>()
+    .into()
+}
+
+#[napi(object)]
+pub struct Coordinate {
+  pub x: f64,
+  pub y: f64,
+}
+
+#[napi(object)]
+pub struct Polyline {
+  pub name: String,
+  pub points: Vec<Coordinate>,
+}
+
+#[napi]
+pub fn polyline_length(polyline: Polyline) -> f64 {
+  polyline
+    .points
+    .windows(2)
+    .map(|w| ((w[1].x - w[0].x).powi(2) + (w[1].y - w[0].y).powi(2)).sqrt())
+    .sum()
+}
+
+#[napi(object, deny_unknown_fields)]
+pub struct ServerConfig {
+  pub host: String,
+  pub ports: Vec<u32>,
+}
+
+/// The misspelled properties are rejected instead of being ignored
+#[napi(object, deny_unknown_fields)]
+pub struct AppConfig {
+  pub name: String,
+  pub server: ServerConfig,
+}
+
+#[napi]
+pub fn count_ports(config: AppConfig) -> u32 {
+  config.server.ports.len() as u32
+}
+
+#[napi]
+pub fn get_nested_port(config: Object) -> Result<Option<u32>> {
+  config.get_nested("server.http.port")
+}
+
+#[napi]
+pub fn set_nested_port(mut config: Object, port: u32) -> Result<Object> {
+  config.set_nested("server.http.port", port)?;
+  Ok(config)
+}
+
+#[napi]
+pub fn memoized_polyline_length(polyline: Memoized<Polyline>) -> f64 {
+  polyline_length(Polyline {
+    name: polyline.name.clone(),
+    points: polyline
+      .points
+      .iter()
+      .map(|p| Coordinate { x: p.x, y: p.y })
+      .collect(),
+  })
+}
+
+#[napi]
+pub fn count_own_keys(obj: JsObject) -> Result<u32> {
+  obj.with_raw(|env, raw| {
+    let mut names = std::ptr::null_mut();
+    check_status!(unsafe { sys::napi_get_property_names(env, raw, &mut names) })?;
+    let mut len = 0;
+    check_status!(unsafe { sys::napi_get_array_length(env, names, &mut len) })?;
+    Ok(len)
+  })
+}
+
+#[napi]
+pub fn use_env_on_other_thread(env: Env) -> Result<()> {
+  let raw_env = env.raw() as usize;
+  std::thread::spawn(move || {
+    let env = unsafe { Env::from_raw(raw_env as sys::napi_env) };
+    // only validated in the debug builds, nothing is called with the env
+    env.with_raw(|_| Ok(()))
+  })
+  .join()
+  .map_err(|_| Error::from_reason("The thread is panicked".to_owned()))?
+}
+
+#[napi]
+pub fn use_handle_out_of_scope(env: Env) -> Result<()> {
+  let obj = env.run_in_scope(|| env.create_object())?;
+  // only validated in the debug builds, nothing is called with the handle
+  obj.with_raw(|_, _| Ok(()))
+}
+
+/// Exported as `delete_`, `delete` can't be declared by the JS binding
+#[napi]
+pub fn delete(mut obj: JsObject, key: String) -> Result<bool> {
+  obj.delete_named_property(&key)
+}
+
+#[napi]
+fn plugin_name(this: This, prefix: String) -> Result<String> {
+  let name: Option<String> = this.get("name")?;
+  Ok(format!("{}{}", prefix, name.unwrap_or_default()))
+}
+
+#[napi]
+fn distance_from_origin(this: This<Coordinate>) -> f64 {
+  this.x.hypot(this.y)
+}
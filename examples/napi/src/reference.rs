@@ -34,6 +34,11 @@ impl JsRepo {
     }
   }
 
+  #[napi]
+  pub async fn dir(&self) -> String {
+    self.inner.dir.clone()
+  }
+
   #[napi]
   pub fn remote(&self, reference: Reference<JsRepo>, env: Env) -> Result<JsRemote> {
     Ok(JsRemote {
@@ -54,3 +59,56 @@ impl JsRemote {
     self.inner.name()
   }
 }
+
+/// Keeps the `JsRepo`s alive until they are removed
+#[napi]
+#[derive(Default)]
+pub struct RepoRegistry {
+  repos: Vec<Reference<JsRepo>>,
+  last_removed: Option<WeakReference<JsRepo>>,
+}
+
+#[napi]
+impl RepoRegistry {
+  #[napi(constructor)]
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  #[napi]
+  pub fn add(&mut self, repo: Reference<JsRepo>) {
+    self.repos.push(repo);
+  }
+
+  #[napi]
+  pub fn get(&self, index: u32, env: Env) -> Result<Option<Reference<JsRepo>>> {
+    self
+      .repos
+      .get(index as usize)
+      .map(|repo| repo.clone(env))
+      .transpose()
+  }
+
+  #[napi]
+  pub fn remove_last(&mut self) -> Option<Reference<JsRepo>> {
+    let repo = self.repos.pop()?;
+    self.last_removed = Some(repo.downgrade());
+    Some(repo)
+  }
+
+  /// The last removed repo, `null` if it's garbage collected
+  #[napi]
+  pub fn last_removed(&self, env: Env) -> Result<Option<Reference<JsRepo>>> {
+    match &self.last_removed {
+      Some(repo) => repo.upgrade(env),
+      None => Ok(None),
+    }
+  }
+}
+
+/// The dir of `repo`, which is kept alive until the `Promise` settled
+#[napi]
+pub async fn repo_dir(repo: Reference<JsRepo>) -> String {
+  napi::tokio::task::yield_now().await;
+  repo.inner.dir.clone()
+}
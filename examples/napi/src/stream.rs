@@ -0,0 +1,87 @@
+use futures::stream::{self, Stream};
+use napi::bindgen_prelude::*;
+
+#[napi(readable_stream)]
+pub fn read_in_chunks(data: Buffer, size: u32) -> Result<impl Stream<Item = Result<Buffer>>> {
+  if size == 0 {
+    return Err(Error::new(
+      Status::InvalidArg,
+      "size must be greater than 0".to_owned(),
+    ));
+  }
+  let chunks = data
+    .chunks(size as usize)
+    .map(|chunk| Ok(Buffer::from(chunk)))
+    .collect::<Vec<_>>();
+  Ok(stream::iter(chunks))
+}
+
+#[napi(readable_stream = object_mode)]
+pub fn count_to(n: u32) -> impl Stream<Item = Result<u32>> {
+  stream::iter((1..=n).map(Ok))
+}
+
+#[napi(readable_stream)]
+pub fn fail_after_first_chunk() -> impl Stream<Item = Result<String>> {
+  stream::iter(vec![
+    Ok("first".to_owned()),
+    Err(Error::from_reason("broken stream".to_owned())),
+  ])
+}
+
+/// The data read by `read()` in chunks of `size` bytes
+#[napi]
+pub struct ChunkedData {
+  data: Vec<u8>,
+  size: u32,
+}
+
+#[napi]
+impl ChunkedData {
+  #[napi(constructor)]
+  pub fn new(data: Buffer, size: u32) -> Result<Self> {
+    if size == 0 {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "size must be greater than 0".to_owned(),
+      ));
+    }
+    Ok(ChunkedData {
+      data: data.to_vec(),
+      size,
+    })
+  }
+
+  #[napi(async_iterator)]
+  pub fn read(&self) -> impl Stream<Item = Result<Buffer>> {
+    let chunks = self
+      .data
+      .chunks(self.size as usize)
+      .map(|chunk| Ok(Buffer::from(chunk)))
+      .collect::<Vec<_>>();
+    stream::iter(chunks)
+  }
+}
+
+/// Never ends, the numbers are produced only when they are pulled
+#[napi(async_iterator)]
+pub fn count_from(start: u32) -> impl Stream<Item = Result<u32>> {
+  stream::unfold(start, |n| async move { Some((Ok(n), n + 1)) })
+}
+
+#[napi(async_iterator)]
+pub fn fail_after_first_item() -> impl Stream<Item = Result<String>> {
+  stream::iter(vec![
+    Ok("first".to_owned()),
+    Err(Error::from_reason("broken stream".to_owned())),
+  ])
+}
+
+#[napi]
+pub fn iterate_words(text: String) -> AsyncIterator<String> {
+  let words = text
+    .split_whitespace()
+    .map(|word| Ok(word.to_owned()))
+    .collect::<Vec<_>>();
+  AsyncIterator::new(stream::iter(words))
+}
@@ -29,3 +29,38 @@ fn without_abort_controller(a: u32, b: u32) -> AsyncTask<DelaySum> {
 fn with_abort_controller(a: u32, b: u32, signal: AbortSignal) -> AsyncTask<DelaySum> {
   AsyncTask::with_signal(DelaySum(a, b), signal)
 }
+
+#[napi(also_async = "divideAsync")]
+fn divide(a: u32, b: u32) -> Result<u32> {
+  if b == 0 {
+    return Err(Error::new(
+      Status::InvalidArg,
+      "Division by zero".to_owned(),
+    ));
+  }
+  Ok(a / b)
+}
+
+#[napi(execution = "blocking_pool")]
+fn sum_in_blocking_pool(a: u32, b: u32) -> u32 {
+  a + b
+}
+
+#[napi(ts_return_type = "Promise<number>")]
+fn sum_in_chunks(env: Env, n: u32) -> Result<napi::JsObject> {
+  let mut i = 0;
+  let mut sum = 0f64;
+  env.run_chunked(std::time::Duration::from_millis(1), move |_env| {
+    // one step is small enough, the budget decides how many steps one chunk runs
+    let end = n.min(i + 1000);
+    while i < end {
+      i += 1;
+      sum += i as f64;
+    }
+    Ok(if i == n {
+      napi::Chunk::Done(sum)
+    } else {
+      napi::Chunk::Continue
+    })
+  })
+}
@@ -0,0 +1,38 @@
+use napi::bindgen_prelude::*;
+use napi::tokio::task;
+
+/// Resolves the module specifiers, implemented by the JavaScript objects
+#[napi]
+pub trait Resolver: Send + Sync {
+  /// The path of `specifier`, relative to `from_dir` if it's passed
+  fn resolve(&self, specifier: String, from_dir: Option<String>) -> Result<String>;
+
+  fn on_resolved(&self, path: String) -> Result<()>;
+
+  /// Only in Rust, the JavaScript objects don't implement it
+  fn resolve_all(&self, specifiers: Vec<String>) -> Result<Vec<String>> {
+    specifiers
+      .into_iter()
+      .map(|specifier| self.resolve(specifier, None))
+      .collect()
+  }
+}
+
+#[napi]
+fn resolve_specifiers(resolver: Box<dyn Resolver>, specifiers: Vec<String>) -> Result<Vec<String>> {
+  let resolved = resolver.resolve_all(specifiers)?;
+  for path in resolved.iter() {
+    resolver.on_resolved(path.clone())?;
+  }
+  Ok(resolved)
+}
+
+#[napi]
+async fn resolve_specifier_off_thread(
+  resolver: Box<dyn Resolver>,
+  specifier: String,
+) -> Result<String> {
+  task::spawn_blocking(move || resolver.resolve(specifier, Some("/".to_owned())))
+    .await
+    .map_err(|err| Error::from_reason(err.to_string()))?
+}
@@ -0,0 +1,52 @@
+/// `[start, end)` of the source text
+#[napi(object)]
+pub struct Span(pub u32, pub u32);
+
+#[napi(object)]
+pub struct Token {
+  pub text: String,
+  pub span: Span,
+  /// `[line, column]` of the start
+  pub position: (u32, u32),
+}
+
+/// The words separated by the spaces
+#[napi]
+fn tokenize(source: String) -> Vec<Token> {
+  let mut tokens = vec![];
+  let mut start = 0;
+  for (line, text) in source.split('\n').enumerate() {
+    let mut column = 0;
+    for word in text.split(' ') {
+      if !word.is_empty() {
+        tokens.push(Token {
+          text: word.to_owned(),
+          span: Span(start as u32, (start + word.len()) as u32),
+          position: (line as u32 + 1, column as u32 + 1),
+        });
+      }
+      start += word.len() + 1;
+      column += word.len() + 1;
+    }
+  }
+  tokens
+}
+
+#[napi]
+fn span_len(span: Span) -> u32 {
+  span.1 - span.0
+}
+
+/// `[min, max]` of the numbers, `null` if there is none
+#[napi]
+fn min_max(nums: Vec<f64>) -> Option<(f64, f64)> {
+  nums.iter().fold(None, |range, &n| match range {
+    None => Some((n, n)),
+    Some((min, max)) => Some((n.min(min), n.max(max))),
+  })
+}
+
+#[napi]
+fn swap_pair(pair: (u32, String)) -> (String, u32) {
+  (pair.1, pair.0)
+}